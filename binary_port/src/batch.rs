@@ -0,0 +1,245 @@
+use core::convert::TryFrom;
+
+#[cfg(test)]
+use casper_types::testing::TestRng;
+use casper_types::{
+    bytesrepr::{self, Bytes, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    Digest, GlobalStateIdentifier,
+};
+#[cfg(test)]
+use rand::Rng;
+
+use crate::{binary_response::BinaryResponse, get_request::GetRequest};
+
+/// A request to get many items from the node in a single round-trip.
+///
+/// All the requests of the batch are evaluated against the same state root hash, resolved once
+/// from the state identifier of the batch.  The individual requests must not carry a state
+/// identifier of their own.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchRequest {
+    /// Global state identifier, `None` means "latest block state".
+    state_identifier: Option<GlobalStateIdentifier>,
+    /// The requests of the batch.
+    requests: Vec<GetRequest>,
+}
+
+impl BatchRequest {
+    /// Creates a new batch request.
+    pub fn new(state_identifier: Option<GlobalStateIdentifier>, requests: Vec<GetRequest>) -> Self {
+        BatchRequest {
+            state_identifier,
+            requests,
+        }
+    }
+
+    /// Returns the global state identifier of the batch.
+    pub fn state_identifier(&self) -> Option<GlobalStateIdentifier> {
+        self.state_identifier
+    }
+
+    /// Returns the requests of the batch.
+    pub fn requests(&self) -> &[GetRequest] {
+        &self.requests
+    }
+
+    /// Returns the number of requests in the batch.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Returns `true` if the batch contains no requests.
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Consumes the batch, returning its state identifier and requests.
+    pub fn destructure(self) -> (Option<GlobalStateIdentifier>, Vec<GetRequest>) {
+        (self.state_identifier, self.requests)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn random(rng: &mut TestRng) -> Self {
+        let state_identifier = rng
+            .gen::<bool>()
+            .then(|| GlobalStateIdentifier::random(rng));
+        let requests = (0..rng.gen_range(0..8))
+            .map(|_| GetRequest::random(rng))
+            .collect();
+        Self {
+            state_identifier,
+            requests,
+        }
+    }
+}
+
+impl ToBytes for BatchRequest {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.state_identifier.write_bytes(writer)?;
+        // Each request is length-prefixed, as some requests (e.g. the ones querying items by key
+        // prefix) are only delimited by the end of the input.
+        let count =
+            u32::try_from(self.requests.len()).map_err(|_| bytesrepr::Error::OutOfMemory)?;
+        count.write_bytes(writer)?;
+        for request in &self.requests {
+            Bytes::from(request.to_bytes()?).write_bytes(writer)?;
+        }
+        Ok(())
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.state_identifier.serialized_length()
+            + U32_SERIALIZED_LENGTH
+            + self
+                .requests
+                .iter()
+                .map(|request| U32_SERIALIZED_LENGTH + request.serialized_length())
+                .sum::<usize>()
+    }
+}
+
+impl FromBytes for BatchRequest {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (state_identifier, remainder) = FromBytes::from_bytes(bytes)?;
+        let (serialized_requests, remainder) = Vec::<Bytes>::from_bytes(remainder)?;
+        let requests = serialized_requests
+            .iter()
+            .map(bytesrepr::deserialize_from_slice)
+            .collect::<Result<_, _>>()?;
+        Ok((
+            BatchRequest {
+                state_identifier,
+                requests,
+            },
+            remainder,
+        ))
+    }
+}
+
+/// The response to a batch request.
+///
+/// Holds one response per request of the batch, in the order of the requests, each with its own
+/// error code.
+#[derive(Debug, PartialEq)]
+pub struct BatchResponse {
+    /// The state root hash all the requests of the batch were evaluated against.
+    state_root_hash: Digest,
+    /// The responses to the requests of the batch.
+    responses: Vec<BinaryResponse>,
+}
+
+impl BatchResponse {
+    /// Creates a new batch response.
+    pub fn new(state_root_hash: Digest, responses: Vec<BinaryResponse>) -> Self {
+        BatchResponse {
+            state_root_hash,
+            responses,
+        }
+    }
+
+    /// Returns the state root hash the batch was evaluated against.
+    pub fn state_root_hash(&self) -> &Digest {
+        &self.state_root_hash
+    }
+
+    /// Returns the responses to the requests of the batch.
+    pub fn responses(&self) -> &[BinaryResponse] {
+        &self.responses
+    }
+
+    #[cfg(test)]
+    pub(crate) fn random(rng: &mut TestRng) -> Self {
+        let responses = (0..rng.gen_range(0..8))
+            .map(|_| BinaryResponse::random(rng))
+            .collect();
+        Self {
+            state_root_hash: Digest::random(rng),
+            responses,
+        }
+    }
+}
+
+impl ToBytes for BatchResponse {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.state_root_hash.write_bytes(writer)?;
+        self.responses.write_bytes(writer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.state_root_hash.serialized_length() + self.responses.serialized_length()
+    }
+}
+
+impl FromBytes for BatchResponse {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (state_root_hash, remainder) = FromBytes::from_bytes(bytes)?;
+        let (responses, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            BatchResponse {
+                state_root_hash,
+                responses,
+            },
+            remainder,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::{testing::TestRng, EntityAddr};
+
+    use crate::{GlobalStateEntityQualifier, GlobalStateRequest, KeyPrefix};
+
+    #[test]
+    fn batch_request_bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+
+        let val = BatchRequest::random(rng);
+        bytesrepr::test_serialization_roundtrip(&val);
+    }
+
+    #[test]
+    fn batch_request_with_key_prefix_request_bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+
+        // A key prefix is only delimited by the end of its input, so it must not swallow the
+        // requests following it.
+        let key_prefix_request = GetRequest::State(Box::new(GlobalStateRequest::new(
+            None,
+            GlobalStateEntityQualifier::ItemsByPrefix {
+                key_prefix: KeyPrefix::NamedKeysByEntity(EntityAddr::new_account(rng.gen())),
+            },
+        )));
+        let val = BatchRequest::new(
+            None,
+            vec![
+                key_prefix_request,
+                GetRequest::Trie {
+                    trie_key: Digest::random(rng),
+                },
+            ],
+        );
+        bytesrepr::test_serialization_roundtrip(&val);
+    }
+
+    #[test]
+    fn batch_response_bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+
+        let val = BatchResponse::random(rng);
+        bytesrepr::test_serialization_roundtrip(&val);
+    }
+}
@@ -2,10 +2,13 @@ use core::convert::TryFrom;
 
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
-    Transaction,
+    GlobalStateIdentifier, Transaction,
 };
 
-use crate::get_request::GetRequest;
+use crate::{
+    batch::BatchRequest, get_request::GetRequest, state_override::StateOverride,
+    subscription::SubscriptionRequest,
+};
 
 #[cfg(test)]
 use casper_types::testing::TestRng;
@@ -115,6 +118,27 @@ pub enum Command {
     TrySpeculativeExec {
         /// Transaction to execute.
         transaction: Transaction,
+        /// Global state identifier to execute the transaction against, `None` means "latest block
+        /// state".
+        state_identifier: Option<GlobalStateIdentifier>,
+        /// Changes to the global state applied before executing the transaction.
+        state_overrides: Vec<StateOverride>,
+        /// Whether to record the execution trace of the transaction.
+        trace_execution: bool,
+    },
+    /// Request to stream notifications over the connection.
+    Subscribe(SubscriptionRequest),
+    /// Request to get many items from the node at once.
+    Batch(BatchRequest),
+    /// Request to estimate the gas consumed by a transaction and the payment it needs.
+    EstimateGas {
+        /// Transaction to estimate the gas of.
+        transaction: Transaction,
+        /// Global state identifier to execute the transaction against, `None` means "latest block
+        /// state".
+        state_identifier: Option<GlobalStateIdentifier>,
+        /// Changes to the global state applied before executing the transaction.
+        state_overrides: Vec<StateOverride>,
     },
 }
 
@@ -125,6 +149,9 @@ impl Command {
             Command::Get(_) => CommandTag::Get,
             Command::TryAcceptTransaction { .. } => CommandTag::TryAcceptTransaction,
             Command::TrySpeculativeExec { .. } => CommandTag::TrySpeculativeExec,
+            Command::Subscribe(_) => CommandTag::Subscribe,
+            Command::Batch(_) => CommandTag::Batch,
+            Command::EstimateGas { .. } => CommandTag::EstimateGas,
         }
     }
 
@@ -137,6 +164,24 @@ impl Command {
             },
             CommandTag::TrySpeculativeExec => Self::TrySpeculativeExec {
                 transaction: Transaction::random(rng),
+                state_identifier: rng
+                    .gen::<bool>()
+                    .then(|| GlobalStateIdentifier::random(rng)),
+                state_overrides: (0..rng.gen_range(0..4))
+                    .map(|_| StateOverride::random(rng))
+                    .collect(),
+                trace_execution: rng.gen(),
+            },
+            CommandTag::Subscribe => Self::Subscribe(SubscriptionRequest::random(rng)),
+            CommandTag::Batch => Self::Batch(BatchRequest::random(rng)),
+            CommandTag::EstimateGas => Self::EstimateGas {
+                transaction: Transaction::random(rng),
+                state_identifier: rng
+                    .gen::<bool>()
+                    .then(|| GlobalStateIdentifier::random(rng)),
+                state_overrides: (0..rng.gen_range(0..4))
+                    .map(|_| StateOverride::random(rng))
+                    .collect(),
             },
         }
     }
@@ -153,7 +198,28 @@ impl ToBytes for Command {
         match self {
             Command::Get(inner) => inner.write_bytes(writer),
             Command::TryAcceptTransaction { transaction } => transaction.write_bytes(writer),
-            Command::TrySpeculativeExec { transaction } => transaction.write_bytes(writer),
+            Command::TrySpeculativeExec {
+                transaction,
+                state_identifier,
+                state_overrides,
+                trace_execution,
+            } => {
+                transaction.write_bytes(writer)?;
+                state_identifier.write_bytes(writer)?;
+                state_overrides.write_bytes(writer)?;
+                trace_execution.write_bytes(writer)
+            }
+            Command::EstimateGas {
+                transaction,
+                state_identifier,
+                state_overrides,
+            } => {
+                transaction.write_bytes(writer)?;
+                state_identifier.write_bytes(writer)?;
+                state_overrides.write_bytes(writer)
+            }
+            Command::Subscribe(inner) => inner.write_bytes(writer),
+            Command::Batch(inner) => inner.write_bytes(writer),
         }
     }
 
@@ -161,7 +227,28 @@ impl ToBytes for Command {
         match self {
             Command::Get(inner) => inner.serialized_length(),
             Command::TryAcceptTransaction { transaction } => transaction.serialized_length(),
-            Command::TrySpeculativeExec { transaction } => transaction.serialized_length(),
+            Command::TrySpeculativeExec {
+                transaction,
+                state_identifier,
+                state_overrides,
+                trace_execution,
+            } => {
+                transaction.serialized_length()
+                    + state_identifier.serialized_length()
+                    + state_overrides.serialized_length()
+                    + trace_execution.serialized_length()
+            }
+            Command::EstimateGas {
+                transaction,
+                state_identifier,
+                state_overrides,
+            } => {
+                transaction.serialized_length()
+                    + state_identifier.serialized_length()
+                    + state_overrides.serialized_length()
+            }
+            Command::Subscribe(inner) => inner.serialized_length(),
+            Command::Batch(inner) => inner.serialized_length(),
         }
     }
 }
@@ -181,7 +268,39 @@ impl TryFrom<(CommandTag, &[u8])> for Command {
             }
             CommandTag::TrySpeculativeExec => {
                 let (transaction, remainder) = FromBytes::from_bytes(bytes)?;
-                (Command::TrySpeculativeExec { transaction }, remainder)
+                let (state_identifier, remainder) = FromBytes::from_bytes(remainder)?;
+                let (state_overrides, remainder) = FromBytes::from_bytes(remainder)?;
+                let (trace_execution, remainder) = FromBytes::from_bytes(remainder)?;
+                (
+                    Command::TrySpeculativeExec {
+                        transaction,
+                        state_identifier,
+                        state_overrides,
+                        trace_execution,
+                    },
+                    remainder,
+                )
+            }
+            CommandTag::Subscribe => {
+                let (subscription_request, remainder) = FromBytes::from_bytes(bytes)?;
+                (Command::Subscribe(subscription_request), remainder)
+            }
+            CommandTag::Batch => {
+                let (batch_request, remainder) = FromBytes::from_bytes(bytes)?;
+                (Command::Batch(batch_request), remainder)
+            }
+            CommandTag::EstimateGas => {
+                let (transaction, remainder) = FromBytes::from_bytes(bytes)?;
+                let (state_identifier, remainder) = FromBytes::from_bytes(remainder)?;
+                let (state_overrides, remainder) = FromBytes::from_bytes(remainder)?;
+                (
+                    Command::EstimateGas {
+                        transaction,
+                        state_identifier,
+                        state_overrides,
+                    },
+                    remainder,
+                )
             }
         };
         if !remainder.is_empty() {
@@ -201,16 +320,25 @@ pub enum CommandTag {
     TryAcceptTransaction = 1,
     /// Request to execute a transaction speculatively.
     TrySpeculativeExec = 2,
+    /// Request to stream notifications over the connection.
+    Subscribe = 3,
+    /// Request to get many items from the node at once.
+    Batch = 4,
+    /// Request to estimate the gas consumed by a transaction and the payment it needs.
+    EstimateGas = 5,
 }
 
 impl CommandTag {
     /// Creates a random `CommandTag`.
     #[cfg(test)]
     pub fn random(rng: &mut TestRng) -> Self {
-        match rng.gen_range(0..3) {
+        match rng.gen_range(0..6) {
             0 => CommandTag::Get,
             1 => CommandTag::TryAcceptTransaction,
             2 => CommandTag::TrySpeculativeExec,
+            3 => CommandTag::Subscribe,
+            4 => CommandTag::Batch,
+            5 => CommandTag::EstimateGas,
             _ => unreachable!(),
         }
     }
@@ -224,6 +352,9 @@ impl TryFrom<u8> for CommandTag {
             0 => Ok(CommandTag::Get),
             1 => Ok(CommandTag::TryAcceptTransaction),
             2 => Ok(CommandTag::TrySpeculativeExec),
+            3 => Ok(CommandTag::Subscribe),
+            4 => Ok(CommandTag::Batch),
+            5 => Ok(CommandTag::EstimateGas),
             _ => Err(InvalidCommandTag),
         }
     }
@@ -14,6 +14,7 @@ const ALL_ITEMS_TAG: u8 = 1;
 const DICTIONARY_ITEM_TAG: u8 = 2;
 const BALANCE_TAG: u8 = 3;
 const ITEMS_BY_PREFIX_TAG: u8 = 4;
+const ITEMS_WITH_MULTI_PROOF_TAG: u8 = 5;
 
 /// A request to get data from the global state.
 #[derive(Clone, Debug, PartialEq)]
@@ -44,12 +45,17 @@ pub enum GlobalStateEntityQualifier {
         /// Key prefix to search for.
         key_prefix: KeyPrefix,
     },
+    /// Get the items under the given keys, along with a single proof of all those found.
+    ItemsWithMultiProof {
+        /// Keys under which data is stored.
+        keys: Vec<Key>,
+    },
 }
 
 impl GlobalStateEntityQualifier {
     #[cfg(test)]
     pub(crate) fn random(rng: &mut TestRng) -> Self {
-        let gen_range = TestRng::gen_range(rng, 0..5);
+        let gen_range = TestRng::gen_range(rng, 0..6);
         random_for_variant(gen_range, rng)
     }
 }
@@ -78,6 +84,14 @@ fn random_for_variant(gen_range: u8, rng: &mut TestRng) -> GlobalStateEntityQual
         ITEMS_BY_PREFIX_TAG => GlobalStateEntityQualifier::ItemsByPrefix {
             key_prefix: KeyPrefix::random(rng),
         },
+        ITEMS_WITH_MULTI_PROOF_TAG => {
+            let key_count = rng.gen_range(1..10);
+            GlobalStateEntityQualifier::ItemsWithMultiProof {
+                keys: std::iter::repeat_with(|| rng.gen())
+                    .take(key_count)
+                    .collect(),
+            }
+        }
         _ => unreachable!(),
     }
 }
@@ -112,6 +126,10 @@ impl ToBytes for GlobalStateEntityQualifier {
                 ITEMS_BY_PREFIX_TAG.write_bytes(writer)?;
                 key_prefix.write_bytes(writer)
             }
+            GlobalStateEntityQualifier::ItemsWithMultiProof { keys } => {
+                ITEMS_WITH_MULTI_PROOF_TAG.write_bytes(writer)?;
+                keys.write_bytes(writer)
+            }
         }
     }
 
@@ -131,6 +149,9 @@ impl ToBytes for GlobalStateEntityQualifier {
                 GlobalStateEntityQualifier::ItemsByPrefix { key_prefix } => {
                     key_prefix.serialized_length()
                 }
+                GlobalStateEntityQualifier::ItemsWithMultiProof { keys } => {
+                    keys.serialized_length()
+                }
             }
     }
 }
@@ -172,6 +193,13 @@ impl FromBytes for GlobalStateEntityQualifier {
                     remainder,
                 ))
             }
+            ITEMS_WITH_MULTI_PROOF_TAG => {
+                let (keys, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((
+                    GlobalStateEntityQualifier::ItemsWithMultiProof { keys },
+                    remainder,
+                ))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -185,7 +213,7 @@ mod tests {
     #[test]
     fn bytesrepr_roundtrip() {
         let rng = &mut TestRng::new();
-        for i in 0..5 {
+        for i in 0..6 {
             let qualifier = random_for_variant(i, rng);
             bytesrepr::test_serialization_roundtrip(&qualifier);
         }
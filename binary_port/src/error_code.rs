@@ -334,6 +334,12 @@ pub enum ErrorCode {
     /// Invalid runtime for Transaction::Deploy
     #[error("Invalid runtime for Transaction::Deploy")]
     InvalidDeployInvalidRuntime = 105,
+    /// The subscriber fell too far behind the stream of notifications.
+    #[error("the subscriber fell too far behind the stream of notifications")]
+    SubscriptionLagged = 106,
+    /// A state override of a speculative execution couldn't be applied.
+    #[error("a state override couldn't be applied")]
+    InvalidStateOverride = 107,
 }
 
 impl TryFrom<u16> for ErrorCode {
@@ -447,6 +453,8 @@ impl TryFrom<u16> for ErrorCode {
             103 => Ok(ErrorCode::PricingModeNotSupported),
             104 => Ok(ErrorCode::InvalidDeployGasLimitNotSupported),
             105 => Ok(ErrorCode::InvalidDeployInvalidRuntime),
+            106 => Ok(ErrorCode::SubscriptionLagged),
+            107 => Ok(ErrorCode::InvalidStateOverride),
             _ => Err(UnknownErrorCode),
         }
     }
@@ -0,0 +1,273 @@
+#[cfg(any(feature = "testing", test))]
+use rand::{
+    distributions::{Alphanumeric, DistString},
+    Rng,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(feature = "testing", test))]
+use casper_types::testing::TestRng;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    BlockHash, Gas, Motes,
+};
+
+const PAYMENT_LIMITED_TAG: u8 = 0;
+const FIXED_TAG: u8 = 1;
+
+/// The payment recommended for a transaction, depending on its pricing mode.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum PaymentRecommendation {
+    /// The transaction pays for a limited amount of gas.
+    PaymentLimited {
+        /// The recommended payment amount, in units of gas.
+        payment_amount: u64,
+    },
+    /// The transaction is charged the fixed amount of gas set for its lane in the cost table.
+    Fixed {
+        /// The gas limit set for the lane of the transaction.
+        gas_limit: Gas,
+        /// The gas price the cost was computed with.
+        gas_price: u8,
+        /// The cost of the transaction.
+        cost: Motes,
+    },
+}
+
+impl PaymentRecommendation {
+    #[cfg(any(feature = "testing", test))]
+    pub fn random(rng: &mut TestRng) -> Self {
+        match rng.gen_range(0..2) {
+            PAYMENT_LIMITED_TAG => PaymentRecommendation::PaymentLimited {
+                payment_amount: rng.gen(),
+            },
+            FIXED_TAG => PaymentRecommendation::Fixed {
+                gas_limit: Gas::random(rng),
+                gas_price: rng.gen(),
+                cost: Motes::new(rng.gen::<u64>()),
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl ToBytes for PaymentRecommendation {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        match self {
+            PaymentRecommendation::PaymentLimited { payment_amount } => {
+                PAYMENT_LIMITED_TAG.write_bytes(writer)?;
+                payment_amount.write_bytes(writer)
+            }
+            PaymentRecommendation::Fixed {
+                gas_limit,
+                gas_price,
+                cost,
+            } => {
+                FIXED_TAG.write_bytes(writer)?;
+                gas_limit.write_bytes(writer)?;
+                gas_price.write_bytes(writer)?;
+                cost.write_bytes(writer)
+            }
+        }
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                PaymentRecommendation::PaymentLimited { payment_amount } => {
+                    payment_amount.serialized_length()
+                }
+                PaymentRecommendation::Fixed {
+                    gas_limit,
+                    gas_price,
+                    cost,
+                } => {
+                    gas_limit.serialized_length()
+                        + gas_price.serialized_length()
+                        + cost.serialized_length()
+                }
+            }
+    }
+}
+
+impl FromBytes for PaymentRecommendation {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            PAYMENT_LIMITED_TAG => {
+                let (payment_amount, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((
+                    PaymentRecommendation::PaymentLimited { payment_amount },
+                    remainder,
+                ))
+            }
+            FIXED_TAG => {
+                let (gas_limit, remainder) = FromBytes::from_bytes(remainder)?;
+                let (gas_price, remainder) = FromBytes::from_bytes(remainder)?;
+                let (cost, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((
+                    PaymentRecommendation::Fixed {
+                        gas_limit,
+                        gas_price,
+                        cost,
+                    },
+                    remainder,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// The gas consumed by a speculatively executed transaction and the payment recommended for it.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct GasEstimate {
+    /// Block hash against which the execution was performed.
+    block_hash: BlockHash,
+    /// The lane the transaction belongs to.
+    lane_id: u8,
+    /// Gas limit the transaction was executed with.
+    limit: Gas,
+    /// Gas consumed.
+    consumed: Gas,
+    /// The error the execution failed with, if any.
+    error: Option<String>,
+    /// The recommended payment.
+    recommendation: PaymentRecommendation,
+}
+
+impl GasEstimate {
+    /// Creates a new gas estimate.
+    pub fn new(
+        block_hash: BlockHash,
+        lane_id: u8,
+        limit: Gas,
+        consumed: Gas,
+        error: Option<String>,
+        recommendation: PaymentRecommendation,
+    ) -> Self {
+        GasEstimate {
+            block_hash,
+            lane_id,
+            limit,
+            consumed,
+            error,
+            recommendation,
+        }
+    }
+
+    /// Returns the hash of the block against which the execution was performed.
+    pub fn block_hash(&self) -> &BlockHash {
+        &self.block_hash
+    }
+
+    /// Returns the lane the transaction belongs to.
+    pub fn lane_id(&self) -> u8 {
+        self.lane_id
+    }
+
+    /// Returns the gas limit the transaction was executed with.
+    pub fn limit(&self) -> Gas {
+        self.limit
+    }
+
+    /// Returns the gas consumed.
+    pub fn consumed(&self) -> Gas {
+        self.consumed
+    }
+
+    /// Returns the error the execution failed with, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Returns the recommended payment.
+    pub fn recommendation(&self) -> &PaymentRecommendation {
+        &self.recommendation
+    }
+
+    #[cfg(any(feature = "testing", test))]
+    pub fn random(rng: &mut TestRng) -> Self {
+        GasEstimate {
+            block_hash: BlockHash::new(rng.gen()),
+            lane_id: rng.gen(),
+            limit: Gas::random(rng),
+            consumed: Gas::random(rng),
+            error: if rng.gen() {
+                None
+            } else {
+                let count = rng.gen_range(16..128);
+                Some(Alphanumeric.sample_string(rng, count))
+            },
+            recommendation: PaymentRecommendation::random(rng),
+        }
+    }
+}
+
+impl ToBytes for GasEstimate {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.block_hash.write_bytes(writer)?;
+        self.lane_id.write_bytes(writer)?;
+        self.limit.write_bytes(writer)?;
+        self.consumed.write_bytes(writer)?;
+        self.error.write_bytes(writer)?;
+        self.recommendation.write_bytes(writer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.block_hash.serialized_length()
+            + self.lane_id.serialized_length()
+            + self.limit.serialized_length()
+            + self.consumed.serialized_length()
+            + self.error.serialized_length()
+            + self.recommendation.serialized_length()
+    }
+}
+
+impl FromBytes for GasEstimate {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (block_hash, remainder) = FromBytes::from_bytes(bytes)?;
+        let (lane_id, remainder) = FromBytes::from_bytes(remainder)?;
+        let (limit, remainder) = FromBytes::from_bytes(remainder)?;
+        let (consumed, remainder) = FromBytes::from_bytes(remainder)?;
+        let (error, remainder) = FromBytes::from_bytes(remainder)?;
+        let (recommendation, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            GasEstimate {
+                block_hash,
+                lane_id,
+                limit,
+                consumed,
+                error,
+                recommendation,
+            },
+            remainder,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::testing::TestRng;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+
+        let val = GasEstimate::random(rng);
+        bytesrepr::test_serialization_roundtrip(&val);
+    }
+}
@@ -3,7 +3,7 @@ use casper_types::{
     Digest,
 };
 
-use crate::state_request::GlobalStateRequest;
+use crate::{state_diff::StateDiffRequest, state_request::GlobalStateRequest};
 
 #[cfg(test)]
 use casper_types::testing::TestRng;
@@ -14,6 +14,7 @@ const RECORD_TAG: u8 = 0;
 const INFORMATION_TAG: u8 = 1;
 const STATE_TAG: u8 = 2;
 const TRIE_TAG: u8 = 3;
+const STATE_DIFF_TAG: u8 = 4;
 
 /// A request to get data from the node.
 #[derive(Clone, Debug, PartialEq)]
@@ -39,12 +40,14 @@ pub enum GetRequest {
         /// A trie key.
         trie_key: Digest,
     },
+    /// Retrieves the differences between two global states.
+    StateDiff(Box<StateDiffRequest>),
 }
 
 impl GetRequest {
     #[cfg(test)]
     pub(crate) fn random(rng: &mut TestRng) -> Self {
-        match rng.gen_range(0..4) {
+        match rng.gen_range(0..5) {
             0 => GetRequest::Record {
                 record_type_tag: rng.gen(),
                 key: rng.random_vec(16..32),
@@ -57,6 +60,7 @@ impl GetRequest {
             3 => GetRequest::Trie {
                 trie_key: Digest::random(rng),
             },
+            4 => GetRequest::StateDiff(Box::new(StateDiffRequest::random(rng))),
             _ => unreachable!(),
         }
     }
@@ -92,6 +96,10 @@ impl ToBytes for GetRequest {
                 TRIE_TAG.write_bytes(writer)?;
                 trie_key.write_bytes(writer)
             }
+            GetRequest::StateDiff(req) => {
+                STATE_DIFF_TAG.write_bytes(writer)?;
+                req.write_bytes(writer)
+            }
         }
     }
 
@@ -107,6 +115,7 @@ impl ToBytes for GetRequest {
                 }
                 GetRequest::State(req) => req.serialized_length(),
                 GetRequest::Trie { trie_key } => trie_key.serialized_length(),
+                GetRequest::StateDiff(req) => req.serialized_length(),
             }
     }
 }
@@ -145,6 +154,10 @@ impl FromBytes for GetRequest {
                 let (trie_key, remainder) = FromBytes::from_bytes(remainder)?;
                 Ok((GetRequest::Trie { trie_key }, remainder))
             }
+            STATE_DIFF_TAG => {
+                let (req, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((GetRequest::StateDiff(Box::new(req)), remainder))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -11,7 +11,8 @@ use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     contracts::{ContractHash, ContractPackageHash},
     system::auction::DelegatorKind,
-    BlockIdentifier, EntityAddr, GlobalStateIdentifier, PackageAddr, PublicKey, TransactionHash,
+    BlockIdentifier, EntityAddr, GlobalStateIdentifier, InitiatorAddr, PackageAddr, PublicKey,
+    TransactionHash,
 };
 
 /// Request for information from the node.
@@ -84,6 +85,23 @@ pub enum InformationRequest {
         /// Whether to return the bytecode with the entity.
         include_bytecode: bool,
     },
+    /// Returns the transactions initiated by an account in blocks within a range of heights,
+    /// ordered by block height and transaction hash.
+    ///
+    /// Requires the initiator transactions index to be enabled on the node.
+    TransactionsByInitiator {
+        /// Initiator of the transactions.
+        initiator_addr: InitiatorAddr,
+        /// Height of the lowest block to include.
+        from_height: u64,
+        /// Height of the highest block to include.
+        to_height: u64,
+        /// Hash of the last transaction returned by the previous page, if any. When set,
+        /// `from_height` should be the height of the block containing it.
+        start_after: Option<TransactionHash>,
+        /// Maximum number of transactions to return.
+        limit: u32,
+    },
 }
 
 impl InformationRequest {
@@ -118,6 +136,9 @@ impl InformationRequest {
             InformationRequest::ProtocolVersion => InformationRequestTag::ProtocolVersion,
             InformationRequest::Package { .. } => InformationRequestTag::Package,
             InformationRequest::Entity { .. } => InformationRequestTag::Entity,
+            InformationRequest::TransactionsByInitiator { .. } => {
+                InformationRequestTag::TransactionsByInitiator
+            }
         }
     }
 
@@ -174,6 +195,15 @@ impl InformationRequest {
                 identifier: EntityIdentifier::random(rng),
                 include_bytecode: rng.gen(),
             },
+            InformationRequestTag::TransactionsByInitiator => {
+                InformationRequest::TransactionsByInitiator {
+                    initiator_addr: InitiatorAddr::random(rng),
+                    from_height: rng.gen(),
+                    to_height: rng.gen(),
+                    start_after: rng.gen::<bool>().then(|| TransactionHash::random(rng)),
+                    limit: rng.gen(),
+                }
+            }
         }
     }
 }
@@ -240,6 +270,19 @@ impl ToBytes for InformationRequest {
                 identifier.write_bytes(writer)?;
                 include_bytecode.write_bytes(writer)
             }
+            InformationRequest::TransactionsByInitiator {
+                initiator_addr,
+                from_height,
+                to_height,
+                start_after,
+                limit,
+            } => {
+                initiator_addr.write_bytes(writer)?;
+                from_height.write_bytes(writer)?;
+                to_height.write_bytes(writer)?;
+                start_after.write_bytes(writer)?;
+                limit.write_bytes(writer)
+            }
         }
     }
 
@@ -291,6 +334,19 @@ impl ToBytes for InformationRequest {
                     + identifier.serialized_length()
                     + include_bytecode.serialized_length()
             }
+            InformationRequest::TransactionsByInitiator {
+                initiator_addr,
+                from_height,
+                to_height,
+                start_after,
+                limit,
+            } => {
+                initiator_addr.serialized_length()
+                    + from_height.serialized_length()
+                    + to_height.serialized_length()
+                    + start_after.serialized_length()
+                    + limit.serialized_length()
+            }
         }
     }
 }
@@ -387,6 +443,23 @@ impl TryFrom<(InformationRequestTag, &[u8])> for InformationRequest {
                     remainder,
                 )
             }
+            InformationRequestTag::TransactionsByInitiator => {
+                let (initiator_addr, remainder) = FromBytes::from_bytes(key_bytes)?;
+                let (from_height, remainder) = FromBytes::from_bytes(remainder)?;
+                let (to_height, remainder) = FromBytes::from_bytes(remainder)?;
+                let (start_after, remainder) = FromBytes::from_bytes(remainder)?;
+                let (limit, remainder) = FromBytes::from_bytes(remainder)?;
+                (
+                    InformationRequest::TransactionsByInitiator {
+                        initiator_addr,
+                        from_height,
+                        to_height,
+                        start_after,
+                        limit,
+                    },
+                    remainder,
+                )
+            }
         };
         if !remainder.is_empty() {
             return Err(bytesrepr::Error::LeftOverBytes);
@@ -450,12 +523,14 @@ pub enum InformationRequestTag {
     Package = 18,
     /// Addressable entity request.
     Entity = 19,
+    /// Transactions by initiator request.
+    TransactionsByInitiator = 20,
 }
 
 impl InformationRequestTag {
     #[cfg(test)]
     pub(crate) fn random(rng: &mut TestRng) -> Self {
-        match rng.gen_range(0..20) {
+        match rng.gen_range(0..21) {
             0 => InformationRequestTag::BlockHeader,
             1 => InformationRequestTag::BlockWithSignatures,
             2 => InformationRequestTag::Transaction,
@@ -476,6 +551,7 @@ impl InformationRequestTag {
             17 => InformationRequestTag::ProtocolVersion,
             18 => InformationRequestTag::Package,
             19 => InformationRequestTag::Entity,
+            20 => InformationRequestTag::TransactionsByInitiator,
             _ => unreachable!(),
         }
     }
@@ -506,6 +582,7 @@ impl TryFrom<u16> for InformationRequestTag {
             17 => Ok(InformationRequestTag::ProtocolVersion),
             18 => Ok(InformationRequestTag::Package),
             19 => Ok(InformationRequestTag::Entity),
+            20 => Ok(InformationRequestTag::TransactionsByInitiator),
             _ => Err(UnknownInformationRequestTag(value)),
         }
     }
@@ -1,6 +1,7 @@
 //! A Rust library for types used by the binary port of a casper node.
 
 mod balance_response;
+mod batch;
 mod binary_message;
 mod binary_response;
 mod binary_response_and_request;
@@ -11,6 +12,7 @@ mod entity_qualifier;
 mod era_identifier;
 mod error;
 mod error_code;
+mod gas_estimate;
 mod get_request;
 mod global_state_query_result;
 mod information_request;
@@ -21,10 +23,14 @@ mod purse_identifier;
 pub mod record_id;
 mod response_type;
 mod speculative_execution_result;
+mod state_diff;
+mod state_override;
 mod state_request;
+mod subscription;
 mod type_wrappers;
 
 pub use balance_response::BalanceResponse;
+pub use batch::{BatchRequest, BatchResponse};
 pub use binary_message::{BinaryMessage, BinaryMessageCodec};
 pub use binary_response::BinaryResponse;
 pub use binary_response_and_request::BinaryResponseAndRequest;
@@ -35,6 +41,7 @@ pub use entity_qualifier::GlobalStateEntityQualifier;
 pub use era_identifier::EraIdentifier;
 pub use error::Error;
 pub use error_code::ErrorCode;
+pub use gas_estimate::{GasEstimate, PaymentRecommendation};
 pub use get_request::GetRequest;
 pub use global_state_query_result::GlobalStateQueryResult;
 pub use information_request::{
@@ -47,9 +54,15 @@ pub use purse_identifier::PurseIdentifier;
 pub use record_id::{RecordId, UnknownRecordId};
 pub use response_type::{PayloadEntity, ResponseType};
 pub use speculative_execution_result::SpeculativeExecutionResult;
+pub use state_diff::{StateDiff, StateDiffEntry, StateDiffRequest};
+pub use state_override::StateOverride;
 pub use state_request::GlobalStateRequest;
+pub use subscription::{
+    Notification, NotificationKind, SubscriptionFilter, SubscriptionRequest, TransactionProcessed,
+};
 pub use type_wrappers::{
     AccountInformation, AddressableEntityInformation, ConsensusStatus, ConsensusValidatorChanges,
-    ContractInformation, DictionaryQueryResult, GetTrieFullResult, LastProgress, NetworkName,
-    ReactorStateName, RewardResponse, TransactionWithExecutionInfo, Uptime, ValueWithProof,
+    ContractInformation, DictionaryQueryResult, GetTrieFullResult, InitiatorTransaction,
+    LastProgress, NetworkName, ReactorStateName, RewardResponse, TransactionWithExecutionInfo,
+    Uptime, ValueWithProof,
 };
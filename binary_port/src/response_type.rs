@@ -10,21 +10,25 @@ use casper_types::testing::TestRng;
 use casper_types::{
     contracts::ContractPackage,
     execution::{ExecutionResult, ExecutionResultV1},
+    global_state::TrieMultiProof,
     AvailableBlockRange, BlockBody, BlockBodyV1, BlockHeader, BlockHeaderV1, BlockSignatures,
     BlockSignaturesV1, BlockSynchronizerStatus, BlockWithSignatures, ChainspecRawBytes, Deploy,
-    NextUpgrade, Package, Peers, ProtocolVersion, StoredValue, Transaction, Transfer,
+    Key, NextUpgrade, Package, Peers, ProtocolVersion, StoredValue, Transaction, Transfer,
 };
 
 use crate::{
+    gas_estimate::GasEstimate,
     global_state_query_result::GlobalStateQueryResult,
     node_status::NodeStatus,
     speculative_execution_result::SpeculativeExecutionResult,
+    state_diff::StateDiff,
     type_wrappers::{
         ConsensusStatus, ConsensusValidatorChanges, GetTrieFullResult, LastProgress, NetworkName,
         ReactorStateName, RewardResponse,
     },
-    AccountInformation, AddressableEntityInformation, BalanceResponse, ContractInformation,
-    DictionaryQueryResult, RecordId, TransactionWithExecutionInfo, Uptime, ValueWithProof,
+    AccountInformation, AddressableEntityInformation, BalanceResponse, BatchResponse,
+    ContractInformation, DictionaryQueryResult, InitiatorTransaction, Notification, RecordId,
+    TransactionWithExecutionInfo, Uptime, ValueWithProof,
 };
 
 /// A type of the payload being returned in a binary response.
@@ -119,6 +123,18 @@ pub enum ResponseType {
     PackageWithProof,
     /// Addressable entity information.
     AddressableEntityInformation,
+    /// Transactions initiated by a given account.
+    InitiatorTransactions,
+    /// Notification pushed to a subscriber.
+    Notification,
+    /// Responses to a batch of requests.
+    BatchResponse,
+    /// Gas estimate of a transaction.
+    GasEstimate,
+    /// Differences between two global states.
+    StateDiff,
+    /// Global state items with a single proof of all of them.
+    GlobalStateMultiProof,
 }
 
 impl ResponseType {
@@ -145,7 +161,7 @@ impl ResponseType {
 
     #[cfg(test)]
     pub(crate) fn random(rng: &mut TestRng) -> Self {
-        Self::try_from(rng.gen_range(0..44)).unwrap()
+        Self::try_from(rng.gen_range(0..50)).unwrap()
     }
 }
 
@@ -228,6 +244,16 @@ impl TryFrom<u8> for ResponseType {
             x if x == ResponseType::AddressableEntityInformation as u8 => {
                 Ok(ResponseType::AddressableEntityInformation)
             }
+            x if x == ResponseType::InitiatorTransactions as u8 => {
+                Ok(ResponseType::InitiatorTransactions)
+            }
+            x if x == ResponseType::Notification as u8 => Ok(ResponseType::Notification),
+            x if x == ResponseType::BatchResponse as u8 => Ok(ResponseType::BatchResponse),
+            x if x == ResponseType::GasEstimate as u8 => Ok(ResponseType::GasEstimate),
+            x if x == ResponseType::StateDiff as u8 => Ok(ResponseType::StateDiff),
+            x if x == ResponseType::GlobalStateMultiProof as u8 => {
+                Ok(ResponseType::GlobalStateMultiProof)
+            }
             _ => Err(()),
         }
     }
@@ -290,6 +316,12 @@ impl fmt::Display for ResponseType {
             ResponseType::AddressableEntityInformation => {
                 write!(f, "AddressableEntityInformation")
             }
+            ResponseType::InitiatorTransactions => write!(f, "InitiatorTransactions"),
+            ResponseType::Notification => write!(f, "Notification"),
+            ResponseType::BatchResponse => write!(f, "BatchResponse"),
+            ResponseType::GasEstimate => write!(f, "GasEstimate"),
+            ResponseType::StateDiff => write!(f, "StateDiff"),
+            ResponseType::GlobalStateMultiProof => write!(f, "GlobalStateMultiProof"),
         }
     }
 }
@@ -452,6 +484,30 @@ impl PayloadEntity for AddressableEntityInformation {
     const RESPONSE_TYPE: ResponseType = ResponseType::AddressableEntityInformation;
 }
 
+impl PayloadEntity for Vec<InitiatorTransaction> {
+    const RESPONSE_TYPE: ResponseType = ResponseType::InitiatorTransactions;
+}
+
+impl PayloadEntity for Notification {
+    const RESPONSE_TYPE: ResponseType = ResponseType::Notification;
+}
+
+impl PayloadEntity for BatchResponse {
+    const RESPONSE_TYPE: ResponseType = ResponseType::BatchResponse;
+}
+
+impl PayloadEntity for GasEstimate {
+    const RESPONSE_TYPE: ResponseType = ResponseType::GasEstimate;
+}
+
+impl PayloadEntity for StateDiff {
+    const RESPONSE_TYPE: ResponseType = ResponseType::StateDiff;
+}
+
+impl PayloadEntity for TrieMultiProof<Key, StoredValue> {
+    const RESPONSE_TYPE: ResponseType = ResponseType::GlobalStateMultiProof;
+}
+
 impl<T> PayloadEntity for Box<T>
 where
     T: PayloadEntity,
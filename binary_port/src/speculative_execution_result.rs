@@ -11,7 +11,7 @@ use casper_types::testing::TestRng;
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     contract_messages::Messages,
-    execution::Effects,
+    execution::{Effects, ExecutionTrace},
     BlockHash, Digest, Gas, InvalidTransaction, Transfer,
 };
 
@@ -43,6 +43,8 @@ pub struct SpeculativeExecutionResult {
     messages: Messages,
     /// Did the wasm execute successfully?
     error: Option<String>,
+    /// Execution trace, if one was requested.
+    execution_trace: Option<ExecutionTrace>,
 }
 
 impl SpeculativeExecutionResult {
@@ -63,9 +65,36 @@ impl SpeculativeExecutionResult {
             messages,
             error,
             block_hash,
+            execution_trace: None,
         }
     }
 
+    /// Sets the execution trace.
+    pub fn with_execution_trace(mut self, execution_trace: Option<ExecutionTrace>) -> Self {
+        self.execution_trace = execution_trace;
+        self
+    }
+
+    /// Returns the gas limit.
+    pub fn limit(&self) -> Gas {
+        self.limit
+    }
+
+    /// Returns the gas consumed.
+    pub fn consumed(&self) -> Gas {
+        self.consumed
+    }
+
+    /// Returns the error the execution failed with, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Returns the execution trace, if one was requested.
+    pub fn execution_trace(&self) -> Option<&ExecutionTrace> {
+        self.execution_trace.as_ref()
+    }
+
     // This method is not intended to be used by third party crates.
     #[doc(hidden)]
     pub fn example() -> &'static Self {
@@ -96,6 +125,7 @@ impl SpeculativeExecutionResult {
                 let count = rng.gen_range(16..128);
                 Some(Alphanumeric.sample_string(rng, count))
             },
+            execution_trace: rng.gen::<bool>().then(|| ExecutionTrace::random(rng)),
         }
     }
 }
@@ -110,6 +140,7 @@ impl From<InvalidTransaction> for SpeculativeExecutionResult {
             messages: Default::default(),
             error: Some(format!("{}", invalid_transaction)),
             block_hash: Default::default(),
+            execution_trace: None,
         }
     }
 }
@@ -129,6 +160,7 @@ impl ToBytes for SpeculativeExecutionResult {
             + ToBytes::serialized_length(&self.messages)
             + ToBytes::serialized_length(&self.error)
             + ToBytes::serialized_length(&self.block_hash)
+            + ToBytes::serialized_length(&self.execution_trace)
     }
 
     fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
@@ -138,7 +170,8 @@ impl ToBytes for SpeculativeExecutionResult {
         self.effects.write_bytes(writer)?;
         self.messages.write_bytes(writer)?;
         self.error.write_bytes(writer)?;
-        self.block_hash.write_bytes(writer)
+        self.block_hash.write_bytes(writer)?;
+        self.execution_trace.write_bytes(writer)
     }
 }
 
@@ -151,6 +184,7 @@ impl FromBytes for SpeculativeExecutionResult {
         let (messages, bytes) = Messages::from_bytes(bytes)?;
         let (error, bytes) = Option::<String>::from_bytes(bytes)?;
         let (block_hash, bytes) = BlockHash::from_bytes(bytes)?;
+        let (execution_trace, bytes) = Option::<ExecutionTrace>::from_bytes(bytes)?;
         Ok((
             SpeculativeExecutionResult {
                 transfers,
@@ -160,6 +194,7 @@ impl FromBytes for SpeculativeExecutionResult {
                 messages,
                 error,
                 block_hash,
+                execution_trace,
             },
             bytes,
         ))
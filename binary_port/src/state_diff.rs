@@ -0,0 +1,338 @@
+#[cfg(any(feature = "testing", test))]
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    Digest, Key, StoredValue,
+};
+#[cfg(any(feature = "testing", test))]
+use casper_types::{testing::TestRng, CLValue};
+
+const ADDED_TAG: u8 = 0;
+const REMOVED_TAG: u8 = 1;
+const MODIFIED_TAG: u8 = 2;
+
+/// A request for the keys added, removed and modified between two global states.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateDiffRequest {
+    /// The state root hash to diff from.
+    from_state_root_hash: Digest,
+    /// The state root hash to diff to.
+    to_state_root_hash: Digest,
+    /// The key to start after, in order to get the next page of differences.
+    start_after: Option<Key>,
+}
+
+impl StateDiffRequest {
+    /// Creates a new request for the first page of differences between two global states.
+    pub fn new(from_state_root_hash: Digest, to_state_root_hash: Digest) -> Self {
+        StateDiffRequest {
+            from_state_root_hash,
+            to_state_root_hash,
+            start_after: None,
+        }
+    }
+
+    /// Sets the key to start after, as returned in the `next_page` of a previous response.
+    pub fn with_start_after(mut self, start_after: Key) -> Self {
+        self.start_after = Some(start_after);
+        self
+    }
+
+    /// Returns the state root hash to diff from.
+    pub fn from_state_root_hash(&self) -> Digest {
+        self.from_state_root_hash
+    }
+
+    /// Returns the state root hash to diff to.
+    pub fn to_state_root_hash(&self) -> Digest {
+        self.to_state_root_hash
+    }
+
+    /// Returns the key to start after, if any.
+    pub fn start_after(&self) -> Option<&Key> {
+        self.start_after.as_ref()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn random(rng: &mut TestRng) -> Self {
+        StateDiffRequest {
+            from_state_root_hash: Digest::random(rng),
+            to_state_root_hash: Digest::random(rng),
+            start_after: rng.gen::<bool>().then(|| rng.gen()),
+        }
+    }
+}
+
+impl ToBytes for StateDiffRequest {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.from_state_root_hash.write_bytes(writer)?;
+        self.to_state_root_hash.write_bytes(writer)?;
+        self.start_after.write_bytes(writer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.from_state_root_hash.serialized_length()
+            + self.to_state_root_hash.serialized_length()
+            + self.start_after.serialized_length()
+    }
+}
+
+impl FromBytes for StateDiffRequest {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (from_state_root_hash, remainder) = FromBytes::from_bytes(bytes)?;
+        let (to_state_root_hash, remainder) = FromBytes::from_bytes(remainder)?;
+        let (start_after, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            StateDiffRequest {
+                from_state_root_hash,
+                to_state_root_hash,
+                start_after,
+            },
+            remainder,
+        ))
+    }
+}
+
+/// A difference between two global states.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum StateDiffEntry {
+    /// The key is only present in the second global state.
+    Added {
+        /// The key.
+        key: Key,
+        /// The value in the second global state.
+        value: StoredValue,
+    },
+    /// The key is only present in the first global state.
+    Removed {
+        /// The key.
+        key: Key,
+        /// The value in the first global state.
+        value: StoredValue,
+    },
+    /// The key is present in both global states, with different values.
+    Modified {
+        /// The key.
+        key: Key,
+        /// The value in the first global state.
+        old_value: StoredValue,
+        /// The value in the second global state.
+        new_value: StoredValue,
+    },
+}
+
+impl StateDiffEntry {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &Key {
+        match self {
+            StateDiffEntry::Added { key, .. }
+            | StateDiffEntry::Removed { key, .. }
+            | StateDiffEntry::Modified { key, .. } => key,
+        }
+    }
+
+    #[cfg(any(feature = "testing", test))]
+    pub fn random(rng: &mut TestRng) -> Self {
+        let value = random_stored_value(rng);
+        match rng.gen_range(0..3) {
+            ADDED_TAG => StateDiffEntry::Added {
+                key: rng.gen(),
+                value,
+            },
+            REMOVED_TAG => StateDiffEntry::Removed {
+                key: rng.gen(),
+                value,
+            },
+            MODIFIED_TAG => StateDiffEntry::Modified {
+                key: rng.gen(),
+                old_value: value,
+                new_value: random_stored_value(rng),
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(any(feature = "testing", test))]
+fn random_stored_value(rng: &mut TestRng) -> StoredValue {
+    StoredValue::CLValue(CLValue::from_t(rng.gen::<u64>()).expect("should create CLValue"))
+}
+
+impl ToBytes for StateDiffEntry {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        match self {
+            StateDiffEntry::Added { key, value } => {
+                ADDED_TAG.write_bytes(writer)?;
+                key.write_bytes(writer)?;
+                value.write_bytes(writer)
+            }
+            StateDiffEntry::Removed { key, value } => {
+                REMOVED_TAG.write_bytes(writer)?;
+                key.write_bytes(writer)?;
+                value.write_bytes(writer)
+            }
+            StateDiffEntry::Modified {
+                key,
+                old_value,
+                new_value,
+            } => {
+                MODIFIED_TAG.write_bytes(writer)?;
+                key.write_bytes(writer)?;
+                old_value.write_bytes(writer)?;
+                new_value.write_bytes(writer)
+            }
+        }
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                StateDiffEntry::Added { key, value } | StateDiffEntry::Removed { key, value } => {
+                    key.serialized_length() + value.serialized_length()
+                }
+                StateDiffEntry::Modified {
+                    key,
+                    old_value,
+                    new_value,
+                } => {
+                    key.serialized_length()
+                        + old_value.serialized_length()
+                        + new_value.serialized_length()
+                }
+            }
+    }
+}
+
+impl FromBytes for StateDiffEntry {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            ADDED_TAG => {
+                let (key, remainder) = FromBytes::from_bytes(remainder)?;
+                let (value, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((StateDiffEntry::Added { key, value }, remainder))
+            }
+            REMOVED_TAG => {
+                let (key, remainder) = FromBytes::from_bytes(remainder)?;
+                let (value, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((StateDiffEntry::Removed { key, value }, remainder))
+            }
+            MODIFIED_TAG => {
+                let (key, remainder) = FromBytes::from_bytes(remainder)?;
+                let (old_value, remainder) = FromBytes::from_bytes(remainder)?;
+                let (new_value, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((
+                    StateDiffEntry::Modified {
+                        key,
+                        old_value,
+                        new_value,
+                    },
+                    remainder,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// A page of the differences between two global states, ordered by key.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct StateDiff {
+    /// The differences.
+    entries: Vec<StateDiffEntry>,
+    /// The key to start the next page after, if there are more differences.
+    next_page: Option<Key>,
+}
+
+impl StateDiff {
+    /// Creates a new page of differences.
+    pub fn new(entries: Vec<StateDiffEntry>, next_page: Option<Key>) -> Self {
+        StateDiff { entries, next_page }
+    }
+
+    /// Returns the differences.
+    pub fn entries(&self) -> &[StateDiffEntry] {
+        &self.entries
+    }
+
+    /// Returns the key to start the next page after, if there are more differences.
+    pub fn next_page(&self) -> Option<&Key> {
+        self.next_page.as_ref()
+    }
+
+    /// Consumes `self`, returning the differences and the key to start the next page after.
+    pub fn into_inner(self) -> (Vec<StateDiffEntry>, Option<Key>) {
+        (self.entries, self.next_page)
+    }
+
+    #[cfg(any(feature = "testing", test))]
+    pub fn random(rng: &mut TestRng) -> Self {
+        StateDiff {
+            entries: (0..rng.gen_range(0..10))
+                .map(|_| StateDiffEntry::random(rng))
+                .collect(),
+            next_page: rng.gen::<bool>().then(|| rng.gen()),
+        }
+    }
+}
+
+impl ToBytes for StateDiff {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.entries.write_bytes(writer)?;
+        self.next_page.write_bytes(writer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.entries.serialized_length() + self.next_page.serialized_length()
+    }
+}
+
+impl FromBytes for StateDiff {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (entries, remainder) = FromBytes::from_bytes(bytes)?;
+        let (next_page, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((StateDiff { entries, next_page }, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::testing::TestRng;
+
+    #[test]
+    fn request_bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+
+        let val = StateDiffRequest::random(rng);
+        bytesrepr::test_serialization_roundtrip(&val);
+    }
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+
+        let val = StateDiff::random(rng);
+        bytesrepr::test_serialization_roundtrip(&val);
+    }
+}
@@ -0,0 +1,177 @@
+#[cfg(test)]
+use casper_types::testing::TestRng;
+#[cfg(test)]
+use rand::Rng;
+
+use casper_types::{
+    bytesrepr::{self, Bytes, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    EntityAddr, Key, URef, U512,
+};
+use serde::Serialize;
+
+const BALANCE_TAG: u8 = 0;
+const NAMED_KEY_TAG: u8 = 1;
+const BYTE_CODE_TAG: u8 = 2;
+
+/// An ephemeral change to the global state applied before executing a transaction speculatively.
+///
+/// Overrides are only visible to the speculative execution they're sent with and are never
+/// committed.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum StateOverride {
+    /// Sets the balance of a purse.
+    Balance {
+        /// The purse.
+        purse: URef,
+        /// The new balance of the purse.
+        balance: U512,
+    },
+    /// Adds a named key to an entity, replacing the key of the same name if there is one.
+    NamedKey {
+        /// The entity to add the named key to.
+        entity_addr: EntityAddr,
+        /// The name of the key.
+        name: String,
+        /// The key.
+        key: Key,
+    },
+    /// Replaces the byte code of a smart contract entity.
+    ByteCode {
+        /// The smart contract entity.
+        entity_addr: EntityAddr,
+        /// The new byte code.
+        byte_code: Bytes,
+    },
+}
+
+impl StateOverride {
+    #[cfg(test)]
+    pub(crate) fn random(rng: &mut TestRng) -> Self {
+        match rng.gen_range(0..3) {
+            BALANCE_TAG => StateOverride::Balance {
+                purse: rng.gen(),
+                balance: rng.gen(),
+            },
+            NAMED_KEY_TAG => StateOverride::NamedKey {
+                entity_addr: rng.gen(),
+                name: rng.random_string(1..32),
+                key: Key::URef(rng.gen()),
+            },
+            BYTE_CODE_TAG => StateOverride::ByteCode {
+                entity_addr: rng.gen(),
+                byte_code: rng.random_vec(16..64).into(),
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl ToBytes for StateOverride {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        match self {
+            StateOverride::Balance { purse, balance } => {
+                BALANCE_TAG.write_bytes(writer)?;
+                purse.write_bytes(writer)?;
+                balance.write_bytes(writer)
+            }
+            StateOverride::NamedKey {
+                entity_addr,
+                name,
+                key,
+            } => {
+                NAMED_KEY_TAG.write_bytes(writer)?;
+                entity_addr.write_bytes(writer)?;
+                name.write_bytes(writer)?;
+                key.write_bytes(writer)
+            }
+            StateOverride::ByteCode {
+                entity_addr,
+                byte_code,
+            } => {
+                BYTE_CODE_TAG.write_bytes(writer)?;
+                entity_addr.write_bytes(writer)?;
+                byte_code.write_bytes(writer)
+            }
+        }
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                StateOverride::Balance { purse, balance } => {
+                    purse.serialized_length() + balance.serialized_length()
+                }
+                StateOverride::NamedKey {
+                    entity_addr,
+                    name,
+                    key,
+                } => {
+                    entity_addr.serialized_length()
+                        + name.serialized_length()
+                        + key.serialized_length()
+                }
+                StateOverride::ByteCode {
+                    entity_addr,
+                    byte_code,
+                } => entity_addr.serialized_length() + byte_code.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for StateOverride {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            BALANCE_TAG => {
+                let (purse, remainder) = FromBytes::from_bytes(remainder)?;
+                let (balance, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((StateOverride::Balance { purse, balance }, remainder))
+            }
+            NAMED_KEY_TAG => {
+                let (entity_addr, remainder) = FromBytes::from_bytes(remainder)?;
+                let (name, remainder) = FromBytes::from_bytes(remainder)?;
+                let (key, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((
+                    StateOverride::NamedKey {
+                        entity_addr,
+                        name,
+                        key,
+                    },
+                    remainder,
+                ))
+            }
+            BYTE_CODE_TAG => {
+                let (entity_addr, remainder) = FromBytes::from_bytes(remainder)?;
+                let (byte_code, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((
+                    StateOverride::ByteCode {
+                        entity_addr,
+                        byte_code,
+                    },
+                    remainder,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::testing::TestRng;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+
+        let val = StateOverride::random(rng);
+        bytesrepr::test_serialization_roundtrip(&val);
+    }
+}
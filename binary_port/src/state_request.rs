@@ -100,6 +100,9 @@ impl Display for GlobalStateRequest {
             GlobalStateEntityQualifier::ItemsByPrefix { .. } => {
                 write!(f, "get items by prefix")
             }
+            GlobalStateEntityQualifier::ItemsWithMultiProof { .. } => {
+                write!(f, "get items with multi-proof")
+            }
         }
     }
 }
@@ -0,0 +1,521 @@
+use core::convert::TryFrom;
+
+#[cfg(test)]
+use casper_types::{testing::TestRng, TestBlockBuilder};
+#[cfg(test)]
+use rand::Rng;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    contract_messages::Messages,
+    execution::ExecutionResult,
+    Block, BlockHash, FinalitySignature, InitiatorAddr, TransactionHash,
+};
+
+const BLOCK_ADDED_TAG: u8 = 0;
+const TRANSACTION_PROCESSED_TAG: u8 = 1;
+const FINALITY_SIGNATURE_TAG: u8 = 2;
+
+/// The kind of a notification pushed to a subscriber.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum NotificationKind {
+    /// A block was added to the linear chain.
+    BlockAdded = BLOCK_ADDED_TAG,
+    /// A transaction was executed in a block.
+    TransactionProcessed = TRANSACTION_PROCESSED_TAG,
+    /// A finality signature was accepted.
+    FinalitySignature = FINALITY_SIGNATURE_TAG,
+}
+
+impl NotificationKind {
+    #[cfg(test)]
+    pub(crate) fn random(rng: &mut TestRng) -> Self {
+        Self::try_from(rng.gen_range(0..3)).unwrap()
+    }
+}
+
+impl TryFrom<u8> for NotificationKind {
+    type Error = bytesrepr::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            BLOCK_ADDED_TAG => Ok(NotificationKind::BlockAdded),
+            TRANSACTION_PROCESSED_TAG => Ok(NotificationKind::TransactionProcessed),
+            FINALITY_SIGNATURE_TAG => Ok(NotificationKind::FinalitySignature),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+impl ToBytes for NotificationKind {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        writer.push(*self as u8);
+        Ok(())
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for NotificationKind {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        Ok((NotificationKind::try_from(tag)?, remainder))
+    }
+}
+
+/// Selects the notifications delivered to a subscriber.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubscriptionFilter {
+    kinds: Vec<NotificationKind>,
+    initiator: Option<InitiatorAddr>,
+}
+
+impl SubscriptionFilter {
+    /// Creates a new filter.
+    ///
+    /// An empty list of `kinds` selects notifications of every kind.  If `initiator` is given,
+    /// only the transactions initiated by it are selected; it doesn't affect the notifications of
+    /// other kinds.
+    pub fn new(kinds: Vec<NotificationKind>, initiator: Option<InitiatorAddr>) -> Self {
+        SubscriptionFilter { kinds, initiator }
+    }
+
+    /// Returns the selected kinds of notifications.
+    pub fn kinds(&self) -> &[NotificationKind] {
+        &self.kinds
+    }
+
+    /// Returns the initiator of the selected transactions.
+    pub fn initiator(&self) -> Option<&InitiatorAddr> {
+        self.initiator.as_ref()
+    }
+
+    /// Returns `true` if notifications of the given kind are selected.
+    pub fn selects_kind(&self, kind: NotificationKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+
+    /// Returns `true` if the given notification is selected.
+    pub fn matches(&self, notification: &Notification) -> bool {
+        if !self.selects_kind(notification.kind()) {
+            return false;
+        }
+        match (notification, &self.initiator) {
+            (Notification::TransactionProcessed(processed), Some(initiator)) => {
+                processed.initiator_addr() == initiator
+            }
+            _ => true,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn random(rng: &mut TestRng) -> Self {
+        let count = rng.gen_range(0..3);
+        SubscriptionFilter {
+            kinds: std::iter::repeat_with(|| NotificationKind::random(rng))
+                .take(count)
+                .collect(),
+            initiator: rng.gen::<bool>().then(|| InitiatorAddr::random(rng)),
+        }
+    }
+}
+
+impl ToBytes for SubscriptionFilter {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.kinds.write_bytes(writer)?;
+        self.initiator.write_bytes(writer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.kinds.serialized_length() + self.initiator.serialized_length()
+    }
+}
+
+impl FromBytes for SubscriptionFilter {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (kinds, remainder) = FromBytes::from_bytes(bytes)?;
+        let (initiator, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((SubscriptionFilter { kinds, initiator }, remainder))
+    }
+}
+
+/// A request to stream notifications over the connection it was sent on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubscriptionRequest {
+    filter: SubscriptionFilter,
+    start_from_height: Option<u64>,
+}
+
+impl SubscriptionRequest {
+    /// Creates a new subscription request.
+    ///
+    /// If `start_from_height` is given, the notifications of the already stored blocks starting
+    /// from that height are delivered before the live ones.
+    pub fn new(filter: SubscriptionFilter, start_from_height: Option<u64>) -> Self {
+        SubscriptionRequest {
+            filter,
+            start_from_height,
+        }
+    }
+
+    /// Returns the filter of the subscription.
+    pub fn filter(&self) -> &SubscriptionFilter {
+        &self.filter
+    }
+
+    /// Returns the height of the block from which to resume the subscription.
+    pub fn start_from_height(&self) -> Option<u64> {
+        self.start_from_height
+    }
+
+    #[cfg(test)]
+    pub(crate) fn random(rng: &mut TestRng) -> Self {
+        SubscriptionRequest {
+            filter: SubscriptionFilter::random(rng),
+            start_from_height: rng.gen(),
+        }
+    }
+}
+
+impl ToBytes for SubscriptionRequest {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.filter.write_bytes(writer)?;
+        self.start_from_height.write_bytes(writer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.filter.serialized_length() + self.start_from_height.serialized_length()
+    }
+}
+
+impl FromBytes for SubscriptionRequest {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (filter, remainder) = FromBytes::from_bytes(bytes)?;
+        let (start_from_height, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            SubscriptionRequest {
+                filter,
+                start_from_height,
+            },
+            remainder,
+        ))
+    }
+}
+
+/// The outcome of a transaction executed in a block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionProcessed {
+    transaction_hash: TransactionHash,
+    initiator_addr: InitiatorAddr,
+    block_hash: BlockHash,
+    block_height: u64,
+    execution_result: ExecutionResult,
+    messages: Option<Messages>,
+}
+
+impl TransactionProcessed {
+    /// Creates a new transaction processed notification.
+    ///
+    /// `messages` is `None` if the messages emitted during the execution aren't known.
+    pub fn new(
+        transaction_hash: TransactionHash,
+        initiator_addr: InitiatorAddr,
+        block_hash: BlockHash,
+        block_height: u64,
+        execution_result: ExecutionResult,
+        messages: Option<Messages>,
+    ) -> Self {
+        TransactionProcessed {
+            transaction_hash,
+            initiator_addr,
+            block_hash,
+            block_height,
+            execution_result,
+            messages,
+        }
+    }
+
+    /// Returns the hash of the transaction.
+    pub fn transaction_hash(&self) -> &TransactionHash {
+        &self.transaction_hash
+    }
+
+    /// Returns the initiator of the transaction.
+    pub fn initiator_addr(&self) -> &InitiatorAddr {
+        &self.initiator_addr
+    }
+
+    /// Returns the hash of the block in which the transaction was executed.
+    pub fn block_hash(&self) -> &BlockHash {
+        &self.block_hash
+    }
+
+    /// Returns the height of the block in which the transaction was executed.
+    pub fn block_height(&self) -> u64 {
+        self.block_height
+    }
+
+    /// Returns the result of the execution.
+    pub fn execution_result(&self) -> &ExecutionResult {
+        &self.execution_result
+    }
+
+    /// Returns the messages emitted during the execution, or `None` if they aren't known.
+    ///
+    /// Messages aren't stored by the node, only their checksums in the global state, so they're
+    /// `None` in the notifications of already stored blocks replayed to a subscriber, as opposed
+    /// to empty if the execution emitted no messages.
+    pub fn messages(&self) -> Option<&Messages> {
+        self.messages.as_ref()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn random(rng: &mut TestRng) -> Self {
+        use casper_types::contract_messages::Message;
+
+        let count = rng.gen_range(0..8);
+        TransactionProcessed {
+            transaction_hash: TransactionHash::random(rng),
+            initiator_addr: InitiatorAddr::random(rng),
+            block_hash: BlockHash::random(rng),
+            block_height: rng.gen(),
+            execution_result: ExecutionResult::random(rng),
+            messages: rng.gen::<bool>().then(|| {
+                std::iter::repeat_with(|| Message::random(rng))
+                    .take(count)
+                    .collect()
+            }),
+        }
+    }
+}
+
+impl ToBytes for TransactionProcessed {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.transaction_hash.write_bytes(writer)?;
+        self.initiator_addr.write_bytes(writer)?;
+        self.block_hash.write_bytes(writer)?;
+        self.block_height.write_bytes(writer)?;
+        self.execution_result.write_bytes(writer)?;
+        self.messages.write_bytes(writer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.transaction_hash.serialized_length()
+            + self.initiator_addr.serialized_length()
+            + self.block_hash.serialized_length()
+            + self.block_height.serialized_length()
+            + self.execution_result.serialized_length()
+            + self.messages.serialized_length()
+    }
+}
+
+impl FromBytes for TransactionProcessed {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (transaction_hash, remainder) = FromBytes::from_bytes(bytes)?;
+        let (initiator_addr, remainder) = FromBytes::from_bytes(remainder)?;
+        let (block_hash, remainder) = FromBytes::from_bytes(remainder)?;
+        let (block_height, remainder) = FromBytes::from_bytes(remainder)?;
+        let (execution_result, remainder) = FromBytes::from_bytes(remainder)?;
+        let (messages, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            TransactionProcessed::new(
+                transaction_hash,
+                initiator_addr,
+                block_hash,
+                block_height,
+                execution_result,
+                messages,
+            ),
+            remainder,
+        ))
+    }
+}
+
+/// A notification pushed to a subscriber.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Notification {
+    /// A block was added to the linear chain.
+    BlockAdded(Box<Block>),
+    /// A transaction was executed in a block.
+    TransactionProcessed(Box<TransactionProcessed>),
+    /// A finality signature was accepted.
+    FinalitySignature(Box<FinalitySignature>),
+}
+
+impl Notification {
+    /// Returns the kind of the notification.
+    pub fn kind(&self) -> NotificationKind {
+        match self {
+            Notification::BlockAdded(_) => NotificationKind::BlockAdded,
+            Notification::TransactionProcessed(_) => NotificationKind::TransactionProcessed,
+            Notification::FinalitySignature(_) => NotificationKind::FinalitySignature,
+        }
+    }
+
+    /// Returns the height of the block the notification relates to, if known.
+    pub fn block_height(&self) -> Option<u64> {
+        match self {
+            Notification::BlockAdded(block) => Some(block.height()),
+            Notification::TransactionProcessed(processed) => Some(processed.block_height()),
+            Notification::FinalitySignature(finality_signature) => match &**finality_signature {
+                FinalitySignature::V1(_) => None,
+                FinalitySignature::V2(finality_signature) => {
+                    Some(finality_signature.block_height())
+                }
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn random(rng: &mut TestRng) -> Self {
+        match NotificationKind::random(rng) {
+            NotificationKind::BlockAdded => {
+                Notification::BlockAdded(Box::new(TestBlockBuilder::new().build(rng).into()))
+            }
+            NotificationKind::TransactionProcessed => {
+                Notification::TransactionProcessed(Box::new(TransactionProcessed::random(rng)))
+            }
+            NotificationKind::FinalitySignature => {
+                Notification::FinalitySignature(Box::new(FinalitySignature::random(rng)))
+            }
+        }
+    }
+}
+
+impl ToBytes for Notification {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.kind().write_bytes(writer)?;
+        match self {
+            Notification::BlockAdded(block) => block.write_bytes(writer),
+            Notification::TransactionProcessed(processed) => processed.write_bytes(writer),
+            Notification::FinalitySignature(finality_signature) => {
+                finality_signature.write_bytes(writer)
+            }
+        }
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                Notification::BlockAdded(block) => block.serialized_length(),
+                Notification::TransactionProcessed(processed) => processed.serialized_length(),
+                Notification::FinalitySignature(finality_signature) => {
+                    finality_signature.serialized_length()
+                }
+            }
+    }
+}
+
+impl FromBytes for Notification {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (kind, remainder) = NotificationKind::from_bytes(bytes)?;
+        match kind {
+            NotificationKind::BlockAdded => {
+                let (block, remainder) = Block::from_bytes(remainder)?;
+                Ok((Notification::BlockAdded(Box::new(block)), remainder))
+            }
+            NotificationKind::TransactionProcessed => {
+                let (processed, remainder) = TransactionProcessed::from_bytes(remainder)?;
+                Ok((
+                    Notification::TransactionProcessed(Box::new(processed)),
+                    remainder,
+                ))
+            }
+            NotificationKind::FinalitySignature => {
+                let (finality_signature, remainder) = FinalitySignature::from_bytes(remainder)?;
+                Ok((
+                    Notification::FinalitySignature(Box::new(finality_signature)),
+                    remainder,
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::testing::TestRng;
+
+    #[test]
+    fn subscription_request_bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+
+        let val = SubscriptionRequest::random(rng);
+        bytesrepr::test_serialization_roundtrip(&val);
+    }
+
+    #[test]
+    fn notification_bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+
+        for _ in 0..10 {
+            let val = Notification::random(rng);
+            bytesrepr::test_serialization_roundtrip(&val);
+        }
+    }
+
+    #[test]
+    fn filter_should_match_notifications() {
+        let rng = &mut TestRng::new();
+        let processed = TransactionProcessed::random(rng);
+        let initiator = processed.initiator_addr().clone();
+        let notification = Notification::TransactionProcessed(Box::new(processed));
+        let finality_signature =
+            Notification::FinalitySignature(Box::new(FinalitySignature::random(rng)));
+
+        assert!(SubscriptionFilter::default().matches(&notification));
+        assert!(SubscriptionFilter::new(vec![], Some(initiator)).matches(&notification));
+        assert!(
+            !SubscriptionFilter::new(vec![], Some(InitiatorAddr::random(rng)))
+                .matches(&notification)
+        );
+        assert!(
+            SubscriptionFilter::new(vec![], Some(InitiatorAddr::random(rng)))
+                .matches(&finality_signature)
+        );
+        assert!(
+            !SubscriptionFilter::new(vec![NotificationKind::BlockAdded], None)
+                .matches(&notification)
+        );
+        assert!(SubscriptionFilter::new(
+            vec![
+                NotificationKind::BlockAdded,
+                NotificationKind::TransactionProcessed
+            ],
+            None
+        )
+        .matches(&notification));
+    }
+}
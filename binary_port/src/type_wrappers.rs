@@ -7,8 +7,8 @@ use casper_types::{
     global_state::TrieMerkleProof,
     system::auction::DelegationRate,
     Account, AddressableEntity, BlockHash, ByteCode, Contract, ContractWasm, EntityAddr, EraId,
-    ExecutionInfo, Key, PublicKey, StoredValue, TimeDiff, Timestamp, Transaction, ValidatorChange,
-    U512,
+    ExecutionInfo, Key, PublicKey, StoredValue, TimeDiff, Timestamp, Transaction, TransactionHash,
+    ValidatorChange, U512,
 };
 use serde::Serialize;
 
@@ -256,6 +256,87 @@ impl FromBytes for RewardResponse {
     }
 }
 
+/// A transaction initiated by a given account, along with the block it was included in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct InitiatorTransaction {
+    transaction_hash: TransactionHash,
+    block_hash: BlockHash,
+    block_height: u64,
+    era_id: EraId,
+}
+
+impl InitiatorTransaction {
+    /// Constructs new initiator transaction.
+    pub fn new(
+        transaction_hash: TransactionHash,
+        block_hash: BlockHash,
+        block_height: u64,
+        era_id: EraId,
+    ) -> Self {
+        Self {
+            transaction_hash,
+            block_hash,
+            block_height,
+            era_id,
+        }
+    }
+
+    /// Returns the hash of the transaction.
+    pub fn transaction_hash(&self) -> TransactionHash {
+        self.transaction_hash
+    }
+
+    /// Returns the hash of the block containing the transaction.
+    pub fn block_hash(&self) -> BlockHash {
+        self.block_hash
+    }
+
+    /// Returns the height of the block containing the transaction.
+    pub fn block_height(&self) -> u64 {
+        self.block_height
+    }
+
+    /// Returns the era ID of the block containing the transaction.
+    pub fn era_id(&self) -> EraId {
+        self.era_id
+    }
+}
+
+impl ToBytes for InitiatorTransaction {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.transaction_hash.serialized_length()
+            + self.block_hash.serialized_length()
+            + self.block_height.serialized_length()
+            + self.era_id.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.transaction_hash.write_bytes(writer)?;
+        self.block_hash.write_bytes(writer)?;
+        self.block_height.write_bytes(writer)?;
+        self.era_id.write_bytes(writer)
+    }
+}
+
+impl FromBytes for InitiatorTransaction {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (transaction_hash, remainder) = FromBytes::from_bytes(bytes)?;
+        let (block_hash, remainder) = FromBytes::from_bytes(remainder)?;
+        let (block_height, remainder) = FromBytes::from_bytes(remainder)?;
+        let (era_id, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            InitiatorTransaction::new(transaction_hash, block_hash, block_height, era_id),
+            remainder,
+        ))
+    }
+}
+
 /// Describes the consensus status.
 #[derive(Debug, PartialEq, Eq, Serialize)]
 pub struct ConsensusStatus {
@@ -713,6 +794,17 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&Uptime::new(rng.gen()));
     }
 
+    #[test]
+    fn initiator_transaction_roundtrip() {
+        let rng = &mut TestRng::new();
+        bytesrepr::test_serialization_roundtrip(&InitiatorTransaction::new(
+            TransactionHash::random(rng),
+            BlockHash::random(rng),
+            rng.gen(),
+            EraId::random(rng),
+        ));
+    }
+
     #[test]
     fn consensus_validator_changes_roundtrip() {
         let rng = &mut TestRng::new();
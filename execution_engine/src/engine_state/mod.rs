@@ -68,6 +68,7 @@ impl ExecutionEngineV1 {
             args,
             authorization_keys,
             phase,
+            trace_execution,
         } = wasm_v1_request;
         // NOTE to core engineers: it is intended for the EE to ONLY execute wasm targeting the
         // casper v1 virtual machine. it should not handle native behavior, database / global state
@@ -131,6 +132,7 @@ impl ExecutionEngineV1 {
                 account_hash,
                 self.config.max_runtime_call_stack_height() as usize,
             ),
+            trace_execution,
         )
     }
 
@@ -204,6 +206,7 @@ impl ExecutionEngineV1 {
                 account_hash,
                 self.config.max_runtime_call_stack_height() as usize,
             ),
+            false,
         )
     }
 }
@@ -8,7 +8,7 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::Bytes,
     contract_messages::Messages,
-    execution::{Effects, TransformKindV2},
+    execution::{Effects, ExecutionTrace, TransformKindV2},
     BlockHash, BlockTime, CLValue, DeployHash, Digest, ExecutableDeployItem, Gas, InitiatorAddr,
     Key, PackageHash, Phase, PricingMode, ProtocolVersion, RuntimeArgs, TransactionEntryPoint,
     TransactionHash, TransactionInvocationTarget, TransactionTarget, TransactionV1Hash, Transfer,
@@ -351,6 +351,8 @@ pub struct WasmV1Request {
     pub authorization_keys: BTreeSet<AccountHash>,
     /// Execution phase.
     pub phase: Phase,
+    /// Whether to record the trace of the execution.
+    pub trace_execution: bool,
 }
 
 impl WasmV1Request {
@@ -415,6 +417,7 @@ impl WasmV1Request {
             entry_point: executable_info.entry_point().clone(),
             args: executable_info.args().clone(),
             phase: executable_info.phase(),
+            trace_execution: false,
         }
     }
 
@@ -478,6 +481,8 @@ pub struct WasmV1Result {
     ret: Option<CLValue>,
     /// Tracking copy cache captured during execution.
     cache: Option<TrackingCopyCache>,
+    /// Trace of the execution, if one was requested.
+    execution_trace: Option<ExecutionTrace>,
 }
 
 impl WasmV1Result {
@@ -502,9 +507,16 @@ impl WasmV1Result {
             error,
             ret,
             cache,
+            execution_trace: None,
         }
     }
 
+    /// Sets the trace of the execution.
+    pub(crate) fn with_execution_trace(mut self, execution_trace: Option<ExecutionTrace>) -> Self {
+        self.execution_trace = execution_trace;
+        self
+    }
+
     /// Error, if any.
     pub fn error(&self) -> Option<&EngineError> {
         self.error.as_ref()
@@ -545,6 +557,11 @@ impl WasmV1Result {
         self.ret.as_ref()
     }
 
+    /// Trace of the execution, if one was requested.
+    pub fn execution_trace(&self) -> Option<&ExecutionTrace> {
+        self.execution_trace.as_ref()
+    }
+
     /// Root not found.
     pub fn root_not_found(gas_limit: Gas, state_hash: Digest) -> Self {
         WasmV1Result {
@@ -556,6 +573,7 @@ impl WasmV1Result {
             error: Some(EngineError::RootNotFound(state_hash)),
             ret: None,
             cache: None,
+            execution_trace: None,
         }
     }
 
@@ -570,6 +588,7 @@ impl WasmV1Result {
             error: Some(error),
             ret: None,
             cache: None,
+            execution_trace: None,
         }
     }
 
@@ -584,6 +603,7 @@ impl WasmV1Result {
             error: Some(EngineError::InvalidExecutableItem(error)),
             ret: None,
             cache: None,
+            execution_trace: None,
         }
     }
 
@@ -615,6 +635,7 @@ impl WasmV1Result {
                 error: None,
                 ret: None,
                 cache: Some(cache),
+                execution_trace: None,
             }),
             TransferResult::Failure(te) => {
                 Some(WasmV1Result {
@@ -626,6 +647,7 @@ impl WasmV1Result {
                     error: Some(EngineError::Transfer(te)),
                     ret: None,
                     cache: None,
+                    execution_trace: None,
                 })
             }
         }
@@ -6,9 +6,13 @@ use casper_storage::{
     AddressGenerator,
 };
 use casper_types::{
-    account::AccountHash, contract_messages::Messages, contracts::NamedKeys, execution::Effects,
+    account::AccountHash,
+    addressable_entity::DEFAULT_ENTRY_POINT_NAME,
+    contract_messages::Messages,
+    contracts::NamedKeys,
+    execution::{Effects, ExecutionTracer},
     ContextAccessRights, EntityAddr, EntryPointType, Gas, Key, Phase, RuntimeArgs,
-    RuntimeFootprint, StoredValue, TransactionHash, U512,
+    RuntimeFootprint, StoredValue, TransactionArgs, TransactionHash, U512,
 };
 
 use crate::{
@@ -41,6 +45,8 @@ impl Executor {
     ///
     /// This method checks if a given contract hash is a system contract, and then short circuits to
     /// a specific native implementation of it. Otherwise, a supplied WASM module is executed.
+    ///
+    /// If `trace_execution` is set, the result holds the trace of the execution.
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn exec<R>(
         &self,
@@ -58,6 +64,7 @@ impl Executor {
         tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         stack: RuntimeStack,
+        trace_execution: bool,
     ) -> WasmV1Result
     where
         R: StateReader<Key, StoredValue, Error = GlobalStateError>,
@@ -99,7 +106,7 @@ impl Executor {
             ExecutionKind::Standard(_) => AllowInstallUpgrade::Forbidden,
         };
 
-        let context = self.create_runtime_context(
+        let mut context = self.create_runtime_context(
             named_keys,
             runtime_footprint,
             context_key,
@@ -118,13 +125,36 @@ impl Executor {
             allow_install_upgrade,
         );
 
+        let execution_tracer =
+            trace_execution.then(|| Rc::new(RefCell::new(ExecutionTracer::new())));
+        if let Some(execution_tracer) = &execution_tracer {
+            context.set_execution_tracer(Rc::clone(execution_tracer));
+        }
+
         let mut runtime = Runtime::new(context);
 
         let result = match execution_kind {
             ExecutionKind::Standard(module_bytes)
             | ExecutionKind::InstallerUpgrader(module_bytes)
             | ExecutionKind::Deploy(module_bytes) => {
-                runtime.execute_module_bytes(module_bytes, stack)
+                // Stored contracts enter their own frame when called, module bytes are executed
+                // in the frame of the initiator.
+                runtime.context().trace(|tracer| {
+                    tracer.enter_call(
+                        context_key,
+                        context_key,
+                        DEFAULT_ENTRY_POINT_NAME.to_string(),
+                        TransactionArgs::Named(args.clone()),
+                    )
+                });
+                let result = runtime.execute_module_bytes(module_bytes, stack);
+                runtime.context().trace(|tracer| {
+                    tracer.exit_call(
+                        runtime.context().gas_counter(),
+                        result.as_ref().err().map(ToString::to_string),
+                    )
+                });
+                result
             }
             ExecutionKind::Stored {
                 entity_hash,
@@ -137,7 +167,10 @@ impl Executor {
             }
         };
 
-        match result {
+        let execution_trace =
+            execution_tracer.map(|execution_tracer| execution_tracer.take().into_trace());
+
+        let result = match result {
             Ok(ret) => WasmV1Result::new(
                 gas_limit,
                 runtime.context().gas_counter(),
@@ -158,7 +191,8 @@ impl Executor {
                 None,
                 None,
             ),
-        }
+        };
+        result.with_execution_trace(execution_trace)
     }
 
     /// Creates new runtime context.
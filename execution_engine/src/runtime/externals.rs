@@ -31,6 +31,11 @@ where
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
+        // The gas function is injected by the preprocessor to meter the Wasm instructions.
+        if func != FunctionIndex::GasFuncIndex {
+            self.context
+                .trace(|tracer| tracer.enter_host_function(func.name()));
+        }
 
         let host_function_costs =
             (*self.context.engine_config().wasm_config().v1()).take_host_function_costs();
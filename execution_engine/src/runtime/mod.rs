@@ -60,8 +60,9 @@ use casper_types::{
     ByteCodeKind, CLTyped, CLValue, ContextAccessRights, Contract, ContractWasm, EntityAddr,
     EntityKind, EntityVersion, EntityVersionKey, EntityVersions, Gas, GrantedAccess, Group, Groups,
     HashAddr, HostFunction, HostFunctionCost, InitiatorAddr, Key, NamedArg, Package, PackageHash,
-    PackageStatus, Phase, PublicKey, RuntimeArgs, RuntimeFootprint, StoredValue, Transfer,
-    TransferResult, TransferV2, TransferredTo, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, U512,
+    PackageStatus, Phase, PublicKey, RuntimeArgs, RuntimeFootprint, StoredValue, TransactionArgs,
+    Transfer, TransferResult, TransferV2, TransferredTo, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH,
+    U512,
 };
 
 use crate::{
@@ -86,6 +87,19 @@ enum CallContractIdentifier {
     },
 }
 
+impl CallContractIdentifier {
+    /// Returns the key of the contract, or of the contract package, being called.
+    fn key(&self) -> Key {
+        match self {
+            CallContractIdentifier::Contract { contract_hash } => Key::Hash(*contract_hash),
+            CallContractIdentifier::ContractPackage {
+                contract_package_hash,
+                ..
+            } => Key::SmartContract(*contract_package_hash),
+        }
+    }
+}
+
 #[repr(u8)]
 enum CallerInformation {
     Initiator = 0,
@@ -1568,6 +1582,31 @@ where
         identifier: CallContractIdentifier,
         entry_point_name: &str,
         args: RuntimeArgs,
+    ) -> Result<CLValue, ExecError> {
+        let gas_counter = self.context.gas_counter();
+        self.context.trace(|tracer| {
+            tracer.enter_call(
+                self.context.get_context_key(),
+                identifier.key(),
+                entry_point_name.to_string(),
+                TransactionArgs::Named(args.clone()),
+            )
+        });
+        let result = self.execute_contract_frame(identifier, entry_point_name, args);
+        self.context.trace(|tracer| {
+            tracer.exit_call(
+                self.context.gas_counter().saturating_sub(gas_counter),
+                result.as_ref().err().map(ToString::to_string),
+            )
+        });
+        result
+    }
+
+    fn execute_contract_frame(
+        &mut self,
+        identifier: CallContractIdentifier,
+        entry_point_name: &str,
+        args: RuntimeArgs,
     ) -> Result<CLValue, ExecError> {
         let (footprint, entity_addr, package) = match identifier {
             CallContractIdentifier::Contract { contract_hash } => {
@@ -4151,6 +4190,8 @@ where
         let cost = host_function
             .calculate_gas_cost(weights)
             .ok_or(ExecError::GasLimit)?; // Overflowing gas calculation means gas limit was exceeded
+        self.context
+            .trace(|tracer| tracer.charge_host_function(cost));
         self.gas(cost)?;
         Ok(())
     }
@@ -34,7 +34,7 @@ use casper_types::{
     bytesrepr::ToBytes,
     contract_messages::{Message, MessageAddr, MessageTopicSummary, Messages, TopicNameHash},
     contracts::{ContractHash, ContractPackage, ContractPackageHash, NamedKeys},
-    execution::Effects,
+    execution::{Effects, ExecutionTracer},
     handle_stored_dictionary_value,
     system::auction::EraInfo,
     AccessRights, AddressableEntity, AddressableEntityHash, BlockTime, CLType, CLValue,
@@ -90,6 +90,8 @@ pub struct RuntimeContext<'a, R> {
     emit_message_cost: U512,
     allow_install_upgrade: AllowInstallUpgrade,
     payment_purse: Option<URef>,
+    // Records the execution trace, if one was requested
+    execution_tracer: Option<Rc<RefCell<ExecutionTracer>>>,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -148,6 +150,7 @@ where
             emit_message_cost,
             allow_install_upgrade,
             payment_purse: None,
+            execution_tracer: None,
         }
     }
 
@@ -179,6 +182,7 @@ where
 
         let transfers = self.transfers.clone();
         let payment_purse = self.payment_purse;
+        let execution_tracer = self.execution_tracer.clone();
 
         RuntimeContext {
             tracking_copy,
@@ -202,6 +206,7 @@ where
             emit_message_cost: self.emit_message_cost,
             allow_install_upgrade: self.allow_install_upgrade,
             payment_purse,
+            execution_tracer,
         }
     }
 
@@ -240,6 +245,21 @@ where
         self.payment_purse = Some(uref);
     }
 
+    /// Sets the tracer recording the execution trace.
+    pub(crate) fn set_execution_tracer(&mut self, execution_tracer: Rc<RefCell<ExecutionTracer>>) {
+        self.execution_tracer = Some(execution_tracer);
+    }
+
+    /// Applies `f` to the execution tracer, if the execution is traced.
+    pub(crate) fn trace<F>(&self, f: F)
+    where
+        F: FnOnce(&mut ExecutionTracer),
+    {
+        if let Some(execution_tracer) = &self.execution_tracer {
+            f(&mut execution_tracer.borrow_mut());
+        }
+    }
+
     /// Returns an instance of the engine config.
     pub fn engine_config(&self) -> &EngineConfig {
         &self.engine_config
@@ -534,6 +554,7 @@ where
         self.validate_readable(key)?;
         self.validate_key(key)?;
 
+        self.trace(|tracer| tracer.record_read(*key));
         let maybe_stored_value = self.tracking_copy.borrow_mut().read(key)?;
 
         let stored_value = match maybe_stored_value {
@@ -551,6 +572,7 @@ where
     /// DO NOT EXPOSE THIS VIA THE FFI - This function bypasses security checks and should be used
     /// with caution.
     pub fn read_gs_unsafe(&mut self, key: &Key) -> Result<Option<StoredValue>, ExecError> {
+        self.trace(|tracer| tracer.record_read(*key));
         self.tracking_copy
             .borrow_mut()
             .read(key)
@@ -939,7 +961,9 @@ where
     where
         K: Into<Key>,
     {
-        self.tracking_copy.borrow_mut().prune(key.into());
+        let key = key.into();
+        self.trace(|tracer| tracer.record_write(key));
+        self.tracking_copy.borrow_mut().prune(key);
     }
 
     pub(crate) fn migrate_package(
@@ -972,9 +996,9 @@ where
         let bytes_count = stored_value.serialized_length();
         self.charge_gas_storage(bytes_count)?;
 
-        self.tracking_copy
-            .borrow_mut()
-            .write(key.into(), stored_value);
+        let key = key.into();
+        self.trace(|tracer| tracer.record_write(key));
+        self.tracking_copy.borrow_mut().write(key, stored_value);
         Ok(())
     }
 
@@ -1038,6 +1062,7 @@ where
         let value_bytes_count = value.serialized_length();
         self.charge_gas_storage(value_bytes_count)?;
 
+        self.trace(|tracer| tracer.record_write(key));
         match self.tracking_copy.borrow_mut().add(key, value) {
             Err(storage_error) => Err(storage_error.into()),
             Ok(AddResult::Success) => Ok(()),
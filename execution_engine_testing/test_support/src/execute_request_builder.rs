@@ -365,6 +365,7 @@ impl ExecuteRequestBuilder {
             args: payment_args,
             authorization_keys: authorization_keys.clone(),
             phase: Phase::Payment,
+            trace_execution: false,
         });
 
         let session = WasmV1Request {
@@ -377,6 +378,7 @@ impl ExecuteRequestBuilder {
             args: session_args,
             authorization_keys,
             phase: Phase::Session,
+            trace_execution: false,
         };
 
         ExecuteRequest {
@@ -0,0 +1,36 @@
+//! Constants and types shared by the cryptographic host functions.
+use num_derive::{FromPrimitive, ToPrimitive};
+
+/// Length of the digests produced by the hashing host functions.
+pub const DIGEST_LENGTH: usize = 32;
+
+/// Length of a public key recovered by `casper_recover_secp256k1`.
+///
+/// The key is serialized as a tag byte followed by the 33 bytes of the compressed secp256k1 key.
+pub const RECOVERED_PUBLIC_KEY_LENGTH: usize = 34;
+
+/// Hash algorithms supported by `casper_generic_hash`.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum HashAlgorithm {
+    /// Blake2b with a 32 byte digest.
+    Blake2b = 0,
+    /// Blake3.
+    Blake3 = 1,
+    /// SHA-256.
+    Sha256 = 2,
+}
+
+/// Hash the input with the given [`HashAlgorithm`].
+///
+/// Shared by the VM2 host and the native SDK environment so both produce identical digests.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn hash<T: AsRef<[u8]>>(hash_algorithm: HashAlgorithm, input: T) -> [u8; DIGEST_LENGTH] {
+    use blake2::{digest::consts::U32, Blake2b, Digest};
+
+    match hash_algorithm {
+        HashAlgorithm::Blake2b => Blake2b::<U32>::digest(input).into(),
+        HashAlgorithm::Blake3 => blake3::hash(input.as_ref()).into(),
+        HashAlgorithm::Sha256 => sha2::Sha256::digest(input).into(),
+    }
+}
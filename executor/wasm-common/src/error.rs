@@ -13,6 +13,10 @@ pub enum Error {
     InvalidData,
     /// The input to the host function was invalid.
     InvalidInput,
+    /// A limit imposed by the chainspec was exceeded.
+    ///
+    /// As an example this could be the maximum number of message topics of a contract.
+    LimitExceeded,
     /// An error code not covered by the other variants.
     Other(i32),
 }
@@ -20,6 +24,7 @@ pub enum Error {
 pub const HOST_ERROR_NOT_FOUND: i32 = 1;
 pub const HOST_ERROR_INVALID_DATA: i32 = 2;
 pub const HOST_ERROR_INVALID_INPUT: i32 = 3;
+pub const HOST_ERROR_LIMIT_EXCEEDED: i32 = 4;
 
 impl From<i32> for Error {
     fn from(value: i32) -> Self {
@@ -27,6 +32,7 @@ impl From<i32> for Error {
             HOST_ERROR_NOT_FOUND => Error::NotFound,
             HOST_ERROR_INVALID_DATA => Error::InvalidData,
             HOST_ERROR_INVALID_INPUT => Error::InvalidInput,
+            HOST_ERROR_LIMIT_EXCEEDED => Error::LimitExceeded,
             other => Error::Other(other),
         }
     }
@@ -54,9 +60,15 @@ mod tests {
         assert_eq!(error, Error::InvalidInput);
     }
 
+    #[test]
+    fn test_from_i32_limit_exceeded() {
+        let error = Error::from(HOST_ERROR_LIMIT_EXCEEDED);
+        assert_eq!(error, Error::LimitExceeded);
+    }
+
     #[test]
     fn test_from_i32_other() {
-        let error = Error::from(4);
-        assert_eq!(error, Error::Other(4));
+        let error = Error::from(5);
+        assert_eq!(error, Error::Other(5));
     }
 }
@@ -1,5 +1,53 @@
 use num_derive::{FromPrimitive, ToPrimitive};
 
+/// Maximum number of members returned by a single `casper_iterate` call.
+pub const MAX_ITERATE_LIMIT: u32 = 100;
+
+/// A page of collection members returned by `casper_iterate`, serialized with borsh.
+pub type IteratePage = Vec<Vec<u8>>;
+
+/// First byte of the context keys holding collection indices.
+///
+/// It never occurs in UTF-8, so these keys can't collide with the entries of a collection with a
+/// non-empty name.
+const COLLECTION_INDEX_MARKER: u8 = 0xff;
+
+const COLLECTION_LENGTH_TAG: u8 = 0;
+const COLLECTION_MEMBER_TAG: u8 = 1;
+const COLLECTION_POSITION_TAG: u8 = 2;
+
+/// Context key under which the number of members of the collection with the given prefix is
+/// stored, as a little endian `u64`.
+pub fn collection_length_key(prefix: &[u8]) -> Vec<u8> {
+    collection_index_key(COLLECTION_LENGTH_TAG, prefix, &[])
+}
+
+/// Context key under which the member at the given position of the collection with the given
+/// prefix is stored.
+///
+/// Members occupy the positions from zero to the length of the collection, so a page of them is
+/// read from consecutive positions.
+pub fn collection_member_key(prefix: &[u8], position: u64) -> Vec<u8> {
+    collection_index_key(COLLECTION_MEMBER_TAG, prefix, &position.to_le_bytes())
+}
+
+/// Context key under which the position of a member of the collection with the given prefix is
+/// stored, as a little endian `u64`.
+pub fn collection_position_key(prefix: &[u8], member: &[u8]) -> Vec<u8> {
+    collection_index_key(COLLECTION_POSITION_TAG, prefix, member)
+}
+
+fn collection_index_key(tag: u8, prefix: &[u8], suffix: &[u8]) -> Vec<u8> {
+    let prefix_length = u32::try_from(prefix.len()).expect("prefix should fit in u32");
+    let mut key = Vec::with_capacity(2 + 4 + prefix.len() + suffix.len());
+    key.push(COLLECTION_INDEX_MARKER);
+    key.push(tag);
+    key.extend(prefix_length.to_le_bytes());
+    key.extend(prefix);
+    key.extend(suffix);
+    key
+}
+
 #[repr(u64)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, ToPrimitive)]
 pub enum KeyspaceTag {
@@ -92,6 +140,24 @@ mod tests {
         assert_eq!(keyspace.as_u64(), 2);
     }
 
+    #[test]
+    fn test_collection_index_keys_are_distinct() {
+        // The prefix length keeps a member of one collection apart from a longer prefix.
+        assert_ne!(
+            collection_position_key(b"ab", b"c"),
+            collection_position_key(b"a", b"bc")
+        );
+        assert_ne!(
+            collection_member_key(b"a", 0),
+            collection_member_key(b"a", 1)
+        );
+        assert_ne!(
+            collection_length_key(b"a"),
+            collection_position_key(b"a", &[])
+        );
+        assert!(std::str::from_utf8(&collection_length_key(b"a")).is_err());
+    }
+
     #[test]
     fn test_as_u64_payment_info() {
         let name = "entry_point";
@@ -1,5 +1,6 @@
 //! A crate that shares common types and utilities between the Wasm executor and the Wasm interface.
 pub mod chain_utils;
+pub mod crypto;
 pub mod entry_point;
 pub mod error;
 pub mod flags;
@@ -6,13 +6,17 @@ use crate::{abi::CreateResult, context::Context};
 use bytes::Bytes;
 use casper_executor_wasm_common::{
     chain_utils,
+    crypto::{self as common_crypto, HashAlgorithm, DIGEST_LENGTH, RECOVERED_PUBLIC_KEY_LENGTH},
     entry_point::{
         ENTRY_POINT_PAYMENT_CALLER, ENTRY_POINT_PAYMENT_DIRECT_INVOCATION_ONLY,
         ENTRY_POINT_PAYMENT_SELF_ONWARD,
     },
-    error::{HOST_ERROR_INVALID_DATA, HOST_ERROR_INVALID_INPUT, HOST_ERROR_NOT_FOUND},
+    error::{
+        HOST_ERROR_INVALID_DATA, HOST_ERROR_INVALID_INPUT, HOST_ERROR_LIMIT_EXCEEDED,
+        HOST_ERROR_NOT_FOUND,
+    },
     flags::ReturnFlags,
-    keyspace::{Keyspace, KeyspaceTag},
+    keyspace::{collection_member_key, IteratePage, Keyspace, KeyspaceTag, MAX_ITERATE_LIMIT},
 };
 use casper_executor_wasm_interface::u32_from_host_result;
 use casper_storage::{
@@ -23,11 +27,15 @@ use casper_storage::{
 use casper_types::{
     account::AccountHash,
     addressable_entity::{ActionThresholds, AssociatedKeys, NamedKeyAddr},
-    bytesrepr::ToBytes,
-    AddressableEntity, AddressableEntityHash, BlockHash, ByteCode, ByteCodeAddr, ByteCodeHash,
-    ByteCodeKind, CLType, ContractRuntimeTag, Digest, EntityAddr, EntityEntryPoint, EntityKind,
-    EntryPointAccess, EntryPointAddr, EntryPointPayment, EntryPointType, EntryPointValue, Groups,
-    HashAddr, Key, Package, PackageHash, PackageStatus, ProtocolVersion, StoredValue, URef, U512,
+    bytesrepr::{self, ToBytes},
+    contract_messages::{Message, MessageAddr, MessagePayload, MessageTopicSummary, TopicNameHash},
+    crypto,
+    execution::ExecutionTracer,
+    AddressableEntity, AddressableEntityHash, BlockGlobalAddr, BlockHash, BlockTime, ByteCode,
+    ByteCodeAddr, ByteCodeHash, ByteCodeKind, CLType, CLValue, ContractRuntimeTag, Digest,
+    EntityAddr, EntityEntryPoint, EntityKind, EntryPointAccess, EntryPointAddr, EntryPointPayment,
+    EntryPointType, EntryPointValue, Groups, HashAddr, Key, Package, PackageHash, PackageStatus,
+    ProtocolVersion, PublicKey, Signature, StoredValue, URef, U512,
 };
 use either::Either;
 use num_derive::FromPrimitive;
@@ -47,6 +55,16 @@ enum EntityKindTag {
     Contract = 1,
 }
 
+/// Applies `f` to the execution tracer, if the execution is traced.
+fn trace<S: GlobalStateReader, E: Executor>(
+    context: &Context<S, E>,
+    f: impl FnOnce(&mut ExecutionTracer),
+) {
+    if let Some(execution_tracer) = &context.execution_tracer {
+        f(&mut execution_tracer.write());
+    }
+}
+
 fn tracking_copy_write_and_charge<S: GlobalStateReader, E: Executor>(
     caller: &mut impl Caller<Context = Context<S, E>>,
     key: Key,
@@ -56,6 +74,7 @@ fn tracking_copy_write_and_charge<S: GlobalStateReader, E: Executor>(
     let gas_cost = storage_costs.calculate_gas_cost(value.serialized_length());
     caller.consume_gas(gas_cost.value().as_u64());
 
+    trace(caller.context(), |tracer| tracer.record_write(key));
     caller.context_mut().tracking_copy.write(key, value);
 }
 
@@ -244,6 +263,9 @@ pub fn casper_read<S: GlobalStateReader, E: Executor>(
             return Ok(1);
         }
     };
+    trace(caller.context(), |tracer| {
+        tracer.record_read(global_state_key)
+    });
     let global_state_read_result = caller.context_mut().tracking_copy.read(&global_state_key);
 
     let global_state_raw_bytes: Cow<[u8]> = match global_state_read_result {
@@ -298,11 +320,173 @@ pub fn casper_read<S: GlobalStateReader, E: Executor>(
     Ok(0)
 }
 
-fn keyspace_to_global_state_key<S: GlobalStateReader, E: Executor>(
-    context: &Context<S, E>,
-    keyspace: Keyspace<'_>,
-) -> Option<Key> {
-    let entity_addr = match context.callee {
+/// Remove the value stored under a key.
+///
+/// Returns [`HOST_ERROR_NOT_FOUND`] if there is no value under the key.  Entry point payment info
+/// is part of the contract and can't be removed.
+pub fn casper_remove<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    key_space: u64,
+    key_ptr: u32,
+    key_size: u32,
+) -> VMResult<i32> {
+    let remove_cost = caller.context().config.host_function_costs().remove;
+    caller.charge_host_function_call(&remove_cost, [key_space as u32, key_ptr, key_size]);
+
+    let keyspace_tag = match KeyspaceTag::from_u64(key_space) {
+        Some(keyspace_tag) => keyspace_tag,
+        None => {
+            // Unknown keyspace received, return error
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        }
+    };
+
+    let key_payload_bytes = caller.memory_read(key_ptr, key_size.try_into().unwrap())?;
+
+    let keyspace = match keyspace_tag {
+        KeyspaceTag::State => Keyspace::State,
+        KeyspaceTag::Context => Keyspace::Context(&key_payload_bytes),
+        KeyspaceTag::NamedKey => {
+            let key_name = match std::str::from_utf8(&key_payload_bytes) {
+                Ok(key_name) => key_name,
+                Err(_) => {
+                    return Ok(HOST_ERROR_INVALID_DATA);
+                }
+            };
+
+            Keyspace::NamedKey(key_name)
+        }
+        KeyspaceTag::PaymentInfo => {
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        }
+    };
+
+    let global_state_key = match keyspace_to_global_state_key(caller.context(), keyspace) {
+        Some(global_state_key) => global_state_key,
+        None => {
+            // Unknown keyspace received, return error
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        }
+    };
+
+    trace(caller.context(), |tracer| {
+        tracer.record_read(global_state_key)
+    });
+    match caller.context_mut().tracking_copy.read(&global_state_key) {
+        Ok(Some(_stored_value)) => {}
+        Ok(None) => return Ok(HOST_ERROR_NOT_FOUND),
+        Err(error) => {
+            error!(?error, "Error while reading from storage; aborting");
+            panic!("Error while reading from storage; aborting key={global_state_key:?} error={error:?}")
+        }
+    }
+
+    trace(caller.context(), |tracer| {
+        tracer.record_write(global_state_key)
+    });
+    caller.context_mut().tracking_copy.prune(global_state_key);
+
+    Ok(0)
+}
+
+/// Read a page of the members of a collection in the context keyspace.
+///
+/// The global state keys of context entries are hashes of their context keys, so entries can't be
+/// visited by an arbitrary prefix of their context keys.  Instead, iterable collections index their
+/// members at consecutive positions, under the context keys given by [`collection_member_key`] for
+/// the prefix of the collection, and only the members of such an index are visited.  The page holds the members at the positions from `start`, up to
+/// `limit` of them, as a borsh serialized [`IteratePage`].  A page with fewer than `limit` members
+/// is the last one.  Each visited member is charged for, on top of the cost of the call itself.
+#[allow(clippy::too_many_arguments)]
+pub fn casper_iterate<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    key_space: u64,
+    prefix_ptr: u32,
+    prefix_size: u32,
+    start: u64,
+    limit: u32,
+    info_ptr: u32,
+    cb_alloc: u32,
+    alloc_ctx: u32,
+) -> VMResult<i32> {
+    let iterate_cost = caller.context().config.host_function_costs().iterate;
+    // The weight of the limit is charged for each visited member instead.
+    caller.charge_host_function_call(
+        &iterate_cost,
+        [
+            key_space as u32,
+            prefix_ptr,
+            prefix_size,
+            start as u32,
+            0,
+            info_ptr,
+            cb_alloc,
+            alloc_ctx,
+        ],
+    );
+    let member_cost = iterate_cost.arguments()[4];
+
+    if KeyspaceTag::from_u64(key_space) != Some(KeyspaceTag::Context) {
+        // Only the context keyspace holds collections.
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    }
+    if limit == 0 || limit > MAX_ITERATE_LIMIT {
+        return Ok(HOST_ERROR_LIMIT_EXCEEDED);
+    }
+
+    let prefix = caller.memory_read(prefix_ptr, prefix_size.try_into().unwrap())?;
+
+    let mut page: IteratePage = Vec::new();
+    for position in (start..).take(limit as usize) {
+        if let MeteringPoints::Exhausted = caller.consume_gas(member_cost.into()) {
+            return Err(VMError::OutOfGas);
+        }
+
+        let member_key = collection_member_key(&prefix, position);
+        let global_state_key =
+            match keyspace_to_global_state_key(caller.context(), Keyspace::Context(&member_key)) {
+                Some(global_state_key) => global_state_key,
+                None => return Ok(HOST_ERROR_INVALID_INPUT),
+            };
+        trace(caller.context(), |tracer| {
+            tracer.record_read(global_state_key)
+        });
+        match caller.context_mut().tracking_copy.read(&global_state_key) {
+            Ok(Some(StoredValue::RawBytes(member))) => page.push(member),
+            Ok(Some(_stored_value)) => return Ok(HOST_ERROR_INVALID_DATA),
+            // Past the last member of the collection.
+            Ok(None) => break,
+            Err(error) => {
+                error!(?error, "Error while reading from storage; aborting");
+                panic!("Error while reading from storage; aborting key={global_state_key:?} error={error:?}")
+            }
+        }
+    }
+
+    let page_bytes = borsh::to_vec(&page).expect("should serialize a page of members");
+
+    let out_ptr: u32 = if cb_alloc != 0 {
+        caller.alloc(cb_alloc, page_bytes.len(), alloc_ctx)?
+    } else {
+        // treats alloc_ctx as data
+        alloc_ctx
+    };
+
+    let read_info = ReadInfo {
+        data: out_ptr,
+        data_size: page_bytes.len().try_into().unwrap(),
+    };
+
+    let read_info_bytes = safe_transmute::transmute_one_to_bytes(&read_info);
+    caller.memory_write(info_ptr, read_info_bytes)?;
+    if out_ptr != 0 {
+        caller.memory_write(out_ptr, &page_bytes)?;
+    }
+    Ok(0)
+}
+
+fn callee_entity_addr<S: GlobalStateReader, E: Executor>(context: &Context<S, E>) -> EntityAddr {
+    match context.callee {
         Key::Account(account_hash) => EntityAddr::new_account(account_hash.value()),
         Key::SmartContract(smart_contract_addr) => {
             EntityAddr::new_smart_contract(smart_contract_addr)
@@ -311,7 +495,14 @@ fn keyspace_to_global_state_key<S: GlobalStateReader, E: Executor>(
             // This should never happen, as the caller is always an account or a smart contract.
             panic!("Unexpected callee variant: {:?}", context.callee)
         }
-    };
+    }
+}
+
+fn keyspace_to_global_state_key<S: GlobalStateReader, E: Executor>(
+    context: &Context<S, E>,
+    keyspace: Keyspace<'_>,
+) -> Option<Key> {
+    let entity_addr = callee_entity_addr(context);
 
     match keyspace {
         Keyspace::State => Some(Key::State(entity_addr)),
@@ -598,6 +789,7 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
                 .with_state_hash(Digest::from_raw([0; 32])) // TODO: Carry on state root hash
                 .with_block_height(1) // TODO: Carry on block height
                 .with_parent_block_hash(BlockHash::new(Digest::from_raw([0; 32]))) // TODO: Carry on parent block hash
+                .with_execution_tracer(caller.context().execution_tracer.clone())
                 .build()
                 .expect("should build");
 
@@ -614,6 +806,7 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
                     gas_usage,
                     effects,
                     cache,
+                    messages,
                 }) => {
                     // output
 
@@ -626,7 +819,7 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
                     caller
                         .context_mut()
                         .tracking_copy
-                        .apply_changes(effects, cache);
+                        .apply_changes(effects, cache, messages);
 
                     output
                 }
@@ -745,6 +938,7 @@ pub fn casper_call<S: GlobalStateReader + 'static, E: Executor + 'static>(
         .with_state_hash(Digest::from_raw([0; 32])) // TODO: Carry on state root hash
         .with_block_height(1) // TODO: Carry on block height
         .with_parent_block_hash(BlockHash::new(Digest::from_raw([0; 32]))) // TODO: Carry on parent block hash
+        .with_execution_tracer(caller.context().execution_tracer.clone())
         .build()
         .expect("should build");
 
@@ -759,6 +953,7 @@ pub fn casper_call<S: GlobalStateReader + 'static, E: Executor + 'static>(
             gas_usage,
             effects,
             cache,
+            messages,
         }) => {
             if let Some(output) = output {
                 let out_ptr: u32 = if cb_alloc != 0 {
@@ -779,7 +974,7 @@ pub fn casper_call<S: GlobalStateReader + 'static, E: Executor + 'static>(
                     caller
                         .context_mut()
                         .tracking_copy
-                        .apply_changes(effects, cache);
+                        .apply_changes(effects, cache, messages);
                     Ok(())
                 }
             };
@@ -1281,6 +1476,7 @@ pub fn casper_upgrade<S: GlobalStateReader + 'static, E: Executor>(
             .with_state_hash(Digest::from_raw([0; 32])) // TODO: Carry on state root hash
             .with_block_height(1) // TODO: Carry on block height
             .with_parent_block_hash(BlockHash::new(Digest::from_raw([0; 32]))) // TODO: Carry on parent block hash
+            .with_execution_tracer(caller.context().execution_tracer.clone())
             .build()
             .expect("should build");
 
@@ -1297,6 +1493,7 @@ pub fn casper_upgrade<S: GlobalStateReader + 'static, E: Executor>(
                 gas_usage,
                 effects,
                 cache,
+                messages,
             }) => {
                 // output
 
@@ -1309,7 +1506,7 @@ pub fn casper_upgrade<S: GlobalStateReader + 'static, E: Executor>(
                 caller
                     .context_mut()
                     .tracking_copy
-                    .apply_changes(effects, cache);
+                    .apply_changes(effects, cache, messages);
 
                 if let Some(output) = output {
                     info!(
@@ -1344,3 +1541,319 @@ pub fn casper_env_block_time<S: GlobalStateReader, E: Executor>(
     let block_time = caller.context().block_time;
     Ok(block_time.value())
 }
+
+/// Emit a message under a topic of the currently executing entity.
+///
+/// The topic is registered on its first use, up to the maximum number of topics per contract.
+pub fn casper_emit<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    topic_name_ptr: u32,
+    topic_name_size: u32,
+    payload_ptr: u32,
+    payload_size: u32,
+) -> VMResult<i32> {
+    let emit_cost = caller.context().config.host_function_costs().emit;
+    caller.charge_host_function_call(
+        &emit_cost,
+        [topic_name_ptr, topic_name_size, payload_ptr, payload_size],
+    );
+
+    let message_limits = caller.context().message_limits;
+    if topic_name_size == 0
+        || topic_name_size > message_limits.max_topic_name_size()
+        || payload_size > message_limits.max_message_size()
+    {
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    }
+
+    let topic_name_bytes =
+        caller.memory_read(topic_name_ptr, topic_name_size.try_into().unwrap())?;
+    let topic_name = match String::from_utf8(topic_name_bytes) {
+        Ok(topic_name) => topic_name,
+        Err(_) => return Ok(HOST_ERROR_INVALID_DATA),
+    };
+    let payload = caller.memory_read(payload_ptr, payload_size.try_into().unwrap())?;
+
+    let hash_addr = match caller.context().callee {
+        Key::Account(account_hash) => account_hash.value(),
+        Key::SmartContract(smart_contract_addr) => smart_contract_addr,
+        _ => {
+            // This should never happen, as the callee is always an account or a smart contract.
+            panic!("Unexpected callee variant: {:?}", caller.context().callee)
+        }
+    };
+    let topic_name_hash = TopicNameHash::new(Digest::hash(topic_name.as_bytes()).value());
+    let topic_key = Key::Message(MessageAddr::new_topic_addr(hash_addr, topic_name_hash));
+    let block_time = caller.context().block_time;
+
+    let prev_topic_summary = match caller.context_mut().tracking_copy.read(&topic_key) {
+        Ok(Some(StoredValue::MessageTopic(topic_summary))) => Some(topic_summary),
+        Ok(Some(stored_value)) => {
+            panic!("Unexpected message topic value: {:?}", stored_value)
+        }
+        Ok(None) => None,
+        Err(error) => {
+            panic!("Error while reading from storage; aborting key={topic_key:?} error={error:?}")
+        }
+    };
+
+    let topic_message_index = match prev_topic_summary {
+        Some(topic_summary) if topic_summary.blocktime() == block_time => {
+            topic_summary.message_count()
+        }
+        Some(topic_summary) => {
+            // Messages of previous blocks are only kept in the execution results, so they are
+            // pruned from the global state once a new block emits under the same topic.
+            for index in 1..topic_summary.message_count() {
+                caller.context_mut().tracking_copy.prune(Key::message(
+                    hash_addr,
+                    topic_name_hash,
+                    index,
+                ));
+            }
+            0
+        }
+        None => {
+            // Register the topic on its first use.
+            let message_topics = match caller
+                .context_mut()
+                .tracking_copy
+                .get_message_topics(hash_addr)
+            {
+                Ok(message_topics) => message_topics,
+                Err(error) => {
+                    panic!("Error while reading message topics; aborting hash_addr={hash_addr:?} error={error:?}")
+                }
+            };
+            if message_topics.len() >= message_limits.max_topics_per_contract() as usize {
+                return Ok(HOST_ERROR_LIMIT_EXCEEDED);
+            }
+            0
+        }
+    };
+
+    let block_message_count_key = Key::BlockGlobal(BlockGlobalAddr::MessageCount);
+    let block_message_index = match caller
+        .context_mut()
+        .tracking_copy
+        .read(&block_message_count_key)
+    {
+        Ok(Some(StoredValue::CLValue(cl_value))) => {
+            let (prev_block_time, prev_count): (BlockTime, u64) =
+                cl_value.into_t().expect("should be a block message count");
+            if prev_block_time == block_time {
+                prev_count
+            } else {
+                0
+            }
+        }
+        Ok(Some(stored_value)) => {
+            panic!("Unexpected block message count value: {:?}", stored_value)
+        }
+        Ok(None) => 0,
+        Err(error) => {
+            panic!("Error while reading from storage; aborting key={block_message_count_key:?} error={error:?}")
+        }
+    };
+
+    let (Some(topic_message_count), Some(block_message_count)) = (
+        topic_message_index.checked_add(1),
+        block_message_index.checked_add(1),
+    ) else {
+        return Ok(HOST_ERROR_LIMIT_EXCEEDED);
+    };
+
+    let message = Message::new(
+        hash_addr,
+        MessagePayload::Bytes(payload.into()),
+        topic_name.clone(),
+        topic_name_hash,
+        topic_message_index,
+        block_message_index,
+    );
+    let topic_value = StoredValue::MessageTopic(MessageTopicSummary::new(
+        topic_message_count,
+        block_time,
+        topic_name,
+    ));
+    let message_key = message.message_key();
+    let message_value = StoredValue::Message(
+        message
+            .checksum()
+            .expect("should compute the checksum of a message"),
+    );
+    let block_message_count_value = StoredValue::CLValue(
+        CLValue::from_t((block_time, block_message_count))
+            .expect("should serialize the block message count"),
+    );
+
+    // Charge for the stored values as measured by their serialized length.
+    let storage_costs = &caller.context().storage_costs;
+    let gas_cost = storage_costs.calculate_gas_cost(
+        topic_value.serialized_length()
+            + message_value.serialized_length()
+            + block_message_count_value.serialized_length(),
+    );
+    caller.consume_gas(gas_cost.value().as_u64());
+
+    caller.context_mut().tracking_copy.emit_message(
+        topic_key,
+        topic_value,
+        message_key,
+        message_value,
+        block_message_count_value,
+        message,
+    );
+
+    Ok(0)
+}
+
+/// Hash the input with Blake2b, writing the 32 byte digest into the output buffer.
+pub fn casper_blake2b<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    in_ptr: u32,
+    in_size: u32,
+    out_ptr: u32,
+    out_size: u32,
+) -> VMResult<i32> {
+    let blake2b_cost = caller.context().config.host_function_costs().blake2b;
+    caller.charge_host_function_call(&blake2b_cost, [in_ptr, in_size, out_ptr, out_size]);
+
+    if out_size as usize != DIGEST_LENGTH {
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    }
+
+    let input = caller.memory_read(in_ptr, in_size.try_into().unwrap())?;
+    let digest = common_crypto::hash(HashAlgorithm::Blake2b, input);
+    caller.memory_write(out_ptr, &digest)?;
+    Ok(0)
+}
+
+/// Hash the input with the given [`HashAlgorithm`], writing the 32 byte digest into the output
+/// buffer.
+pub fn casper_generic_hash<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    in_ptr: u32,
+    in_size: u32,
+    hash_algorithm: u32,
+    out_ptr: u32,
+    out_size: u32,
+) -> VMResult<i32> {
+    let generic_hash_cost = caller.context().config.host_function_costs().generic_hash;
+    caller.charge_host_function_call(
+        &generic_hash_cost,
+        [in_ptr, in_size, hash_algorithm, out_ptr, out_size],
+    );
+
+    let Some(hash_algorithm) = HashAlgorithm::from_u32(hash_algorithm) else {
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    };
+    if out_size as usize != DIGEST_LENGTH {
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    }
+
+    let input = caller.memory_read(in_ptr, in_size.try_into().unwrap())?;
+    let digest = common_crypto::hash(hash_algorithm, input);
+    caller.memory_write(out_ptr, &digest)?;
+    Ok(0)
+}
+
+/// Recover the secp256k1 public key which signed the data, writing it into the output buffer of
+/// [`RECOVERED_PUBLIC_KEY_LENGTH`] bytes.
+///
+/// The signature is expected to be serialized with `bytesrepr`.
+pub fn casper_recover_secp256k1<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    data_ptr: u32,
+    data_size: u32,
+    signature_ptr: u32,
+    signature_size: u32,
+    public_key_ptr: u32,
+    recovery_id: u32,
+) -> VMResult<i32> {
+    let recover_secp256k1_cost = caller
+        .context()
+        .config
+        .host_function_costs()
+        .recover_secp256k1;
+    caller.charge_host_function_call(
+        &recover_secp256k1_cost,
+        [
+            data_ptr,
+            data_size,
+            signature_ptr,
+            signature_size,
+            public_key_ptr,
+            recovery_id,
+        ],
+    );
+
+    let Ok(recovery_id) = u8::try_from(recovery_id) else {
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    };
+
+    let data = caller.memory_read(data_ptr, data_size.try_into().unwrap())?;
+    let signature_bytes = caller.memory_read(signature_ptr, signature_size.try_into().unwrap())?;
+    let Ok(signature) = bytesrepr::deserialize_from_slice::<_, Signature>(signature_bytes) else {
+        return Ok(HOST_ERROR_INVALID_DATA);
+    };
+
+    let Ok(public_key) = crypto::recover_secp256k1(data, &signature, recovery_id) else {
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    };
+    let public_key_bytes = public_key
+        .to_bytes()
+        .expect("should serialize a public key");
+    debug_assert_eq!(public_key_bytes.len(), RECOVERED_PUBLIC_KEY_LENGTH);
+
+    caller.memory_write(public_key_ptr, &public_key_bytes)?;
+    Ok(0)
+}
+
+/// Verify the signature of a message against a public key.
+///
+/// Both the signature and the public key are expected to be serialized with `bytesrepr`.  Returns
+/// [`HOST_ERROR_INVALID_INPUT`] if the signature is not valid.
+pub fn casper_verify_signature<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    message_ptr: u32,
+    message_size: u32,
+    signature_ptr: u32,
+    signature_size: u32,
+    public_key_ptr: u32,
+    public_key_size: u32,
+) -> VMResult<i32> {
+    let verify_signature_cost = caller
+        .context()
+        .config
+        .host_function_costs()
+        .verify_signature;
+    caller.charge_host_function_call(
+        &verify_signature_cost,
+        [
+            message_ptr,
+            message_size,
+            signature_ptr,
+            signature_size,
+            public_key_ptr,
+            public_key_size,
+        ],
+    );
+
+    let message = caller.memory_read(message_ptr, message_size.try_into().unwrap())?;
+    let signature_bytes = caller.memory_read(signature_ptr, signature_size.try_into().unwrap())?;
+    let public_key_bytes =
+        caller.memory_read(public_key_ptr, public_key_size.try_into().unwrap())?;
+
+    let (Ok(signature), Ok(public_key)) = (
+        bytesrepr::deserialize_from_slice::<_, Signature>(signature_bytes),
+        bytesrepr::deserialize_from_slice::<_, PublicKey>(public_key_bytes),
+    ) else {
+        return Ok(HOST_ERROR_INVALID_DATA);
+    };
+
+    if crypto::verify(message, &signature, &public_key).is_err() {
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    }
+    Ok(0)
+}
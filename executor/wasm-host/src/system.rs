@@ -107,6 +107,7 @@ fn dispatch_system_contract<R: GlobalStateReader, Ret>(
     tracking_copy.apply_changes(
         modified_tracking_copy.effects(),
         modified_tracking_copy.cache(),
+        modified_tracking_copy.messages(),
     );
 
     Ok(ret)
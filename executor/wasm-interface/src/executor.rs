@@ -8,8 +8,10 @@ use casper_storage::{
     AddressGenerator, TrackingCopy,
 };
 use casper_types::{
-    account::AccountHash, execution::Effects, BlockHash, BlockTime, Digest, HashAddr, Key,
-    TransactionHash,
+    account::AccountHash,
+    contract_messages::Messages,
+    execution::{Effects, ExecutionTracer},
+    BlockHash, BlockTime, Digest, HashAddr, Key, TransactionHash,
 };
 use parking_lot::RwLock;
 use thiserror::Error;
@@ -51,6 +53,11 @@ pub struct ExecuteRequest {
     pub parent_block_hash: BlockHash,
     /// Block height.
     pub block_height: u64,
+    /// Execution tracer.
+    ///
+    /// Records the execution trace if set.  Shared across the chain of execution requests of a
+    /// traced execution.
+    pub execution_tracer: Option<Arc<RwLock<ExecutionTracer>>>,
 }
 
 /// Builder for `ExecuteRequest`.
@@ -69,6 +76,7 @@ pub struct ExecuteRequestBuilder {
     state_hash: Option<Digest>,
     parent_block_hash: Option<BlockHash>,
     block_height: Option<u64>,
+    execution_tracer: Option<Arc<RwLock<ExecutionTracer>>>,
 }
 
 impl ExecuteRequestBuilder {
@@ -173,6 +181,17 @@ impl ExecuteRequestBuilder {
         self
     }
 
+    /// Set the shared execution tracer.
+    ///
+    /// The execution is only traced if a tracer is set.
+    pub fn with_execution_tracer(
+        mut self,
+        execution_tracer: Option<Arc<RwLock<ExecutionTracer>>>,
+    ) -> Self {
+        self.execution_tracer = execution_tracer;
+        self
+    }
+
     /// Build the `ExecuteRequest`.
     pub fn build(self) -> Result<ExecuteRequest, &'static str> {
         let initiator = self.initiator.ok_or("Initiator is not set")?;
@@ -206,6 +225,7 @@ impl ExecuteRequestBuilder {
             state_hash,
             parent_block_hash,
             block_height,
+            execution_tracer: self.execution_tracer,
         })
     }
 }
@@ -223,6 +243,8 @@ pub struct ExecuteResult {
     pub effects: Effects,
     /// Cache of tracking copy effects produced by the execution.
     pub cache: TrackingCopyCache,
+    /// Messages emitted by the execution.
+    pub messages: Messages,
 }
 
 /// Result of executing a Wasm contract on a state provider.
@@ -236,6 +258,8 @@ pub struct ExecuteWithProviderResult {
     pub gas_usage: GasUsage,
     /// Effects produced by the execution.
     pub effects: Effects,
+    /// Messages emitted by the execution.
+    pub messages: Messages,
     /// Post state hash.
     pub post_state_hash: Digest,
 }
@@ -253,6 +277,10 @@ impl ExecuteWithProviderResult {
         &self.effects
     }
 
+    pub fn messages(&self) -> &Messages {
+        &self.messages
+    }
+
     pub fn post_state_hash(&self) -> Digest {
         self.post_state_hash
     }
@@ -279,6 +307,10 @@ impl ExecuteResult {
     pub fn gas_usage(&self) -> &GasUsage {
         &self.gas_usage
     }
+
+    pub fn messages(&self) -> &Messages {
+        &self.messages
+    }
 }
 
 /// Target for Wasm execution.
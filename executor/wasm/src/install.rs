@@ -4,7 +4,8 @@ use bytes::Bytes;
 use casper_executor_wasm_interface::{executor::ExecuteError, GasUsage, HostError};
 use casper_storage::{global_state::error::Error as GlobalStateError, AddressGenerator};
 use casper_types::{
-    account::AccountHash, execution::Effects, BlockHash, BlockTime, Digest, TransactionHash,
+    account::AccountHash, contract_messages::Messages, execution::Effects, BlockHash, BlockTime,
+    Digest, TransactionHash,
 };
 use parking_lot::RwLock;
 use thiserror::Error;
@@ -183,6 +184,8 @@ pub struct InstallContractResult {
     pub(crate) gas_usage: GasUsage,
     /// Effects produced by the execution.
     pub(crate) effects: Effects,
+    /// Messages emitted by the constructor.
+    pub(crate) messages: Messages,
     /// Post state hash after installation.
     pub(crate) post_state_hash: Digest,
 }
@@ -195,6 +198,10 @@ impl InstallContractResult {
         &self.gas_usage
     }
 
+    pub fn messages(&self) -> &Messages {
+        &self.messages
+    }
+
     pub fn post_state_hash(&self) -> Digest {
         self.post_state_hash
     }
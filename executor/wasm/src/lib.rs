@@ -3,6 +3,7 @@ pub(crate) mod system;
 
 use std::{
     collections::{BTreeSet, VecDeque},
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -20,7 +21,8 @@ use casper_executor_wasm_interface::{
     },
     ConfigBuilder, GasUsage, HostError, TrapCode, VMError, WasmInstance,
 };
-use casper_executor_wasmer_backend::WasmerEngine;
+pub use casper_executor_wasmer_backend::ModuleCacheStats;
+use casper_executor_wasmer_backend::{WasmerEngine, DEFAULT_MODULE_CACHE_CAPACITY};
 use casper_storage::{
     global_state::{
         error::Error as GlobalStateError,
@@ -34,8 +36,8 @@ use casper_types::{
     addressable_entity::{ActionThresholds, AssociatedKeys},
     bytesrepr, AddressableEntity, AddressableEntityHash, ByteCode, ByteCodeAddr, ByteCodeHash,
     ByteCodeKind, ContractRuntimeTag, Digest, EntityAddr, EntityKind, Gas, Groups, InitiatorAddr,
-    Key, Package, PackageHash, PackageStatus, Phase, ProtocolVersion, StorageCosts, StoredValue,
-    TransactionInvocationTarget, URef, WasmV2Config, U512,
+    Key, MessageLimits, Package, PackageHash, PackageStatus, Phase, ProtocolVersion, StorageCosts,
+    StoredValue, TransactionArgs, TransactionInvocationTarget, URef, WasmV2Config, U512,
 };
 use either::Either;
 use install::{InstallContractError, InstallContractRequest, InstallContractResult};
@@ -55,12 +57,15 @@ pub enum ExecutorKind {
     Compiled,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct ExecutorConfig {
     memory_limit: u32,
     executor_kind: ExecutorKind,
     wasm_config: WasmV2Config,
     storage_costs: StorageCosts,
+    message_limits: MessageLimits,
+    module_cache_capacity: usize,
+    module_artifacts_dir: Option<PathBuf>,
 }
 
 impl ExecutorConfigBuilder {
@@ -75,6 +80,9 @@ pub struct ExecutorConfigBuilder {
     executor_kind: Option<ExecutorKind>,
     wasm_config: Option<WasmV2Config>,
     storage_costs: Option<StorageCosts>,
+    message_limits: Option<MessageLimits>,
+    module_cache_capacity: Option<usize>,
+    module_artifacts_dir: Option<PathBuf>,
 }
 
 impl ExecutorConfigBuilder {
@@ -102,18 +110,47 @@ impl ExecutorConfigBuilder {
         self
     }
 
+    /// Set the limits of the messages emitted by contracts.
+    ///
+    /// Defaults to [`MessageLimits::default`].
+    pub fn with_message_limits(mut self, message_limits: MessageLimits) -> Self {
+        self.message_limits = Some(message_limits);
+        self
+    }
+
+    /// Set the number of compiled Wasm modules kept in memory.
+    ///
+    /// Defaults to [`DEFAULT_MODULE_CACHE_CAPACITY`].
+    pub fn with_module_cache_capacity(mut self, module_cache_capacity: usize) -> Self {
+        self.module_cache_capacity = Some(module_cache_capacity);
+        self
+    }
+
+    /// Set the directory in which compiled Wasm modules are stored across restarts.
+    pub fn with_module_artifacts_dir(mut self, module_artifacts_dir: PathBuf) -> Self {
+        self.module_artifacts_dir = Some(module_artifacts_dir);
+        self
+    }
+
     /// Build the `ExecutorConfig`.
     pub fn build(self) -> Result<ExecutorConfig, &'static str> {
         let memory_limit = self.memory_limit.ok_or("Memory limit is not set")?;
         let executor_kind = self.executor_kind.ok_or("Executor kind is not set")?;
         let wasm_config = self.wasm_config.ok_or("Wasm config is not set")?;
         let storage_costs = self.storage_costs.ok_or("Storage costs are not set")?;
+        let message_limits = self.message_limits.unwrap_or_default();
+        let module_cache_capacity = self
+            .module_cache_capacity
+            .unwrap_or(DEFAULT_MODULE_CACHE_CAPACITY);
 
         Ok(ExecutorConfig {
             memory_limit,
             executor_kind,
             wasm_config,
             storage_costs,
+            message_limits,
+            module_cache_capacity,
+            module_artifacts_dir: self.module_artifacts_dir,
         })
     }
 }
@@ -288,12 +325,13 @@ impl ExecutorV2 {
                         gas_usage,
                         effects,
                         cache,
+                        messages,
                     }) => {
                         if let Some(host_error) = host_error {
                             return Err(InstallContractError::Constructor { host_error });
                         }
 
-                        tracking_copy.apply_changes(effects, cache);
+                        tracking_copy.apply_changes(effects, cache, messages);
 
                         if let Some(output) = output {
                             warn!(?output, "unexpected output from constructor");
@@ -315,12 +353,14 @@ impl ExecutorV2 {
         };
 
         let effects = tracking_copy.effects();
+        let messages = tracking_copy.messages();
 
         match state_provider.commit_effects(state_root_hash, effects.clone()) {
             Ok(post_state_hash) => Ok(InstallContractResult {
                 smart_contract_addr,
                 gas_usage: ctor_gas_usage,
                 effects,
+                messages,
                 post_state_hash,
             }),
             Err(error) => Err(InstallContractError::GlobalState(error)),
@@ -346,6 +386,7 @@ impl ExecutorV2 {
             state_hash,
             parent_block_hash,
             block_height,
+            execution_tracer,
         } = execute_request;
 
         // TODO: Purse uref does not need to be optional once value transfers to WasmBytes are
@@ -470,6 +511,7 @@ impl ExecutorV2 {
                                         ),
                                         effects: tracking_copy.effects(),
                                         cache: tracking_copy.cache(),
+                                        messages: tracking_copy.messages(),
                                     });
                                 }
                             }
@@ -529,6 +571,7 @@ impl ExecutorV2 {
             initiator,
             config: self.config.wasm_config,
             storage_costs: self.config.storage_costs,
+            message_limits: self.config.message_limits,
             caller: caller_key,
             callee: callee_key,
             transferred_value,
@@ -537,8 +580,9 @@ impl ExecutorV2 {
             address_generator: Arc::clone(&address_generator),
             transaction_hash,
             chain_name,
-            input,
+            input: input.clone(),
             block_time,
+            execution_tracer: execution_tracer.clone(),
         };
 
         let wasm_instance_config = ConfigBuilder::new()
@@ -549,6 +593,18 @@ impl ExecutorV2 {
         let mut instance = vm.instantiate(wasm_bytes, context, wasm_instance_config)?;
 
         self.push_execution_stack(execution_kind.clone());
+        if let Some(execution_tracer) = &execution_tracer {
+            let entry_point = match export_or_selector {
+                Either::Left(export_name) => export_name.to_string(),
+                Either::Right(selector) => selector.to_string(),
+            };
+            execution_tracer.write().enter_call(
+                caller_key,
+                callee_key,
+                entry_point,
+                TransactionArgs::Bytesrepr(input.to_vec().into()),
+            );
+        }
         let (vm_result, gas_usage) = match export_or_selector {
             Either::Left(export_name) => instance.call_export(export_name),
             Either::Right(_entry_point) => todo!("Restore selectors"), /* instance.call_export(&
@@ -567,59 +623,76 @@ impl ExecutorV2 {
             ..
         } = context;
 
-        match vm_result {
-            Ok(()) => Ok(ExecuteResult {
+        let execute_result = match vm_result {
+            Ok(()) => ExecuteResult {
                 host_error: None,
                 output: None,
                 gas_usage,
                 effects: final_tracking_copy.effects(),
                 cache: final_tracking_copy.cache(),
-            }),
+                messages: final_tracking_copy.messages(),
+            },
             Err(VMError::Return { flags, data }) => {
                 let host_error = if flags.contains(ReturnFlags::REVERT) {
                     // The contract has reverted.
                     Some(HostError::CalleeReverted)
                 } else {
                     // Merge the tracking copy parts since the execution has succeeded.
-                    initial_tracking_copy
-                        .apply_changes(final_tracking_copy.effects(), final_tracking_copy.cache());
+                    initial_tracking_copy.apply_changes(
+                        final_tracking_copy.effects(),
+                        final_tracking_copy.cache(),
+                        final_tracking_copy.messages(),
+                    );
 
                     None
                 };
 
-                Ok(ExecuteResult {
+                ExecuteResult {
                     host_error,
                     output: data,
                     gas_usage,
                     effects: initial_tracking_copy.effects(),
                     cache: initial_tracking_copy.cache(),
-                })
+                    messages: initial_tracking_copy.messages(),
+                }
             }
-            Err(VMError::OutOfGas) => Ok(ExecuteResult {
+            Err(VMError::OutOfGas) => ExecuteResult {
                 host_error: Some(HostError::CalleeGasDepleted),
                 output: None,
                 gas_usage,
                 effects: final_tracking_copy.effects(),
                 cache: final_tracking_copy.cache(),
-            }),
-            Err(VMError::Trap(trap_code)) => Ok(ExecuteResult {
+                messages: final_tracking_copy.messages(),
+            },
+            Err(VMError::Trap(trap_code)) => ExecuteResult {
                 host_error: Some(HostError::CalleeTrapped(trap_code)),
                 output: None,
                 gas_usage,
                 effects: initial_tracking_copy.effects(),
                 cache: initial_tracking_copy.cache(),
-            }),
+                messages: initial_tracking_copy.messages(),
+            },
             Err(VMError::Export(export_error)) => {
                 error!(?export_error, "export error");
-                Ok(ExecuteResult {
+                ExecuteResult {
                     host_error: Some(HostError::NotCallable),
                     output: None,
                     gas_usage,
                     effects: initial_tracking_copy.effects(),
                     cache: initial_tracking_copy.cache(),
-                })
+                    messages: initial_tracking_copy.messages(),
+                }
             }
+        };
+
+        if let Some(execution_tracer) = &execution_tracer {
+            execution_tracer.write().exit_call(
+                Gas::new(execute_result.gas_usage.gas_spent()),
+                execute_result.host_error.as_ref().map(ToString::to_string),
+            );
         }
+
+        Ok(execute_result)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -664,7 +737,11 @@ impl ExecutorV2 {
         let effects = wasm_v1_result.effects();
         match wasm_v1_result.cache() {
             Some(cache) => {
-                tracking_copy.apply_changes(effects.clone(), cache.clone());
+                tracking_copy.apply_changes(
+                    effects.clone(),
+                    cache.clone(),
+                    wasm_v1_result.messages().clone(),
+                );
             }
             None => {
                 debug_assert!(
@@ -714,6 +791,7 @@ impl ExecutorV2 {
             gas_usage: GasUsage::new(gas_limit, remaining_points),
             effects: fork2.effects(),
             cache: fork2.cache(),
+            messages: fork2.messages(),
         })
     }
 
@@ -746,6 +824,7 @@ impl ExecutorV2 {
                 gas_usage,
                 effects,
                 cache: _,
+                messages,
             }) => match state_provider.commit_effects(state_root_hash, effects.clone()) {
                 Ok(post_state_hash) => Ok(ExecuteWithProviderResult {
                     host_error,
@@ -753,6 +832,7 @@ impl ExecutorV2 {
                     gas_usage,
                     post_state_hash,
                     effects,
+                    messages,
                 }),
                 Err(error) => Err(error.into()),
             },
@@ -765,7 +845,10 @@ impl ExecutorV2 {
     /// Create a new `ExecutorV2` instance.
     pub fn new(config: ExecutorConfig, execution_engine_v1: Arc<ExecutionEngineV1>) -> Self {
         let wasm_engine = match config.executor_kind {
-            ExecutorKind::Compiled => WasmerEngine::new(),
+            ExecutorKind::Compiled => WasmerEngine::with_module_cache(
+                config.module_cache_capacity,
+                config.module_artifacts_dir.clone(),
+            ),
         };
         ExecutorV2 {
             config,
@@ -775,6 +858,11 @@ impl ExecutorV2 {
         }
     }
 
+    /// Returns the statistics of the compiled Wasm module cache.
+    pub fn module_cache_stats(&self) -> ModuleCacheStats {
+        self.compiled_wasm_engine.module_cache_stats()
+    }
+
     /// Push the execution stack.
     pub(crate) fn push_execution_stack(&self, execution_kind: ExecutionKind) {
         let mut execution_stack = self.execution_stack.write();
@@ -678,6 +678,13 @@ fn call_dummy_host_fn_by_name(
                 upgrade: HostFunction::fixed(1),
                 call: HostFunction::fixed(1),
                 print: HostFunction::fixed(1),
+                emit: HostFunction::fixed(1),
+                blake2b: HostFunction::fixed(1),
+                generic_hash: HostFunction::fixed(1),
+                recover_secp256k1: HostFunction::fixed(1),
+                verify_signature: HostFunction::fixed(1),
+                remove: HostFunction::fixed(1),
+                iterate: HostFunction::fixed(1),
             },
         );
         let executor_config = ExecutorConfigBuilder::default()
@@ -770,6 +777,13 @@ fn write_n_bytes_at_limit(
                 upgrade: HostFunction::fixed(0),
                 call: HostFunction::fixed(0),
                 print: HostFunction::fixed(0),
+                emit: HostFunction::fixed(0),
+                blake2b: HostFunction::fixed(0),
+                generic_hash: HostFunction::fixed(0),
+                recover_secp256k1: HostFunction::fixed(0),
+                verify_signature: HostFunction::fixed(0),
+                remove: HostFunction::fixed(0),
+                iterate: HostFunction::fixed(0),
             },
         );
         let executor_config = ExecutorConfigBuilder::default()
@@ -1,8 +1,10 @@
 pub(crate) mod imports;
 mod metering_middleware;
+mod module_cache;
 
 use std::{
     collections::BinaryHeap,
+    path::PathBuf,
     sync::{Arc, Weak},
 };
 
@@ -18,14 +20,13 @@ use casper_types::{HostFunction, HostFunctionCost};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use wasmer::{
-    AsStoreMut, AsStoreRef, CompilerConfig, Engine, Function, FunctionEnv, FunctionEnvMut, Imports,
-    Instance, Memory, MemoryView, Module, RuntimeError, Store, StoreMut, Table, TypedFunction,
-    WasmPtr,
+    AsStoreMut, AsStoreRef, Function, FunctionEnv, FunctionEnvMut, Imports, Instance, Memory,
+    MemoryView, RuntimeError, Store, StoreMut, Table, TypedFunction, WasmPtr,
 };
-use wasmer_compiler_singlepass::Singlepass;
 use wasmer_middlewares::metering;
 
-use metering_middleware::make_wasmer_metering_middleware;
+use module_cache::{CompiledModule, ModuleCache};
+pub use module_cache::{ModuleCacheStats, DEFAULT_MODULE_CACHE_CAPACITY};
 
 fn from_wasmer_memory_access_error(error: wasmer::MemoryAccessError) -> VMError {
     let trap_code = match error {
@@ -76,21 +77,43 @@ fn from_wasmer_export_error(error: wasmer::ExportError) -> VMError {
     VMError::Export(export_error)
 }
 
-#[derive(Default)]
-pub struct WasmerEngine(());
+pub struct WasmerEngine {
+    module_cache: ModuleCache,
+}
+
+impl Default for WasmerEngine {
+    fn default() -> Self {
+        Self::with_module_cache(DEFAULT_MODULE_CACHE_CAPACITY, None)
+    }
+}
 
 impl WasmerEngine {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Creates an engine keeping up to `capacity` compiled modules in memory, and storing compiled
+    /// module artifacts in `artifacts_dir` if given.
+    pub fn with_module_cache(capacity: usize, artifacts_dir: Option<PathBuf>) -> Self {
+        WasmerEngine {
+            module_cache: ModuleCache::new(capacity, artifacts_dir),
+        }
+    }
+
+    /// Returns the statistics of the compiled module cache.
+    pub fn module_cache_stats(&self) -> ModuleCacheStats {
+        self.module_cache.stats()
+    }
+
     pub fn instantiate<T: Into<Bytes>, S: GlobalStateReader + 'static, E: Executor + 'static>(
         &self,
         wasm_bytes: T,
         context: Context<S, E>,
         config: Config,
     ) -> Result<impl WasmInstance<Context = Context<S, E>>, WasmPreparationError> {
-        WasmerInstance::from_wasm_bytes(wasm_bytes, context, config)
+        let wasm_bytes: Bytes = wasm_bytes.into();
+        let compiled_module = self.module_cache.get_or_compile(&wasm_bytes)?;
+        WasmerInstance::from_compiled_module(compiled_module, wasm_bytes, context, config)
     }
 }
 
@@ -106,7 +129,15 @@ pub(crate) struct WasmerCaller<'a, S: GlobalStateReader, E: Executor> {
     env: FunctionEnvMut<'a, WasmerEnv<S, E>>,
 }
 
-impl<S: GlobalStateReader + 'static, E: Executor + 'static> WasmerCaller<'_, S, E> {
+impl<'a, S: GlobalStateReader + 'static, E: Executor + 'static> WasmerCaller<'a, S, E> {
+    /// Creates the caller of a host function, recording its invocation in the execution trace.
+    fn for_host_function(env: FunctionEnvMut<'a, WasmerEnv<S, E>>, name: &str) -> Self {
+        if let Some(execution_tracer) = &env.data().context.execution_tracer {
+            execution_tracer.write().enter_host_function(name);
+        }
+        WasmerCaller { env }
+    }
+
     fn with_memory<T>(&self, f: impl FnOnce(MemoryView<'_>) -> T) -> T {
         let mem = &self.env.data().exported_runtime().memory;
         let binding = self.env.as_store_ref();
@@ -226,6 +257,9 @@ impl<S: GlobalStateReader + 'static, E: Executor + 'static> Caller for WasmerCal
             return MeteringPoints::Exhausted; // Overflowing gas calculation means gas limit was
                                               // exceeded
         };
+        if let Some(execution_tracer) = &self.context().execution_tracer {
+            execution_tracer.write().charge_host_function(cost);
+        }
         self.consume_gas(cost.value().as_u64())
     }
 }
@@ -295,26 +329,12 @@ where
         Ok(())
     }
 
-    pub(crate) fn from_wasm_bytes<C: Into<Bytes>>(
-        wasm_bytes: C,
+    pub(crate) fn from_compiled_module(
+        CompiledModule { engine, module }: CompiledModule,
+        wasm_bytes: Bytes,
         context: Context<S, E>,
         config: Config,
     ) -> Result<Self, WasmPreparationError> {
-        // let mut store = Engine
-        let engine = {
-            let mut singlepass_compiler = Singlepass::new();
-            let metering = make_wasmer_metering_middleware(config.gas_limit());
-            singlepass_compiler.push_middleware(metering);
-            singlepass_compiler
-        };
-
-        let engine = Engine::from(engine);
-
-        let wasm_bytes: Bytes = wasm_bytes.into();
-
-        let module = Module::new(&engine, &wasm_bytes)
-            .map_err(|error| WasmPreparationError::Compile(error.to_string()))?;
-
         let mut store = Store::new(engine);
 
         let wasmer_env = WasmerEnv::new(context, wasm_bytes, InterfaceVersion::from(1u32));
@@ -352,7 +372,7 @@ where
                      key_size: u32,
                      value_ptr: u32,
                      value_size: u32| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_write");
                         host::casper_write(
                             wasmer_caller,
                             key_space,
@@ -378,7 +398,7 @@ where
                      info_ptr: u32,
                      cb_alloc: u32,
                      cb_ctx: u32| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_read");
                         host::casper_read(
                             wasmer_caller,
                             key_space,
@@ -391,6 +411,53 @@ where
                     },
                 ),
             );
+            imports.define(
+                "env",
+                "casper_remove",
+                Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    |env: FunctionEnvMut<WasmerEnv<S, E>>,
+                     key_space: u64,
+                     key_ptr: u32,
+                     key_size: u32| {
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_remove");
+                        host::casper_remove(wasmer_caller, key_space, key_ptr, key_size)
+                    },
+                ),
+            );
+
+            imports.define(
+                "env",
+                "casper_iterate",
+                Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    |env: FunctionEnvMut<WasmerEnv<S, E>>,
+                     key_space: u64,
+                     prefix_ptr: u32,
+                     prefix_size: u32,
+                     start: u64,
+                     limit: u32,
+                     info_ptr: u32,
+                     cb_alloc: u32,
+                     alloc_ctx: u32| {
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_iterate");
+                        host::casper_iterate(
+                            wasmer_caller,
+                            key_space,
+                            prefix_ptr,
+                            prefix_size,
+                            start,
+                            limit,
+                            info_ptr,
+                            cb_alloc,
+                            alloc_ctx,
+                        )
+                    },
+                ),
+            );
+
             imports.define(
                 "env",
                 "casper_print",
@@ -398,7 +465,7 @@ where
                     &mut store,
                     &function_env,
                     |env: FunctionEnvMut<WasmerEnv<S, E>>, message_ptr: u32, message_size: u32| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_print");
                         host::casper_print(wasmer_caller, message_ptr, message_size)
                     },
                 ),
@@ -411,7 +478,7 @@ where
                     &mut store,
                     &function_env,
                     |env: FunctionEnvMut<WasmerEnv<S, E>>, flags, data_ptr, data_len| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_return");
                         host::casper_return(wasmer_caller, flags, data_ptr, data_len)
                     },
                 ),
@@ -427,7 +494,8 @@ where
                      cb_alloc: u32,
                      cb_ctx: u32|
                      -> VMResult<u32> {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller =
+                            WasmerCaller::for_host_function(env, "casper_copy_input");
                         host::casper_copy_input(wasmer_caller, cb_alloc, cb_ctx)
                     },
                 ),
@@ -443,7 +511,7 @@ where
                      flags: u32,
                      data_ptr: u32,
                      data_len: u32| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_return");
                         host::casper_return(wasmer_caller, flags, data_ptr, data_len)
                     },
                 ),
@@ -466,7 +534,7 @@ where
                      seed_ptr: u32,
                      seed_len: u32,
                      result_ptr: u32| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_create");
 
                         match host::casper_create(
                             wasmer_caller,
@@ -504,7 +572,7 @@ where
                      input_len: u32,
                      cb_alloc: u32,
                      cb_ctx: u32| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_call");
                         match host::casper_call(
                             wasmer_caller,
                             address_ptr,
@@ -531,7 +599,8 @@ where
                     &mut store,
                     &function_env,
                     |env: FunctionEnvMut<WasmerEnv<S, E>>, dest_ptr, dest_len, entity_kind_ptr| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller =
+                            WasmerCaller::for_host_function(env, "casper_env_caller");
                         host::casper_env_caller(wasmer_caller, dest_ptr, dest_len, entity_kind_ptr)
                     },
                 ),
@@ -546,7 +615,8 @@ where
                     |env: FunctionEnvMut<WasmerEnv<S, E>>,
                      output: WasmPtr<u128>|
                      -> Result<(), VMError> {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller =
+                            WasmerCaller::for_host_function(env, "casper_env_transferred_value");
                         host::casper_env_transferred_value(wasmer_caller, output.offset())?;
                         Ok(())
                     },
@@ -564,7 +634,8 @@ where
                      entity_addr,
                      entity_addr_len,
                      output_ptr: WasmPtr<u128>| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller =
+                            WasmerCaller::for_host_function(env, "casper_env_balance");
                         host::casper_env_balance(
                             wasmer_caller,
                             entity_kind,
@@ -586,7 +657,7 @@ where
                      address_ptr,
                      address_len,
                      amount: WasmPtr<u128>| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_transfer");
                         host::casper_transfer(
                             wasmer_caller,
                             address_ptr,
@@ -611,7 +682,7 @@ where
                      input_ptr,
                      input_len|
                      -> Result<u32, VMError> {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_upgrade");
                         // match
                         match host::casper_upgrade(
                             wasmer_caller,
@@ -636,12 +707,135 @@ where
                     &mut store,
                     &function_env,
                     |env: FunctionEnvMut<WasmerEnv<S, E>>| {
-                        let wasmer_caller = WasmerCaller { env };
+                        let wasmer_caller =
+                            WasmerCaller::for_host_function(env, "casper_env_block_time");
                         host::casper_env_block_time(wasmer_caller)
                     },
                 ),
             );
 
+            imports.define(
+                "env",
+                "casper_emit",
+                Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    |env: FunctionEnvMut<WasmerEnv<S, E>>,
+                     topic_name_ptr: u32,
+                     topic_name_size: u32,
+                     payload_ptr: u32,
+                     payload_size: u32| {
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_emit");
+                        host::casper_emit(
+                            wasmer_caller,
+                            topic_name_ptr,
+                            topic_name_size,
+                            payload_ptr,
+                            payload_size,
+                        )
+                    },
+                ),
+            );
+
+            imports.define(
+                "env",
+                "casper_blake2b",
+                Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    |env: FunctionEnvMut<WasmerEnv<S, E>>,
+                     in_ptr: u32,
+                     in_size: u32,
+                     out_ptr: u32,
+                     out_size: u32| {
+                        let wasmer_caller = WasmerCaller::for_host_function(env, "casper_blake2b");
+                        host::casper_blake2b(wasmer_caller, in_ptr, in_size, out_ptr, out_size)
+                    },
+                ),
+            );
+
+            imports.define(
+                "env",
+                "casper_generic_hash",
+                Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    |env: FunctionEnvMut<WasmerEnv<S, E>>,
+                     in_ptr: u32,
+                     in_size: u32,
+                     hash_algorithm: u32,
+                     out_ptr: u32,
+                     out_size: u32| {
+                        let wasmer_caller =
+                            WasmerCaller::for_host_function(env, "casper_generic_hash");
+                        host::casper_generic_hash(
+                            wasmer_caller,
+                            in_ptr,
+                            in_size,
+                            hash_algorithm,
+                            out_ptr,
+                            out_size,
+                        )
+                    },
+                ),
+            );
+
+            imports.define(
+                "env",
+                "casper_recover_secp256k1",
+                Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    |env: FunctionEnvMut<WasmerEnv<S, E>>,
+                     data_ptr: u32,
+                     data_size: u32,
+                     signature_ptr: u32,
+                     signature_size: u32,
+                     public_key_ptr: u32,
+                     recovery_id: u32| {
+                        let wasmer_caller =
+                            WasmerCaller::for_host_function(env, "casper_recover_secp256k1");
+                        host::casper_recover_secp256k1(
+                            wasmer_caller,
+                            data_ptr,
+                            data_size,
+                            signature_ptr,
+                            signature_size,
+                            public_key_ptr,
+                            recovery_id,
+                        )
+                    },
+                ),
+            );
+
+            imports.define(
+                "env",
+                "casper_verify_signature",
+                Function::new_typed_with_env(
+                    &mut store,
+                    &function_env,
+                    |env: FunctionEnvMut<WasmerEnv<S, E>>,
+                     message_ptr: u32,
+                     message_size: u32,
+                     signature_ptr: u32,
+                     signature_size: u32,
+                     public_key_ptr: u32,
+                     public_key_size: u32| {
+                        let wasmer_caller =
+                            WasmerCaller::for_host_function(env, "casper_verify_signature");
+                        host::casper_verify_signature(
+                            wasmer_caller,
+                            message_ptr,
+                            message_size,
+                            signature_ptr,
+                            signature_size,
+                            public_key_ptr,
+                            public_key_size,
+                        )
+                    },
+                ),
+            );
+
             imports
         };
 
@@ -652,6 +846,10 @@ where
             let instance = Instance::new(&mut store, &module, &imports)
                 .map_err(|error| WasmPreparationError::Instantiation(error.to_string()))?;
 
+            // Compiled modules are shared between executions, so the gas limit isn't compiled into
+            // the module but set on each instance.
+            metering::set_remaining_points(&mut store, &instance, config.gas_limit());
+
             // We don't necessarily need atomic counter. Arc's purpose is to be able to retrieve a
             // Weak reference to the instance to be able to invoke recursive calls to the wasm
             // itself from within a host function implementation.
@@ -749,6 +947,7 @@ where
             callee: data.context.callee,
             config: data.context.config,
             storage_costs: data.context.storage_costs,
+            message_limits: data.context.message_limits,
             transferred_value: data.context.transferred_value,
             tracking_copy: data.context.tracking_copy.fork2(),
             executor: data.context.executor.clone(),
@@ -757,6 +956,7 @@ where
             chain_name: data.context.chain_name.clone(),
             input: data.context.input.clone(),
             block_time: data.context.block_time,
+            execution_tracer: data.context.execution_tracer.clone(),
         }
     }
 }
@@ -620,6 +620,10 @@ use wasmer_middlewares::Metering;
 // 1 // useful for debugging how many instructions were executed
 // }
 
+/// Version of the metering compiled into modules, which must be changed whenever the cost function
+/// changes, so that modules compiled with another cost function are not loaded from disk.
+pub(crate) const METERING_VERSION: u32 = 1;
+
 pub(crate) fn make_wasmer_metering_middleware(initial_limit: u64) -> Arc<dyn ModuleMiddleware> {
     Arc::new(Metering::new(initial_limit, move |_operator| {
         // cost_function(opcode_costs, operator)
@@ -0,0 +1,356 @@
+//! Cache of compiled Wasm modules.
+//!
+//! Compiling a module is by far the most expensive part of preparing a Wasm instance, so compiled
+//! modules are kept in an LRU cache keyed by the hash of their byte code and of the configuration
+//! they are compiled with.  Optionally, compiled modules are also serialized as artifacts into a
+//! directory on disk, so they survive restarts.
+//!
+//! An artifact is native code, so it is only loaded if its header matches the compilation
+//! configuration of the running node (the wasmer version, the compiler, the target and the
+//! metering) and if it passes an integrity check.
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use casper_executor_wasm_interface::WasmPreparationError;
+use casper_types::Digest;
+use linked_hash_map::LinkedHashMap;
+use parking_lot::Mutex;
+use tracing::warn;
+use wasmer::{CompilerConfig, Engine, Module, Target};
+use wasmer_compiler_singlepass::Singlepass;
+
+use crate::metering_middleware::{make_wasmer_metering_middleware, METERING_VERSION};
+
+/// Default number of compiled modules kept in memory.
+pub const DEFAULT_MODULE_CACHE_CAPACITY: usize = 64;
+
+/// Extension of the compiled module artifacts stored on disk.
+const ARTIFACT_EXTENSION: &str = "wasmer";
+
+/// Magic bytes starting the compiled module artifacts stored on disk.
+const ARTIFACT_MAGIC: &[u8; 8] = b"CSPRWASM";
+
+/// Length of the header of the compiled module artifacts stored on disk: the magic bytes, the hash
+/// of the compilation configuration and the hash of the serialized module.
+const ARTIFACT_HEADER_LENGTH: usize = ARTIFACT_MAGIC.len() + 2 * Digest::LENGTH;
+
+/// Statistics of a module cache.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleCacheStats {
+    /// Number of modules found in memory.
+    pub hits: u64,
+    /// Number of modules not found in memory, which had to be loaded from disk or compiled.
+    pub misses: u64,
+    /// Number of modules currently held in memory.
+    pub size: usize,
+}
+
+/// A compiled module along with the engine it was compiled with.
+///
+/// A module may only be instantiated in a store backed by the engine which compiled it.
+#[derive(Clone)]
+pub(crate) struct CompiledModule {
+    pub(crate) engine: Engine,
+    pub(crate) module: Module,
+}
+
+/// An LRU cache of compiled modules, keyed by the hash of their byte code and of the compilation
+/// configuration.
+pub(crate) struct ModuleCache {
+    capacity: usize,
+    artifacts_dir: Option<PathBuf>,
+    /// The hash of the configuration modules are compiled with.
+    config_hash: Digest,
+    modules: Mutex<LinkedHashMap<Digest, CompiledModule>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ModuleCache {
+    /// Creates a cache holding up to `capacity` modules in memory, and storing their artifacts in
+    /// `artifacts_dir` if given.
+    ///
+    /// A `capacity` of zero disables in-memory caching.
+    pub(crate) fn new(capacity: usize, artifacts_dir: Option<PathBuf>) -> Self {
+        ModuleCache {
+            capacity,
+            artifacts_dir,
+            config_hash: Digest::hash(compilation_config()),
+            modules: Mutex::new(LinkedHashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the compiled module of the given byte code, compiling it if necessary.
+    pub(crate) fn get_or_compile(
+        &self,
+        wasm_bytes: &[u8],
+    ) -> Result<CompiledModule, WasmPreparationError> {
+        let cache_key = Digest::hash_pair(Digest::hash(wasm_bytes), self.config_hash);
+        if let Some(compiled_module) = self.modules.lock().get_refresh(&cache_key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(compiled_module.clone());
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        let compiled_module = match self.load_artifact(&cache_key) {
+            Some(compiled_module) => compiled_module,
+            None => {
+                let compiled_module = compile(wasm_bytes)?;
+                self.store_artifact(&cache_key, &compiled_module);
+                compiled_module
+            }
+        };
+
+        if self.capacity > 0 {
+            let mut modules = self.modules.lock();
+            modules.insert(cache_key, compiled_module.clone());
+            while modules.len() > self.capacity {
+                modules.pop_front();
+            }
+        }
+        Ok(compiled_module)
+    }
+
+    /// Returns the statistics of the cache.
+    pub(crate) fn stats(&self) -> ModuleCacheStats {
+        ModuleCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            size: self.modules.lock().len(),
+        }
+    }
+
+    fn artifact_path(&self, cache_key: &Digest) -> Option<PathBuf> {
+        let artifacts_dir = self.artifacts_dir.as_ref()?;
+        Some(artifacts_dir.join(format!("{:x}.{}", cache_key, ARTIFACT_EXTENSION)))
+    }
+
+    fn load_artifact(&self, cache_key: &Digest) -> Option<CompiledModule> {
+        let path = self.artifact_path(cache_key)?;
+        let artifact = fs::read(&path).ok()?;
+        let serialized_module = match self.check_artifact(&artifact) {
+            Ok(serialized_module) => serialized_module,
+            Err(error) => {
+                warn!(path = %path.display(), error, "ignoring compiled wasm module");
+                return None;
+            }
+        };
+        let engine = new_engine();
+        // SAFETY: the artifact was written by `store_artifact` for the same compilation
+        // configuration, and wasn't altered since, as checked above.  `deserialize` additionally
+        // validates the serialized module.
+        match unsafe { Module::deserialize(&engine, serialized_module) } {
+            Ok(module) => Some(CompiledModule { engine, module }),
+            Err(error) => {
+                warn!(path = %path.display(), %error, "failed to load compiled wasm module");
+                None
+            }
+        }
+    }
+
+    /// Checks the header of an artifact, returning the serialized module it holds.
+    fn check_artifact<'a>(&self, artifact: &'a [u8]) -> Result<&'a [u8], &'static str> {
+        if artifact.len() < ARTIFACT_HEADER_LENGTH || !artifact.starts_with(ARTIFACT_MAGIC) {
+            return Err("invalid header");
+        }
+        let (config_hash, rest) = artifact[ARTIFACT_MAGIC.len()..].split_at(Digest::LENGTH);
+        let (module_hash, serialized_module) = rest.split_at(Digest::LENGTH);
+        if config_hash != self.config_hash.value() {
+            return Err("compiled with another configuration");
+        }
+        if module_hash != Digest::hash(serialized_module).value() {
+            return Err("integrity check failed");
+        }
+        Ok(serialized_module)
+    }
+
+    fn store_artifact(&self, cache_key: &Digest, compiled_module: &CompiledModule) {
+        let Some(path) = self.artifact_path(cache_key) else {
+            return;
+        };
+        let result = compiled_module
+            .module
+            .serialize()
+            .map_err(|error| error.to_string())
+            .and_then(|serialized_module| {
+                let mut artifact =
+                    Vec::with_capacity(ARTIFACT_HEADER_LENGTH + serialized_module.len());
+                artifact.extend_from_slice(ARTIFACT_MAGIC);
+                artifact.extend_from_slice(&self.config_hash.value());
+                artifact.extend_from_slice(&Digest::hash(&serialized_module).value());
+                artifact.extend_from_slice(&serialized_module);
+                // Write to a temporary file first, so a partially written artifact is never
+                // loaded.
+                let temp_path = path.with_extension("tmp");
+                fs::create_dir_all(path.parent().unwrap_or(&path))
+                    .and_then(|_| fs::write(&temp_path, &artifact))
+                    .and_then(|_| fs::rename(&temp_path, &path))
+                    .map_err(|error| error.to_string())
+            });
+        if let Err(error) = result {
+            warn!(path = %path.display(), %error, "failed to store compiled wasm module");
+        }
+    }
+}
+
+/// Returns a description of everything a compiled module depends on besides its byte code.
+fn compilation_config() -> String {
+    let target = Target::default();
+    format!(
+        "wasmer {}; compiler singlepass; target {} {:?}; metering version {}",
+        wasmer::VERSION,
+        target.triple(),
+        target.cpu_features(),
+        METERING_VERSION
+    )
+}
+
+/// Creates a new engine for compiling a single module.
+///
+/// The metering middleware can only be used for a single module, hence a new engine is created for
+/// every compilation.  The initial gas limit compiled into the module is zero; the actual limit is
+/// set on every instance.
+fn new_engine() -> Engine {
+    let mut singlepass_compiler = Singlepass::new();
+    singlepass_compiler.push_middleware(make_wasmer_metering_middleware(0));
+    Engine::from(singlepass_compiler)
+}
+
+fn compile(wasm_bytes: &[u8]) -> Result<CompiledModule, WasmPreparationError> {
+    let engine = new_engine();
+    let module = Module::new(&engine, wasm_bytes)
+        .map_err(|error| WasmPreparationError::Compile(error.to_string()))?;
+    Ok(CompiledModule { engine, module })
+}
+
+#[cfg(test)]
+mod tests {
+    use wasmer_middlewares::metering;
+
+    use super::*;
+
+    /// A module exporting an empty `call` function.
+    const WASM: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+        0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section
+        0x03, 0x02, 0x01, 0x00, // function section
+        0x07, 0x08, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x00, // export section
+        0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b, // code section
+    ];
+    /// An empty module.
+    const EMPTY_WASM: &[u8] = &[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+    #[test]
+    fn should_cache_compiled_modules() {
+        let cache = ModuleCache::new(1, None);
+        cache.get_or_compile(WASM).unwrap();
+        cache.get_or_compile(WASM).unwrap();
+        assert_eq!(
+            cache.stats(),
+            ModuleCacheStats {
+                hits: 1,
+                misses: 1,
+                size: 1
+            }
+        );
+
+        // Compiling another module evicts the least recently used one.
+        cache.get_or_compile(EMPTY_WASM).unwrap();
+        cache.get_or_compile(WASM).unwrap();
+        assert_eq!(cache.stats().misses, 3);
+        assert_eq!(cache.stats().size, 1);
+    }
+
+    #[test]
+    fn should_load_artifacts_from_disk() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let cache = ModuleCache::new(1, Some(tempdir.path().to_path_buf()));
+        cache.get_or_compile(WASM).unwrap();
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+
+        let cache = ModuleCache::new(1, Some(tempdir.path().to_path_buf()));
+        let compiled_module = cache.get_or_compile(WASM).unwrap();
+        assert!(compiled_module
+            .module
+            .exports()
+            .any(|export| export.name() == "call"));
+    }
+
+    #[test]
+    fn should_not_load_altered_artifacts() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let cache = ModuleCache::new(1, Some(tempdir.path().to_path_buf()));
+        cache.get_or_compile(WASM).unwrap();
+        let cache_key = Digest::hash_pair(Digest::hash(WASM), cache.config_hash);
+        let path = cache.artifact_path(&cache_key).unwrap();
+        let artifact = fs::read(&path).unwrap();
+        assert!(cache.load_artifact(&cache_key).is_some());
+
+        // An artifact whose serialized module was altered fails the integrity check.
+        let mut altered_artifact = artifact.clone();
+        *altered_artifact.last_mut().unwrap() ^= 1;
+        fs::write(&path, &altered_artifact).unwrap();
+        assert!(cache.load_artifact(&cache_key).is_none());
+
+        // An artifact compiled with another configuration is not loaded.
+        let mut other_config_artifact = artifact;
+        other_config_artifact[ARTIFACT_MAGIC.len()] ^= 1;
+        fs::write(&path, &other_config_artifact).unwrap();
+        assert!(cache.load_artifact(&cache_key).is_none());
+
+        // The module is compiled again and its artifact replaced.
+        let cache = ModuleCache::new(1, Some(tempdir.path().to_path_buf()));
+        cache.get_or_compile(WASM).unwrap();
+        assert!(cache.load_artifact(&cache_key).is_some());
+    }
+
+    #[test]
+    fn should_not_share_artifacts_between_compilation_configs() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let cache = ModuleCache::new(1, Some(tempdir.path().to_path_buf()));
+        cache.get_or_compile(WASM).unwrap();
+
+        let other_config_cache = ModuleCache {
+            config_hash: Digest::hash("another configuration"),
+            ..ModuleCache::new(1, Some(tempdir.path().to_path_buf()))
+        };
+        let cache_key = Digest::hash_pair(Digest::hash(WASM), other_config_cache.config_hash);
+        assert!(other_config_cache.load_artifact(&cache_key).is_none());
+        other_config_cache.get_or_compile(WASM).unwrap();
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn should_meter_instances_of_cached_modules() {
+        let cache = ModuleCache::new(1, None);
+        let CompiledModule { engine, module } = cache.get_or_compile(WASM).unwrap();
+        let mut store = wasmer::Store::new(engine);
+        let instance = wasmer::Instance::new(&mut store, &module, &wasmer::imports! {}).unwrap();
+        metering::set_remaining_points(&mut store, &instance, 100);
+        let call = instance
+            .exports
+            .get_typed_function::<(), ()>(&store, "call")
+            .unwrap();
+        call.call(&mut store).unwrap();
+        assert!(matches!(
+            metering::get_remaining_points(&mut store, &instance),
+            metering::MeteringPoints::Remaining(remaining) if remaining < 100
+        ));
+    }
+
+    #[test]
+    fn should_not_cache_invalid_modules() {
+        let cache = ModuleCache::new(1, None);
+        assert!(matches!(
+            cache.get_or_compile(b"invalid"),
+            Err(WasmPreparationError::Compile(_))
+        ));
+        assert_eq!(cache.stats().size, 0);
+    }
+}
@@ -0,0 +1,26 @@
+//! # Casper signing daemon
+//!
+//! Reference implementation of a remote signer for the Casper node, holding the validator's secret
+//! key in a separate process. Run with `--help` to see available command-line arguments.
+
+use std::path::PathBuf;
+
+use structopt::StructOpt;
+
+use casper_node::signer;
+
+/// Signing daemon holding the secret key of a Casper validator.
+///
+/// Serves signing requests of a node configured with `consensus.remote_signer`, refusing to sign
+/// messages conflicting with previously signed ones.
+#[derive(Debug, StructOpt)]
+struct Args {
+    /// Path to configuration file.
+    config: PathBuf,
+}
+
+/// Main function.
+fn main() -> anyhow::Result<()> {
+    let args = Args::from_args();
+    signer::run_daemon(&args.config)
+}
@@ -0,0 +1,436 @@
+//! Offline replay of stored blocks.
+//!
+//! Re-executes a range of blocks from an existing storage directory, each one starting from the
+//! state root hash of its stored parent, and compares the outcome with what was stored when the
+//! block was originally executed.  This is used to check that a new version of the node or of the
+//! execution engine reproduces the history of the chain.
+
+use std::{collections::BTreeMap, ops::RangeInclusive, sync::Arc};
+
+use prometheus::Registry;
+use serde::Serialize;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use casper_types::{
+    execution::{Effects, ExecutionResult, TransformKindV2},
+    Block, BlockHash, BlockHeader, Chainspec, Digest, EraId, Key, ProtocolVersion, TransactionHash,
+};
+
+use crate::{
+    components::{
+        contract_runtime::{
+            compute_execution_results_checksum, BlockAndExecutionArtifacts, BlockExecutionError,
+            ConfigError, ContractRuntime, ExecutionPreState,
+        },
+        storage::{FatalStorageError, Storage},
+    },
+    reactor::main_reactor::Config,
+    utils::WithDir,
+};
+
+/// Error returned as a result of replaying blocks.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// The range of blocks to replay is empty, or starts at genesis.
+    #[error("invalid range of blocks to replay: {from}..={to}")]
+    InvalidRange {
+        /// The height of the first block to replay.
+        from: u64,
+        /// The height of the last block to replay.
+        to: u64,
+    },
+
+    /// Error opening the storage.
+    #[error("error opening storage: {0}")]
+    Storage(#[from] FatalStorageError),
+
+    /// Error opening the global state.
+    #[error("error opening global state: {0}")]
+    ContractRuntime(#[from] ConfigError),
+
+    /// A block needed for the replay is not in storage.
+    #[error("block at height {0} not found in storage")]
+    MissingBlock(u64),
+
+    /// A block to replay is not a version 2 block, or some of its transactions are not in
+    /// storage.
+    #[error("block {0} cannot be executed from storage")]
+    NotExecutable(BlockHash),
+
+    /// A block to replay was executed with a different protocol version.
+    #[error(
+        "block {block_hash} was executed with protocol version {block_protocol_version}, but the \
+        chainspec is for protocol version {chainspec_protocol_version}"
+    )]
+    ProtocolVersionMismatch {
+        /// The hash of the block.
+        block_hash: BlockHash,
+        /// The protocol version of the block.
+        block_protocol_version: ProtocolVersion,
+        /// The protocol version of the chainspec.
+        chainspec_protocol_version: ProtocolVersion,
+    },
+
+    /// Error executing a block.
+    #[error("error executing block {block_hash}: {error}")]
+    Execution {
+        /// The hash of the block.
+        block_hash: BlockHash,
+        /// The execution error.
+        error: BlockExecutionError,
+    },
+}
+
+/// The outcome of replaying a range of blocks.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct ReplayReport {
+    /// The number of blocks replayed.
+    replayed_block_count: u64,
+    /// The blocks whose replay diverged from what is stored.
+    divergences: Vec<BlockDivergence>,
+}
+
+impl ReplayReport {
+    /// Returns `true` if the replay of any block diverged from what is stored.
+    pub(crate) fn has_divergences(&self) -> bool {
+        !self.divergences.is_empty()
+    }
+
+    /// Returns the number of blocks replayed.
+    pub(crate) fn replayed_block_count(&self) -> u64 {
+        self.replayed_block_count
+    }
+
+    /// Returns the number of blocks whose replay diverged from what is stored.
+    pub(crate) fn divergent_block_count(&self) -> usize {
+        self.divergences.len()
+    }
+}
+
+/// A value which differs between what is stored and what the replay produced.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Mismatch<T> {
+    /// The stored value.
+    stored: T,
+    /// The value produced by the replay.
+    replayed: T,
+}
+
+impl<T: PartialEq> Mismatch<T> {
+    fn check(stored: T, replayed: T) -> Option<Self> {
+        (stored != replayed).then_some(Mismatch { stored, replayed })
+    }
+}
+
+/// The differences between a stored block and its replay.
+#[derive(Debug, Serialize)]
+pub(crate) struct BlockDivergence {
+    /// The height of the block.
+    block_height: u64,
+    /// The hash of the stored block.
+    block_hash: BlockHash,
+    /// The post-state root hash, if it differs.
+    state_root_hash: Option<Mismatch<Digest>>,
+    /// The checksum of the execution results of the block, if it differs.
+    execution_results_checksum: Option<Mismatch<Digest>>,
+    /// The transactions whose execution differs.
+    transactions: Vec<TransactionDivergence>,
+}
+
+/// The differences between the stored execution of a transaction and its replay.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) enum TransactionDivergence {
+    /// There is no stored version 2 execution result for the transaction.
+    MissingExecutionResult {
+        /// The hash of the transaction.
+        transaction_hash: TransactionHash,
+    },
+    /// The effects of the transaction differ.
+    Effects {
+        /// The hash of the transaction.
+        transaction_hash: TransactionHash,
+        /// The keys whose transforms differ.
+        keys: Vec<KeyDivergence>,
+    },
+}
+
+/// The transforms applied to a key which differ between what is stored and what the replay
+/// produced, in the order they were applied.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct KeyDivergence {
+    /// The key.
+    key: Key,
+    /// The stored transforms.
+    stored: Vec<TransformKindV2>,
+    /// The transforms produced by the replay.
+    replayed: Vec<TransformKindV2>,
+}
+
+/// Replays the blocks of the given range of heights, and reports any divergence from what is
+/// stored.
+///
+/// The blocks are executed against a scratch copy of the global state, so no tries are written to
+/// the node's trie store.
+pub(crate) fn replay_blocks(
+    config: WithDir<Config>,
+    chainspec: Arc<Chainspec>,
+    heights: RangeInclusive<u64>,
+) -> Result<ReplayReport, Error> {
+    let (from, to) = (*heights.start(), *heights.end());
+    if from == 0 || from > to {
+        return Err(Error::InvalidRange { from, to });
+    }
+
+    let root_dir = config.dir();
+    let config = config.value();
+    let storage_config = WithDir::new(root_dir, config.storage.clone());
+    let activation_era = chainspec.protocol_config.activation_point.era_id();
+    let storage = Storage::new(
+        &storage_config,
+        None,
+        chainspec.protocol_version(),
+        activation_era,
+        &chainspec.network_config.name,
+        chainspec.transaction_config.max_ttl.into(),
+        chainspec.core_config.recent_era_count(),
+        None,
+        false,
+        chainspec.transaction_config.clone(),
+    )?;
+    let contract_runtime = ContractRuntime::new(
+        storage.root_path(),
+        &config.contract_runtime,
+        Arc::clone(&chainspec),
+        &Registry::new(),
+    )?;
+
+    let key_block_height_for_activation_point =
+        match switch_block_header(&storage, activation_era.predecessor().unwrap_or_default())? {
+            Some(key_block_header) => key_block_header.height(),
+            None => {
+                warn!("key block height for current activation point unknown");
+                0
+            }
+        };
+
+    let mut report = ReplayReport::default();
+    let mut parent_header = read_block(&storage, from - 1)?.take_header();
+    for height in heights {
+        let block = read_block(&storage, height)?;
+        let block_hash = *block.hash();
+        if block.protocol_version() != chainspec.protocol_version() {
+            return Err(Error::ProtocolVersionMismatch {
+                block_hash,
+                block_protocol_version: block.protocol_version(),
+                chainspec_protocol_version: chainspec.protocol_version(),
+            });
+        }
+        let executable_block = storage
+            .make_executable_block(&block_hash)?
+            .ok_or(Error::NotExecutable(block_hash))?;
+        let last_switch_block_hash = match block.era_id().predecessor() {
+            Some(previous_era) => {
+                switch_block_header(&storage, previous_era)?.map(|header| header.block_hash())
+            }
+            None => None,
+        };
+        let execution_pre_state = ExecutionPreState::new(
+            height,
+            *parent_header.state_root_hash(),
+            parent_header.block_hash(),
+            *parent_header.accumulated_seed(),
+        );
+
+        info!(%block_hash, height, "replaying block");
+        let replayed = contract_runtime
+            .replay_block(
+                execution_pre_state,
+                executable_block,
+                key_block_height_for_activation_point,
+                last_switch_block_hash,
+            )
+            .map_err(|error| Error::Execution { block_hash, error })?;
+
+        if let Some(divergence) = compare(&storage, &block, &replayed)? {
+            warn!(%block_hash, height, "replay diverged from stored block");
+            report.divergences.push(divergence);
+        }
+        report.replayed_block_count += 1;
+        parent_header = block.take_header();
+    }
+
+    Ok(report)
+}
+
+fn read_block(storage: &Storage, height: u64) -> Result<Block, Error> {
+    storage
+        .get_block_by_height(height)?
+        .ok_or(Error::MissingBlock(height))
+}
+
+fn switch_block_header(storage: &Storage, era_id: EraId) -> Result<Option<BlockHeader>, Error> {
+    Ok(storage
+        .get_switch_block_by_era_id(&era_id)?
+        .map(Block::take_header))
+}
+
+/// Compares a stored block with its replay, returning `None` if they match.
+fn compare(
+    storage: &Storage,
+    block: &Block,
+    replayed: &BlockAndExecutionArtifacts,
+) -> Result<Option<BlockDivergence>, Error> {
+    let state_root_hash =
+        Mismatch::check(*block.state_root_hash(), *replayed.block.state_root_hash());
+
+    let mut stored_results = Vec::with_capacity(replayed.execution_artifacts.len());
+    let mut transactions = vec![];
+    for artifact in &replayed.execution_artifacts {
+        let transaction_hash = artifact.transaction_hash;
+        let stored_result = storage.get_execution_result(&transaction_hash)?;
+        let (Some(ExecutionResult::V2(stored)), ExecutionResult::V2(replayed)) =
+            (&stored_result, &artifact.execution_result)
+        else {
+            transactions.push(TransactionDivergence::MissingExecutionResult { transaction_hash });
+            continue;
+        };
+        let keys = diff_effects(&stored.effects, &replayed.effects);
+        if !keys.is_empty() {
+            transactions.push(TransactionDivergence::Effects {
+                transaction_hash,
+                keys,
+            });
+        }
+        stored_results.extend(stored_result);
+    }
+
+    // The checksums can only be compared if all the stored execution results are available.
+    let execution_results_checksum = if stored_results.len() == replayed.execution_artifacts.len() {
+        let stored = compute_execution_results_checksum(stored_results.iter());
+        let replayed = compute_execution_results_checksum(
+            replayed
+                .execution_artifacts
+                .iter()
+                .map(|artifact| &artifact.execution_result),
+        );
+        match (stored, replayed) {
+            (Ok(stored), Ok(replayed)) => Mismatch::check(stored, replayed),
+            (Err(error), _) | (_, Err(error)) => {
+                warn!(%error, "failed to compute execution results checksum");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    if state_root_hash.is_none() && execution_results_checksum.is_none() && transactions.is_empty()
+    {
+        return Ok(None);
+    }
+    Ok(Some(BlockDivergence {
+        block_height: block.height(),
+        block_hash: *block.hash(),
+        state_root_hash,
+        execution_results_checksum,
+        transactions,
+    }))
+}
+
+/// Returns the keys whose transforms differ between the two sets of effects.
+fn diff_effects(stored: &Effects, replayed: &Effects) -> Vec<KeyDivergence> {
+    let mut transforms: BTreeMap<Key, (Vec<TransformKindV2>, Vec<TransformKindV2>)> =
+        BTreeMap::new();
+    for transform in stored.transforms() {
+        transforms
+            .entry(*transform.key())
+            .or_default()
+            .0
+            .push(transform.kind().clone());
+    }
+    for transform in replayed.transforms() {
+        transforms
+            .entry(*transform.key())
+            .or_default()
+            .1
+            .push(transform.kind().clone());
+    }
+    transforms
+        .into_iter()
+        .filter(|(_, (stored, replayed))| stored != replayed)
+        .map(|(key, (stored, replayed))| KeyDivergence {
+            key,
+            stored,
+            replayed,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use casper_types::{execution::TransformV2, testing::TestRng, CLValue, StoredValue, U512};
+
+    use super::*;
+
+    #[test]
+    fn should_diff_effects_by_key() {
+        let rng = &mut TestRng::new();
+        let same_key = Key::URef(rng.gen());
+        let changed_key = Key::URef(rng.gen());
+        let missing_key = Key::URef(rng.gen());
+
+        let mut stored = Effects::new();
+        stored.push(TransformV2::new(same_key, TransformKindV2::AddUInt64(1)));
+        stored.push(TransformV2::new(
+            changed_key,
+            TransformKindV2::AddUInt512(U512::from(1)),
+        ));
+        stored.push(TransformV2::new(missing_key, TransformKindV2::Identity));
+
+        let mut replayed = Effects::new();
+        replayed.push(TransformV2::new(same_key, TransformKindV2::AddUInt64(1)));
+        replayed.push(TransformV2::new(
+            changed_key,
+            TransformKindV2::AddUInt512(U512::from(2)),
+        ));
+
+        let mut keys = diff_effects(&stored, &replayed);
+        keys.sort_by_key(|divergence| divergence.key);
+        let mut expected = vec![
+            KeyDivergence {
+                key: changed_key,
+                stored: vec![TransformKindV2::AddUInt512(U512::from(1))],
+                replayed: vec![TransformKindV2::AddUInt512(U512::from(2))],
+            },
+            KeyDivergence {
+                key: missing_key,
+                stored: vec![TransformKindV2::Identity],
+                replayed: vec![],
+            },
+        ];
+        expected.sort_by_key(|divergence| divergence.key);
+        assert_eq!(keys, expected);
+
+        assert!(diff_effects(&stored, &stored).is_empty());
+    }
+
+    #[test]
+    fn should_diff_order_of_transforms() {
+        let rng = &mut TestRng::new();
+        let key = Key::URef(rng.gen());
+        let write = TransformKindV2::Write(StoredValue::CLValue(CLValue::from_t(1u8).unwrap()));
+
+        let mut stored = Effects::new();
+        stored.push(TransformV2::new(key, write.clone()));
+        stored.push(TransformV2::new(key, TransformKindV2::Identity));
+
+        let mut replayed = Effects::new();
+        replayed.push(TransformV2::new(key, TransformKindV2::Identity));
+        replayed.push(TransformV2::new(key, write));
+
+        assert_eq!(diff_effects(&stored, &replayed).len(), 1);
+    }
+}
@@ -20,7 +20,7 @@ use structopt::StructOpt;
 use toml::{value::Table, Value};
 use tracing::{error, info};
 
-use casper_types::{Chainspec, ChainspecRawBytes};
+use casper_types::{BlockHash, Chainspec, ChainspecRawBytes, Digest};
 
 use crate::{
     components::network::Identity as NetworkIdentity,
@@ -88,6 +88,86 @@ pub enum Cli {
         /// Path to configuration file.
         config: PathBuf,
     },
+    /// Re-execute a range of stored blocks and report any divergence from the stored results.
+    ///
+    /// Each block is executed from the state root hash of its stored parent, and the resulting
+    /// state root hash, execution results checksum and effects of each transaction are compared
+    /// with the stored ones.  The blocks are executed against a scratch copy of the global state,
+    /// so the replay never writes to the node's trie store.
+    ReplayBlocks {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Height of the first block to replay.
+        #[structopt(long)]
+        from: u64,
+        /// Height of the last block to replay.
+        #[structopt(long)]
+        to: u64,
+        /// Path of the file to write the report to, in JSON.  Written to stdout if not given.
+        #[structopt(long)]
+        output: Option<PathBuf>,
+    },
+    /// Export the global state of a stored block to a snapshot file.
+    ///
+    /// The snapshot can be imported by a new node with `import-snapshot`, rather than fetching the
+    /// global state from its peers.
+    ExportSnapshot {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Height of the block whose global state is exported.  Defaults to the highest complete
+        /// block.
+        #[structopt(long)]
+        height: Option<u64>,
+        /// Path of the snapshot file to write.
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Import a snapshot file of the global state of the trusted block.
+    ///
+    /// The snapshot is only imported if it is for the trusted block, in which case the node can
+    /// then be started with the same trusted hash, and will not need to fetch the global state of
+    /// the trusted block from its peers.
+    ImportSnapshot {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Path of the snapshot file to import.
+        #[structopt(long)]
+        snapshot: PathBuf,
+        /// Hash of the trusted block, hex-encoded.  Defaults to the trusted hash of the
+        /// configuration file.
+        #[structopt(long)]
+        trusted_hash: Option<String>,
+    },
+    /// Check the integrity of the storage of a node which is not running.
+    ///
+    /// Every complete block is checked to have its body, transactions, approvals hashes and
+    /// execution results, the indices to agree with the stored blocks, and the global state under
+    /// the state root of every complete block to be complete.
+    CheckStorage {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Rebuild the indices and drop the damaged blocks from the complete blocks, so that the
+        /// node acquires them again once started.
+        #[structopt(long)]
+        repair: bool,
+        /// Path of the file to write the report to, in JSON.  Written to stdout if not given.
+        #[structopt(long)]
+        output: Option<PathBuf>,
+    },
+    /// Delete the global state which isn't needed anymore from the storage of a node which is
+    /// not running.
+    ///
+    /// Only the global state of the most recent complete blocks is retained.  A garbage collection
+    /// left in progress by the node is resumed rather than restarted, provided garbage collection
+    /// is enabled in the configuration file.
+    CollectGarbage {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// The number of most recent blocks whose global state is retained.  Defaults to
+        /// `contract_runtime.gc_retained_state_roots` of the configuration file.
+        #[structopt(long)]
+        retained_state_roots: Option<u64>,
+    },
 }
 
 #[derive(Debug)]
@@ -249,6 +329,147 @@ impl Cli {
                 )?;
                 Ok(ExitCode::Success as i32)
             }
+            Cli::ReplayBlocks {
+                config,
+                from,
+                to,
+                output,
+            } => {
+                let reactor_config = Self::init(&config, vec![])?;
+                let (chainspec, _) =
+                    <(Chainspec, ChainspecRawBytes)>::from_path(reactor_config.dir())?;
+
+                info!(build_version = %crate::VERSION_STRING.as_str(), from, to, "replaying blocks");
+                let report = crate::block_replay::replay_blocks(
+                    reactor_config,
+                    Arc::new(chainspec),
+                    from..=to,
+                )?;
+                let encoded_report = serde_json::to_string_pretty(&report)?;
+                match output {
+                    Some(path) => fs::write(&path, encoded_report)
+                        .context("could not write replay report")
+                        .with_context(|| path.display().to_string())?,
+                    None => println!("{}", encoded_report),
+                }
+
+                if report.has_divergences() {
+                    bail!(
+                        "replay of {} out of {} blocks diverged from storage",
+                        report.divergent_block_count(),
+                        report.replayed_block_count()
+                    );
+                }
+                info!(
+                    replayed_block_count = report.replayed_block_count(),
+                    "replayed blocks match storage"
+                );
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ExportSnapshot {
+                config,
+                height,
+                output,
+            } => {
+                let reactor_config = Self::init(&config, vec![])?;
+                let (chainspec, _) =
+                    <(Chainspec, ChainspecRawBytes)>::from_path(reactor_config.dir())?;
+
+                info!(build_version = %crate::VERSION_STRING.as_str(), ?height, "exporting snapshot");
+                let stats = crate::global_state_snapshot::export_snapshot(
+                    reactor_config,
+                    Arc::new(chainspec),
+                    height,
+                    &output,
+                )?;
+                info!(tries = stats.tries, chunks = stats.chunks, output = %output.display(), "exported snapshot");
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ImportSnapshot {
+                config,
+                snapshot,
+                trusted_hash,
+            } => {
+                let trusted_hash = trusted_hash
+                    .map(|hash| Digest::from_hex(hash).map(BlockHash::new))
+                    .transpose()
+                    .map_err(|error| anyhow::anyhow!("invalid trusted hash: {}", error))?;
+                let reactor_config = Self::init(&config, vec![])?;
+                let (chainspec, _) =
+                    <(Chainspec, ChainspecRawBytes)>::from_path(reactor_config.dir())?;
+
+                info!(build_version = %crate::VERSION_STRING.as_str(), snapshot = %snapshot.display(), "importing snapshot");
+                let stats = crate::global_state_snapshot::import_snapshot(
+                    reactor_config,
+                    Arc::new(chainspec),
+                    &snapshot,
+                    trusted_hash,
+                )?;
+                info!(
+                    tries = stats.tries,
+                    chunks = stats.chunks,
+                    "imported snapshot"
+                );
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::CheckStorage {
+                config,
+                repair,
+                output,
+            } => {
+                let reactor_config = Self::init(&config, vec![])?;
+                let (chainspec, _) =
+                    <(Chainspec, ChainspecRawBytes)>::from_path(reactor_config.dir())?;
+
+                info!(build_version = %crate::VERSION_STRING.as_str(), repair, "checking storage");
+                let report = crate::storage_check::check_storage(
+                    reactor_config,
+                    Arc::new(chainspec),
+                    repair,
+                )?;
+                let encoded_report = serde_json::to_string_pretty(&report)?;
+                match output {
+                    Some(path) => fs::write(&path, encoded_report)
+                        .context("could not write storage report")
+                        .with_context(|| path.display().to_string())?,
+                    None => println!("{}", encoded_report),
+                }
+
+                if report.has_issues() && !report.repaired() {
+                    bail!(
+                        "found {} issues in storage, run with --repair to repair them",
+                        report.issue_count()
+                    );
+                }
+                info!(
+                    checked_block_count = report.checked_block_count(),
+                    issue_count = report.issue_count(),
+                    repaired = report.repaired(),
+                    "checked storage"
+                );
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::CollectGarbage {
+                config,
+                retained_state_roots,
+            } => {
+                let reactor_config = Self::init(&config, vec![])?;
+                let (chainspec, _) =
+                    <(Chainspec, ChainspecRawBytes)>::from_path(reactor_config.dir())?;
+
+                info!(build_version = %crate::VERSION_STRING.as_str(), "collecting trie store garbage");
+                let progress = crate::global_state_gc::collect_garbage(
+                    reactor_config,
+                    Arc::new(chainspec),
+                    retained_state_roots,
+                )?;
+                info!(
+                    tries_marked = progress.tries_marked,
+                    tries_deleted = progress.tries_deleted,
+                    "collected trie store garbage"
+                );
+                Ok(ExitCode::Success as i32)
+            }
             Cli::ValidateConfig { config } => {
                 info!(build_version = %crate::VERSION_STRING.as_str(), config_file = ?config, "validating config file");
                 match Self::init(&config, vec![]) {
@@ -5,29 +5,32 @@ mod error;
 mod event;
 mod metrics;
 mod rate_limiter;
+mod subscription;
 #[cfg(test)]
 mod tests;
 
 use std::{convert::TryFrom, net::SocketAddr, sync::Arc};
 
 use casper_binary_port::{
-    AccountInformation, AddressableEntityInformation, BalanceResponse, BinaryMessage,
-    BinaryMessageCodec, BinaryResponse, BinaryResponseAndRequest, Command, CommandHeader,
-    CommandTag, ContractInformation, DictionaryItemIdentifier, DictionaryQueryResult,
-    EntityIdentifier, EraIdentifier, ErrorCode, GetRequest, GetTrieFullResult,
-    GlobalStateEntityQualifier, GlobalStateQueryResult, GlobalStateRequest, InformationRequest,
-    InformationRequestTag, KeyPrefix, NodeStatus, PackageIdentifier, PurseIdentifier,
-    ReactorStateName, RecordId, ResponseType, RewardResponse, TransactionWithExecutionInfo,
-    ValueWithProof,
+    AccountInformation, AddressableEntityInformation, BalanceResponse, BatchRequest, BatchResponse,
+    BinaryMessage, BinaryMessageCodec, BinaryResponse, BinaryResponseAndRequest, Command,
+    CommandHeader, CommandTag, ContractInformation, DictionaryItemIdentifier,
+    DictionaryQueryResult, EntityIdentifier, EraIdentifier, ErrorCode, GetRequest,
+    GetTrieFullResult, GlobalStateEntityQualifier, GlobalStateQueryResult, GlobalStateRequest,
+    InformationRequest, InformationRequestTag, InitiatorTransaction, KeyPrefix, NodeStatus,
+    Notification, PackageIdentifier, PurseIdentifier, ReactorStateName, RecordId, ResponseType,
+    RewardResponse, StateDiff, StateDiffEntry, StateDiffRequest, StateOverride,
+    SubscriptionRequest, TransactionWithExecutionInfo, ValueWithProof,
 };
 use casper_storage::{
     data_access_layer::{
         balance::BalanceHandling,
         prefixed_values::{PrefixedValuesRequest, PrefixedValuesResult},
         tagged_values::{TaggedValuesRequest, TaggedValuesResult, TaggedValuesSelection},
-        BalanceIdentifier, BalanceRequest, BalanceResult, ProofHandling, ProofsResult,
-        QueryRequest, QueryResult, SeigniorageRecipientsRequest, SeigniorageRecipientsResult,
-        TrieRequest,
+        BalanceIdentifier, BalanceRequest, BalanceResult, MultiProofRequest, MultiProofResult,
+        ProofHandling, ProofsResult, QueryRequest, QueryResult, SeigniorageRecipientsRequest,
+        SeigniorageRecipientsResult, StateDiffEntry as StorageStateDiffEntry,
+        StateDiffRequest as StorageStateDiffRequest, StateDiffResult, TrieRequest,
     },
     global_state::trie::TrieRaw,
     system::auction,
@@ -51,12 +54,13 @@ use datasize::DataSize;
 use either::Either;
 use futures::{SinkExt, StreamExt};
 use once_cell::sync::OnceCell;
-use rate_limiter::{LimiterResponse, RateLimiter, RateLimiterError};
+use rate_limiter::{batch_weight, LimiterResponse, RateLimiter, RateLimiterError};
+use subscription::Subscription;
 use tokio::{
     join,
     net::{TcpListener, TcpStream},
     select,
-    sync::{Mutex, Notify, OwnedSemaphorePermit, Semaphore},
+    sync::{broadcast, Mutex, Notify, OwnedSemaphorePermit, Semaphore},
 };
 use tokio_util::codec::{Encoder, Framed};
 use tracing::{debug, error, info, trace, warn};
@@ -66,7 +70,7 @@ use futures::{future::BoxFuture, FutureExt};
 
 use self::error::Error;
 use crate::{
-    contract_runtime::SpeculativeExecutionResult,
+    contract_runtime::{GasEstimationResult, SpeculativeExecutionResult},
     effect::{
         requests::{
             AcceptTransactionRequest, BlockSynchronizerRequest, ChainspecRawBytesRequest,
@@ -88,6 +92,9 @@ pub(crate) use event::Event;
 
 const COMPONENT_NAME: &str = "binary_port";
 
+/// The maximum number of transactions returned for a single transactions by initiator request.
+const MAX_INITIATOR_TRANSACTIONS_PER_REQUEST: u32 = 1000;
+
 #[derive(Debug, ThisError)]
 pub(crate) enum BinaryPortInitializationError {
     #[error("could not initialize rate limiter: {0}")]
@@ -128,10 +135,13 @@ pub(crate) struct BinaryPort {
     server_join_handle: OnceCell<tokio::task::JoinHandle<()>>,
     #[data_size(skip)]
     rate_limiter: OnceCell<Arc<Mutex<RateLimiter>>>,
+    #[data_size(skip)]
+    notifications: broadcast::Sender<Arc<Notification>>,
 }
 
 impl BinaryPort {
     pub(crate) fn new(config: Config, chainspec: Arc<Chainspec>, metrics: Metrics) -> Self {
+        let (notifications, _) = broadcast::channel(config.subscription_buffer_size.max(1));
         Self {
             state: ComponentState::Uninitialized,
             connection_limit: Arc::new(Semaphore::new(config.max_connections)),
@@ -142,7 +152,20 @@ impl BinaryPort {
             shutdown_trigger: Arc::new(Notify::new()),
             server_join_handle: OnceCell::new(),
             rate_limiter: OnceCell::new(),
+            notifications,
+        }
+    }
+
+    /// Pushes the notification to the subscribers, if there are any.
+    fn notify<F>(&self, make_notification: F)
+    where
+        F: FnOnce() -> Notification,
+    {
+        if self.notifications.receiver_count() == 0 {
+            return;
         }
+        // Sending only fails if all the subscribers were dropped in the meantime.
+        let _ = self.notifications.send(Arc::new(make_notification()));
     }
 
     /// Returns the binding address.
@@ -159,8 +182,10 @@ struct BinaryRequestTerminationDelayValues {
     get_information: TimeDiff,
     get_state: TimeDiff,
     get_trie: TimeDiff,
+    get_state_diff: TimeDiff,
     accept_transaction: TimeDiff,
     speculative_exec: TimeDiff,
+    subscribe: TimeDiff,
 }
 
 impl BinaryRequestTerminationDelayValues {
@@ -170,18 +195,36 @@ impl BinaryRequestTerminationDelayValues {
             get_information: config.get_information_request_termination_delay,
             get_state: config.get_state_request_termination_delay,
             get_trie: config.get_trie_request_termination_delay,
+            get_state_diff: config.get_state_diff_request_termination_delay,
             accept_transaction: config.accept_transaction_request_termination_delay,
             speculative_exec: config.speculative_exec_request_termination_delay,
+            subscribe: config.subscribe_request_termination_delay,
         }
     }
     fn get_life_termination_delay(&self, request: &Command) -> TimeDiff {
         match request {
-            Command::Get(GetRequest::Record { .. }) => self.get_record,
-            Command::Get(GetRequest::Information { .. }) => self.get_information,
-            Command::Get(GetRequest::State(_)) => self.get_state,
-            Command::Get(GetRequest::Trie { .. }) => self.get_trie,
+            Command::Get(get_req) => self.get_request_termination_delay(get_req),
             Command::TryAcceptTransaction { .. } => self.accept_transaction,
-            Command::TrySpeculativeExec { .. } => self.speculative_exec,
+            Command::TrySpeculativeExec { .. } | Command::EstimateGas { .. } => {
+                self.speculative_exec
+            }
+            Command::Subscribe(_) => self.subscribe,
+            Command::Batch(batch) => batch
+                .requests()
+                .iter()
+                .map(|get_req| self.get_request_termination_delay(get_req))
+                .max()
+                .unwrap_or_default(),
+        }
+    }
+
+    fn get_request_termination_delay(&self, get_req: &GetRequest) -> TimeDiff {
+        match get_req {
+            GetRequest::Record { .. } => self.get_record,
+            GetRequest::Information { .. } => self.get_information,
+            GetRequest::State(_) => self.get_state,
+            GetRequest::Trie { .. } => self.get_trie,
+            GetRequest::StateDiff(_) => self.get_state_diff,
         }
     }
 }
@@ -211,7 +254,12 @@ where
             metrics.binary_port_try_accept_transaction_count.inc();
             try_accept_transaction(effect_builder, transaction, false).await
         }
-        Command::TrySpeculativeExec { transaction } => {
+        Command::TrySpeculativeExec {
+            transaction,
+            state_identifier,
+            state_overrides,
+            trace_execution,
+        } => {
             metrics.binary_port_try_speculative_exec_count.inc();
             if !config.allow_request_speculative_exec {
                 debug!(
@@ -220,15 +268,170 @@ where
                 );
                 return BinaryResponse::new_error(ErrorCode::FunctionDisabled);
             }
-            let response = try_accept_transaction(effect_builder, transaction.clone(), true).await;
-            if !response.is_success() {
-                return response;
+            if state_identifier.is_none() && state_overrides.is_empty() {
+                let response =
+                    try_accept_transaction(effect_builder, transaction.clone(), true).await;
+                if !response.is_success() {
+                    return response;
+                }
+            } else if let Err(error) = transaction.verify() {
+                // The acceptor validates transactions against the latest block state, which would
+                // reject e.g. expired transactions executed against historical state, so only the
+                // transaction itself is verified.
+                debug!(%error, "invalid transaction submitted for speculative execution");
+                return BinaryResponse::new_error(error.into());
             }
-            try_speculative_execution(effect_builder, transaction).await
+            try_speculative_execution(
+                effect_builder,
+                transaction,
+                state_identifier,
+                state_overrides,
+                trace_execution,
+            )
+            .await
+        }
+        Command::EstimateGas {
+            transaction,
+            state_identifier,
+            state_overrides,
+        } => {
+            metrics.binary_port_estimate_gas_count.inc();
+            if !config.allow_request_speculative_exec {
+                debug!(
+                    hash = %transaction.hash(),
+                    "received a request for gas estimation while the feature is disabled"
+                );
+                return BinaryResponse::new_error(ErrorCode::FunctionDisabled);
+            }
+            // The acceptor would check the payment of the transaction, which is what's being
+            // estimated, so only the transaction itself is verified.
+            if let Err(error) = transaction.verify() {
+                debug!(%error, "invalid transaction submitted for gas estimation");
+                return BinaryResponse::new_error(error.into());
+            }
+            estimate_gas(
+                effect_builder,
+                transaction,
+                state_identifier,
+                state_overrides,
+                config.gas_estimate_payment_margin_percent,
+            )
+            .await
         }
         Command::Get(get_req) => {
             handle_get_request(get_req, effect_builder, config, metrics, protocol_version).await
         }
+        // Subscriptions are bound to a connection and handled by the client loop.
+        Command::Subscribe(_) => BinaryResponse::new_error(ErrorCode::UnsupportedRequest),
+        Command::Batch(batch) => {
+            handle_batch_request(batch, effect_builder, config, metrics, protocol_version).await
+        }
+    }
+}
+
+async fn handle_batch_request<REv>(
+    batch: BatchRequest,
+    effect_builder: EffectBuilder<REv>,
+    config: &Config,
+    metrics: &Metrics,
+    protocol_version: ProtocolVersion,
+) -> BinaryResponse
+where
+    REv: From<Event>
+        + From<StorageRequest>
+        + From<NetworkInfoRequest>
+        + From<ReactorInfoRequest>
+        + From<ConsensusRequest>
+        + From<BlockSynchronizerRequest>
+        + From<UpgradeWatcherRequest>
+        + From<ChainspecRawBytesRequest>
+        + From<ContractRuntimeRequest>
+        + Send,
+{
+    if batch.len() > config.max_batch_size {
+        debug!(
+            batch_size = batch.len(),
+            max_batch_size = config.max_batch_size,
+            "received a batch exceeding the maximum batch size"
+        );
+        return BinaryResponse::new_error(ErrorCode::BadRequest);
+    }
+    let (state_identifier, requests) = batch.destructure();
+    // All the requests are evaluated against the same state root hash, so that the results are
+    // consistent even if new blocks are added while the batch is being handled.
+    let Some(state_root_hash) = resolve_state_root_hash(effect_builder, state_identifier).await
+    else {
+        return BinaryResponse::new_error(ErrorCode::RootNotFound);
+    };
+    let mut responses = Vec::with_capacity(requests.len());
+    for get_req in requests {
+        let response = match pin_to_state_root_hash(get_req, state_root_hash) {
+            Ok(get_req) => {
+                handle_get_request(get_req, effect_builder, config, metrics, protocol_version).await
+            }
+            Err(error_code) => BinaryResponse::new_error(error_code),
+        };
+        responses.push(response);
+    }
+    BinaryResponse::from_value(BatchResponse::new(state_root_hash, responses))
+}
+
+/// Makes a request of a batch query the global state under the given state root hash.
+///
+/// Requests which don't read the global state are returned unchanged.  Returns an error if the
+/// request specifies a state identifier of its own.
+fn pin_to_state_root_hash(
+    get_req: GetRequest,
+    state_root_hash: Digest,
+) -> Result<GetRequest, ErrorCode> {
+    let pinned = Some(GlobalStateIdentifier::StateRootHash(state_root_hash));
+    match get_req {
+        GetRequest::State(req) => {
+            let (state_identifier, qualifier) = req.destructure();
+            if state_identifier.is_some() {
+                debug!("received a batched state request with its own state identifier");
+                return Err(ErrorCode::BadRequest);
+            }
+            Ok(GetRequest::State(Box::new(GlobalStateRequest::new(
+                pinned, qualifier,
+            ))))
+        }
+        GetRequest::Information { info_type_tag, key } => {
+            // Malformed requests are passed on as they are and rejected when handled.
+            let parsed = InformationRequestTag::try_from(info_type_tag)
+                .ok()
+                .and_then(|tag| InformationRequest::try_from((tag, &key[..])).ok());
+            let Some(req) = parsed else {
+                return Ok(GetRequest::Information { info_type_tag, key });
+            };
+            let req = match req {
+                InformationRequest::Package {
+                    state_identifier: None,
+                    identifier,
+                } => InformationRequest::Package {
+                    state_identifier: pinned,
+                    identifier,
+                },
+                InformationRequest::Entity {
+                    state_identifier: None,
+                    identifier,
+                    include_bytecode,
+                } => InformationRequest::Entity {
+                    state_identifier: pinned,
+                    identifier,
+                    include_bytecode,
+                },
+                InformationRequest::Package { .. } | InformationRequest::Entity { .. } => {
+                    debug!("received a batched information request with its own state identifier");
+                    return Err(ErrorCode::BadRequest);
+                }
+                _ => return Ok(GetRequest::Information { info_type_tag, key }),
+            };
+            GetRequest::try_from(req).map_err(|_| ErrorCode::InternalError)
+        }
+        GetRequest::Record { .. } | GetRequest::Trie { .. } | GetRequest::StateDiff(_) => {
+            Ok(get_req)
+        }
     }
 }
 
@@ -321,6 +524,10 @@ where
             metrics.binary_port_get_trie_count.inc();
             handle_trie_request(effect_builder, trie_key, config).await
         }
+        GetRequest::StateDiff(req) => {
+            metrics.binary_port_get_state_diff_count.inc();
+            handle_state_diff_request(effect_builder, *req, config).await
+        }
     }
 }
 
@@ -363,6 +570,39 @@ where
     }
 }
 
+async fn handle_get_items_with_multi_proof<REv>(
+    state_identifier: Option<GlobalStateIdentifier>,
+    keys: Vec<Key>,
+    effect_builder: EffectBuilder<REv>,
+    config: &Config,
+) -> BinaryResponse
+where
+    REv: From<Event> + From<ContractRuntimeRequest> + From<StorageRequest>,
+{
+    if keys.is_empty() || keys.len() > config.max_multi_proof_keys {
+        debug!(
+            key_count = keys.len(),
+            max_multi_proof_keys = config.max_multi_proof_keys,
+            "received a multi-proof request with no keys or too many keys"
+        );
+        return BinaryResponse::new_error(ErrorCode::BadRequest);
+    }
+    let Some(state_root_hash) = resolve_state_root_hash(effect_builder, state_identifier).await
+    else {
+        return BinaryResponse::new_error(ErrorCode::RootNotFound);
+    };
+    let request = MultiProofRequest::new(state_root_hash, keys);
+    match effect_builder.get_multi_proof(request).await {
+        MultiProofResult::Success { proof } => BinaryResponse::from_value(*proof),
+        MultiProofResult::ValuesNotFound => BinaryResponse::new_empty(),
+        MultiProofResult::RootNotFound => BinaryResponse::new_error(ErrorCode::RootNotFound),
+        MultiProofResult::Failure(error) => {
+            debug!(%error, "failed when querying for items with multi-proof");
+            BinaryResponse::new_error(ErrorCode::FailedQuery)
+        }
+    }
+}
+
 async fn handle_get_all_items<REv>(
     state_identifier: Option<GlobalStateIdentifier>,
     key_tag: casper_types::KeyTag,
@@ -508,6 +748,9 @@ where
         GlobalStateEntityQualifier::ItemsByPrefix { key_prefix } => {
             handle_get_items_by_prefix(state_identifier, key_prefix, effect_builder).await
         }
+        GlobalStateEntityQualifier::ItemsWithMultiProof { keys } => {
+            handle_get_items_with_multi_proof(state_identifier, keys, effect_builder, config).await
+        }
     }
 }
 
@@ -539,6 +782,56 @@ where
     }
 }
 
+async fn handle_state_diff_request<REv>(
+    effect_builder: EffectBuilder<REv>,
+    request: StateDiffRequest,
+    config: &Config,
+) -> BinaryResponse
+where
+    REv: From<Event> + From<ContractRuntimeRequest>,
+{
+    if !config.allow_request_get_state_diff {
+        debug!("received a state diff request while the feature is disabled");
+        return BinaryResponse::new_error(ErrorCode::FunctionDisabled);
+    }
+    let req = StorageStateDiffRequest::new(
+        request.from_state_root_hash(),
+        request.to_state_root_hash(),
+        request.start_after().copied(),
+        config.max_state_diff_entries,
+    );
+    match effect_builder.get_state_diff(req).await {
+        StateDiffResult::Success { entries, next_page } => {
+            let entries = entries
+                .into_iter()
+                .map(|entry| match entry {
+                    StorageStateDiffEntry::Added { key, value } => {
+                        StateDiffEntry::Added { key, value }
+                    }
+                    StorageStateDiffEntry::Removed { key, value } => {
+                        StateDiffEntry::Removed { key, value }
+                    }
+                    StorageStateDiffEntry::Modified {
+                        key,
+                        old_value,
+                        new_value,
+                    } => StateDiffEntry::Modified {
+                        key,
+                        old_value,
+                        new_value,
+                    },
+                })
+                .collect();
+            BinaryResponse::from_value(StateDiff::new(entries, next_page))
+        }
+        StateDiffResult::RootNotFound => BinaryResponse::new_error(ErrorCode::RootNotFound),
+        StateDiffResult::Failure(error) => {
+            debug!(%error, "failed when diffing global states");
+            BinaryResponse::new_error(ErrorCode::InternalError)
+        }
+    }
+}
+
 async fn get_dictionary_item_by_legacy_named_key<REv>(
     effect_builder: EffectBuilder<REv>,
     state_root_hash: Digest,
@@ -1058,6 +1351,42 @@ where
                 execution_info,
             ))
         }
+        InformationRequest::TransactionsByInitiator {
+            initiator_addr,
+            from_height,
+            to_height,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.min(MAX_INITIATOR_TRANSACTIONS_PER_REQUEST) as usize;
+            let Some(entries) = effect_builder
+                .get_transactions_by_initiator_from_storage(
+                    initiator_addr,
+                    from_height,
+                    to_height,
+                    start_after,
+                    limit,
+                )
+                .await
+            else {
+                debug!(
+                    "received a request for transactions by initiator while the index is disabled"
+                );
+                return BinaryResponse::new_error(ErrorCode::FunctionDisabled);
+            };
+            let transactions: Vec<InitiatorTransaction> = entries
+                .into_iter()
+                .map(|(transaction_hash, block_info)| {
+                    InitiatorTransaction::new(
+                        transaction_hash,
+                        block_info.block_hash,
+                        block_info.block_height,
+                        block_info.era_id,
+                    )
+                })
+                .collect();
+            BinaryResponse::from_value(transactions)
+        }
         InformationRequest::Peers => {
             BinaryResponse::from_value(Peers::from(effect_builder.network_peers().await))
         }
@@ -1358,20 +1687,27 @@ where
 async fn try_speculative_execution<REv>(
     effect_builder: EffectBuilder<REv>,
     transaction: Transaction,
+    state_identifier: Option<GlobalStateIdentifier>,
+    state_overrides: Vec<StateOverride>,
+    trace_execution: bool,
 ) -> BinaryResponse
 where
     REv: From<Event> + From<ContractRuntimeRequest> + From<StorageRequest>,
 {
-    let tip = match effect_builder
-        .get_highest_complete_block_header_from_storage()
-        .await
-    {
-        Some(tip) => tip,
-        None => return BinaryResponse::new_error(ErrorCode::NoCompleteBlocks),
-    };
+    let (block_header, state_root_hash) =
+        match resolve_execution_context(effect_builder, state_identifier).await {
+            Ok(execution_context) => execution_context,
+            Err(error_code) => return BinaryResponse::new_error(error_code),
+        };
 
     let result = effect_builder
-        .speculatively_execute(Box::new(tip), Box::new(transaction))
+        .speculatively_execute(
+            Box::new(block_header),
+            state_root_hash,
+            Box::new(transaction),
+            state_overrides,
+            trace_execution,
+        )
         .await;
 
     match result {
@@ -1385,9 +1721,83 @@ where
         SpeculativeExecutionResult::ReceivedV1Transaction => {
             BinaryResponse::new_error(ErrorCode::ReceivedV1Transaction)
         }
+        SpeculativeExecutionResult::InvalidStateOverride => {
+            BinaryResponse::new_error(ErrorCode::InvalidStateOverride)
+        }
+    }
+}
+
+async fn estimate_gas<REv>(
+    effect_builder: EffectBuilder<REv>,
+    transaction: Transaction,
+    state_identifier: Option<GlobalStateIdentifier>,
+    state_overrides: Vec<StateOverride>,
+    payment_margin_percent: u8,
+) -> BinaryResponse
+where
+    REv: From<Event> + From<ContractRuntimeRequest> + From<StorageRequest>,
+{
+    let (block_header, state_root_hash) =
+        match resolve_execution_context(effect_builder, state_identifier).await {
+            Ok(execution_context) => execution_context,
+            Err(error_code) => return BinaryResponse::new_error(error_code),
+        };
+
+    let result = effect_builder
+        .estimate_gas(
+            Box::new(block_header),
+            state_root_hash,
+            Box::new(transaction),
+            state_overrides,
+            payment_margin_percent,
+        )
+        .await;
+
+    match result {
+        GasEstimationResult::InvalidTransaction(error) => {
+            debug!(%error, "invalid transaction submitted for gas estimation");
+            BinaryResponse::new_error(error.into())
+        }
+        GasEstimationResult::Estimated(gas_estimate) => BinaryResponse::from_value(gas_estimate),
+        GasEstimationResult::InvalidStateOverride => {
+            BinaryResponse::new_error(ErrorCode::InvalidStateOverride)
+        }
     }
 }
 
+/// Resolves the header of the block a transaction is executed on top of, and the state root hash
+/// it's executed against.
+async fn resolve_execution_context<REv>(
+    effect_builder: EffectBuilder<REv>,
+    state_identifier: Option<GlobalStateIdentifier>,
+) -> Result<(BlockHeader, Digest), ErrorCode>
+where
+    REv: From<Event> + From<ContractRuntimeRequest> + From<StorageRequest>,
+{
+    let block_identifier = match state_identifier {
+        Some(GlobalStateIdentifier::BlockHash(block_hash)) => {
+            Some(BlockIdentifier::Hash(block_hash))
+        }
+        Some(GlobalStateIdentifier::BlockHeight(block_height)) => {
+            Some(BlockIdentifier::Height(block_height))
+        }
+        // A bare state root hash doesn't identify a block, so the execution takes place in the
+        // context of the block following the highest complete block.
+        Some(GlobalStateIdentifier::StateRootHash(_)) | None => None,
+    };
+    let Some(block_header) = resolve_block_header(effect_builder, block_identifier).await else {
+        return match block_identifier {
+            Some(_) => Err(ErrorCode::RootNotFound),
+            None => Err(ErrorCode::NoCompleteBlocks),
+        };
+    };
+    let state_root_hash = match state_identifier {
+        Some(GlobalStateIdentifier::StateRootHash(state_root_hash)) => state_root_hash,
+        _ => *block_header.state_root_hash(),
+    };
+    Ok((block_header, state_root_hash))
+}
+
 async fn handle_client_loop<REv>(
     stream: TcpStream,
     effect_builder: EffectBuilder<REv>,
@@ -1395,6 +1805,7 @@ async fn handle_client_loop<REv>(
     rate_limiter: Arc<Mutex<RateLimiter>>,
     monitor: ConnectionTerminator,
     life_extensions_config: BinaryRequestTerminationDelayValues,
+    notifications: broadcast::Sender<Arc<Notification>>,
 ) -> Result<(), Error>
 where
     REv: From<Event>
@@ -1415,6 +1826,7 @@ where
         .terminate_at(Timestamp::now() + config.initial_connection_lifetime)
         .await;
     let cancellation_token = monitor.get_cancellation_token();
+    let mut subscription: Option<(Subscription, Bytes)> = None;
     loop {
         select! {
             maybe_bytes = framed.next() => {
@@ -1431,12 +1843,33 @@ where
                     return Err(Error::NoPayload);
                 }
                 let mut bytes_buf = bytes::BytesMut::with_capacity(payload.len() + 4);
-                let response =
-                    handle_payload(effect_builder, payload, limiter_response, &monitor, &life_extensions_config).await;
+                let outcome =
+                    handle_payload(effect_builder, payload, limiter_response, &rate_limiter, &monitor, &life_extensions_config).await;
                 codec.clone().encode(binary_message, &mut bytes_buf)?;
+                let request_bytes = Bytes::from(bytes_buf.freeze().to_vec());
+                let response = match outcome {
+                    Either::Left(response) => response,
+                    Either::Right(subscription_request) => {
+                        // A new subscription replaces the previous one, if any.
+                        match Subscription::new(effect_builder, subscription_request, &notifications, &config).await {
+                            Ok(new_subscription) => {
+                                subscription = Some((new_subscription, request_bytes.clone()));
+                                BinaryResponse::new_empty()
+                            }
+                            Err(error_code) => BinaryResponse::new_error(error_code),
+                        }
+                    }
+                };
+                framed
+                    .send(BinaryMessage::new(
+                        BinaryResponseAndRequest::new(response, request_bytes).to_bytes()?,
+                    ))
+                    .await?
+            }
+            (response, request_bytes) = next_notification(effect_builder, &mut subscription), if subscription.is_some() => {
                 framed
                     .send(BinaryMessage::new(
-                        BinaryResponseAndRequest::new(response, Bytes::from(bytes_buf.freeze().to_vec())).to_bytes()?,
+                        BinaryResponseAndRequest::new(response, request_bytes).to_bytes()?,
                     ))
                     .await?
             }
@@ -1448,6 +1881,35 @@ where
     }
 }
 
+/// Returns the next notification of the subscription as a response to the subscription request.
+///
+/// If the subscription fails, it's dropped and the error is returned as the response.
+async fn next_notification<REv>(
+    effect_builder: EffectBuilder<REv>,
+    subscription: &mut Option<(Subscription, Bytes)>,
+) -> (BinaryResponse, Bytes)
+where
+    REv: From<StorageRequest>,
+{
+    let Some((active_subscription, request_bytes)) = subscription.as_mut() else {
+        return futures::future::pending().await;
+    };
+    match active_subscription.next(effect_builder).await {
+        Ok(notification) => {
+            let response = notification.to_bytes().map_or(
+                BinaryResponse::new_error(ErrorCode::InternalError),
+                |payload| BinaryResponse::from_raw_bytes(ResponseType::Notification, payload),
+            );
+            (response, request_bytes.clone())
+        }
+        Err(error_code) => {
+            let request_bytes = request_bytes.clone();
+            *subscription = None;
+            (BinaryResponse::new_error(error_code), request_bytes)
+        }
+    }
+}
+
 fn extract_header(payload: &[u8]) -> Result<(CommandHeader, &[u8]), ErrorCode> {
     const BINARY_VERSION_LENGTH_BYTES: usize = size_of::<u16>();
 
@@ -1477,43 +1939,60 @@ async fn handle_payload<REv>(
     effect_builder: EffectBuilder<REv>,
     payload: &[u8],
     limiter_response: LimiterResponse,
+    rate_limiter: &Mutex<RateLimiter>,
     connection_terminator: &ConnectionTerminator,
     life_extensions_config: &BinaryRequestTerminationDelayValues,
-) -> BinaryResponse
+) -> Either<BinaryResponse, SubscriptionRequest>
 where
     REv: From<Event>,
 {
     let (header, remainder) = match extract_header(payload) {
         Ok(header) => header,
-        Err(error_code) => return BinaryResponse::new_error(error_code),
+        Err(error_code) => return Either::Left(BinaryResponse::new_error(error_code)),
     };
 
     if let LimiterResponse::Throttled = limiter_response {
-        return BinaryResponse::new_error(ErrorCode::RequestThrottled);
+        return Either::Left(BinaryResponse::new_error(ErrorCode::RequestThrottled));
     }
 
     // we might receive a request added in a minor version if we're behind
     let Ok(tag) = CommandTag::try_from(header.type_tag()) else {
-        return BinaryResponse::new_error(ErrorCode::UnsupportedRequest);
+        return Either::Left(BinaryResponse::new_error(ErrorCode::UnsupportedRequest));
     };
 
     let request = match Command::try_from((tag, remainder)) {
         Ok(request) => request,
         Err(error) => {
             debug!(%error, "failed to parse binary request body");
-            return BinaryResponse::new_error(ErrorCode::MalformedCommand);
+            return Either::Left(BinaryResponse::new_error(ErrorCode::MalformedCommand));
         }
     };
+    if let Command::Batch(batch) = &request {
+        // The frame carrying the batch has already been counted as a single request.
+        let extra_weight = batch_weight(batch.len()) - 1;
+        if extra_weight > 0
+            && rate_limiter.lock().await.throttle_weighted(extra_weight)
+                == LimiterResponse::Throttled
+        {
+            return Either::Left(BinaryResponse::new_error(ErrorCode::RequestThrottled));
+        }
+    }
     connection_terminator
         .delay_termination(life_extensions_config.get_life_termination_delay(&request))
         .await;
 
-    effect_builder
-        .make_request(
-            |responder| Event::HandleRequest { request, responder },
-            QueueKind::Regular,
-        )
-        .await
+    if let Command::Subscribe(subscription_request) = request {
+        return Either::Right(subscription_request);
+    }
+
+    Either::Left(
+        effect_builder
+            .make_request(
+                |responder| Event::HandleRequest { request, responder },
+                QueueKind::Regular,
+            )
+            .await,
+    )
 }
 
 async fn handle_client<REv>(
@@ -1523,6 +2002,7 @@ async fn handle_client<REv>(
     config: Arc<Config>,
     _permit: OwnedSemaphorePermit,
     rate_limiter: Arc<Mutex<RateLimiter>>,
+    notifications: broadcast::Sender<Arc<Notification>>,
 ) where
     REv: From<Event>
         + From<StorageRequest>
@@ -1545,6 +2025,7 @@ async fn handle_client<REv>(
         rate_limiter,
         keep_alive_monitor,
         life_extensions_config,
+        notifications,
     )
     .await
     {
@@ -1795,6 +2276,10 @@ where
                     <Self as InitializedComponent<MainEvent>>::set_state(self, state);
                     effects
                 }
+                // There are no subscribers to notify before the server is started.
+                Event::BlockAdded(_)
+                | Event::TransactionProcessed { .. }
+                | Event::FinalitySignature(_) => Effects::new(),
                 _ => {
                     warn!(
                         ?event,
@@ -1833,6 +2318,7 @@ where
                             config,
                             permit,
                             rate_limiter,
+                            self.notifications.clone(),
                         ));
                     } else {
                         warn!(
@@ -1859,6 +2345,34 @@ where
                     }
                     .ignore()
                 }
+                Event::BlockAdded(block) => {
+                    self.notify(|| Notification::BlockAdded(Box::new((*block).clone())));
+                    Effects::new()
+                }
+                Event::TransactionProcessed {
+                    transaction_hash,
+                    transaction_header,
+                    block_hash,
+                    block_height,
+                    execution_result,
+                    messages,
+                } => {
+                    self.notify(|| {
+                        subscription::transaction_processed(
+                            transaction_hash,
+                            &transaction_header,
+                            block_hash,
+                            block_height,
+                            *execution_result,
+                            messages,
+                        )
+                    });
+                    Effects::new()
+                }
+                Event::FinalitySignature(finality_signature) => {
+                    self.notify(|| Notification::FinalitySignature(finality_signature));
+                    Effects::new()
+                }
             },
             ComponentState::Fatal(msg) => {
                 error!(
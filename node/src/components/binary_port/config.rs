@@ -27,11 +27,29 @@ const DEFAULT_GET_STATE_REQUEST_TERMINATION_DELAY: &str = "0 seconds";
 // [`Command::Get(GetRequest::Trie)`] is sent to the node
 const DEFAULT_GET_TRIE_REQUEST_TERMINATION_DELAY: &str = "0 seconds";
 // Default amount of time which is given to a connection to extend it's lifetime when a valid
+// [`Command::Get(GetRequest::StateDiff)`] is sent to the node
+const DEFAULT_GET_STATE_DIFF_REQUEST_TERMINATION_DELAY: &str = "0 seconds";
+// Default amount of time which is given to a connection to extend it's lifetime when a valid
 // [`Command::TryAcceptTransaction`] is sent to the node
 const DEFAULT_ACCEPT_TRANSACTION_REQUEST_TERMINATION_DELAY: &str = "24 seconds";
 // Default amount of time which is given to a connection to extend it's lifetime when a valid
 // [`Command::TrySpeculativeExec`] is sent to the node
 const DEFAULT_SPECULATIVE_EXEC_REQUEST_TERMINATION_DELAY: &str = "0 seconds";
+// Default amount of time which is given to a connection to extend it's lifetime when a valid
+// [`Command::Subscribe`] is sent to the node
+const DEFAULT_SUBSCRIBE_REQUEST_TERMINATION_DELAY: &str = "60 seconds";
+/// Default maximum number of notifications buffered for a subscriber.
+const DEFAULT_SUBSCRIPTION_BUFFER_SIZE: usize = 1024;
+/// Default maximum number of stored blocks replayed to a subscriber resuming from a height.
+const DEFAULT_MAX_SUBSCRIPTION_REPLAY_BLOCKS: u64 = 100;
+/// Default maximum number of requests in a single batch.
+const DEFAULT_MAX_BATCH_SIZE: usize = 200;
+/// Default maximum number of differences returned by a single state diff request.
+const DEFAULT_MAX_STATE_DIFF_ENTRIES: usize = 500;
+/// Default maximum number of keys proven by a single multi-proof request.
+const DEFAULT_MAX_MULTI_PROOF_KEYS: usize = 100;
+/// Default margin added to the consumed gas to make up a recommended payment amount, in percent.
+const DEFAULT_GAS_ESTIMATE_PAYMENT_MARGIN_PERCENT: u8 = 10;
 
 /// Binary port server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
@@ -48,8 +66,10 @@ pub struct Config {
     pub allow_request_get_all_values: bool,
     /// Flag used to enable/disable the [`Trie`] request
     pub allow_request_get_trie: bool,
-    /// Flag used to enable/disable the [`TrySpeculativeExec`] request.
+    /// Flag used to enable/disable the [`TrySpeculativeExec`] and [`EstimateGas`] requests.
     pub allow_request_speculative_exec: bool,
+    /// Flag used to enable/disable the [`StateDiff`] request.
+    pub allow_request_get_state_diff: bool,
     /// Maximum size of the binary port message.
     pub max_message_size_bytes: u32,
     /// Maximum number of connections to the server.
@@ -71,11 +91,32 @@ pub struct Config {
     // [`Command::Get(GetRequest::Trie)`] is sent to the node
     pub get_trie_request_termination_delay: TimeDiff,
     // The amount of time which is given to a connection to extend it's lifetime when a valid
+    // [`Command::Get(GetRequest::StateDiff)`] is sent to the node
+    pub get_state_diff_request_termination_delay: TimeDiff,
+    // The amount of time which is given to a connection to extend it's lifetime when a valid
     // [`Command::TryAcceptTransaction`] is sent to the node
     pub accept_transaction_request_termination_delay: TimeDiff,
     // The amount of time which is given to a connection to extend it's lifetime when a valid
     // [`Command::TrySpeculativeExec`] is sent to the node
     pub speculative_exec_request_termination_delay: TimeDiff,
+    // The amount of time which is given to a connection to extend it's lifetime when a valid
+    // [`Command::Subscribe`] is sent to the node
+    pub subscribe_request_termination_delay: TimeDiff,
+    /// Maximum number of notifications buffered for a subscriber before it's considered lagging.
+    pub subscription_buffer_size: usize,
+    /// Maximum number of stored blocks replayed to a subscriber resuming from a given height.
+    pub max_subscription_replay_blocks: u64,
+    /// Maximum number of requests in a single [`Command::Batch`].
+    pub max_batch_size: usize,
+    /// Maximum number of differences returned in a single page by a
+    /// [`Command::Get(GetRequest::StateDiff)`].
+    pub max_state_diff_entries: usize,
+    /// Maximum number of keys read with a single proof of all of them by a
+    /// [`Command::Get(GetRequest::State)`].
+    pub max_multi_proof_keys: usize,
+    /// Margin added to the gas consumed by a transaction to make up the payment amount recommended
+    /// by a [`Command::EstimateGas`], in percent.
+    pub gas_estimate_payment_margin_percent: u8,
 }
 
 impl Config {
@@ -87,6 +128,7 @@ impl Config {
             allow_request_get_all_values: false,
             allow_request_get_trie: false,
             allow_request_speculative_exec: false,
+            allow_request_get_state_diff: false,
             max_message_size_bytes: DEFAULT_MAX_MESSAGE_SIZE,
             max_connections: DEFAULT_MAX_CONNECTIONS,
             qps_limit: DEFAULT_QPS_LIMIT,
@@ -108,6 +150,10 @@ impl Config {
                 DEFAULT_GET_TRIE_REQUEST_TERMINATION_DELAY,
             )
             .unwrap(),
+            get_state_diff_request_termination_delay: TimeDiff::from_str(
+                DEFAULT_GET_STATE_DIFF_REQUEST_TERMINATION_DELAY,
+            )
+            .unwrap(),
             accept_transaction_request_termination_delay: TimeDiff::from_str(
                 DEFAULT_ACCEPT_TRANSACTION_REQUEST_TERMINATION_DELAY,
             )
@@ -116,6 +162,16 @@ impl Config {
                 DEFAULT_SPECULATIVE_EXEC_REQUEST_TERMINATION_DELAY,
             )
             .unwrap(),
+            subscribe_request_termination_delay: TimeDiff::from_str(
+                DEFAULT_SUBSCRIBE_REQUEST_TERMINATION_DELAY,
+            )
+            .unwrap(),
+            subscription_buffer_size: DEFAULT_SUBSCRIPTION_BUFFER_SIZE,
+            max_subscription_replay_blocks: DEFAULT_MAX_SUBSCRIPTION_REPLAY_BLOCKS,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            max_state_diff_entries: DEFAULT_MAX_STATE_DIFF_ENTRIES,
+            max_multi_proof_keys: DEFAULT_MAX_MULTI_PROOF_KEYS,
+            gas_estimate_payment_margin_percent: DEFAULT_GAS_ESTIMATE_PAYMENT_MARGIN_PERCENT,
         }
     }
 }
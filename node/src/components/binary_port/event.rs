@@ -1,12 +1,17 @@
 use std::{
     fmt::{Display, Formatter},
     net::SocketAddr,
+    sync::Arc,
 };
 
 use casper_binary_port::{BinaryResponse, Command, GetRequest};
+use casper_types::{
+    contract_messages::Messages, execution::ExecutionResult, Block, BlockHash, FinalitySignature,
+    TransactionHash,
+};
 use tokio::net::TcpStream;
 
-use crate::effect::Responder;
+use crate::{effect::Responder, types::TransactionHeader};
 
 #[derive(Debug)]
 pub(crate) enum Event {
@@ -20,6 +25,18 @@ pub(crate) enum Event {
         request: Command,
         responder: Responder<BinaryResponse>,
     },
+    BlockAdded(Arc<Block>),
+    TransactionProcessed {
+        transaction_hash: TransactionHash,
+        transaction_header: Box<TransactionHeader>,
+        block_hash: BlockHash,
+        block_height: u64,
+        execution_result: Box<ExecutionResult>,
+        /// The messages emitted by the transaction, or `None` if they aren't known, as for
+        /// historically synced blocks.
+        messages: Option<Messages>,
+    },
+    FinalitySignature(Box<FinalitySignature>),
 }
 
 impl Display for Event {
@@ -40,6 +57,12 @@ impl Display for Event {
                     }
                     GetRequest::State(state_request) => state_request.as_ref().fmt(f),
                     GetRequest::Trie { trie_key } => write!(f, "get trie ({})", trie_key),
+                    GetRequest::StateDiff(state_diff_request) => write!(
+                        f,
+                        "get state diff ({} to {})",
+                        state_diff_request.from_state_root_hash(),
+                        state_diff_request.to_state_root_hash()
+                    ),
                 },
                 Command::TryAcceptTransaction { transaction, .. } => {
                     write!(f, "try accept transaction ({})", transaction.hash())
@@ -47,7 +70,17 @@ impl Display for Event {
                 Command::TrySpeculativeExec { transaction, .. } => {
                     write!(f, "try speculative exec ({})", transaction.hash())
                 }
+                Command::EstimateGas { transaction, .. } => {
+                    write!(f, "estimate gas ({})", transaction.hash())
+                }
+                Command::Subscribe(_) => write!(f, "subscribe"),
+                Command::Batch(batch) => write!(f, "batch of {} requests", batch.len()),
             },
+            Event::BlockAdded(block) => write!(f, "block added {}", block.hash()),
+            Event::TransactionProcessed {
+                transaction_hash, ..
+            } => write!(f, "transaction processed {}", transaction_hash),
+            Event::FinalitySignature(fs) => write!(f, "finality signature {}", fs),
         }
     }
 }
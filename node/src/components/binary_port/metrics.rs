@@ -11,6 +11,9 @@ const BINARY_PORT_TRY_SPECULATIVE_EXEC_COUNT_NAME: &str = "binary_port_try_specu
 const BINARY_PORT_TRY_SPECULATIVE_EXEC_COUNT_HELP: &str =
     "number of TrySpeculativeExec queries received";
 
+const BINARY_PORT_ESTIMATE_GAS_COUNT_NAME: &str = "binary_port_estimate_gas_count";
+const BINARY_PORT_ESTIMATE_GAS_COUNT_HELP: &str = "number of EstimateGas queries received";
+
 const BINARY_PORT_GET_RECORD_COUNT_NAME: &str = "binary_port_get_record_count";
 const BINARY_PORT_GET_RECORD_COUNT_HELP: &str = "number of received Get queries for records";
 
@@ -29,6 +32,10 @@ const BINARY_PORT_CONNECTIONS_COUNT_HELP: &str =
 const BINARY_PORT_TRIE_COUNT_NAME: &str = "binary_port_get_trie_count";
 const BINARY_PORT_TRIE_COUNT_HELP: &str = "number of Get queries received for the trie state";
 
+const BINARY_PORT_GET_STATE_DIFF_COUNT_NAME: &str = "binary_port_get_state_diff_count";
+const BINARY_PORT_GET_STATE_DIFF_COUNT_HELP: &str =
+    "number of Get queries received for the differences between two global states";
+
 /// Metrics.
 #[derive(Debug)]
 pub(crate) struct Metrics {
@@ -36,6 +43,8 @@ pub(crate) struct Metrics {
     pub(super) binary_port_try_accept_transaction_count: IntCounter,
     /// Number of `TrySpeculativeExec` queries received.
     pub(super) binary_port_try_speculative_exec_count: IntCounter,
+    /// Number of `EstimateGas` queries received.
+    pub(super) binary_port_estimate_gas_count: IntCounter,
     /// Number of `Get::Record` queries received.
     pub(super) binary_port_get_record_count: IntCounter,
     /// Number of `Get::Information` queries received.
@@ -46,6 +55,8 @@ pub(crate) struct Metrics {
     pub(super) binary_port_connections_count: IntCounter,
     /// Number of `Get::Trie` queries received.
     pub(super) binary_port_get_trie_count: IntCounter,
+    /// Number of `Get::StateDiff` queries received.
+    pub(super) binary_port_get_state_diff_count: IntCounter,
 
     registry: Registry,
 }
@@ -63,6 +74,11 @@ impl Metrics {
             BINARY_PORT_TRY_SPECULATIVE_EXEC_COUNT_HELP.to_string(),
         )?;
 
+        let binary_port_estimate_gas_count = IntCounter::new(
+            BINARY_PORT_ESTIMATE_GAS_COUNT_NAME.to_string(),
+            BINARY_PORT_ESTIMATE_GAS_COUNT_HELP.to_string(),
+        )?;
+
         let binary_port_get_record_count = IntCounter::new(
             BINARY_PORT_GET_RECORD_COUNT_NAME.to_string(),
             BINARY_PORT_GET_RECORD_COUNT_HELP.to_string(),
@@ -88,22 +104,31 @@ impl Metrics {
             BINARY_PORT_TRIE_COUNT_HELP.to_string(),
         )?;
 
+        let binary_port_get_state_diff_count = IntCounter::new(
+            BINARY_PORT_GET_STATE_DIFF_COUNT_NAME.to_string(),
+            BINARY_PORT_GET_STATE_DIFF_COUNT_HELP.to_string(),
+        )?;
+
         registry.register(Box::new(binary_port_try_accept_transaction_count.clone()))?;
         registry.register(Box::new(binary_port_try_speculative_exec_count.clone()))?;
+        registry.register(Box::new(binary_port_estimate_gas_count.clone()))?;
         registry.register(Box::new(binary_port_get_record_count.clone()))?;
         registry.register(Box::new(binary_port_get_info_count.clone()))?;
         registry.register(Box::new(binary_port_get_state_count.clone()))?;
         registry.register(Box::new(binary_port_connections_count.clone()))?;
         registry.register(Box::new(binary_port_get_trie_count.clone()))?;
+        registry.register(Box::new(binary_port_get_state_diff_count.clone()))?;
 
         Ok(Metrics {
             binary_port_try_accept_transaction_count,
             binary_port_try_speculative_exec_count,
+            binary_port_estimate_gas_count,
             binary_port_get_record_count,
             binary_port_get_info_count,
             binary_port_get_state_count,
             binary_port_connections_count,
             binary_port_get_trie_count,
+            binary_port_get_state_diff_count,
             registry: registry.clone(),
         })
     }
@@ -113,9 +138,11 @@ impl Drop for Metrics {
     fn drop(&mut self) {
         unregister_metric!(self.registry, self.binary_port_try_accept_transaction_count);
         unregister_metric!(self.registry, self.binary_port_try_speculative_exec_count);
+        unregister_metric!(self.registry, self.binary_port_estimate_gas_count);
         unregister_metric!(self.registry, self.binary_port_get_record_count);
         unregister_metric!(self.registry, self.binary_port_get_info_count);
         unregister_metric!(self.registry, self.binary_port_get_state_count);
         unregister_metric!(self.registry, self.binary_port_connections_count);
+        unregister_metric!(self.registry, self.binary_port_get_state_diff_count);
     }
 }
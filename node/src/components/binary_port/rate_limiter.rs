@@ -13,6 +13,20 @@ pub(crate) enum RateLimiterError {
 
 const MAX_WINDOW_DURATION_MS: u64 = 1000 * 60 * 60; // 1 hour
 
+/// Number of requests of a batch which together weigh as much as a single request.
+const BATCH_REQUESTS_PER_UNIT_OF_WEIGHT: usize = 5;
+
+/// Returns the weight of a batch of the given number of requests, i.e. the number of single
+/// requests it's counted as by the rate limiter.
+///
+/// Every started group of `BATCH_REQUESTS_PER_UNIT_OF_WEIGHT` requests weighs one, and even an
+/// empty batch weighs as much as a single request.
+pub(crate) fn batch_weight(number_of_requests: usize) -> usize {
+    number_of_requests
+        .div_ceil(BATCH_REQUESTS_PER_UNIT_OF_WEIGHT)
+        .max(1)
+}
+
 #[derive(PartialEq, Eq, Debug)]
 /// Response from the rate limiter.
 pub(crate) enum LimiterResponse {
@@ -50,6 +64,12 @@ impl Buffer {
         self.in_index == self.out_index
     }
 
+    /// Returns the number of values which can still be pushed.
+    fn free_slots(&self) -> usize {
+        let len = (self.in_index + self.capacity - self.out_index) % self.capacity;
+        self.capacity - 1 - len
+    }
+
     //This should only be used from `push`
     fn push_and_slide(&mut self, value: u64) -> bool {
         let out_index = self.out_index as i32;
@@ -149,22 +169,34 @@ impl RateLimiter {
         self.internal_throttle(Timestamp::now().millis())
     }
 
+    /// Throttles a request counted as `weight` single requests.
+    ///
+    /// The request is either allowed as a whole or not at all.  Weights exceeding the maximum
+    /// number of requests in the window are capped, so that heavy requests still go through once
+    /// the window is empty.
+    pub(crate) fn throttle_weighted(&mut self, weight: usize) -> LimiterResponse {
+        self.internal_throttle_weighted(Timestamp::now().millis(), weight)
+    }
+
     fn internal_throttle(&mut self, now: u64) -> LimiterResponse {
-        let is_full = self.buffer.is_full();
-        if !is_full {
-            self.buffer.push(now);
-            return LimiterResponse::Allowed;
-        } else {
+        self.internal_throttle_weighted(now, 1)
+    }
+
+    fn internal_throttle_weighted(&mut self, now: u64, weight: usize) -> LimiterResponse {
+        let weight = weight.min(self.buffer.capacity - 1);
+        if self.buffer.free_slots() < weight {
             //The following subtraction could theoretically not fit in unsigned, but in real-life
             // cases we limit the window duration to 1 hour (it's checked in ctor). So unless
             // someone calls it from the perspective of 1970, it should be fine.
-            let no_of_pruned = self.buffer.prune_lt(now - self.window_ms);
-            if no_of_pruned == 0 {
-                //No pruning was done, so we are still at max_requests
+            self.buffer.prune_lt(now - self.window_ms);
+            if self.buffer.free_slots() < weight {
+                //Not enough was pruned, so we would exceed max_requests
                 return LimiterResponse::Throttled;
             }
         }
-        self.buffer.push(now);
+        for _ in 0..weight {
+            self.buffer.push(now);
+        }
         LimiterResponse::Allowed
     }
 }
@@ -290,6 +322,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sliding_window_should_limit_weighted_requests() {
+        let mut rate_limiter = RateLimiter::new(5, TimeDiff::from_millis(1000)).unwrap();
+        let t_1 = 10000_u64;
+        let t_2 = 10500_u64;
+        let t_3 = 11001_u64;
+        assert_eq!(
+            rate_limiter.internal_throttle_weighted(t_1, 3),
+            LimiterResponse::Allowed
+        );
+        assert_eq!(
+            rate_limiter.internal_throttle_weighted(t_2, 3),
+            LimiterResponse::Throttled
+        );
+        assert_eq!(
+            rate_limiter.internal_throttle_weighted(t_2, 2),
+            LimiterResponse::Allowed
+        );
+        assert_eq!(
+            rate_limiter.internal_throttle(t_2),
+            LimiterResponse::Throttled
+        );
+        assert_eq!(
+            rate_limiter.internal_throttle_weighted(t_3, 3),
+            LimiterResponse::Allowed
+        );
+        assert_eq!(
+            rate_limiter.internal_throttle(t_3),
+            LimiterResponse::Throttled
+        );
+    }
+
+    #[test]
+    fn sliding_window_should_cap_weight_to_max_requests() {
+        let mut rate_limiter = rate_limiter();
+        let t_1 = 10000_u64;
+        let t_2 = 11001_u64;
+        assert_eq!(
+            rate_limiter.internal_throttle_weighted(t_1, 10),
+            LimiterResponse::Allowed
+        );
+        assert_eq!(
+            rate_limiter.internal_throttle(t_1),
+            LimiterResponse::Throttled
+        );
+        assert_eq!(
+            rate_limiter.internal_throttle(t_2),
+            LimiterResponse::Allowed
+        );
+        assert_eq!(
+            rate_limiter.internal_throttle_weighted(t_2, 10),
+            LimiterResponse::Throttled
+        );
+    }
+
+    #[test]
+    fn batch_weight_should_round_up() {
+        assert_eq!(batch_weight(0), 1);
+        assert_eq!(batch_weight(1), 1);
+        assert_eq!(batch_weight(5), 1);
+        assert_eq!(batch_weight(6), 2);
+        assert_eq!(batch_weight(200), 40);
+    }
+
     #[test]
     fn buffer_should_saturate_with_values() {
         let mut buffer = Buffer::new(3);
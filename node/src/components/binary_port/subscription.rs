@@ -0,0 +1,225 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use casper_binary_port::{
+    ErrorCode, Notification, NotificationKind, SubscriptionFilter, SubscriptionRequest,
+    TransactionProcessed,
+};
+use casper_types::{
+    contract_messages::Messages, execution::ExecutionResult, BlockHash, InitiatorAddr,
+    TransactionHash,
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::debug;
+
+use super::Config;
+use crate::{
+    effect::{requests::StorageRequest, EffectBuilder},
+    types::{BlockWithMetadata, TransactionHeader},
+};
+
+/// A subscription of a single connection to the notifications pushed by the node.
+///
+/// If the subscription resumes from a given height, the notifications of the already stored
+/// blocks are replayed from storage first.  Live notifications of blocks covered by the replay are
+/// skipped, so every block is delivered once.
+pub(super) struct Subscription {
+    filter: SubscriptionFilter,
+    receiver: broadcast::Receiver<Arc<Notification>>,
+    /// Replayed notifications waiting to be delivered.
+    replayed: VecDeque<Arc<Notification>>,
+    /// Height of the next stored block to replay.
+    next_replay_height: u64,
+    /// Height of the first block delivered live rather than replayed.
+    live_from_height: u64,
+}
+
+impl Subscription {
+    /// Starts a new subscription.
+    pub(super) async fn new<REv>(
+        effect_builder: EffectBuilder<REv>,
+        request: SubscriptionRequest,
+        notifications: &broadcast::Sender<Arc<Notification>>,
+        config: &Config,
+    ) -> Result<Self, ErrorCode>
+    where
+        REv: From<StorageRequest>,
+    {
+        // Subscribe before looking up the stored blocks, so no block falls between the replay and
+        // the live notifications.
+        let receiver = notifications.subscribe();
+        let (next_replay_height, live_from_height) = match request.start_from_height() {
+            None => (0, 0),
+            Some(start_from_height) => {
+                let available_block_range = effect_builder
+                    .get_available_block_range_from_storage()
+                    .await;
+                if start_from_height < available_block_range.low() {
+                    debug!(
+                        start_from_height,
+                        "cannot resume subscription from a block which isn't available"
+                    );
+                    return Err(ErrorCode::NotFound);
+                }
+                let live_from_height =
+                    start_from_height.max(available_block_range.high().saturating_add(1));
+                if live_from_height - start_from_height > config.max_subscription_replay_blocks {
+                    debug!(
+                        start_from_height,
+                        live_from_height, "too many blocks to replay for subscription"
+                    );
+                    return Err(ErrorCode::BadRequest);
+                }
+                (start_from_height, live_from_height)
+            }
+        };
+        Ok(Subscription {
+            filter: request.filter().clone(),
+            receiver,
+            replayed: VecDeque::new(),
+            next_replay_height,
+            live_from_height,
+        })
+    }
+
+    /// Returns the next notification selected by the filter of the subscription.
+    ///
+    /// Returns an error if the subscriber fell too far behind the live notifications, after which
+    /// the subscription should be dropped.
+    ///
+    /// The returned future is cancel safe: no notification is lost if it's dropped before
+    /// completion.
+    pub(super) async fn next<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Result<Arc<Notification>, ErrorCode>
+    where
+        REv: From<StorageRequest>,
+    {
+        loop {
+            if let Some(notification) = self.replayed.pop_front() {
+                if self.filter.matches(&notification) {
+                    return Ok(notification);
+                }
+                continue;
+            }
+
+            if self.next_replay_height < self.live_from_height {
+                let replayed =
+                    stored_notifications(effect_builder, self.next_replay_height, &self.filter)
+                        .await;
+                self.replayed.extend(replayed);
+                self.next_replay_height += 1;
+                continue;
+            }
+
+            match self.receiver.recv().await {
+                Ok(notification) => {
+                    if self.is_replayed(&notification) || !self.filter.matches(&notification) {
+                        continue;
+                    }
+                    return Ok(notification);
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    debug!(skipped, "binary port subscriber lagged");
+                    return Err(ErrorCode::SubscriptionLagged);
+                }
+                Err(RecvError::Closed) => return Err(ErrorCode::InternalError),
+            }
+        }
+    }
+
+    /// Returns `true` if the live notification relates to a block delivered by the replay.
+    ///
+    /// Finality signatures may be accepted long after their block was stored, so they're never
+    /// considered replayed.
+    fn is_replayed(&self, notification: &Notification) -> bool {
+        if notification.kind() == NotificationKind::FinalitySignature {
+            return false;
+        }
+        notification
+            .block_height()
+            .is_some_and(|height| height < self.live_from_height)
+    }
+}
+
+/// Creates a transaction processed notification.
+pub(super) fn transaction_processed(
+    transaction_hash: TransactionHash,
+    transaction_header: &TransactionHeader,
+    block_hash: BlockHash,
+    block_height: u64,
+    execution_result: ExecutionResult,
+    messages: Option<Messages>,
+) -> Notification {
+    let initiator_addr = match transaction_header {
+        TransactionHeader::Deploy(deploy_header) => {
+            InitiatorAddr::PublicKey(deploy_header.account().clone())
+        }
+        TransactionHeader::V1(metadata) => metadata.initiator_addr().clone(),
+    };
+    Notification::TransactionProcessed(Box::new(TransactionProcessed::new(
+        transaction_hash,
+        initiator_addr,
+        block_hash,
+        block_height,
+        execution_result,
+        messages,
+    )))
+}
+
+/// Returns the notifications of the stored block at the given height, in the order they're pushed
+/// live.
+///
+/// Only the kinds of notifications selected by the filter are read from storage.  Returns no
+/// notifications if the block isn't stored.
+async fn stored_notifications<REv>(
+    effect_builder: EffectBuilder<REv>,
+    height: u64,
+    filter: &SubscriptionFilter,
+) -> Vec<Arc<Notification>>
+where
+    REv: From<StorageRequest>,
+{
+    let Some(BlockWithMetadata {
+        block,
+        block_signatures,
+    }) = effect_builder
+        .get_block_at_height_with_metadata_from_storage(height, false)
+        .await
+    else {
+        debug!(height, "block to replay for subscription not found");
+        return vec![];
+    };
+    let block_hash = *block.hash();
+
+    let mut notifications = vec![Arc::new(Notification::BlockAdded(Box::new(block)))];
+    if filter.selects_kind(NotificationKind::TransactionProcessed) {
+        let execution_results = effect_builder
+            .get_execution_results_from_storage(block_hash)
+            .await
+            .unwrap_or_default();
+        for (transaction_hash, transaction_header, execution_result) in execution_results {
+            notifications.push(Arc::new(transaction_processed(
+                transaction_hash,
+                &transaction_header,
+                block_hash,
+                height,
+                execution_result,
+                // Messages aren't stored, so can't be replayed.
+                None,
+            )));
+        }
+    }
+    if filter.selects_kind(NotificationKind::FinalitySignature) {
+        notifications.extend(
+            block_signatures
+                .finality_signatures()
+                .map(|finality_signature| {
+                    Arc::new(Notification::FinalitySignature(Box::new(
+                        finality_signature,
+                    )))
+                }),
+        );
+    }
+    notifications
+}
@@ -6,12 +6,14 @@ use rand::Rng;
 use serde::Serialize;
 
 use casper_binary_port::{
-    BinaryResponse, Command, GetRequest, GlobalStateEntityQualifier, GlobalStateRequest, RecordId,
+    BatchRequest, BatchResponse, BinaryResponse, Command, GetRequest, GlobalStateEntityQualifier,
+    GlobalStateRequest, Notification, RecordId, StateDiffRequest, TransactionProcessed,
 };
 
 use casper_types::{
-    BlockHeader, Digest, GlobalStateIdentifier, KeyTag, PublicKey, Timestamp, Transaction,
-    TransactionV1,
+    bytesrepr, contract_messages::Messages, execution::ExecutionResult, Block, BlockHeader, Digest,
+    FinalitySignature, GlobalStateIdentifier, KeyTag, PublicKey, TestBlockBuilder, Timestamp,
+    Transaction, TransactionV1,
 };
 
 use crate::{
@@ -43,7 +45,7 @@ use crate::{
     effect::{EffectBuilder, EffectExt, Effects, Responder},
     reactor::{self, EventQueueHandle, QueueKind, Reactor, Runner},
     testing::{network::NetworkedReactor, ConditionCheckReactor},
-    types::NodeRng,
+    types::{NodeRng, TransactionHeader},
     utils::Loadable,
 };
 
@@ -56,6 +58,7 @@ struct TestCase {
     allow_request_get_all_values: bool,
     allow_request_get_trie: bool,
     allow_request_speculative_exec: bool,
+    allow_request_get_state_diff: bool,
     request_generator: Either<fn(&mut TestRng) -> Command, Command>,
 }
 
@@ -67,6 +70,7 @@ async fn should_enqueue_requests_for_enabled_functions() {
         allow_request_get_all_values: ENABLED,
         allow_request_get_trie: rng.gen(),
         allow_request_speculative_exec: rng.gen(),
+        allow_request_get_state_diff: rng.gen(),
         request_generator: Either::Left(|_| all_values_request()),
     };
 
@@ -74,6 +78,7 @@ async fn should_enqueue_requests_for_enabled_functions() {
         allow_request_get_all_values: rng.gen(),
         allow_request_get_trie: ENABLED,
         allow_request_speculative_exec: rng.gen(),
+        allow_request_get_state_diff: rng.gen(),
         request_generator: Either::Left(|_| trie_request()),
     };
 
@@ -81,13 +86,23 @@ async fn should_enqueue_requests_for_enabled_functions() {
         allow_request_get_all_values: rng.gen(),
         allow_request_get_trie: rng.gen(),
         allow_request_speculative_exec: ENABLED,
+        allow_request_get_state_diff: rng.gen(),
         request_generator: Either::Left(try_speculative_exec_request),
     };
 
+    let get_state_diff_enabled = TestCase {
+        allow_request_get_all_values: rng.gen(),
+        allow_request_get_trie: rng.gen(),
+        allow_request_speculative_exec: rng.gen(),
+        allow_request_get_state_diff: ENABLED,
+        request_generator: Either::Left(|_| state_diff_request()),
+    };
+
     for test_case in [
         get_all_values_enabled,
         get_trie_enabled,
         try_speculative_exec_enabled,
+        get_state_diff_enabled,
     ] {
         let (_, mut runner) = run_test_case(test_case, &mut rng).await;
 
@@ -111,6 +126,7 @@ async fn should_return_error_for_disabled_functions() {
         allow_request_get_all_values: DISABLED,
         allow_request_get_trie: rng.gen(),
         allow_request_speculative_exec: rng.gen(),
+        allow_request_get_state_diff: rng.gen(),
         request_generator: Either::Left(|_| all_values_request()),
     };
 
@@ -118,6 +134,7 @@ async fn should_return_error_for_disabled_functions() {
         allow_request_get_all_values: rng.gen(),
         allow_request_get_trie: DISABLED,
         allow_request_speculative_exec: rng.gen(),
+        allow_request_get_state_diff: rng.gen(),
         request_generator: Either::Left(|_| trie_request()),
     };
 
@@ -125,13 +142,23 @@ async fn should_return_error_for_disabled_functions() {
         allow_request_get_all_values: rng.gen(),
         allow_request_get_trie: rng.gen(),
         allow_request_speculative_exec: DISABLED,
+        allow_request_get_state_diff: DISABLED,
         request_generator: Either::Left(try_speculative_exec_request),
     };
 
+    let get_state_diff_disabled = TestCase {
+        allow_request_get_all_values: rng.gen(),
+        allow_request_get_trie: rng.gen(),
+        allow_request_speculative_exec: rng.gen(),
+        allow_request_get_state_diff: DISABLED,
+        request_generator: Either::Left(|_| state_diff_request()),
+    };
+
     for test_case in [
         get_all_values_disabled,
         get_trie_disabled,
         try_speculative_exec_disabled,
+        get_state_diff_disabled,
     ] {
         let (receiver, mut runner) = run_test_case(test_case, &mut rng).await;
 
@@ -159,6 +186,7 @@ async fn should_return_empty_response_when_fetching_empty_key() {
             allow_request_get_all_values: DISABLED,
             allow_request_get_trie: DISABLED,
             allow_request_speculative_exec: DISABLED,
+            allow_request_get_state_diff: DISABLED,
             request_generator: Either::Right(request),
         })
         .collect();
@@ -181,11 +209,105 @@ async fn should_return_empty_response_when_fetching_empty_key() {
     }
 }
 
+#[tokio::test]
+async fn should_respond_to_each_request_of_a_batch() {
+    let mut rng = TestRng::new();
+
+    let state_root_hash = Digest::hash([2u8; 32]);
+    let batch = BatchRequest::new(
+        Some(GlobalStateIdentifier::StateRootHash(state_root_hash)),
+        vec![
+            GetRequest::Record {
+                record_type_tag: RecordId::BlockHeader.into(),
+                key: vec![],
+            },
+            GetRequest::State(Box::new(GlobalStateRequest::new(
+                Some(GlobalStateIdentifier::BlockHeight(1)),
+                GlobalStateEntityQualifier::AllItems {
+                    key_tag: KeyTag::Account,
+                },
+            ))),
+            GetRequest::Trie {
+                trie_key: Digest::hash([1u8; 32]),
+            },
+        ],
+    );
+    let test_case = TestCase {
+        allow_request_get_all_values: DISABLED,
+        allow_request_get_trie: DISABLED,
+        allow_request_speculative_exec: DISABLED,
+        allow_request_get_state_diff: DISABLED,
+        request_generator: Either::Right(Command::Batch(batch)),
+    };
+    let (receiver, mut runner) = run_test_case(test_case, &mut rng).await;
+
+    let result = tokio::select! {
+        result = receiver => result.expect("expected successful response"),
+        _ = runner.crank_until(
+            &mut rng,
+            got_contract_runtime_request,
+            Duration::from_secs(10),
+        ) => {
+            panic!("expected receiver to complete first")
+        }
+    };
+    assert_eq!(result.error_code(), 0);
+    let batch_response: BatchResponse =
+        bytesrepr::deserialize_from_slice(result.payload()).expect("should deserialize");
+    assert_eq!(batch_response.state_root_hash(), &state_root_hash);
+    let error_codes: Vec<u16> = batch_response
+        .responses()
+        .iter()
+        .map(BinaryResponse::error_code)
+        .collect();
+    assert_eq!(
+        error_codes,
+        vec![
+            0,
+            ErrorCode::BadRequest as u16,
+            ErrorCode::FunctionDisabled as u16
+        ]
+    );
+}
+
+#[tokio::test]
+async fn should_reject_too_large_batch() {
+    let mut rng = TestRng::new();
+
+    let max_batch_size = BinaryPortConfig::default().max_batch_size;
+    let requests = (0..=max_batch_size)
+        .map(|_| GetRequest::Trie {
+            trie_key: Digest::hash([1u8; 32]),
+        })
+        .collect();
+    let test_case = TestCase {
+        allow_request_get_all_values: DISABLED,
+        allow_request_get_trie: DISABLED,
+        allow_request_speculative_exec: DISABLED,
+        allow_request_get_state_diff: DISABLED,
+        request_generator: Either::Right(Command::Batch(BatchRequest::new(None, requests))),
+    };
+    let (receiver, mut runner) = run_test_case(test_case, &mut rng).await;
+
+    let result = tokio::select! {
+        result = receiver => result.expect("expected successful response"),
+        _ = runner.crank_until(
+            &mut rng,
+            got_contract_runtime_request,
+            Duration::from_secs(10),
+        ) => {
+            panic!("expected receiver to complete first")
+        }
+    };
+    assert_eq!(result.error_code(), ErrorCode::BadRequest as u16);
+}
+
 async fn run_test_case(
     TestCase {
         allow_request_get_all_values,
         allow_request_get_trie,
         allow_request_speculative_exec,
+        allow_request_get_state_diff,
         request_generator,
     }: TestCase,
     rng: &mut TestRng,
@@ -198,6 +320,7 @@ async fn run_test_case(
         allow_request_get_all_values,
         allow_request_get_trie,
         allow_request_speculative_exec,
+        allow_request_get_state_diff,
         max_message_size_bytes: 1024,
         max_connections: 2,
         ..Default::default()
@@ -246,6 +369,113 @@ async fn run_test_case(
     (receiver, runner)
 }
 
+#[tokio::test]
+async fn should_push_notifications_to_subscribers() {
+    let mut rng = TestRng::new();
+
+    let (chainspec, chainspec_raw_bytes) =
+        <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+    let mut runner: Runner<ConditionCheckReactor<MockReactor>> = Runner::new(
+        BinaryPortConfig::default(),
+        Arc::new(chainspec),
+        Arc::new(chainspec_raw_bytes),
+        &mut rng,
+    )
+    .await
+    .unwrap();
+    runner
+        .process_injected_effects(|effect_builder| {
+            effect_builder
+                .into_inner()
+                .schedule(BinaryPortEvent::Initialize, QueueKind::Api)
+                .ignore()
+        })
+        .await;
+    runner.crank(&mut rng).await;
+
+    let mut subscriber = runner
+        .reactor()
+        .inner()
+        .binary_port
+        .notifications
+        .subscribe();
+
+    let block = Arc::new(Block::from(TestBlockBuilder::new().build(&mut rng)));
+    let transaction = Transaction::random(&mut rng);
+    let execution_result = ExecutionResult::random(&mut rng);
+    let finality_signature = FinalitySignature::random(&mut rng);
+    for event in [
+        BinaryPortEvent::BlockAdded(Arc::clone(&block)),
+        BinaryPortEvent::TransactionProcessed {
+            transaction_hash: transaction.hash(),
+            transaction_header: Box::new(TransactionHeader::from(&transaction)),
+            block_hash: *block.hash(),
+            block_height: block.height(),
+            execution_result: Box::new(execution_result.clone()),
+            messages: Some(Messages::new()),
+        },
+        BinaryPortEvent::TransactionProcessed {
+            transaction_hash: transaction.hash(),
+            transaction_header: Box::new(TransactionHeader::from(&transaction)),
+            block_hash: *block.hash(),
+            block_height: block.height(),
+            execution_result: Box::new(execution_result.clone()),
+            messages: None,
+        },
+        BinaryPortEvent::FinalitySignature(Box::new(finality_signature.clone())),
+    ] {
+        runner
+            .process_injected_effects(|effect_builder| {
+                effect_builder
+                    .into_inner()
+                    .schedule(event, QueueKind::Api)
+                    .ignore()
+            })
+            .await;
+        runner.crank(&mut rng).await;
+    }
+
+    assert_eq!(
+        *subscriber.try_recv().expect("should receive block added"),
+        Notification::BlockAdded(Box::new((*block).clone()))
+    );
+    // Messages pushed live are known, even if there are none.
+    assert_eq!(
+        *subscriber
+            .try_recv()
+            .expect("should receive transaction processed"),
+        Notification::TransactionProcessed(Box::new(TransactionProcessed::new(
+            transaction.hash(),
+            transaction.initiator_addr(),
+            *block.hash(),
+            block.height(),
+            execution_result.clone(),
+            Some(Messages::new()),
+        )))
+    );
+    // The messages of historically synced blocks aren't known.
+    assert_eq!(
+        *subscriber
+            .try_recv()
+            .expect("should receive transaction processed"),
+        Notification::TransactionProcessed(Box::new(TransactionProcessed::new(
+            transaction.hash(),
+            transaction.initiator_addr(),
+            *block.hash(),
+            block.height(),
+            execution_result,
+            None,
+        )))
+    );
+    assert_eq!(
+        *subscriber
+            .try_recv()
+            .expect("should receive finality signature"),
+        Notification::FinalitySignature(Box::new(finality_signature))
+    );
+    assert!(subscriber.try_recv().is_err());
+}
+
 struct MockReactor {
     binary_port: BinaryPort,
 }
@@ -445,9 +675,19 @@ fn trie_request() -> Command {
     })
 }
 
+fn state_diff_request() -> Command {
+    Command::Get(GetRequest::StateDiff(Box::new(StateDiffRequest::new(
+        Digest::hash([1u8; 32]),
+        Digest::hash([2u8; 32]),
+    ))))
+}
+
 fn try_speculative_exec_request(rng: &mut TestRng) -> Command {
     Command::TrySpeculativeExec {
         transaction: Transaction::V1(TransactionV1::random(rng)),
+        state_identifier: None,
+        state_overrides: vec![],
+        trace_execution: false,
     }
 }
 
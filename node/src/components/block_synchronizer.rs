@@ -53,7 +53,7 @@ use crate::{
     reactor::{self, main_reactor::MainEvent},
     types::{
         sync_leap_validation_metadata::SyncLeapValidationMetaData, BlockExecutionResultsOrChunk,
-        ExecutableBlock, LegacyDeploy, MetaBlock, MetaBlockState, NodeId, SyncLeap,
+        ExecutableBlock, LegacyDeploy, MetaBlock, MetaBlockState, NodeId, PeerReputation, SyncLeap,
         SyncLeapIdentifier, TrieOrChunk, ValidatorMatrix,
     },
     NodeRng,
@@ -138,6 +138,7 @@ pub(crate) struct BlockSynchronizer {
     chainspec: Arc<Chainspec>,
     max_simultaneous_peers: u8,
     validator_matrix: ValidatorMatrix,
+    peer_reputation: PeerReputation,
 
     // execute forward block (do not get global state or execution effects)
     forward: Option<BlockBuilder>,
@@ -155,6 +156,7 @@ impl BlockSynchronizer {
         chainspec: Arc<Chainspec>,
         max_simultaneous_peers: u8,
         validator_matrix: ValidatorMatrix,
+        peer_reputation: PeerReputation,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(BlockSynchronizer {
@@ -163,9 +165,13 @@ impl BlockSynchronizer {
             chainspec,
             max_simultaneous_peers,
             validator_matrix,
+            global_sync: GlobalStateSynchronizer::new(
+                config.max_parallel_trie_fetches as usize,
+                peer_reputation.clone(),
+            ),
+            peer_reputation,
             forward: None,
             historical: None,
-            global_sync: GlobalStateSynchronizer::new(config.max_parallel_trie_fetches as usize),
             metrics: Metrics::new(registry)?,
         })
     }
@@ -234,6 +240,7 @@ impl BlockSynchronizer {
             self.chainspec
                 .core_config
                 .start_protocol_version_with_strict_finality_signatures_required,
+            self.peer_reputation.clone(),
         );
         if should_fetch_execution_state {
             self.historical.replace(builder);
@@ -282,6 +289,7 @@ impl BlockSynchronizer {
                     self.chainspec
                         .core_config
                         .start_protocol_version_with_strict_finality_signatures_required,
+                    self.peer_reputation.clone(),
                 );
                 apply_sigs(&mut builder, maybe_sigs);
                 if should_fetch_execution_state {
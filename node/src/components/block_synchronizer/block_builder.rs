@@ -29,7 +29,8 @@ use super::{
 use crate::{
     components::block_synchronizer::block_builder::latch::Latch,
     types::{
-        BlockExecutionResultsOrChunk, EraValidatorWeights, ExecutableBlock, NodeId, ValidatorMatrix,
+        BlockExecutionResultsOrChunk, EraValidatorWeights, ExecutableBlock, NodeId, PeerReputation,
+        ValidatorMatrix,
     },
     NodeRng,
 };
@@ -114,6 +115,7 @@ impl BlockBuilder {
         peer_refresh_interval: TimeDiff,
         legacy_required_finality: LegacyRequiredFinality,
         strict_finality_protocol_version: ProtocolVersion,
+        peer_reputation: PeerReputation,
     ) -> Self {
         BlockBuilder {
             block_hash,
@@ -123,7 +125,11 @@ impl BlockBuilder {
                 block_hash,
                 SignatureAcquisition::new(vec![], legacy_required_finality),
             ),
-            peer_list: PeerList::new(max_simultaneous_peers, peer_refresh_interval),
+            peer_list: PeerList::new(
+                max_simultaneous_peers,
+                peer_refresh_interval,
+                peer_reputation,
+            ),
             should_fetch_execution_state,
             strict_finality_protocol_version,
             sync_start: Instant::now(),
@@ -144,6 +150,7 @@ impl BlockBuilder {
         peer_refresh_interval: TimeDiff,
         legacy_required_finality: LegacyRequiredFinality,
         strict_finality_protocol_version: ProtocolVersion,
+        peer_reputation: PeerReputation,
     ) -> Self {
         let block_hash = block_header.block_hash();
         let era_id = Some(block_header.era_id());
@@ -161,7 +168,11 @@ impl BlockBuilder {
             Box::new(block_header),
             signature_acquisition,
         );
-        let mut peer_list = PeerList::new(max_simultaneous_peers, peer_refresh_interval);
+        let mut peer_list = PeerList::new(
+            max_simultaneous_peers,
+            peer_refresh_interval,
+            peer_reputation,
+        );
         peers.iter().for_each(|p| peer_list.register_peer(*p));
 
         BlockBuilder {
@@ -21,6 +21,7 @@ fn handle_acceptance_promotes_and_disqualifies_peers() {
         TimeDiff::from_seconds(1),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
+        PeerReputation::new(),
     );
 
     let honest_peer = NodeId::random(&mut rng);
@@ -109,6 +110,7 @@ fn handle_acceptance_unlatches_builder() {
         TimeDiff::from_seconds(1),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
+        PeerReputation::new(),
     );
 
     // Check that if a valid element was received, the latch is reset
@@ -174,6 +176,7 @@ fn register_era_validator_weights() {
         TimeDiff::from_seconds(1),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
+        PeerReputation::new(),
     );
     let latest_timestamp = builder.last_progress;
 
@@ -224,6 +227,7 @@ fn register_executable_block() {
         TimeDiff::from_seconds(1),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
+        PeerReputation::new(),
     );
     let mut latest_timestamp = builder.last_progress;
     // Create mock era weights for the block's era.
@@ -300,6 +304,7 @@ fn register_block_execution() {
         TimeDiff::from_seconds(1),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
+        PeerReputation::new(),
     );
     let mut latest_timestamp = builder.last_progress;
     // Create mock era weights for the block's era.
@@ -383,6 +388,7 @@ fn register_block_executed() {
         TimeDiff::from_seconds(1),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
+        PeerReputation::new(),
     );
     let mut latest_timestamp = builder.last_progress;
     // Create mock era weights for the block's era.
@@ -455,6 +461,7 @@ fn register_block_marked_complete() {
         TimeDiff::from_seconds(1),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
+        PeerReputation::new(),
     );
     // Make the builder historical.
     builder.should_fetch_execution_state = true;
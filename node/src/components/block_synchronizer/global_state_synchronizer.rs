@@ -29,7 +29,7 @@ use crate::{
         EffectBuilder, EffectExt, Effects, Responder,
     },
     reactor,
-    types::{NodeId, TrieOrChunk},
+    types::{NodeId, PeerReputation, TrieOrChunk},
     NodeRng,
 };
 
@@ -233,10 +233,10 @@ pub(super) struct GlobalStateSynchronizer {
 }
 
 impl GlobalStateSynchronizer {
-    pub(super) fn new(max_parallel_trie_fetches: usize) -> Self {
+    pub(super) fn new(max_parallel_trie_fetches: usize, peer_reputation: PeerReputation) -> Self {
         Self {
             max_parallel_trie_fetches,
-            trie_accumulator: TrieAccumulator::new(),
+            trie_accumulator: TrieAccumulator::new(peer_reputation),
             request_state: None,
             tries_awaiting_children: Default::default(),
             fetch_queue: Default::default(),
@@ -106,7 +106,8 @@ fn random_sync_global_state_request(
 async fn fetch_request_without_peers_is_canceled() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(rng.gen_range(2..10));
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(rng.gen_range(2..10), PeerReputation::new());
 
     // Create a responder to allow assertion of the error
     let (sender, receiver) = oneshot::channel();
@@ -147,7 +148,8 @@ async fn sync_global_state_request_starts_maximum_trie_fetches() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
     let parallel_fetch_limit = rng.gen_range(2..10);
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(parallel_fetch_limit);
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(parallel_fetch_limit, PeerReputation::new());
 
     let mut progress = Timestamp::now();
 
@@ -258,7 +260,7 @@ async fn trie_accumulator_error_cancels_request() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
     // Set the parallel fetch limit to allow only 1 fetch
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(1);
+    let mut global_state_synchronizer = GlobalStateSynchronizer::new(1, PeerReputation::new());
 
     // Create and register one request
     let (sender, receiver1) = oneshot::channel();
@@ -339,7 +341,8 @@ async fn trie_accumulator_error_cancels_request() {
 async fn successful_trie_fetch_puts_trie_to_store() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(rng.gen_range(2..10));
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(rng.gen_range(2..10), PeerReputation::new());
 
     // Create a request
     let (request, trie) = random_sync_global_state_request(
@@ -393,7 +396,8 @@ async fn successful_trie_fetch_puts_trie_to_store() {
 async fn trie_store_error_cancels_request() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(rng.gen_range(2..10));
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(rng.gen_range(2..10), PeerReputation::new());
 
     // Create a request
     let (sender, receiver) = oneshot::channel();
@@ -447,7 +451,8 @@ async fn missing_trie_node_children_triggers_fetch() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
     let parallel_fetch_limit = rng.gen_range(2..10);
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(parallel_fetch_limit);
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(parallel_fetch_limit, PeerReputation::new());
 
     // Create a request
     let (request, request_trie) = random_sync_global_state_request(
@@ -648,7 +653,8 @@ async fn stored_trie_finalizes_request() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
     let parallel_fetch_limit = rng.gen_range(2..10);
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(parallel_fetch_limit);
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(parallel_fetch_limit, PeerReputation::new());
 
     // Create a request
     let (sender, receiver) = oneshot::channel();
@@ -5,10 +5,12 @@ use std::collections::{btree_map::Entry, BTreeMap};
 
 use datasize::DataSize;
 use itertools::Itertools;
-use rand::seq::IteratorRandom;
 use tracing::debug;
 
-use crate::{types::NodeId, NodeRng};
+use crate::{
+    types::{NodeId, PeerReputation},
+    NodeRng,
+};
 use casper_types::{TimeDiff, Timestamp};
 
 #[derive(Copy, Clone, PartialEq, Eq, DataSize, Debug, Default)]
@@ -26,21 +28,28 @@ pub(super) enum PeersStatus {
     Stale,
 }
 
-#[derive(Clone, PartialEq, Eq, DataSize, Debug)]
+#[derive(Clone, DataSize, Debug)]
 pub(super) struct PeerList {
     peer_list: BTreeMap<NodeId, PeerQuality>,
     keep_fresh: Timestamp,
     max_simultaneous_peers: u8,
     peer_refresh_interval: TimeDiff,
+    #[data_size(skip)]
+    peer_reputation: PeerReputation,
 }
 
 impl PeerList {
-    pub(super) fn new(max_simultaneous_peers: u8, peer_refresh_interval: TimeDiff) -> Self {
+    pub(super) fn new(
+        max_simultaneous_peers: u8,
+        peer_refresh_interval: TimeDiff,
+        peer_reputation: PeerReputation,
+    ) -> Self {
         PeerList {
             peer_list: BTreeMap::new(),
             keep_fresh: Timestamp::now(),
             max_simultaneous_peers,
             peer_refresh_interval,
+            peer_reputation,
         }
     }
     pub(super) fn register_peer(&mut self, peer: NodeId) {
@@ -143,19 +152,21 @@ impl PeerList {
         PeersStatus::Sufficient
     }
 
+    /// Chooses up to `up_to` peers of the given quality, preferring peers with a better
+    /// reputation.
     fn get_random_peers_by_quality(
         &self,
         rng: &mut NodeRng,
         up_to: usize,
         peer_quality: PeerQuality,
     ) -> Vec<NodeId> {
-        self.peer_list
+        let peers = self
+            .peer_list
             .iter()
             .filter(|(_peer, quality)| **quality == peer_quality)
-            .choose_multiple(rng, up_to)
-            .into_iter()
             .map(|(peer, _)| *peer)
-            .collect()
+            .collect();
+        self.peer_reputation.select_peers(None, peers, up_to, rng)
     }
 
     pub(super) fn qualified_peers(&self, rng: &mut NodeRng) -> Vec<NodeId> {
@@ -25,7 +25,7 @@ fn random_peers(rng: &mut TestRng, num_random_peers: usize) -> HashSet<NodeId> {
 #[test]
 fn number_of_qualified_peers_is_correct() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerReputation::new());
 
     let test_peers: Vec<NodeId> = random_peers(&mut rng, 10).into_iter().collect();
 
@@ -65,7 +65,7 @@ fn number_of_qualified_peers_is_correct() {
 #[test]
 fn unknown_peer_becomes_reliable_when_promoted() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerReputation::new());
     let test_peer = NodeId::random(&mut rng);
 
     peer_list.register_peer(test_peer);
@@ -77,7 +77,7 @@ fn unknown_peer_becomes_reliable_when_promoted() {
 #[test]
 fn unknown_peer_becomes_unreliable_when_demoted() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerReputation::new());
     let test_peer = NodeId::random(&mut rng);
 
     peer_list.register_peer(test_peer);
@@ -89,7 +89,7 @@ fn unknown_peer_becomes_unreliable_when_demoted() {
 #[test]
 fn reliable_peer_becomes_unreliable_when_demoted() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerReputation::new());
     let test_peer = NodeId::random(&mut rng);
 
     peer_list.register_peer(test_peer);
@@ -103,7 +103,7 @@ fn reliable_peer_becomes_unreliable_when_demoted() {
 #[test]
 fn unreliable_peer_becomes_reliable_when_promoted() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerReputation::new());
     let test_peer = NodeId::random(&mut rng);
 
     peer_list.register_peer(test_peer);
@@ -117,7 +117,7 @@ fn unreliable_peer_becomes_reliable_when_promoted() {
 #[test]
 fn unreliable_peer_remains_unreliable_if_demoted() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerReputation::new());
     let test_peer = NodeId::random(&mut rng);
 
     peer_list.register_peer(test_peer);
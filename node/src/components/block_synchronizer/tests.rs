@@ -29,6 +29,7 @@ use crate::{
     },
     effect::Effect,
     reactor::{EventQueueHandle, QueueKind, Scheduler},
+    signer::LocalSigner,
     tls::KeyFingerprint,
     types::{BlockExecutionResultsOrChunkId, ValueOrChunk},
     utils,
@@ -148,8 +149,7 @@ impl TestEnv {
             ChainNameDigest::from_chain_name("casper-example"),
             None,
             EraId::from(0),
-            self.validator_keys[0].clone(),
-            PublicKey::from(self.validator_keys[0].as_ref()),
+            Arc::new(LocalSigner::new(self.validator_keys[0].clone())),
             1,
             3,
         );
@@ -276,6 +276,7 @@ impl BlockSynchronizer {
             Arc::new(Chainspec::random(rng)),
             MAX_SIMULTANEOUS_PEERS,
             validator_matrix,
+            PeerReputation::new(),
             &Registry::new(),
         )
         .expect("Failed to create BlockSynchronizer");
@@ -8,7 +8,6 @@ use std::{
 
 use datasize::DataSize;
 use derive_more::From;
-use rand::seq::SliceRandom;
 use serde::Serialize;
 use thiserror::Error;
 use tracing::{debug, error, trace, warn};
@@ -20,6 +19,7 @@ use crate::{
     components::{
         fetcher::{
             EmptyValidationMetadata, Error as FetcherError, FetchItem, FetchResult, FetchedData,
+            Tag,
         },
         Component,
     },
@@ -28,7 +28,7 @@ use crate::{
         requests::{FetcherRequest, TrieAccumulatorRequest},
         EffectBuilder, EffectExt, Effects, Responder,
     },
-    types::{NodeId, TrieOrChunk, TrieOrChunkId},
+    types::{NodeId, PeerReputation, TrieOrChunk, TrieOrChunkId},
     NodeRng,
 };
 
@@ -124,6 +124,8 @@ impl PartialChunks {
 #[derive(DataSize, Debug)]
 pub(super) struct TrieAccumulator {
     partial_chunks: HashMap<Digest, PartialChunks>,
+    #[data_size(skip)]
+    peer_reputation: PeerReputation,
 }
 
 #[derive(DataSize, Debug, From, Serialize)]
@@ -148,9 +150,10 @@ impl fmt::Display for Event {
 }
 
 impl TrieAccumulator {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(peer_reputation: PeerReputation) -> Self {
         TrieAccumulator {
             partial_chunks: Default::default(),
+            peer_reputation,
         }
     }
 
@@ -274,7 +277,11 @@ where
                 responder,
                 mut peers,
             }) => {
-                peers.shuffle(rng);
+                // Peers are tried starting from the back of the list, so put the most preferred
+                // ones last.
+                self.peer_reputation
+                    .rank_peers(Some(Tag::TrieOrChunk), &mut peers, rng);
+                peers.reverse();
                 let trie_id = TrieOrChunkId(0, hash);
                 let peer = match peers.last() {
                     Some(peer) => *peer,
@@ -2,39 +2,45 @@ use std::sync::Arc;
 
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use tracing::{info, warn};
 
-use casper_types::{crypto, Digest, PublicKey, SecretKey, Signature};
+#[cfg(test)]
+use casper_types::SecretKey;
+use casper_types::{crypto, Digest, EraId, PublicKey, Signature};
 
+#[cfg(test)]
+use crate::signer::LocalSigner;
 use crate::{
     components::consensus::traits::{ConsensusValueT, Context, ValidatorSecret},
+    signer::{ConsensusPayload, SigningRequest, ValidatorSigner},
     types::BlockPayload,
 };
 
+/// The validator's signer, used to sign consensus messages in a particular era.
 #[derive(DataSize)]
 pub struct Keypair {
-    secret_key: Arc<SecretKey>,
-    public_key: PublicKey,
+    #[data_size(skip)]
+    signer: Arc<dyn ValidatorSigner>,
+    era_id: EraId,
 }
 
 impl Keypair {
-    pub(crate) fn new(secret_key: Arc<SecretKey>, public_key: PublicKey) -> Self {
-        Self {
-            secret_key,
-            public_key,
-        }
+    pub(crate) fn new(signer: Arc<dyn ValidatorSigner>, era_id: EraId) -> Self {
+        Self { signer, era_id }
     }
 
     #[cfg(test)]
     pub(crate) fn public_key(&self) -> &PublicKey {
-        &self.public_key
+        self.signer.public_key()
     }
 }
 
+/// Creates a keypair that signs conflicting messages, so tests can create equivocations.
+#[cfg(test)]
 impl From<Arc<SecretKey>> for Keypair {
     fn from(secret_key: Arc<SecretKey>) -> Self {
-        let public_key: PublicKey = secret_key.as_ref().into();
-        Self::new(secret_key, public_key)
+        let signer = Arc::new(LocalSigner::without_history(secret_key));
+        Self::new(signer, EraId::default())
     }
 }
 
@@ -42,8 +48,18 @@ impl ValidatorSecret for Keypair {
     type Hash = Digest;
     type Signature = Signature;
 
-    fn sign(&self, hash: &Digest) -> Signature {
-        crypto::sign(hash, self.secret_key.as_ref(), &self.public_key)
+    fn sign(&self, payload: ConsensusPayload) -> Option<Signature> {
+        let request = SigningRequest::Consensus {
+            era_id: self.era_id,
+            payload,
+        };
+        match self.signer.sign(&request) {
+            Ok(signature) => Some(signature),
+            Err(error) => {
+                warn!(%error, era_id = %self.era_id, "failed to sign consensus message");
+                None
+            }
+        }
     }
 }
 
@@ -82,18 +98,18 @@ impl Context for ClContext {
 
 mod specimen_support {
     use super::Keypair;
-    use crate::utils::specimen::{Cache, LargestSpecimen, SizeEstimator};
-    use casper_types::{PublicKey, SecretKey};
+    use crate::{
+        signer::LocalSigner,
+        utils::specimen::{Cache, LargestSpecimen, SizeEstimator},
+    };
+    use casper_types::{EraId, SecretKey};
     use std::sync::Arc;
 
     impl LargestSpecimen for Keypair {
         fn largest_specimen<E: SizeEstimator>(estimator: &E, cache: &mut Cache) -> Self {
             let secret_key = SecretKey::largest_specimen(estimator, cache);
-            let public_key = PublicKey::from(&secret_key);
-            Keypair {
-                secret_key: Arc::new(secret_key),
-                public_key,
-            }
+            let signer = LocalSigner::new(Arc::new(secret_key));
+            Keypair::new(Arc::new(signer), EraId::default())
         }
     }
 }
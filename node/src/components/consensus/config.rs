@@ -11,6 +11,7 @@ use crate::{
         protocols::{highway::config::Config as HighwayConfig, zug::config::Config as ZugConfig},
         EraId,
     },
+    signer::{LocalSigner, RemoteSigner, RemoteSignerConfig, SignerError, ValidatorSigner},
     utils::{External, LoadError, Loadable},
 };
 
@@ -23,6 +24,11 @@ const DEFAULT_MAX_EXECUTION_DELAY: u64 = 3;
 pub struct Config {
     /// Path to secret key file.
     pub secret_key_path: External,
+    /// Remote signing daemon holding the secret key.
+    ///
+    /// If set, `secret_key_path` is ignored and all signatures are created by the daemon instead.
+    #[serde(default)]
+    pub remote_signer: Option<RemoteSignerConfig>,
     /// The maximum number of blocks by which execution is allowed to lag behind finalization.
     /// If it is more than that, consensus will pause, and resume once the executor has caught up.
     pub max_execution_delay: u64,
@@ -38,6 +44,7 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             secret_key_path: External::Missing,
+            remote_signer: None,
             max_execution_delay: DEFAULT_MAX_EXECUTION_DELAY,
             highway: HighwayConfig::default(),
             zug: ZugConfig::default(),
@@ -57,6 +64,23 @@ impl Config {
         let public_key: PublicKey = PublicKey::from(secret_signing_key.as_ref());
         Ok((secret_signing_key, public_key))
     }
+
+    /// Creates the validator signer: connects to the remote signer if one is configured, otherwise
+    /// loads the secret key.
+    pub(crate) fn load_signer<P: AsRef<Path>>(
+        &self,
+        root: P,
+    ) -> Result<Arc<dyn ValidatorSigner>, SignerError> {
+        match &self.remote_signer {
+            Some(remote_signer_config) => Ok(Arc::new(RemoteSigner::connect(
+                remote_signer_config.clone(),
+            )?)),
+            None => {
+                let (secret_key, _) = self.load_keys(root)?;
+                Ok(Arc::new(LocalSigner::new(secret_key)))
+            }
+        }
+    }
 }
 
 pub trait ChainspecConsensusExt {
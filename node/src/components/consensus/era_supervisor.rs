@@ -383,10 +383,7 @@ impl EraSupervisor {
             vec![]
         } else {
             info!(era = era_id.value(), %our_id, "start voting");
-            let secret = Keypair::new(
-                self.validator_matrix.secret_signing_key().clone(),
-                our_id.clone(),
-            );
+            let secret = Keypair::new(self.validator_matrix.signer().clone(), era_id);
             let instance_id = self.era(era_id).consensus.instance_id();
             let unit_hash_file = self.protocol_state_file(instance_id);
             self.era_mut(era_id).consensus.activate_validator(
@@ -576,10 +573,7 @@ impl EraSupervisor {
                 info!(era = era_id.value(), %our_id, "not voting; not a validator");
             } else {
                 info!(era = era_id.value(), %our_id, "start voting");
-                let secret = Keypair::new(
-                    self.validator_matrix.secret_signing_key().clone(),
-                    our_id.clone(),
-                );
+                let secret = Keypair::new(self.validator_matrix.signer().clone(), era_id);
                 let unit_hash_file = self.protocol_state_file(&instance_id);
                 outcomes.extend(self.era_mut(era_id).consensus.activate_validator(
                     our_id,
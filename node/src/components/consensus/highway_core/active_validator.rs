@@ -110,7 +110,7 @@ impl<C: Context> ActiveValidator<C> {
             paused: false,
         };
         let mut effects = av.schedule_timer(start_time, state);
-        effects.push(av.send_ping(current_time, instance_id));
+        effects.extend(av.send_ping(current_time, instance_id));
         (av, effects)
     }
 
@@ -173,15 +173,19 @@ impl<C: Context> ActiveValidator<C> {
             one_max_round_ago.saturating_add(TimeDiff::from_millis(1)),
         ) {
             warn!(%timestamp, "too many validators offline, sending ping");
-            effects.push(self.send_ping(timestamp, instance_id));
+            effects.extend(self.send_ping(timestamp, instance_id));
         }
         effects
     }
 
-    /// Creates a Ping vertex.
-    pub(crate) fn send_ping(&self, timestamp: Timestamp, instance_id: C::InstanceId) -> Effect<C> {
-        let ping = Ping::new(self.vidx, timestamp, instance_id, &self.secret);
-        Effect::NewVertex(ValidVertex(Vertex::Ping(ping)))
+    /// Creates a Ping vertex, or returns `None` if it couldn't be signed.
+    pub(crate) fn send_ping(
+        &self,
+        timestamp: Timestamp,
+        instance_id: C::InstanceId,
+    ) -> Option<Effect<C>> {
+        let ping = Ping::new(self.vidx, timestamp, instance_id, &self.secret)?;
+        Some(Effect::NewVertex(ValidVertex(Vertex::Ping(ping))))
     }
 
     /// Returns whether enough validators are online to finalize values with the target fault
@@ -225,8 +229,9 @@ impl<C: Context> ActiveValidator<C> {
             }
         };
         if self.should_endorse(uhash, state) {
-            let endorsement = self.endorse(uhash);
-            effects.extend(vec![Effect::NewVertex(ValidVertex(endorsement))]);
+            if let Some(endorsement) = self.endorse(uhash) {
+                effects.push(Effect::NewVertex(ValidVertex(endorsement)));
+            }
         }
         effects
     }
@@ -249,7 +254,7 @@ impl<C: Context> ActiveValidator<C> {
                 let unit = state.unit(v);
                 unit.new_hash_obs(state, vidx)
             })
-            .map(|v| self.endorse(v))
+            .filter_map(|v| self.endorse(v))
             .map(|endorsement| Effect::NewVertex(ValidVertex(endorsement)))
             .collect()
     }
@@ -370,7 +375,8 @@ impl<C: Context> ActiveValidator<C> {
 
     /// Returns a new unit with the given data, and the correct sequence number.
     ///
-    /// Returns `None` if it's not possible to create a valid unit with the given panorama.
+    /// Returns `None` if it's not possible to create a valid unit with the given panorama, or to
+    /// sign it.
     fn new_unit(
         &mut self,
         panorama: Panorama<C>,
@@ -417,8 +423,7 @@ impl<C: Context> ActiveValidator<C> {
             endorsed,
         }
         .into_hashed();
-        let swunit = SignedWireUnit::new(hwunit, &self.secret);
-        Some(swunit)
+        SignedWireUnit::new(hwunit, &self.secret)
     }
 
     /// Returns a `ScheduleTimer` effect for the next time we need to be called.
@@ -524,11 +529,13 @@ impl<C: Context> ActiveValidator<C> {
                 .any(|(vidx, _)| state.is_faulty(vidx) && unit.new_hash_obs(state, vidx))
     }
 
-    /// Creates endorsement of the `vhash`.
-    fn endorse(&self, vhash: &C::Hash) -> Vertex<C> {
+    /// Creates endorsement of the `vhash`, or returns `None` if it couldn't be signed.
+    fn endorse(&self, vhash: &C::Hash) -> Option<Vertex<C>> {
         let endorsement = Endorsement::new(*vhash, self.vidx);
-        let signature = self.secret.sign(&endorsement.hash());
-        Vertex::Endorsements(SignedEndorsement::new(endorsement, signature).into())
+        let signature = self.secret.sign(endorsement.signing_payload())?;
+        Some(Vertex::Endorsements(
+            SignedEndorsement::new(endorsement, signature).into(),
+        ))
     }
 
     /// Returns a panorama that is valid to use in our own unit at the given timestamp.
@@ -904,7 +911,8 @@ mod tests {
             TEST_INSTANCE_ID,
         );
 
-        let ping = Vertex::Ping(Ping::new(ALICE, 500.into(), TEST_INSTANCE_ID, &ALICE_SEC));
+        let ping =
+            Vertex::Ping(Ping::new(ALICE, 500.into(), TEST_INSTANCE_ID, &ALICE_SEC).unwrap());
 
         // The ping is suspicious if it is newer than the latest ping (or unit) that has been added
         // to the state.
@@ -2,7 +2,10 @@ use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::components::consensus::{traits::Context, utils::ValidatorIndex};
+use crate::{
+    components::consensus::{traits::Context, utils::ValidatorIndex},
+    signer::ConsensusPayload,
+};
 
 /// An error due to an invalid endorsement.
 #[derive(Debug, Error, Eq, PartialEq)]
@@ -42,11 +45,16 @@ impl<C: Context> Endorsement<C> {
         }
     }
 
+    /// Returns the serialized endorsement, whose hash is signed.
+    pub(crate) fn signing_payload(&self) -> ConsensusPayload {
+        ConsensusPayload::HighwayEndorsement(
+            bincode::serialize(&(self.unit, self.creator)).expect("serialize endorsement"),
+        )
+    }
+
     /// Returns the hash of the endorsement.
     pub fn hash(&self) -> C::Hash {
-        <C as Context>::hash(
-            &bincode::serialize(&(self.unit, self.creator)).expect("serialize endorsement"),
-        )
+        <C as Context>::hash(self.signing_payload().serialized())
     }
 }
 
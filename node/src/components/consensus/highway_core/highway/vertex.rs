@@ -5,14 +5,17 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use casper_types::Timestamp;
 
-use crate::components::consensus::{
-    highway_core::{
-        endorsement::SignedEndorsement,
-        highway::{PingError, VertexError},
-        state::Panorama,
+use crate::{
+    components::consensus::{
+        highway_core::{
+            endorsement::SignedEndorsement,
+            highway::{PingError, VertexError},
+            state::Panorama,
+        },
+        traits::{Context, ValidatorSecret},
+        utils::{ValidatorIndex, Validators},
     },
-    traits::{Context, ValidatorSecret},
-    utils::{ValidatorIndex, Validators},
+    signer::ConsensusPayload,
 };
 
 #[allow(clippy::arithmetic_side_effects)]
@@ -318,15 +321,16 @@ where
 }
 
 impl<C: Context> SignedWireUnit<C> {
+    /// Signs the unit, or returns `None` if no signature could be created.
     pub(crate) fn new(
         hashed_wire_unit: HashedWireUnit<C>,
         secret_key: &C::ValidatorSecret,
-    ) -> Self {
-        let signature = secret_key.sign(&hashed_wire_unit.hash);
-        SignedWireUnit {
+    ) -> Option<Self> {
+        let signature = secret_key.sign(hashed_wire_unit.wire_unit().signing_payload())?;
+        Some(SignedWireUnit {
             hashed_wire_unit,
             signature,
-        }
+        })
     }
 
     /// Returns the inner `WireUnit`.
@@ -456,10 +460,15 @@ impl<C: Context> WireUnit<C> {
         self.panorama[self.creator].correct()
     }
 
+    /// Returns the serialized unit, whose hash is signed.
+    pub(crate) fn signing_payload(&self) -> ConsensusPayload {
+        ConsensusPayload::HighwayUnit(bincode::serialize(self).expect("serialize WireUnit"))
+    }
+
     /// Returns the unit's hash, which is used as a unit identifier.
     fn compute_hash(&self) -> C::Hash {
         // TODO: Use serialize_into to avoid allocation?
-        <C as Context>::hash(&bincode::serialize(self).expect("serialize WireUnit"))
+        <C as Context>::hash(self.signing_payload().serialized())
     }
 }
 
@@ -518,20 +527,20 @@ where
 }
 
 impl<C: Context> Ping<C> {
-    /// Creates a new signed ping.
+    /// Creates a new signed ping, or returns `None` if no signature could be created.
     pub(crate) fn new(
         creator: ValidatorIndex,
         timestamp: Timestamp,
         instance_id: C::InstanceId,
         sk: &C::ValidatorSecret,
-    ) -> Self {
-        let signature = sk.sign(&Self::hash(creator, timestamp, instance_id));
-        Ping {
+    ) -> Option<Self> {
+        let signature = sk.sign(Self::signing_payload(creator, timestamp, instance_id))?;
+        Some(Ping {
             creator,
             timestamp,
             instance_id,
             signature,
-        }
+        })
     }
 
     /// The creator who signals that it is online.
@@ -567,9 +576,18 @@ impl<C: Context> Ping<C> {
         Ok(())
     }
 
+    /// Returns the serialized creator, timestamp and instance ID, whose hash is signed.
+    fn signing_payload(
+        creator: ValidatorIndex,
+        timestamp: Timestamp,
+        instance_id: C::InstanceId,
+    ) -> ConsensusPayload {
+        let bytes = bincode::serialize(&(creator, timestamp, instance_id)).expect("serialize Ping");
+        ConsensusPayload::HighwayPing(bytes)
+    }
+
     /// Computes the hash of a ping, i.e. of the creator and timestamp.
     fn hash(creator: ValidatorIndex, timestamp: Timestamp, instance_id: C::InstanceId) -> C::Hash {
-        let bytes = bincode::serialize(&(creator, timestamp, instance_id)).expect("serialize Ping");
-        <C as Context>::hash(&bytes)
+        <C as Context>::hash(Self::signing_payload(creator, timestamp, instance_id).serialized())
     }
 }
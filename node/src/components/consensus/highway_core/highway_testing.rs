@@ -38,6 +38,7 @@ use crate::{
         utils::{Validators, Weight},
         BlockContext,
     },
+    signer::ConsensusPayload,
     NodeRng,
 };
 
@@ -283,7 +284,7 @@ impl HighwayValidator {
                                 }
                                 let secret = TestSecret(wunit2.creator.0.into());
                                 let hwunit2 = wunit2.into_hashed();
-                                let swunit2 = SignedWireUnit::new(hwunit2, &secret);
+                                let swunit2 = SignedWireUnit::new(hwunit2, &secret).unwrap();
                                 let vertex2 = Box::new(Vertex::Unit(swunit2));
                                 vec![msg, HighwayMessage::NewVertex(vertex2)]
                             }
@@ -1033,8 +1034,9 @@ impl ValidatorSecret for TestSecret {
     type Hash = HashWrapper;
     type Signature = SignatureWrapper;
 
-    fn sign(&self, data: &Self::Hash) -> Self::Signature {
-        SignatureWrapper(data.0 + self.0)
+    fn sign(&self, payload: ConsensusPayload) -> Option<Self::Signature> {
+        let data = <TestContext as Context>::hash(payload.serialized());
+        Some(SignatureWrapper(data.0 + self.0))
     }
 }
 
@@ -102,14 +102,14 @@ use crate::{
     utils, NodeRng,
 };
 use fault::Fault;
-use message::{Content, SignedMessage, SyncResponse};
+use message::{SignedMessage, SyncResponse};
 use params::Params;
 use participation::{Participation, ParticipationStatus};
 use proposal::{HashedProposal, Proposal};
 use round::Round;
 use serde::{Deserialize, Serialize};
 
-pub(crate) use message::{Message, SyncRequest};
+pub(crate) use message::{Content, Message, SyncRequest};
 
 /// The timer for syncing with a random peer.
 const TIMER_ID_SYNC_PEER: TimerId = TimerId(0);
@@ -679,7 +679,7 @@ impl<C: Context + 'static> Zug<C> {
             content,
             validator_idx,
             secret_key,
-        );
+        )?;
         // We only return the new message if we are able to record it. If that fails we
         // wouldn't know about our own message after a restart and risk double-signing.
         if self.record_entry(&ZugWalEntry::SignedMessage(signed_msg.clone()))
@@ -2686,6 +2686,7 @@ mod specimen_support {
                 LargestSpecimen::largest_specimen(estimator, cache),
                 &LargestSpecimen::largest_specimen(estimator, cache),
             )
+            .expect("should sign largest specimen")
         }
     }
 
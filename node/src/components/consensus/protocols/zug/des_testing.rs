@@ -36,6 +36,7 @@ use crate::{
         utils::{Validators, Weight},
         ActionId, BlockContext, SerializedMessage, TimerId,
     },
+    signer::ConsensusPayload,
     types::NodeId,
     NodeRng,
 };
@@ -315,7 +316,8 @@ impl ZugValidator {
                                     )),
                                     signed_msg.validator_idx,
                                     &TestSecret(signed_msg.validator_idx.0.into()),
-                                );
+                                )
+                                .unwrap();
                                 vec![
                                     ZugMessage::GossipMessage(SerializedMessage::from_message(
                                         &ZugProtocolMessage::Signed(conflicting_message),
@@ -330,7 +332,8 @@ impl ZugValidator {
                                     Content::<TestContext>::Vote(!vote),
                                     signed_msg.validator_idx,
                                     &TestSecret(signed_msg.validator_idx.0.into()),
-                                );
+                                )
+                                .unwrap();
                                 vec![
                                     ZugMessage::GossipMessage(SerializedMessage::from_message(
                                         &ZugProtocolMessage::Signed(conflicting_message),
@@ -1007,8 +1010,9 @@ impl ValidatorSecret for TestSecret {
     type Hash = HashWrapper;
     type Signature = SignatureWrapper;
 
-    fn sign(&self, data: &Self::Hash) -> Self::Signature {
-        SignatureWrapper(data.0 + self.0)
+    fn sign(&self, payload: ConsensusPayload) -> Option<Self::Signature> {
+        let data = <TestContext as Context>::hash(payload.serialized());
+        Some(SignatureWrapper(data.0 + self.0))
     }
 }
 
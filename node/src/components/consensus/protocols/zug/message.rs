@@ -11,6 +11,7 @@ use crate::{
         traits::{ConsensusNetworkMessage, Context, ValidatorSecret},
         utils::ValidatorIndex,
     },
+    signer::ConsensusPayload,
     utils::ds,
 };
 
@@ -131,21 +132,24 @@ where
 
 impl<C: Context> SignedMessage<C> {
     /// Creates a new signed message with a valid signature.
+    ///
+    /// Returns `None` if the signer is unavailable or refuses to sign.
     pub(crate) fn sign_new(
         round_id: RoundId,
         instance_id: C::InstanceId,
         content: Content<C>,
         validator_idx: ValidatorIndex,
         secret: &C::ValidatorSecret,
-    ) -> SignedMessage<C> {
-        let hash = Self::hash_fields(round_id, &instance_id, &content, validator_idx);
-        SignedMessage {
+    ) -> Option<SignedMessage<C>> {
+        let payload = Self::signing_payload(round_id, &instance_id, &content, validator_idx);
+        let signature = secret.sign(payload)?;
+        Some(SignedMessage {
             round_id,
             instance_id,
             content,
             validator_idx,
-            signature: secret.sign(&hash),
-        }
+            signature,
+        })
     }
 
     /// Creates a new signed message with the alternative content and signature.
@@ -168,17 +172,28 @@ impl<C: Context> SignedMessage<C> {
         C::verify_signature(&hash, validator_id, &self.signature)
     }
 
-    /// Returns the hash of all fields except the signature.
-    fn hash_fields(
+    /// Returns all fields except the signature, serialized for signing.
+    fn signing_payload(
         round_id: RoundId,
         instance_id: &C::InstanceId,
         content: &Content<C>,
         validator_idx: ValidatorIndex,
-    ) -> C::Hash {
+    ) -> ConsensusPayload {
         let serialized_fields =
             bincode::serialize(&(round_id, instance_id, content, validator_idx))
                 .expect("failed to serialize fields");
-        <C as Context>::hash(&serialized_fields)
+        ConsensusPayload::Zug(serialized_fields)
+    }
+
+    /// Returns the hash of all fields except the signature.
+    fn hash_fields(
+        round_id: RoundId,
+        instance_id: &C::InstanceId,
+        content: &Content<C>,
+        validator_idx: ValidatorIndex,
+    ) -> C::Hash {
+        let payload = Self::signing_payload(round_id, instance_id, content, validator_idx);
+        <C as Context>::hash(payload.serialized())
     }
 }
 
@@ -77,7 +77,7 @@ fn create_signed_message(
 ) -> SignedMessage<ClContext> {
     let validator_idx = validators.get_index(keypair.public_key()).unwrap();
     let instance_id = ClContext::hash(INSTANCE_ID_DATA);
-    SignedMessage::sign_new(round_id, instance_id, content, validator_idx, keypair)
+    SignedMessage::sign_new(round_id, instance_id, content, validator_idx, keypair).unwrap()
 }
 
 /// Creates a `Message::Signed`.
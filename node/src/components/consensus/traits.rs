@@ -6,6 +6,8 @@ use std::{
 use datasize::DataSize;
 use serde::{de::DeserializeOwned, Serialize};
 
+use crate::signer::ConsensusPayload;
+
 /// A validator identifier.
 pub trait ValidatorIdT: Eq + Ord + Clone + Debug + Hash + Send + DataSize + Display {}
 impl<VID> ValidatorIdT for VID where VID: Eq + Ord + Clone + Debug + Hash + Send + DataSize + Display
@@ -45,7 +47,11 @@ pub trait ValidatorSecret: Send + DataSize {
 
     type Signature: Eq + PartialEq + Clone + Debug + Hash + Serialize + DeserializeOwned + DataSize;
 
-    fn sign(&self, hash: &Self::Hash) -> Self::Signature;
+    /// Signs the hash of the given serialized consensus message.
+    ///
+    /// Returns `None` if no signature could be created, e.g. because a remote signer is unavailable
+    /// or refuses to sign a message conflicting with one it signed before.
+    fn sign(&self, payload: ConsensusPayload) -> Option<Self::Signature>;
 }
 
 /// The collection of types the user can choose for cryptography, IDs, transactions, etc.
@@ -9,6 +9,7 @@ mod operations;
 mod rewards;
 #[cfg(test)]
 mod tests;
+mod trie_gc;
 mod types;
 mod utils;
 
@@ -17,6 +18,7 @@ use std::{
     collections::BTreeMap,
     convert::TryInto,
     fmt::{self, Debug, Formatter},
+    io::{Read, Write},
     path::Path,
     sync::{Arc, Mutex},
     time::Instant,
@@ -26,6 +28,7 @@ use casper_executor_wasm::{ExecutorConfigBuilder, ExecutorKind, ExecutorV2};
 use datasize::DataSize;
 use lmdb::DatabaseFlags;
 use prometheus::Registry;
+use tokio::sync::Mutex as AsyncMutex;
 use tracing::{debug, error, info, trace};
 
 use casper_execution_engine::engine_state::{EngineConfigBuilder, ExecutionEngineV1};
@@ -36,7 +39,9 @@ use casper_storage::{
         GenesisRequest, GenesisResult, TrieRequest,
     },
     global_state::{
-        state::{lmdb::LmdbGlobalState, CommitProvider, StateProvider},
+        gc::{GcProgress, TrieGarbageCollector},
+        snapshot::{self, SnapshotError, SnapshotReader, SnapshotStats},
+        state::{lmdb::LmdbGlobalState, CommitProvider, ScratchProvider, StateProvider},
         transaction_source::lmdb::LmdbEnvironment,
         trie_store::lmdb::LmdbTrieStore,
     },
@@ -44,8 +49,8 @@ use casper_storage::{
     tracking_copy::TrackingCopyError,
 };
 use casper_types::{
-    account::AccountHash, ActivationPoint, Chainspec, ChainspecRawBytes, ChainspecRegistry,
-    EntityAddr, EraId, Key, PublicKey,
+    account::AccountHash, ActivationPoint, BlockHash, Chainspec, ChainspecRawBytes,
+    ChainspecRegistry, Digest, EntityAddr, EraId, Key, PublicKey,
 };
 
 use crate::{
@@ -73,13 +78,13 @@ pub(crate) use error::{BlockExecutionError, ConfigError, ContractRuntimeError, S
 pub(crate) use event::Event;
 use exec_queue::{ExecQueue, QueueItem};
 use metrics::Metrics;
-#[cfg(test)]
 pub(crate) use operations::compute_execution_results_checksum;
 pub use operations::execute_finalized_block;
-use operations::speculatively_execute;
+use operations::{apply_state_overrides, estimate_gas, speculatively_execute};
+use trie_gc::TrieGc;
 pub(crate) use types::{
-    BlockAndExecutionArtifacts, ExecutionArtifact, ExecutionPreState, SpeculativeExecutionResult,
-    StepOutcome,
+    BlockAndExecutionArtifacts, ExecutionArtifact, ExecutionPreState, GasEstimationResult,
+    SpeculativeExecutionResult, StepOutcome,
 };
 use utils::{exec_or_requeue, run_intensive_task};
 
@@ -88,6 +93,9 @@ const COMPONENT_NAME: &str = "contract_runtime";
 pub(crate) const APPROVALS_CHECKSUM_NAME: &str = "approvals_checksum";
 pub(crate) const EXECUTION_RESULTS_CHECKSUM_NAME: &str = "execution_results_checksum";
 
+/// Name of the directory, within the global state directory, holding compiled Wasm modules.
+const WASM_MODULE_ARTIFACTS_DIR_NAME: &str = "wasm_module_artifacts";
+
 /// The contract runtime components.
 #[derive(DataSize)]
 pub(crate) struct ContractRuntime {
@@ -104,6 +112,11 @@ pub(crate) struct ContractRuntime {
     chainspec: Arc<Chainspec>,
     #[data_size(skip)]
     data_access_layer: Arc<DataAccessLayer<LmdbGlobalState>>,
+    /// The trie store garbage collection, if enabled.
+    #[data_size(skip)]
+    trie_gc: Option<Arc<AsyncMutex<TrieGc>>>,
+    /// The number of threads executing the transactions of a block.
+    execution_threads: usize,
     current_gas_price: EraPrice,
 }
 
@@ -162,17 +175,27 @@ impl ContractRuntime {
             )
             .map_err(ConfigError::GlobalState)?,
         );
+        let trie_gc = TrieGc::new(data_access_layer.state(), contract_runtime_config)
+            .map_err(ConfigError::GlobalState)?
+            .map(|trie_gc| Arc::new(AsyncMutex::new(trie_gc)));
 
         let execution_engine_v1 = Arc::new(ExecutionEngineV1::new(engine_config));
 
         let executor_v2 = {
-            let executor_config = ExecutorConfigBuilder::default()
+            let mut executor_config_builder = ExecutorConfigBuilder::default()
                 .with_memory_limit(chainspec.wasm_config.v2().max_memory())
                 .with_executor_kind(ExecutorKind::Compiled)
                 .with_wasm_config(*chainspec.wasm_config.v2())
                 .with_storage_costs(chainspec.storage_costs)
-                .build()
-                .expect("Should build");
+                .with_message_limits(chainspec.wasm_config.messages_limits())
+                .with_module_cache_capacity(
+                    contract_runtime_config.wasm_module_cache_capacity_or_default(),
+                );
+            if contract_runtime_config.wasm_module_artifacts_enabled_or_default() {
+                executor_config_builder = executor_config_builder
+                    .with_module_artifacts_dir(storage_dir.join(WASM_MODULE_ARTIFACTS_DIR_NAME));
+            }
+            let executor_config = executor_config_builder.build().expect("Should build");
             ExecutorV2::new(executor_config, Arc::clone(&execution_engine_v1))
         };
 
@@ -187,6 +210,8 @@ impl ContractRuntime {
             exec_queue: Default::default(),
             chainspec,
             data_access_layer,
+            trie_gc,
+            execution_threads: contract_runtime_config.execution_threads_or_default(),
             current_gas_price,
         })
     }
@@ -216,11 +241,10 @@ impl ContractRuntime {
                 contract_runtime_config.manual_sync_enabled_or_default(),
             )?);
 
-            let trie_store = Arc::new(LmdbTrieStore::new(
-                &environment,
-                None,
-                DatabaseFlags::empty(),
-            )?);
+            let trie_store = Arc::new(
+                LmdbTrieStore::new(&environment, None, DatabaseFlags::empty())?
+                    .with_node_cache(contract_runtime_config.trie_node_cache_capacity_or_default()),
+            );
 
             let block_store = BlockStore::new();
 
@@ -495,6 +519,42 @@ impl ContractRuntime {
                 }
                 .ignore()
             }
+            ContractRuntimeRequest::GetMultiProof {
+                request: multi_proof_request,
+                responder,
+            } => {
+                trace!(?multi_proof_request, "multi-proof request");
+                let metrics = Arc::clone(&self.metrics);
+                let data_access_layer = Arc::clone(&self.data_access_layer);
+                async move {
+                    let start = Instant::now();
+                    let result = data_access_layer.multi_proof(multi_proof_request);
+                    metrics
+                        .get_multi_proof
+                        .observe(start.elapsed().as_secs_f64());
+                    trace!(?result, "multi-proof result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            ContractRuntimeRequest::GetStateDiff {
+                request: state_diff_request,
+                responder,
+            } => {
+                trace!(?state_diff_request, "state diff request");
+                let metrics = Arc::clone(&self.metrics);
+                let data_access_layer = Arc::clone(&self.data_access_layer);
+                async move {
+                    let start = Instant::now();
+                    let result = data_access_layer.state_diff(state_diff_request);
+                    metrics
+                        .get_state_diff
+                        .observe(start.elapsed().as_secs_f64());
+                    trace!(?result, "state diff result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             // trie related events
             ContractRuntimeRequest::GetTrie {
                 request: trie_request,
@@ -674,6 +734,7 @@ impl ContractRuntime {
                         let chainspec = Arc::clone(&self.chainspec);
                         let metrics = Arc::clone(&self.metrics);
                         let shared_pre_state = Arc::clone(&self.execution_pre_state);
+                        let trie_gc = self.trie_gc.clone();
                         effects.extend(
                             exec_or_requeue(
                                 data_access_layer,
@@ -681,6 +742,7 @@ impl ContractRuntime {
                                 execution_engine_v2,
                                 chainspec,
                                 metrics,
+                                trie_gc,
                                 exec_queue,
                                 shared_pre_state,
                                 current_pre_state.clone(),
@@ -688,6 +750,7 @@ impl ContractRuntime {
                                 executable_block,
                                 key_block_height_for_activation_point,
                                 meta_block_state,
+                                self.execution_threads,
                             )
                             .ignore(),
                         )
@@ -700,7 +763,10 @@ impl ContractRuntime {
             }
             ContractRuntimeRequest::SpeculativelyExecute {
                 block_header,
+                state_root_hash,
                 transaction,
+                state_overrides,
+                trace_execution,
                 responder,
             } => {
                 let chainspec = Arc::clone(&self.chainspec);
@@ -708,12 +774,80 @@ impl ContractRuntime {
                 let execution_engine_v1 = Arc::clone(&self.execution_engine_v1);
                 async move {
                     let result = run_intensive_task(move || {
+                        if state_overrides.is_empty() {
+                            return speculatively_execute(
+                                data_access_layer.as_ref(),
+                                chainspec.as_ref(),
+                                execution_engine_v1.as_ref(),
+                                *block_header,
+                                state_root_hash,
+                                *transaction,
+                                trace_execution,
+                            );
+                        }
+                        // The overrides are applied to a scratch global state which is dropped
+                        // after the execution, so they never reach the global state.
+                        let scratch_state = data_access_layer.get_scratch_global_state();
+                        if let Err(error) =
+                            apply_state_overrides(&scratch_state, state_root_hash, state_overrides)
+                        {
+                            debug!(%error, "failed to apply state overrides");
+                            return SpeculativeExecutionResult::InvalidStateOverride;
+                        }
                         speculatively_execute(
-                            data_access_layer.as_ref(),
+                            &scratch_state,
+                            chainspec.as_ref(),
+                            execution_engine_v1.as_ref(),
+                            *block_header,
+                            state_root_hash,
+                            *transaction,
+                            trace_execution,
+                        )
+                    })
+                    .await;
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            ContractRuntimeRequest::EstimateGas {
+                block_header,
+                state_root_hash,
+                transaction,
+                state_overrides,
+                payment_margin_percent,
+                responder,
+            } => {
+                let chainspec = Arc::clone(&self.chainspec);
+                let data_access_layer = Arc::clone(&self.data_access_layer);
+                let execution_engine_v1 = Arc::clone(&self.execution_engine_v1);
+                let execution_engine_v2 = self.execution_engine_v2.clone();
+                let gas_price = self.current_gas_price.gas_price();
+                async move {
+                    let result = run_intensive_task(move || {
+                        // Executing VM2 transactions commits their effects to the state provider,
+                        // so the estimation always takes place on a scratch global state which is
+                        // dropped afterwards.
+                        let scratch_state = data_access_layer.get_scratch_global_state();
+                        if !state_overrides.is_empty() {
+                            if let Err(error) = apply_state_overrides(
+                                &scratch_state,
+                                state_root_hash,
+                                state_overrides,
+                            ) {
+                                debug!(%error, "failed to apply state overrides");
+                                return GasEstimationResult::InvalidStateOverride;
+                            }
+                        }
+                        estimate_gas(
+                            &scratch_state,
                             chainspec.as_ref(),
                             execution_engine_v1.as_ref(),
+                            &execution_engine_v2,
                             *block_header,
+                            state_root_hash,
                             *transaction,
+                            gas_price,
+                            payment_margin_percent,
                         )
                     })
                     .await;
@@ -813,6 +947,92 @@ impl ContractRuntime {
         Ok(FetchResponse::from_opt(trie_or_chunk_id, maybe_trie))
     }
 
+    /// Re-executes a block which has already been executed, in the same way it would be executed
+    /// by the reactor.
+    ///
+    /// The block is executed against a scratch copy of the global state: neither the tries of its
+    /// post state, nor the block and its execution results are stored.
+    pub(crate) fn replay_block(
+        &self,
+        execution_pre_state: ExecutionPreState,
+        executable_block: ExecutableBlock,
+        key_block_height_for_activation_point: u64,
+        last_switch_block_hash: Option<BlockHash>,
+    ) -> Result<BlockAndExecutionArtifacts, BlockExecutionError> {
+        self.replay_block_with_execution_threads(
+            execution_pre_state,
+            executable_block,
+            key_block_height_for_activation_point,
+            last_switch_block_hash,
+            self.execution_threads,
+        )
+    }
+
+    /// Re-executes a block which has already been executed, using the given number of execution
+    /// threads rather than the configured one.
+    pub(crate) fn replay_block_with_execution_threads(
+        &self,
+        execution_pre_state: ExecutionPreState,
+        executable_block: ExecutableBlock,
+        key_block_height_for_activation_point: u64,
+        last_switch_block_hash: Option<BlockHash>,
+        execution_threads: usize,
+    ) -> Result<BlockAndExecutionArtifacts, BlockExecutionError> {
+        let current_gas_price = executable_block.current_gas_price;
+        let next_era_gas_price = executable_block.next_era_gas_price;
+        execute_finalized_block(
+            self.data_access_layer.as_ref(),
+            self.execution_engine_v1.as_ref(),
+            self.execution_engine_v2.clone(),
+            self.chainspec.as_ref(),
+            Some(Arc::clone(&self.metrics)),
+            execution_pre_state,
+            executable_block,
+            key_block_height_for_activation_point,
+            current_gas_price,
+            next_era_gas_price,
+            last_switch_block_hash,
+            execution_threads,
+            false,
+        )
+    }
+
+    /// Writes a snapshot of the global state under the given state root.
+    pub(crate) fn export_snapshot<W: Write>(
+        &self,
+        state_root_hash: Digest,
+        metadata: &[u8],
+        writer: W,
+    ) -> Result<SnapshotStats, SnapshotError> {
+        snapshot::export_snapshot(
+            self.data_access_layer.state(),
+            state_root_hash,
+            metadata,
+            writer,
+        )
+    }
+
+    /// Imports a snapshot into the global state.
+    pub(crate) fn import_snapshot<R: Read>(
+        &self,
+        reader: SnapshotReader<R>,
+    ) -> Result<SnapshotStats, SnapshotError> {
+        reader.import(self.data_access_layer.state())
+    }
+
+    /// Runs a garbage collection of the trie store to completion, retaining the tries reachable
+    /// from the given state roots.
+    ///
+    /// Must only be used while no blocks are being executed.
+    pub(crate) fn collect_garbage(
+        &self,
+        retained_state_roots: &[Digest],
+        batch_size: usize,
+    ) -> Result<GcProgress, casper_storage::global_state::error::Error> {
+        TrieGarbageCollector::new(self.data_access_layer.state())?
+            .run(retained_state_roots, batch_size)
+    }
+
     /// Returns data_access_layer, for testing only.
     #[cfg(test)]
     pub(crate) fn data_access_layer(&self) -> Arc<DataAccessLayer<LmdbGlobalState>> {
@@ -823,6 +1043,13 @@ impl ContractRuntime {
     pub(crate) fn current_era_price(&self) -> EraPrice {
         self.current_gas_price
     }
+
+    /// Returns the number of transactions executed again after conflicting with an earlier
+    /// transaction of their block, for testing only.
+    #[cfg(test)]
+    pub(crate) fn transactions_reexecuted(&self) -> u64 {
+        self.metrics.transactions_reexecuted.get()
+    }
 }
 
 impl<REv> Component<REv> for ContractRuntime
@@ -8,6 +8,11 @@ const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
 const DEFAULT_MANUAL_SYNC_ENABLED: bool = true;
+const DEFAULT_GC_BATCH_SIZE: usize = 10_000;
+const DEFAULT_TRIE_NODE_CACHE_CAPACITY: usize = 10_000;
+const DEFAULT_WASM_MODULE_CACHE_CAPACITY: usize = 64;
+const DEFAULT_WASM_MODULE_ARTIFACTS_ENABLED: bool = false;
+const DEFAULT_EXECUTION_THREADS: usize = 1;
 
 /// Contract runtime configuration.
 #[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
@@ -32,6 +37,39 @@ pub struct Config {
     ///
     /// Defaults to `true`.
     pub enable_manual_sync: Option<bool>,
+    /// The number of most recent blocks whose global state is retained by the trie store garbage
+    /// collection, which is started at every switch block.
+    ///
+    /// Defaults to `None`, i.e. garbage collection is disabled.
+    pub gc_retained_state_roots: Option<u64>,
+    /// The maximum number of tries processed by the trie store garbage collection after each
+    /// executed block.
+    ///
+    /// Defaults to 10,000.
+    pub gc_batch_size: Option<usize>,
+    /// The number of decoded global state trie nodes kept in memory, shared by block execution
+    /// and queries.  Setting it to 0 disables the cache.
+    ///
+    /// Defaults to 10,000.
+    pub trie_node_cache_capacity: Option<usize>,
+    /// The number of compiled VM2 Wasm modules kept in memory.
+    ///
+    /// Defaults to 64.
+    pub wasm_module_cache_capacity: Option<usize>,
+    /// Enable storing compiled VM2 Wasm modules on disk, so they don't need to be recompiled
+    /// after a restart.
+    ///
+    /// Defaults to `false`.
+    pub enable_wasm_module_artifacts: Option<bool>,
+    /// The number of threads executing the transactions of a block.
+    ///
+    /// The transactions are executed speculatively in parallel, and executed again in block order
+    /// if they conflict with earlier transactions of the block, so the outcome is the same as
+    /// executing them one after another.  Parallel execution is experimental and must be enabled
+    /// explicitly by setting a value greater than 1.
+    ///
+    /// Defaults to 1, i.e. the transactions are executed one after another.
+    pub execution_threads: Option<usize>,
 }
 
 impl Config {
@@ -64,6 +102,42 @@ impl Config {
         self.enable_manual_sync
             .unwrap_or(DEFAULT_MANUAL_SYNC_ENABLED)
     }
+
+    /// Number of most recent blocks whose global state is retained by the garbage collection, if
+    /// enabled.
+    pub fn gc_retained_state_roots(&self) -> Option<u64> {
+        self.gc_retained_state_roots.filter(|count| *count > 0)
+    }
+
+    /// Garbage collection batch size.
+    pub fn gc_batch_size_or_default(&self) -> usize {
+        self.gc_batch_size.unwrap_or(DEFAULT_GC_BATCH_SIZE).max(1)
+    }
+
+    /// Trie node cache capacity.
+    pub fn trie_node_cache_capacity_or_default(&self) -> usize {
+        self.trie_node_cache_capacity
+            .unwrap_or(DEFAULT_TRIE_NODE_CACHE_CAPACITY)
+    }
+
+    /// Wasm module cache capacity.
+    pub fn wasm_module_cache_capacity_or_default(&self) -> usize {
+        self.wasm_module_cache_capacity
+            .unwrap_or(DEFAULT_WASM_MODULE_CACHE_CAPACITY)
+    }
+
+    /// Are Wasm module artifacts enabled.
+    pub fn wasm_module_artifacts_enabled_or_default(&self) -> bool {
+        self.enable_wasm_module_artifacts
+            .unwrap_or(DEFAULT_WASM_MODULE_ARTIFACTS_ENABLED)
+    }
+
+    /// Number of threads executing the transactions of a block.
+    pub fn execution_threads_or_default(&self) -> usize {
+        self.execution_threads
+            .unwrap_or(DEFAULT_EXECUTION_THREADS)
+            .max(1)
+    }
 }
 
 impl Default for Config {
@@ -73,6 +147,12 @@ impl Default for Config {
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
             enable_manual_sync: Some(DEFAULT_MANUAL_SYNC_ENABLED),
+            gc_retained_state_roots: None,
+            gc_batch_size: Some(DEFAULT_GC_BATCH_SIZE),
+            trie_node_cache_capacity: Some(DEFAULT_TRIE_NODE_CACHE_CAPACITY),
+            wasm_module_cache_capacity: Some(DEFAULT_WASM_MODULE_CACHE_CAPACITY),
+            enable_wasm_module_artifacts: Some(DEFAULT_WASM_MODULE_ARTIFACTS_ENABLED),
+            execution_threads: Some(DEFAULT_EXECUTION_THREADS),
         }
     }
 }
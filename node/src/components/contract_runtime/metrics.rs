@@ -1,4 +1,7 @@
-use prometheus::{self, Gauge, Histogram, IntGauge, Registry};
+use prometheus::{self, Gauge, Histogram, IntCounter, IntGauge, Registry};
+
+use casper_executor_wasm::ModuleCacheStats;
+use casper_storage::global_state::trie_store::node_cache::TrieNodeCacheStats;
 
 use crate::{unregister_metric, utils};
 
@@ -69,6 +72,14 @@ const GET_ALL_VALUES_NAME: &str = "contract_runtime_get_all_values";
 const GET_ALL_VALUES_NAME_HELP: &str =
     "time in seconds to get all values under a give key from global state";
 
+const GET_STATE_DIFF_NAME: &str = "contract_runtime_get_state_diff";
+const GET_STATE_DIFF_HELP: &str =
+    "time in seconds to get the differences between two global states";
+
+const GET_MULTI_PROOF_NAME: &str = "contract_runtime_get_multi_proof";
+const GET_MULTI_PROOF_HELP: &str =
+    "time in seconds to get several global state items with a single proof";
+
 const EXECUTION_RESULTS_CHECKSUM_NAME: &str = "contract_runtime_execution_results_checksum";
 const EXECUTION_RESULTS_CHECKSUM_HELP: &str = "contract_runtime_execution_results_checksum";
 
@@ -113,6 +124,53 @@ const SCRATCH_LMDB_WRITE_TIME_HELP: &str = "time in seconds to write changes to
 const SEIGNIORAGE_TARGET_FRACTION: &str = "contract_runtime_seigniorage_target_fraction";
 const SEIGNIORAGE_TARGET_FRACTION_HELP: &str = "fraction of target seigniorage minted in era";
 
+const TRIE_GC_PHASE_NAME: &str = "contract_runtime_trie_gc_phase";
+const TRIE_GC_PHASE_HELP: &str =
+    "phase of the trie store garbage collection (0 = idle, 1 = mark, 2 = sweep)";
+
+const TRIE_GC_TRIES_MARKED_NAME: &str = "contract_runtime_trie_gc_tries_marked";
+const TRIE_GC_TRIES_MARKED_HELP: &str =
+    "number of tries marked as reachable by the current trie store garbage collection";
+
+const TRIE_GC_TRIES_SWEPT_NAME: &str = "contract_runtime_trie_gc_tries_swept";
+const TRIE_GC_TRIES_SWEPT_HELP: &str =
+    "number of tries visited by the sweep phase of the current trie store garbage collection";
+
+const TRIE_GC_TRIES_DELETED_NAME: &str = "contract_runtime_trie_gc_tries_deleted";
+const TRIE_GC_TRIES_DELETED_HELP: &str =
+    "number of tries deleted by the current or last trie store garbage collection";
+
+const TRIE_GC_STEP_TIME_NAME: &str = "contract_runtime_trie_gc_step_time";
+const TRIE_GC_STEP_TIME_HELP: &str =
+    "time in seconds to perform a step of the trie store garbage collection";
+
+const TRANSACTIONS_REEXECUTED_NAME: &str = "contract_runtime_transactions_reexecuted";
+const TRANSACTIONS_REEXECUTED_HELP: &str =
+    "number of transactions executed again as they conflicted with an earlier transaction of their \
+    block";
+
+const TRIE_NODE_CACHE_HITS_NAME: &str = "contract_runtime_trie_node_cache_hits";
+const TRIE_NODE_CACHE_HITS_HELP: &str =
+    "number of global state trie nodes found in the trie node cache since the node started";
+
+const TRIE_NODE_CACHE_MISSES_NAME: &str = "contract_runtime_trie_node_cache_misses";
+const TRIE_NODE_CACHE_MISSES_HELP: &str =
+    "number of global state trie nodes not found in the trie node cache since the node started";
+
+const TRIE_NODE_CACHE_SIZE_NAME: &str = "contract_runtime_trie_node_cache_size";
+const TRIE_NODE_CACHE_SIZE_HELP: &str = "number of decoded trie nodes in the trie node cache";
+
+const WASM_MODULE_CACHE_HITS_NAME: &str = "contract_runtime_wasm_module_cache_hits";
+const WASM_MODULE_CACHE_HITS_HELP: &str =
+    "number of compiled wasm modules found in the module cache since the node started";
+
+const WASM_MODULE_CACHE_MISSES_NAME: &str = "contract_runtime_wasm_module_cache_misses";
+const WASM_MODULE_CACHE_MISSES_HELP: &str =
+    "number of wasm modules not found in the module cache since the node started";
+
+const WASM_MODULE_CACHE_SIZE_NAME: &str = "contract_runtime_wasm_module_cache_size";
+const WASM_MODULE_CACHE_SIZE_HELP: &str = "number of compiled wasm modules in the module cache";
+
 /// Metrics for the contract runtime component.
 #[derive(Debug)]
 pub struct Metrics {
@@ -139,6 +197,8 @@ pub struct Metrics {
     pub(super) get_era_validators: Histogram,
     pub(super) get_seigniorage_recipients: Histogram,
     pub(super) get_all_values: Histogram,
+    pub(super) get_state_diff: Histogram,
+    pub(super) get_multi_proof: Histogram,
     pub(super) execution_results_checksum: Histogram,
     pub(super) addressable_entity: Histogram,
     pub(super) entry_points: Histogram,
@@ -152,6 +212,18 @@ pub struct Metrics {
     pub(super) database_flush_time: Histogram,
     pub(super) scratch_lmdb_write_time: Histogram,
     pub(super) seigniorage_target_fraction: Gauge,
+    pub(super) trie_gc_phase: IntGauge,
+    pub(super) trie_gc_tries_marked: IntGauge,
+    pub(super) trie_gc_tries_swept: IntGauge,
+    pub(super) trie_gc_tries_deleted: IntGauge,
+    pub(super) trie_gc_step_time: Histogram,
+    pub(super) transactions_reexecuted: IntCounter,
+    pub(super) trie_node_cache_hits: IntCounter,
+    pub(super) trie_node_cache_misses: IntCounter,
+    pub(super) trie_node_cache_size: IntGauge,
+    pub(super) wasm_module_cache_hits: IntCounter,
+    pub(super) wasm_module_cache_misses: IntCounter,
+    pub(super) wasm_module_cache_size: IntGauge,
     registry: Registry,
 }
 
@@ -189,6 +261,49 @@ impl Metrics {
         )?;
         registry.register(Box::new(seigniorage_target_fraction.clone()))?;
 
+        let trie_gc_phase = IntGauge::new(TRIE_GC_PHASE_NAME, TRIE_GC_PHASE_HELP)?;
+        registry.register(Box::new(trie_gc_phase.clone()))?;
+
+        let trie_gc_tries_marked =
+            IntGauge::new(TRIE_GC_TRIES_MARKED_NAME, TRIE_GC_TRIES_MARKED_HELP)?;
+        registry.register(Box::new(trie_gc_tries_marked.clone()))?;
+
+        let trie_gc_tries_swept =
+            IntGauge::new(TRIE_GC_TRIES_SWEPT_NAME, TRIE_GC_TRIES_SWEPT_HELP)?;
+        registry.register(Box::new(trie_gc_tries_swept.clone()))?;
+
+        let trie_gc_tries_deleted =
+            IntGauge::new(TRIE_GC_TRIES_DELETED_NAME, TRIE_GC_TRIES_DELETED_HELP)?;
+        registry.register(Box::new(trie_gc_tries_deleted.clone()))?;
+
+        let transactions_reexecuted =
+            IntCounter::new(TRANSACTIONS_REEXECUTED_NAME, TRANSACTIONS_REEXECUTED_HELP)?;
+        registry.register(Box::new(transactions_reexecuted.clone()))?;
+
+        let trie_node_cache_hits =
+            IntCounter::new(TRIE_NODE_CACHE_HITS_NAME, TRIE_NODE_CACHE_HITS_HELP)?;
+        registry.register(Box::new(trie_node_cache_hits.clone()))?;
+
+        let trie_node_cache_misses =
+            IntCounter::new(TRIE_NODE_CACHE_MISSES_NAME, TRIE_NODE_CACHE_MISSES_HELP)?;
+        registry.register(Box::new(trie_node_cache_misses.clone()))?;
+
+        let trie_node_cache_size =
+            IntGauge::new(TRIE_NODE_CACHE_SIZE_NAME, TRIE_NODE_CACHE_SIZE_HELP)?;
+        registry.register(Box::new(trie_node_cache_size.clone()))?;
+
+        let wasm_module_cache_hits =
+            IntCounter::new(WASM_MODULE_CACHE_HITS_NAME, WASM_MODULE_CACHE_HITS_HELP)?;
+        registry.register(Box::new(wasm_module_cache_hits.clone()))?;
+
+        let wasm_module_cache_misses =
+            IntCounter::new(WASM_MODULE_CACHE_MISSES_NAME, WASM_MODULE_CACHE_MISSES_HELP)?;
+        registry.register(Box::new(wasm_module_cache_misses.clone()))?;
+
+        let wasm_module_cache_size =
+            IntGauge::new(WASM_MODULE_CACHE_SIZE_NAME, WASM_MODULE_CACHE_SIZE_HELP)?;
+        registry.register(Box::new(wasm_module_cache_size.clone()))?;
+
         Ok(Metrics {
             exec_block_pre_processing: utils::register_histogram_metric(
                 registry,
@@ -292,6 +407,18 @@ impl Metrics {
                 GET_ALL_VALUES_NAME_HELP,
                 common_buckets.clone(),
             )?,
+            get_state_diff: utils::register_histogram_metric(
+                registry,
+                GET_STATE_DIFF_NAME,
+                GET_STATE_DIFF_HELP,
+                common_buckets.clone(),
+            )?,
+            get_multi_proof: utils::register_histogram_metric(
+                registry,
+                GET_MULTI_PROOF_NAME,
+                GET_MULTI_PROOF_HELP,
+                common_buckets.clone(),
+            )?,
             execution_results_checksum: utils::register_histogram_metric(
                 registry,
                 EXECUTION_RESULTS_CHECKSUM_NAME,
@@ -355,11 +482,56 @@ impl Metrics {
                 wider_buckets.clone(),
             )?,
             seigniorage_target_fraction,
+            trie_gc_phase,
+            trie_gc_tries_marked,
+            trie_gc_tries_swept,
+            trie_gc_tries_deleted,
+            trie_gc_step_time: utils::register_histogram_metric(
+                registry,
+                TRIE_GC_STEP_TIME_NAME,
+                TRIE_GC_STEP_TIME_HELP,
+                common_buckets.clone(),
+            )?,
+            transactions_reexecuted,
+            trie_node_cache_hits,
+            trie_node_cache_misses,
+            trie_node_cache_size,
+            wasm_module_cache_hits,
+            wasm_module_cache_misses,
+            wasm_module_cache_size,
             registry: registry.clone(),
         })
     }
 }
 
+impl Metrics {
+    /// Updates the metrics of the trie node cache from its statistics.
+    pub(super) fn update_trie_node_cache(&self, stats: TrieNodeCacheStats) {
+        self.trie_node_cache_hits
+            .inc_by(stats.hits.saturating_sub(self.trie_node_cache_hits.get()));
+        self.trie_node_cache_misses.inc_by(
+            stats
+                .misses
+                .saturating_sub(self.trie_node_cache_misses.get()),
+        );
+        self.trie_node_cache_size
+            .set(stats.size.try_into().unwrap_or(i64::MAX));
+    }
+
+    /// Updates the metrics of the wasm module cache from its statistics.
+    pub(super) fn update_wasm_module_cache(&self, stats: ModuleCacheStats) {
+        self.wasm_module_cache_hits
+            .inc_by(stats.hits.saturating_sub(self.wasm_module_cache_hits.get()));
+        self.wasm_module_cache_misses.inc_by(
+            stats
+                .misses
+                .saturating_sub(self.wasm_module_cache_misses.get()),
+        );
+        self.wasm_module_cache_size
+            .set(stats.size.try_into().unwrap_or(i64::MAX));
+    }
+}
+
 impl Drop for Metrics {
     fn drop(&mut self) {
         unregister_metric!(self.registry, self.exec_block_pre_processing);
@@ -379,6 +551,8 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.get_era_validators);
         unregister_metric!(self.registry, self.get_seigniorage_recipients);
         unregister_metric!(self.registry, self.get_all_values);
+        unregister_metric!(self.registry, self.get_state_diff);
+        unregister_metric!(self.registry, self.get_multi_proof);
         unregister_metric!(self.registry, self.execution_results_checksum);
         unregister_metric!(self.registry, self.put_trie);
         unregister_metric!(self.registry, self.get_trie);
@@ -391,5 +565,17 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.database_flush_time);
         unregister_metric!(self.registry, self.scratch_lmdb_write_time);
         unregister_metric!(self.registry, self.seigniorage_target_fraction);
+        unregister_metric!(self.registry, self.trie_gc_phase);
+        unregister_metric!(self.registry, self.trie_gc_tries_marked);
+        unregister_metric!(self.registry, self.trie_gc_tries_swept);
+        unregister_metric!(self.registry, self.trie_gc_tries_deleted);
+        unregister_metric!(self.registry, self.trie_gc_step_time);
+        unregister_metric!(self.registry, self.transactions_reexecuted);
+        unregister_metric!(self.registry, self.trie_node_cache_hits);
+        unregister_metric!(self.registry, self.trie_node_cache_misses);
+        unregister_metric!(self.registry, self.trie_node_cache_size);
+        unregister_metric!(self.registry, self.wasm_module_cache_hits);
+        unregister_metric!(self.registry, self.wasm_module_cache_misses);
+        unregister_metric!(self.registry, self.wasm_module_cache_size);
     }
 }
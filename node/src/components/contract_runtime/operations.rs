@@ -1,8 +1,18 @@
 pub(crate) mod wasm_v2_request;
 
+use casper_binary_port::{GasEstimate, PaymentRecommendation, StateOverride};
 use casper_executor_wasm::ExecutorV2;
 use itertools::Itertools;
-use std::{collections::BTreeMap, convert::TryInto, sync::Arc, time::Instant};
+use std::{
+    collections::BTreeMap,
+    convert::TryInto,
+    sync::{
+        atomic::{self, AtomicUsize},
+        Arc,
+    },
+    thread,
+    time::Instant,
+};
 use tracing::{debug, error, info, trace, warn};
 use wasm_v2_request::WasmV2Request;
 
@@ -24,26 +34,35 @@ use casper_storage::{
         InsufficientBalanceHandling, ProofHandling, PruneRequest, PruneResult, StepRequest,
         StepResult, TransferRequest,
     },
-    global_state::state::{
-        lmdb::LmdbGlobalState, scratch::ScratchGlobalState, CommitProvider, ScratchProvider,
-        StateProvider, StateReader,
+    global_state::{
+        error::Error as GlobalStateError,
+        state::{
+            lmdb::LmdbGlobalState, scratch::ScratchGlobalState, CommitProvider, ScratchProvider,
+            StateProvider, StateReader,
+        },
     },
     system::runtime_native::Config as NativeRuntimeConfig,
+    tracking_copy::{TrackingCopy, TrackingCopyError},
 };
 use casper_types::{
+    account::AccountHash,
+    addressable_entity::{NamedKeyAddr, NamedKeyValue},
     bytesrepr::{self, ToBytes, U32_SERIALIZED_LENGTH},
+    contracts::NamedKeys,
     execution::{Effects, ExecutionResult, TransformKindV2, TransformV2},
     system::handle_payment::ARG_AMOUNT,
-    BlockHash, BlockHeader, BlockTime, BlockV2, CLValue, Chainspec, ChecksumRegistry, Digest,
-    EntityAddr, EraEndV2, EraId, FeeHandling, Gas, InvalidTransaction, InvalidTransactionV1, Key,
-    ProtocolVersion, PublicKey, RefundHandling, Transaction, AUCTION_LANE_ID, MINT_LANE_ID, U512,
+    BlockHash, BlockHeader, BlockTime, BlockV2, ByteCode, ByteCodeAddr, ByteCodeKind, CLValue,
+    Chainspec, ChecksumRegistry, ContractWasm, Digest, EntityAddr, EraEndV2, EraId, FeeHandling,
+    Gas, InitiatorAddr, InvalidTransaction, InvalidTransactionV1, Key, Motes, PricingHandling,
+    PricingMode, ProtocolVersion, PublicKey, RefundHandling, StoredValue, StoredValueTypeMismatch,
+    Transaction, TransactionHash, URefAddr, AUCTION_LANE_ID, MINT_LANE_ID, U512,
 };
 
 use super::{
-    types::{SpeculativeExecutionResult, StepOutcome},
+    types::{GasEstimationResult, SpeculativeExecutionResult, StepOutcome},
     utils::{self, calculate_prune_eras},
-    BlockAndExecutionArtifacts, BlockExecutionError, ExecutionPreState, Metrics, StateResultError,
-    APPROVALS_CHECKSUM_NAME, EXECUTION_RESULTS_CHECKSUM_NAME,
+    BlockAndExecutionArtifacts, BlockExecutionError, ExecutionArtifact, ExecutionPreState, Metrics,
+    StateResultError, APPROVALS_CHECKSUM_NAME, EXECUTION_RESULTS_CHECKSUM_NAME,
 };
 use crate::{
     components::fetcher::FetchItem,
@@ -52,6 +71,9 @@ use crate::{
 };
 
 /// Executes a finalized block.
+///
+/// Unless `persist_post_state` is set, the post state of the block is only computed in memory, and
+/// none of its tries are written to the global state.
 #[allow(clippy::too_many_arguments)]
 pub fn execute_finalized_block(
     data_access_layer: &DataAccessLayer<LmdbGlobalState>,
@@ -65,6 +87,8 @@ pub fn execute_finalized_block(
     current_gas_price: u8,
     next_era_gas_price: Option<u8>,
     last_switch_block_hash: Option<BlockHash>,
+    execution_threads: usize,
+    persist_post_state: bool,
 ) -> Result<BlockAndExecutionArtifacts, BlockExecutionError> {
     let block_height = executable_block.height;
     if block_height != execution_pre_state.next_block_height() {
@@ -206,679 +230,73 @@ pub fn execute_finalized_block(
         }
     }
 
-    let transaction_config = &chainspec.transaction_config;
-
-    for stored_transaction in executable_block.transactions {
-        let mut artifact_builder =
-            ExecutionArtifactBuilder::new(&stored_transaction, baseline_motes_amount);
-        let transaction =
-            MetaTransaction::from_transaction(&stored_transaction, transaction_config)
-                .map_err(|err| BlockExecutionError::TransactionConversion(err.to_string()))?;
-        let initiator_addr = transaction.initiator_addr();
-        let transaction_hash = transaction.hash();
-        let transaction_args = transaction.session_args().clone();
-        let entry_point = transaction.entry_point();
-        let authorization_keys = transaction.signers();
-
-        /*
-        we solve for halting state using a `gas limit` which is the maximum amount of
-        computation we will allow a given transaction to consume. the transaction itself
-        provides a function to determine this if provided with the current cost tables
-        gas_limit is ALWAYS calculated with price == 1.
-
-        next there is the actual cost, i.e. how much we charge for that computation
-        this is calculated by multiplying the gas limit by the current `gas_price`
-        gas price has a floor of 1, and the ceiling is configured in the chainspec
-        NOTE: when the gas price is 1, the gas limit and the cost are coincidentally
-        equal because x == x * 1; thus it is recommended to run tests with
-        price >1 to avoid being confused by this.
-
-        the third important value is the amount of computation consumed by executing a
-        transaction  for native transactions there is no wasm and the consumed always
-        equals the limit  for bytecode / wasm based transactions the consumed is based on
-        what opcodes were executed and can range from >=0 to <=gas_limit.
-        consumed is determined after execution and is used for refund & fee post-processing.
-
-        we check these top level concerns early so that we can skip if there is an error
-        */
-
-        // NOTE: this is the allowed computation limit (gas limit)
-        let gas_limit =
-            match stored_transaction.gas_limit(chainspec, transaction.transaction_lane()) {
-                Ok(gas) => gas,
-                Err(ite) => {
-                    debug!(%transaction_hash, %ite, "invalid transaction (gas limit)");
-                    artifact_builder.with_invalid_transaction(&ite);
-                    artifacts.push(artifact_builder.build());
-                    continue;
-                }
-            };
-        artifact_builder.with_gas_limit(gas_limit);
-
-        // NOTE: this is the actual adjusted cost that we charge for (gas limit * gas price)
-        let cost = match stored_transaction.gas_cost(
-            chainspec,
-            transaction.transaction_lane(),
-            current_gas_price,
-        ) {
-            Ok(motes) => motes.value(),
-            Err(ite) => {
-                debug!(%transaction_hash, "invalid transaction (motes conversion)");
-                artifact_builder.with_invalid_transaction(&ite);
-                artifacts.push(artifact_builder.build());
-                continue;
-            }
-        };
-        artifact_builder.with_added_cost(cost);
-
-        let is_standard_payment = transaction.is_standard_payment();
-        let is_custom_payment = !is_standard_payment && transaction.is_custom_payment();
-        let is_v1_wasm = transaction.is_v1_wasm();
-        let is_v2_wasm = transaction.is_v2_wasm();
-        let refund_purse_active = is_custom_payment;
-        if refund_purse_active {
-            // if custom payment before doing any processing, initialize the initiator's main purse
-            //  to be the refund purse for this transaction.
-            // NOTE: when executed, custom payment logic has the option to call set_refund_purse
-            //  on the handle payment contract to set up a different refund purse, if desired.
-            let handle_refund_request = HandleRefundRequest::new(
-                native_runtime_config.clone(),
-                state_root_hash,
-                protocol_version,
-                transaction_hash,
-                HandleRefundMode::SetRefundPurse {
-                    target: Box::new(initiator_addr.clone().into()),
-                },
-            );
-            let handle_refund_result = scratch_state.handle_refund(handle_refund_request);
-            if let Err(root_not_found) =
-                artifact_builder.with_set_refund_purse_result(&handle_refund_result)
-            {
-                if root_not_found {
-                    return Err(BlockExecutionError::RootNotFound(state_root_hash));
-                }
-                artifacts.push(artifact_builder.build());
-                continue; // don't commit effects, move on
-            }
-            state_root_hash = scratch_state
-                .commit_effects(state_root_hash, handle_refund_result.effects().clone())?;
-        }
-
-        {
-            // Ensure the initiator's main purse can cover the penalty payment before proceeding.
-            let initial_balance_result = scratch_state.balance(BalanceRequest::new(
-                state_root_hash,
-                protocol_version,
-                initiator_addr.clone().into(),
-                balance_handling,
-                ProofHandling::NoProofs,
-            ));
-
-            if let Err(root_not_found) = artifact_builder
-                .with_initial_balance_result(initial_balance_result.clone(), baseline_motes_amount)
-            {
-                if root_not_found {
-                    return Err(BlockExecutionError::RootNotFound(state_root_hash));
-                }
-                trace!(%transaction_hash, "insufficient initial balance");
-                debug!(%transaction_hash, ?initial_balance_result, %baseline_motes_amount, "insufficient initial balance");
-                artifacts.push(artifact_builder.build());
-                // only reads have happened so far, and we can't charge due
-                // to insufficient balance, so move on with no effects committed
-                continue;
-            }
-        }
-
-        let mut balance_identifier = {
-            if is_standard_payment {
-                let contract_might_pay =
-                    addressable_entity_enabled && transaction.is_contract_by_hash_invocation();
-
-                if contract_might_pay {
-                    match invoked_contract_will_pay(&scratch_state, state_root_hash, &transaction) {
-                        Ok(Some(entity_addr)) => BalanceIdentifier::Entity(entity_addr),
-                        Ok(None) => {
-                            // the initiating account pays using its main purse
-                            trace!(%transaction_hash, "direct invocation with account payment");
-                            initiator_addr.clone().into()
-                        }
-                        Err(err) => {
-                            trace!(%transaction_hash, "failed to resolve contract self payment");
-                            artifact_builder
-                                .with_state_result_error(err)
-                                .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-                            BalanceIdentifier::PenalizedAccount(
-                                initiator_addr.clone().account_hash(),
-                            )
-                        }
-                    }
-                } else {
-                    // the initiating account pays using its main purse
-                    trace!(%transaction_hash, "account session with standard payment");
-                    initiator_addr.clone().into()
-                }
-            } else if is_v2_wasm {
-                // vm2 does not support custom payment, so it MUST be standard payment
-                // if transaction runtime is v2 then the initiating account will pay using
-                // the refund purse
-                initiator_addr.clone().into()
-            } else if is_custom_payment {
-                // this is the custom payment flow
-                // the initiating account will pay, but wants to do so with a different purse or
-                // in a custom way. If anything goes wrong, penalize the sender, do not execute
-                let custom_payment_gas_limit =
-                    Gas::new(chainspec.transaction_config.native_transfer_minimum_motes * 5);
-                let pay_result = match WasmV1Request::new_custom_payment(
-                    BlockInfo::new(
-                        state_root_hash,
-                        block_time,
-                        parent_block_hash,
-                        block_height,
-                        protocol_version,
-                    ),
-                    custom_payment_gas_limit,
-                    &transaction.to_payment_input_data(),
-                ) {
-                    Ok(mut pay_request) => {
-                        pay_request
-                            .args
-                            .insert(ARG_AMOUNT, cost)
-                            .map_err(|e| BlockExecutionError::PaymentError(e.to_string()))?;
-                        execution_engine_v1.execute(&scratch_state, pay_request)
-                    }
-                    Err(error) => {
-                        WasmV1Result::invalid_executable_item(custom_payment_gas_limit, error)
-                    }
-                };
-
-                let insufficient_payment_deposited =
-                    !pay_result.balance_increased_by_amount(payment_balance_addr, cost);
-
-                if insufficient_payment_deposited || pay_result.error().is_some() {
-                    // Charge initiator for the penalty payment amount
-                    // the most expedient way to do this that aligns with later code
-                    // is to transfer from the initiator's main purse to the payment purse
-                    let transfer_result = scratch_state.transfer(TransferRequest::new_indirect(
-                        native_runtime_config.clone(),
-                        state_root_hash,
-                        protocol_version,
-                        transaction_hash,
-                        initiator_addr.clone(),
-                        authorization_keys.clone(),
-                        BalanceIdentifierTransferArgs::new(
-                            None,
-                            initiator_addr.clone().into(),
-                            BalanceIdentifier::Payment,
-                            baseline_motes_amount,
-                            None,
-                        ),
-                    ));
-
-                    let msg = match pay_result.error() {
-                        Some(err) => format!("{}", err),
-                        None => {
-                            if insufficient_payment_deposited {
-                                "Insufficient custom payment".to_string()
-                            } else {
-                                // this should be unreachable due to guard condition above
-                                let unk = "Unknown custom payment issue";
-                                warn!(%transaction_hash, unk);
-                                debug_assert!(false, "{}", unk);
-                                unk.to_string()
-                            }
-                        }
-                    };
-                    // commit penalty payment effects
-                    state_root_hash = scratch_state
-                        .commit_effects(state_root_hash, transfer_result.effects().clone())?;
-                    artifact_builder
-                        .with_error_message(msg)
-                        .with_transfer_result(transfer_result)
-                        .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-                    trace!(%transaction_hash, balance_identifier=?BalanceIdentifier::PenalizedPayment, "account session with custom payment failed");
-                    BalanceIdentifier::PenalizedPayment
-                } else {
-                    // commit successful effects
-                    state_root_hash = scratch_state
-                        .commit_effects(state_root_hash, pay_result.effects().clone())?;
-                    artifact_builder
-                        .with_wasm_v1_result(pay_result)
-                        .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-                    trace!(%transaction_hash, balance_identifier=?BalanceIdentifier::Payment, "account session with custom payment success");
-                    BalanceIdentifier::Payment
-                }
-            } else {
-                BalanceIdentifier::PenalizedAccount(initiator_addr.clone().account_hash())
-            }
-        };
-
-        let post_payment_balance_result = scratch_state.balance(BalanceRequest::new(
-            state_root_hash,
-            protocol_version,
-            balance_identifier.clone(),
-            balance_handling,
-            ProofHandling::NoProofs,
-        ));
-
-        let lane_id = transaction.transaction_lane();
-
-        let allow_execution = {
-            let is_not_penalized = !balance_identifier.is_penalty();
-            // in the case of custom payment, we do all payment processing up front after checking
-            // if the initiator can cover the penalty payment, and then either charge the full
-            // amount in the happy path or the penalty amount in the sad path...in whichever case
-            // the sad path is handled by is_penalty and the balance in the payment purse is
-            // the penalty payment or the full amount but is 'sufficient' either way
-            let is_sufficient_balance =
-                is_custom_payment || post_payment_balance_result.is_sufficient(cost);
-            let is_allowed_by_chainspec = chainspec.is_supported(lane_id);
-            let allow = is_not_penalized && is_sufficient_balance && is_allowed_by_chainspec;
-            if !allow {
-                if artifact_builder.error_message().is_none() {
-                    artifact_builder.with_error_message(format!(
-                        "penalized: {}, sufficient balance: {}, allowed by chainspec: {}",
-                        !is_not_penalized, is_sufficient_balance, is_allowed_by_chainspec
-                    ));
-                }
-                info!(%transaction_hash, ?balance_identifier, ?is_sufficient_balance, ?is_not_penalized, ?is_allowed_by_chainspec, "payment preprocessing unsuccessful");
-            } else {
-                debug!(%transaction_hash, ?balance_identifier, ?is_sufficient_balance, ?is_not_penalized, ?is_allowed_by_chainspec, "payment preprocessing successful");
-            }
-            allow
-        };
-
-        if allow_execution {
-            debug!(%transaction_hash, ?allow_execution, "execution allowed");
-            if is_standard_payment {
-                // place a processing hold on the paying account to prevent double spend.
-                let hold_amount = cost;
-                let hold_request = BalanceHoldRequest::new_processing_hold(
-                    state_root_hash,
-                    protocol_version,
-                    balance_identifier.clone(),
-                    hold_amount,
-                    insufficient_balance_handling,
-                );
-                let hold_result = scratch_state.balance_hold(hold_request);
-                state_root_hash =
-                    scratch_state.commit_effects(state_root_hash, hold_result.effects().clone())?;
-                artifact_builder
-                    .with_balance_hold_result(&hold_result)
-                    .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-            }
+    let context = TransactionExecutionContext {
+        execution_engine_v1,
+        execution_engine_v2: &execution_engine_v2,
+        chainspec,
+        metrics: metrics.as_deref(),
+        native_runtime_config: &native_runtime_config,
+        protocol_version,
+        block_time,
+        block_height,
+        parent_block_hash,
+        era_id,
+        proposer: &proposer,
+        current_gas_price,
+        payment_balance_addr,
+        addressable_entity_enabled,
+        insufficient_balance_handling,
+        refund_handling,
+        fee_handling,
+        baseline_motes_amount,
+        balance_handling,
+    };
 
-            trace!(%transaction_hash, ?lane_id, "eligible for execution");
-            match lane_id {
-                lane_id if lane_id == MINT_LANE_ID => {
-                    let runtime_args = transaction_args
-                        .as_named()
-                        .ok_or(BlockExecutionError::InvalidTransactionArgs)?;
-                    let transfer_result =
-                        scratch_state.transfer(TransferRequest::with_runtime_args(
-                            native_runtime_config.clone(),
-                            state_root_hash,
-                            protocol_version,
-                            transaction_hash,
-                            initiator_addr.clone(),
-                            authorization_keys,
-                            runtime_args.clone(),
-                        ));
-                    let consumed = gas_limit;
-                    state_root_hash = scratch_state
-                        .commit_effects(state_root_hash, transfer_result.effects().clone())?;
-                    artifact_builder
-                        .with_added_consumed(consumed)
-                        .with_transfer_result(transfer_result)
-                        .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-                }
-                lane_id if lane_id == AUCTION_LANE_ID => {
-                    let runtime_args = transaction_args
-                        .as_named()
-                        .ok_or(BlockExecutionError::InvalidTransactionArgs)?;
-                    match AuctionMethod::from_parts(entry_point, runtime_args, chainspec) {
-                        Ok(auction_method) => {
-                            let bidding_result = scratch_state.bidding(BiddingRequest::new(
-                                native_runtime_config.clone(),
-                                state_root_hash,
-                                protocol_version,
-                                transaction_hash,
-                                initiator_addr.clone(),
-                                authorization_keys,
-                                auction_method,
-                            ));
-                            let consumed = gas_limit;
-                            state_root_hash = scratch_state.commit_effects(
-                                state_root_hash,
-                                bidding_result.effects().clone(),
-                            )?;
-                            artifact_builder
-                                .with_added_consumed(consumed)
-                                .with_bidding_result(bidding_result)
-                                .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-                        }
-                        Err(ame) => {
-                            error!(
-                                %transaction_hash,
-                                ?ame,
-                                "failed to determine auction method"
-                            );
-                            artifact_builder.with_auction_method_error(&ame);
-                        }
-                    };
-                }
-                _ if is_v1_wasm => {
-                    let wasm_v1_start = Instant::now();
-                    let session_input_data = transaction.to_session_input_data();
-                    match WasmV1Request::new_session(
-                        BlockInfo::new(
-                            state_root_hash,
-                            block_time,
-                            parent_block_hash,
-                            block_height,
-                            protocol_version,
-                        ),
-                        gas_limit,
-                        &session_input_data,
-                    ) {
-                        Ok(wasm_v1_request) => {
-                            trace!(%transaction_hash, ?lane_id, ?wasm_v1_request, "able to get wasm v1 request");
-                            let wasm_v1_result =
-                                execution_engine_v1.execute(&scratch_state, wasm_v1_request);
-                            trace!(%transaction_hash, ?lane_id, ?wasm_v1_result, "able to get wasm v1 result");
-                            state_root_hash = scratch_state.commit_effects(
-                                state_root_hash,
-                                wasm_v1_result.effects().clone(),
-                            )?;
-                            // note: consumed is scraped from wasm_v1_result along w/ other fields
-                            artifact_builder
-                                .with_wasm_v1_result(wasm_v1_result)
-                                .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-                        }
-                        Err(ire) => {
-                            debug!(%transaction_hash, ?lane_id, ?ire, "unable to get wasm v1 request");
-                            artifact_builder.with_invalid_wasm_v1_request(&ire);
-                        }
-                    };
+    // transactions are first executed speculatively in parallel, each on its own fork of the
+    // scratch state.  the forks are then merged back in block order, and the transactions which
+    // read anything changed by an earlier transaction of the block are executed again, so that
+    // the outcome is the same as executing all the transactions one after another.  transactions
+    // are only charged for once merged, on the scratch state itself and in block order, so the
+    // fees and refunds paid to the purses shared by all the transactions of the block are never
+    // part of a fork, and only make the transactions which read those purses conflict.
+    let speculative_executions = speculatively_execute_transactions(
+        &context,
+        &scratch_state,
+        state_root_hash,
+        &executable_block.transactions,
+        execution_threads,
+    );
+    for (stored_transaction, speculative_execution) in executable_block
+        .transactions
+        .iter()
+        .zip(speculative_executions)
+    {
+        let execution = match speculative_execution {
+            Some((fork, execution)) if scratch_state.merge(&fork)? => execution?,
+            maybe_conflicting_execution => {
+                if maybe_conflicting_execution.is_some() {
                     if let Some(metrics) = metrics.as_ref() {
-                        metrics
-                            .exec_wasm_v1
-                            .observe(wasm_v1_start.elapsed().as_secs_f64());
+                        metrics.transactions_reexecuted.inc();
                     }
                 }
-                _ if is_v2_wasm => match WasmV2Request::new(
-                    gas_limit,
-                    chainspec.network_config.name.clone(),
+                execute_transaction(
+                    &context,
+                    &scratch_state,
                     state_root_hash,
-                    parent_block_hash,
-                    block_height,
-                    &transaction,
-                ) {
-                    Ok(wasm_v2_request) => {
-                        let result = wasm_v2_request.execute(
-                            &execution_engine_v2,
-                            state_root_hash,
-                            &scratch_state,
-                        );
-                        match result {
-                            Ok(wasm_v2_result) => {
-                                info!(contract_hash=wasm_v2_result.smart_contract_addr().map(base16::encode_lower).unwrap_or_default(),
-                                      pre_state_root_hash=%state_root_hash,
-                                      post_state_root_hash=%wasm_v2_result.post_state_hash(),
-                                      "install contract result");
-
-                                state_root_hash = wasm_v2_result.state_root_hash();
-
-                                artifact_builder.with_wasm_v2_result(wasm_v2_result);
-                            }
-                            Err(wasm_v2_error) => {
-                                artifact_builder.with_wasm_v2_error(wasm_v2_error);
-                            }
-                        }
-                    }
-                    Err(ire) => {
-                        debug!(%transaction_hash, ?lane_id, ?ire, "unable to get wasm v2 request");
-                        artifact_builder.with_invalid_wasm_v2_request(ire);
-                    }
-                },
-                _ => {
-                    // it is currently not possible to specify a vm other than v1 or v2 on the
-                    // transaction itself, so this should be unreachable
-                    unreachable!("Unknown VM target")
-                }
-            }
-        }
-
-        // clear all holds on the balance_identifier purse before payment processing
-        {
-            let hold_request = BalanceHoldRequest::new_clear(
-                state_root_hash,
-                protocol_version,
-                BalanceHoldKind::All,
-                balance_identifier.clone(),
-            );
-            let hold_result = scratch_state.balance_hold(hold_request);
-            state_root_hash =
-                scratch_state.commit_effects(state_root_hash, hold_result.effects().clone())?;
-            artifact_builder
-                .with_balance_hold_result(&hold_result)
-                .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-        }
-
-        // handle refunds per the chainspec determined setting.
-        let refund_amount = {
-            let consumed = artifact_builder.consumed();
-            let refund_mode = match refund_handling {
-                RefundHandling::NoRefund => {
-                    if fee_handling.is_no_fee() && is_custom_payment {
-                        // in no fee mode, we need to return the motes to the refund purse,
-                        //  and then point the balance_identifier to the refund purse
-                        // this will result in the downstream no fee handling logic
-                        //  placing a hold on the correct purse.
-                        balance_identifier = BalanceIdentifier::Refund;
-                        Some(HandleRefundMode::RefundNoFeeCustomPayment {
-                            initiator_addr: Box::new(initiator_addr.clone()),
-                            limit: gas_limit.value(),
-                            gas_price: current_gas_price,
-                            cost,
-                        })
-                    } else {
-                        None
-                    }
-                }
-                RefundHandling::Burn { refund_ratio } => Some(HandleRefundMode::Burn {
-                    limit: gas_limit.value(),
-                    gas_price: current_gas_price,
-                    cost,
-                    consumed,
-                    source: Box::new(balance_identifier.clone()),
-                    ratio: refund_ratio,
-                }),
-                RefundHandling::Refund { refund_ratio } => {
-                    let source = Box::new(balance_identifier.clone());
-                    if is_custom_payment {
-                        // in custom payment we have to do all payment handling up front.
-                        // therefore, if refunds are turned on we have to transfer the refunded
-                        // amount back to the specified refund purse.
-
-                        // the refund purse for a given transaction is set to the initiator's main
-                        // purse by default, but the custom payment provided by the initiator can
-                        // set a different purse when executed. thus, the handle payment system
-                        // contract tracks a refund purse and is handled internally at processing
-                        // time. Outer logic should never assume or refer to a specific purse for
-                        // purposes of refund. instead, `BalanceIdentifier::Refund` is used by outer
-                        // logic, which is interpreted by inner logic to use the currently set
-                        // refund purse.
-                        let target = Box::new(BalanceIdentifier::Refund);
-                        Some(HandleRefundMode::Refund {
-                            initiator_addr: Box::new(initiator_addr.clone()),
-                            limit: gas_limit.value(),
-                            gas_price: current_gas_price,
-                            consumed,
-                            cost,
-                            ratio: refund_ratio,
-                            source,
-                            target,
-                        })
-                    } else {
-                        // in normal payment handling we put a temporary processing hold
-                        // on the paying purse rather than take the token up front.
-                        // thus, here we only want to determine the refund amount rather than
-                        // attempt to process a refund on something we haven't actually taken yet.
-                        // later in the flow when the processing hold is released and payment is
-                        // finalized we reduce the amount taken by the refunded amount. This avoids
-                        // the churn of taking the token up front via transfer (which writes
-                        // multiple permanent records) and then transfer some of it back (which
-                        // writes more permanent records).
-                        Some(HandleRefundMode::CalculateAmount {
-                            limit: gas_limit.value(),
-                            gas_price: current_gas_price,
-                            consumed,
-                            cost,
-                            ratio: refund_ratio,
-                            source,
-                        })
-                    }
-                }
-            };
-            match refund_mode {
-                Some(refund_mode) => {
-                    let handle_refund_request = HandleRefundRequest::new(
-                        native_runtime_config.clone(),
-                        state_root_hash,
-                        protocol_version,
-                        transaction_hash,
-                        refund_mode,
-                    );
-                    let handle_refund_result = scratch_state.handle_refund(handle_refund_request);
-                    let refunded_amount = handle_refund_result.refund_amount();
-                    state_root_hash = scratch_state
-                        .commit_effects(state_root_hash, handle_refund_result.effects().clone())?;
-                    artifact_builder
-                        .with_handle_refund_result(&handle_refund_result)
-                        .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-
-                    refunded_amount
-                }
-                None => U512::zero(),
+                    stored_transaction,
+                )?
             }
         };
-
-        // handle fees per the chainspec determined setting.
-        let handle_fee_result = match fee_handling {
-            FeeHandling::NoFee => {
-                // in this mode, a gas hold is placed on the payer's purse.
-                let amount = cost.saturating_sub(refund_amount);
-                let hold_request = BalanceHoldRequest::new_gas_hold(
-                    state_root_hash,
-                    protocol_version,
-                    balance_identifier,
-                    amount,
-                    insufficient_balance_handling,
-                );
-                let hold_result = scratch_state.balance_hold(hold_request);
-                state_root_hash =
-                    scratch_state.commit_effects(state_root_hash, hold_result.effects().clone())?;
-                artifact_builder
-                    .with_balance_hold_result(&hold_result)
-                    .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-                let handle_fee_request = HandleFeeRequest::new(
-                    native_runtime_config.clone(),
-                    state_root_hash,
-                    protocol_version,
-                    transaction_hash,
-                    HandleFeeMode::credit(proposer.clone(), amount, era_id),
-                );
-                scratch_state.handle_fee(handle_fee_request)
-            }
-            FeeHandling::Burn => {
-                // in this mode, the fee portion is burned.
-                let amount = cost.saturating_sub(refund_amount);
-                let handle_fee_request = HandleFeeRequest::new(
-                    native_runtime_config.clone(),
-                    state_root_hash,
-                    protocol_version,
-                    transaction_hash,
-                    HandleFeeMode::burn(balance_identifier, Some(amount)),
-                );
-                scratch_state.handle_fee(handle_fee_request)
-            }
-            FeeHandling::PayToProposer => {
-                // in this mode, the consumed gas is paid as a fee to the block proposer
-                let amount = cost.saturating_sub(refund_amount);
-                let handle_fee_request = HandleFeeRequest::new(
-                    native_runtime_config.clone(),
-                    state_root_hash,
-                    protocol_version,
-                    transaction_hash,
-                    HandleFeeMode::pay(
-                        Box::new(initiator_addr),
-                        balance_identifier,
-                        BalanceIdentifier::Public(*(proposer.clone())),
-                        amount,
-                    ),
-                );
-                scratch_state.handle_fee(handle_fee_request)
-            }
-            FeeHandling::Accumulate => {
-                // in this mode, consumed gas is accumulated into a single purse
-                // for later distribution
-                let amount = cost.saturating_sub(refund_amount);
-                let handle_fee_request = HandleFeeRequest::new(
-                    native_runtime_config.clone(),
-                    state_root_hash,
-                    protocol_version,
-                    transaction_hash,
-                    HandleFeeMode::pay(
-                        Box::new(initiator_addr),
-                        balance_identifier,
-                        BalanceIdentifier::Accumulate,
-                        amount,
-                    ),
-                );
-                scratch_state.handle_fee(handle_fee_request)
+        let artifact = match execution {
+            TransactionExecution::Skipped(artifact) => *artifact,
+            TransactionExecution::Executed(executed_transaction) => {
+                let (post_state_hash, artifact) =
+                    charge_for_transaction(&context, &scratch_state, *executed_transaction)?;
+                state_root_hash = post_state_hash;
+                artifact
             }
         };
-
-        state_root_hash =
-            scratch_state.commit_effects(state_root_hash, handle_fee_result.effects().clone())?;
-
-        artifact_builder
-            .with_handle_fee_result(&handle_fee_result)
-            .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
-
-        // clear refund purse if it was set
-        if refund_purse_active {
-            // if refunds are turned on we initialize the refund purse to the initiator's main
-            // purse before doing any processing. NOTE: when executed, custom payment logic
-            // has the option to call set_refund_purse on the handle payment contract to set
-            // up a different refund purse, if desired.
-            let handle_refund_request = HandleRefundRequest::new(
-                native_runtime_config.clone(),
-                state_root_hash,
-                protocol_version,
-                transaction_hash,
-                HandleRefundMode::ClearRefundPurse,
-            );
-            let handle_refund_result = scratch_state.handle_refund(handle_refund_request);
-            if let Err(root_not_found) =
-                artifact_builder.with_clear_refund_purse_result(&handle_refund_result)
-            {
-                if root_not_found {
-                    return Err(BlockExecutionError::RootNotFound(state_root_hash));
-                }
-                warn!(
-                    "{}",
-                    artifact_builder.error_message().unwrap_or(
-                        "unknown error encountered when attempting to clear refund purse"
-                            .to_string()
-                    )
-                );
-            }
-            state_root_hash = scratch_state
-                .commit_effects(state_root_hash, handle_refund_result.effects().clone())?;
-        }
-
-        artifacts.push(artifact_builder.build());
+        artifacts.push(artifact);
     }
 
     // transaction processing is finished
@@ -1143,7 +561,7 @@ pub fn execute_finalized_block(
         }
     }
 
-    {
+    let proof_of_checksum_registry = if persist_post_state {
         let database_write_start = Instant::now();
         // Finally, the new state-root-hash from the cumulative changes to global state is
         // returned when they are written to LMDB.
@@ -1167,7 +585,24 @@ pub fn execute_finalized_block(
                 .database_flush_time
                 .observe(database_flush_start.elapsed().as_secs_f64());
         }
-    }
+
+        match data_access_layer.tracking_copy(state_root_hash)? {
+            Some(tc) => match tc.reader().read_with_proof(&Key::ChecksumRegistry)? {
+                Some(proof) => proof,
+                None => return Err(BlockExecutionError::MissingChecksumRegistry),
+            },
+            None => return Err(BlockExecutionError::RootNotFound(state_root_hash)),
+        }
+    } else {
+        let post_state = data_access_layer
+            .state()
+            .scratch_post_state(state_root_hash, scratch_state)?;
+        state_root_hash = post_state.state_root_hash();
+        match post_state.read_with_proof(&Key::ChecksumRegistry)? {
+            Some(proof) => proof,
+            None => return Err(BlockExecutionError::MissingChecksumRegistry),
+        }
+    };
 
     // the rest of this is post process, picking out data bits to return to caller
     let next_era_id = executable_block.era_id.successor();
@@ -1241,14 +676,6 @@ pub fn execute_finalized_block(
         last_switch_block_hash,
     ));
 
-    let proof_of_checksum_registry = match data_access_layer.tracking_copy(state_root_hash)? {
-        Some(tc) => match tc.reader().read_with_proof(&Key::ChecksumRegistry)? {
-            Some(proof) => proof,
-            None => return Err(BlockExecutionError::MissingChecksumRegistry),
-        },
-        None => return Err(BlockExecutionError::RootNotFound(state_root_hash)),
-    };
-
     let approvals_hashes = Box::new(ApprovalsHashes::new(
         *block.hash(),
         transaction_approvals_hashes,
@@ -1273,39 +700,893 @@ pub fn execute_finalized_block(
     })
 }
 
-/// Execute the transaction without committing the effects.
-/// Intended to be used for discovery operations on read-only nodes.
+/// The settings and block level values the transactions of a block are executed with.
+#[derive(Clone, Copy)]
+struct TransactionExecutionContext<'a> {
+    execution_engine_v1: &'a ExecutionEngineV1,
+    execution_engine_v2: &'a ExecutorV2,
+    chainspec: &'a Chainspec,
+    metrics: Option<&'a Metrics>,
+    native_runtime_config: &'a NativeRuntimeConfig,
+    protocol_version: ProtocolVersion,
+    block_time: BlockTime,
+    block_height: u64,
+    parent_block_hash: BlockHash,
+    era_id: EraId,
+    proposer: &'a PublicKey,
+    current_gas_price: u8,
+    payment_balance_addr: URefAddr,
+    addressable_entity_enabled: bool,
+    insufficient_balance_handling: InsufficientBalanceHandling,
+    refund_handling: RefundHandling,
+    fee_handling: FeeHandling,
+    baseline_motes_amount: U512,
+    balance_handling: BalanceHandling,
+}
+
+/// The outcome of executing a transaction, before it is charged for.
+enum TransactionExecution {
+    /// The transaction was not executed, and its execution artifact is complete.
+    Skipped(Box<ExecutionArtifact>),
+    /// The transaction was executed, and is still to be charged for.
+    Executed(Box<ExecutedTransaction>),
+}
+
+/// A transaction which was executed, but not yet charged for.
+struct ExecutedTransaction {
+    artifact_builder: ExecutionArtifactBuilder,
+    initiator_addr: InitiatorAddr,
+    transaction_hash: TransactionHash,
+    balance_identifier: BalanceIdentifier,
+    gas_limit: Gas,
+    cost: U512,
+    is_custom_payment: bool,
+    state_root_hash: Digest,
+}
+
+/// The fork of the scratch state a transaction was speculatively executed on, and the outcome of
+/// the execution.
+type SpeculativeExecution = (
+    ScratchGlobalState,
+    Result<TransactionExecution, BlockExecutionError>,
+);
+
+/// Executes the transactions of a block speculatively, each on its own fork of the scratch state,
+/// using up to `execution_threads` threads.
 ///
-/// Returns effects of the execution.
-pub(super) fn speculatively_execute<S>(
-    state_provider: &S,
-    chainspec: &Chainspec,
-    execution_engine_v1: &ExecutionEngineV1,
-    block_header: BlockHeader,
-    input_transaction: Transaction,
-) -> SpeculativeExecutionResult
-where
-    S: StateProvider,
-{
-    let transaction_config = &chainspec.transaction_config;
-    let maybe_transaction =
-        MetaTransaction::from_transaction(&input_transaction, transaction_config);
-    if let Err(error) = maybe_transaction {
-        return SpeculativeExecutionResult::invalid_transaction(error);
+/// Returns the speculative execution of each transaction, or `None` for the transactions which
+/// were not executed speculatively.  Nothing is executed speculatively with a single execution
+/// thread.
+fn speculatively_execute_transactions(
+    context: &TransactionExecutionContext,
+    scratch_state: &ScratchGlobalState,
+    state_root_hash: Digest,
+    transactions: &[Transaction],
+    execution_threads: usize,
+) -> Vec<Option<SpeculativeExecution>> {
+    let mut speculative_executions = transactions.iter().map(|_| None).collect_vec();
+    if execution_threads <= 1 || transactions.len() <= 1 {
+        return speculative_executions;
     }
-    let transaction = maybe_transaction.unwrap();
-    let state_root_hash = block_header.state_root_hash();
-    let parent_block_hash = block_header.block_hash();
-    let block_height = block_header.height();
-    let block_time = block_header
-        .timestamp()
-        .saturating_add(chainspec.core_config.minimum_block_time);
-    let gas_limit = match input_transaction.gas_limit(chainspec, transaction.transaction_lane()) {
-        Ok(gas_limit) => gas_limit,
-        Err(_) => {
-            return SpeculativeExecutionResult::invalid_gas_limit(input_transaction);
-        }
-    };
+
+    let next_index = AtomicUsize::new(0);
+    let execute = || {
+        let mut executions = vec![];
+        loop {
+            let index = next_index.fetch_add(1, atomic::Ordering::Relaxed);
+            let Some(transaction) = transactions.get(index) else {
+                return executions;
+            };
+            // the executions of VM2 transactions share the execution stack of the executor, so
+            // they are not run concurrently.
+            let is_v2_wasm = MetaTransaction::from_transaction(
+                transaction,
+                &context.chainspec.transaction_config,
+            )
+            .map_or(true, |transaction| transaction.is_v2_wasm());
+            if is_v2_wasm {
+                continue;
+            }
+            let fork = scratch_state.fork();
+            let execution = execute_transaction(context, &fork, state_root_hash, transaction);
+            executions.push((index, (fork, execution)));
+        }
+    };
+    thread::scope(|scope| {
+        let workers = (0..execution_threads.min(transactions.len()))
+            .map(|_| scope.spawn(execute))
+            .collect_vec();
+        for worker in workers {
+            let executions = worker
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            for (index, speculative_execution) in executions {
+                speculative_executions[index] = Some(speculative_execution);
+            }
+        }
+    });
+    speculative_executions
+}
+
+/// Executes a transaction, up to the point where it is charged for.
+///
+/// This covers the initial checks, the payment and the execution of the session, none of which
+/// depend on the outcome of the charging of other transactions of the block.
+fn execute_transaction(
+    context: &TransactionExecutionContext,
+    scratch_state: &ScratchGlobalState,
+    mut state_root_hash: Digest,
+    stored_transaction: &Transaction,
+) -> Result<TransactionExecution, BlockExecutionError> {
+    let TransactionExecutionContext {
+        execution_engine_v1,
+        execution_engine_v2,
+        chainspec,
+        metrics,
+        native_runtime_config,
+        protocol_version,
+        block_time,
+        block_height,
+        parent_block_hash,
+        current_gas_price,
+        payment_balance_addr,
+        addressable_entity_enabled,
+        insufficient_balance_handling,
+        baseline_motes_amount,
+        balance_handling,
+        ..
+    } = *context;
+
+    let mut artifact_builder =
+        ExecutionArtifactBuilder::new(stored_transaction, baseline_motes_amount);
+    let transaction =
+        MetaTransaction::from_transaction(stored_transaction, &chainspec.transaction_config)
+            .map_err(|err| BlockExecutionError::TransactionConversion(err.to_string()))?;
+    let initiator_addr = transaction.initiator_addr();
+    let transaction_hash = transaction.hash();
+    let transaction_args = transaction.session_args().clone();
+    let entry_point = transaction.entry_point();
+    let authorization_keys = transaction.signers();
+
+    /*
+    we solve for halting state using a `gas limit` which is the maximum amount of
+    computation we will allow a given transaction to consume. the transaction itself
+    provides a function to determine this if provided with the current cost tables
+    gas_limit is ALWAYS calculated with price == 1.
+
+    next there is the actual cost, i.e. how much we charge for that computation
+    this is calculated by multiplying the gas limit by the current `gas_price`
+    gas price has a floor of 1, and the ceiling is configured in the chainspec
+    NOTE: when the gas price is 1, the gas limit and the cost are coincidentally
+    equal because x == x * 1; thus it is recommended to run tests with
+    price >1 to avoid being confused by this.
+
+    the third important value is the amount of computation consumed by executing a
+    transaction  for native transactions there is no wasm and the consumed always
+    equals the limit  for bytecode / wasm based transactions the consumed is based on
+    what opcodes were executed and can range from >=0 to <=gas_limit.
+    consumed is determined after execution and is used for refund & fee post-processing.
+
+    we check these top level concerns early so that we can skip if there is an error
+    */
+
+    // NOTE: this is the allowed computation limit (gas limit)
+    let gas_limit = match stored_transaction.gas_limit(chainspec, transaction.transaction_lane()) {
+        Ok(gas) => gas,
+        Err(ite) => {
+            debug!(%transaction_hash, %ite, "invalid transaction (gas limit)");
+            artifact_builder.with_invalid_transaction(&ite);
+            return Ok(TransactionExecution::Skipped(Box::new(
+                artifact_builder.build(),
+            )));
+        }
+    };
+    artifact_builder.with_gas_limit(gas_limit);
+
+    // NOTE: this is the actual adjusted cost that we charge for (gas limit * gas price)
+    let cost = match stored_transaction.gas_cost(
+        chainspec,
+        transaction.transaction_lane(),
+        current_gas_price,
+    ) {
+        Ok(motes) => motes.value(),
+        Err(ite) => {
+            debug!(%transaction_hash, "invalid transaction (motes conversion)");
+            artifact_builder.with_invalid_transaction(&ite);
+            return Ok(TransactionExecution::Skipped(Box::new(
+                artifact_builder.build(),
+            )));
+        }
+    };
+    artifact_builder.with_added_cost(cost);
+
+    let is_standard_payment = transaction.is_standard_payment();
+    let is_custom_payment = !is_standard_payment && transaction.is_custom_payment();
+    let is_v1_wasm = transaction.is_v1_wasm();
+    let is_v2_wasm = transaction.is_v2_wasm();
+    let refund_purse_active = is_custom_payment;
+    if refund_purse_active {
+        // if custom payment before doing any processing, initialize the initiator's main purse
+        //  to be the refund purse for this transaction.
+        // NOTE: when executed, custom payment logic has the option to call set_refund_purse
+        //  on the handle payment contract to set up a different refund purse, if desired.
+        let handle_refund_request = HandleRefundRequest::new(
+            native_runtime_config.clone(),
+            state_root_hash,
+            protocol_version,
+            transaction_hash,
+            HandleRefundMode::SetRefundPurse {
+                target: Box::new(initiator_addr.clone().into()),
+            },
+        );
+        let handle_refund_result = scratch_state.handle_refund(handle_refund_request);
+        if let Err(root_not_found) =
+            artifact_builder.with_set_refund_purse_result(&handle_refund_result)
+        {
+            if root_not_found {
+                return Err(BlockExecutionError::RootNotFound(state_root_hash));
+            }
+            // don't commit effects, move on
+            return Ok(TransactionExecution::Skipped(Box::new(
+                artifact_builder.build(),
+            )));
+        }
+        state_root_hash = scratch_state
+            .commit_effects(state_root_hash, handle_refund_result.effects().clone())?;
+    }
+
+    {
+        // Ensure the initiator's main purse can cover the penalty payment before proceeding.
+        let initial_balance_result = scratch_state.balance(BalanceRequest::new(
+            state_root_hash,
+            protocol_version,
+            initiator_addr.clone().into(),
+            balance_handling,
+            ProofHandling::NoProofs,
+        ));
+
+        if let Err(root_not_found) = artifact_builder
+            .with_initial_balance_result(initial_balance_result.clone(), baseline_motes_amount)
+        {
+            if root_not_found {
+                return Err(BlockExecutionError::RootNotFound(state_root_hash));
+            }
+            trace!(%transaction_hash, "insufficient initial balance");
+            debug!(%transaction_hash, ?initial_balance_result, %baseline_motes_amount, "insufficient initial balance");
+            // only reads have happened so far, and we can't charge due
+            // to insufficient balance, so move on with no effects committed
+            return Ok(TransactionExecution::Skipped(Box::new(
+                artifact_builder.build(),
+            )));
+        }
+    }
+
+    let balance_identifier = {
+        if is_standard_payment {
+            let contract_might_pay =
+                addressable_entity_enabled && transaction.is_contract_by_hash_invocation();
+
+            if contract_might_pay {
+                match invoked_contract_will_pay(scratch_state, state_root_hash, &transaction) {
+                    Ok(Some(entity_addr)) => BalanceIdentifier::Entity(entity_addr),
+                    Ok(None) => {
+                        // the initiating account pays using its main purse
+                        trace!(%transaction_hash, "direct invocation with account payment");
+                        initiator_addr.clone().into()
+                    }
+                    Err(err) => {
+                        trace!(%transaction_hash, "failed to resolve contract self payment");
+                        artifact_builder
+                            .with_state_result_error(err)
+                            .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+                        BalanceIdentifier::PenalizedAccount(initiator_addr.clone().account_hash())
+                    }
+                }
+            } else {
+                // the initiating account pays using its main purse
+                trace!(%transaction_hash, "account session with standard payment");
+                initiator_addr.clone().into()
+            }
+        } else if is_v2_wasm {
+            // vm2 does not support custom payment, so it MUST be standard payment
+            // if transaction runtime is v2 then the initiating account will pay using
+            // the refund purse
+            initiator_addr.clone().into()
+        } else if is_custom_payment {
+            // this is the custom payment flow
+            // the initiating account will pay, but wants to do so with a different purse or
+            // in a custom way. If anything goes wrong, penalize the sender, do not execute
+            let custom_payment_gas_limit =
+                Gas::new(chainspec.transaction_config.native_transfer_minimum_motes * 5);
+            let pay_result = match WasmV1Request::new_custom_payment(
+                BlockInfo::new(
+                    state_root_hash,
+                    block_time,
+                    parent_block_hash,
+                    block_height,
+                    protocol_version,
+                ),
+                custom_payment_gas_limit,
+                &transaction.to_payment_input_data(),
+            ) {
+                Ok(mut pay_request) => {
+                    pay_request
+                        .args
+                        .insert(ARG_AMOUNT, cost)
+                        .map_err(|e| BlockExecutionError::PaymentError(e.to_string()))?;
+                    execution_engine_v1.execute(scratch_state, pay_request)
+                }
+                Err(error) => {
+                    WasmV1Result::invalid_executable_item(custom_payment_gas_limit, error)
+                }
+            };
+
+            let insufficient_payment_deposited =
+                !pay_result.balance_increased_by_amount(payment_balance_addr, cost);
+
+            if insufficient_payment_deposited || pay_result.error().is_some() {
+                // Charge initiator for the penalty payment amount
+                // the most expedient way to do this that aligns with later code
+                // is to transfer from the initiator's main purse to the payment purse
+                let transfer_result = scratch_state.transfer(TransferRequest::new_indirect(
+                    native_runtime_config.clone(),
+                    state_root_hash,
+                    protocol_version,
+                    transaction_hash,
+                    initiator_addr.clone(),
+                    authorization_keys.clone(),
+                    BalanceIdentifierTransferArgs::new(
+                        None,
+                        initiator_addr.clone().into(),
+                        BalanceIdentifier::Payment,
+                        baseline_motes_amount,
+                        None,
+                    ),
+                ));
+
+                let msg = match pay_result.error() {
+                    Some(err) => format!("{}", err),
+                    None => {
+                        if insufficient_payment_deposited {
+                            "Insufficient custom payment".to_string()
+                        } else {
+                            // this should be unreachable due to guard condition above
+                            let unk = "Unknown custom payment issue";
+                            warn!(%transaction_hash, unk);
+                            debug_assert!(false, "{}", unk);
+                            unk.to_string()
+                        }
+                    }
+                };
+                // commit penalty payment effects
+                state_root_hash = scratch_state
+                    .commit_effects(state_root_hash, transfer_result.effects().clone())?;
+                artifact_builder
+                    .with_error_message(msg)
+                    .with_transfer_result(transfer_result)
+                    .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+                trace!(%transaction_hash, balance_identifier=?BalanceIdentifier::PenalizedPayment, "account session with custom payment failed");
+                BalanceIdentifier::PenalizedPayment
+            } else {
+                // commit successful effects
+                state_root_hash =
+                    scratch_state.commit_effects(state_root_hash, pay_result.effects().clone())?;
+                artifact_builder
+                    .with_wasm_v1_result(pay_result)
+                    .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+                trace!(%transaction_hash, balance_identifier=?BalanceIdentifier::Payment, "account session with custom payment success");
+                BalanceIdentifier::Payment
+            }
+        } else {
+            BalanceIdentifier::PenalizedAccount(initiator_addr.clone().account_hash())
+        }
+    };
+
+    let post_payment_balance_result = scratch_state.balance(BalanceRequest::new(
+        state_root_hash,
+        protocol_version,
+        balance_identifier.clone(),
+        balance_handling,
+        ProofHandling::NoProofs,
+    ));
+
+    let lane_id = transaction.transaction_lane();
+
+    let allow_execution = {
+        let is_not_penalized = !balance_identifier.is_penalty();
+        // in the case of custom payment, we do all payment processing up front after checking
+        // if the initiator can cover the penalty payment, and then either charge the full
+        // amount in the happy path or the penalty amount in the sad path...in whichever case
+        // the sad path is handled by is_penalty and the balance in the payment purse is
+        // the penalty payment or the full amount but is 'sufficient' either way
+        let is_sufficient_balance =
+            is_custom_payment || post_payment_balance_result.is_sufficient(cost);
+        let is_allowed_by_chainspec = chainspec.is_supported(lane_id);
+        let allow = is_not_penalized && is_sufficient_balance && is_allowed_by_chainspec;
+        if !allow {
+            if artifact_builder.error_message().is_none() {
+                artifact_builder.with_error_message(format!(
+                    "penalized: {}, sufficient balance: {}, allowed by chainspec: {}",
+                    !is_not_penalized, is_sufficient_balance, is_allowed_by_chainspec
+                ));
+            }
+            info!(%transaction_hash, ?balance_identifier, ?is_sufficient_balance, ?is_not_penalized, ?is_allowed_by_chainspec, "payment preprocessing unsuccessful");
+        } else {
+            debug!(%transaction_hash, ?balance_identifier, ?is_sufficient_balance, ?is_not_penalized, ?is_allowed_by_chainspec, "payment preprocessing successful");
+        }
+        allow
+    };
+
+    if allow_execution {
+        debug!(%transaction_hash, ?allow_execution, "execution allowed");
+        if is_standard_payment {
+            // place a processing hold on the paying account to prevent double spend.
+            let hold_amount = cost;
+            let hold_request = BalanceHoldRequest::new_processing_hold(
+                state_root_hash,
+                protocol_version,
+                balance_identifier.clone(),
+                hold_amount,
+                insufficient_balance_handling,
+            );
+            let hold_result = scratch_state.balance_hold(hold_request);
+            state_root_hash =
+                scratch_state.commit_effects(state_root_hash, hold_result.effects().clone())?;
+            artifact_builder
+                .with_balance_hold_result(&hold_result)
+                .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+        }
+
+        trace!(%transaction_hash, ?lane_id, "eligible for execution");
+        match lane_id {
+            lane_id if lane_id == MINT_LANE_ID => {
+                let runtime_args = transaction_args
+                    .as_named()
+                    .ok_or(BlockExecutionError::InvalidTransactionArgs)?;
+                let transfer_result = scratch_state.transfer(TransferRequest::with_runtime_args(
+                    native_runtime_config.clone(),
+                    state_root_hash,
+                    protocol_version,
+                    transaction_hash,
+                    initiator_addr.clone(),
+                    authorization_keys,
+                    runtime_args.clone(),
+                ));
+                let consumed = gas_limit;
+                state_root_hash = scratch_state
+                    .commit_effects(state_root_hash, transfer_result.effects().clone())?;
+                artifact_builder
+                    .with_added_consumed(consumed)
+                    .with_transfer_result(transfer_result)
+                    .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+            }
+            lane_id if lane_id == AUCTION_LANE_ID => {
+                let runtime_args = transaction_args
+                    .as_named()
+                    .ok_or(BlockExecutionError::InvalidTransactionArgs)?;
+                match AuctionMethod::from_parts(entry_point, runtime_args, chainspec) {
+                    Ok(auction_method) => {
+                        let bidding_result = scratch_state.bidding(BiddingRequest::new(
+                            native_runtime_config.clone(),
+                            state_root_hash,
+                            protocol_version,
+                            transaction_hash,
+                            initiator_addr.clone(),
+                            authorization_keys,
+                            auction_method,
+                        ));
+                        let consumed = gas_limit;
+                        state_root_hash = scratch_state
+                            .commit_effects(state_root_hash, bidding_result.effects().clone())?;
+                        artifact_builder
+                            .with_added_consumed(consumed)
+                            .with_bidding_result(bidding_result)
+                            .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+                    }
+                    Err(ame) => {
+                        error!(
+                            %transaction_hash,
+                            ?ame,
+                            "failed to determine auction method"
+                        );
+                        artifact_builder.with_auction_method_error(&ame);
+                    }
+                };
+            }
+            _ if is_v1_wasm => {
+                let wasm_v1_start = Instant::now();
+                let session_input_data = transaction.to_session_input_data();
+                match WasmV1Request::new_session(
+                    BlockInfo::new(
+                        state_root_hash,
+                        block_time,
+                        parent_block_hash,
+                        block_height,
+                        protocol_version,
+                    ),
+                    gas_limit,
+                    &session_input_data,
+                ) {
+                    Ok(wasm_v1_request) => {
+                        trace!(%transaction_hash, ?lane_id, ?wasm_v1_request, "able to get wasm v1 request");
+                        let wasm_v1_result =
+                            execution_engine_v1.execute(scratch_state, wasm_v1_request);
+                        trace!(%transaction_hash, ?lane_id, ?wasm_v1_result, "able to get wasm v1 result");
+                        state_root_hash = scratch_state
+                            .commit_effects(state_root_hash, wasm_v1_result.effects().clone())?;
+                        // note: consumed is scraped from wasm_v1_result along w/ other fields
+                        artifact_builder
+                            .with_wasm_v1_result(wasm_v1_result)
+                            .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+                    }
+                    Err(ire) => {
+                        debug!(%transaction_hash, ?lane_id, ?ire, "unable to get wasm v1 request");
+                        artifact_builder.with_invalid_wasm_v1_request(&ire);
+                    }
+                };
+                if let Some(metrics) = metrics.as_ref() {
+                    metrics
+                        .exec_wasm_v1
+                        .observe(wasm_v1_start.elapsed().as_secs_f64());
+                }
+            }
+            _ if is_v2_wasm => match WasmV2Request::new(
+                gas_limit,
+                chainspec.network_config.name.clone(),
+                state_root_hash,
+                parent_block_hash,
+                block_height,
+                &transaction,
+            ) {
+                Ok(wasm_v2_request) => {
+                    let result = wasm_v2_request.execute(
+                        execution_engine_v2,
+                        state_root_hash,
+                        scratch_state,
+                    );
+                    match result {
+                        Ok(wasm_v2_result) => {
+                            info!(contract_hash=wasm_v2_result.smart_contract_addr().map(base16::encode_lower).unwrap_or_default(),
+                                  pre_state_root_hash=%state_root_hash,
+                                  post_state_root_hash=%wasm_v2_result.post_state_hash(),
+                                  "install contract result");
+
+                            state_root_hash = wasm_v2_result.state_root_hash();
+
+                            artifact_builder.with_wasm_v2_result(wasm_v2_result);
+                        }
+                        Err(wasm_v2_error) => {
+                            artifact_builder.with_wasm_v2_error(wasm_v2_error);
+                        }
+                    }
+                }
+                Err(ire) => {
+                    debug!(%transaction_hash, ?lane_id, ?ire, "unable to get wasm v2 request");
+                    artifact_builder.with_invalid_wasm_v2_request(ire);
+                }
+            },
+            _ => {
+                // it is currently not possible to specify a vm other than v1 or v2 on the
+                // transaction itself, so this should be unreachable
+                unreachable!("Unknown VM target")
+            }
+        }
+    }
+
+    Ok(TransactionExecution::Executed(Box::new(
+        ExecutedTransaction {
+            artifact_builder,
+            initiator_addr,
+            transaction_hash,
+            balance_identifier,
+            gas_limit,
+            cost,
+            is_custom_payment,
+            state_root_hash,
+        },
+    )))
+}
+
+/// Charges for an executed transaction, handling its refund and fee, and returns the resulting
+/// state root hash and execution artifact.
+fn charge_for_transaction(
+    context: &TransactionExecutionContext,
+    scratch_state: &ScratchGlobalState,
+    executed_transaction: ExecutedTransaction,
+) -> Result<(Digest, ExecutionArtifact), BlockExecutionError> {
+    let TransactionExecutionContext {
+        native_runtime_config,
+        protocol_version,
+        era_id,
+        proposer,
+        current_gas_price,
+        insufficient_balance_handling,
+        refund_handling,
+        fee_handling,
+        ..
+    } = *context;
+    let ExecutedTransaction {
+        mut artifact_builder,
+        initiator_addr,
+        transaction_hash,
+        mut balance_identifier,
+        gas_limit,
+        cost,
+        is_custom_payment,
+        mut state_root_hash,
+    } = executed_transaction;
+    let refund_purse_active = is_custom_payment;
+
+    // clear all holds on the balance_identifier purse before payment processing
+    {
+        let hold_request = BalanceHoldRequest::new_clear(
+            state_root_hash,
+            protocol_version,
+            BalanceHoldKind::All,
+            balance_identifier.clone(),
+        );
+        let hold_result = scratch_state.balance_hold(hold_request);
+        state_root_hash =
+            scratch_state.commit_effects(state_root_hash, hold_result.effects().clone())?;
+        artifact_builder
+            .with_balance_hold_result(&hold_result)
+            .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+    }
+
+    // handle refunds per the chainspec determined setting.
+    let refund_amount = {
+        let consumed = artifact_builder.consumed();
+        let refund_mode = match refund_handling {
+            RefundHandling::NoRefund => {
+                if fee_handling.is_no_fee() && is_custom_payment {
+                    // in no fee mode, we need to return the motes to the refund purse,
+                    //  and then point the balance_identifier to the refund purse
+                    // this will result in the downstream no fee handling logic
+                    //  placing a hold on the correct purse.
+                    balance_identifier = BalanceIdentifier::Refund;
+                    Some(HandleRefundMode::RefundNoFeeCustomPayment {
+                        initiator_addr: Box::new(initiator_addr.clone()),
+                        limit: gas_limit.value(),
+                        gas_price: current_gas_price,
+                        cost,
+                    })
+                } else {
+                    None
+                }
+            }
+            RefundHandling::Burn { refund_ratio } => Some(HandleRefundMode::Burn {
+                limit: gas_limit.value(),
+                gas_price: current_gas_price,
+                cost,
+                consumed,
+                source: Box::new(balance_identifier.clone()),
+                ratio: refund_ratio,
+            }),
+            RefundHandling::Refund { refund_ratio } => {
+                let source = Box::new(balance_identifier.clone());
+                if is_custom_payment {
+                    // in custom payment we have to do all payment handling up front.
+                    // therefore, if refunds are turned on we have to transfer the refunded
+                    // amount back to the specified refund purse.
+
+                    // the refund purse for a given transaction is set to the initiator's main
+                    // purse by default, but the custom payment provided by the initiator can
+                    // set a different purse when executed. thus, the handle payment system
+                    // contract tracks a refund purse and is handled internally at processing
+                    // time. Outer logic should never assume or refer to a specific purse for
+                    // purposes of refund. instead, `BalanceIdentifier::Refund` is used by outer
+                    // logic, which is interpreted by inner logic to use the currently set
+                    // refund purse.
+                    let target = Box::new(BalanceIdentifier::Refund);
+                    Some(HandleRefundMode::Refund {
+                        initiator_addr: Box::new(initiator_addr.clone()),
+                        limit: gas_limit.value(),
+                        gas_price: current_gas_price,
+                        consumed,
+                        cost,
+                        ratio: refund_ratio,
+                        source,
+                        target,
+                    })
+                } else {
+                    // in normal payment handling we put a temporary processing hold
+                    // on the paying purse rather than take the token up front.
+                    // thus, here we only want to determine the refund amount rather than
+                    // attempt to process a refund on something we haven't actually taken yet.
+                    // later in the flow when the processing hold is released and payment is
+                    // finalized we reduce the amount taken by the refunded amount. This avoids
+                    // the churn of taking the token up front via transfer (which writes
+                    // multiple permanent records) and then transfer some of it back (which
+                    // writes more permanent records).
+                    Some(HandleRefundMode::CalculateAmount {
+                        limit: gas_limit.value(),
+                        gas_price: current_gas_price,
+                        consumed,
+                        cost,
+                        ratio: refund_ratio,
+                        source,
+                    })
+                }
+            }
+        };
+        match refund_mode {
+            Some(refund_mode) => {
+                let handle_refund_request = HandleRefundRequest::new(
+                    native_runtime_config.clone(),
+                    state_root_hash,
+                    protocol_version,
+                    transaction_hash,
+                    refund_mode,
+                );
+                let handle_refund_result = scratch_state.handle_refund(handle_refund_request);
+                let refunded_amount = handle_refund_result.refund_amount();
+                state_root_hash = scratch_state
+                    .commit_effects(state_root_hash, handle_refund_result.effects().clone())?;
+                artifact_builder
+                    .with_handle_refund_result(&handle_refund_result)
+                    .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+
+                refunded_amount
+            }
+            None => U512::zero(),
+        }
+    };
+
+    // handle fees per the chainspec determined setting.
+    let handle_fee_result = match fee_handling {
+        FeeHandling::NoFee => {
+            // in this mode, a gas hold is placed on the payer's purse.
+            let amount = cost.saturating_sub(refund_amount);
+            let hold_request = BalanceHoldRequest::new_gas_hold(
+                state_root_hash,
+                protocol_version,
+                balance_identifier,
+                amount,
+                insufficient_balance_handling,
+            );
+            let hold_result = scratch_state.balance_hold(hold_request);
+            state_root_hash =
+                scratch_state.commit_effects(state_root_hash, hold_result.effects().clone())?;
+            artifact_builder
+                .with_balance_hold_result(&hold_result)
+                .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+            let handle_fee_request = HandleFeeRequest::new(
+                native_runtime_config.clone(),
+                state_root_hash,
+                protocol_version,
+                transaction_hash,
+                HandleFeeMode::credit(Box::new(proposer.clone()), amount, era_id),
+            );
+            scratch_state.handle_fee(handle_fee_request)
+        }
+        FeeHandling::Burn => {
+            // in this mode, the fee portion is burned.
+            let amount = cost.saturating_sub(refund_amount);
+            let handle_fee_request = HandleFeeRequest::new(
+                native_runtime_config.clone(),
+                state_root_hash,
+                protocol_version,
+                transaction_hash,
+                HandleFeeMode::burn(balance_identifier, Some(amount)),
+            );
+            scratch_state.handle_fee(handle_fee_request)
+        }
+        FeeHandling::PayToProposer => {
+            // in this mode, the consumed gas is paid as a fee to the block proposer
+            let amount = cost.saturating_sub(refund_amount);
+            let handle_fee_request = HandleFeeRequest::new(
+                native_runtime_config.clone(),
+                state_root_hash,
+                protocol_version,
+                transaction_hash,
+                HandleFeeMode::pay(
+                    Box::new(initiator_addr),
+                    balance_identifier,
+                    BalanceIdentifier::Public(proposer.clone()),
+                    amount,
+                ),
+            );
+            scratch_state.handle_fee(handle_fee_request)
+        }
+        FeeHandling::Accumulate => {
+            // in this mode, consumed gas is accumulated into a single purse
+            // for later distribution
+            let amount = cost.saturating_sub(refund_amount);
+            let handle_fee_request = HandleFeeRequest::new(
+                native_runtime_config.clone(),
+                state_root_hash,
+                protocol_version,
+                transaction_hash,
+                HandleFeeMode::pay(
+                    Box::new(initiator_addr),
+                    balance_identifier,
+                    BalanceIdentifier::Accumulate,
+                    amount,
+                ),
+            );
+            scratch_state.handle_fee(handle_fee_request)
+        }
+    };
+
+    state_root_hash =
+        scratch_state.commit_effects(state_root_hash, handle_fee_result.effects().clone())?;
+
+    artifact_builder
+        .with_handle_fee_result(&handle_fee_result)
+        .map_err(|_| BlockExecutionError::RootNotFound(state_root_hash))?;
+
+    // clear refund purse if it was set
+    if refund_purse_active {
+        // if refunds are turned on we initialize the refund purse to the initiator's main
+        // purse before doing any processing. NOTE: when executed, custom payment logic
+        // has the option to call set_refund_purse on the handle payment contract to set
+        // up a different refund purse, if desired.
+        let handle_refund_request = HandleRefundRequest::new(
+            native_runtime_config.clone(),
+            state_root_hash,
+            protocol_version,
+            transaction_hash,
+            HandleRefundMode::ClearRefundPurse,
+        );
+        let handle_refund_result = scratch_state.handle_refund(handle_refund_request);
+        if let Err(root_not_found) =
+            artifact_builder.with_clear_refund_purse_result(&handle_refund_result)
+        {
+            if root_not_found {
+                return Err(BlockExecutionError::RootNotFound(state_root_hash));
+            }
+            warn!(
+                "{}",
+                artifact_builder.error_message().unwrap_or(
+                    "unknown error encountered when attempting to clear refund purse".to_string()
+                )
+            );
+        }
+        state_root_hash = scratch_state
+            .commit_effects(state_root_hash, handle_refund_result.effects().clone())?;
+    }
+
+    Ok((state_root_hash, artifact_builder.build()))
+}
+
+/// Execute the transaction without committing the effects.
+/// Intended to be used for discovery operations on read-only nodes.
+///
+/// The transaction is executed against the given state root hash, in the context of the block
+/// following the given block header.  If `trace_execution` is set, the call tree, the host
+/// function calls and the reads and writes of the execution are recorded in the result.
+///
+/// Returns effects of the execution.
+pub(super) fn speculatively_execute<S>(
+    state_provider: &S,
+    chainspec: &Chainspec,
+    execution_engine_v1: &ExecutionEngineV1,
+    block_header: BlockHeader,
+    state_root_hash: Digest,
+    input_transaction: Transaction,
+    trace_execution: bool,
+) -> SpeculativeExecutionResult
+where
+    S: StateProvider,
+{
+    let transaction_config = &chainspec.transaction_config;
+    let maybe_transaction =
+        MetaTransaction::from_transaction(&input_transaction, transaction_config);
+    if let Err(error) = maybe_transaction {
+        return SpeculativeExecutionResult::invalid_transaction(error);
+    }
+    let transaction = maybe_transaction.unwrap();
+    let parent_block_hash = block_header.block_hash();
+    let block_height = block_header.height();
+    let block_time = block_header
+        .timestamp()
+        .saturating_add(chainspec.core_config.minimum_block_time);
+    let gas_limit = match input_transaction.gas_limit(chainspec, transaction.transaction_lane()) {
+        Ok(gas_limit) => gas_limit,
+        Err(_) => {
+            return SpeculativeExecutionResult::invalid_gas_limit(input_transaction);
+        }
+    };
 
     if transaction.is_deploy_transaction() {
         if transaction.is_native() {
@@ -1326,7 +1607,7 @@ where
 
             let result = state_provider.transfer(TransferRequest::with_runtime_args(
                 native_runtime_config.clone(),
-                *state_root_hash,
+                state_root_hash,
                 protocol_version,
                 transaction_hash,
                 initiator_addr,
@@ -1340,7 +1621,7 @@ where
             )))
         } else {
             let block_info = BlockInfo::new(
-                *state_root_hash,
+                state_root_hash,
                 block_time.into(),
                 parent_block_hash,
                 block_height,
@@ -1349,7 +1630,8 @@ where
             let session_input_data = transaction.to_session_input_data();
             let wasm_v1_result =
                 match WasmV1Request::new_session(block_info, gas_limit, &session_input_data) {
-                    Ok(wasm_v1_request) => {
+                    Ok(mut wasm_v1_request) => {
+                        wasm_v1_request.trace_execution = trace_execution;
                         execution_engine_v1.execute(state_provider, wasm_v1_request)
                     }
                     Err(error) => WasmV1Result::invalid_executable_item(gas_limit, error),
@@ -1364,6 +1646,282 @@ where
     }
 }
 
+/// Estimates the gas consumed by a transaction and the payment it needs, executing it
+/// speculatively on the given scratch global state.
+///
+/// A transaction paying a limited amount is executed with the maximum gas limit of its lane, or
+/// with the cost of its operation in the cost table if it's native, and the recommended payment
+/// amount is the consumed gas increased by the given margin.  A transaction with a fixed price is
+/// executed with the gas limit the cost table sets for its lane, which is recommended along with
+/// its cost at the given gas price.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn estimate_gas(
+    scratch_state: &ScratchGlobalState,
+    chainspec: &Chainspec,
+    execution_engine_v1: &ExecutionEngineV1,
+    execution_engine_v2: &ExecutorV2,
+    block_header: BlockHeader,
+    state_root_hash: Digest,
+    input_transaction: Transaction,
+    gas_price: u8,
+    payment_margin_percent: u8,
+) -> GasEstimationResult {
+    let transaction_config = &chainspec.transaction_config;
+    let transaction =
+        match MetaTransaction::from_transaction(&input_transaction, transaction_config) {
+            Ok(transaction) => transaction,
+            Err(error) => return GasEstimationResult::InvalidTransaction(error),
+        };
+    let lane_id = transaction.transaction_lane();
+    let is_payment_limited = match &input_transaction {
+        Transaction::Deploy(_) => matches!(
+            chainspec.core_config.pricing_handling,
+            PricingHandling::PaymentLimited
+        ),
+        Transaction::V1(transaction_v1) => matches!(
+            transaction_v1.pricing_mode(),
+            PricingMode::PaymentLimited { .. }
+        ),
+    };
+    let maybe_gas_limit = if !is_payment_limited {
+        input_transaction.gas_limit(chainspec, lane_id).ok()
+    } else if transaction.is_native() {
+        // Native transactions consume their whole gas limit, so they're limited to the cost of
+        // their operation, as they would be with a fixed price.
+        PricingMode::Fixed {
+            gas_price_tolerance: 0,
+            additional_computation_factor: 0,
+        }
+        .gas_limit(chainspec, &transaction.entry_point(), lane_id)
+        .ok()
+    } else {
+        // The payment amount is what's being estimated, so the transaction is given as much gas
+        // as its lane allows.
+        Some(Gas::new(chainspec.get_max_gas_limit_by_category(lane_id)))
+    };
+    let Some(gas_limit) = maybe_gas_limit else {
+        return GasEstimationResult::invalid_gas_limit(input_transaction);
+    };
+
+    let block_hash = block_header.block_hash();
+    let protocol_version = chainspec.protocol_version();
+    let spec_exec_result = if lane_id == MINT_LANE_ID || lane_id == AUCTION_LANE_ID {
+        let runtime_args = match transaction.session_args().as_named() {
+            Some(runtime_args) => runtime_args.clone(),
+            None => {
+                return GasEstimationResult::InvalidTransaction(InvalidTransaction::V1(
+                    InvalidTransactionV1::ExpectedNamedArguments,
+                ));
+            }
+        };
+        let native_runtime_config = NativeRuntimeConfig::from_chainspec(chainspec);
+        if lane_id == MINT_LANE_ID {
+            let result = scratch_state.transfer(TransferRequest::with_runtime_args(
+                native_runtime_config,
+                state_root_hash,
+                protocol_version,
+                transaction.hash(),
+                transaction.initiator_addr(),
+                transaction.authorization_keys(),
+                runtime_args,
+            ));
+            utils::spec_exec_from_transfer_result(gas_limit, result, block_hash)
+        } else {
+            match AuctionMethod::from_parts(transaction.entry_point(), &runtime_args, chainspec) {
+                Ok(auction_method) => {
+                    let result = scratch_state.bidding(BiddingRequest::new(
+                        native_runtime_config,
+                        state_root_hash,
+                        protocol_version,
+                        transaction.hash(),
+                        transaction.initiator_addr(),
+                        transaction.authorization_keys(),
+                        auction_method,
+                    ));
+                    utils::spec_exec_from_bidding_result(gas_limit, result, block_hash)
+                }
+                Err(error) => {
+                    utils::spec_exec_from_error(gas_limit, format!("{:?}", error), block_hash)
+                }
+            }
+        }
+    } else if transaction.is_v1_wasm() {
+        let block_time = block_header
+            .timestamp()
+            .saturating_add(chainspec.core_config.minimum_block_time);
+        let block_info = BlockInfo::new(
+            state_root_hash,
+            block_time.into(),
+            block_hash,
+            block_header.height(),
+            execution_engine_v1.config().protocol_version(),
+        );
+        let session_input_data = transaction.to_session_input_data();
+        let wasm_v1_result =
+            match WasmV1Request::new_session(block_info, gas_limit, &session_input_data) {
+                Ok(wasm_v1_request) => execution_engine_v1.execute(scratch_state, wasm_v1_request),
+                Err(error) => WasmV1Result::invalid_executable_item(gas_limit, error),
+            };
+        utils::spec_exec_from_wasm_v1_result(wasm_v1_result, block_hash)
+    } else {
+        match WasmV2Request::new(
+            gas_limit,
+            chainspec.network_config.name.clone(),
+            state_root_hash,
+            block_hash,
+            block_header.height(),
+            &transaction,
+        ) {
+            Ok(wasm_v2_request) => {
+                let result =
+                    wasm_v2_request.execute(execution_engine_v2, state_root_hash, scratch_state);
+                utils::spec_exec_from_wasm_v2_result(gas_limit, result, block_hash)
+            }
+            Err(error) => utils::spec_exec_from_error(gas_limit, error.to_string(), block_hash),
+        }
+    };
+
+    let recommendation = if is_payment_limited {
+        let margin = U512::from(100 + u64::from(payment_margin_percent));
+        let consumed_with_margin = (spec_exec_result.consumed().value() * margin + 99) / 100;
+        // The transaction can't be given more gas than it was executed with.
+        let payment_amount = consumed_with_margin.min(gas_limit.value()).as_u64();
+        PaymentRecommendation::PaymentLimited { payment_amount }
+    } else {
+        let Some(cost) = Motes::from_gas(gas_limit, gas_price) else {
+            return GasEstimationResult::invalid_gas_limit(input_transaction);
+        };
+        PaymentRecommendation::Fixed {
+            gas_limit,
+            gas_price,
+            cost,
+        }
+    };
+    GasEstimationResult::Estimated(Box::new(GasEstimate::new(
+        block_hash,
+        lane_id,
+        spec_exec_result.limit(),
+        spec_exec_result.consumed(),
+        spec_exec_result.error().map(str::to_string),
+        recommendation,
+    )))
+}
+
+/// Applies the state overrides of a speculative execution to the scratch global state.
+///
+/// The overrides are only written to the cache of the scratch global state, so they're discarded
+/// along with it and never reach the global state.
+pub(super) fn apply_state_overrides(
+    scratch_state: &ScratchGlobalState,
+    state_root_hash: Digest,
+    state_overrides: Vec<StateOverride>,
+) -> Result<(), StateResultError> {
+    let mut tracking_copy = match scratch_state.tracking_copy(state_root_hash) {
+        Ok(Some(tracking_copy)) => tracking_copy,
+        Ok(None) => return Err(StateResultError::RootNotFound),
+        Err(error) => return Err(StateResultError::Failure(TrackingCopyError::Storage(error))),
+    };
+    for state_override in state_overrides {
+        apply_state_override(&mut tracking_copy, state_override)
+            .map_err(StateResultError::Failure)?;
+    }
+    scratch_state
+        .commit_effects(state_root_hash, tracking_copy.effects())
+        .map_err(|error| StateResultError::Failure(TrackingCopyError::Storage(error)))?;
+    Ok(())
+}
+
+fn apply_state_override<R>(
+    tracking_copy: &mut TrackingCopy<R>,
+    state_override: StateOverride,
+) -> Result<(), TrackingCopyError>
+where
+    R: StateReader<Key, StoredValue, Error = GlobalStateError>,
+{
+    match state_override {
+        StateOverride::Balance { purse, balance } => {
+            let cl_value = CLValue::from_t(balance).map_err(TrackingCopyError::CLValue)?;
+            tracking_copy.write(Key::Balance(purse.addr()), StoredValue::CLValue(cl_value));
+        }
+        StateOverride::NamedKey {
+            entity_addr,
+            name,
+            key,
+        } if tracking_copy.enable_addressable_entity() => {
+            let named_key_addr = NamedKeyAddr::new_from_string(entity_addr, name.clone())
+                .map_err(TrackingCopyError::BytesRepr)?;
+            let named_key_value = NamedKeyValue::from_concrete_values(key, name)
+                .map_err(TrackingCopyError::CLValue)?;
+            tracking_copy.write(
+                Key::NamedKey(named_key_addr),
+                StoredValue::NamedKey(named_key_value),
+            );
+        }
+        StateOverride::NamedKey {
+            entity_addr,
+            name,
+            key,
+        } => {
+            let mut named_keys = NamedKeys::new();
+            named_keys.insert(name, key);
+            // Without addressable entities, named keys are stored within accounts and contracts.
+            let entity_key = match entity_addr {
+                EntityAddr::Account(account_hash) => Key::Account(AccountHash::new(account_hash)),
+                EntityAddr::System(hash_addr) | EntityAddr::SmartContract(hash_addr) => {
+                    Key::Hash(hash_addr)
+                }
+            };
+            match tracking_copy.read(&entity_key)? {
+                Some(StoredValue::Account(mut account)) => {
+                    account.named_keys_mut().append(named_keys);
+                    tracking_copy.write(entity_key, StoredValue::Account(account));
+                }
+                Some(StoredValue::Contract(mut contract)) => {
+                    contract.named_keys_append(named_keys);
+                    tracking_copy.write(entity_key, StoredValue::Contract(contract));
+                }
+                Some(other) => {
+                    return Err(TrackingCopyError::TypeMismatch(
+                        StoredValueTypeMismatch::new(
+                            "Account or Contract".to_string(),
+                            other.type_name(),
+                        ),
+                    ));
+                }
+                None => return Err(TrackingCopyError::KeyNotFound(entity_key)),
+            }
+        }
+        StateOverride::ByteCode {
+            entity_addr,
+            byte_code,
+        } if tracking_copy.enable_addressable_entity() => {
+            let entity_key = Key::AddressableEntity(entity_addr);
+            let Some(StoredValue::AddressableEntity(entity)) = tracking_copy.read(&entity_key)?
+            else {
+                return Err(TrackingCopyError::ContractNotFound(entity_key));
+            };
+            tracking_copy.write(
+                Key::ByteCode(ByteCodeAddr::V1CasperWasm(entity.byte_code_hash().value())),
+                StoredValue::ByteCode(ByteCode::new(ByteCodeKind::V1CasperWasm, byte_code.into())),
+            );
+        }
+        StateOverride::ByteCode {
+            entity_addr,
+            byte_code,
+        } => {
+            let contract_key = Key::Hash(entity_addr.value());
+            let Some(StoredValue::Contract(contract)) = tracking_copy.read(&contract_key)? else {
+                return Err(TrackingCopyError::ContractNotFound(contract_key));
+            };
+            tracking_copy.write(
+                Key::Hash(contract.contract_wasm_hash().value()),
+                StoredValue::ContractWasm(ContractWasm::new(byte_code.into())),
+            );
+        }
+    }
+    Ok(())
+}
+
 fn invoked_contract_will_pay(
     state_provider: &ScratchGlobalState,
     state_root_hash: Digest,
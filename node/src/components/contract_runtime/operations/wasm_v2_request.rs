@@ -20,8 +20,9 @@ use casper_storage::{
     AddressGeneratorBuilder,
 };
 use casper_types::{
-    execution::Effects, BlockHash, Digest, Gas, Key, TransactionEntryPoint,
-    TransactionInvocationTarget, TransactionRuntimeParams, TransactionTarget, U512,
+    contract_messages::Messages, execution::Effects, BlockHash, Digest, Gas, Key,
+    TransactionEntryPoint, TransactionInvocationTarget, TransactionRuntimeParams,
+    TransactionTarget, U512,
 };
 use thiserror::Error;
 use tracing::info;
@@ -69,6 +70,14 @@ impl WasmV2Result {
         }
     }
 
+    /// Returns the messages emitted by the contract execution.
+    pub(crate) fn messages(&self) -> &Messages {
+        match self {
+            WasmV2Result::Install(result) => result.messages(),
+            WasmV2Result::Execute(result) => result.messages(),
+        }
+    }
+
     pub(crate) fn smart_contract_addr(&self) -> Option<&[u8; 32]> {
         match self {
             WasmV2Result::Install(result) => Some(result.smart_contract_addr()),
@@ -0,0 +1,132 @@
+//! Online garbage collection of the trie store.
+
+use std::{sync::Arc, time::Instant};
+
+use tokio::sync::OwnedMutexGuard;
+use tracing::{error, info};
+
+use casper_storage::global_state::{
+    error::Error as GlobalStateError,
+    gc::{GcPhase, GcProgress, TrieGarbageCollector},
+    state::lmdb::LmdbGlobalState,
+};
+use casper_types::BlockV2;
+
+use crate::{
+    contract_runtime::{metrics::Metrics, utils::run_intensive_task, Config},
+    effect::{requests::StorageRequest, EffectBuilder},
+};
+
+/// Garbage collection of the tries which aren't reachable from the state roots of the most recent
+/// blocks.
+///
+/// A collection is started at every switch block, and progresses by a bounded step after every
+/// executed block.  It is shared behind an asynchronous lock, which is held from the execution of
+/// a block until the step following it is done, so that steps and executions alternate.
+pub(super) struct TrieGc {
+    collector: TrieGarbageCollector,
+    retained_state_roots: u64,
+    batch_size: usize,
+}
+
+impl TrieGc {
+    /// Returns a new `TrieGc` if garbage collection is enabled in the config.
+    ///
+    /// If it is disabled, any collection left in progress by a previous run is aborted.  Must be
+    /// called before any tries are written to the global state.
+    pub(super) fn new(
+        global_state: &LmdbGlobalState,
+        config: &Config,
+    ) -> Result<Option<Self>, GlobalStateError> {
+        let collector = TrieGarbageCollector::new(global_state)?;
+        let retained_state_roots = match config.gc_retained_state_roots() {
+            Some(retained_state_roots) => retained_state_roots,
+            None => {
+                if collector.progress()?.phase != GcPhase::Idle {
+                    info!("garbage collection disabled; aborting trie store garbage collection");
+                    collector.abort()?;
+                }
+                return Ok(None);
+            }
+        };
+        Ok(Some(TrieGc {
+            collector,
+            retained_state_roots,
+            batch_size: config.gc_batch_size_or_default(),
+        }))
+    }
+}
+
+/// Starts a garbage collection if `block` is a switch block and none is in progress, then
+/// performs a step of the current collection, if any.
+///
+/// Must be called with the lock held since the execution of `block`, which the execution of its
+/// child waits for, since the state of blocks executed concurrently with the start of a collection
+/// could be swept.
+pub(super) async fn collect_garbage<REv>(
+    effect_builder: EffectBuilder<REv>,
+    trie_gc: OwnedMutexGuard<TrieGc>,
+    metrics: Arc<Metrics>,
+    block: Arc<BlockV2>,
+) where
+    REv: From<StorageRequest>,
+{
+    let progress = match trie_gc.collector.progress() {
+        Ok(progress) => progress,
+        Err(error) => {
+            error!(%error, "failed to read trie store garbage collection progress");
+            return;
+        }
+    };
+
+    if progress.phase == GcPhase::Idle {
+        if !block.is_switch_block() {
+            return;
+        }
+        // The executed block isn't stored yet, so its state root is taken from the block itself.
+        let mut retained_state_roots = vec![*block.state_root_hash()];
+        let lowest_height = block
+            .height()
+            .saturating_sub(trie_gc.retained_state_roots.saturating_sub(1));
+        for height in lowest_height..block.height() {
+            if let Some(header) = effect_builder
+                .get_block_header_at_height_from_storage(height, false)
+                .await
+            {
+                retained_state_roots.push(*header.state_root_hash());
+            }
+        }
+        if let Err(error) = trie_gc.collector.start(&retained_state_roots) {
+            error!(%error, "failed to start trie store garbage collection");
+            return;
+        }
+    }
+
+    let start = Instant::now();
+    let result = run_intensive_task(move || trie_gc.collector.step(trie_gc.batch_size)).await;
+    metrics
+        .trie_gc_step_time
+        .observe(start.elapsed().as_secs_f64());
+    match result {
+        Ok(progress) => update_metrics(&metrics, progress),
+        Err(error) => error!(%error, "failed to perform trie store garbage collection step"),
+    }
+}
+
+fn update_metrics(metrics: &Metrics, progress: GcProgress) {
+    let phase = match progress.phase {
+        GcPhase::Idle => 0,
+        GcPhase::Mark => 1,
+        GcPhase::Sweep => 2,
+    };
+    metrics.trie_gc_phase.set(phase);
+    metrics
+        .trie_gc_tries_marked
+        .set(progress.tries_marked.try_into().unwrap_or(i64::MAX));
+    metrics
+        .trie_gc_tries_swept
+        .set(progress.tries_swept.try_into().unwrap_or(i64::MAX));
+    metrics
+        .trie_gc_tries_deleted
+        .set(progress.tries_deleted.try_into().unwrap_or(i64::MAX));
+}
@@ -382,7 +382,8 @@ impl ExecutionArtifactBuilder {
 
         // TODO: Use system message to notify about contract hash
 
-        self.with_appended_effects(result.effects().clone());
+        self.with_appended_effects(result.effects().clone())
+            .with_appended_messages(&mut result.messages().clone());
 
         self
     }
@@ -449,6 +450,7 @@ pub enum SpeculativeExecutionResult {
     InvalidTransaction(InvalidTransaction),
     WasmV1(Box<casper_binary_port::SpeculativeExecutionResult>),
     ReceivedV1Transaction,
+    InvalidStateOverride,
 }
 
 impl SpeculativeExecutionResult {
@@ -468,6 +470,27 @@ impl SpeculativeExecutionResult {
     }
 }
 
+/// Type representing results of the gas estimation.
+#[derive(Debug)]
+pub enum GasEstimationResult {
+    InvalidTransaction(InvalidTransaction),
+    Estimated(Box<casper_binary_port::GasEstimate>),
+    InvalidStateOverride,
+}
+
+impl GasEstimationResult {
+    pub fn invalid_gas_limit(transaction: Transaction) -> Self {
+        match transaction {
+            Transaction::Deploy(_) => GasEstimationResult::InvalidTransaction(
+                InvalidTransaction::Deploy(InvalidDeploy::UnableToCalculateGasLimit),
+            ),
+            Transaction::V1(_) => GasEstimationResult::InvalidTransaction(InvalidTransaction::V1(
+                InvalidTransactionV1::UnableToCalculateGasLimit,
+            )),
+        }
+    }
+}
+
 /// State to use to construct the next block in the blockchain. Includes the state root hash for the
 /// execution engine as well as certain values the next header will be based on.
 #[derive(DataSize, Default, Debug, Clone, Serialize)]
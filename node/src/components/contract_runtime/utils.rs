@@ -9,6 +9,7 @@ use std::{
     sync::{Arc, Mutex},
     time::Instant,
 };
+use tokio::sync::Mutex as AsyncMutex;
 use tracing::{debug, error, info, warn};
 
 use crate::{
@@ -16,7 +17,10 @@ use crate::{
         exec_queue::{ExecQueue, QueueItem},
         execute_finalized_block,
         metrics::Metrics,
-        rewards, BlockAndExecutionArtifacts, BlockExecutionError, ExecutionPreState, StepOutcome,
+        operations::wasm_v2_request::{WasmV2Error, WasmV2Result},
+        rewards,
+        trie_gc::{self, TrieGc},
+        BlockAndExecutionArtifacts, BlockExecutionError, ExecutionPreState, StepOutcome,
     },
     effect::{
         announcements::{ContractRuntimeAnnouncement, FatalAnnouncement, MetaBlockAnnouncement},
@@ -31,12 +35,15 @@ use casper_binary_port::SpeculativeExecutionResult;
 use casper_execution_engine::engine_state::{ExecutionEngineV1, WasmV1Result};
 use casper_storage::{
     data_access_layer::{
-        DataAccessLayer, FlushRequest, FlushResult, ProtocolUpgradeRequest, ProtocolUpgradeResult,
-        TransferResult,
+        BiddingResult, DataAccessLayer, FlushRequest, FlushResult, ProtocolUpgradeRequest,
+        ProtocolUpgradeResult, TransferResult,
     },
     global_state::state::{lmdb::LmdbGlobalState, CommitProvider, StateProvider},
 };
-use casper_types::{BlockHash, Chainspec, Digest, EraId, Gas, Key, ProtocolUpgradeConfig};
+use casper_types::{
+    contract_messages::Messages, execution::Effects, BlockHash, Chainspec, Digest, EraId, Gas, Key,
+    ProtocolUpgradeConfig,
+};
 
 /// Maximum number of resource intensive tasks that can be run in parallel.
 ///
@@ -75,6 +82,7 @@ pub(super) async fn exec_or_requeue<REv>(
     execution_engine_v2: ExecutorV2,
     chainspec: Arc<Chainspec>,
     metrics: Arc<Metrics>,
+    maybe_trie_gc: Option<Arc<AsyncMutex<TrieGc>>>,
     mut exec_queue: ExecQueue,
     shared_pre_state: Arc<Mutex<ExecutionPreState>>,
     current_pre_state: ExecutionPreState,
@@ -82,6 +90,7 @@ pub(super) async fn exec_or_requeue<REv>(
     mut executable_block: ExecutableBlock,
     key_block_height_for_activation_point: u64,
     mut meta_block_state: MetaBlockState,
+    execution_threads: usize,
 ) where
     REv: From<ContractRuntimeRequest>
         + From<ContractRuntimeAnnouncement>
@@ -263,6 +272,9 @@ pub(super) async fn exec_or_requeue<REv>(
         None
     };
 
+    // Kept to report the statistics of their caches once the block is executed.
+    let trie_store = data_access_layer.state().trie_store().clone();
+    let cached_execution_engine_v2 = execution_engine_v2.clone();
     let task = move || {
         debug!("ContractRuntime: execute_finalized_block");
         execute_finalized_block(
@@ -277,8 +289,16 @@ pub(super) async fn exec_or_requeue<REv>(
             current_gas_price,
             maybe_next_era_gas_price,
             last_switch_block_hash,
+            execution_threads,
+            true,
         )
     };
+    // Waits for the garbage collection step following the parent block, and holds off the one
+    // following this block until it is announced.
+    let maybe_trie_gc = match maybe_trie_gc {
+        Some(trie_gc) => Some(trie_gc.lock_owned().await),
+        None => None,
+    };
     let BlockAndExecutionArtifacts {
         block,
         approvals_hashes,
@@ -292,6 +312,9 @@ pub(super) async fn exec_or_requeue<REv>(
         }
     };
 
+    metrics.update_trie_node_cache(trie_store.node_cache_stats());
+    metrics.update_wasm_module_cache(cached_execution_engine_v2.module_cache_stats());
+
     let new_execution_pre_state = ExecutionPreState::from_block_header(block.header());
     {
         // The `shared_pre_state` could have been set to a block we just fully synced after
@@ -387,9 +410,20 @@ pub(super) async fn exec_or_requeue<REv>(
         );
     }
 
+    let executed_block = Arc::clone(&block);
     let meta_block = MetaBlock::new_forward(block, execution_artifacts, meta_block_state);
     effect_builder.announce_meta_block(meta_block).await;
 
+    // The garbage collection step runs on its own, and the execution of the child waits for it.
+    if let Some(trie_gc) = maybe_trie_gc {
+        tokio::spawn(trie_gc::collect_garbage(
+            effect_builder,
+            trie_gc,
+            Arc::clone(&metrics),
+            executed_block,
+        ));
+    }
+
     // If the child is already finalized, start execution.
     let next_block = exec_queue.remove(new_execution_pre_state.next_block_height());
 
@@ -551,6 +585,70 @@ pub(crate) fn spec_exec_from_transfer_result(
     )
 }
 
+pub(crate) fn spec_exec_from_bidding_result(
+    limit: Gas,
+    bidding_result: BiddingResult,
+    block_hash: BlockHash,
+) -> SpeculativeExecutionResult {
+    let consumed = limit;
+    let effects = bidding_result.effects();
+    let error_msg = match bidding_result {
+        BiddingResult::Success { .. } => None,
+        BiddingResult::RootNotFound => Some("RootNotFound".to_string()),
+        BiddingResult::Failure(err) => Some(format!("{:?}", err)),
+    };
+
+    SpeculativeExecutionResult::new(
+        block_hash,
+        vec![],
+        limit,
+        consumed,
+        effects,
+        Messages::new(),
+        error_msg,
+    )
+}
+
+pub(crate) fn spec_exec_from_wasm_v2_result(
+    limit: Gas,
+    wasm_v2_result: Result<WasmV2Result, WasmV2Error>,
+    block_hash: BlockHash,
+) -> SpeculativeExecutionResult {
+    match wasm_v2_result {
+        Ok(wasm_v2_result) => {
+            let consumed = Gas::from(wasm_v2_result.gas_usage().gas_spent());
+            let effects = wasm_v2_result.effects().to_owned();
+            let messages = wasm_v2_result.messages().to_owned();
+            SpeculativeExecutionResult::new(
+                block_hash,
+                vec![],
+                limit,
+                consumed,
+                effects,
+                messages,
+                None,
+            )
+        }
+        Err(err) => spec_exec_from_error(limit, err.to_string(), block_hash),
+    }
+}
+
+pub(crate) fn spec_exec_from_error(
+    limit: Gas,
+    error_msg: String,
+    block_hash: BlockHash,
+) -> SpeculativeExecutionResult {
+    SpeculativeExecutionResult::new(
+        block_hash,
+        vec![],
+        limit,
+        Gas::zero(),
+        Effects::new(),
+        Messages::new(),
+        Some(error_msg),
+    )
+}
+
 pub(crate) fn spec_exec_from_wasm_v1_result(
     wasm_v1_result: WasmV1Result,
     block_hash: BlockHash,
@@ -564,10 +662,12 @@ pub(crate) fn spec_exec_from_wasm_v1_result(
         .error()
         .to_owned()
         .map(|err| format!("{:?}", err));
+    let execution_trace = wasm_v1_result.execution_trace().cloned();
 
     SpeculativeExecutionResult::new(
         block_hash, transfers, limit, consumed, effects, messages, error_msg,
     )
+    .with_execution_trace(execution_trace)
 }
 
 #[cfg(test)]
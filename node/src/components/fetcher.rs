@@ -31,7 +31,7 @@ use crate::{
         EffectBuilder, EffectExt, Effects, Responder,
     },
     protocol::Message,
-    types::NodeId,
+    types::{NodeId, PeerReputation},
     utils::Source,
     NodeRng,
 };
@@ -63,6 +63,8 @@ where
     name: &'static str,
     #[data_size(skip)]
     metrics: Metrics,
+    #[data_size(skip)]
+    peer_reputation: PeerReputation,
 }
 
 impl<T: FetchItem> Fetcher<T> {
@@ -70,12 +72,14 @@ impl<T: FetchItem> Fetcher<T> {
         name: &'static str,
         config: &Config,
         registry: &Registry,
+        peer_reputation: PeerReputation,
     ) -> Result<Self, prometheus::Error> {
         Ok(Fetcher {
             get_from_peer_timeout: config.get_from_peer_timeout().into(),
             item_handles: HashMap::new(),
             name,
             metrics: Metrics::new(name, registry)?,
+            peer_reputation,
         })
     }
 }
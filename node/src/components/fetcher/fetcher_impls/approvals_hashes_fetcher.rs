@@ -11,7 +11,7 @@ use crate::{
         metrics::Metrics, FetchItem, Fetcher, ItemFetcher, ItemHandle, StoringState, Tag,
     },
     effect::{requests::StorageRequest, EffectBuilder},
-    types::NodeId,
+    types::{NodeId, PeerReputation},
 };
 
 impl FetchItem for ApprovalsHashes {
@@ -48,6 +48,10 @@ impl ItemFetcher<ApprovalsHashes> for Fetcher<ApprovalsHashes> {
         self.get_from_peer_timeout
     }
 
+    fn peer_reputation(&self) -> &PeerReputation {
+        &self.peer_reputation
+    }
+
     async fn get_locally<REv: From<StorageRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: BlockHash,
@@ -5,7 +5,7 @@ use async_trait::async_trait;
 use crate::{
     components::fetcher::{metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, StoringState},
     effect::{requests::StorageRequest, EffectBuilder},
-    types::{BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, NodeId},
+    types::{BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, NodeId, PeerReputation},
 };
 
 #[async_trait]
@@ -29,6 +29,10 @@ impl ItemFetcher<BlockExecutionResultsOrChunk> for Fetcher<BlockExecutionResults
         self.get_from_peer_timeout
     }
 
+    fn peer_reputation(&self) -> &PeerReputation {
+        &self.peer_reputation
+    }
+
     async fn get_locally<REv: From<StorageRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: BlockExecutionResultsOrChunkId,
@@ -11,7 +11,7 @@ use crate::{
         StoringState, Tag,
     },
     effect::{requests::StorageRequest, EffectBuilder},
-    types::NodeId,
+    types::{NodeId, PeerReputation},
 };
 
 impl FetchItem for BlockHeader {
@@ -50,6 +50,10 @@ impl ItemFetcher<BlockHeader> for Fetcher<BlockHeader> {
         self.get_from_peer_timeout
     }
 
+    fn peer_reputation(&self) -> &PeerReputation {
+        &self.peer_reputation
+    }
+
     async fn get_locally<REv: From<StorageRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: BlockHash,
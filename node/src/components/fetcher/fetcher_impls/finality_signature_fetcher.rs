@@ -15,7 +15,7 @@ use crate::{
         requests::{BlockAccumulatorRequest, StorageRequest},
         EffectBuilder,
     },
-    types::NodeId,
+    types::{NodeId, PeerReputation},
 };
 
 impl FetchItem for FinalitySignature {
@@ -57,6 +57,10 @@ impl ItemFetcher<FinalitySignature> for Fetcher<FinalitySignature> {
         self.get_from_peer_timeout
     }
 
+    fn peer_reputation(&self) -> &PeerReputation {
+        &self.peer_reputation
+    }
+
     async fn get_locally<REv: From<StorageRequest> + From<BlockAccumulatorRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: Box<FinalitySignatureId>,
@@ -8,7 +8,7 @@ use casper_types::{Deploy, DeployHash, Transaction};
 use crate::{
     components::fetcher::{metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, StoringState},
     effect::{requests::StorageRequest, EffectBuilder},
-    types::{LegacyDeploy, NodeId},
+    types::{LegacyDeploy, NodeId, PeerReputation},
 };
 
 #[async_trait]
@@ -29,6 +29,10 @@ impl ItemFetcher<LegacyDeploy> for Fetcher<LegacyDeploy> {
         self.get_from_peer_timeout
     }
 
+    fn peer_reputation(&self) -> &PeerReputation {
+        &self.peer_reputation
+    }
+
     async fn get_locally<REv: From<StorageRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: DeployHash,
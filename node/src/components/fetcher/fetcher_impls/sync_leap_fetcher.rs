@@ -6,7 +6,7 @@ use futures::FutureExt;
 use crate::{
     components::fetcher::{metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, StoringState},
     effect::{requests::StorageRequest, EffectBuilder},
-    types::{NodeId, SyncLeap, SyncLeapIdentifier},
+    types::{NodeId, PeerReputation, SyncLeap, SyncLeapIdentifier},
 };
 
 #[async_trait]
@@ -30,6 +30,10 @@ impl ItemFetcher<SyncLeap> for Fetcher<SyncLeap> {
         self.get_from_peer_timeout
     }
 
+    fn peer_reputation(&self) -> &PeerReputation {
+        &self.peer_reputation
+    }
+
     async fn get_locally<REv: Send>(
         _effect_builder: EffectBuilder<REv>,
         _id: SyncLeapIdentifier,
@@ -11,7 +11,7 @@ use crate::{
         StoringState, Tag,
     },
     effect::{requests::StorageRequest, EffectBuilder},
-    types::NodeId,
+    types::{NodeId, PeerReputation},
 };
 
 impl FetchItem for Transaction {
@@ -48,6 +48,10 @@ impl ItemFetcher<Transaction> for Fetcher<Transaction> {
         self.get_from_peer_timeout
     }
 
+    fn peer_reputation(&self) -> &PeerReputation {
+        &self.peer_reputation
+    }
+
     async fn get_locally<REv: From<StorageRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: TransactionId,
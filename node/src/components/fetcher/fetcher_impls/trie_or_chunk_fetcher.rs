@@ -8,7 +8,7 @@ use casper_storage::data_access_layer::{TrieElement, TrieRequest, TrieResult};
 use crate::{
     components::fetcher::{metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, StoringState},
     effect::{requests::ContractRuntimeRequest, EffectBuilder},
-    types::{NodeId, TrieOrChunk, TrieOrChunkId},
+    types::{NodeId, PeerReputation, TrieOrChunk, TrieOrChunkId},
 };
 
 #[async_trait]
@@ -29,6 +29,10 @@ impl ItemFetcher<TrieOrChunk> for Fetcher<TrieOrChunk> {
         self.get_from_peer_timeout
     }
 
+    fn peer_reputation(&self) -> &PeerReputation {
+        &self.peer_reputation
+    }
+
     async fn get_locally<REv: From<ContractRuntimeRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: TrieOrChunkId,
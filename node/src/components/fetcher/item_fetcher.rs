@@ -21,7 +21,7 @@ use crate::{
         EffectBuilder, EffectExt, Effects,
     },
     protocol::Message,
-    types::NodeId,
+    types::{NodeId, PeerReputation},
 };
 
 pub(super) enum StoringState<'a, T> {
@@ -44,6 +44,9 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
 
     fn peer_timeout(&self) -> Duration;
 
+    /// The shared reputation of peers, updated with the outcome of every request.
+    fn peer_reputation(&self) -> &PeerReputation;
+
     /// We've been asked to fetch the item by another component of this node.  We'll try to get it
     /// locally first (generally from our own storage component), and if that fails, we'll send a
     /// request to `peer` for the item.
@@ -111,6 +114,7 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
             }
             Entry::Vacant(entry) => {
                 entry.insert(ItemHandle::new(validation_metadata, responder));
+                self.peer_reputation().request_sent(peer, T::TAG);
             }
         }
         match Message::new_get_request::<T>(&id) {
@@ -148,12 +152,15 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
     {
         self.metrics().found_on_peer.inc();
 
-        let validation_metadata = match self
+        let (validation_metadata, requested_at) = match self
             .item_handles()
             .get(&item.fetch_id())
             .and_then(|item_handles| item_handles.get(&peer))
         {
-            Some(item_handle) => item_handle.validation_metadata(),
+            Some(item_handle) => (
+                item_handle.validation_metadata(),
+                item_handle.requested_at(),
+            ),
             None => {
                 debug!(item_id = %item.fetch_id(), tag = ?T::TAG, %peer, "got unexpected item from peer");
                 return Effects::new();
@@ -162,6 +169,7 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
 
         if let Err(err) = item.validate(validation_metadata) {
             debug!(%peer, %err, ?item, "peer sent invalid item");
+            self.peer_reputation().record_bad_item(peer, T::TAG);
             effect_builder
                 .announce_block_peer_with_justification(
                     peer,
@@ -172,6 +180,9 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
                 )
                 .ignore()
         } else {
+            let size = bincode::serialized_size(&*item).unwrap_or_default() as usize;
+            self.peer_reputation()
+                .record_success(peer, T::TAG, requested_at.elapsed(), size);
             match Self::put_to_storage(effect_builder, *item.clone()) {
                 StoringState::WontStore(item) => self.signal(item.fetch_id(), Ok(item), peer),
                 StoringState::Enqueued(store_future) => {
@@ -185,7 +196,8 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
     fn respond_to_all(&mut self, id: T::Id, fetched_data: FetchedData<T>) -> Effects<Event<T>> {
         let mut effects = Effects::new();
         let item_handles = self.item_handles().remove(&id).unwrap_or_default();
-        for (_peer, item_handle) in item_handles {
+        for (peer, item_handle) in item_handles {
+            self.peer_reputation().request_finished(peer, T::TAG);
             for responder in item_handle.take_responders() {
                 effects.extend(responder.respond(Ok(fetched_data.clone())).ignore());
             }
@@ -205,9 +217,8 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
         match result {
             Ok(item) => {
                 // Since this is a success, we can safely respond to all awaiting processes.
-                for responder in item_handles
-                    .remove(&peer)
-                    .map(ItemHandle::take_responders)
+                for responder in self
+                    .finish_request(&mut item_handles, peer)
                     .unwrap_or_default()
                 {
                     effects.extend(
@@ -230,13 +241,14 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
                             // if the item had been fetched.
                             trace!(TAG=%T::TAG, %id, %peer, "request timed out");
                             self.metrics().timeouts.inc();
+                            self.peer_reputation().record_timeout(peer, T::TAG);
                         }
                         item_handle.has_no_responders()
                     }
                     None => false,
                 };
                 if should_remove_item_handle {
-                    item_handles.remove(&peer);
+                    self.finish_request(&mut item_handles, peer);
                 }
             }
             Err(
@@ -247,9 +259,11 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
             ) => {
                 // For all other error variants we can safely respond with failure as there's no
                 // chance for the request to succeed.
-                for responder in item_handles
-                    .remove(&peer)
-                    .map(ItemHandle::take_responders)
+                if matches!(error, Error::Absent { .. } | Error::Rejected { .. }) {
+                    self.peer_reputation().record_absent(peer, T::TAG);
+                }
+                for responder in self
+                    .finish_request(&mut item_handles, peer)
                     .unwrap_or_default()
                 {
                     effects.extend(responder.respond(Err(error.clone())).ignore());
@@ -262,6 +276,17 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
         effects
     }
 
+    /// Removes the handle of the request to `peer` from `item_handles`, returning its responders.
+    fn finish_request(
+        &self,
+        item_handles: &mut HashMap<NodeId, ItemHandle<T>>,
+        peer: NodeId,
+    ) -> Option<Vec<FetchResponder<T>>> {
+        let item_handle = item_handles.remove(&peer)?;
+        self.peer_reputation().request_finished(peer, T::TAG);
+        Some(item_handle.take_responders())
+    }
+
     fn put_to_storage<'a, REv>(
         _effect_builder: EffectBuilder<REv>,
         _item: T,
@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use datasize::DataSize;
 
 use super::{FetchItem, FetchResponder};
@@ -9,6 +11,7 @@ where
 {
     validation_metadata: Box<T::ValidationMetadata>,
     responders: Vec<FetchResponder<T>>,
+    requested_at: Instant,
 }
 
 impl<T: FetchItem> ItemHandle<T> {
@@ -19,6 +22,7 @@ impl<T: FetchItem> ItemHandle<T> {
         Self {
             validation_metadata,
             responders: vec![responder],
+            requested_at: Instant::now(),
         }
     }
 
@@ -26,6 +30,11 @@ impl<T: FetchItem> ItemHandle<T> {
         &self.validation_metadata
     }
 
+    /// Returns when the item was first requested from the peer.
+    pub(super) fn requested_at(&self) -> Instant {
+        self.requested_at
+    }
+
     pub(super) fn push_responder(&mut self, responder: FetchResponder<T>) {
         self.responders.push(responder)
     }
@@ -43,7 +43,7 @@ use crate::{
         network::{NetworkedReactor, TestingNetwork},
         ConditionCheckReactor, FakeTransactionAcceptor,
     },
-    types::NodeId,
+    types::{NodeId, PeerReputation},
     utils::WithDir,
 };
 
@@ -300,8 +300,13 @@ impl ReactorTrait for Reactor {
         .unwrap();
 
         let fake_transaction_acceptor = FakeTransactionAcceptor::new();
-        let transaction_fetcher =
-            Fetcher::<Transaction>::new("transaction", &cfg.fetcher_config, registry).unwrap();
+        let transaction_fetcher = Fetcher::<Transaction>::new(
+            "transaction",
+            &cfg.fetcher_config,
+            registry,
+            PeerReputation::new(),
+        )
+        .unwrap();
         let reactor = Reactor {
             network,
             storage,
@@ -23,9 +23,11 @@
 //! Nodes gossip their public listening addresses periodically, and will try to establish and
 //! maintain an outgoing connection to any new address learned.
 
+mod allowlist;
 mod bincode_format;
 pub(crate) mod blocklist;
 mod chain_info;
+mod compression;
 mod config;
 mod counting_format;
 mod error;
@@ -39,6 +41,7 @@ mod message;
 mod message_pack_format;
 mod metrics;
 mod outgoing;
+mod peer_store;
 mod symmetry;
 pub(crate) mod tasks;
 #[cfg(test)]
@@ -52,6 +55,7 @@ use std::{
     fmt::{self, Debug, Display, Formatter},
     io,
     net::{SocketAddr, TcpListener},
+    path::Path,
     sync::{Arc, Weak},
     time::{Duration, Instant},
 };
@@ -79,24 +83,13 @@ use tracing::{debug, error, info, trace, warn, Instrument, Span};
 #[cfg(test)]
 use futures::{future::BoxFuture, FutureExt};
 
-use casper_types::{EraId, PublicKey, SecretKey};
+use casper_types::{EraId, Timestamp};
 
-pub(crate) use self::{
-    bincode_format::BincodeFormat,
-    config::{Config, IdentityConfig},
-    error::Error,
-    event::Event,
-    gossiped_address::GossipedAddress,
-    identity::Identity,
-    insights::NetworkInsights,
-    message::{
-        within_message_size_limit_tolerance, EstimatorWeights, FromIncoming, Message, MessageKind,
-        Payload,
-    },
-};
 use self::{
+    allowlist::Allowlist,
     blocklist::BlocklistJustification,
     chain_info::ChainInfo,
+    compression::{CompressingFormat, CompressionSettings},
     counting_format::{ConnectionId, CountingFormat, Role},
     error::{ConnectionError, Result},
     event::{IncomingConnection, OutgoingConnection},
@@ -105,9 +98,23 @@ use self::{
     message::NodeKeyPair,
     metrics::Metrics,
     outgoing::{DialOutcome, DialRequest, OutgoingConfig, OutgoingManager},
+    peer_store::PeerStore,
     symmetry::ConnectionSymmetry,
     tasks::{MessageQueueItem, NetworkContext},
 };
+pub(crate) use self::{
+    bincode_format::BincodeFormat,
+    config::{Config, IdentityConfig},
+    error::Error,
+    event::Event,
+    gossiped_address::GossipedAddress,
+    identity::Identity,
+    insights::NetworkInsights,
+    message::{
+        within_message_size_limit_tolerance, EstimatorWeights, FromIncoming, Message, MessageKind,
+        Payload,
+    },
+};
 use crate::{
     components::{gossiper::GossipItem, Component, ComponentState, InitializedComponent},
     effect::{
@@ -116,8 +123,9 @@ use crate::{
         AutoClosingResponder, EffectBuilder, EffectExt, Effects, GossipTarget,
     },
     reactor::ReactorEvent,
+    signer::ValidatorSigner,
     tls,
-    types::{NodeId, ValidatorMatrix},
+    types::{NodeId, PeerReputation, ValidatorMatrix},
     utils::{self, display_error, Source},
     NodeRng,
 };
@@ -176,11 +184,18 @@ where
 
     /// Outgoing connections manager.
     outgoing_manager: OutgoingManager<OutgoingHandle<P>, ConnectionError>,
+    /// Persistent store of peers we successfully connected to, if enabled.
+    peer_store: Option<PeerStore>,
+    /// The peers we are allowed to connect to, if we are a private validator.
+    allowlist: Option<Allowlist>,
     /// Tracks whether a connection is symmetric or not.
     connection_symmetries: HashMap<NodeId, ConnectionSymmetry>,
 
     /// Tracks nodes that have announced themselves as nodes that are syncing.
     syncing_nodes: HashSet<NodeId>,
+    /// Reputation of the peers we fetch data from, reported through the network insights.
+    #[data_size(skip)]
+    peer_reputation: PeerReputation,
     #[data_size(skip)]
     channel_management: Option<ChannelManagement>,
 
@@ -240,15 +255,19 @@ where
         + From<BeginGossipRequest<GossipedAddress>>,
 {
     /// Creates a new network component instance.
-    #[allow(clippy::type_complexity)]
+    ///
+    /// The peer store is kept in `peer_store_dir`, if given and enabled in the config.
+    #[allow(clippy::type_complexity, clippy::too_many_arguments)]
     pub(crate) fn new<C: Into<ChainInfo>>(
         cfg: Config,
         our_identity: Identity,
-        node_key_pair: Option<(Arc<SecretKey>, PublicKey)>,
+        node_key_pair: Option<Arc<dyn ValidatorSigner>>,
         registry: &Registry,
         chain_info_source: C,
         validator_matrix: ValidatorMatrix,
+        peer_reputation: PeerReputation,
         allow_handshake: bool,
+        peer_store_dir: Option<&Path>,
     ) -> Result<Network<REv, P>> {
         let net_metrics = Arc::new(Metrics::new(registry)?);
 
@@ -290,12 +309,19 @@ where
             allow_handshake,
         ));
 
+        let peer_store = peer_store_dir
+            .filter(|_| cfg.peer_store.enabled)
+            .map(|dir| PeerStore::load(dir, &cfg.peer_store, Timestamp::now()));
+
         let component = Network {
             cfg,
             context,
             outgoing_manager,
+            peer_store,
+            allowlist: None,
             connection_symmetries: HashMap::new(),
             syncing_nodes: HashSet::new(),
+            peer_reputation,
             channel_management: None,
             net_metrics,
             outgoing_limiter,
@@ -323,6 +349,21 @@ where
             }
         }
 
+        if self.cfg.sentry.private_validator {
+            info!("running as a private validator, only connecting to allowed peers");
+            self.allowlist = Some(Allowlist::new(&self.cfg.sentry));
+
+            // Never dial a known address that is not allowed, as the handshake alone would reveal
+            // our address and consensus key to it.
+            known_addresses.retain(|addr| {
+                let is_allowed = self.is_dialing_allowed(*addr);
+                if !is_allowed {
+                    warn!(%addr, "ignoring known address not among the allowed addresses");
+                }
+                is_allowed
+            });
+        }
+
         // Assert we have at least one known address in the config.
         if known_addresses.is_empty() {
             warn!("no known addresses provided via config or all failed DNS resolution");
@@ -383,19 +424,40 @@ where
 
         // Learn all known addresses and mark them as unforgettable.
         let now = Instant::now();
-        let dial_requests: Vec<_> = known_addresses
-            .into_iter()
-            .filter_map(|addr| self.outgoing_manager.learn_addr(addr, true, now))
+        let mut dial_requests: Vec<_> = known_addresses
+            .iter()
+            .filter_map(|addr| self.outgoing_manager.learn_addr(*addr, true, now))
             .collect();
 
+        // Learn the addresses from the peer store as regular, forgettable addresses.
+        if let Some(peer_store) = self.peer_store.as_ref() {
+            let candidates: Vec<_> = peer_store
+                .dial_candidates(Timestamp::now(), self.cfg.blocklist_retain_max_duration)
+                .into_iter()
+                .filter(|addr| *addr != public_addr && !known_addresses.contains(addr))
+                .filter(|addr| self.is_dialing_allowed(*addr))
+                .collect();
+            info!(
+                candidate_count = candidates.len(),
+                "dialing addresses from peer store"
+            );
+            dial_requests.extend(
+                candidates
+                    .into_iter()
+                    .filter_map(|addr| self.outgoing_manager.learn_addr(addr, false, now)),
+            );
+        }
+
         let mut effects = self.process_dial_requests(dial_requests);
 
-        // Start broadcasting our public listening address.
-        effects.extend(
-            effect_builder
-                .set_timeout(self.cfg.initial_gossip_delay.into())
-                .event(|_| Event::GossipOurAddress),
-        );
+        // Start broadcasting our public listening address, unless we are hiding behind sentries.
+        if !self.cfg.sentry.private_validator {
+            effects.extend(
+                effect_builder
+                    .set_timeout(self.cfg.initial_gossip_delay.into())
+                    .event(|_| Event::GossipOurAddress),
+            );
+        }
 
         // Start regular housekeeping of the outgoing connections.
         effects.extend(
@@ -424,7 +486,10 @@ where
 
         for peer_id in self.outgoing_manager.connected_peers() {
             total_outgoing_manager_connected_peers += 1;
-            if self.outgoing_limiter.is_validator_in_era(era_id, &peer_id) {
+            // A private validator is only connected to its sentries, which relay on its behalf.
+            if self.cfg.sentry.private_validator
+                || self.outgoing_limiter.is_validator_in_era(era_id, &peer_id)
+            {
                 total_connected_validators_in_era += 1;
                 self.send_message(peer_id, msg.clone(), None);
             }
@@ -571,6 +636,13 @@ where
                     }
                 }
 
+                if let Some(allowlist) = self.allowlist.as_ref() {
+                    if !allowlist.allows_incoming(peer_id, peer_addr) {
+                        info!(%public_addr, %peer_id, "rejecting new incoming connection, peer not allowed");
+                        return Effects::new();
+                    }
+                }
+
                 info!(%public_addr, "new incoming connection established");
 
                 // Learn the address the peer gave us.
@@ -677,6 +749,7 @@ where
             ConnectionError::NoPeerCertificate
             | ConnectionError::PeerCertificateInvalid(_)
             | ConnectionError::DidNotSendHandshake
+            | ConnectionError::DidNotSendCertificate
             | ConnectionError::InvalidRemoteHandshakeMessage(_)
             | ConnectionError::InvalidConsensusCertificate(_) => None,
 
@@ -719,7 +792,14 @@ where
                 // We perform blocking first, to not trigger a reconnection before blocking.
                 let mut requests = Vec::new();
 
+                if let Some(peer_store) = self.peer_store.as_mut() {
+                    peer_store.record_failure(peer_addr);
+                }
+
                 if let Some(justification) = Self::is_blockable_offense_for_outgoing(&error) {
+                    if let Some(peer_store) = self.peer_store.as_mut() {
+                        peer_store.record_block(peer_addr, &justification, Timestamp::now());
+                    }
                     requests.extend(self.outgoing_manager.block_addr(
                         peer_addr,
                         now,
@@ -743,6 +823,9 @@ where
             OutgoingConnection::Loopback { peer_addr } => {
                 // Loopback connections are marked, but closed.
                 info!("successful outgoing loopback connection, will be dropped");
+                if let Some(peer_store) = self.peer_store.as_mut() {
+                    peer_store.forget(peer_addr);
+                }
                 let request = self
                     .outgoing_manager
                     .handle_dial_outcome(DialOutcome::Loopback { addr: peer_addr });
@@ -755,8 +838,26 @@ where
                 sink,
                 is_syncing,
             } => {
+                if let Some(allowlist) = self.allowlist.as_ref() {
+                    if !allowlist.allows_outgoing(peer_id, peer_addr) {
+                        // Blocking the address closes the connection by dropping the sink.
+                        info!("outgoing connection established to peer not allowed, blocking");
+                        let request = self.outgoing_manager.block_addr(
+                            peer_addr,
+                            now,
+                            BlocklistJustification::NotAllowed,
+                            rng,
+                        );
+                        return self.process_dial_requests(request);
+                    }
+                }
+
                 info!("new outgoing connection established");
 
+                if let Some(peer_store) = self.peer_store.as_mut() {
+                    peer_store.record_success(peer_addr, Timestamp::now());
+                }
+
                 let (sender, receiver) = mpsc::unbounded_channel();
                 let handle = OutgoingHandle { sender, peer_addr };
 
@@ -936,6 +1037,11 @@ where
                 warn!("received unexpected handshake");
                 Effects::new()
             }
+            Message::Certificate { .. } => {
+                // Certificates are only exchanged while handshaking, so this is discarded as well.
+                warn!("received unexpected certificate");
+                Effects::new()
+            }
             Message::Ping { nonce } => {
                 // Send a pong. Incoming pings and pongs are rate limited.
 
@@ -957,11 +1063,60 @@ where
                 Effects::new()
             }
             Message::Payload(payload) => {
+                if matches!(payload.message_kind(), MessageKind::Consensus) {
+                    self.relay_consensus_message(peer_id, &payload);
+                }
                 effect_builder.announce_incoming(peer_id, payload).ignore()
             }
         })
     }
 
+    /// Relays a consensus message if we are a sentry for the sender or for any of our peers.
+    ///
+    /// Messages from protected validators are passed on to the validators of the active era, while
+    /// messages from any other peer are passed on to the protected validators.
+    fn relay_consensus_message(&self, sender: NodeId, payload: &P) {
+        let protected_peer_ids = &self.cfg.sentry.protected_peer_ids;
+        if protected_peer_ids.is_empty() {
+            return;
+        }
+
+        let recipients: Vec<_> = if protected_peer_ids.contains(&sender) {
+            self.outgoing_manager
+                .connected_peers()
+                .filter(|peer_id| {
+                    !protected_peer_ids.contains(peer_id)
+                        && self
+                            .outgoing_limiter
+                            .is_validator_in_era(self.active_era, peer_id)
+                })
+                .collect()
+        } else {
+            self.outgoing_manager
+                .connected_peers()
+                .filter(|peer_id| protected_peer_ids.contains(peer_id))
+                .collect()
+        };
+
+        if recipients.is_empty() {
+            return;
+        }
+        trace!(%sender, recipient_count = recipients.len(), "relaying consensus message");
+        let msg = Arc::new(Message::Payload(payload.clone()));
+        for peer_id in recipients {
+            self.send_message(peer_id, msg.clone(), None);
+        }
+    }
+
+    /// Returns whether we may dial the given address without knowing who is behind it.
+    ///
+    /// Always true, unless we are a private validator.
+    fn is_dialing_allowed(&self, addr: SocketAddr) -> bool {
+        self.allowlist
+            .as_ref()
+            .is_none_or(|allowlist| allowlist.allows_dialing(addr))
+    }
+
     /// Emits an announcement that a connection has been completed.
     fn connection_completed(&self, peer_id: NodeId) {
         trace!(num_peers = self.peers().len(), new_peer=%peer_id, "connection complete");
@@ -1016,6 +1171,14 @@ where
             .choose_multiple(rng, count)
     }
 
+    pub(crate) fn fully_connected_peers(&self) -> Vec<NodeId> {
+        self.connection_symmetries
+            .iter()
+            .filter(|(_, sym)| matches!(sym, ConnectionSymmetry::Symmetric { .. }))
+            .map(|(node_id, _)| *node_id)
+            .collect()
+    }
+
     pub(crate) fn has_sufficient_fully_connected_peers(&self) -> bool {
         self.connection_symmetries
             .iter()
@@ -1245,16 +1408,23 @@ where
                     effects
                 }
                 Event::PeerAddressReceived(gossiped_address) => {
-                    let requests = self.outgoing_manager.learn_addr(
-                        gossiped_address.into(),
-                        false,
-                        Instant::now(),
-                    );
+                    let addr = gossiped_address.into();
+                    if !self.is_dialing_allowed(addr) {
+                        trace!(%addr, "ignoring gossiped address of peer not allowed");
+                        return Effects::new();
+                    }
+                    let requests = self
+                        .outgoing_manager
+                        .learn_addr(addr, false, Instant::now());
                     self.process_dial_requests(requests)
                 }
                 Event::SweepOutgoing => {
                     let now = Instant::now();
                     let requests = self.outgoing_manager.perform_housekeeping(rng, now);
+                    if let Some(peer_store) = self.peer_store.as_mut() {
+                        peer_store.perform_housekeeping(Timestamp::now(), now);
+                    }
+                    self.peer_reputation.prune(now);
 
                     let mut effects = self.process_dial_requests(requests);
 
@@ -1276,6 +1446,9 @@ where
                         info!(%offender, %justification, "adding peer to blocklist after transgression");
 
                         if let Some(addr) = self.outgoing_manager.get_addr(*offender) {
+                            if let Some(peer_store) = self.peer_store.as_mut() {
+                                peer_store.record_block(addr, &justification, Timestamp::now());
+                            }
                             let requests = self.outgoing_manager.block_addr(
                                 addr,
                                 Instant::now(),
@@ -1328,7 +1501,7 @@ pub(crate) type FullTransport<P> = tokio_serde::Framed<
     FramedTransport,
     Message<P>,
     Arc<Message<P>>,
-    CountingFormat<BincodeFormat>,
+    CountingFormat<CompressingFormat<BincodeFormat>>,
 >;
 
 pub(crate) type FramedTransport = tokio_util::codec::Framed<Transport, LengthDelimitedCodec>;
@@ -1341,14 +1514,22 @@ fn full_transport<P>(
     connection_id: ConnectionId,
     framed: FramedTransport,
     role: Role,
+    compression: Option<CompressionSettings>,
+    maximum_net_message_size: u32,
 ) -> FullTransport<P>
 where
     for<'de> P: Serialize + Deserialize<'de>,
     for<'de> Message<P>: Serialize + Deserialize<'de>,
 {
+    let format = CompressingFormat::new(
+        metrics.clone(),
+        compression,
+        maximum_net_message_size,
+        BincodeFormat::default(),
+    );
     tokio_serde::Framed::new(
         framed,
-        CountingFormat::new(metrics, connection_id, role, BincodeFormat::default()),
+        CountingFormat::new(metrics, connection_id, role, format),
     )
 }
 
@@ -0,0 +1,101 @@
+//! Allowlisting support for private validators.
+//!
+//! A private validator hidden behind sentry nodes only keeps connections to peers on its allowlist,
+//! identified either by their node ID or by their address.
+
+use std::{collections::HashSet, net::SocketAddr};
+
+use datasize::DataSize;
+use tracing::warn;
+
+use super::{config::SentryConfig, NodeId};
+use crate::utils::{self, display_error};
+
+/// The set of peers a private validator is allowed to connect to.
+#[derive(DataSize, Debug)]
+pub(super) struct Allowlist {
+    /// Allowed node IDs.
+    node_ids: HashSet<NodeId>,
+    /// Allowed addresses.
+    addresses: HashSet<SocketAddr>,
+}
+
+impl Allowlist {
+    /// Creates the allowlist from the configuration, resolving the allowed addresses.
+    ///
+    /// Addresses that fail to resolve are skipped with a warning.
+    pub(super) fn new(config: &SentryConfig) -> Self {
+        let addresses = config
+            .allowed_addresses
+            .iter()
+            .filter_map(|address| match utils::resolve_address(address) {
+                Ok(addr) => Some(addr),
+                Err(ref err) => {
+                    warn!(%address, err=display_error(err), "failed to resolve allowed address");
+                    None
+                }
+            })
+            .collect();
+
+        Allowlist {
+            node_ids: config.allowed_peer_ids.iter().copied().collect(),
+            addresses,
+        }
+    }
+
+    /// Returns whether an address may be dialed before the node ID behind it is known.
+    pub(super) fn allows_dialing(&self, addr: SocketAddr) -> bool {
+        self.addresses.contains(&addr)
+    }
+
+    /// Returns whether an outgoing connection to the given peer, dialed at `addr`, is allowed.
+    pub(super) fn allows_outgoing(&self, peer_id: NodeId, addr: SocketAddr) -> bool {
+        self.node_ids.contains(&peer_id) || self.addresses.contains(&addr)
+    }
+
+    /// Returns whether an incoming connection from the given peer is allowed.
+    ///
+    /// As incoming connections originate from an arbitrary port, only the host of `peer_addr` is
+    /// compared against the allowed addresses.
+    pub(super) fn allows_incoming(&self, peer_id: NodeId, peer_addr: SocketAddr) -> bool {
+        self.node_ids.contains(&peer_id)
+            || self
+                .addresses
+                .iter()
+                .any(|addr| addr.ip() == peer_addr.ip())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn should_allow_configured_peers_only() {
+        let mut rng = TestRng::new();
+        let allowed_id = NodeId::random(&mut rng);
+        let other_id = NodeId::random(&mut rng);
+        let allowlist = Allowlist::new(&SentryConfig {
+            private_validator: true,
+            allowed_peer_ids: vec![allowed_id],
+            allowed_addresses: vec!["10.0.0.1:34553".to_string()],
+            protected_peer_ids: vec![],
+        });
+
+        let allowed_addr: SocketAddr = "10.0.0.1:34553".parse().unwrap();
+        let other_addr: SocketAddr = "10.0.0.2:34553".parse().unwrap();
+
+        assert!(allowlist.allows_dialing(allowed_addr));
+        assert!(!allowlist.allows_dialing(other_addr));
+
+        assert!(allowlist.allows_outgoing(allowed_id, other_addr));
+        assert!(allowlist.allows_outgoing(other_id, allowed_addr));
+        assert!(!allowlist.allows_outgoing(other_id, other_addr));
+
+        assert!(allowlist.allows_incoming(allowed_id, other_addr));
+        assert!(allowlist.allows_incoming(other_id, "10.0.0.1:50123".parse().unwrap()));
+        assert!(!allowlist.allows_incoming(other_id, "10.0.0.2:50123".parse().unwrap()));
+    }
+}
@@ -63,6 +63,8 @@ pub(crate) enum BlocklistJustification {
     DishonestPeer,
     /// Peer sent too many finality signatures.
     SentTooManyFinalitySignatures { max_allowed: u32 },
+    /// Peer is not on the allowlist of this private validator.
+    NotAllowed,
 }
 
 impl Display for BlocklistJustification {
@@ -111,6 +113,9 @@ impl Display for BlocklistJustification {
                 f,
                 "sent too many finality signatures: maximum {max_allowed} signatures are allowed"
             ),
+            BlocklistJustification::NotAllowed => {
+                f.write_str("is not on the allowlist of this private validator")
+            }
         }
     }
 }
@@ -8,11 +8,7 @@ use std::net::SocketAddr;
 use casper_types::{Chainspec, Digest, ProtocolVersion};
 use datasize::DataSize;
 
-use super::{
-    counting_format::ConnectionId,
-    message::{ConsensusCertificate, NodeKeyPair},
-    Message,
-};
+use super::{compression::Compression, Message};
 
 /// Data retained from the chainspec by the networking component.
 ///
@@ -44,21 +40,24 @@ impl ChainInfo {
     }
 
     /// Create a handshake based on chain identification data.
+    ///
+    /// The handshake carries no consensus certificate: Only tagged certificates are signed, and
+    /// only once the peer's handshake shows it supports them.
     pub(super) fn create_handshake<P>(
         &self,
         public_addr: SocketAddr,
-        consensus_keys: Option<&NodeKeyPair>,
-        connection_id: ConnectionId,
         is_syncing: bool,
+        compression: Option<Compression>,
     ) -> Message<P> {
         Message::Handshake {
             network_name: self.network_name.clone(),
             public_addr,
             protocol_version: self.protocol_version,
-            consensus_certificate: consensus_keys
-                .map(|key_pair| ConsensusCertificate::create(connection_id, key_pair)),
+            consensus_certificate: None,
             is_syncing,
             chainspec_hash: Some(self.chainspec_hash),
+            compression,
+            tagged_certificates: true,
         }
     }
 }
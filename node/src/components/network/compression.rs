@@ -0,0 +1,287 @@
+//! Payload compression for network messages.
+//!
+//! Compression is negotiated during the handshake: each node offers the algorithm it supports (if
+//! enabled), and only if both sides offer the same one is it used on the connection. Connections
+//! without compression use the exact same wire format as before, ensuring compatibility with peers
+//! that do not support it.
+//!
+//! On a connection with compression, every frame is prefixed with a single byte indicating
+//! whether the remainder is compressed. Only messages of the configured kinds above the configured
+//! size threshold are compressed by the sender, and only if compression actually reduces their
+//! size; the receiver accepts compressed frames of any kind.
+
+use std::{
+    io::{self, Read, Write},
+    pin::Pin,
+    sync::{Arc, Weak},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use datasize::DataSize;
+use flate2::{read::DeflateDecoder, write::DeflateEncoder};
+use pin_project::pin_project;
+use serde::{Deserialize, Serialize};
+use tokio_serde::{Deserializer, Serializer};
+
+use super::{config::CompressionConfig, Message, MessageKind, Metrics, Payload};
+
+/// Frame prefix of an uncompressed message.
+const FRAME_UNCOMPRESSED: u8 = 0;
+
+/// Frame prefix of a deflate compressed message.
+const FRAME_DEFLATE: u8 = 1;
+
+/// A compression algorithm for network messages.
+#[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub(crate) enum Compression {
+    /// Deflate compression, as specified in RFC 1951.
+    Deflate,
+}
+
+impl Compression {
+    /// Returns the algorithm agreed upon given our and the peer's offer, if any.
+    pub(super) fn negotiate(ours: Option<Self>, theirs: Option<Self>) -> Option<Self> {
+        ours.filter(|ours| theirs == Some(*ours))
+    }
+}
+
+/// Compression settings of a single connection.
+#[derive(Clone, Debug)]
+pub(super) struct CompressionSettings {
+    /// Minimum size in bytes of a serialized message to be compressed.
+    threshold: usize,
+    /// Kinds of messages to compress.
+    message_kinds: Arc<[MessageKind]>,
+}
+
+impl CompressionSettings {
+    /// Creates the settings to use for connections on which compression was negotiated.
+    pub(super) fn new(config: &CompressionConfig) -> Self {
+        CompressionSettings {
+            threshold: config.threshold as usize,
+            message_kinds: config.message_kinds.iter().copied().collect(),
+        }
+    }
+
+    /// Returns whether or not a serialized message of the given kind and size should be
+    /// compressed.
+    fn should_compress(&self, kind: MessageKind, size: usize) -> bool {
+        size >= self.threshold && self.message_kinds.contains(&kind)
+    }
+}
+
+/// A serializer/deserializer wrapper compressing messages, if enabled on the connection.
+#[pin_project]
+#[derive(Debug)]
+pub struct CompressingFormat<F> {
+    /// The actual serializer performing the work.
+    #[pin]
+    inner: F,
+    /// The compression settings, `None` if compression was not negotiated.
+    settings: Option<CompressionSettings>,
+    /// Maximum size of a decompressed message.
+    max_message_size: usize,
+    /// Metrics to update.
+    metrics: Weak<Metrics>,
+}
+
+impl<F> CompressingFormat<F> {
+    /// Creates a new compressing formatter.
+    ///
+    /// If `settings` is `None`, messages are passed on to the inner formatter unchanged.
+    pub(super) fn new(
+        metrics: Weak<Metrics>,
+        settings: Option<CompressionSettings>,
+        max_message_size: u32,
+        inner: F,
+    ) -> Self {
+        CompressingFormat {
+            inner,
+            settings,
+            max_message_size: max_message_size as usize,
+            metrics,
+        }
+    }
+}
+
+/// Prefixes `data` with the given frame prefix.
+fn prefixed(prefix: u8, data: &[u8]) -> Bytes {
+    let mut buffer = BytesMut::with_capacity(data.len() + 1);
+    buffer.put_u8(prefix);
+    buffer.put_slice(data);
+    buffer.freeze()
+}
+
+/// Compresses `data` using deflate.
+fn deflate(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::fast());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+/// Decompresses deflate compressed `data`, failing if the result exceeds `max_size` bytes.
+fn inflate(data: &[u8], max_size: usize) -> io::Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    DeflateDecoder::new(data)
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() > max_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "decompressed message exceeds the maximum message size",
+        ));
+    }
+    Ok(decompressed)
+}
+
+impl<F, P> Serializer<Arc<Message<P>>> for CompressingFormat<F>
+where
+    F: Serializer<Arc<Message<P>>, Error = io::Error>,
+    P: Payload,
+{
+    type Error = io::Error;
+
+    fn serialize(self: Pin<&mut Self>, item: &Arc<Message<P>>) -> Result<Bytes, Self::Error> {
+        let this = self.project();
+        let serialized = F::serialize(this.inner, item)?;
+
+        let settings = match this.settings {
+            Some(settings) => settings,
+            None => return Ok(serialized),
+        };
+
+        if settings.should_compress(item.classify(), serialized.len()) {
+            let compressed = deflate(&serialized)?;
+            if compressed.len() < serialized.len() {
+                Metrics::record_compression_out(this.metrics, serialized.len(), compressed.len());
+                return Ok(prefixed(FRAME_DEFLATE, &compressed));
+            }
+        }
+
+        Ok(prefixed(FRAME_UNCOMPRESSED, &serialized))
+    }
+}
+
+impl<F, P> Deserializer<Message<P>> for CompressingFormat<F>
+where
+    F: Deserializer<Message<P>, Error = io::Error>,
+    P: Payload,
+{
+    type Error = io::Error;
+
+    fn deserialize(self: Pin<&mut Self>, src: &BytesMut) -> Result<Message<P>, Self::Error> {
+        let this = self.project();
+        if this.settings.is_none() {
+            return F::deserialize(this.inner, src);
+        }
+
+        let (prefix, data) = src.split_first().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "missing compression frame prefix",
+            )
+        })?;
+        match *prefix {
+            FRAME_UNCOMPRESSED => F::deserialize(this.inner, &BytesMut::from(data)),
+            FRAME_DEFLATE => {
+                let decompressed = inflate(data, *this.max_message_size)?;
+                Metrics::record_compression_in(this.metrics, decompressed.len(), data.len());
+                F::deserialize(this.inner, &BytesMut::from(decompressed.as_slice()))
+            }
+            other => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unknown compression frame prefix {}", other),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use casper_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{components::network::BincodeFormat, protocol};
+
+    fn format(settings: Option<CompressionSettings>) -> CompressingFormat<BincodeFormat> {
+        CompressingFormat::new(Weak::new(), settings, 1024 * 1024, BincodeFormat::default())
+    }
+
+    fn settings(threshold: usize) -> CompressionSettings {
+        CompressionSettings {
+            threshold,
+            message_kinds: vec![MessageKind::Protocol].into(),
+        }
+    }
+
+    /// Creates a well compressible message of the protocol kind.
+    fn message() -> Arc<Message<protocol::Message>> {
+        Arc::new(Message::Handshake {
+            network_name: "a".repeat(1000),
+            public_addr: SocketAddr::from(([127, 0, 0, 1], 34553)),
+            protocol_version: ProtocolVersion::V1_0_0,
+            consensus_certificate: None,
+            is_syncing: false,
+            chainspec_hash: None,
+            compression: None,
+            tagged_certificates: false,
+        })
+    }
+
+    fn roundtrip(
+        format: &mut CompressingFormat<BincodeFormat>,
+        message: &Arc<Message<protocol::Message>>,
+    ) -> Bytes {
+        let serialized = Pin::new(&mut *format).serialize(message).unwrap();
+        let deserialized: Message<protocol::Message> = Pin::new(&mut *format)
+            .deserialize(&BytesMut::from(serialized.as_ref()))
+            .unwrap();
+        assert_eq!(deserialized.to_string(), message.to_string());
+        serialized
+    }
+
+    #[test]
+    fn should_negotiate_only_common_algorithm() {
+        let deflate = Some(Compression::Deflate);
+        assert_eq!(Compression::negotiate(deflate, deflate), deflate);
+        assert_eq!(Compression::negotiate(deflate, None), None);
+        assert_eq!(Compression::negotiate(None, deflate), None);
+    }
+
+    #[test]
+    fn should_not_change_wire_format_without_compression() {
+        let message = message();
+        let serialized = roundtrip(&mut format(None), &message);
+        let plain = BincodeFormat::default()
+            .serialize_arbitrary(&*message)
+            .unwrap();
+        assert_eq!(serialized.as_ref(), plain.as_slice());
+    }
+
+    #[test]
+    fn should_compress_messages_above_threshold() {
+        let message = message();
+        let plain_size = BincodeFormat::default()
+            .serialize_arbitrary(&*message)
+            .unwrap()
+            .len();
+
+        let serialized = roundtrip(&mut format(Some(settings(100))), &message);
+        assert_eq!(serialized[0], FRAME_DEFLATE);
+        assert!(serialized.len() < plain_size);
+
+        let serialized = roundtrip(&mut format(Some(settings(plain_size + 1))), &message);
+        assert_eq!(serialized[0], FRAME_UNCOMPRESSED);
+        assert_eq!(serialized.len(), plain_size + 1);
+    }
+
+    #[test]
+    fn should_reject_oversized_decompressed_messages() {
+        let data = vec![0; 2048];
+        let compressed = deflate(&data).unwrap();
+        assert_eq!(inflate(&compressed, 2048).unwrap(), data);
+        assert!(inflate(&compressed, 2047).is_err());
+    }
+}
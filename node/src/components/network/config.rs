@@ -6,7 +6,8 @@ use casper_types::{ProtocolVersion, TimeDiff};
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use super::EstimatorWeights;
+use super::{EstimatorWeights, MessageKind};
+use crate::types::NodeId;
 
 /// Default binding address.
 ///
@@ -32,6 +33,15 @@ const DEFAULT_MAX_ADDR_PENDING_TIME: TimeDiff = TimeDiff::from_seconds(60);
 /// Default timeout during which the handshake needs to be completed.
 const DEFAULT_HANDSHAKE_TIMEOUT: TimeDiff = TimeDiff::from_seconds(20);
 
+/// Default time after which peers that have not been seen are dropped from the peer store.
+const DEFAULT_PEER_STORE_MAX_AGE: TimeDiff = TimeDiff::from_seconds(3 * 24 * 60 * 60);
+
+/// Default maximum number of addresses kept in the peer store.
+const DEFAULT_PEER_STORE_MAX_SIZE: u32 = 256;
+
+/// Default minimum size in bytes of a serialized message to be compressed.
+const DEFAULT_COMPRESSION_THRESHOLD: u32 = 4 * 1024;
+
 impl Default for Config {
     fn default() -> Self {
         Config {
@@ -54,6 +64,19 @@ impl Default for Config {
             blocklist_retain_min_duration: TimeDiff::from_seconds(600),
             blocklist_retain_max_duration: TimeDiff::from_seconds(1600),
             identity: None,
+            peer_store: Default::default(),
+            sentry: Default::default(),
+            compression: Default::default(),
+        }
+    }
+}
+
+impl Default for PeerStoreConfig {
+    fn default() -> Self {
+        PeerStoreConfig {
+            enabled: true,
+            max_age: DEFAULT_PEER_STORE_MAX_AGE,
+            max_size: DEFAULT_PEER_STORE_MAX_SIZE,
         }
     }
 }
@@ -71,6 +94,72 @@ pub struct IdentityConfig {
     pub ca_certificate: PathBuf,
 }
 
+/// Peer store configuration.
+#[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct PeerStoreConfig {
+    /// Whether or not addresses of successfully connected peers are persisted across restarts.
+    pub enabled: bool,
+    /// Time after which a peer that has not been seen is dropped from the peer store.
+    pub max_age: TimeDiff,
+    /// Maximum number of addresses kept in the peer store.
+    pub max_size: u32,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        CompressionConfig {
+            enabled: true,
+            threshold: DEFAULT_COMPRESSION_THRESHOLD,
+            message_kinds: vec![
+                MessageKind::TransactionGossip,
+                MessageKind::TransactionTransfer,
+                MessageKind::BlockTransfer,
+                MessageKind::TrieTransfer,
+            ],
+        }
+    }
+}
+
+/// Payload compression configuration.
+#[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct CompressionConfig {
+    /// Whether or not to offer payload compression to peers.
+    ///
+    /// Compression is only used on connections to peers that offer it as well.
+    pub enabled: bool,
+    /// Minimum size in bytes of a serialized message to be compressed.
+    pub threshold: u32,
+    /// Kinds of messages to compress.
+    pub message_kinds: Vec<MessageKind>,
+}
+
+/// Configuration of the sentry node topology.
+///
+/// A private validator is hidden behind sentry nodes: it only connects to its sentries, which
+/// connect to the rest of the network and relay consensus messages on its behalf.
+#[derive(DataSize, Debug, Clone, Default, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct SentryConfig {
+    /// Whether or not this node is a private validator.
+    ///
+    /// A private validator only keeps connections to the allowed peers, never gossips its own
+    /// address and sends its consensus messages to all of its peers.
+    pub private_validator: bool,
+    /// Node IDs of the peers a private validator is allowed to connect to.
+    pub allowed_peer_ids: Vec<NodeId>,
+    /// Addresses of the peers a private validator is allowed to connect to.
+    ///
+    /// Incoming connections are allowed from any port of the given hosts.
+    pub allowed_addresses: Vec<String>,
+    /// Node IDs of the private validators this node relays consensus messages for.
+    pub protected_peer_ids: Vec<NodeId>,
+}
+
 /// Network configuration.
 #[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -123,6 +212,17 @@ pub struct Config {
     /// An identity will be automatically generated when starting up a node if this option is
     /// unspecified.
     pub identity: Option<IdentityConfig>,
+    /// Peer store configuration.
+    ///
+    /// Addresses from the peer store are dialed on startup in addition to the known addresses.
+    #[serde(default)]
+    pub peer_store: PeerStoreConfig,
+    /// Sentry node topology configuration.
+    #[serde(default)]
+    pub sentry: SentryConfig,
+    /// Payload compression configuration.
+    #[serde(default)]
+    pub compression: CompressionConfig,
 }
 
 #[cfg(test)]
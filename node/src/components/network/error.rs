@@ -184,6 +184,9 @@ pub enum ConnectionError {
         #[source]
         io::Error,
     ),
+    /// Peer supports tagged consensus certificates, but did not follow its handshake with one.
+    #[error("peer did not send certificate message")]
+    DidNotSendCertificate,
     /// The peer sent a consensus certificate, but it was invalid.
     #[error("invalid consensus certificate")]
     InvalidConsensusCertificate(
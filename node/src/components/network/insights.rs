@@ -17,7 +17,7 @@ use casper_types::{DisplayIter, EraId, PublicKey};
 use serde::Serialize;
 
 use crate::{
-    types::NodeId,
+    types::{NodeId, PeerScore},
     utils::{opt_display::OptDisplay, TimeAnchor},
 };
 
@@ -49,6 +49,8 @@ pub(crate) struct NetworkInsights {
     outgoing_connections: Vec<(SocketAddr, OutgoingInsight)>,
     /// Map of incoming connections.
     connection_symmetries: Vec<(NodeId, ConnectionSymmetryInsight)>,
+    /// Reputation of the peers we fetch data from, by peer and kind of item.
+    peer_scores: Vec<PeerScore>,
 }
 
 /// Insight into an outgoing connection.
@@ -324,6 +326,7 @@ impl NetworkInsights {
                 .debug_inspect_unspent_allowance(),
             outgoing_connections,
             connection_symmetries,
+            peer_scores: net.peer_reputation.scores(),
         }
     }
 }
@@ -385,6 +388,29 @@ impl Display for NetworkInsights {
             f.write_str("\n")?;
         }
 
+        f.write_str("peer scores:\n")?;
+        writeln!(
+            f,
+            "peer ID     item                     score  in flight  latency (ms)  throughput (B/s)  \
+             ok/timeouts/bad/absent"
+        )?;
+        for score in &self.peer_scores {
+            writeln!(
+                f,
+                "{:10}  {:23}  {:.3}  {:9}  {:12}  {:16}  {}/{}/{}/{}",
+                score.peer,
+                score.tag.to_string(),
+                score.score,
+                score.in_flight,
+                OptDisplay::new(score.latency.map(|latency| latency.as_millis()), "?"),
+                OptDisplay::new(score.throughput.map(|throughput| throughput as u64), "?"),
+                score.successes,
+                score.timeouts,
+                score.bad_items,
+                score.absent,
+            )?;
+        }
+
         Ok(())
     }
 }
@@ -12,17 +12,22 @@ use serde::{
 };
 use strum::EnumDiscriminants;
 
-#[cfg(test)]
-use casper_types::testing::TestRng;
 use casper_types::{
-    crypto, AsymmetricType, Chainspec, Digest, ProtocolVersion, PublicKey, SecretKey, Signature,
+    crypto, AsymmetricType, Chainspec, Digest, ProtocolVersion, PublicKey, Signature,
     AUCTION_LANE_ID, INSTALL_UPGRADE_LANE_ID, MINT_LANE_ID,
 };
+#[cfg(test)]
+use casper_types::{testing::TestRng, SecretKey};
 
-use super::{counting_format::ConnectionId, health::Nonce, BincodeFormat};
+use super::{
+    compression::Compression, counting_format::ConnectionId, health::Nonce, BincodeFormat,
+};
+#[cfg(test)]
+use crate::signer::LocalSigner;
 use crate::{
     effect::EffectBuilder,
     protocol,
+    signer::{self, SignerError, SigningRequest, ValidatorSigner},
     types::NodeId,
     utils::{
         opt_display::OptDisplay,
@@ -62,6 +67,15 @@ pub(crate) enum Message<P> {
         /// Hash of the chainspec the node is running.
         #[serde(default)]
         chainspec_hash: Option<Digest>,
+        /// The payload compression the node offers, if any.
+        #[serde(default)]
+        compression: Option<Compression>,
+        /// True if the node supports tagged consensus certificates.
+        ///
+        /// If both nodes do, their certificates are exchanged in `Certificate` messages following
+        /// the handshakes instead.
+        #[serde(default)]
+        tagged_certificates: bool,
     },
     /// A ping request.
     Ping {
@@ -74,6 +88,11 @@ pub(crate) enum Message<P> {
         nonce: Nonce,
     },
     Payload(P),
+    /// A tagged certificate, sent after the handshake if both nodes support them.
+    Certificate {
+        /// A self-signed certificate indicating validator status.
+        consensus_certificate: Option<ConsensusCertificate>,
+    },
 }
 
 impl<P: Payload> Message<P> {
@@ -81,9 +100,10 @@ impl<P: Payload> Message<P> {
     #[inline]
     pub(super) fn classify(&self) -> MessageKind {
         match self {
-            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => {
-                MessageKind::Protocol
-            }
+            Message::Handshake { .. }
+            | Message::Certificate { .. }
+            | Message::Ping { .. }
+            | Message::Pong { .. } => MessageKind::Protocol,
             Message::Payload(payload) => payload.message_kind(),
         }
     }
@@ -92,7 +112,10 @@ impl<P: Payload> Message<P> {
     #[inline]
     pub(super) fn is_low_priority(&self) -> bool {
         match self {
-            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => false,
+            Message::Handshake { .. }
+            | Message::Certificate { .. }
+            | Message::Ping { .. }
+            | Message::Pong { .. } => false,
             Message::Payload(payload) => payload.is_low_priority(),
         }
     }
@@ -101,7 +124,7 @@ impl<P: Payload> Message<P> {
     #[inline]
     pub(super) fn payload_incoming_resource_estimate(&self, weights: &EstimatorWeights) -> u32 {
         match self {
-            Message::Handshake { .. } => 0,
+            Message::Handshake { .. } | Message::Certificate { .. } => 0,
             // Ping and Pong have a hardcoded weights. Since every ping will result in a pong being
             // sent as a reply, it has a higher weight.
             Message::Ping { .. } => 2,
@@ -114,7 +137,10 @@ impl<P: Payload> Message<P> {
     #[inline]
     pub(super) fn payload_is_unsafe_for_syncing_nodes(&self) -> bool {
         match self {
-            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => false,
+            Message::Handshake { .. }
+            | Message::Certificate { .. }
+            | Message::Ping { .. }
+            | Message::Pong { .. } => false,
             Message::Payload(payload) => payload.is_unsafe_for_syncing_peers(),
         }
     }
@@ -131,9 +157,10 @@ impl<P: Payload> Message<P> {
         REv: FromIncoming<P> + Send,
     {
         match self {
-            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => {
-                Err(self.into())
-            }
+            Message::Handshake { .. }
+            | Message::Certificate { .. }
+            | Message::Ping { .. }
+            | Message::Pong { .. } => Err(self.into()),
             Message::Payload(payload) => {
                 // Note: For now, the wrapping/unwrap of the payload is a bit unfortunate here.
                 REv::try_demand_from_incoming(effect_builder, sender, payload)
@@ -143,24 +170,22 @@ impl<P: Payload> Message<P> {
     }
 }
 
-/// A pair of secret keys used by consensus.
+/// The validator signer used to prove our identity to peers.
 pub(super) struct NodeKeyPair {
-    secret_key: Arc<SecretKey>,
-    public_key: PublicKey,
+    signer: Arc<dyn ValidatorSigner>,
 }
 
 impl NodeKeyPair {
     /// Creates a new key pair for consensus signing.
-    pub(super) fn new(key_pair: (Arc<SecretKey>, PublicKey)) -> Self {
-        Self {
-            secret_key: key_pair.0,
-            public_key: key_pair.1,
-        }
+    pub(super) fn new(signer: Arc<dyn ValidatorSigner>) -> Self {
+        Self { signer }
     }
 
-    /// Sign a value using this keypair.
-    fn sign<T: AsRef<[u8]>>(&self, value: T) -> Signature {
-        crypto::sign(value, &self.secret_key, &self.public_key)
+    /// Signs a tagged connection ID using the validator signer.
+    fn sign(&self, connection_id: ConnectionId) -> Result<Signature, SignerError> {
+        self.signer.sign(&SigningRequest::ConsensusCertificate {
+            connection_id: connection_id.as_bytes().to_vec(),
+        })
     }
 }
 
@@ -176,18 +201,33 @@ pub(crate) struct ConsensusCertificate {
 }
 
 impl ConsensusCertificate {
-    /// Creates a new consensus certificate from a connection ID and key pair.
-    pub(super) fn create(connection_id: ConnectionId, key_pair: &NodeKeyPair) -> Self {
-        let signature = key_pair.sign(connection_id.as_bytes());
-        ConsensusCertificate {
-            public_key: key_pair.public_key.clone(),
+    /// Creates a new tagged consensus certificate from a connection ID and key pair.
+    pub(super) fn create(
+        connection_id: ConnectionId,
+        key_pair: &NodeKeyPair,
+    ) -> Result<Self, SignerError> {
+        let signature = key_pair.sign(connection_id)?;
+        Ok(ConsensusCertificate {
+            public_key: key_pair.signer.public_key().clone(),
             signature,
-        }
+        })
     }
 
     /// Validates a certificate, returning a `PublicKey` if valid.
-    pub(super) fn validate(self, connection_id: ConnectionId) -> Result<PublicKey, crypto::Error> {
-        crypto::verify(connection_id.as_bytes(), &self.signature, &self.public_key)?;
+    ///
+    /// Untagged certificates, which sign the bare connection ID, are sent by nodes not supporting
+    /// tagged ones.
+    pub(super) fn validate(
+        self,
+        connection_id: ConnectionId,
+        tagged: bool,
+    ) -> Result<PublicKey, crypto::Error> {
+        let bytes = if tagged {
+            signer::consensus_certificate_bytes(connection_id.as_bytes())
+        } else {
+            connection_id.as_bytes().to_vec()
+        };
+        crypto::verify(bytes, &self.signature, &self.public_key)?;
         Ok(self.public_key)
     }
 
@@ -195,11 +235,12 @@ impl ConsensusCertificate {
     #[cfg(test)]
     fn random(rng: &mut TestRng) -> Self {
         let secret_key = SecretKey::random(rng);
-        let public_key = PublicKey::from(&secret_key);
+        let signer = LocalSigner::new(Arc::new(secret_key));
         ConsensusCertificate::create(
             ConnectionId::random(rng),
-            &NodeKeyPair::new((Arc::new(secret_key), public_key)),
+            &NodeKeyPair::new(Arc::new(signer)),
         )
+        .expect("should sign connection id")
     }
 }
 
@@ -300,18 +341,29 @@ impl<P: Display> Display for Message<P> {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                compression,
+                tagged_certificates,
             } => {
                 write!(
                     f,
-                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}",
+                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}, compression: {:?}, tagged_certificates: {}",
                     network_name,
                     public_addr,
                     protocol_version,
                     OptDisplay::new(consensus_certificate.as_ref(), "none"),
                     is_syncing,
-                    OptDisplay::new(chainspec_hash.as_ref(), "none")
+                    OptDisplay::new(chainspec_hash.as_ref(), "none"),
+                    compression,
+                    tagged_certificates
                 )
             }
+            Message::Certificate {
+                consensus_certificate,
+            } => write!(
+                f,
+                "certificate: {}",
+                OptDisplay::new(consensus_certificate.as_ref(), "none")
+            ),
             Message::Ping { nonce } => write!(f, "ping({})", nonce),
             Message::Pong { nonce } => write!(f, "pong({})", nonce),
             Message::Payload(payload) => write!(f, "payload: {}", payload),
@@ -320,8 +372,9 @@ impl<P: Display> Display for Message<P> {
 }
 
 /// A classification system for networking messages.
-#[derive(Copy, Clone, Debug)]
-pub(crate) enum MessageKind {
+#[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
     /// Non-payload messages, like handshakes.
     Protocol,
     /// Messages directly related to consensus.
@@ -448,7 +501,7 @@ mod specimen_support {
         largest_variant, Cache, LargestSpecimen, SizeEstimator, HIGHEST_UNICODE_CODEPOINT,
     };
 
-    use super::{ConsensusCertificate, Message, MessageDiscriminants};
+    use super::{Compression, ConsensusCertificate, Message, MessageDiscriminants};
 
     impl<P> LargestSpecimen for Message<P>
     where
@@ -469,6 +522,11 @@ mod specimen_support {
                         consensus_certificate: LargestSpecimen::largest_specimen(estimator, cache),
                         is_syncing: LargestSpecimen::largest_specimen(estimator, cache),
                         chainspec_hash: LargestSpecimen::largest_specimen(estimator, cache),
+                        compression: Some(Compression::Deflate),
+                        tagged_certificates: true,
+                    },
+                    MessageDiscriminants::Certificate => Message::Certificate {
+                        consensus_certificate: LargestSpecimen::largest_specimen(estimator, cache),
                     },
                     MessageDiscriminants::Ping => Message::Ping {
                         nonce: LargestSpecimen::largest_specimen(estimator, cache),
@@ -828,6 +886,8 @@ mod tests {
             consensus_certificate: Some(ConsensusCertificate::random(&mut rng)),
             is_syncing: false,
             chainspec_hash: Some(Digest::hash("example-chainspec")),
+            compression: Some(Compression::Deflate),
+            tagged_certificates: true,
         };
 
         let legacy_handshake: V1_0_0_Message = roundtrip_message(&modern_handshake);
@@ -862,6 +922,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            compression,
+            tagged_certificates,
         } = modern_handshake
         {
             assert_eq!(network_name, "example-handshake");
@@ -869,7 +931,9 @@ mod tests {
             assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
             assert!(consensus_certificate.is_none());
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(compression.is_none());
+            assert!(!tagged_certificates);
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -886,6 +950,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            compression,
+            tagged_certificates,
         } = modern_handshake
         {
             assert!(!is_syncing);
@@ -894,7 +960,9 @@ mod tests {
             assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
             assert!(consensus_certificate.is_none());
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(compression.is_none());
+            assert!(!tagged_certificates);
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -911,6 +979,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            compression,
+            tagged_certificates,
         } = modern_handshake
         {
             assert_eq!(network_name, "example-handshake");
@@ -938,7 +1008,9 @@ mod tests {
                 .unwrap()
             );
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(compression.is_none());
+            assert!(!tagged_certificates);
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -955,6 +1027,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            compression,
+            tagged_certificates,
         } = modern_handshake
         {
             assert!(!is_syncing);
@@ -982,7 +1056,9 @@ mod tests {
                 .unwrap()
             );
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(compression.is_none());
+            assert!(!tagged_certificates);
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -1002,6 +1078,53 @@ mod tests {
         assert_eq!(certificate, deserialized);
     }
 
+    #[test]
+    fn untagged_certificate_validates_for_peer_without_tagged_certificates() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+        let public_key = PublicKey::from(&secret_key);
+        let connection_id = ConnectionId::random(&mut rng);
+        // Nodes not supporting tagged certificates sign the bare connection ID.
+        let untagged_certificate = ConsensusCertificate {
+            signature: crypto::sign(connection_id.as_bytes(), &secret_key, &public_key),
+            public_key: public_key.clone(),
+        };
+
+        assert_eq!(
+            untagged_certificate
+                .clone()
+                .validate(connection_id, false)
+                .expect("untagged certificate should be valid"),
+            public_key
+        );
+        assert!(untagged_certificate.validate(connection_id, true).is_err());
+
+        let tagged_certificate = ConsensusCertificate::random(&mut rng);
+        assert!(tagged_certificate
+            .validate(ConnectionId::random(&mut rng), false)
+            .is_err());
+    }
+
+    #[test]
+    fn tagged_certificate_validates() {
+        let mut rng = crate::new_rng();
+        let connection_id = ConnectionId::random(&mut rng);
+        let signer = LocalSigner::new(Arc::new(SecretKey::random(&mut rng)));
+        let public_key = signer.public_key().clone();
+        let certificate =
+            ConsensusCertificate::create(connection_id, &NodeKeyPair::new(Arc::new(signer)))
+                .expect("should sign connection id");
+
+        assert_eq!(
+            certificate
+                .clone()
+                .validate(connection_id, true)
+                .expect("tagged certificate should be valid"),
+            public_key
+        );
+        assert!(certificate.validate(connection_id, false).is_err());
+    }
+
     #[test]
     fn serde_json_roundtrip_certificate() {
         roundtrip_certificate(true)
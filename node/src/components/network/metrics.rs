@@ -105,6 +105,15 @@ pub(super) struct Metrics {
     /// Count of incoming messages with other payload.
     pub(super) in_count_other: IntCounter,
 
+    /// Count of outgoing messages that were sent compressed.
+    pub(super) out_count_compressed: IntCounter,
+    /// Volume in bytes saved on outgoing messages by compression.
+    pub(super) out_bytes_compression_saved: IntCounter,
+    /// Count of incoming messages that were received compressed.
+    pub(super) in_count_compressed: IntCounter,
+    /// Volume in bytes saved on incoming messages by compression.
+    pub(super) in_bytes_compression_saved: IntCounter,
+
     /// Number of trie requests accepted for processing.
     pub(super) requests_for_trie_accepted: IntCounter,
     /// Number of trie requests finished (successful or unsuccessful).
@@ -319,6 +328,23 @@ impl Metrics {
             "volume in bytes of incoming messages with other payload",
         )?;
 
+        let out_count_compressed = IntCounter::new(
+            "net_out_count_compressed",
+            "count of outgoing messages that were sent compressed",
+        )?;
+        let out_bytes_compression_saved = IntCounter::new(
+            "net_out_bytes_compression_saved",
+            "volume in bytes saved on outgoing messages by compression",
+        )?;
+        let in_count_compressed = IntCounter::new(
+            "net_in_count_compressed",
+            "count of incoming messages that were received compressed",
+        )?;
+        let in_bytes_compression_saved = IntCounter::new(
+            "net_in_bytes_compression_saved",
+            "volume in bytes saved on incoming messages by compression",
+        )?;
+
         let requests_for_trie_accepted = IntCounter::new(
             "requests_for_trie_accepted",
             "number of trie requests accepted for processing",
@@ -392,6 +418,11 @@ impl Metrics {
         registry.register(Box::new(in_bytes_trie_transfer.clone()))?;
         registry.register(Box::new(in_bytes_other.clone()))?;
 
+        registry.register(Box::new(out_count_compressed.clone()))?;
+        registry.register(Box::new(out_bytes_compression_saved.clone()))?;
+        registry.register(Box::new(in_count_compressed.clone()))?;
+        registry.register(Box::new(in_bytes_compression_saved.clone()))?;
+
         registry.register(Box::new(requests_for_trie_accepted.clone()))?;
         registry.register(Box::new(requests_for_trie_finished.clone()))?;
 
@@ -448,6 +479,10 @@ impl Metrics {
             in_bytes_block_transfer,
             in_bytes_trie_transfer,
             in_bytes_other,
+            out_count_compressed,
+            out_bytes_compression_saved,
+            in_count_compressed,
+            in_bytes_compression_saved,
             requests_for_trie_accepted,
             requests_for_trie_finished,
             accumulated_outgoing_limiter_delay,
@@ -556,6 +591,40 @@ impl Metrics {
         }
     }
 
+    /// Records an outgoing message that was compressed from `original_size` to `compressed_size`
+    /// bytes.
+    pub(super) fn record_compression_out(
+        this: &Weak<Self>,
+        original_size: usize,
+        compressed_size: usize,
+    ) {
+        if let Some(metrics) = this.upgrade() {
+            metrics.out_count_compressed.inc();
+            metrics
+                .out_bytes_compression_saved
+                .inc_by(original_size.saturating_sub(compressed_size) as u64);
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records an incoming message that was decompressed from `compressed_size` to
+    /// `original_size` bytes.
+    pub(super) fn record_compression_in(
+        this: &Weak<Self>,
+        original_size: usize,
+        compressed_size: usize,
+    ) {
+        if let Some(metrics) = this.upgrade() {
+            metrics.in_count_compressed.inc();
+            metrics
+                .in_bytes_compression_saved
+                .inc_by(original_size.saturating_sub(compressed_size) as u64);
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
     /// Creates a set of outgoing metrics that is connected to this set of metrics.
     pub(super) fn create_outgoing_metrics(&self) -> OutgoingMetrics {
         OutgoingMetrics {
@@ -643,6 +712,11 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.in_bytes_trie_transfer);
         unregister_metric!(self.registry, self.in_bytes_other);
 
+        unregister_metric!(self.registry, self.out_count_compressed);
+        unregister_metric!(self.registry, self.out_bytes_compression_saved);
+        unregister_metric!(self.registry, self.in_count_compressed);
+        unregister_metric!(self.registry, self.in_bytes_compression_saved);
+
         unregister_metric!(self.registry, self.requests_for_trie_accepted);
         unregister_metric!(self.registry, self.requests_for_trie_finished);
 
@@ -0,0 +1,351 @@
+//! Persistent store of peer addresses.
+//!
+//! The outgoing manager forgets everything it learned about other peers when the node shuts down,
+//! leaving a restarted node with nothing but the configured known addresses to connect to. The
+//! peer store keeps a small on-disk record of addresses we successfully completed a handshake
+//! with, which are used as additional dial candidates on startup.
+//!
+//! Records are aged out once a peer has not been seen for longer than the configured maximum age,
+//! and the least recently seen peers are evicted if the store grows beyond its maximum size.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use casper_types::{TimeDiff, Timestamp};
+
+use super::{blocklist::BlocklistJustification, config::PeerStoreConfig};
+
+/// Filename of the peer store, inside the storage directory.
+const PEER_STORE_FILENAME: &str = "peer_store.json";
+
+/// Minimum interval between two writes of the peer store to disk.
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Number of blocklist entries kept per peer.
+const MAX_BLOCKLIST_HISTORY: usize = 8;
+
+/// A past blocking of a peer.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub(super) struct BlocklistEntry {
+    /// When the peer was blocked.
+    blocked_at: Timestamp,
+    /// The justification given for blocking.
+    justification: String,
+}
+
+/// Everything known about a single peer address.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub(super) struct PeerRecord {
+    /// When a handshake with the peer was last completed.
+    last_seen: Timestamp,
+    /// Number of successful connection attempts.
+    success_count: u32,
+    /// Number of failed connection attempts.
+    failure_count: u32,
+    /// The most recent times the peer was blocked, oldest first.
+    blocklist_history: Vec<BlocklistEntry>,
+}
+
+impl PeerRecord {
+    /// Returns when the peer was most recently blocked, if ever.
+    fn last_blocked(&self) -> Option<Timestamp> {
+        self.blocklist_history.last().map(|entry| entry.blocked_at)
+    }
+}
+
+/// On-disk store of peers we successfully connected to.
+#[derive(DataSize, Debug)]
+pub(super) struct PeerStore {
+    /// Path of the file backing the store.
+    path: PathBuf,
+    /// Time after which a peer that has not been seen is dropped.
+    max_age: TimeDiff,
+    /// Maximum number of peers kept.
+    max_size: usize,
+    /// The records, by address.
+    peers: BTreeMap<SocketAddr, PeerRecord>,
+    /// Whether or not the records changed since they were last written to disk.
+    dirty: bool,
+    /// When the records were last written to disk.
+    last_saved: Instant,
+}
+
+impl PeerStore {
+    /// Loads the peer store from the given directory.
+    ///
+    /// A missing or unreadable file results in an empty store, since losing the peer store only
+    /// costs us some dial candidates.
+    pub(super) fn load(dir: &Path, config: &PeerStoreConfig, now: Timestamp) -> Self {
+        let path = dir.join(PEER_STORE_FILENAME);
+        let peers = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|error| {
+                warn!(file = %path.display(), %error, "failed to parse peer store");
+                BTreeMap::new()
+            }),
+            Err(error) => {
+                if path.exists() {
+                    warn!(file = %path.display(), %error, "failed to read peer store");
+                }
+                BTreeMap::new()
+            }
+        };
+
+        let mut peer_store = PeerStore {
+            path,
+            max_age: config.max_age,
+            max_size: config.max_size as usize,
+            peers,
+            dirty: false,
+            last_saved: Instant::now(),
+        };
+        peer_store.age_out(now);
+        debug!(peer_count = peer_store.peers.len(), "loaded peer store");
+        peer_store
+    }
+
+    /// Returns the addresses worth dialing on startup, most recently seen first.
+    ///
+    /// Peers that were blocked within `block_duration` are skipped, as they would most likely
+    /// still be blocked by their previous session.
+    pub(super) fn dial_candidates(
+        &self,
+        now: Timestamp,
+        block_duration: TimeDiff,
+    ) -> Vec<SocketAddr> {
+        let mut candidates: Vec<_> = self
+            .peers
+            .iter()
+            .filter(|(_, record)| {
+                record
+                    .last_blocked()
+                    .is_none_or(|blocked_at| blocked_at + block_duration <= now)
+            })
+            .collect();
+        candidates.sort_by(|(_, record1), (_, record2)| record2.last_seen.cmp(&record1.last_seen));
+        candidates.into_iter().map(|(addr, _)| *addr).collect()
+    }
+
+    /// Records a successful connection to a peer, including a completed handshake.
+    pub(super) fn record_success(&mut self, addr: SocketAddr, now: Timestamp) {
+        let record = self.peers.entry(addr).or_insert_with(|| PeerRecord {
+            last_seen: now,
+            success_count: 0,
+            failure_count: 0,
+            blocklist_history: Vec::new(),
+        });
+        record.last_seen = now;
+        record.success_count = record.success_count.saturating_add(1);
+        self.dirty = true;
+    }
+
+    /// Records a failed connection attempt to a peer.
+    ///
+    /// Addresses we never successfully connected to are not recorded.
+    pub(super) fn record_failure(&mut self, addr: SocketAddr) {
+        if let Some(record) = self.peers.get_mut(&addr) {
+            record.failure_count = record.failure_count.saturating_add(1);
+            self.dirty = true;
+        }
+    }
+
+    /// Records the blocking of a peer.
+    ///
+    /// Addresses we never successfully connected to are not recorded.
+    pub(super) fn record_block(
+        &mut self,
+        addr: SocketAddr,
+        justification: &BlocklistJustification,
+        now: Timestamp,
+    ) {
+        if let Some(record) = self.peers.get_mut(&addr) {
+            if record.blocklist_history.len() >= MAX_BLOCKLIST_HISTORY {
+                record.blocklist_history.remove(0);
+            }
+            record.blocklist_history.push(BlocklistEntry {
+                blocked_at: now,
+                justification: justification.to_string(),
+            });
+            self.dirty = true;
+        }
+    }
+
+    /// Removes a peer from the store, e.g. because the address turned out to be our own.
+    pub(super) fn forget(&mut self, addr: SocketAddr) {
+        if self.peers.remove(&addr).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    /// Ages out stale peers and writes the store to disk if it changed and was not written
+    /// recently.
+    pub(super) fn perform_housekeeping(&mut self, now: Timestamp, now_instant: Instant) {
+        if now_instant.saturating_duration_since(self.last_saved) < SAVE_INTERVAL {
+            return;
+        }
+        self.age_out(now);
+        self.last_saved = now_instant;
+        self.save();
+    }
+
+    /// Removes peers not seen within the maximum age, then evicts the least recently seen peers
+    /// until the store fits its maximum size.
+    fn age_out(&mut self, now: Timestamp) {
+        let count_before = self.peers.len();
+        let max_age = self.max_age;
+        self.peers
+            .retain(|_, record| record.last_seen + max_age >= now);
+
+        if self.peers.len() > self.max_size {
+            let mut by_recency: Vec<_> = self
+                .peers
+                .iter()
+                .map(|(addr, record)| (record.last_seen, *addr))
+                .collect();
+            by_recency.sort_unstable_by(|entry1, entry2| entry2.cmp(entry1));
+            for (_, addr) in by_recency.into_iter().skip(self.max_size) {
+                self.peers.remove(&addr);
+            }
+        }
+
+        if self.peers.len() != count_before {
+            debug!(
+                removed = count_before - self.peers.len(),
+                "aged out peers from peer store"
+            );
+            self.dirty = true;
+        }
+    }
+
+    /// Writes the store to disk if it changed since it was last written.
+    fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let bytes = match serde_json::to_vec_pretty(&self.peers) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                warn!(%error, "failed to serialize peer store");
+                return;
+            }
+        };
+        // Write to a temporary file first, so a crash while writing does not corrupt the store.
+        let temp_path = self.path.with_extension("json.tmp");
+        match fs::write(&temp_path, bytes).and_then(|()| fs::rename(&temp_path, &self.path)) {
+            Ok(()) => {
+                debug!(file = %self.path.display(), peer_count = self.peers.len(), "saved peer store");
+                self.dirty = false;
+            }
+            Err(error) => warn!(file = %self.path.display(), %error, "failed to write peer store"),
+        }
+    }
+}
+
+impl Drop for PeerStore {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max_size: u32) -> PeerStoreConfig {
+        PeerStoreConfig {
+            enabled: true,
+            max_age: TimeDiff::from_seconds(100),
+            max_size,
+        }
+    }
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    #[test]
+    fn should_persist_peers() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let now = Timestamp::from(1_000_000);
+
+        let mut peer_store = PeerStore::load(tempdir.path(), &config(10), now);
+        peer_store.record_success(addr(1), now);
+        peer_store.record_success(addr(1), now);
+        peer_store.record_failure(addr(1));
+        // Failures of addresses never connected to are not recorded.
+        peer_store.record_failure(addr(2));
+        peer_store.record_block(addr(1), &BlocklistJustification::DishonestPeer, now);
+        let expected = peer_store.peers.clone();
+        drop(peer_store);
+
+        let peer_store = PeerStore::load(tempdir.path(), &config(10), now);
+        assert_eq!(peer_store.peers, expected);
+        let record = &peer_store.peers[&addr(1)];
+        assert_eq!(record.success_count, 2);
+        assert_eq!(record.failure_count, 1);
+        assert_eq!(record.blocklist_history.len(), 1);
+        assert_eq!(peer_store.peers.len(), 1);
+    }
+
+    #[test]
+    fn should_age_out_peers() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let start = Timestamp::from(1_000_000);
+
+        let mut peer_store = PeerStore::load(tempdir.path(), &config(2), start);
+        for port in 1..=3 {
+            peer_store.record_success(addr(port), start + TimeDiff::from_seconds(port.into()));
+        }
+        peer_store.age_out(start + TimeDiff::from_seconds(3));
+        // The least recently seen peer is evicted to fit the maximum size.
+        assert_eq!(
+            peer_store.peers.keys().copied().collect::<Vec<_>>(),
+            vec![addr(2), addr(3)]
+        );
+
+        peer_store.age_out(start + TimeDiff::from_seconds(103));
+        assert_eq!(
+            peer_store.peers.keys().copied().collect::<Vec<_>>(),
+            vec![addr(3)]
+        );
+    }
+
+    #[test]
+    fn should_skip_recently_blocked_dial_candidates() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let now = Timestamp::from(1_000_000);
+        let block_duration = TimeDiff::from_seconds(10);
+
+        let mut peer_store = PeerStore::load(tempdir.path(), &config(10), now);
+        peer_store.record_success(addr(1), now);
+        peer_store.record_success(addr(2), now + TimeDiff::from_seconds(1));
+        peer_store.record_block(addr(1), &BlocklistJustification::DishonestPeer, now);
+        assert_eq!(
+            peer_store.dial_candidates(now, block_duration),
+            vec![addr(2)]
+        );
+
+        let later = now + block_duration;
+        assert_eq!(
+            peer_store.dial_candidates(later, block_duration),
+            vec![addr(2), addr(1)]
+        );
+    }
+
+    #[test]
+    fn should_start_empty_from_unparsable_file() {
+        let tempdir = tempfile::tempdir().unwrap();
+        fs::write(tempdir.path().join(PEER_STORE_FILENAME), b"not json").unwrap();
+
+        let peer_store = PeerStore::load(tempdir.path(), &config(10), Timestamp::from(0));
+        assert!(peer_store.peers.is_empty());
+    }
+}
@@ -43,12 +43,13 @@ use casper_types::{ProtocolVersion, PublicKey, TimeDiff};
 
 use super::{
     chain_info::ChainInfo,
+    compression::{Compression, CompressionSettings},
     counting_format::{ConnectionId, Role},
     error::{ConnectionError, IoError},
     event::{IncomingConnection, OutgoingConnection},
     full_transport,
     limiter::LimiterHandle,
-    message::NodeKeyPair,
+    message::{ConsensusCertificate, NodeKeyPair},
     message_pack_format::MessagePackFormat,
     EstimatorWeights, Event, FramedTransport, FullTransport, Identity, Message, Metrics, Payload,
     Transport,
@@ -81,6 +82,8 @@ struct HandshakeOutcome {
     peer_consensus_public_key: Option<PublicKey>,
     /// Holds the information whether the remote node is syncing.
     is_peer_syncing: bool,
+    /// The compression negotiated with the peer, if any.
+    compression: Option<Compression>,
 }
 
 /// Low-level TLS connection function.
@@ -163,6 +166,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            compression,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("consensus_key", field::display(public_key));
@@ -179,6 +183,8 @@ where
                 connection_id,
                 framed_transport,
                 Role::Dialer,
+                context.compression_settings(compression),
+                context.chain_info.maximum_net_message_size,
             );
             let (sink, _stream) = full_transport.split();
 
@@ -238,6 +244,8 @@ where
     is_syncing: AtomicBool,
     /// If false, will not allow handshake.
     allow_handshake: bool,
+    /// Compression settings, if compression is offered to peers.
+    compression: Option<CompressionSettings>,
 }
 
 impl<REv> NetworkContext<REv> {
@@ -281,6 +289,10 @@ impl<REv> NetworkContext<REv> {
             max_in_flight_demands,
             is_syncing: AtomicBool::new(false),
             allow_handshake,
+            compression: cfg
+                .compression
+                .enabled
+                .then(|| CompressionSettings::new(&cfg.compression)),
         }
     }
 
@@ -315,6 +327,16 @@ impl<REv> NetworkContext<REv> {
         }
     }
 
+    /// The compression we offer to peers during the handshake, if any.
+    fn offered_compression(&self) -> Option<Compression> {
+        self.compression.as_ref().map(|_| Compression::Deflate)
+    }
+
+    /// The compression settings to use on a connection with the given negotiated compression.
+    fn compression_settings(&self, negotiated: Option<Compression>) -> Option<CompressionSettings> {
+        negotiated.and(self.compression.clone())
+    }
+
     pub(crate) fn network_ca(&self) -> Option<&Arc<X509>> {
         self.network_ca.as_ref()
     }
@@ -366,6 +388,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: _,
+            compression,
         }) => {
             if !context.allow_handshake {
                 return IncomingConnection::Failed {
@@ -385,6 +408,8 @@ where
                 connection_id,
                 framed_transport,
                 Role::Listener,
+                context.compression_settings(compression),
+                context.chain_info.maximum_net_message_size,
             );
 
             let (_sink, stream) = full_transport.split();
@@ -483,9 +508,8 @@ where
     // Manually encode a handshake.
     let handshake_message = context.chain_info.create_handshake::<P>(
         context.public_addr.expect("component not initialized"),
-        context.node_key_pair.as_ref(),
-        connection_id,
         context.is_syncing.load(Ordering::SeqCst),
+        context.offered_compression(),
     );
 
     let serialized_handshake_message = Pin::new(&mut encoder)
@@ -509,7 +533,7 @@ where
         .map_err(ConnectionError::HandshakeRecv)?;
 
     // Ensure the handshake was sent correctly.
-    let sink = handshake_send
+    let mut sink = handshake_send
         .await
         .map_err(ConnectionError::HandshakeSenderCrashed)?
         .map_err(ConnectionError::HandshakeSend)?;
@@ -525,6 +549,8 @@ where
         consensus_certificate,
         is_syncing,
         chainspec_hash,
+        compression,
+        tagged_certificates,
     } = remote_message
     {
         debug!(%protocol_version, "handshake received");
@@ -567,9 +593,53 @@ where
             return Err(ConnectionError::WrongChainspecHash(peer_chainspec_hash));
         }
 
-        let peer_consensus_public_key = consensus_certificate
-            .map(|cert| {
-                cert.validate(connection_id)
+        let peer_consensus_certificate = if tagged_certificates {
+            // Both nodes support tagged certificates, so they are exchanged only now, in the same
+            // manner as the handshakes.
+            let certificate_message = Message::<P>::Certificate {
+                consensus_certificate: context.node_key_pair.as_ref().and_then(|key_pair| {
+                    ConsensusCertificate::create(connection_id, key_pair)
+                        .map_err(|error| warn!(%error, "failed to create consensus certificate"))
+                        .ok()
+                }),
+            };
+            let serialized_certificate_message = Pin::new(&mut encoder)
+                .serialize(&Arc::new(certificate_message))
+                .map_err(ConnectionError::CouldNotEncodeOurHandshake)?;
+
+            let certificate_send =
+                tokio::spawn(io_timeout(context.handshake_timeout.into(), async move {
+                    sink.send(serialized_certificate_message).await?;
+                    Ok(sink)
+                }));
+
+            let remote_message_raw =
+                io_opt_timeout(context.handshake_timeout.into(), stream.next())
+                    .await
+                    .map_err(ConnectionError::HandshakeRecv)?;
+
+            sink = certificate_send
+                .await
+                .map_err(ConnectionError::HandshakeSenderCrashed)?
+                .map_err(ConnectionError::HandshakeSend)?;
+
+            let remote_message: Message<P> = Pin::new(&mut encoder)
+                .deserialize(&remote_message_raw)
+                .map_err(ConnectionError::InvalidRemoteHandshakeMessage)?;
+            match remote_message {
+                Message::Certificate {
+                    consensus_certificate,
+                } => consensus_certificate.map(|cert| (cert, true)),
+                _ => return Err(ConnectionError::DidNotSendCertificate),
+            }
+        } else {
+            // Peers not supporting tagged certificates send an untagged one in their handshake.
+            consensus_certificate.map(|cert| (cert, false))
+        };
+
+        let peer_consensus_public_key = peer_consensus_certificate
+            .map(|(cert, tagged)| {
+                cert.validate(connection_id, tagged)
                     .map_err(ConnectionError::InvalidConsensusCertificate)
             })
             .transpose()?;
@@ -583,6 +653,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            compression: Compression::negotiate(context.offered_compression(), compression),
         })
     } else {
         // Received a non-handshake, this is an error.
@@ -685,111 +756,110 @@ where
     let demands_in_flight = Arc::new(Semaphore::new(context.max_in_flight_demands));
     let event_queue = context.event_queue.expect("component not initialized");
 
-    let read_messages = async move {
-        while let Some(msg_result) = stream.next().await {
-            match msg_result {
-                Ok(msg) => {
-                    trace!(%msg, "message received");
-
-                    let effect_builder = EffectBuilder::new(event_queue);
-
-                    match msg.try_into_demand(effect_builder, peer_id) {
-                        Ok((event, wait_for_response)) => {
-                            // Note: For now, demands bypass the limiter, as we expect the
-                            //       backpressure to handle this instead.
-
-                            // Acquire a permit. If we are handling too many demands at this
-                            // time, this will block, halting the processing of new message,
-                            // thus letting the peer they have reached their maximum allowance.
-                            let in_flight = demands_in_flight
-                                .clone()
-                                .acquire_owned()
-                                .await
-                                // Note: Since the semaphore is reference counted, it must
-                                //       explicitly be closed for acquisition to fail, which we
-                                //       never do. If this happens, there is a bug in the code;
-                                //       we exit with an error and close the connection.
-                                .map_err(|_| {
-                                    io::Error::new(
-                                        io::ErrorKind::Other,
-                                        "demand limiter semaphore closed unexpectedly",
-                                    )
-                                })?;
-
-                            Metrics::record_trie_request_start(&context.net_metrics);
-
-                            let net_metrics = context.net_metrics.clone();
-                            // Spawn a future that will eventually send the returned message. It
-                            // will essentially buffer the response.
-                            tokio::spawn(async move {
-                                if let Some(payload) = wait_for_response.await {
-                                    // Send message and await its return. `send_message` should
-                                    // only return when the message has been buffered, if the
-                                    // peer is not accepting data, we will block here until the
-                                    // send buffer has sufficient room.
-                                    effect_builder.send_message(peer_id, payload).await;
-
-                                    // Note: We could short-circuit the event queue here and
-                                    //       directly insert into the outgoing message queue,
-                                    //       which may be potential performance improvement.
-                                }
-
-                                // Missing else: The handler of the demand did not deem it
-                                // worthy a response. Just drop it.
-
-                                // After we have either successfully buffered the message for
-                                // sending, failed to do so or did not have a message to send
-                                // out, we consider the request handled and free up the permit.
-                                Metrics::record_trie_request_end(&net_metrics);
-                                drop(in_flight);
-                            });
-
-                            // Schedule the created event.
-                            event_queue
-                                .schedule::<REv>(event, QueueKind::NetworkDemand)
-                                .await;
-                        }
-                        Err(msg) => {
-                            // We've received a non-demand message. Ensure we have the proper amount
-                            // of resources, then push it to the reactor.
-                            limiter
-                                .request_allowance(
-                                    msg.payload_incoming_resource_estimate(
+    let read_messages =
+        async move {
+            while let Some(msg_result) = stream.next().await {
+                match msg_result {
+                    Ok(msg) => {
+                        trace!(%msg, "message received");
+
+                        let effect_builder = EffectBuilder::new(event_queue);
+
+                        match msg.try_into_demand(effect_builder, peer_id) {
+                            Ok((event, wait_for_response)) => {
+                                // Note: For now, demands bypass the limiter, as we expect the
+                                //       backpressure to handle this instead.
+
+                                // Acquire a permit. If we are handling too many demands at this
+                                // time, this will block, halting the processing of new message,
+                                // thus letting the peer they have reached their maximum allowance.
+                                let in_flight = demands_in_flight
+                                    .clone()
+                                    .acquire_owned()
+                                    .await
+                                    // Note: Since the semaphore is reference counted, it must
+                                    //       explicitly be closed for acquisition to fail, which we
+                                    //       never do. If this happens, there is a bug in the code;
+                                    //       we exit with an error and close the connection.
+                                    .map_err(|_| {
+                                        io::Error::new(
+                                            io::ErrorKind::Other,
+                                            "demand limiter semaphore closed unexpectedly",
+                                        )
+                                    })?;
+
+                                Metrics::record_trie_request_start(&context.net_metrics);
+
+                                let net_metrics = context.net_metrics.clone();
+                                // Spawn a future that will eventually send the returned message. It
+                                // will essentially buffer the response.
+                                tokio::spawn(async move {
+                                    if let Some(payload) = wait_for_response.await {
+                                        // Send message and await its return. `send_message` should
+                                        // only return when the message has been buffered, if the
+                                        // peer is not accepting data, we will block here until the
+                                        // send buffer has sufficient room.
+                                        effect_builder.send_message(peer_id, payload).await;
+
+                                        // Note: We could short-circuit the event queue here and
+                                        //       directly insert into the outgoing message queue,
+                                        //       which may be potential performance improvement.
+                                    }
+
+                                    // Missing else: The handler of the demand did not deem it
+                                    // worthy a response. Just drop it.
+
+                                    // After we have either successfully buffered the message for
+                                    // sending, failed to do so or did not have a message to send
+                                    // out, we consider the request handled and free up the permit.
+                                    Metrics::record_trie_request_end(&net_metrics);
+                                    drop(in_flight);
+                                });
+
+                                // Schedule the created event.
+                                event_queue
+                                    .schedule::<REv>(event, QueueKind::NetworkDemand)
+                                    .await;
+                            }
+                            Err(msg) => {
+                                // We've received a non-demand message. Ensure we have the proper amount
+                                // of resources, then push it to the reactor.
+                                limiter
+                                    .request_allowance(msg.payload_incoming_resource_estimate(
                                         &context.payload_weights,
-                                    ),
-                                )
-                                .await;
-
-                            let queue_kind = if msg.is_low_priority() {
-                                QueueKind::NetworkLowPriority
-                            } else {
-                                QueueKind::NetworkIncoming
-                            };
-
-                            event_queue
-                                .schedule(
-                                    Event::IncomingMessage {
-                                        peer_id: Box::new(peer_id),
-                                        msg,
-                                        span: span.clone(),
-                                    },
-                                    queue_kind,
-                                )
-                                .await;
+                                    ))
+                                    .await;
+
+                                let queue_kind = if msg.is_low_priority() {
+                                    QueueKind::NetworkLowPriority
+                                } else {
+                                    QueueKind::NetworkIncoming
+                                };
+
+                                event_queue
+                                    .schedule(
+                                        Event::IncomingMessage {
+                                            peer_id: Box::new(peer_id),
+                                            msg,
+                                            span: span.clone(),
+                                        },
+                                        queue_kind,
+                                    )
+                                    .await;
+                            }
                         }
                     }
-                }
-                Err(err) => {
-                    warn!(
-                        err = display_error(&err),
-                        "receiving message failed, closing connection"
-                    );
-                    return Err(err);
+                    Err(err) => {
+                        warn!(
+                            err = display_error(&err),
+                            "receiving message failed, closing connection"
+                        );
+                        return Err(err);
+                    }
                 }
             }
-        }
-        Ok(())
-    };
+            Ok(())
+        };
 
     let shutdown_messages = async move { while close_incoming_receiver.changed().await.is_ok() {} };
 
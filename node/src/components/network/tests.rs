@@ -6,6 +6,8 @@
 use std::{
     collections::{HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
+    iter,
+    net::{Ipv4Addr, SocketAddr},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -18,7 +20,7 @@ use serde::{Deserialize, Serialize};
 use smallvec::smallvec;
 use tracing::{debug, info};
 
-use casper_types::{Chainspec, ChainspecRawBytes, SecretKey};
+use casper_types::{Chainspec, ChainspecRawBytes, EraId, PublicKey, SecretKey};
 
 use super::{
     chain_info::ChainInfo, Event as NetworkEvent, FromIncoming, GossipedAddress, Identity,
@@ -36,16 +38,17 @@ use crate::{
             BeginGossipRequest, ChainspecRawBytesRequest, ContractRuntimeRequest, NetworkRequest,
             StorageRequest,
         },
-        EffectBuilder, Effects,
+        EffectBuilder, EffectExt, Effects,
     },
     protocol,
     reactor::{self, main_reactor::Config, EventQueueHandle, Finalize, Reactor, Runner},
+    signer::{LocalSigner, ValidatorSigner},
     testing::{
         self, init_logging,
         network::{NetworkedReactor, Nodes, TestingNetwork},
         ConditionCheckReactor,
     },
-    types::{NodeId, SyncHandling, ValidatorMatrix},
+    types::{NodeId, PeerReputation, SyncHandling, ValidatorMatrix},
     NodeRng,
 };
 
@@ -68,6 +71,8 @@ enum Event {
     AddressGossiperIncoming(GossiperIncoming<GossipedAddress>),
     #[from]
     BlocklistAnnouncement(PeerBehaviorAnnouncement),
+    /// An incoming network message with a consensus message.
+    ConsensusIncoming { sender: NodeId, id: u64 },
 }
 
 impl ReactorEvent for Event {
@@ -129,6 +134,7 @@ impl FromIncoming<Message> for Event {
                 sender,
                 message: Box::new(message),
             }),
+            Message::Consensus(id) => Event::ConsensusIncoming { sender, id },
         }
     }
 }
@@ -143,6 +149,8 @@ impl Display for Event {
 enum Message {
     #[from]
     AddressGossiper(gossiper::Message<GossipedAddress>),
+    /// A stand-in for a consensus message, identified by a number.
+    Consensus(u64),
 }
 
 impl Display for Message {
@@ -156,6 +164,7 @@ impl Payload for Message {
     fn message_kind(&self) -> MessageKind {
         match self {
             Message::AddressGossiper(_) => MessageKind::AddressGossip,
+            Message::Consensus(_) => MessageKind::Consensus,
         }
     }
 
@@ -168,6 +177,26 @@ impl Payload for Message {
     }
 }
 
+/// Test reactor configuration.
+#[derive(Clone, Debug, Default)]
+struct TestReactorConfig {
+    /// The main reactor configuration, of which only the network configuration is used.
+    main: Config,
+    /// The consensus key of the node, if it takes part in the handshake as a validator.
+    consensus_key: Option<Arc<SecretKey>>,
+    /// The other validators of era 0.
+    validators: Vec<PublicKey>,
+}
+
+impl From<Config> for TestReactorConfig {
+    fn from(main: Config) -> Self {
+        TestReactorConfig {
+            main,
+            ..Default::default()
+        }
+    }
+}
+
 /// Test reactor.
 ///
 /// Runs a single network.
@@ -175,11 +204,15 @@ impl Payload for Message {
 struct TestReactor {
     net: Network<Event, Message>,
     address_gossiper: Gossiper<{ GossipedAddress::ID_IS_COMPLETE_ITEM }, GossipedAddress>,
+    /// Whether the node ever started gossiping its own address.
+    gossiped_own_address: bool,
+    /// The consensus messages received, along with their senders.
+    consensus_messages: Vec<(NodeId, u64)>,
 }
 
 impl Reactor for TestReactor {
     type Event = Event;
-    type Config = Config;
+    type Config = TestReactorConfig;
     type Error = anyhow::Error;
 
     fn dispatch_event(
@@ -227,17 +260,24 @@ impl Reactor for TestReactor {
                 // Addresses shouldn't have an item body when gossiped.
                 Effects::new()
             }
-            Event::BeginAddressGossipRequest(ev) => reactor::wrap_effects(
-                Event::AddressGossiper,
-                self.address_gossiper
-                    .handle_event(effect_builder, rng, ev.into()),
-            ),
+            Event::BeginAddressGossipRequest(ev) => {
+                self.gossiped_own_address = true;
+                reactor::wrap_effects(
+                    Event::AddressGossiper,
+                    self.address_gossiper
+                        .handle_event(effect_builder, rng, ev.into()),
+                )
+            }
             Event::AddressGossiperIncoming(incoming) => reactor::wrap_effects(
                 Event::AddressGossiper,
                 self.address_gossiper
                     .handle_event(effect_builder, rng, incoming.into()),
             ),
             Event::BlocklistAnnouncement(_announcement) => Effects::new(),
+            Event::ConsensusIncoming { sender, id } => {
+                self.consensus_messages.push((sender, id));
+                Effects::new()
+            }
         }
     }
 
@@ -250,16 +290,28 @@ impl Reactor for TestReactor {
         _event_queue: EventQueueHandle<Self::Event>,
         rng: &mut NodeRng,
     ) -> anyhow::Result<(Self, Effects<Self::Event>)> {
-        let secret_key = SecretKey::random(rng);
-        let allow_handshake = cfg.node.sync_handling != SyncHandling::Isolated;
+        let secret_key = cfg
+            .consensus_key
+            .clone()
+            .unwrap_or_else(|| Arc::new(SecretKey::random(rng)));
+        let validators = cfg
+            .validators
+            .into_iter()
+            .chain(iter::once(PublicKey::from(&*secret_key)));
+        let node_key_pair = cfg
+            .consensus_key
+            .map(|key| -> Arc<dyn ValidatorSigner> { Arc::new(LocalSigner::new(key)) });
+        let allow_handshake = cfg.main.node.sync_handling != SyncHandling::Isolated;
         let mut net = Network::new(
-            cfg.network.clone(),
+            cfg.main.network.clone(),
             our_identity,
-            None,
+            node_key_pair,
             registry,
             ChainInfo::create_for_testing(),
-            ValidatorMatrix::new_with_validator(Arc::new(secret_key)),
+            ValidatorMatrix::new_with_validators(secret_key, validators),
+            PeerReputation::new(),
             allow_handshake,
+            None,
         )?;
         let gossiper_config = gossiper::Config::new_with_small_timeouts();
         let address_gossiper = Gossiper::<{ GossipedAddress::ID_IS_COMPLETE_ITEM }, _>::new(
@@ -275,6 +327,8 @@ impl Reactor for TestReactor {
             TestReactor {
                 net,
                 address_gossiper,
+                gossiped_own_address: false,
+                consensus_messages: Vec::new(),
             },
             effects,
         ))
@@ -350,18 +404,20 @@ async fn run_two_node_network_five_times() {
     for i in 0..5 {
         info!("two-network test round {}", i);
 
-        let mut net = TestingNetwork::new();
+        let mut net = TestingNetwork::<TestReactor>::new();
 
         let start = Instant::now();
 
         let cfg = Config::default().with_network_config(
             network::Config::default_local_net_first_node(first_node_port),
         );
-        net.add_node_with_config(cfg, &mut rng).await.unwrap();
+        net.add_node_with_config(cfg.into(), &mut rng)
+            .await
+            .unwrap();
 
         let cfg = Config::default()
             .with_network_config(network::Config::default_local_net(first_node_port));
-        net.add_node_with_config(cfg.clone(), &mut rng)
+        net.add_node_with_config(cfg.into(), &mut rng)
             .await
             .unwrap();
         let end = Instant::now();
@@ -425,7 +481,9 @@ async fn bind_to_real_network_interface() {
         Config::default().with_network_config(network::Config::new((local_addr, port).into()));
 
     let mut net = TestingNetwork::<TestReactor>::new();
-    net.add_node_with_config(cfg, &mut rng).await.unwrap();
+    net.add_node_with_config(cfg.into(), &mut rng)
+        .await
+        .unwrap();
 
     // The network should be fully connected.
     let timeout = Duration::from_secs(2);
@@ -451,7 +509,7 @@ async fn check_varying_size_network_connects() {
     for &number_of_nodes in &[2u16, 3, 5, 9, 15] {
         let timeout = Duration::from_secs(3 * number_of_nodes as u64);
 
-        let mut net = TestingNetwork::new();
+        let mut net = TestingNetwork::<TestReactor>::new();
 
         // Pick a random port in the higher ranges that is likely to be unused.
         let first_node_port = testing::unused_port_on_localhost();
@@ -459,12 +517,15 @@ async fn check_varying_size_network_connects() {
             network::Config::default_local_net_first_node(first_node_port),
         );
 
-        let _ = net.add_node_with_config(cfg, &mut rng).await.unwrap();
+        let _ = net
+            .add_node_with_config(cfg.into(), &mut rng)
+            .await
+            .unwrap();
         let cfg = Config::default()
             .with_network_config(network::Config::default_local_net(first_node_port));
 
         for _ in 1..number_of_nodes {
-            net.add_node_with_config(cfg.clone(), &mut rng)
+            net.add_node_with_config(cfg.clone().into(), &mut rng)
                 .await
                 .unwrap();
         }
@@ -503,7 +564,7 @@ async fn ensure_peers_metric_is_correct() {
     for &number_of_nodes in &[2u16, 3, 5] {
         let timeout = Duration::from_secs(3 * number_of_nodes as u64);
 
-        let mut net = TestingNetwork::new();
+        let mut net = TestingNetwork::<TestReactor>::new();
 
         // Pick a random port in the higher ranges that is likely to be unused.
         let first_node_port = testing::unused_port_on_localhost();
@@ -512,13 +573,16 @@ async fn ensure_peers_metric_is_correct() {
             network::Config::default_local_net_first_node(first_node_port),
         );
 
-        let _ = net.add_node_with_config(cfg, &mut rng).await.unwrap();
+        let _ = net
+            .add_node_with_config(cfg.into(), &mut rng)
+            .await
+            .unwrap();
 
         let cfg = Config::default()
             .with_network_config(network::Config::default_local_net(first_node_port));
 
         for _ in 1..number_of_nodes {
-            net.add_node_with_config(cfg.clone(), &mut rng)
+            net.add_node_with_config(cfg.clone().into(), &mut rng)
                 .await
                 .unwrap();
         }
@@ -538,3 +602,140 @@ async fn ensure_peers_metric_is_correct() {
         net.finalize().await;
     }
 }
+
+/// Checks that a private validator hidden behind a sentry exchanges consensus messages with the
+/// other validators through the sentry only, and never gossips its own address.
+#[tokio::test]
+async fn private_validator_communicates_through_sentry() {
+    init_logging();
+
+    let mut rng = crate::new_rng();
+
+    let sentry_addr = SocketAddr::from((Ipv4Addr::LOCALHOST, testing::unused_port_on_localhost()));
+    let private_addr = SocketAddr::from((Ipv4Addr::LOCALHOST, testing::unused_port_on_localhost()));
+
+    let private_key = Arc::new(SecretKey::random(&mut rng));
+    let validator_key = Arc::new(SecretKey::random(&mut rng));
+    let validators = vec![
+        PublicKey::from(&*private_key),
+        PublicKey::from(&*validator_key),
+    ];
+
+    let mut net = TestingNetwork::<TestReactor>::new();
+
+    // The private validator only knows and allows its sentry.
+    let mut network_cfg = network::Config::new(private_addr);
+    network_cfg.known_addresses = vec![sentry_addr.to_string()];
+    network_cfg.sentry.private_validator = true;
+    network_cfg.sentry.allowed_addresses = vec![sentry_addr.to_string()];
+    let cfg = TestReactorConfig {
+        main: Config::default().with_network_config(network_cfg),
+        consensus_key: Some(Arc::clone(&private_key)),
+        validators: validators.clone(),
+    };
+    let (private_id, _) = net.add_node_with_config(cfg, &mut rng).await.unwrap();
+
+    // The sentry knows the address of the private validator it protects.
+    let mut network_cfg = network::Config::new(sentry_addr);
+    network_cfg.known_addresses.push(private_addr.to_string());
+    network_cfg.sentry.protected_peer_ids = vec![private_id];
+    let cfg = TestReactorConfig {
+        main: Config::default().with_network_config(network_cfg),
+        consensus_key: None,
+        validators: validators.clone(),
+    };
+    let (sentry_id, _) = net.add_node_with_config(cfg, &mut rng).await.unwrap();
+
+    // A regular validator joins the network through the sentry.
+    let cfg = TestReactorConfig {
+        main: Config::default()
+            .with_network_config(network::Config::default_local_net(sentry_addr.port())),
+        consensus_key: Some(Arc::clone(&validator_key)),
+        validators,
+    };
+    let (validator_id, _) = net.add_node_with_config(cfg, &mut rng).await.unwrap();
+
+    let is_connected = |nodes: &Nodes<TestReactor>, from: NodeId, to: NodeId| {
+        nodes[&from]
+            .reactor()
+            .inner()
+            .net
+            .outgoing_manager
+            .get_route(to)
+            .is_some()
+    };
+    net.settle_on(
+        &mut rng,
+        |nodes| {
+            is_connected(nodes, private_id, sentry_id)
+                && is_connected(nodes, sentry_id, private_id)
+                && is_connected(nodes, sentry_id, validator_id)
+                && is_connected(nodes, validator_id, sentry_id)
+        },
+        Duration::from_secs(30),
+    )
+    .await;
+
+    // The consensus messages of the private validator reach the validators through the sentry.
+    net.process_injected_effect_on(&private_id, |effect_builder| {
+        effect_builder
+            .broadcast_message_to_validators(Message::Consensus(1), EraId::new(0))
+            .ignore()
+    })
+    .await;
+    net.settle_on(
+        &mut rng,
+        |nodes| {
+            nodes[&validator_id]
+                .reactor()
+                .inner()
+                .consensus_messages
+                .contains(&(sentry_id, 1))
+        },
+        Duration::from_secs(10),
+    )
+    .await;
+
+    // The consensus messages of the validators reach the private validator through the sentry.
+    net.process_injected_effect_on(&validator_id, |effect_builder| {
+        effect_builder
+            .send_message(sentry_id, Message::Consensus(2))
+            .ignore()
+    })
+    .await;
+    net.settle_on(
+        &mut rng,
+        |nodes| {
+            nodes[&private_id]
+                .reactor()
+                .inner()
+                .consensus_messages
+                .contains(&(sentry_id, 2))
+        },
+        Duration::from_secs(10),
+    )
+    .await;
+
+    // Once the other nodes have gossiped their addresses, the private validator would have done so
+    // as well.
+    net.settle_on(
+        &mut rng,
+        |nodes| {
+            nodes[&sentry_id].reactor().inner().gossiped_own_address
+                && nodes[&validator_id].reactor().inner().gossiped_own_address
+        },
+        Duration::from_secs(30),
+    )
+    .await;
+    let nodes = net.nodes();
+    assert!(!nodes[&private_id].reactor().inner().gossiped_own_address);
+
+    let validator = nodes[&validator_id].reactor().inner();
+    assert!(!validator.net.peers().contains_key(&private_id));
+    assert!(validator
+        .consensus_messages
+        .iter()
+        .all(|(sender, _)| *sender != private_id));
+
+    net.finalize().await;
+}
@@ -43,8 +43,9 @@ mod utils;
 use casper_storage::block_store::{
     lmdb::{IndexedLmdbBlockStore, LmdbBlockStore},
     types::{
-        ApprovalsHashes, BlockExecutionResults, BlockHashHeightAndEra, BlockHeight, BlockTransfers,
-        LatestSwitchBlock, StateStore, StateStoreKey, Tip, TransactionFinalizedApprovals,
+        ApprovalsHashes, BlockExecutionResults, BlockHashHeightAndEra, BlockHeight,
+        BlockTransactionInitiators, BlockTransfers, InitiatorTransactionsQuery, LatestSwitchBlock,
+        PrunableBlockData, StateStore, StateStoreKey, Tip, TransactionFinalizedApprovals,
     },
     BlockStoreError, BlockStoreProvider, BlockStoreTransaction, DataReader, DataWriter,
 };
@@ -109,7 +110,11 @@ use object_pool::ObjectPool;
 const COMPONENT_NAME: &str = "storage";
 
 /// Key under which completed blocks are to be stored.
-const COMPLETED_BLOCKS_STORAGE_KEY: &[u8] = b"completed_blocks_disjoint_sequences";
+pub(crate) const COMPLETED_BLOCKS_STORAGE_KEY: &[u8] = b"completed_blocks_disjoint_sequences";
+/// Key under which the height of the lowest block whose data has not been pruned is stored.
+const LOWEST_UNPRUNED_BLOCK_HEIGHT_STORAGE_KEY: &[u8] = b"lowest_unpruned_block_height";
+/// Maximum number of blocks pruned per `PruneBlocks` event, within a single database transaction.
+const PRUNING_BATCH_SIZE: u64 = 100;
 /// Name of the file created when initializing a force resync.
 const FORCE_RESYNC_FILE_NAME: &str = "force_resync";
 
@@ -136,6 +141,14 @@ pub struct Storage {
     key_block_height_for_activation_point: Option<u64>,
     /// Whether or not memory deduplication is enabled.
     enable_mem_deduplication: bool,
+    /// Whether or not the transactions of executed blocks are indexed by their initiators.
+    enable_initiator_transactions_index: bool,
+    /// The number of most recent eras for which block data is retained, if pruning is enabled.
+    pruning_retained_era_count: Option<u64>,
+    /// The height of the lowest block whose data has not been pruned.
+    lowest_unpruned_block_height: u64,
+    /// Whether a `PruneBlocks` event is pending.
+    pruning_scheduled: bool,
     /// An in-memory pool of already loaded serialized items.
     ///
     /// Keyed by serialized item ID, contains the serialized item.
@@ -211,7 +224,10 @@ where
                     }
                 }
             }
-            Event::MarkBlockCompletedRequest(req) => self.handle_mark_block_completed_request(req),
+            Event::MarkBlockCompletedRequest(req) => {
+                self.handle_mark_block_completed_request(effect_builder, req)
+            }
+            Event::PruneBlocks => self.handle_prune_blocks(effect_builder),
             Event::MakeBlockExecutableRequest(req) => {
                 let ret = self.make_executable_block(&req.block_hash);
                 match ret {
@@ -268,13 +284,7 @@ impl Storage {
 
         root = network_subdir;
 
-        // Calculate the upper bound for the memory map that is potentially used.
-        let total_size = config
-            .max_block_store_size
-            .saturating_add(config.max_deploy_store_size)
-            .saturating_add(config.max_deploy_metadata_store_size);
-
-        let block_store = LmdbBlockStore::new(root.as_path(), total_size)?;
+        let block_store = LmdbBlockStore::new(root.as_path(), config.max_total_size())?;
         let indexed_block_store =
             IndexedLmdbBlockStore::new(block_store, hard_reset_to_start_of_era, protocol_version)?;
 
@@ -287,6 +297,10 @@ impl Storage {
             activation_era,
             key_block_height_for_activation_point: None,
             enable_mem_deduplication: config.enable_mem_deduplication,
+            enable_initiator_transactions_index: config.enable_initiator_transactions_index,
+            pruning_retained_era_count: config.pruning_retained_era_count,
+            lowest_unpruned_block_height: 0,
+            pruning_scheduled: false,
             serialized_item_pool: ObjectPool::new(config.mem_pool_prune_interval),
             recent_era_count,
             max_ttl,
@@ -332,6 +346,15 @@ impl Storage {
 
         {
             let ro_txn = component.block_store.checkout_ro()?;
+            let maybe_lowest_unpruned: Option<Vec<u8>> = ro_txn.read(StateStoreKey::new(
+                Cow::Borrowed(LOWEST_UNPRUNED_BLOCK_HEIGHT_STORAGE_KEY),
+            ))?;
+            if let Some(raw) = maybe_lowest_unpruned {
+                let (lowest_unpruned_block_height, _) = u64::from_bytes(&raw)
+                    .map_err(FatalStorageError::UnexpectedDeserializationFailure)?;
+                component.lowest_unpruned_block_height = lowest_unpruned_block_height;
+            }
+
             let maybe_state_store: Option<Vec<u8>> = ro_txn.read(StateStoreKey::new(
                 Cow::Borrowed(COMPLETED_BLOCKS_STORAGE_KEY),
             ))?;
@@ -451,7 +474,7 @@ impl Storage {
                     .map_err(FatalStorageError::from)?
                     .read(id)
                     .map_err(FatalStorageError::from)?;
-                let fetch_response = FetchResponse::from_opt(id, opt_item);
+                let fetch_response = self.fetch_response_for_block_data(id, &id, opt_item)?;
 
                 Ok(self.update_pool_and_send(
                     effect_builder,
@@ -525,7 +548,8 @@ impl Storage {
                     .map_err(FatalStorageError::from)?
                     .read(item_id)
                     .map_err(FatalStorageError::from)?;
-                let fetch_response = FetchResponse::from_opt(item_id, opt_item);
+                let fetch_response =
+                    self.fetch_response_for_block_data(item_id, &item_id, opt_item)?;
 
                 Ok(self.update_pool_and_send(
                     effect_builder,
@@ -537,7 +561,9 @@ impl Storage {
             NetRequest::BlockExecutionResults(ref serialized_id) => {
                 let item_id = decode_item_id::<BlockExecutionResultsOrChunk>(serialized_id)?;
                 let opt_item = self.read_block_execution_results_or_chunk(&item_id)?;
-                let fetch_response = FetchResponse::from_opt(item_id, opt_item);
+                let block_hash = *item_id.block_hash();
+                let fetch_response =
+                    self.fetch_response_for_block_data(item_id, &block_hash, opt_item)?;
 
                 Ok(self.update_pool_and_send(
                     effect_builder,
@@ -730,6 +756,30 @@ impl Storage {
                     .respond(Some((transaction, Some(execution_info))))
                     .ignore()
             }
+            StorageRequest::GetTransactionsByInitiator {
+                initiator_addr,
+                from_height,
+                to_height,
+                start_after,
+                limit,
+                responder,
+            } => {
+                if !self.enable_initiator_transactions_index {
+                    return Ok(responder.respond(None).ignore());
+                }
+                let ro_txn = self.block_store.checkout_ro()?;
+                let entries: Option<Vec<(TransactionHash, BlockHashHeightAndEra)>> =
+                    ro_txn.read(InitiatorTransactionsQuery {
+                        initiator_addr: *initiator_addr,
+                        from_height,
+                        to_height,
+                        start_after,
+                        limit,
+                    })?;
+                responder
+                    .respond(Some(entries.unwrap_or_default()))
+                    .ignore()
+            }
             StorageRequest::IsTransactionStored {
                 transaction_id,
                 responder,
@@ -1040,6 +1090,22 @@ impl Storage {
         txn.read(*era_id).map_err(FatalStorageError::from)
     }
 
+    pub(crate) fn get_block_by_height(
+        &self,
+        block_height: u64,
+    ) -> Result<Option<Block>, FatalStorageError> {
+        let txn = self.block_store.checkout_ro()?;
+        txn.read(block_height).map_err(FatalStorageError::from)
+    }
+
+    pub(crate) fn get_execution_result(
+        &self,
+        transaction_hash: &TransactionHash,
+    ) -> Result<Option<ExecutionResult>, FatalStorageError> {
+        let txn = self.block_store.checkout_ro()?;
+        txn.read(*transaction_hash).map_err(FatalStorageError::from)
+    }
+
     /// Retrieves a set of transactions, along with their potential finalized approvals.
     #[allow(clippy::type_complexity)]
     fn get_transactions_with_finalized_approvals<'a>(
@@ -1133,6 +1199,26 @@ impl Storage {
 
         debug!("Utilization for block is {utilization}");
 
+        if self.enable_initiator_transactions_index {
+            let mut initiators = Vec::with_capacity(execution_results.len());
+            for transaction_hash in execution_results.keys() {
+                let maybe_transaction: Option<Transaction> = txn.read(*transaction_hash)?;
+                match maybe_transaction {
+                    Some(transaction) => {
+                        initiators.push((*transaction_hash, transaction.initiator_addr()))
+                    }
+                    None => debug!(
+                        %transaction_hash,
+                        "transaction not stored, skipping initiator transactions index"
+                    ),
+                }
+            }
+            let _ = txn.write(&BlockTransactionInitiators {
+                block_info,
+                initiators,
+            })?;
+        }
+
         let _ = txn.write(&BlockExecutionResults {
             block_info,
             exec_results: execution_results,
@@ -1154,20 +1240,50 @@ impl Storage {
     }
 
     /// Handles a [`BlockCompletedAnnouncement`].
-    fn handle_mark_block_completed_request(
+    ///
+    /// Once a switch block is complete, the blocks of the era which is no longer retained are
+    /// pruned, if pruning is enabled.
+    fn handle_mark_block_completed_request<REv>(
         &mut self,
+        effect_builder: EffectBuilder<REv>,
         MarkBlockCompletedRequest {
             block_height,
             responder,
         }: MarkBlockCompletedRequest,
     ) -> Result<Effects<Event>, FatalStorageError> {
         let is_new = self.mark_block_complete(block_height)?;
-        Ok(responder.respond(is_new).ignore())
+        let mut effects = responder.respond(is_new).ignore();
+        if is_new
+            && self.pruning_retained_era_count.is_some()
+            && !self.pruning_scheduled
+            && self
+                .read_block_header_by_height(block_height, false)?
+                .is_some_and(|header| header.is_switch_block())
+        {
+            effects.extend(self.schedule_pruning(effect_builder));
+        }
+        Ok(effects)
+    }
+
+    /// Schedules a [`Event::PruneBlocks`] if pruning is enabled and no such event is pending
+    /// already, so that there is never more than a single chain of pruning batches.
+    pub(crate) fn schedule_pruning<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Effects<Event> {
+        if self.pruning_retained_era_count.is_none() || self.pruning_scheduled {
+            return Effects::new();
+        }
+        self.pruning_scheduled = true;
+        effect_builder.immediately().event(|()| Event::PruneBlocks)
     }
 
     /// Marks the block at height `block_height` as complete by inserting it
     /// into the `completed_blocks` index and storing it to disk.
-    fn mark_block_complete(&mut self, block_height: u64) -> Result<bool, FatalStorageError> {
+    pub(crate) fn mark_block_complete(
+        &mut self,
+        block_height: u64,
+    ) -> Result<bool, FatalStorageError> {
         let is_new = self.completed_blocks.insert(block_height);
         if is_new {
             self.persist_completed_blocks()?;
@@ -1200,6 +1316,161 @@ impl Storage {
         rw_txn.commit().map_err(FatalStorageError::from)
     }
 
+    /// Handles a [`Event::PruneBlocks`], pruning the next batch of blocks and scheduling another
+    /// event if more remain, so that pruning doesn't hold up the handling of other events.
+    fn handle_prune_blocks<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Result<Effects<Event>, FatalStorageError> {
+        if self.prune_block_batch()? {
+            self.pruning_scheduled = true;
+            Ok(effect_builder.immediately().event(|()| Event::PruneBlocks))
+        } else {
+            self.pruning_scheduled = false;
+            Ok(Effects::new())
+        }
+    }
+
+    /// Prunes the bodies, transactions and execution results of up to [`PRUNING_BATCH_SIZE`]
+    /// non-switch blocks which precede the retained eras, if pruning is enabled.
+    ///
+    /// Pruning stops at the switch block concluding the last non-retained era, and never touches
+    /// blocks which are still required for replay protection.  The lower bound of the completed
+    /// blocks is raised along with the pruned blocks, up to that switch block, so that they are no
+    /// longer advertised as available.
+    ///
+    /// Returns `true` if more blocks remain to be pruned.
+    fn prune_block_batch(&mut self) -> Result<bool, FatalStorageError> {
+        let prune_below_height = match self.prune_below_height()? {
+            Some(height) if height > self.lowest_unpruned_block_height => height,
+            Some(_) | None => return Ok(false),
+        };
+
+        let batch_end =
+            prune_below_height.min(self.lowest_unpruned_block_height + PRUNING_BATCH_SIZE);
+        debug!(
+            from_height = self.lowest_unpruned_block_height,
+            to_height = batch_end,
+            "Storage: pruning block data"
+        );
+        let mut block_hashes = vec![];
+        {
+            let ro_txn = self.block_store.checkout_ro()?;
+            for height in self.lowest_unpruned_block_height..batch_end {
+                let maybe_header: Option<BlockHeader> = ro_txn.read(height)?;
+                match maybe_header {
+                    Some(header) if !header.is_switch_block() => {
+                        block_hashes.push(header.block_hash())
+                    }
+                    Some(_) | None => {}
+                }
+            }
+        }
+
+        let mut rw_txn = self.block_store.checkout_rw()?;
+        for block_hash in block_hashes {
+            DataWriter::<BlockHash, PrunableBlockData>::delete(&mut rw_txn, block_hash)?;
+        }
+        rw_txn.write(&StateStore {
+            key: Cow::Borrowed(LOWEST_UNPRUNED_BLOCK_HEIGHT_STORAGE_KEY),
+            value: batch_end
+                .to_bytes()
+                .map_err(FatalStorageError::UnexpectedSerializationFailure)?,
+        })?;
+        rw_txn.commit()?;
+        self.lowest_unpruned_block_height = batch_end;
+
+        self.completed_blocks.truncate_below(batch_end);
+        self.persist_completed_blocks()?;
+        self.update_chain_height_metrics();
+        if batch_end < prune_below_height {
+            return Ok(true);
+        }
+        info!(
+            "Storage: pruned block data below height {}: {}",
+            prune_below_height,
+            self.get_available_block_range()
+        );
+        Ok(false)
+    }
+
+    /// Returns the height of the switch block concluding the last era whose blocks are to be
+    /// pruned, or `None` if pruning is disabled or there is no such era yet.
+    fn prune_below_height(&self) -> Result<Option<u64>, FatalStorageError> {
+        let retained_era_count = match self.pruning_retained_era_count {
+            Some(retained_era_count) => retained_era_count,
+            None => return Ok(None),
+        };
+
+        let ro_txn = self.block_store.checkout_ro()?;
+        let latest_switch_block_header =
+            match DataReader::<LatestSwitchBlock, BlockHeader>::read(&ro_txn, LatestSwitchBlock)? {
+                Some(header) => header,
+                None => return Ok(None),
+            };
+        let replay_protection_timestamp = latest_switch_block_header
+            .timestamp()
+            .saturating_sub(self.max_ttl.value());
+
+        // The switch block concluding the era preceding the retained eras.
+        let mut era_id = match latest_switch_block_header
+            .era_id()
+            .successor()
+            .value()
+            .checked_sub(retained_era_count)
+        {
+            Some(era_id) => EraId::new(era_id),
+            None => return Ok(None),
+        };
+        loop {
+            let maybe_switch_block_header: Option<BlockHeader> = ro_txn.read(era_id)?;
+            match maybe_switch_block_header {
+                Some(header) if header.timestamp() < replay_protection_timestamp => {
+                    return Ok(Some(header.height()));
+                }
+                Some(_) | None => match era_id.predecessor() {
+                    Some(predecessor) => era_id = predecessor,
+                    None => return Ok(None),
+                },
+            }
+        }
+    }
+
+    /// Returns `true` if the data of the given block has been pruned.
+    ///
+    /// Headers of pruned blocks are retained, as are switch blocks in their entirety.
+    fn is_block_pruned(
+        &self,
+        txn: &impl DataReader<BlockHash, BlockHeader>,
+        block_hash: &BlockHash,
+    ) -> Result<bool, FatalStorageError> {
+        if self.lowest_unpruned_block_height == 0 {
+            return Ok(false);
+        }
+        let maybe_header: Option<BlockHeader> = txn.read(*block_hash)?;
+        Ok(maybe_header.is_some_and(|header| {
+            !header.is_switch_block() && header.height() < self.lowest_unpruned_block_height
+        }))
+    }
+
+    /// Constructs the response to a peer's request for an item belonging to the given block.
+    ///
+    /// If the item is missing because the block's data has been pruned, `NotProvided` is returned
+    /// rather than `NotFound`, as the item will never become available from this node.
+    fn fetch_response_for_block_data<T, Id>(
+        &self,
+        id: Id,
+        block_hash: &BlockHash,
+        opt_item: Option<T>,
+    ) -> Result<FetchResponse<T, Id>, FatalStorageError> {
+        if opt_item.is_none()
+            && self.is_block_pruned(&self.block_store.checkout_ro()?, block_hash)?
+        {
+            return Ok(FetchResponse::NotProvided(id));
+        }
+        Ok(FetchResponse::from_opt(id, opt_item))
+    }
+
     /// Retrieves the height of the highest complete block (if any).
     pub(crate) fn highest_complete_block_height(&self) -> Option<u64> {
         self.completed_blocks.highest_sequence().map(Sequence::high)
@@ -1735,7 +2006,16 @@ impl Storage {
             only_from_available_block_range,
         )? {
             Some(trusted_block_header) => trusted_block_header,
-            None => return Ok(FetchResponse::NotFound(sync_leap_identifier)),
+            None => {
+                // The trusted block and its ancestors are no longer available if they were pruned.
+                let maybe_header: Option<BlockHeader> = txn.read(block_hash)?;
+                if maybe_header
+                    .is_some_and(|header| header.height() < self.lowest_unpruned_block_height)
+                {
+                    return Ok(FetchResponse::NotProvided(sync_leap_identifier));
+                }
+                return Ok(FetchResponse::NotFound(sync_leap_identifier));
+            }
         };
 
         let trusted_ancestor_headers =
@@ -39,6 +39,18 @@ pub struct Config {
     pub enable_mem_deduplication: bool,
     /// How many loads before memory duplication checks for dead references.
     pub mem_pool_prune_interval: u16,
+    /// Whether or not to index the transactions of executed blocks by their initiators.
+    ///
+    /// Only blocks stored while the index is enabled are indexed.
+    #[serde(default)]
+    pub enable_initiator_transactions_index: bool,
+    /// The number of most recent eras for which block bodies, transactions and execution results
+    /// are retained.
+    ///
+    /// Data of blocks in older eras is pruned, with the exception of block headers and switch
+    /// blocks.  If `None`, no data is pruned.
+    #[serde(default)]
+    pub pruning_retained_era_count: Option<u64>,
 }
 
 impl Default for Config {
@@ -52,11 +64,20 @@ impl Default for Config {
             max_state_store_size: DEFAULT_MAX_STATE_STORE_SIZE,
             enable_mem_deduplication: true,
             mem_pool_prune_interval: 4096,
+            enable_initiator_transactions_index: false,
+            pruning_retained_era_count: None,
         }
     }
 }
 
 impl Config {
+    /// Returns the upper bound for the memory map potentially used by the block store.
+    pub(crate) fn max_total_size(&self) -> usize {
+        self.max_block_store_size
+            .saturating_add(self.max_deploy_store_size)
+            .saturating_add(self.max_deploy_metadata_store_size)
+    }
+
     /// Returns a `Config` suitable for tests, along with a `TempDir` which must be kept alive for
     /// the duration of the test since its destructor removes the dir from the filesystem.
     ///
@@ -100,7 +100,7 @@ impl From<Sequence> for AvailableBlockRange {
 /// be reduced to `[9,3]`.
 #[derive(Default, Debug, DataSize)]
 #[cfg_attr(test, derive(Clone))]
-pub(super) struct DisjointSequences {
+pub(crate) struct DisjointSequences {
     sequences: Vec<Sequence>,
 }
 
@@ -191,7 +191,7 @@ impl DisjointSequences {
     }
 
     /// Returns all the sequences, if any.
-    pub(super) fn sequences(&self) -> &Vec<Sequence> {
+    pub(crate) fn sequences(&self) -> &Vec<Sequence> {
         &self.sequences
     }
 
@@ -217,7 +217,66 @@ impl DisjointSequences {
             true
         })
     }
+
+    /// Reduces the sequence(s), keeping all entries above and including `min_value`.  If
+    /// `min_value` is not already included in a sequence, it will not be added.
+    ///
+    /// If the current lowest value is higher than `min_value`, or if there are no sequences, this
+    /// has no effect.
+    pub(super) fn truncate_below(&mut self, min_value: u64) {
+        self.sequences.retain_mut(|sequence| {
+            if sequence.low >= min_value {
+                // Keep this sequence unchanged.
+                return true;
+            }
+
+            if sequence.high < min_value {
+                // Delete this entire sequence.
+                return false;
+            }
+
+            // This sequence contains `min_value`, so keep the sequence, but raise its low value.
+            sequence.low = min_value;
+            true
+        })
+    }
+
+    /// Removes `value`, splitting the sequence containing it in two if required.
+    ///
+    /// Returns `true` if `value` was previously contained in the disjoint sequences.
+    pub(crate) fn remove(&mut self, value: u64) -> bool {
+        let Some(index) = self
+            .sequences
+            .iter()
+            .position(|sequence| value >= sequence.low && value <= sequence.high)
+        else {
+            return false;
+        };
+        let sequence = self.sequences[index];
+        match (sequence.low < value, value < sequence.high) {
+            (false, false) => {
+                let _ = self.sequences.remove(index);
+            }
+            (true, false) => self.sequences[index].high = value - 1,
+            (false, true) => self.sequences[index].low = value + 1,
+            (true, true) => {
+                self.sequences[index].low = value + 1;
+                self.sequences
+                    .insert(index + 1, Sequence::new(sequence.low, value - 1));
+            }
+        }
+        trace!(%self, "current state of disjoint sequences");
+        true
+    }
+
+    /// Returns `true` if `value` exists in the disjoint sequences.
+    pub(crate) fn contains(&self, value: u64) -> bool {
+        self.sequences
+            .iter()
+            .any(|sequence| value >= sequence.low && value <= sequence.high)
+    }
 }
+
 #[cfg(test)]
 impl DisjointSequences {
     /// Inserts multiple values produced by the given iterator.
@@ -226,13 +285,6 @@ impl DisjointSequences {
             self.insert(height);
         })
     }
-
-    /// Returns `true` if `value` exists in the disjoint sequences.
-    fn contains(&self, value: u64) -> bool {
-        self.sequences
-            .iter()
-            .any(|sequence| value >= sequence.low && value <= sequence.high)
-    }
 }
 
 impl FromBytes for Sequence {
@@ -547,6 +599,88 @@ mod tests {
         assert!(disjoint_sequences.sequences.is_empty());
     }
 
+    #[test]
+    fn should_truncate_below() {
+        const SEQ_HIGH: Sequence = Sequence { high: 11, low: 9 };
+        const SEQ_MID: Sequence = Sequence { high: 6, low: 6 };
+        const SEQ_LOW: Sequence = Sequence { high: 3, low: 1 };
+        let initial_sequences = DisjointSequences {
+            sequences: vec![SEQ_HIGH, SEQ_MID, SEQ_LOW],
+        };
+
+        // Truncate with `min_value` less or equal to current lowest value should be a no-op.
+        let mut disjoint_sequences = initial_sequences.clone();
+        disjoint_sequences.truncate_below(0);
+        assert_eq!(disjoint_sequences.sequences, initial_sequences.sequences);
+        disjoint_sequences.truncate_below(1);
+        assert_eq!(disjoint_sequences.sequences, initial_sequences.sequences);
+
+        // Truncate with `min_value` between two sequences should cause the lower sequences to get
+        // removed and the higher ones retained unchanged.
+        disjoint_sequences = initial_sequences.clone();
+        disjoint_sequences.truncate_below(SEQ_LOW.high + 1);
+        assert_eq!(disjoint_sequences.sequences, vec![SEQ_HIGH, SEQ_MID]);
+
+        disjoint_sequences = initial_sequences.clone();
+        disjoint_sequences.truncate_below(SEQ_MID.low);
+        assert_eq!(disjoint_sequences.sequences, vec![SEQ_HIGH, SEQ_MID]);
+
+        disjoint_sequences = initial_sequences.clone();
+        disjoint_sequences.truncate_below(SEQ_MID.high + 1);
+        assert_eq!(disjoint_sequences.sequences, vec![SEQ_HIGH]);
+
+        // Truncate with `min_value` higher than the highest value should cause all sequences to
+        // get removed.
+        disjoint_sequences = initial_sequences.clone();
+        disjoint_sequences.truncate_below(SEQ_HIGH.high + 1);
+        assert!(disjoint_sequences.sequences.is_empty());
+
+        // Truncate with `min_value` within a sequence should cause that sequence to get updated,
+        // any lower sequences to get removed, and any higher ones retained unchanged.
+        disjoint_sequences = initial_sequences.clone();
+        let min_value = SEQ_LOW.low + 1;
+        disjoint_sequences.truncate_below(min_value);
+        assert_eq!(
+            disjoint_sequences.sequences,
+            vec![SEQ_HIGH, SEQ_MID, new_sequence(SEQ_LOW.high, min_value)]
+        );
+
+        disjoint_sequences = initial_sequences.clone();
+        let min_value = SEQ_HIGH.high;
+        disjoint_sequences.truncate_below(min_value);
+        assert_eq!(
+            disjoint_sequences.sequences,
+            vec![new_sequence(SEQ_HIGH.high, min_value)]
+        );
+
+        // Truncate on an empty set of sequences should have no effect.
+        disjoint_sequences = DisjointSequences::default();
+        disjoint_sequences.truncate_below(100);
+        assert!(disjoint_sequences.sequences.is_empty());
+    }
+
+    #[test]
+    fn should_remove() {
+        let mut rng = crate::new_rng();
+
+        let mut values: Vec<u64> = (0..=255).collect();
+        let mut disjoint_sequences = DisjointSequences::from(values.clone());
+        let mut expected: BTreeSet<u64> = values.iter().copied().collect();
+        values.shuffle(&mut rng);
+
+        for value in values {
+            assert!(disjoint_sequences.remove(value));
+            assert!(!disjoint_sequences.remove(value));
+            expected.remove(&value);
+            assert_matches(&disjoint_sequences, &expected);
+            assert!(disjoint_sequences
+                .sequences
+                .windows(2)
+                .all(|pair| pair[0].low > pair[1].high + 1));
+        }
+        assert!(disjoint_sequences.sequences.is_empty());
+    }
+
     #[test]
     fn roundtrip_to_bytes() {
         let mut disjoint_sequences = DisjointSequences::default();
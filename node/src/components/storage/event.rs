@@ -27,6 +27,8 @@ pub(crate) enum Event {
     /// Make block executable request.
     #[from]
     MakeBlockExecutableRequest(Box<MakeBlockExecutableRequest>),
+    /// Prune the next batch of blocks outside the retained eras.
+    PruneBlocks,
 }
 
 impl Display for Event {
@@ -36,6 +38,7 @@ impl Display for Event {
             Event::NetRequestIncoming(incoming) => incoming.fmt(f),
             Event::MarkBlockCompletedRequest(req) => req.fmt(f),
             Event::MakeBlockExecutableRequest(req) => req.fmt(f),
+            Event::PruneBlocks => write!(f, "prune blocks"),
         }
     }
 }
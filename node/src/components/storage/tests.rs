@@ -16,25 +16,26 @@ use serde::{Deserialize, Serialize};
 use smallvec::smallvec;
 
 use casper_storage::block_store::{
-    types::{ApprovalsHashes, BlockHashHeightAndEra, BlockTransfers},
+    types::{ApprovalsHashes, BlockHashHeightAndEra, BlockTransfers, PrunableBlockData},
     BlockStoreProvider, BlockStoreTransaction, DataReader, DataWriter,
 };
 use casper_types::{
     execution::{Effects, ExecutionResult, ExecutionResultV2},
     generate_ed25519_keypair,
+    global_state::TrieMerkleProof,
     testing::TestRng,
     ApprovalsHash, AvailableBlockRange, Block, BlockHash, BlockHeader, BlockHeaderWithSignatures,
-    BlockSignatures, BlockSignaturesV2, BlockV2, ChainNameDigest, Chainspec, ChainspecRawBytes,
-    Deploy, DeployHash, Digest, EraId, ExecutionInfo, FinalitySignature, FinalitySignatureV2, Gas,
-    InitiatorAddr, ProtocolVersion, PublicKey, SecretKey, TestBlockBuilder, TestBlockV1Builder,
-    TimeDiff, Transaction, TransactionConfig, TransactionHash, TransactionV1Hash, Transfer,
-    TransferV2, U512,
+    BlockSignatures, BlockSignaturesV2, BlockV2, CLValue, ChainNameDigest, Chainspec,
+    ChainspecRawBytes, Deploy, DeployHash, Digest, EraId, ExecutionInfo, FinalitySignature,
+    FinalitySignatureV2, Gas, InitiatorAddr, Key, ProtocolVersion, PublicKey, SecretKey,
+    StoredValue, TestBlockBuilder, TestBlockV1Builder, TimeDiff, Timestamp, Transaction,
+    TransactionConfig, TransactionHash, TransactionV1Hash, Transfer, TransferV2, U512,
 };
 use tempfile::tempdir;
 
 use super::{
     move_storage_files_to_network_subdir, should_move_storage_files_to_network_subdir, Config,
-    Storage, FORCE_RESYNC_FILE_NAME,
+    Event, Storage, FORCE_RESYNC_FILE_NAME,
 };
 use crate::{
     components::fetcher::{FetchItem, FetchResponse},
@@ -66,6 +67,8 @@ fn new_config(harness: &ComponentHarness<UnitTestEvent>) -> Config {
         max_state_store_size: 50 * MIB,
         enable_mem_deduplication: true,
         mem_pool_prune_interval: 4,
+        enable_initiator_transactions_index: true,
+        pruning_retained_era_count: None,
     }
 }
 
@@ -569,6 +572,34 @@ fn put_execution_results(
     assert!(harness.is_idle());
 }
 
+/// Gets the transactions initiated by the given account from storage.
+fn get_transactions_by_initiator(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    initiator_addr: InitiatorAddr,
+    from_height: u64,
+    to_height: u64,
+) -> Option<Vec<(TransactionHash, BlockHashHeightAndEra)>> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetTransactionsByInitiator {
+            initiator_addr: Box::new(initiator_addr),
+            from_height,
+            to_height,
+            start_after: None,
+            limit: usize::MAX,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Handles `PruneBlocks` events until no more are scheduled.
+fn prune_blocks(harness: &mut ComponentHarness<UnitTestEvent>, storage: &mut Storage) {
+    while !harness.send_event(storage, Event::PruneBlocks).is_empty() {}
+}
+
 /// Gets available block range from storage.
 fn get_available_block_range(
     harness: &mut ComponentHarness<UnitTestEvent>,
@@ -1299,6 +1330,248 @@ fn store_random_execution_results() {
     }
 }
 
+#[test]
+fn should_index_transactions_by_initiator() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let transaction = Transaction::random(&mut harness.rng);
+    put_transaction(&mut harness, &mut storage, &transaction);
+
+    let block: Block = TestBlockBuilder::new()
+        .height(5)
+        .build(&mut harness.rng)
+        .into();
+    let approvals_hashes = ApprovalsHashes::new(
+        *block.hash(),
+        vec![],
+        TrieMerkleProof::new(
+            Key::ChecksumRegistry,
+            StoredValue::CLValue(CLValue::from_t(()).unwrap()),
+            Default::default(),
+        ),
+    );
+    let execution_results = iter::once((
+        transaction.hash(),
+        ExecutionResult::from(ExecutionResultV2::random(&mut harness.rng)),
+    ))
+    .collect();
+    storage
+        .put_executed_block(
+            TransactionConfig::default(),
+            &block,
+            &approvals_hashes,
+            execution_results,
+        )
+        .unwrap();
+
+    let expected = vec![(
+        transaction.hash(),
+        BlockHashHeightAndEra::new(*block.hash(), block.height(), block.era_id()),
+    )];
+    let initiator_addr = transaction.initiator_addr();
+    assert_eq!(
+        get_transactions_by_initiator(&mut harness, &mut storage, initiator_addr.clone(), 0, 10),
+        Some(expected)
+    );
+    assert_eq!(
+        get_transactions_by_initiator(&mut harness, &mut storage, initiator_addr, 6, 10),
+        Some(vec![])
+    );
+    let other_initiator_addr = InitiatorAddr::random(&mut harness.rng);
+    assert_eq!(
+        get_transactions_by_initiator(&mut harness, &mut storage, other_initiator_addr, 0, 10),
+        Some(vec![])
+    );
+}
+
+#[test]
+fn should_remove_deleted_and_pruned_blocks_from_initiator_index() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let mut blocks = vec![];
+    for height in [5, 6] {
+        let transaction = Transaction::random(&mut harness.rng);
+        put_transaction(&mut harness, &mut storage, &transaction);
+        let block: Block = TestBlockBuilder::new()
+            .height(height)
+            .transactions(Some(&transaction))
+            .build(&mut harness.rng)
+            .into();
+        let approvals_hashes = ApprovalsHashes::new(
+            *block.hash(),
+            vec![],
+            TrieMerkleProof::new(
+                Key::ChecksumRegistry,
+                StoredValue::CLValue(CLValue::from_t(()).unwrap()),
+                Default::default(),
+            ),
+        );
+        let execution_results = iter::once((
+            transaction.hash(),
+            ExecutionResult::from(ExecutionResultV2::random(&mut harness.rng)),
+        ))
+        .collect();
+        storage
+            .put_executed_block(
+                TransactionConfig::default(),
+                &block,
+                &approvals_hashes,
+                execution_results,
+            )
+            .unwrap();
+        let initiator_addr = transaction.initiator_addr();
+        assert_eq!(
+            get_transactions_by_initiator(&mut harness, &mut storage, initiator_addr, 0, 10)
+                .unwrap()
+                .len(),
+            1
+        );
+        blocks.push((block, transaction));
+    }
+
+    let mut rw_txn = storage.block_store.checkout_rw().unwrap();
+    DataWriter::<BlockHash, Block>::delete(&mut rw_txn, *blocks[0].0.hash()).unwrap();
+    DataWriter::<BlockHash, PrunableBlockData>::delete(&mut rw_txn, *blocks[1].0.hash()).unwrap();
+    rw_txn.commit().unwrap();
+
+    for (_block, transaction) in blocks {
+        assert_eq!(
+            get_transactions_by_initiator(
+                &mut harness,
+                &mut storage,
+                transaction.initiator_addr(),
+                0,
+                10
+            ),
+            Some(vec![])
+        );
+    }
+}
+
+#[test]
+fn should_prune_blocks_outside_retained_eras() {
+    // Test chain:
+    //      S0      B1 B2 S3 | B4 B5 S6 | B7 B8 S9 | B10
+    //  era 0 |     era 1    |  era 2   |  era 3   | era 4
+    //
+    // With two retained eras, the data of blocks 1, 2, 4 and 5 should get pruned once switch block
+    // 9 is complete.
+    let mut harness = ComponentHarness::default();
+    let mut cfg = new_config(&harness);
+    cfg.pruning_retained_era_count = Some(2);
+    let cfg = WithDir::new(harness.tmp.path(), cfg);
+    let max_ttl = TimeDiff::from_seconds(1);
+    let storage_from_cfg = |cfg: &WithDir<Config>| {
+        Storage::new(
+            cfg,
+            None,
+            ProtocolVersion::from_parts(1, 0, 0),
+            EraId::default(),
+            "test",
+            max_ttl.into(),
+            RECENT_ERA_COUNT,
+            None,
+            false,
+            TransactionConfig::default(),
+        )
+        .expect("could not create storage component fixture")
+    };
+    let mut storage = storage_from_cfg(&cfg);
+
+    let mut blocks = vec![];
+    let mut transactions = vec![];
+    for height in 0..=10_u64 {
+        let transaction: Transaction =
+            Deploy::random_valid_native_transfer(&mut harness.rng).into();
+        let block: Block = TestBlockBuilder::new()
+            .era((height + 2) / 3)
+            .height(height)
+            .timestamp(Timestamp::from(height * 10_000))
+            .switch_block(height % 3 == 0)
+            .transactions(Some(&transaction))
+            .build_versioned(&mut harness.rng);
+        put_transaction(&mut harness, &mut storage, &transaction);
+        let execution_results = iter::once((
+            transaction.hash(),
+            ExecutionResult::from(ExecutionResultV2::random(&mut harness.rng)),
+        ))
+        .collect();
+        put_execution_results(
+            &mut harness,
+            &mut storage,
+            *block.hash(),
+            block.height(),
+            block.era_id(),
+            execution_results,
+        );
+        assert!(put_complete_block(
+            &mut harness,
+            &mut storage,
+            block.clone()
+        ));
+        blocks.push(block);
+        transactions.push(transaction);
+    }
+
+    // Blocks are only pruned as `PruneBlocks` events are handled.
+    assert!(get_block(&mut harness, &mut storage, *blocks[1].hash()).is_some());
+    prune_blocks(&mut harness, &mut storage);
+    assert!(!storage.pruning_scheduled);
+
+    // Pruning is never scheduled while a `PruneBlocks` event is pending.
+    assert_eq!(storage.schedule_pruning(harness.effect_builder).len(), 1);
+    assert!(storage.schedule_pruning(harness.effect_builder).is_empty());
+    prune_blocks(&mut harness, &mut storage);
+    assert!(!storage.pruning_scheduled);
+    assert_eq!(
+        get_available_block_range(&mut harness, &mut storage),
+        AvailableBlockRange::new(6, 10)
+    );
+    for (block, transaction) in blocks.iter().zip(transactions.iter()) {
+        let block_hash = *block.hash();
+        let is_pruned = block.height() < 6 && !block.is_switch_block();
+        assert_eq!(
+            get_block(&mut harness, &mut storage, block_hash).is_none(),
+            is_pruned
+        );
+        assert_eq!(
+            get_execution_results(&mut harness, &mut storage, block_hash).is_none(),
+            is_pruned
+        );
+        assert_eq!(
+            get_naive_transactions(&mut harness, &mut storage, smallvec![transaction.hash()])[0]
+                .is_none(),
+            is_pruned
+        );
+        assert!(get_block_header(&mut harness, &mut storage, block_hash, false).is_some());
+
+        let fetch_response = storage
+            .fetch_response_for_block_data(block_hash, &block_hash, None::<Block>)
+            .unwrap();
+        if is_pruned {
+            assert!(matches!(fetch_response, FetchResponse::NotProvided(_)));
+        } else {
+            assert!(matches!(fetch_response, FetchResponse::NotFound(_)));
+        }
+    }
+
+    let sync_leap_response = storage
+        .get_sync_leap(SyncLeapIdentifier::sync_to_tip(*blocks[4].hash()))
+        .unwrap();
+    assert!(matches!(sync_leap_response, FetchResponse::NotProvided(_)));
+
+    // The pruning progress should be persisted across instantiations.
+    drop(storage);
+    let storage = storage_from_cfg(&cfg);
+    assert_eq!(storage.lowest_unpruned_block_height, 6);
+    assert_eq!(
+        storage.get_available_block_range(),
+        AvailableBlockRange::new(6, 10)
+    );
+}
+
 #[test]
 fn store_execution_results_twice_for_same_block_deploy_pair() {
     let mut harness = ComponentHarness::default();
@@ -18,13 +18,13 @@ use casper_types::Chainspec;
 
 use crate::{
     components::{
-        fetcher::{self, FetchResult, FetchedData},
+        fetcher::{self, FetchResult, FetchedData, Tag},
         Component,
     },
     effect::{requests::FetcherRequest, EffectBuilder, EffectExt, Effects},
     types::{
-        sync_leap_validation_metadata::SyncLeapValidationMetaData, NodeId, SyncLeap,
-        SyncLeapIdentifier,
+        sync_leap_validation_metadata::SyncLeapValidationMetaData, NodeId, PeerReputation,
+        SyncLeap, SyncLeapIdentifier,
     },
     NodeRng,
 };
@@ -77,17 +77,21 @@ pub(crate) struct SyncLeaper {
     leap_activity: Option<LeapActivity>,
     chainspec: Arc<Chainspec>,
     #[data_size(skip)]
+    peer_reputation: PeerReputation,
+    #[data_size(skip)]
     metrics: Metrics,
 }
 
 impl SyncLeaper {
     pub(crate) fn new(
         chainspec: Arc<Chainspec>,
+        peer_reputation: PeerReputation,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(SyncLeaper {
             leap_activity: None,
             chainspec,
+            peer_reputation,
             metrics: Metrics::new(registry)?,
         })
     }
@@ -197,6 +201,33 @@ impl SyncLeaper {
         RegisterLeapAttemptOutcome::FetchSyncLeapFromPeers(peers_to_ask)
     }
 
+    /// Chooses up to `simultaneous_peer_requests` of the given peers to ask for a sync leap,
+    /// preferring peers with a good reputation.
+    ///
+    /// Peers already asked as part of the ongoing attempt are not counted, but kept in the
+    /// returned list.
+    fn choose_peers_to_ask(
+        &self,
+        sync_leap_identifier: &SyncLeapIdentifier,
+        peers_to_ask: Vec<NodeId>,
+        rng: &mut NodeRng,
+    ) -> Vec<NodeId> {
+        let (asked, not_asked): (Vec<_>, Vec<_>) = peers_to_ask.into_iter().partition(|peer| {
+            self.leap_activity.as_ref().is_some_and(|leap_activity| {
+                leap_activity.sync_leap_identifier() == sync_leap_identifier
+                    && leap_activity.peers().contains_key(peer)
+            })
+        });
+        let mut chosen = self.peer_reputation.select_peers(
+            Some(Tag::SyncLeap),
+            not_asked,
+            self.chainspec.core_config.simultaneous_peer_requests as usize,
+            rng,
+        );
+        chosen.extend(asked);
+        chosen
+    }
+
     fn fetch_received(
         &mut self,
         sync_leap_identifier: SyncLeapIdentifier,
@@ -278,36 +309,40 @@ where
     fn handle_event(
         &mut self,
         effect_builder: EffectBuilder<REv>,
-        _rng: &mut NodeRng,
+        rng: &mut NodeRng,
         event: Self::Event,
     ) -> Effects<Self::Event> {
         match event {
             Event::AttemptLeap {
                 sync_leap_identifier,
                 peers_to_ask,
-            } => match self.register_leap_attempt(sync_leap_identifier, peers_to_ask) {
-                RegisterLeapAttemptOutcome::DoNothing => Effects::new(),
-                RegisterLeapAttemptOutcome::FetchSyncLeapFromPeers(peers) => {
-                    let mut effects = Effects::new();
-                    peers.into_iter().for_each(|peer| {
-                        effects.extend(
-                            effect_builder
-                                .fetch::<SyncLeap>(
-                                    sync_leap_identifier,
-                                    peer,
-                                    Box::new(SyncLeapValidationMetaData::from_chainspec(
-                                        self.chainspec.as_ref(),
-                                    )),
-                                )
-                                .event(move |fetch_result| Event::FetchedSyncLeapFromPeer {
-                                    sync_leap_identifier,
-                                    fetch_result,
-                                }),
-                        )
-                    });
-                    effects
+            } => {
+                let peers_to_ask =
+                    self.choose_peers_to_ask(&sync_leap_identifier, peers_to_ask, rng);
+                match self.register_leap_attempt(sync_leap_identifier, peers_to_ask) {
+                    RegisterLeapAttemptOutcome::DoNothing => Effects::new(),
+                    RegisterLeapAttemptOutcome::FetchSyncLeapFromPeers(peers) => {
+                        let mut effects = Effects::new();
+                        peers.into_iter().for_each(|peer| {
+                            effects.extend(
+                                effect_builder
+                                    .fetch::<SyncLeap>(
+                                        sync_leap_identifier,
+                                        peer,
+                                        Box::new(SyncLeapValidationMetaData::from_chainspec(
+                                            self.chainspec.as_ref(),
+                                        )),
+                                    )
+                                    .event(move |fetch_result| Event::FetchedSyncLeapFromPeer {
+                                        sync_leap_identifier,
+                                        fetch_result,
+                                    }),
+                            )
+                        });
+                        effects
+                    }
                 }
-            },
+            }
             Event::FetchedSyncLeapFromPeer {
                 sync_leap_identifier,
                 fetch_result,
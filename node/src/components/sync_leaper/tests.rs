@@ -9,7 +9,7 @@ use crate::{
         fetcher::{self, FetchResult, FetchedData},
         sync_leaper::{LeapState, PeerState, RegisterLeapAttemptOutcome},
     },
-    types::{NodeId, SyncLeap, SyncLeapIdentifier},
+    types::{NodeId, PeerReputation, SyncLeap, SyncLeapIdentifier},
 };
 
 use super::{Error, SyncLeaper};
@@ -27,7 +27,7 @@ pub(crate) fn make_test_sync_leap(rng: &mut TestRng) -> SyncLeap {
 fn make_sync_leaper(rng: &mut TestRng) -> SyncLeaper {
     let chainspec = Chainspec::random(rng);
     let registry = Registry::new();
-    SyncLeaper::new(Arc::new(chainspec), &registry).unwrap()
+    SyncLeaper::new(Arc::new(chainspec), PeerReputation::new(), &registry).unwrap()
 }
 
 fn assert_peers(expected: &[NodeId], actual: &Vec<(NodeId, PeerState)>) {
@@ -173,6 +173,34 @@ fn register_leap_attempt_with_reattempt_for_the_same_leap_identifier() {
     assert_peers(&expected_peers, &actual_peers);
 }
 
+#[test]
+fn choose_peers_to_ask_limits_new_peers() {
+    let mut rng = TestRng::new();
+
+    let mut sync_leaper = make_sync_leaper(&mut rng);
+    let max_peers = sync_leaper.chainspec.core_config.simultaneous_peer_requests as usize;
+
+    let sync_leap_identifier = SyncLeapIdentifier::sync_to_tip(BlockHash::random(&mut rng));
+    let peers: Vec<NodeId> = (0..max_peers * 2)
+        .map(|_| NodeId::random(&mut rng))
+        .collect();
+
+    // Only up to the configured number of new peers should be chosen.
+    let chosen = sync_leaper.choose_peers_to_ask(&sync_leap_identifier, peers.clone(), &mut rng);
+    assert_eq!(chosen.len(), max_peers);
+    assert!(chosen.iter().all(|peer| peers.contains(peer)));
+
+    // Peers already asked for the same identifier are passed through in addition.
+    let outcome = sync_leaper.register_leap_attempt(sync_leap_identifier, chosen.clone());
+    assert!(matches!(
+        outcome,
+        RegisterLeapAttemptOutcome::FetchSyncLeapFromPeers(_)
+    ));
+    let chosen_again = sync_leaper.choose_peers_to_ask(&sync_leap_identifier, peers, &mut rng);
+    assert_eq!(chosen_again.len(), max_peers * 2);
+    assert!(chosen.iter().all(|peer| chosen_again.contains(peer)));
+}
+
 #[test]
 fn fetch_received_from_storage() {
     let mut rng = TestRng::new();
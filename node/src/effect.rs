@@ -115,17 +115,18 @@ use tokio::{sync::Semaphore, time};
 use tracing::{debug, error, warn};
 
 use casper_binary_port::{
-    ConsensusStatus, ConsensusValidatorChanges, LastProgress, NetworkName, RecordId, Uptime,
+    ConsensusStatus, ConsensusValidatorChanges, LastProgress, NetworkName, RecordId, StateOverride,
+    Uptime,
 };
 use casper_storage::{
-    block_store::types::ApprovalsHashes,
+    block_store::types::{ApprovalsHashes, BlockHashHeightAndEra},
     data_access_layer::{
         prefixed_values::{PrefixedValuesRequest, PrefixedValuesResult},
         tagged_values::{TaggedValuesRequest, TaggedValuesResult},
         AddressableEntityResult, BalanceRequest, BalanceResult, EraValidatorsRequest,
-        EraValidatorsResult, ExecutionResultsChecksumResult, PutTrieRequest, PutTrieResult,
-        QueryRequest, QueryResult, SeigniorageRecipientsRequest, SeigniorageRecipientsResult,
-        TrieRequest, TrieResult,
+        EraValidatorsResult, ExecutionResultsChecksumResult, MultiProofRequest, MultiProofResult,
+        PutTrieRequest, PutTrieResult, QueryRequest, QueryResult, SeigniorageRecipientsRequest,
+        SeigniorageRecipientsResult, StateDiffRequest, StateDiffResult, TrieRequest, TrieResult,
     },
     DbRawBytesSpec,
 };
@@ -133,9 +134,9 @@ use casper_types::{
     execution::{Effects as ExecutionEffects, ExecutionResult},
     Approval, AvailableBlockRange, Block, BlockHash, BlockHeader, BlockSignatures,
     BlockSynchronizerStatus, BlockV2, ChainspecRawBytes, DeployHash, Digest, EntityAddr, EraId,
-    ExecutionInfo, FinalitySignature, FinalitySignatureId, FinalitySignatureV2, HashAddr, Key,
-    NextUpgrade, Package, PackageAddr, ProtocolUpgradeConfig, PublicKey, TimeDiff, Timestamp,
-    Transaction, TransactionHash, TransactionId, Transfer, U512,
+    ExecutionInfo, FinalitySignature, FinalitySignatureId, FinalitySignatureV2, HashAddr,
+    InitiatorAddr, Key, NextUpgrade, Package, PackageAddr, ProtocolUpgradeConfig, PublicKey,
+    TimeDiff, Timestamp, Transaction, TransactionHash, TransactionId, Transfer, U512,
 };
 
 use crate::{
@@ -145,7 +146,7 @@ use crate::{
             TrieAccumulatorResponse,
         },
         consensus::{ClContext, EraDump, ProposedBlock},
-        contract_runtime::SpeculativeExecutionResult,
+        contract_runtime::{GasEstimationResult, SpeculativeExecutionResult},
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
         gossiper::GossipItem,
@@ -1393,6 +1394,30 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Get several global state items with a single proof of all of them.
+    pub(crate) async fn get_multi_proof(self, request: MultiProofRequest) -> MultiProofResult
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetMultiProof { request, responder },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
+    /// Get the differences between two global states.
+    pub(crate) async fn get_state_diff(self, request: StateDiffRequest) -> StateDiffResult
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetStateDiff { request, responder },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
     /// Get a trie or chunk by its ID.
     pub(crate) async fn get_trie(self, request: TrieRequest) -> TrieResult
     where
@@ -1555,6 +1580,35 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the transactions initiated by the given account in blocks within the inclusive height
+    /// range from storage.
+    ///
+    /// Returns `None` if the initiator transactions index is disabled.
+    pub(crate) async fn get_transactions_by_initiator_from_storage(
+        self,
+        initiator_addr: InitiatorAddr,
+        from_height: u64,
+        to_height: u64,
+        start_after: Option<TransactionHash>,
+        limit: usize,
+    ) -> Option<Vec<(TransactionHash, BlockHashHeightAndEra)>>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetTransactionsByInitiator {
+                initiator_addr: Box::new(initiator_addr),
+                from_height,
+                to_height,
+                start_after,
+                limit,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Gets the requested legacy deploy from the legacy deploy store by DeployHash only.
     ///
     /// Returns the legacy deploy containing the set of approvals used during execution of the
@@ -2271,10 +2325,17 @@ impl<REv> EffectBuilder<REv> {
 
     /// Requests execution of a single transaction, without committing its effects.  Intended to be
     /// used for debugging & discovery purposes.
+    ///
+    /// The transaction is executed against the given state root hash with the state overrides
+    /// applied, in the context of the block following the given block header.  If
+    /// `trace_execution` is set, the result holds the execution trace of the transaction.
     pub(crate) async fn speculatively_execute(
         self,
         block_header: Box<BlockHeader>,
+        state_root_hash: Digest,
         transaction: Box<Transaction>,
+        state_overrides: Vec<StateOverride>,
+        trace_execution: bool,
     ) -> SpeculativeExecutionResult
     where
         REv: From<ContractRuntimeRequest>,
@@ -2282,7 +2343,40 @@ impl<REv> EffectBuilder<REv> {
         self.make_request(
             |responder| ContractRuntimeRequest::SpeculativelyExecute {
                 block_header,
+                state_root_hash,
+                transaction,
+                state_overrides,
+                trace_execution,
+                responder,
+            },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
+    /// Requests an estimate of the gas consumed by a single transaction and of the payment it
+    /// needs, executing it without committing its effects.
+    ///
+    /// The transaction is executed against the given state root hash with the state overrides
+    /// applied, in the context of the block following the given block header.
+    pub(crate) async fn estimate_gas(
+        self,
+        block_header: Box<BlockHeader>,
+        state_root_hash: Digest,
+        transaction: Box<Transaction>,
+        state_overrides: Vec<StateOverride>,
+        payment_margin_percent: u8,
+    ) -> GasEstimationResult
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::EstimateGas {
+                block_header,
+                state_root_hash,
                 transaction,
+                state_overrides,
+                payment_margin_percent,
                 responder,
             },
             QueueKind::ContractRuntime,
@@ -16,17 +16,19 @@ use smallvec::SmallVec;
 use static_assertions::const_assert;
 
 use casper_binary_port::{
-    ConsensusStatus, ConsensusValidatorChanges, LastProgress, NetworkName, RecordId, Uptime,
+    ConsensusStatus, ConsensusValidatorChanges, LastProgress, NetworkName, RecordId, StateOverride,
+    Uptime,
 };
 use casper_storage::{
-    block_store::types::ApprovalsHashes,
+    block_store::types::{ApprovalsHashes, BlockHashHeightAndEra},
     data_access_layer::{
         prefixed_values::{PrefixedValuesRequest, PrefixedValuesResult},
         tagged_values::{TaggedValuesRequest, TaggedValuesResult},
         AddressableEntityResult, BalanceRequest, BalanceResult, EntryPointExistsResult,
-        EraValidatorsRequest, EraValidatorsResult, ExecutionResultsChecksumResult, PutTrieRequest,
-        PutTrieResult, QueryRequest, QueryResult, SeigniorageRecipientsRequest,
-        SeigniorageRecipientsResult, TrieRequest, TrieResult,
+        EraValidatorsRequest, EraValidatorsResult, ExecutionResultsChecksumResult,
+        MultiProofRequest, MultiProofResult, PutTrieRequest, PutTrieResult, QueryRequest,
+        QueryResult, SeigniorageRecipientsRequest, SeigniorageRecipientsResult, StateDiffRequest,
+        StateDiffResult, TrieRequest, TrieResult,
     },
     DbRawBytesSpec,
 };
@@ -34,8 +36,8 @@ use casper_types::{
     execution::ExecutionResult, Approval, AvailableBlockRange, Block, BlockHash, BlockHeader,
     BlockSignatures, BlockSynchronizerStatus, BlockV2, ChainspecRawBytes, DeployHash, Digest,
     DisplayIter, EntityAddr, EraId, ExecutionInfo, FinalitySignature, FinalitySignatureId,
-    HashAddr, NextUpgrade, ProtocolUpgradeConfig, PublicKey, TimeDiff, Timestamp, Transaction,
-    TransactionHash, TransactionId, Transfer,
+    HashAddr, InitiatorAddr, NextUpgrade, ProtocolUpgradeConfig, PublicKey, TimeDiff, Timestamp,
+    Transaction, TransactionHash, TransactionId, Transfer,
 };
 
 use super::{AutoClosingResponder, GossipTarget, Responder};
@@ -46,7 +48,7 @@ use crate::{
             TrieAccumulatorResponse,
         },
         consensus::{ClContext, ProposedBlock},
-        contract_runtime::SpeculativeExecutionResult,
+        contract_runtime::{GasEstimationResult, SpeculativeExecutionResult},
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
         gossiper::GossipItem,
@@ -396,6 +398,17 @@ pub(crate) enum StorageRequest {
         transaction_id: TransactionId,
         responder: Responder<bool>,
     },
+    /// Retrieve the transactions initiated by a given account within a range of block heights.
+    ///
+    /// Responds with `None` if the initiator transactions index is disabled.
+    GetTransactionsByInitiator {
+        initiator_addr: Box<InitiatorAddr>,
+        from_height: u64,
+        to_height: u64,
+        start_after: Option<TransactionHash>,
+        limit: usize,
+        responder: Responder<Option<Vec<(TransactionHash, BlockHashHeightAndEra)>>>,
+    },
     GetTransactionAndExecutionInfo {
         transaction_hash: TransactionHash,
         with_finalized_approvals: bool,
@@ -588,6 +601,18 @@ impl Display for StorageRequest {
             StorageRequest::IsTransactionStored { transaction_id, .. } => {
                 write!(formatter, "is transaction {} stored", transaction_id)
             }
+            StorageRequest::GetTransactionsByInitiator {
+                initiator_addr,
+                from_height,
+                to_height,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "get transactions by initiator {} in blocks {}..={}",
+                    initiator_addr, from_height, to_height
+                )
+            }
             StorageRequest::PutExecutionResults { block_hash, .. } => {
                 write!(formatter, "put execution results for {}", block_hash)
             }
@@ -839,6 +864,22 @@ pub(crate) enum ContractRuntimeRequest {
         entry_point_name: String,
         responder: Responder<EntryPointExistsResult>,
     },
+    /// Get several global state items with a single proof of all of them.
+    GetMultiProof {
+        /// A request for the items and their proof.
+        #[serde(skip_serializing)]
+        request: MultiProofRequest,
+        /// Responder to call with the result.
+        responder: Responder<MultiProofResult>,
+    },
+    /// Get the differences between two global states.
+    GetStateDiff {
+        /// A request for the differences between two global states.
+        #[serde(skip_serializing)]
+        request: StateDiffRequest,
+        /// Responder to call with the result.
+        responder: Responder<StateDiffResult>,
+    },
     /// Get a trie or chunk by its ID.
     GetTrie {
         /// A request for a trie element.
@@ -857,13 +898,35 @@ pub(crate) enum ContractRuntimeRequest {
     },
     /// Execute transaction without committing results
     SpeculativelyExecute {
-        /// Pre-state.
+        /// Header of the block preceding the execution.
         block_header: Box<BlockHeader>,
+        /// Pre-state.
+        state_root_hash: Digest,
         /// Transaction to execute.
         transaction: Box<Transaction>,
+        /// Changes applied to the pre-state before the execution.
+        state_overrides: Vec<StateOverride>,
+        /// Whether to record the execution trace.
+        trace_execution: bool,
         /// Results
         responder: Responder<SpeculativeExecutionResult>,
     },
+    /// Estimate the gas consumed by a transaction and the payment it needs, without committing
+    /// the results of its execution
+    EstimateGas {
+        /// Header of the block preceding the execution.
+        block_header: Box<BlockHeader>,
+        /// Pre-state.
+        state_root_hash: Digest,
+        /// Transaction to estimate the gas of.
+        transaction: Box<Transaction>,
+        /// Changes applied to the pre-state before the execution.
+        state_overrides: Vec<StateOverride>,
+        /// Margin added to the consumed gas to make up the recommended payment amount, in percent.
+        payment_margin_percent: u8,
+        /// Results
+        responder: Responder<GasEstimationResult>,
+    },
     UpdateRuntimePrice(EraId, u8),
     GetEraGasPrice {
         era_id: EraId,
@@ -935,6 +998,12 @@ impl Display for ContractRuntimeRequest {
                     entity_addr, state_root_hash
                 )
             }
+            ContractRuntimeRequest::GetMultiProof { request, .. } => {
+                write!(formatter, "get multi-proof: {:?}", request)
+            }
+            ContractRuntimeRequest::GetStateDiff { request, .. } => {
+                write!(formatter, "get state diff: {:?}", request)
+            }
             ContractRuntimeRequest::GetTrie { request, .. } => {
                 write!(formatter, "get trie: {:?}", request)
             }
@@ -943,14 +1012,26 @@ impl Display for ContractRuntimeRequest {
             }
             ContractRuntimeRequest::SpeculativelyExecute {
                 transaction,
-                block_header,
+                state_root_hash,
                 ..
             } => {
                 write!(
                     formatter,
                     "Execute {} on {}",
                     transaction.hash(),
-                    block_header.state_root_hash()
+                    state_root_hash
+                )
+            }
+            ContractRuntimeRequest::EstimateGas {
+                transaction,
+                state_root_hash,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "Estimate gas of {} on {}",
+                    transaction.hash(),
+                    state_root_hash
                 )
             }
             ContractRuntimeRequest::UpdateRuntimePrice(_, era_gas_price) => {
@@ -0,0 +1,254 @@
+//! Offline garbage collection of the trie store.
+//!
+//! The global state of every executed block is kept forever unless the node runs with trie store
+//! garbage collection enabled, and even then only a bounded amount of work is done after each
+//! block.  This collects the garbage of a node which is not running in one go instead, retaining
+//! the global state of its most recent complete blocks only.
+
+use std::sync::Arc;
+
+use prometheus::Registry;
+use thiserror::Error;
+use tracing::info;
+
+use casper_storage::global_state::{error::Error as GlobalStateError, gc::GcProgress};
+use casper_types::{Chainspec, Digest};
+
+use crate::{
+    components::{
+        contract_runtime::{ConfigError, ContractRuntime},
+        storage::{FatalStorageError, Storage},
+    },
+    reactor::main_reactor::Config,
+    utils::WithDir,
+};
+
+/// Error returned as a result of collecting the garbage of the trie store.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// Error opening or reading the storage.
+    #[error("error reading storage: {0}")]
+    Storage(#[from] FatalStorageError),
+
+    /// Error opening the global state.
+    #[error("error opening global state: {0}")]
+    ContractRuntime(#[from] ConfigError),
+
+    /// Error collecting the garbage.
+    #[error("error collecting trie store garbage: {0}")]
+    GlobalState(#[from] GlobalStateError),
+
+    /// Neither the command line nor the config give the number of state roots to retain.
+    #[error(
+        "the number of state roots to retain must be given, either on the command line or as \
+        `contract_runtime.gc_retained_state_roots` in the config"
+    )]
+    MissingRetainedStateRoots,
+
+    /// There is no complete block whose global state to retain in storage.
+    #[error("no complete block found in storage")]
+    NoCompleteBlock,
+}
+
+/// Deletes the tries which aren't reachable from the state roots of the given number of most
+/// recent complete blocks, or of the number given by the config if not given.
+///
+/// The node must not be running.
+pub(crate) fn collect_garbage(
+    config: WithDir<Config>,
+    chainspec: Arc<Chainspec>,
+    retained_state_roots: Option<u64>,
+) -> Result<GcProgress, Error> {
+    let retained_state_roots = retained_state_roots
+        .filter(|count| *count > 0)
+        .or_else(|| config.value().contract_runtime.gc_retained_state_roots())
+        .ok_or(Error::MissingRetainedStateRoots)?;
+    let batch_size = config.value().contract_runtime.gc_batch_size_or_default();
+    let (storage, contract_runtime) = open(config, chainspec)?;
+
+    let state_roots = recent_state_roots(&storage, retained_state_roots)?;
+    info!(
+        retained_state_roots = state_roots.len(),
+        "collecting trie store garbage"
+    );
+    Ok(contract_runtime.collect_garbage(&state_roots, batch_size)?)
+}
+
+/// Returns the state roots of the blocks at the `count` highest heights up to the highest complete
+/// block.
+fn recent_state_roots(storage: &Storage, count: u64) -> Result<Vec<Digest>, Error> {
+    let highest_height = storage
+        .highest_complete_block_height()
+        .ok_or(Error::NoCompleteBlock)?;
+    let lowest_height = highest_height.saturating_sub(count.saturating_sub(1));
+    let mut state_roots = vec![];
+    for height in lowest_height..=highest_height {
+        if let Some(header) = storage.read_block_header_by_height(height, false)? {
+            state_roots.push(*header.state_root_hash());
+        }
+    }
+    Ok(state_roots)
+}
+
+fn open(
+    config: WithDir<Config>,
+    chainspec: Arc<Chainspec>,
+) -> Result<(Storage, ContractRuntime), Error> {
+    let root_dir = config.dir();
+    let config = config.value();
+    let storage_config = WithDir::new(root_dir, config.storage.clone());
+    let storage = Storage::new(
+        &storage_config,
+        None,
+        chainspec.protocol_version(),
+        chainspec.protocol_config.activation_point.era_id(),
+        &chainspec.network_config.name,
+        chainspec.transaction_config.max_ttl.into(),
+        chainspec.core_config.recent_era_count(),
+        None,
+        false,
+        chainspec.transaction_config.clone(),
+    )?;
+    let contract_runtime = ContractRuntime::new(
+        storage.root_path(),
+        &config.contract_runtime,
+        chainspec,
+        &Registry::new(),
+    )?;
+    Ok((storage, contract_runtime))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use casper_storage::{
+        block_store::types::ApprovalsHashes,
+        global_state::{
+            gc::GcPhase,
+            state::{CommitProvider, StateProvider, StateReader},
+        },
+    };
+    use casper_types::{
+        execution::{Effects, TransformKindV2, TransformV2},
+        global_state::TrieMerkleProof,
+        testing::TestRng,
+        Block, CLValue, ChainspecRawBytes, Key, StoredValue, TestBlockBuilder,
+    };
+
+    use super::*;
+    use crate::utils::Loadable;
+
+    fn test_value(value: u64) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(value).unwrap())
+    }
+
+    /// Stores a complete block at each height, each with the global state of a new generation
+    /// of values, returning the state roots.
+    fn store_blocks(
+        storage: &mut Storage,
+        contract_runtime: &ContractRuntime,
+        heights: u64,
+        rng: &mut TestRng,
+    ) -> Vec<Digest> {
+        let data_access_layer = contract_runtime.data_access_layer();
+        let mut state_root_hash = data_access_layer.empty_root();
+        let mut state_roots = vec![];
+        for height in 0..heights {
+            let mut effects = Effects::new();
+            for index in 0..10_u8 {
+                effects.push(TransformV2::new(
+                    Key::Hash([index; 32]),
+                    TransformKindV2::Write(test_value(height)),
+                ));
+            }
+            state_root_hash = data_access_layer
+                .commit_effects(state_root_hash, effects)
+                .unwrap();
+            state_roots.push(state_root_hash);
+
+            let block = Block::from(
+                TestBlockBuilder::new()
+                    .height(height)
+                    .era(0)
+                    .switch_block(false)
+                    .state_root_hash(state_root_hash)
+                    .build(rng),
+            );
+            let approvals_hashes = ApprovalsHashes::new(
+                *block.hash(),
+                vec![],
+                TrieMerkleProof::new(
+                    Key::ChecksumRegistry,
+                    StoredValue::CLValue(CLValue::from_t(()).unwrap()),
+                    Default::default(),
+                ),
+            );
+            storage
+                .put_executed_block(
+                    Default::default(),
+                    &block,
+                    &approvals_hashes,
+                    Default::default(),
+                )
+                .unwrap();
+            storage.mark_block_complete(height).unwrap();
+        }
+        state_roots
+    }
+
+    fn read_value(contract_runtime: &ContractRuntime, state_root_hash: Digest) -> Option<u64> {
+        let reader = contract_runtime
+            .data_access_layer()
+            .state()
+            .checkout(state_root_hash)
+            .unwrap()?;
+        match reader.read(&Key::Hash([0; 32])).unwrap() {
+            Some(StoredValue::CLValue(value)) => Some(value.into_t().unwrap()),
+            other => panic!("unexpected value {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_retain_global_state_of_most_recent_complete_blocks() {
+        let rng = &mut TestRng::new();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        let chainspec = Arc::new(chainspec);
+        let mut config = Config::default();
+        config.storage.path = temp_dir.path().join("storage");
+
+        let state_roots = {
+            let (mut storage, contract_runtime) = open(
+                WithDir::new(temp_dir.path(), config.clone()),
+                Arc::clone(&chainspec),
+            )
+            .unwrap();
+            store_blocks(&mut storage, &contract_runtime, 5, rng)
+        };
+
+        assert!(matches!(
+            collect_garbage(
+                WithDir::new(temp_dir.path(), config.clone()),
+                Arc::clone(&chainspec),
+                None
+            ),
+            Err(Error::MissingRetainedStateRoots)
+        ));
+        let progress = collect_garbage(
+            WithDir::new(temp_dir.path(), config.clone()),
+            Arc::clone(&chainspec),
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(progress.phase, GcPhase::Idle);
+        assert!(progress.tries_deleted > 0);
+
+        let (_storage, contract_runtime) =
+            open(WithDir::new(temp_dir.path(), config), chainspec).unwrap();
+        for (height, state_root_hash) in iter::zip(0.., state_roots) {
+            let expected = (height >= 3).then_some(height);
+            assert_eq!(read_value(&contract_runtime, state_root_hash), expected);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! Export and import of global state snapshots.
+//!
+//! Fetching the global state of a recent block trie by trie from peers can take many hours.  A
+//! snapshot holds the global state of a single block instead, and can be exported by any node
+//! and imported by a new one before it is started.  The header of the block is stored in the
+//! snapshot, and is only imported if it hashes to the node's trusted hash, so the snapshot itself
+//! needn't be trusted.  Once started, the node's block synchronizer finds the global state of the
+//! trusted block already present, and carries on from there.
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+    sync::Arc,
+};
+
+use prometheus::Registry;
+use thiserror::Error;
+use tracing::info;
+
+use casper_storage::global_state::snapshot::{SnapshotError, SnapshotReader, SnapshotStats};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    BlockHash, BlockHeader, Chainspec, Digest,
+};
+
+use crate::{
+    components::{
+        contract_runtime::{ConfigError, ContractRuntime},
+        storage::{FatalStorageError, Storage},
+    },
+    reactor::main_reactor::Config,
+    utils::WithDir,
+};
+
+/// Error returned as a result of exporting or importing a snapshot.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// Error opening the storage.
+    #[error("error opening storage: {0}")]
+    Storage(#[from] FatalStorageError),
+
+    /// Error opening the global state.
+    #[error("error opening global state: {0}")]
+    ContractRuntime(#[from] ConfigError),
+
+    /// Error opening the snapshot file.
+    #[error("error opening snapshot file: {0}")]
+    File(#[from] std::io::Error),
+
+    /// Error exporting or importing the snapshot.
+    #[error(transparent)]
+    Snapshot(#[from] SnapshotError),
+
+    /// The block to export is not in storage.
+    #[error("block at height {0} not found in storage")]
+    MissingBlock(u64),
+
+    /// There is no complete block to export in storage.
+    #[error("no complete block found in storage")]
+    NoCompleteBlock,
+
+    /// The header of the block of the snapshot could not be encoded or decoded.
+    #[error("invalid snapshot block header: {0}")]
+    BlockHeader(bytesrepr::Error),
+
+    /// No trusted hash to verify the snapshot against was given.
+    #[error("a trusted hash is required to import a snapshot")]
+    MissingTrustedHash,
+
+    /// The block of the snapshot is not the trusted block.
+    #[error("snapshot is for block {snapshot_block_hash}, not for trusted block {trusted_hash}")]
+    UntrustedBlock {
+        /// The hash of the block of the snapshot.
+        snapshot_block_hash: BlockHash,
+        /// The trusted hash.
+        trusted_hash: BlockHash,
+    },
+
+    /// The state root of the snapshot is not the one of its block.
+    #[error(
+        "snapshot is for state root {snapshot_state_root_hash}, but its block has state root \
+        {block_state_root_hash}"
+    )]
+    StateRootMismatch {
+        /// The state root hash of the snapshot.
+        snapshot_state_root_hash: Digest,
+        /// The state root hash of the block of the snapshot.
+        block_state_root_hash: Digest,
+    },
+}
+
+/// Exports the global state of the block at the given height, or of the highest complete block if
+/// not given, to a snapshot file.
+pub(crate) fn export_snapshot(
+    config: WithDir<Config>,
+    chainspec: Arc<Chainspec>,
+    height: Option<u64>,
+    output: &Path,
+) -> Result<SnapshotStats, Error> {
+    let (storage, contract_runtime) = open(config, chainspec)?;
+    let block = match height {
+        Some(height) => storage
+            .get_block_by_height(height)?
+            .ok_or(Error::MissingBlock(height))?,
+        None => storage
+            .get_highest_complete_block()?
+            .ok_or(Error::NoCompleteBlock)?,
+    };
+    let block_header = block.take_header();
+    let metadata = block_header.to_bytes().map_err(Error::BlockHeader)?;
+
+    info!(
+        block_hash = %block_header.block_hash(),
+        height = block_header.height(),
+        state_root_hash = %block_header.state_root_hash(),
+        "exporting snapshot"
+    );
+    let writer = BufWriter::new(File::create(output)?);
+    Ok(contract_runtime.export_snapshot(*block_header.state_root_hash(), &metadata, writer)?)
+}
+
+/// Imports a snapshot file into the global state, provided it is for the trusted block.
+///
+/// The trusted hash given overrides the one of the node config.
+pub(crate) fn import_snapshot(
+    config: WithDir<Config>,
+    chainspec: Arc<Chainspec>,
+    snapshot_path: &Path,
+    trusted_hash: Option<BlockHash>,
+) -> Result<SnapshotStats, Error> {
+    let trusted_hash = trusted_hash
+        .or(config.value().node.trusted_hash)
+        .ok_or(Error::MissingTrustedHash)?;
+
+    let reader = SnapshotReader::new(BufReader::new(File::open(snapshot_path)?))?;
+    let block_header = verify_header(
+        reader.header().state_root_hash,
+        reader.header().metadata.as_ref(),
+        trusted_hash,
+    )?;
+
+    info!(
+        block_hash = %trusted_hash,
+        height = block_header.height(),
+        state_root_hash = %block_header.state_root_hash(),
+        "importing snapshot"
+    );
+    let (_storage, contract_runtime) = open(config, chainspec)?;
+    Ok(contract_runtime.import_snapshot(reader)?)
+}
+
+/// Checks that the block header stored in a snapshot is the trusted one, and that its state root
+/// is the one of the snapshot.
+fn verify_header(
+    snapshot_state_root_hash: Digest,
+    metadata: &[u8],
+    trusted_hash: BlockHash,
+) -> Result<BlockHeader, Error> {
+    let block_header: BlockHeader =
+        bytesrepr::deserialize_from_slice(metadata).map_err(Error::BlockHeader)?;
+    let snapshot_block_hash = block_header.block_hash();
+    if snapshot_block_hash != trusted_hash {
+        return Err(Error::UntrustedBlock {
+            snapshot_block_hash,
+            trusted_hash,
+        });
+    }
+    if *block_header.state_root_hash() != snapshot_state_root_hash {
+        return Err(Error::StateRootMismatch {
+            snapshot_state_root_hash,
+            block_state_root_hash: *block_header.state_root_hash(),
+        });
+    }
+    Ok(block_header)
+}
+
+fn open(
+    config: WithDir<Config>,
+    chainspec: Arc<Chainspec>,
+) -> Result<(Storage, ContractRuntime), Error> {
+    let root_dir = config.dir();
+    let config = config.value();
+    let storage_config = WithDir::new(root_dir, config.storage.clone());
+    let storage = Storage::new(
+        &storage_config,
+        None,
+        chainspec.protocol_version(),
+        chainspec.protocol_config.activation_point.era_id(),
+        &chainspec.network_config.name,
+        chainspec.transaction_config.max_ttl.into(),
+        chainspec.core_config.recent_era_count(),
+        None,
+        false,
+        chainspec.transaction_config.clone(),
+    )?;
+    let contract_runtime = ContractRuntime::new(
+        storage.root_path(),
+        &config.contract_runtime,
+        chainspec,
+        &Registry::new(),
+    )?;
+    Ok((storage, contract_runtime))
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{testing::TestRng, Block, TestBlockBuilder};
+
+    use super::*;
+
+    #[test]
+    fn should_only_accept_trusted_block_header() {
+        let rng = &mut TestRng::new();
+        let block = TestBlockBuilder::new().build(rng);
+        let block_header = Block::from(block).take_header();
+        let metadata = block_header.to_bytes().unwrap();
+        let state_root_hash = *block_header.state_root_hash();
+
+        assert_eq!(
+            verify_header(state_root_hash, &metadata, block_header.block_hash()).unwrap(),
+            block_header
+        );
+        assert!(matches!(
+            verify_header(state_root_hash, &metadata, BlockHash::random(rng)),
+            Err(Error::UntrustedBlock { .. })
+        ));
+        assert!(matches!(
+            verify_header(Digest::random(rng), &metadata, block_header.block_hash()),
+            Err(Error::StateRootMismatch { .. })
+        ));
+    }
+}
@@ -22,6 +22,7 @@
 )]
 #![allow(clippy::bool_comparison)]
 
+mod block_replay;
 pub mod cli;
 pub(crate) mod components;
 mod config_migration;
@@ -29,10 +30,14 @@ mod data_migration;
 pub(crate) mod effect;
 #[cfg_attr(not(feature = "failpoints"), path = "failpoints_disabled.rs")]
 pub(crate) mod failpoints;
+mod global_state_gc;
+mod global_state_snapshot;
 
 pub mod logging;
 pub(crate) mod protocol;
 pub(crate) mod reactor;
+pub mod signer;
+mod storage_check;
 #[cfg(test)]
 pub(crate) mod testing;
 pub(crate) mod tls;
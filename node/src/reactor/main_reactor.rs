@@ -35,7 +35,9 @@ use casper_types::{
 use crate::testing::network::NetworkedReactor;
 use crate::{
     components::{
-        binary_port::{BinaryPort, BinaryPortInitializationError, Metrics as BinaryPortMetrics},
+        binary_port::{
+            self, BinaryPort, BinaryPortInitializationError, Metrics as BinaryPortMetrics,
+        },
         block_accumulator::{self, BlockAccumulator},
         block_synchronizer::{self, BlockSynchronizer},
         block_validator::{self, BlockValidator},
@@ -81,7 +83,8 @@ use crate::{
         EventQueueHandle, QueueKind,
     },
     types::{
-        ForwardMetaBlock, MetaBlock, MetaBlockState, SyncHandling, TrieOrChunk, ValidatorMatrix,
+        ForwardMetaBlock, MetaBlock, MetaBlockState, PeerReputation, SyncHandling, TrieOrChunk,
+        ValidatorMatrix,
     },
     utils::{Source, WithDir},
     NodeRng,
@@ -541,6 +544,17 @@ impl reactor::Reactor for MainReactor {
                         effect_builder,
                         rng,
                         event_stream_server::Event::FinalitySignature(Box::new(
+                            (*finality_signature).clone().into(),
+                        )),
+                    ),
+                ));
+
+                effects.extend(reactor::wrap_effects(
+                    MainEvent::BinaryPort,
+                    self.binary_port.handle_event(
+                        effect_builder,
+                        rng,
+                        binary_port::Event::FinalitySignature(Box::new(
                             (*finality_signature).into(),
                         )),
                     ),
@@ -1092,7 +1106,7 @@ impl reactor::Reactor for MainReactor {
 
         let trusted_hash = config.value().node.trusted_hash;
         let (root_dir, config) = config.into_parts();
-        let (our_secret_key, our_public_key) = config.consensus.load_keys(&root_dir)?;
+        let signer = config.consensus.load_signer(&root_dir)?;
         let validator_matrix = ValidatorMatrix::new(
             chainspec.core_config.finality_threshold_fraction,
             chainspec.name_hash(),
@@ -1102,12 +1116,13 @@ impl reactor::Reactor for MainReactor {
                 .as_ref()
                 .and_then(|global_state_update| global_state_update.validators.clone()),
             chainspec.protocol_config.activation_point.era_id(),
-            our_secret_key.clone(),
-            our_public_key.clone(),
+            Arc::clone(&signer),
             chainspec.core_config.auction_delay,
             chainspec.core_config.signature_rewards_max_delay,
         );
 
+        let peer_reputation = PeerReputation::new();
+
         let storage_config = WithDir::new(&root_dir, config.storage.clone());
 
         let hard_reset_to_start_of_era = chainspec.hard_reset_to_start_of_era();
@@ -1136,11 +1151,13 @@ impl reactor::Reactor for MainReactor {
         let network = Network::new(
             config.network.clone(),
             network_identity,
-            Some((our_secret_key, our_public_key)),
+            Some(signer),
             registry,
             chainspec.as_ref(),
             validator_matrix.clone(),
+            peer_reputation.clone(),
             allow_handshake,
+            Some(storage.root_path()),
         )?;
 
         let address_gossiper = Gossiper::<{ GossipedAddress::ID_IS_COMPLETE_ITEM }, _>::new(
@@ -1171,8 +1188,8 @@ impl reactor::Reactor for MainReactor {
         let shutdown_trigger = ShutdownTrigger::new();
 
         // local / remote data management
-        let sync_leaper = SyncLeaper::new(chainspec.clone(), registry)?;
-        let fetchers = Fetchers::new(&config.fetcher, registry)?;
+        let sync_leaper = SyncLeaper::new(chainspec.clone(), peer_reputation.clone(), registry)?;
+        let fetchers = Fetchers::new(&config.fetcher, registry, &peer_reputation)?;
 
         // gossipers
         let block_gossiper = Gossiper::<{ BlockV2::ID_IS_COMPLETE_ITEM }, _>::new(
@@ -1216,6 +1233,7 @@ impl reactor::Reactor for MainReactor {
             chainspec.clone(),
             chainspec.core_config.simultaneous_peer_requests,
             validator_matrix.clone(),
+            peer_reputation,
             registry,
         )?;
         let block_validator = BlockValidator::new(
@@ -1234,7 +1252,7 @@ impl reactor::Reactor for MainReactor {
         let transaction_buffer =
             TransactionBuffer::new(Arc::clone(&chainspec), config.transaction_buffer, registry)?;
 
-        let reactor = MainReactor {
+        let mut reactor = MainReactor {
             chainspec,
             chainspec_raw_bytes,
             storage,
@@ -1288,7 +1306,7 @@ impl reactor::Reactor for MainReactor {
         // shut down immediately for upgrade.
         let should_upgrade_immediately = reactor.upgrade_watcher.next_upgrade_activation_point()
             == Some(reactor.chainspec.protocol_config.activation_point.era_id());
-        let effects = if should_upgrade_immediately {
+        let mut effects = if should_upgrade_immediately {
             info!("MainReactor: immediate shutdown for upgrade");
             effect_builder
                 .immediately()
@@ -1298,6 +1316,11 @@ impl reactor::Reactor for MainReactor {
                 .immediately()
                 .event(|()| MainEvent::ReactorCrank)
         };
+        // Resume pruning any blocks left outside the retained eras.
+        effects.extend(reactor::wrap_effects(
+            MainEvent::Storage,
+            reactor.storage.schedule_pruning(effect_builder),
+        ));
         Ok((reactor, effects))
     }
 
@@ -1691,6 +1714,14 @@ impl MainReactor {
                 event_stream_server::Event::BlockAdded(Arc::clone(&versioned_block)),
             ),
         ));
+        effects.extend(reactor::wrap_effects(
+            MainEvent::BinaryPort,
+            self.binary_port.handle_event(
+                effect_builder,
+                rng,
+                binary_port::Event::BlockAdded(Arc::clone(&versioned_block)),
+            ),
+        ));
 
         match &meta_block {
             MetaBlock::Forward(fwd_meta_block) => {
@@ -1708,6 +1739,19 @@ impl MainReactor {
                         self.event_stream_server
                             .handle_event(effect_builder, rng, event),
                     ));
+
+                    let event = binary_port::Event::TransactionProcessed {
+                        transaction_hash: exec_artifact.transaction_hash,
+                        transaction_header: Box::new(exec_artifact.transaction_header.clone()),
+                        block_hash: *fwd_meta_block.block.hash(),
+                        block_height: fwd_meta_block.block.height(),
+                        execution_result: Box::new(exec_artifact.execution_result.clone()),
+                        messages: Some(exec_artifact.messages.clone()),
+                    };
+                    effects.extend(reactor::wrap_effects(
+                        MainEvent::BinaryPort,
+                        self.binary_port.handle_event(effect_builder, rng, event),
+                    ));
                 }
             }
             MetaBlock::Historical(historical_meta_block) => {
@@ -1726,6 +1770,20 @@ impl MainReactor {
                         self.event_stream_server
                             .handle_event(effect_builder, rng, event),
                     ));
+
+                    let event = binary_port::Event::TransactionProcessed {
+                        transaction_hash: *transaction_hash,
+                        transaction_header: Box::new(transaction_header.clone()),
+                        block_hash: *historical_meta_block.block.hash(),
+                        block_height: historical_meta_block.block.height(),
+                        execution_result: Box::new(execution_result.clone()),
+                        // The messages of historically synced blocks aren't known.
+                        messages: None,
+                    };
+                    effects.extend(reactor::wrap_effects(
+                        MainEvent::BinaryPort,
+                        self.binary_port.handle_event(effect_builder, rng, event),
+                    ));
                 }
             }
         }
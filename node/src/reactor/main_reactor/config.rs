@@ -71,6 +71,25 @@ impl Config {
             self.transaction_acceptor.timestamp_leeway =
                 chainspec.transaction_config.max_timestamp_leeway;
         }
+
+        if let Some(retained_era_count) = self.storage.pruning_retained_era_count {
+            if self.node.sync_handling.is_sync_to_genesis() {
+                error!(
+                    "disabling 'storage.pruning_retained_era_count' as 'node.sync_handling' is set \
+                    to 'genesis'",
+                );
+                self.storage.pruning_retained_era_count = None;
+            } else if retained_era_count < chainspec.core_config.recent_era_count() {
+                error!(
+                    configured_retained_era_count = retained_era_count,
+                    recent_era_count = chainspec.core_config.recent_era_count(),
+                    "setting value for 'storage.pruning_retained_era_count' to minimum permitted \
+                    by chainspec 'core.unbonding_delay' - 'core.auction_delay'",
+                );
+                self.storage.pruning_retained_era_count =
+                    Some(chainspec.core_config.recent_era_count());
+            }
+        }
     }
 
     /// Set network config.
@@ -1,7 +1,7 @@
 use thiserror::Error;
 
 use casper_execution_engine::engine_state;
-use casper_types::{bytesrepr, crypto::ErrorExt as CryptoError};
+use casper_types::bytesrepr;
 
 use crate::{
     components::{
@@ -9,7 +9,8 @@ use crate::{
         contract_runtime::{self, BlockExecutionError},
         diagnostics_port, network, storage, upgrade_watcher,
     },
-    utils::{ListeningError, LoadError},
+    signer::SignerError,
+    utils::ListeningError,
 };
 
 /// Error type returned by the validator reactor.
@@ -59,9 +60,9 @@ pub(crate) enum Error {
     #[error("diagnostics port: {0}")]
     DiagnosticsPort(#[from] diagnostics_port::Error),
 
-    /// Error while loading the signing key pair.
-    #[error("signing key pair load error: {0}")]
-    LoadSigningKeyPair(#[from] LoadError<CryptoError>),
+    /// Error while setting up the validator signer.
+    #[error("validator signer error: {0}")]
+    Signer(#[from] SignerError),
 
     /// `BinaryPort` component error.
     #[error("binary port: {0}")]
@@ -8,7 +8,7 @@ use crate::{
     effect::{announcements::TransactionAcceptorAnnouncement, EffectBuilder, Effects},
     reactor,
     reactor::main_reactor::MainEvent,
-    types::{BlockExecutionResultsOrChunk, LegacyDeploy, SyncLeap, TrieOrChunk},
+    types::{BlockExecutionResultsOrChunk, LegacyDeploy, PeerReputation, SyncLeap, TrieOrChunk},
     utils::Source,
     FetcherConfig, NodeRng,
 };
@@ -31,24 +31,62 @@ impl Fetchers {
     pub(super) fn new(
         config: &FetcherConfig,
         metrics_registry: &Registry,
+        peer_reputation: &PeerReputation,
     ) -> Result<Self, prometheus::Error> {
         Ok(Fetchers {
-            sync_leap_fetcher: Fetcher::new("sync_leap_fetcher", config, metrics_registry)?,
-            block_header_by_hash_fetcher: Fetcher::new("block_header", config, metrics_registry)?,
-            approvals_hashes_fetcher: Fetcher::new("approvals_hashes", config, metrics_registry)?,
+            sync_leap_fetcher: Fetcher::new(
+                "sync_leap_fetcher",
+                config,
+                metrics_registry,
+                peer_reputation.clone(),
+            )?,
+            block_header_by_hash_fetcher: Fetcher::new(
+                "block_header",
+                config,
+                metrics_registry,
+                peer_reputation.clone(),
+            )?,
+            approvals_hashes_fetcher: Fetcher::new(
+                "approvals_hashes",
+                config,
+                metrics_registry,
+                peer_reputation.clone(),
+            )?,
             finality_signature_fetcher: Fetcher::new(
                 "finality_signature_fetcher",
                 config,
                 metrics_registry,
+                peer_reputation.clone(),
+            )?,
+            legacy_deploy_fetcher: Fetcher::new(
+                "legacy_deploy",
+                config,
+                metrics_registry,
+                peer_reputation.clone(),
+            )?,
+            block_fetcher: Fetcher::new(
+                "block",
+                config,
+                metrics_registry,
+                peer_reputation.clone(),
+            )?,
+            transaction_fetcher: Fetcher::new(
+                "transaction",
+                config,
+                metrics_registry,
+                peer_reputation.clone(),
+            )?,
+            trie_or_chunk_fetcher: Fetcher::new(
+                "trie_or_chunk",
+                config,
+                metrics_registry,
+                peer_reputation.clone(),
             )?,
-            legacy_deploy_fetcher: Fetcher::new("legacy_deploy", config, metrics_registry)?,
-            block_fetcher: Fetcher::new("block", config, metrics_registry)?,
-            transaction_fetcher: Fetcher::new("transaction", config, metrics_registry)?,
-            trie_or_chunk_fetcher: Fetcher::new("trie_or_chunk", config, metrics_registry)?,
             block_execution_results_or_chunk_fetcher: Fetcher::new(
                 "block_execution_results_or_chunk_fetcher",
                 config,
                 metrics_registry,
+                peer_reputation.clone(),
             )?,
         })
     }
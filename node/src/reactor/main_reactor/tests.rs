@@ -588,6 +588,7 @@ impl TestFixture {
                 allow_request_get_all_values: true,
                 allow_request_get_trie: true,
                 allow_request_speculative_exec: true,
+                allow_request_get_state_diff: true,
                 ..Default::default()
             },
             ..Default::default()
@@ -613,6 +614,9 @@ impl TestFixture {
         cfg.node.trusted_hash = maybe_trusted_hash;
         cfg.contract_runtime.max_global_state_size =
             Some(1024 * 1024 * storage_multiplier as usize);
+        // Always execute the transactions of a block on several threads, so that the speculative
+        // parallel execution is covered regardless of the number of CPUs.
+        cfg.contract_runtime.execution_threads = Some(4);
 
         (cfg, temp_dir)
     }
@@ -10,10 +10,11 @@ use casper_binary_port::{
     AccountInformation, AddressableEntityInformation, BalanceResponse, BinaryMessage,
     BinaryMessageCodec, BinaryResponse, BinaryResponseAndRequest, Command, CommandHeader,
     ConsensusStatus, ConsensusValidatorChanges, ContractInformation, DictionaryItemIdentifier,
-    DictionaryQueryResult, EntityIdentifier, EraIdentifier, ErrorCode, GetRequest,
+    DictionaryQueryResult, EntityIdentifier, EraIdentifier, ErrorCode, GasEstimate, GetRequest,
     GetTrieFullResult, GlobalStateEntityQualifier, GlobalStateQueryResult, GlobalStateRequest,
     InformationRequest, InformationRequestTag, KeyPrefix, LastProgress, NetworkName, NodeStatus,
-    PackageIdentifier, PurseIdentifier, ReactorStateName, RecordId, ResponseType, RewardResponse,
+    PackageIdentifier, PaymentRecommendation, PurseIdentifier, ReactorStateName, RecordId,
+    ResponseType, RewardResponse, StateDiff, StateDiffEntry, StateDiffRequest, StateOverride,
     Uptime, ValueWithProof,
 };
 use casper_storage::global_state::state::CommitProvider;
@@ -23,15 +24,16 @@ use casper_types::{
     bytesrepr::{Bytes, FromBytes, ToBytes},
     contracts::{ContractHash, ContractPackage, ContractPackageHash},
     execution::{Effects, TransformKindV2, TransformV2},
+    global_state::TrieMultiProof,
     system::auction::DelegatorKind,
     testing::TestRng,
     Account, AddressableEntity, AvailableBlockRange, Block, BlockHash, BlockHeader,
     BlockIdentifier, BlockSynchronizerStatus, BlockWithSignatures, ByteCode, ByteCodeAddr,
     ByteCodeHash, ByteCodeKind, CLValue, CLValueDictionary, ChainspecRawBytes, Contract,
-    ContractRuntimeTag, ContractWasm, ContractWasmHash, DictionaryAddr, Digest, EntityAddr,
+    ContractRuntimeTag, ContractWasm, ContractWasmHash, Deploy, DictionaryAddr, Digest, EntityAddr,
     EntityKind, EntityVersions, GlobalStateIdentifier, Key, KeyTag, NextUpgrade, Package,
-    PackageAddr, PackageHash, Peers, ProtocolVersion, PublicKey, Rewards, SecretKey, StoredValue,
-    Transaction, Transfer, URef, U512,
+    PackageAddr, PackageHash, Peers, PricingMode, ProtocolVersion, PublicKey, Rewards, SecretKey,
+    StoredValue, Transaction, Transfer, URef, MINT_LANE_ID, U512,
 };
 use futures::{SinkExt, StreamExt};
 use rand::Rng;
@@ -96,6 +98,11 @@ async fn setup() -> (
     )
     .await;
     let chainspec_raw_bytes = ChainspecRawBytes::clone(&fixture.chainspec_raw_bytes);
+    let node_secret_keys: Vec<_> = fixture
+        .node_contexts
+        .iter()
+        .map(|node_context| (node_context.id, Arc::clone(&node_context.secret_key)))
+        .collect();
     let mut rng = fixture.rng_mut().create_child();
     let net = fixture.network_mut();
     net.settle_on(
@@ -104,16 +111,15 @@ async fn setup() -> (
         Duration::from_secs(59),
     )
     .await;
-    let (_, first_node) = net
+    let (first_node_id, first_node) = net
         .nodes()
         .iter()
         .next()
         .expect("should have at least one node");
-    let secret_signing_key = first_node
-        .main_reactor()
-        .validator_matrix
-        .secret_signing_key()
-        .clone();
+    let secret_signing_key = node_secret_keys
+        .into_iter()
+        .find_map(|(node_id, secret_key)| (node_id == *first_node_id).then_some(secret_key))
+        .expect("should have the first node's secret key");
     let highest_block = net
         .nodes()
         .iter()
@@ -437,6 +443,16 @@ async fn binary_port_component_handles_all_requests() {
         get_era_summary(state_root_hash),
         get_all_bids(state_root_hash),
         get_trie(state_root_hash),
+        get_state_diff(
+            *highest_block.state_root_hash(),
+            state_root_hash,
+            effects.pre_migration_account_hash,
+        ),
+        get_items_with_multi_proof(
+            state_root_hash,
+            effects.pre_migration_account_hash,
+            Key::dictionary(effects.dict_seed_uref, TEST_DICT_ITEM_KEY.as_bytes()),
+        ),
         get_dictionary_item_by_addr(
             state_root_hash,
             *Key::dictionary(effects.dict_seed_uref, TEST_DICT_ITEM_KEY.as_bytes())
@@ -461,6 +477,9 @@ async fn binary_port_component_handles_all_requests() {
             TEST_DICT_ITEM_KEY.to_owned(),
         ),
         try_spec_exec_invalid(&mut rng),
+        try_spec_exec_invalid_state_override(&mut rng, highest_block.height()),
+        estimate_gas_payment_limited(&mut rng, &secret_signing_key),
+        estimate_gas_fixed(&mut rng, &secret_signing_key),
         try_accept_transaction_invalid(&mut rng),
         try_accept_transaction(&secret_signing_key),
         get_balance(state_root_hash, effects.pre_migration_account_hash),
@@ -874,6 +893,64 @@ fn get_trie(digest: Digest) -> TestCase {
     }
 }
 
+fn get_state_diff(
+    from_state_root_hash: Digest,
+    to_state_root_hash: Digest,
+    account_hash: AccountHash,
+) -> TestCase {
+    TestCase {
+        name: "get_state_diff",
+        request: Command::Get(GetRequest::StateDiff(Box::new(StateDiffRequest::new(
+            from_state_root_hash,
+            to_state_root_hash,
+        )))),
+        asserter: Box::new(move |response| {
+            assert_response::<StateDiff, _>(response, Some(ResponseType::StateDiff), |res| {
+                res.next_page().is_none()
+                    && res.entries().iter().any(|entry| {
+                        matches!(
+                            entry,
+                            StateDiffEntry::Added { key, .. } if *key == Key::Account(account_hash)
+                        )
+                    })
+            })
+        }),
+    }
+}
+
+fn get_items_with_multi_proof(
+    state_root_hash: Digest,
+    account_hash: AccountHash,
+    dictionary_key: Key,
+) -> TestCase {
+    TestCase {
+        name: "get_items_with_multi_proof",
+        request: Command::Get(GetRequest::State(Box::new(GlobalStateRequest::new(
+            Some(GlobalStateIdentifier::StateRootHash(state_root_hash)),
+            GlobalStateEntityQualifier::ItemsWithMultiProof {
+                keys: vec![
+                    Key::Account(account_hash),
+                    dictionary_key,
+                    Key::Hash([0; 32]),
+                ],
+            },
+        )))),
+        asserter: Box::new(move |response| {
+            assert_response::<TrieMultiProof<Key, StoredValue>, _>(
+                response,
+                Some(ResponseType::GlobalStateMultiProof),
+                |proof| {
+                    let mut keys: Vec<_> = proof.entries().iter().map(|(key, _)| *key).collect();
+                    keys.sort();
+                    let mut expected = vec![Key::Account(account_hash), dictionary_key];
+                    expected.sort();
+                    keys == expected && proof.compute_state_hash() == Ok(state_root_hash)
+                },
+            )
+        }),
+    }
+}
+
 fn get_dictionary_item_by_addr(state_root_hash: Digest, addr: DictionaryAddr) -> TestCase {
     TestCase {
         name: "get_dictionary_item_by_addr",
@@ -1349,11 +1426,121 @@ fn try_spec_exec_invalid(rng: &mut TestRng) -> TestCase {
     let transaction = Transaction::V1(TransactionV1Builder::new_random(rng).build().unwrap());
     TestCase {
         name: "try_spec_exec_invalid",
-        request: Command::TrySpeculativeExec { transaction },
+        request: Command::TrySpeculativeExec {
+            transaction,
+            state_identifier: None,
+            state_overrides: vec![],
+            trace_execution: false,
+        },
         asserter: Box::new(|response| ErrorCode::try_from(response.error_code()).is_ok()),
     }
 }
 
+fn try_spec_exec_invalid_state_override(rng: &mut TestRng, block_height: u64) -> TestCase {
+    let transaction = Transaction::Deploy(Deploy::random(rng));
+    TestCase {
+        name: "try_spec_exec_invalid_state_override",
+        request: Command::TrySpeculativeExec {
+            transaction,
+            state_identifier: Some(GlobalStateIdentifier::BlockHeight(block_height)),
+            state_overrides: vec![StateOverride::ByteCode {
+                entity_addr: EntityAddr::SmartContract(rng.gen()),
+                byte_code: vec![0; 8].into(),
+            }],
+            trace_execution: false,
+        },
+        asserter: Box::new(|response| {
+            response.error_code() == ErrorCode::InvalidStateOverride as u16
+        }),
+    }
+}
+
+fn native_transfer(rng: &mut TestRng, key: &SecretKey, pricing_mode: PricingMode) -> Transaction {
+    Transaction::V1(
+        TransactionV1Builder::new_transfer(
+            U512::from(2_500_000_000u64),
+            None,
+            PublicKey::random(rng),
+            None,
+        )
+        .unwrap()
+        .with_pricing_mode(pricing_mode)
+        .with_secret_key(key)
+        .with_chain_name("casper-example")
+        .build()
+        .unwrap(),
+    )
+}
+
+fn estimate_gas_payment_limited(rng: &mut TestRng, key: &SecretKey) -> TestCase {
+    let transaction = native_transfer(
+        rng,
+        key,
+        PricingMode::PaymentLimited {
+            payment_amount: 1,
+            gas_price_tolerance: 5,
+            standard_payment: true,
+        },
+    );
+    TestCase {
+        name: "estimate_gas_payment_limited",
+        request: Command::EstimateGas {
+            transaction,
+            state_identifier: None,
+            state_overrides: vec![],
+        },
+        asserter: Box::new(|response| {
+            assert_response::<GasEstimate, _>(
+                response,
+                Some(ResponseType::GasEstimate),
+                |estimate| {
+                    // Native transactions consume their whole gas limit, which is recommended
+                    // as is.
+                    estimate.lane_id() == MINT_LANE_ID
+                        && estimate.consumed() == estimate.limit()
+                        && estimate.recommendation()
+                            == &PaymentRecommendation::PaymentLimited {
+                                payment_amount: estimate.consumed().value().as_u64(),
+                            }
+                },
+            )
+        }),
+    }
+}
+
+fn estimate_gas_fixed(rng: &mut TestRng, key: &SecretKey) -> TestCase {
+    let transaction = native_transfer(
+        rng,
+        key,
+        PricingMode::Fixed {
+            gas_price_tolerance: 5,
+            additional_computation_factor: 0,
+        },
+    );
+    TestCase {
+        name: "estimate_gas_fixed",
+        request: Command::EstimateGas {
+            transaction,
+            state_identifier: None,
+            state_overrides: vec![],
+        },
+        asserter: Box::new(|response| {
+            assert_response::<GasEstimate, _>(
+                response,
+                Some(ResponseType::GasEstimate),
+                |estimate| {
+                    estimate.lane_id() == MINT_LANE_ID
+                        && matches!(
+                            estimate.recommendation(),
+                            PaymentRecommendation::Fixed { gas_limit, .. }
+                                if *gas_limit == estimate.limit()
+                        )
+                },
+            )
+        }),
+    }
+}
+
 #[tokio::test]
 async fn binary_port_component_rejects_requests_with_invalid_header_version() {
     testing::init_logging();
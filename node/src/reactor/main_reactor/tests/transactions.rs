@@ -1,5 +1,8 @@
 use super::*;
-use crate::{testing::LARGE_WASM_LANE_ID, types::MetaTransaction};
+use crate::{
+    components::contract_runtime::ExecutionPreState, testing::LARGE_WASM_LANE_ID,
+    types::MetaTransaction,
+};
 use casper_storage::data_access_layer::{
     AddressableEntityRequest, BalanceIdentifier, ProofHandling, QueryRequest, QueryResult,
 };
@@ -4284,3 +4287,223 @@ async fn should_allow_custom_payment() {
         "should have consumed gas"
     );
 }
+
+fn transfer_to_charlie_txn(from: &SecretKey, amount: u64) -> Transaction {
+    let mut txn = Transaction::from(
+        TransactionV1Builder::new_transfer(amount, None, CHARLIE_PUBLIC_KEY.clone(), None)
+            .unwrap()
+            .with_initiator_addr(PublicKey::from(from))
+            .with_chain_name(CHAIN_NAME)
+            .build()
+            .unwrap(),
+    );
+    txn.sign(from);
+    txn
+}
+
+/// Injects the given transactions at once, runs until they are all executed successfully, and
+/// returns the number of them executed in each block.
+async fn execute_transactions_together(
+    fixture: &mut TestFixture,
+    txns: Vec<Transaction>,
+) -> BTreeMap<u64, usize> {
+    let txn_hashes = txns.iter().map(Transaction::hash).collect::<Vec<_>>();
+    for txn in txns {
+        fixture.inject_transaction(txn).await;
+    }
+    for txn_hash in &txn_hashes {
+        fixture
+            .run_until_executed_transaction(txn_hash, THIRTY_SECS)
+            .await;
+    }
+
+    let (_node_id, runner) = fixture.network.nodes().iter().next().unwrap();
+    let mut block_heights = BTreeMap::<u64, usize>::new();
+    for txn_hash in txn_hashes {
+        let exec_info = runner
+            .main_reactor()
+            .storage()
+            .read_execution_info(txn_hash)
+            .expect("Expected transaction to be included in a block.");
+        assert!(exec_result_is_success(
+            &exec_info
+                .execution_result
+                .expect("Exec result should have been stored.")
+        ));
+        *block_heights.entry(exec_info.block_height).or_default() += 1;
+    }
+    block_heights
+}
+
+/// Executes the stored block at the given height again, both sequentially and in parallel, checks
+/// that both executions reproduce the stored block, and returns the number of transactions
+/// executed again after conflicting with an earlier transaction during the parallel execution.
+fn assert_parallel_execution_matches_sequential(fixture: &TestFixture, block_height: u64) -> u64 {
+    let (_node_id, runner) = fixture.network.nodes().iter().next().unwrap();
+    let storage = runner.main_reactor().storage();
+    let contract_runtime = runner.main_reactor().contract_runtime();
+
+    let block = storage.get_block_by_height(block_height).unwrap().unwrap();
+    let parent = storage
+        .get_block_by_height(block_height - 1)
+        .unwrap()
+        .unwrap();
+    let execution_pre_state = ExecutionPreState::new(
+        block_height,
+        *parent.state_root_hash(),
+        *parent.hash(),
+        *parent.accumulated_seed(),
+    );
+    let executable_block = storage
+        .make_executable_block(block.hash())
+        .unwrap()
+        .unwrap();
+    let last_switch_block_hash = storage
+        .get_switch_block_by_era_id(&block.era_id().predecessor().unwrap())
+        .unwrap()
+        .map(|switch_block| *switch_block.hash());
+    let replay = |execution_threads| {
+        contract_runtime
+            .replay_block_with_execution_threads(
+                execution_pre_state.clone(),
+                executable_block.clone(),
+                0,
+                last_switch_block_hash,
+                execution_threads,
+            )
+            .expect("should execute block")
+    };
+
+    let sequential = replay(1);
+    let reexecuted_before = contract_runtime.transactions_reexecuted();
+    let parallel = replay(4);
+    let reexecuted = contract_runtime.transactions_reexecuted() - reexecuted_before;
+
+    assert_eq!(sequential.block.state_root_hash(), block.state_root_hash());
+    assert_eq!(
+        sequential.block.state_root_hash(),
+        parallel.block.state_root_hash()
+    );
+    assert_eq!(sequential.execution_artifacts, parallel.execution_artifacts);
+    reexecuted
+}
+
+#[tokio::test]
+async fn parallel_execution_of_conflicting_transactions_matches_sequential_execution() {
+    let config = SingleTransactionTestCase::default_test_config()
+        .with_pricing_handling(PricingHandling::Fixed)
+        .with_refund_handling(RefundHandling::Refund {
+            refund_ratio: Ratio::new(1, 2),
+        })
+        .with_fee_handling(FeeHandling::PayToProposer);
+
+    let mut test = SingleTransactionTestCase::new(
+        ALICE_SECRET_KEY.clone(),
+        BOB_SECRET_KEY.clone(),
+        CHARLIE_SECRET_KEY.clone(),
+        Some(config),
+    )
+    .await;
+
+    test.fixture
+        .run_until_consensus_in_era(ERA_ONE, ONE_MIN)
+        .await;
+
+    const TRANSFER_AMOUNT: u64 = 30_000_000_000;
+    const DELEGATE_AMOUNT: u64 = 500_000_000_000;
+
+    let delegate = |from: &SecretKey, amount: u64| {
+        let mut txn = Transaction::from(
+            TransactionV1Builder::new_delegate(
+                PublicKey::from(from),
+                ALICE_PUBLIC_KEY.clone(),
+                amount,
+            )
+            .unwrap()
+            .with_initiator_addr(PublicKey::from(from))
+            .with_chain_name(CHAIN_NAME)
+            .build()
+            .unwrap(),
+        );
+        txn.sign(from);
+        txn
+    };
+
+    // Transfers from the same account and to the same purse, and delegations changing the same
+    // bid, all of which pay their fees to the same proposer.
+    let txns = vec![
+        transfer_to_charlie_txn(&ALICE_SECRET_KEY, TRANSFER_AMOUNT),
+        transfer_to_charlie_txn(&ALICE_SECRET_KEY, 2 * TRANSFER_AMOUNT),
+        transfer_to_charlie_txn(&BOB_SECRET_KEY, 3 * TRANSFER_AMOUNT),
+        delegate(&BOB_SECRET_KEY, DELEGATE_AMOUNT),
+        delegate(&BOB_SECRET_KEY, 2 * DELEGATE_AMOUNT),
+    ];
+    let block_heights = execute_transactions_together(&mut test.fixture, txns).await;
+    assert!(
+        block_heights.values().any(|count| *count > 1),
+        "Expected several of the transactions to be executed in the same block."
+    );
+
+    let mut reexecuted = 0;
+    for block_height in block_heights.into_keys() {
+        reexecuted += assert_parallel_execution_matches_sequential(&test.fixture, block_height);
+    }
+    assert!(
+        reexecuted > 0,
+        "Expected some of the transactions to conflict."
+    );
+}
+
+#[tokio::test]
+async fn fees_and_refunds_do_not_make_parallel_transactions_conflict() {
+    let config = SingleTransactionTestCase::default_test_config()
+        .with_pricing_handling(PricingHandling::Fixed)
+        .with_refund_handling(RefundHandling::Refund {
+            refund_ratio: Ratio::new(1, 2),
+        })
+        .with_fee_handling(FeeHandling::Accumulate);
+
+    let mut test = SingleTransactionTestCase::new(
+        ALICE_SECRET_KEY.clone(),
+        BOB_SECRET_KEY.clone(),
+        CHARLIE_SECRET_KEY.clone(),
+        Some(config),
+    )
+    .await;
+
+    test.fixture
+        .run_until_consensus_in_era(ERA_ONE, ONE_MIN)
+        .await;
+
+    const TRANSFER_AMOUNT: u64 = 30_000_000_000;
+
+    // Transfers from different accounts to different purses, which only share the purses their
+    // fees and refunds are paid from and to.
+    let dave_public_key =
+        PublicKey::from(&SecretKey::ed25519_from_bytes([0xDD; SecretKey::ED25519_LENGTH]).unwrap());
+    let mut to_dave = Transaction::from(
+        TransactionV1Builder::new_transfer(TRANSFER_AMOUNT, None, dave_public_key, None)
+            .unwrap()
+            .with_initiator_addr(BOB_PUBLIC_KEY.clone())
+            .with_chain_name(CHAIN_NAME)
+            .build()
+            .unwrap(),
+    );
+    to_dave.sign(&BOB_SECRET_KEY);
+    let txns = vec![
+        transfer_to_charlie_txn(&ALICE_SECRET_KEY, TRANSFER_AMOUNT),
+        to_dave,
+    ];
+    let block_heights = execute_transactions_together(&mut test.fixture, txns).await;
+    assert_eq!(
+        block_heights.len(),
+        1,
+        "Expected the transactions to be executed in the same block."
+    );
+
+    let block_height = *block_heights.keys().next().unwrap();
+    assert_eq!(
+        assert_parallel_execution_matches_sequential(&test.fixture, block_height),
+        0
+    );
+}
@@ -0,0 +1,271 @@
+//! Signing with the validator's secret key.
+//!
+//! The validator key signs consensus messages, finality signatures and the consensus certificates
+//! proving our identity to peers. All of these are created by a [`ValidatorSigner`], which either
+//! holds the secret key in the node process ([`LocalSigner`]), or forwards the requests to an
+//! external signing daemon over a Unix or TCP socket ([`RemoteSigner`]). The latter allows keeping
+//! the key in a separate, hardened process; a reference implementation of such a daemon is the
+//! `casper-signer` binary.
+//!
+//! Signers are sent the full consensus messages rather than their hashes, and compute the hashes
+//! themselves. They refuse to sign a message conflicting with one they signed before, i.e. a
+//! second Highway unit with the same sequence number, a second Zug echo or vote in the same round,
+//! or a finality signature for a different block at the same height.
+
+mod config;
+mod daemon;
+mod history;
+mod protocol;
+mod remote;
+#[cfg(test)]
+mod tests;
+
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    io,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use casper_types::{
+    crypto::{self, ErrorExt as CryptoError},
+    BlockHash, ChainNameDigest, Digest, EraId, FinalitySignatureV2, PublicKey, SecretKey,
+    Signature, TimeDiff,
+};
+
+use crate::utils::LoadError;
+use history::SigningHistory;
+
+pub use config::{RemoteSignerConfig, SignerAddress};
+pub use daemon::run_daemon;
+pub(crate) use remote::RemoteSigner;
+
+/// A signer creating signatures with the validator's secret key.
+pub(crate) trait ValidatorSigner: Debug + Send + Sync {
+    /// Returns the validator's public key.
+    fn public_key(&self) -> &PublicKey;
+
+    /// Signs the given message.
+    ///
+    /// Fails if the signer is unavailable, or if the message conflicts with one signed before.
+    fn sign(&self, request: &SigningRequest) -> Result<Signature, SignerError>;
+}
+
+/// A consensus message to be signed, serialized the same way as for computing its hash.
+///
+/// The signer computes the hash itself, and decodes the message to check it for conflicts with
+/// the ones signed before.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsensusPayload {
+    /// A Highway unit: the serialized `WireUnit`.
+    HighwayUnit(Vec<u8>),
+    /// A Highway endorsement of another validator's unit: the serialized unit hash and endorser.
+    HighwayEndorsement(Vec<u8>),
+    /// A Highway ping, signaling that the validator is online: the serialized creator, timestamp
+    /// and instance ID.
+    HighwayPing(Vec<u8>),
+    /// A Zug echo or vote: the serialized round ID, instance ID, content and creator.
+    Zug(Vec<u8>),
+}
+
+impl ConsensusPayload {
+    /// Returns the serialized message.
+    pub fn serialized(&self) -> &[u8] {
+        match self {
+            ConsensusPayload::HighwayUnit(bytes)
+            | ConsensusPayload::HighwayEndorsement(bytes)
+            | ConsensusPayload::HighwayPing(bytes)
+            | ConsensusPayload::Zug(bytes) => bytes,
+        }
+    }
+}
+
+impl Display for ConsensusPayload {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            ConsensusPayload::HighwayUnit(_) => "Highway unit",
+            ConsensusPayload::HighwayEndorsement(_) => "Highway endorsement",
+            ConsensusPayload::HighwayPing(_) => "Highway ping",
+            ConsensusPayload::Zug(_) => "Zug message",
+        };
+        write!(formatter, "{} {}", kind, Digest::hash(self.serialized()))
+    }
+}
+
+/// A message to be signed with the validator's secret key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum SigningRequest {
+    /// A consensus message in the given era.
+    Consensus {
+        era_id: EraId,
+        payload: ConsensusPayload,
+    },
+    /// A finality signature for a block.
+    FinalitySignature {
+        block_hash: BlockHash,
+        block_height: u64,
+        era_id: EraId,
+        chain_name_hash: ChainNameDigest,
+    },
+    /// The ID of a connection to a peer, proving to the peer that we are a validator.
+    ConsensusCertificate { connection_id: Vec<u8> },
+}
+
+/// Prefix of the bytes signed for a consensus certificate.
+///
+/// The bytes signed for consensus messages and finality signatures start with a hash, so they
+/// can't start with this tag in practice, and certificates can't be passed off as either.
+const CONSENSUS_CERTIFICATE_TAG: &[u8] = b"casper-node consensus certificate:";
+
+/// Returns the bytes signed in a consensus certificate for the connection with the given ID.
+pub(crate) fn consensus_certificate_bytes(connection_id: &[u8]) -> Vec<u8> {
+    [CONSENSUS_CERTIFICATE_TAG, connection_id].concat()
+}
+
+impl SigningRequest {
+    /// Returns the bytes to be signed.
+    pub(crate) fn bytes_to_sign(&self) -> Vec<u8> {
+        match self {
+            SigningRequest::Consensus { payload, .. } => {
+                Digest::hash(payload.serialized()).as_ref().to_vec()
+            }
+            SigningRequest::FinalitySignature {
+                block_hash,
+                block_height,
+                era_id,
+                chain_name_hash,
+            } => FinalitySignatureV2::bytes_to_sign(
+                *block_hash,
+                *block_height,
+                *era_id,
+                *chain_name_hash,
+            ),
+            SigningRequest::ConsensusCertificate { connection_id } => {
+                consensus_certificate_bytes(connection_id)
+            }
+        }
+    }
+}
+
+impl Display for SigningRequest {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SigningRequest::Consensus { era_id, payload } => {
+                write!(formatter, "{} in {}", payload, era_id)
+            }
+            SigningRequest::FinalitySignature {
+                block_hash,
+                block_height,
+                era_id,
+                ..
+            } => write!(
+                formatter,
+                "finality signature for block {} at height {} in {}",
+                block_hash, block_height, era_id
+            ),
+            SigningRequest::ConsensusCertificate { .. } => {
+                write!(formatter, "consensus certificate")
+            }
+        }
+    }
+}
+
+/// Error creating a signature.
+#[derive(Debug, Error)]
+pub(crate) enum SignerError {
+    /// The secret key could not be loaded.
+    #[error("failed to load the secret key: {0}")]
+    LoadSecretKey(#[from] LoadError<CryptoError>),
+    /// A different message was signed in the same slot before.
+    #[error("refusing to sign {0}: conflicts with a previously signed message")]
+    Conflict(String),
+    /// The message could not be decoded as the consensus message it claims to be.
+    #[error("refusing to sign {0}: not a valid consensus message")]
+    InvalidMessage(String),
+    /// The message is too old to be checked for conflicts.
+    #[error("refusing to sign {0}: too old to check for conflicts")]
+    Outdated(String),
+    /// The record of signed messages could not be persisted.
+    #[error("failed to persist the signing history: {0}")]
+    History(io::Error),
+    /// The remote signer didn't respond in time.
+    #[error("remote signer did not respond within {0}")]
+    Timeout(TimeDiff),
+    /// Communication with the remote signer failed.
+    #[error("remote signer i/o error: {0}")]
+    Io(#[from] io::Error),
+    /// The remote signer refused to sign.
+    #[error("remote signer refused to sign: {0}")]
+    Refused(String),
+    /// The remote signer sent a response not matching the request.
+    #[error("unexpected response from remote signer")]
+    UnexpectedResponse,
+    /// The remote signer sent an invalid signature.
+    #[error("invalid signature from remote signer: {0}")]
+    InvalidSignature(crypto::Error),
+}
+
+/// A signer holding the secret key in the node process.
+pub(crate) struct LocalSigner {
+    secret_key: Arc<SecretKey>,
+    public_key: PublicKey,
+    /// The record of signed messages, `None` if conflicting messages may be signed.
+    history: Option<Mutex<SigningHistory>>,
+}
+
+impl LocalSigner {
+    /// Creates a new signer, keeping the record of signed messages in memory.
+    pub(crate) fn new(secret_key: Arc<SecretKey>) -> Self {
+        Self::with_history(secret_key, SigningHistory::new())
+    }
+
+    /// Creates a new signer using the given record of signed messages.
+    fn with_history(secret_key: Arc<SecretKey>, history: SigningHistory) -> Self {
+        let public_key = PublicKey::from(secret_key.as_ref());
+        LocalSigner {
+            secret_key,
+            public_key,
+            history: Some(Mutex::new(history)),
+        }
+    }
+
+    /// Creates a new signer that signs conflicting messages, e.g. to create equivocations in
+    /// tests.
+    #[cfg(test)]
+    pub(crate) fn without_history(secret_key: Arc<SecretKey>) -> Self {
+        let public_key = PublicKey::from(secret_key.as_ref());
+        LocalSigner {
+            secret_key,
+            public_key,
+            history: None,
+        }
+    }
+}
+
+impl Debug for LocalSigner {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("LocalSigner")
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ValidatorSigner for LocalSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    fn sign(&self, request: &SigningRequest) -> Result<Signature, SignerError> {
+        let bytes = request.bytes_to_sign();
+        if let Some(history) = &self.history {
+            history
+                .lock()
+                .expect("signing history lock poisoned")
+                .record(request, &bytes)?;
+        }
+        Ok(crypto::sign(bytes, &self.secret_key, &self.public_key))
+    }
+}
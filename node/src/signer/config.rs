@@ -0,0 +1,83 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use casper_types::TimeDiff;
+
+/// Default timeout for a response from the signing daemon.
+const DEFAULT_TIMEOUT: TimeDiff = TimeDiff::from_millis(200);
+
+/// Configuration of a remote signer.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct RemoteSignerConfig {
+    /// The address of the signing daemon.
+    pub address: SignerAddress,
+    /// The longest time to wait for a response from the signing daemon, including reconnecting
+    /// if the connection was lost.
+    ///
+    /// Consensus messages are signed while handling consensus events, which are blocked for up to
+    /// this long, so it should stay well below the round length.
+    #[serde(default = "default_timeout")]
+    pub timeout: TimeDiff,
+}
+
+fn default_timeout() -> TimeDiff {
+    DEFAULT_TIMEOUT
+}
+
+/// The address of a signing daemon.
+#[derive(Clone, DataSize, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum SignerAddress {
+    /// A TCP address, in the form `<host>:<port>`.
+    Tcp(String),
+    /// The path of a Unix domain socket.
+    Unix(PathBuf),
+}
+
+impl FromStr for SignerAddress {
+    type Err = String;
+
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        if let Some(tcp_address) = address.strip_prefix("tcp:") {
+            Ok(SignerAddress::Tcp(tcp_address.to_string()))
+        } else if let Some(path) = address.strip_prefix("unix:") {
+            Ok(SignerAddress::Unix(PathBuf::from(path)))
+        } else {
+            Err(format!(
+                "invalid signer address '{}', expected 'tcp:<host>:<port>' or 'unix:<path>'",
+                address
+            ))
+        }
+    }
+}
+
+impl TryFrom<String> for SignerAddress {
+    type Error = String;
+
+    fn try_from(address: String) -> Result<Self, Self::Error> {
+        address.parse()
+    }
+}
+
+impl From<SignerAddress> for String {
+    fn from(address: SignerAddress) -> Self {
+        address.to_string()
+    }
+}
+
+impl Display for SignerAddress {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SignerAddress::Tcp(address) => write!(formatter, "tcp:{}", address),
+            SignerAddress::Unix(path) => write!(formatter, "unix:{}", path.display()),
+        }
+    }
+}
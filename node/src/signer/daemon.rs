@@ -0,0 +1,166 @@
+//! Reference implementation of a signing daemon.
+//!
+//! The daemon holds the validator's secret key and serves the requests of a node configured with
+//! a remote signer. It keeps a record of the signed messages on disk, so that it keeps refusing
+//! conflicting messages after a restart.
+
+use std::{
+    fs, io,
+    net::TcpListener,
+    os::unix::net::UnixListener,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use casper_types::SecretKey;
+
+use super::{
+    history::SigningHistory,
+    protocol::{self, Listener, Request, Response, Stream},
+    LocalSigner, SignerAddress, ValidatorSigner,
+};
+use crate::{
+    logging::{self, LoggingConfig},
+    utils::{umask, External},
+};
+
+/// `umask` to apply before creating a Unix socket, making it accessible to its owner only.
+const SOCKET_UMASK: umask::Mode = 0o077;
+
+/// Configuration of the signing daemon.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files contain valid keys.
+#[serde(deny_unknown_fields)]
+struct DaemonConfig {
+    /// Path to the validator's secret key file.
+    secret_key_path: External,
+    /// The address to listen on for requests from the node.
+    address: SignerAddress,
+    /// Path of the file recording the signed messages.
+    history_path: PathBuf,
+    /// Logging configuration.
+    #[serde(default)]
+    logging: LoggingConfig,
+}
+
+/// Runs the signing daemon with the configuration file at the given path.
+///
+/// Relative paths in the configuration are interpreted as relative to the directory containing the
+/// configuration file. Only returns if the daemon fails to start.
+pub fn run_daemon(config_path: &Path) -> anyhow::Result<()> {
+    let config_contents = fs::read_to_string(config_path)
+        .with_context(|| format!("failed to read {}", config_path.display()))?;
+    let config: DaemonConfig = toml::from_str(&config_contents)
+        .with_context(|| format!("failed to parse {}", config_path.display()))?;
+    logging::init_with_config(&config.logging)?;
+
+    let root = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let secret_key: Arc<SecretKey> = config
+        .secret_key_path
+        .load(root)
+        .context("failed to load the secret key")?;
+    let history_path = root.join(&config.history_path);
+    let history = SigningHistory::load(history_path.clone()).with_context(|| {
+        format!(
+            "failed to load the signing history from {}",
+            history_path.display()
+        )
+    })?;
+    let signer = Arc::new(LocalSigner::with_history(secret_key, history));
+
+    let listener =
+        bind(&config.address).with_context(|| format!("failed to listen on {}", config.address))?;
+    info!(
+        address = %config.address,
+        public_key = %signer.public_key(),
+        "signing daemon listening"
+    );
+    serve(listener, signer)
+}
+
+/// Creates a listener on the given address.
+///
+/// A stale Unix socket left behind by a previous run is removed first.
+fn bind(address: &SignerAddress) -> io::Result<Listener> {
+    match address {
+        SignerAddress::Tcp(tcp_address) => Ok(Listener::Tcp(TcpListener::bind(tcp_address)?)),
+        SignerAddress::Unix(path) => {
+            if path.exists() {
+                debug!(path = %path.display(), "removing stale socket file");
+                fs::remove_file(path)?;
+            }
+            let umask_guard = umask::temp_umask(SOCKET_UMASK);
+            let listener = UnixListener::bind(path)?;
+            drop(umask_guard);
+            Ok(Listener::Unix(listener))
+        }
+    }
+}
+
+/// Serves the requests of all connections accepted by the listener, each in its own thread.
+fn serve(listener: Listener, signer: Arc<LocalSigner>) -> ! {
+    loop {
+        match listener.accept() {
+            Ok(stream) => {
+                let signer = Arc::clone(&signer);
+                thread::spawn(move || handle_connection(stream, &signer));
+            }
+            Err(error) => warn!(%error, "failed to accept connection"),
+        }
+    }
+}
+
+/// Serves the requests of a single connection until it is closed.
+fn handle_connection(mut stream: Stream, signer: &LocalSigner) {
+    debug!("node connected");
+    loop {
+        let request = match protocol::read_message(&mut stream) {
+            Ok(request) => request,
+            Err(error) => {
+                debug!(%error, "node disconnected");
+                return;
+            }
+        };
+        let response = handle_request(signer, request);
+        if let Err(error) = protocol::write_message(&mut stream, &response) {
+            warn!(%error, "failed to send response");
+            return;
+        }
+    }
+}
+
+/// Returns the response to a single request.
+fn handle_request(signer: &LocalSigner, request: Request) -> Response {
+    match request {
+        Request::PublicKey => Response::PublicKey(signer.public_key().clone()),
+        Request::Sign(signing_request) => match signer.sign(&signing_request) {
+            Ok(signature) => {
+                debug!(%signing_request, "signed");
+                Response::Signature(signature)
+            }
+            Err(error) => {
+                warn!(%error, "refused to sign");
+                Response::Refused(error.to_string())
+            }
+        },
+    }
+}
+
+/// Starts a signing daemon in a background thread, listening on a Unix socket in the given
+/// directory, and returns its address.
+///
+/// This is a stand-in for an external signing daemon in tests. Unlike the actual daemon, it keeps
+/// the record of signed messages in memory.
+#[cfg(test)]
+pub(super) fn spawn_test_daemon(secret_key: Arc<SecretKey>, dir: &Path) -> SignerAddress {
+    let address = SignerAddress::Unix(dir.join("signer.sock"));
+    let listener = bind(&address).expect("should bind test signer socket");
+    let signer = Arc::new(LocalSigner::new(secret_key));
+    thread::spawn(move || serve(listener, signer));
+    address
+}
@@ -0,0 +1,212 @@
+//! Record of signed messages, used to refuse signing conflicting ones.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use bincode::Options;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use casper_types::{Digest, EraId, Timestamp};
+
+use super::{ConsensusPayload, SignerError, SigningRequest};
+use crate::components::consensus::{
+    highway_core::highway::WireUnit,
+    protocols::zug::{Content, RoundId},
+    utils::ValidatorIndex,
+    ClContext,
+};
+
+/// The number of eras before the most recent one for which signed messages are retained.
+///
+/// Messages in earlier eras are refused, as they cannot be checked for conflicts anymore.  As the
+/// era is claimed by the node, a single message advances the most recent era by at most one, so
+/// that claiming a far future era can't discard the record of the current ones.
+const RETAINED_ERAS: u64 = 2;
+
+/// A slot in which at most one message may be signed.
+///
+/// Consensus slots are scoped by the instance ID in the message itself rather than by the era
+/// claimed by the node, which only determines how long the record is kept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+enum Slot {
+    HighwayUnit {
+        instance_id: Digest,
+        seq_number: u64,
+    },
+    ZugEcho {
+        instance_id: Digest,
+        round_id: RoundId,
+    },
+    ZugVote {
+        instance_id: Digest,
+        round_id: RoundId,
+    },
+    FinalitySignature {
+        block_height: u64,
+    },
+}
+
+impl Slot {
+    /// Returns the era and slot of the given message, or `None` if it can't conflict with others.
+    ///
+    /// Fails if a consensus message can't be decoded as the kind of message it claims to be.
+    fn of(request: &SigningRequest) -> Result<Option<(EraId, Slot)>, SignerError> {
+        let invalid = || SignerError::InvalidMessage(request.to_string());
+        match request {
+            SigningRequest::Consensus { era_id, payload } => {
+                let slot = match payload {
+                    ConsensusPayload::HighwayUnit(bytes) => {
+                        let wire_unit: WireUnit<ClContext> = decode(bytes).ok_or_else(invalid)?;
+                        Slot::HighwayUnit {
+                            instance_id: wire_unit.instance_id,
+                            seq_number: wire_unit.seq_number,
+                        }
+                    }
+                    ConsensusPayload::Zug(bytes) => {
+                        let (round_id, instance_id, content, _): ZugFields =
+                            decode(bytes).ok_or_else(invalid)?;
+                        match content {
+                            Content::Echo(_) => Slot::ZugEcho {
+                                instance_id,
+                                round_id,
+                            },
+                            Content::Vote(_) => Slot::ZugVote {
+                                instance_id,
+                                round_id,
+                            },
+                        }
+                    }
+                    // Endorsements and pings can't equivocate, but they are decoded anyway, so
+                    // that only actual endorsements and pings are signed as such.
+                    ConsensusPayload::HighwayEndorsement(bytes) => {
+                        let _: EndorsementFields = decode(bytes).ok_or_else(invalid)?;
+                        return Ok(None);
+                    }
+                    ConsensusPayload::HighwayPing(bytes) => {
+                        let _: PingFields = decode(bytes).ok_or_else(invalid)?;
+                        return Ok(None);
+                    }
+                };
+                Ok(Some((*era_id, slot)))
+            }
+            SigningRequest::FinalitySignature {
+                block_height,
+                era_id,
+                ..
+            } => Ok(Some((
+                *era_id,
+                Slot::FinalitySignature {
+                    block_height: *block_height,
+                },
+            ))),
+            SigningRequest::ConsensusCertificate { .. } => Ok(None),
+        }
+    }
+}
+
+/// The fields of a Zug message that are signed: round ID, instance ID, content and creator.
+type ZugFields = (RoundId, Digest, Content<ClContext>, ValidatorIndex);
+
+/// The fields of a Highway endorsement that are signed: the endorsed unit and the endorser.
+type EndorsementFields = (Digest, ValidatorIndex);
+
+/// The fields of a Highway ping that are signed: creator, timestamp and instance ID.
+type PingFields = (ValidatorIndex, Timestamp, Digest);
+
+/// Decodes a message serialized for computing its hash, rejecting any trailing bytes.
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Option<T> {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .deserialize(bytes)
+        .ok()
+}
+
+/// The hashes of the signed messages, by era and slot.
+#[derive(Debug, Default)]
+pub(super) struct SigningHistory {
+    /// The most recent era, determining which eras are retained.
+    latest_era_id: Option<EraId>,
+    signed: BTreeMap<EraId, BTreeMap<Slot, Digest>>,
+    /// The file the history is persisted to, if any.
+    path: Option<PathBuf>,
+}
+
+impl SigningHistory {
+    /// Creates an empty history kept in memory only.
+    pub(super) fn new() -> Self {
+        SigningHistory::default()
+    }
+
+    /// Loads the history from the given file, or creates an empty one if it doesn't exist.
+    ///
+    /// All changes are written back to the file.
+    pub(super) fn load(path: PathBuf) -> io::Result<Self> {
+        let (latest_era_id, signed) = match fs::read(&path) {
+            Ok(bytes) => bincode::deserialize(&bytes)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => (None, BTreeMap::new()),
+            Err(error) => return Err(error),
+        };
+        Ok(SigningHistory {
+            latest_era_id,
+            signed,
+            path: Some(path),
+        })
+    }
+
+    /// Records that the message with the given bytes is about to be signed.
+    ///
+    /// Fails if a different message was signed in the same slot before, or if the message is too
+    /// old to be checked.
+    pub(super) fn record(
+        &mut self,
+        request: &SigningRequest,
+        bytes: &[u8],
+    ) -> Result<(), SignerError> {
+        let (era_id, slot) = match Slot::of(request)? {
+            Some(era_and_slot) => era_and_slot,
+            None => return Ok(()),
+        };
+        let latest_era_id = self.latest_era_id.unwrap_or(era_id);
+        if era_id.saturating_add(RETAINED_ERAS) < latest_era_id {
+            return Err(SignerError::Outdated(request.to_string()));
+        }
+
+        // The slot is looked up in all retained eras, as the era is not part of every message.
+        let digest = Digest::hash(bytes);
+        match self.signed.values().find_map(|slots| slots.get(&slot)) {
+            Some(signed_digest) if *signed_digest == digest => return Ok(()),
+            Some(_) => return Err(SignerError::Conflict(request.to_string())),
+            None => {
+                self.signed.entry(era_id).or_default().insert(slot, digest);
+            }
+        }
+
+        let latest_era_id = latest_era_id.max(era_id.min(latest_era_id.successor()));
+        self.latest_era_id = Some(latest_era_id);
+        self.signed = self
+            .signed
+            .split_off(&latest_era_id.saturating_sub(RETAINED_ERAS));
+        self.persist().map_err(SignerError::History)
+    }
+
+    /// Writes the history to its file, if any.
+    fn persist(&self) -> io::Result<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let bytes = bincode::serialize(&(self.latest_era_id, &self.signed))
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        // Write to a temporary file first, so that a crash can't leave a truncated history behind.
+        let temp_path = path.with_extension("tmp");
+        let mut file = File::create(&temp_path)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+        fs::rename(temp_path, path)
+    }
+}
@@ -0,0 +1,174 @@
+//! Wire protocol between the node and a signing daemon.
+//!
+//! Every message is serialized using bincode and prefixed with its length as a big-endian `u32`.
+//! The node sends a [`Request`] and waits for the corresponding [`Response`] before sending the
+//! next one.
+
+use std::{
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    os::unix::net::{UnixListener, UnixStream},
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use casper_types::{PublicKey, Signature};
+
+use super::{SignerAddress, SigningRequest};
+
+/// Maximum size of a serialized message.
+///
+/// Highway units are sent in full, including any block proposal, so this matches the maximum
+/// network message size of the production chainspec.
+const MAX_MESSAGE_SIZE: u32 = 24 * 1024 * 1024;
+
+/// A request to the signing daemon.
+#[derive(Debug, Deserialize, Serialize)]
+pub(super) enum Request {
+    /// Asks for the public key of the validator.
+    PublicKey,
+    /// Asks for a signature of the given message.
+    Sign(SigningRequest),
+}
+
+/// A response from the signing daemon.
+#[derive(Debug, Deserialize, Serialize)]
+pub(super) enum Response {
+    /// The public key of the validator.
+    PublicKey(PublicKey),
+    /// The requested signature.
+    Signature(Signature),
+    /// The daemon refused to sign, for the given reason.
+    Refused(String),
+}
+
+/// A connection between the node and a signing daemon.
+#[derive(Debug)]
+pub(super) enum Stream {
+    Tcp(TcpStream),
+    Unix(UnixStream),
+}
+
+impl Stream {
+    /// Connects to the signing daemon at the given address.
+    ///
+    /// The timeout applies to establishing the connection as well as to every subsequent read and
+    /// write.
+    pub(super) fn connect(address: &SignerAddress, timeout: Duration) -> io::Result<Self> {
+        let stream = match address {
+            SignerAddress::Tcp(tcp_address) => {
+                let socket_address = tcp_address.to_socket_addrs()?.next().ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("could not resolve {}", tcp_address),
+                    )
+                })?;
+                let stream = TcpStream::connect_timeout(&socket_address, timeout)?;
+                stream.set_nodelay(true)?;
+                Stream::Tcp(stream)
+            }
+            SignerAddress::Unix(path) => Stream::Unix(UnixStream::connect(path)?),
+        };
+        stream.set_timeout(Some(timeout))?;
+        Ok(stream)
+    }
+
+    /// Sets the read and write timeout.
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => {
+                stream.set_read_timeout(timeout)?;
+                stream.set_write_timeout(timeout)
+            }
+            Stream::Unix(stream) => {
+                stream.set_read_timeout(timeout)?;
+                stream.set_write_timeout(timeout)
+            }
+        }
+    }
+
+    /// Sends a request and waits for the response.
+    pub(super) fn exchange(&mut self, request: &Request) -> io::Result<Response> {
+        write_message(self, request)?;
+        read_message(self)
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.read(buf),
+            Stream::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.write(buf),
+            Stream::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.flush(),
+            Stream::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+/// A listener accepting connections from nodes.
+#[derive(Debug)]
+pub(super) enum Listener {
+    Tcp(TcpListener),
+    Unix(UnixListener),
+}
+
+impl Listener {
+    /// Waits for the next connection.
+    pub(super) fn accept(&self) -> io::Result<Stream> {
+        match self {
+            Listener::Tcp(listener) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_nodelay(true)?;
+                Ok(Stream::Tcp(stream))
+            }
+            Listener::Unix(listener) => {
+                let (stream, _) = listener.accept()?;
+                Ok(Stream::Unix(stream))
+            }
+        }
+    }
+}
+
+/// Writes a length-prefixed message.
+pub(super) fn write_message<W: Write, T: Serialize>(writer: &mut W, message: &T) -> io::Result<()> {
+    let bytes = bincode::serialize(message)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    let length = u32::try_from(bytes.len())
+        .ok()
+        .filter(|length| *length <= MAX_MESSAGE_SIZE)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "message too large"))?;
+    writer.write_all(&length.to_be_bytes())?;
+    writer.write_all(&bytes)?;
+    writer.flush()
+}
+
+/// Reads a length-prefixed message.
+pub(super) fn read_message<R: Read, T: DeserializeOwned>(reader: &mut R) -> io::Result<T> {
+    let mut length_bytes = [0; 4];
+    reader.read_exact(&mut length_bytes)?;
+    let length = u32::from_be_bytes(length_bytes);
+    if length > MAX_MESSAGE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("message of {} bytes exceeds the maximum size", length),
+        ));
+    }
+    let mut bytes = vec![0; length as usize];
+    reader.read_exact(&mut bytes)?;
+    bincode::deserialize(&bytes).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
@@ -0,0 +1,174 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    io,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use tracing::{debug, info};
+
+use casper_types::{crypto, PublicKey, Signature};
+
+use super::{
+    protocol::{Request, Response, Stream},
+    RemoteSignerConfig, SignerAddress, SignerError, SigningRequest, ValidatorSigner,
+};
+
+/// A signer forwarding requests to a signing daemon.
+///
+/// Signatures are created while handling consensus events, so a request never blocks the caller
+/// for longer than the configured timeout: the connection is served by a dedicated thread, which
+/// also re-establishes it if it is lost, and the caller gives up if no response arrives in time.
+pub(crate) struct RemoteSigner {
+    config: RemoteSignerConfig,
+    /// The public key of the validator, as reported by the daemon.
+    public_key: PublicKey,
+    /// The queue of requests to the connection thread.
+    requests: Mutex<mpsc::Sender<PendingRequest>>,
+}
+
+/// A request waiting to be sent to the daemon by the connection thread.
+struct PendingRequest {
+    request: Request,
+    /// The time at which the caller stops waiting for the response.
+    deadline: Instant,
+    responder: mpsc::SyncSender<io::Result<Response>>,
+}
+
+impl RemoteSigner {
+    /// Connects to the signing daemon, retrieves the validator's public key and starts the
+    /// connection thread.
+    pub(crate) fn connect(config: RemoteSignerConfig) -> Result<Self, SignerError> {
+        let timeout: Duration = config.timeout.into();
+        let mut stream = Stream::connect(&config.address, timeout)?;
+        let public_key = match stream.exchange(&Request::PublicKey)? {
+            Response::PublicKey(public_key) => public_key,
+            Response::Signature(_) | Response::Refused(_) => {
+                return Err(SignerError::UnexpectedResponse)
+            }
+        };
+        info!(address = %config.address, %public_key, "connected to remote signer");
+
+        let (sender, receiver) = mpsc::channel();
+        let address = config.address.clone();
+        thread::Builder::new()
+            .name("remote-signer".to_string())
+            .spawn(move || serve_requests(&address, timeout, stream, receiver))?;
+        Ok(RemoteSigner {
+            config,
+            public_key,
+            requests: Mutex::new(sender),
+        })
+    }
+
+    /// Sends a request to the daemon and waits for the response, at most until the timeout
+    /// expires.
+    fn request(&self, request: Request) -> Result<Response, SignerError> {
+        let timeout: Duration = self.config.timeout.into();
+        let (responder, response) = mpsc::sync_channel(1);
+        let pending = PendingRequest {
+            request,
+            deadline: Instant::now() + timeout,
+            responder,
+        };
+        self.requests
+            .lock()
+            .expect("signer request queue lock poisoned")
+            .send(pending)
+            .map_err(|_| connection_thread_stopped())?;
+        match response.recv_timeout(timeout) {
+            Ok(result) => Ok(result?),
+            Err(RecvTimeoutError::Timeout) => Err(SignerError::Timeout(self.config.timeout)),
+            Err(RecvTimeoutError::Disconnected) => Err(connection_thread_stopped().into()),
+        }
+    }
+}
+
+fn connection_thread_stopped() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::BrokenPipe,
+        "remote signer connection thread stopped",
+    )
+}
+
+/// Sends the queued requests to the daemon one at a time, until the signer is dropped.
+///
+/// Requests whose caller stopped waiting already are skipped.
+fn serve_requests(
+    address: &SignerAddress,
+    timeout: Duration,
+    stream: Stream,
+    requests: mpsc::Receiver<PendingRequest>,
+) {
+    let mut connection = Some(stream);
+    for pending in requests {
+        if Instant::now() >= pending.deadline {
+            debug!("skipping expired request to remote signer");
+            continue;
+        }
+        let result = exchange(address, timeout, &mut connection, &pending.request);
+        // The caller may have stopped waiting in the meantime, so the response can be dropped.
+        let _ = pending.responder.send(result);
+    }
+}
+
+/// Sends a request to the daemon and waits for the response.
+///
+/// If sending the request over an existing connection fails, it is retried once over a new one, as
+/// the daemon may have been restarted in the meantime.
+fn exchange(
+    address: &SignerAddress,
+    timeout: Duration,
+    connection: &mut Option<Stream>,
+    request: &Request,
+) -> io::Result<Response> {
+    if let Some(stream) = connection.as_mut() {
+        match stream.exchange(request) {
+            Ok(response) => return Ok(response),
+            Err(error) => {
+                debug!(%error, "lost connection to remote signer, reconnecting");
+                *connection = None;
+            }
+        }
+    }
+    let stream = connection.insert(Stream::connect(address, timeout)?);
+    match stream.exchange(request) {
+        Ok(response) => Ok(response),
+        Err(error) => {
+            *connection = None;
+            Err(error)
+        }
+    }
+}
+
+impl Debug for RemoteSigner {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("RemoteSigner")
+            .field("address", &self.config.address)
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ValidatorSigner for RemoteSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    fn sign(&self, request: &SigningRequest) -> Result<Signature, SignerError> {
+        match self.request(Request::Sign(request.clone()))? {
+            Response::Signature(signature) => {
+                crypto::verify(request.bytes_to_sign(), &signature, &self.public_key)
+                    .map_err(SignerError::InvalidSignature)?;
+                Ok(signature)
+            }
+            Response::Refused(reason) => Err(SignerError::Refused(reason)),
+            Response::PublicKey(_) => Err(SignerError::UnexpectedResponse),
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use std::{
+    collections::BTreeSet,
+    os::unix::net::UnixListener,
+    path::PathBuf,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use tempfile::tempdir;
+
+use casper_types::{
+    crypto, testing::TestRng, BlockHash, ChainNameDigest, Digest, EraId, PublicKey, SecretKey,
+    TimeDiff, Timestamp,
+};
+
+use super::{
+    daemon::spawn_test_daemon,
+    history::SigningHistory,
+    protocol::{self, Request, Response, Stream},
+    ConsensusPayload, LocalSigner, RemoteSigner, RemoteSignerConfig, SignerAddress, SignerError,
+    SigningRequest, ValidatorSigner,
+};
+use crate::components::consensus::{
+    highway_core::{highway::WireUnit, Panorama},
+    protocols::zug::Content,
+    utils::ValidatorIndex,
+    ClContext,
+};
+
+fn consensus_request(era_id: u64, payload: ConsensusPayload) -> SigningRequest {
+    SigningRequest::Consensus {
+        era_id: EraId::new(era_id),
+        payload,
+    }
+}
+
+/// Returns a request to sign a Highway unit with a random timestamp.
+fn unit_request(
+    era_id: u64,
+    instance_id: Digest,
+    seq_number: u64,
+    rng: &mut TestRng,
+) -> SigningRequest {
+    let wire_unit = WireUnit::<ClContext> {
+        panorama: Panorama::new(1),
+        creator: ValidatorIndex(0),
+        instance_id,
+        value: None,
+        seq_number,
+        timestamp: Timestamp::random(rng),
+        round_exp: 0,
+        endorsed: BTreeSet::new(),
+    };
+    consensus_request(era_id, wire_unit.signing_payload())
+}
+
+fn zug_request(
+    era_id: u64,
+    instance_id: Digest,
+    round_id: u32,
+    content: Content<ClContext>,
+) -> SigningRequest {
+    let fields = (round_id, instance_id, content, ValidatorIndex(0));
+    let payload = ConsensusPayload::Zug(bincode::serialize(&fields).unwrap());
+    consensus_request(era_id, payload)
+}
+
+fn ping_request(rng: &mut TestRng, instance_id: Digest) -> SigningRequest {
+    let fields = (ValidatorIndex(0), Timestamp::random(rng), instance_id);
+    let payload = ConsensusPayload::HighwayPing(bincode::serialize(&fields).unwrap());
+    consensus_request(1, payload)
+}
+
+fn endorsement_request(rng: &mut TestRng) -> SigningRequest {
+    let fields = (Digest::random(rng), ValidatorIndex(0));
+    let payload = ConsensusPayload::HighwayEndorsement(bincode::serialize(&fields).unwrap());
+    consensus_request(1, payload)
+}
+
+fn finality_signature_request(block_hash: BlockHash, block_height: u64) -> SigningRequest {
+    SigningRequest::FinalitySignature {
+        block_hash,
+        block_height,
+        era_id: EraId::new(1),
+        chain_name_hash: ChainNameDigest::from_chain_name("casper-example"),
+    }
+}
+
+fn connect(address: SignerAddress) -> RemoteSigner {
+    RemoteSigner::connect(RemoteSignerConfig {
+        address,
+        timeout: TimeDiff::from_seconds(5),
+    })
+    .expect("should connect to test signer")
+}
+
+#[test]
+fn remote_signer_signs_with_daemon_key() {
+    let mut rng = TestRng::new();
+    let dir = tempdir().unwrap();
+    let secret_key = Arc::new(SecretKey::random(&mut rng));
+    let public_key = PublicKey::from(secret_key.as_ref());
+    let signer = connect(spawn_test_daemon(secret_key, dir.path()));
+    assert_eq!(*signer.public_key(), public_key);
+
+    let instance_id = Digest::random(&mut rng);
+    let requests = [
+        unit_request(1, instance_id, 0, &mut rng),
+        endorsement_request(&mut rng),
+        ping_request(&mut rng, instance_id),
+        zug_request(1, instance_id, 0, Content::Vote(true)),
+        finality_signature_request(BlockHash::random(&mut rng), 5),
+        SigningRequest::ConsensusCertificate {
+            connection_id: vec![1, 2, 3],
+        },
+    ];
+    for request in &requests {
+        let signature = signer.sign(request).expect("should sign");
+        crypto::verify(request.bytes_to_sign(), &signature, &public_key)
+            .expect("signature should be valid");
+    }
+}
+
+#[test]
+fn signatures_are_not_interchangeable() {
+    let mut rng = TestRng::new();
+    let secret_key = Arc::new(SecretKey::random(&mut rng));
+    let public_key = PublicKey::from(secret_key.as_ref());
+    let signer = LocalSigner::new(secret_key);
+
+    // Consensus messages are signed by hash, computed by the signer itself.
+    let unit = unit_request(1, Digest::random(&mut rng), 0, &mut rng);
+    let SigningRequest::Consensus { payload, .. } = &unit else {
+        unreachable!()
+    };
+    let signature = signer.sign(&unit).expect("should sign unit");
+    let hash = Digest::hash(payload.serialized());
+    crypto::verify(hash, &signature, &public_key).expect("should sign the unit's hash");
+
+    // A certificate for a connection ID equal to that hash is not a valid signature of the hash.
+    let certificate = SigningRequest::ConsensusCertificate {
+        connection_id: hash.as_ref().to_vec(),
+    };
+    let signature = signer.sign(&certificate).expect("should sign certificate");
+    assert!(crypto::verify(hash, &signature, &public_key).is_err());
+
+    // A unit can't be signed as a ping, nor an arbitrary hash as a unit.
+    for payload in [
+        ConsensusPayload::HighwayPing(payload.serialized().to_vec()),
+        ConsensusPayload::HighwayUnit(hash.as_ref().to_vec()),
+        ConsensusPayload::HighwayUnit([payload.serialized(), &[0]].concat()),
+    ] {
+        assert!(matches!(
+            signer.sign(&consensus_request(1, payload)),
+            Err(SignerError::InvalidMessage(_))
+        ));
+    }
+}
+
+#[test]
+fn remote_signer_gives_up_after_timeout() {
+    let mut rng = TestRng::new();
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("signer.sock");
+    let listener = UnixListener::bind(&path).unwrap();
+    let public_key = PublicKey::from(&SecretKey::random(&mut rng));
+
+    // A daemon that reports its public key, but never responds to signing requests.
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut stream = Stream::Unix(stream);
+        let _: Request = protocol::read_message(&mut stream).unwrap();
+        protocol::write_message(&mut stream, &Response::PublicKey(public_key)).unwrap();
+        while protocol::read_message::<_, Request>(&mut stream).is_ok() {}
+    });
+
+    let signer = RemoteSigner::connect(RemoteSignerConfig {
+        address: SignerAddress::Unix(path),
+        timeout: TimeDiff::from_millis(100),
+    })
+    .expect("should connect to test signer");
+    let request = finality_signature_request(BlockHash::random(&mut rng), 1);
+    let start = Instant::now();
+    assert!(matches!(
+        signer.sign(&request),
+        Err(SignerError::Timeout(_))
+    ));
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn remote_signer_refuses_conflicting_messages() {
+    let mut rng = TestRng::new();
+    let dir = tempdir().unwrap();
+    let secret_key = Arc::new(SecretKey::random(&mut rng));
+    let signer = connect(spawn_test_daemon(secret_key, dir.path()));
+
+    let instance_id = Digest::random(&mut rng);
+    let unit = unit_request(1, instance_id, 3, &mut rng);
+    signer.sign(&unit).expect("should sign unit");
+    signer.sign(&unit).expect("should sign the same unit again");
+    let conflicting_unit = unit_request(1, instance_id, 3, &mut rng);
+    assert!(matches!(
+        signer.sign(&conflicting_unit),
+        Err(SignerError::Refused(_))
+    ));
+    // Claiming another era doesn't avoid the conflict.
+    let conflicting_unit = unit_request(2, instance_id, 3, &mut rng);
+    assert!(matches!(
+        signer.sign(&conflicting_unit),
+        Err(SignerError::Refused(_))
+    ));
+    // The same sequence number in another instance doesn't conflict.
+    signer
+        .sign(&unit_request(2, Digest::random(&mut rng), 3, &mut rng))
+        .expect("should sign unit in another instance");
+
+    let finality_signature = finality_signature_request(BlockHash::random(&mut rng), 7);
+    signer
+        .sign(&finality_signature)
+        .expect("should sign finality signature");
+    let conflicting_finality_signature = finality_signature_request(BlockHash::random(&mut rng), 7);
+    assert!(matches!(
+        signer.sign(&conflicting_finality_signature),
+        Err(SignerError::Refused(_))
+    ));
+}
+
+#[test]
+fn local_signer_refuses_conflicting_zug_messages() {
+    let mut rng = TestRng::new();
+    let signer = LocalSigner::new(Arc::new(SecretKey::random(&mut rng)));
+    let instance_id = Digest::random(&mut rng);
+
+    let echo =
+        |rng: &mut TestRng| zug_request(1, instance_id, 4, Content::Echo(Digest::random(rng)));
+    signer.sign(&echo(&mut rng)).expect("should sign echo");
+    assert!(matches!(
+        signer.sign(&echo(&mut rng)),
+        Err(SignerError::Conflict(_))
+    ));
+
+    // Votes don't conflict with echoes, but with other votes in the same round.
+    signer
+        .sign(&zug_request(1, instance_id, 4, Content::Vote(true)))
+        .expect("should sign vote");
+    assert!(matches!(
+        signer.sign(&zug_request(1, instance_id, 4, Content::Vote(false))),
+        Err(SignerError::Conflict(_))
+    ));
+
+    // Pings and endorsements never conflict.
+    for _ in 0..2 {
+        signer
+            .sign(&ping_request(&mut rng, instance_id))
+            .expect("should sign ping");
+        signer
+            .sign(&endorsement_request(&mut rng))
+            .expect("should sign endorsement");
+    }
+}
+
+#[test]
+fn local_signer_refuses_outdated_messages() {
+    let mut rng = TestRng::new();
+    let signer = LocalSigner::new(Arc::new(SecretKey::random(&mut rng)));
+
+    signer
+        .sign(&unit_request(10, Digest::random(&mut rng), 0, &mut rng))
+        .expect("should sign unit");
+    signer
+        .sign(&unit_request(8, Digest::random(&mut rng), 0, &mut rng))
+        .expect("should sign unit in a retained era");
+    assert!(matches!(
+        signer.sign(&unit_request(7, Digest::random(&mut rng), 0, &mut rng)),
+        Err(SignerError::Outdated(_))
+    ));
+}
+
+#[test]
+fn local_signer_refuses_conflicts_after_far_future_era() {
+    let mut rng = TestRng::new();
+    let signer = LocalSigner::new(Arc::new(SecretKey::random(&mut rng)));
+    let instance_id = Digest::random(&mut rng);
+
+    signer
+        .sign(&unit_request(10, instance_id, 0, &mut rng))
+        .expect("should sign unit");
+    let far_future_signature = SigningRequest::FinalitySignature {
+        block_hash: BlockHash::random(&mut rng),
+        block_height: 1,
+        era_id: EraId::new(20),
+        chain_name_hash: ChainNameDigest::from_chain_name("casper-example"),
+    };
+    signer
+        .sign(&far_future_signature)
+        .expect("should sign finality signature");
+    // The far future era doesn't discard the record of the current one.
+    assert!(matches!(
+        signer.sign(&unit_request(10, instance_id, 0, &mut rng)),
+        Err(SignerError::Conflict(_))
+    ));
+    assert!(matches!(
+        signer.sign(&unit_request(20, instance_id, 0, &mut rng)),
+        Err(SignerError::Conflict(_))
+    ));
+}
+
+#[test]
+fn signing_history_survives_restart() {
+    let mut rng = TestRng::new();
+    let dir = tempdir().unwrap();
+    let path: PathBuf = dir.path().join("history.bin");
+    let secret_key = Arc::new(SecretKey::random(&mut rng));
+    let instance_id = Digest::random(&mut rng);
+    let vote = zug_request(1, instance_id, 2, Content::Vote(true));
+
+    let signer = LocalSigner::with_history(
+        Arc::clone(&secret_key),
+        SigningHistory::load(path.clone()).unwrap(),
+    );
+    signer.sign(&vote).expect("should sign vote");
+    drop(signer);
+
+    let signer = LocalSigner::with_history(secret_key, SigningHistory::load(path).unwrap());
+    signer.sign(&vote).expect("should sign the same vote again");
+    assert!(matches!(
+        signer.sign(&zug_request(1, instance_id, 2, Content::Vote(false))),
+        Err(SignerError::Conflict(_))
+    ));
+}
+
+#[test]
+fn should_parse_signer_address() {
+    let tcp: SignerAddress = "tcp:127.0.0.1:7777".parse().unwrap();
+    assert_eq!(tcp, SignerAddress::Tcp("127.0.0.1:7777".to_string()));
+    assert_eq!(tcp.to_string(), "tcp:127.0.0.1:7777");
+
+    let unix: SignerAddress = "unix:/run/casper/signer.sock".parse().unwrap();
+    assert_eq!(
+        unix,
+        SignerAddress::Unix(PathBuf::from("/run/casper/signer.sock"))
+    );
+    assert_eq!(unix.to_string(), "unix:/run/casper/signer.sock");
+
+    assert!("127.0.0.1:7777".parse::<SignerAddress>().is_err());
+}
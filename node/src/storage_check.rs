@@ -0,0 +1,767 @@
+//! Offline integrity check and repair of the storage.
+//!
+//! Opens the block store and the global state of a node which is not running, read-only, and
+//! checks that every complete block has its body, transactions, approvals hashes and execution
+//! results, all hashing to what the block commits to, that the persistent indices agree with the
+//! stored blocks, and that the global state under the state root of every complete block is
+//! complete.  If trie store garbage collection is enabled, only the state roots of the most recent
+//! complete blocks it retains are checked, as the tries of older ones are deleted by design.  In
+//! repair mode, the indices are rebuilt, and the damaged blocks are dropped from the
+//! complete blocks so that the node acquires them again once started.
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::Path,
+    sync::Arc,
+};
+
+use serde::Serialize;
+use thiserror::Error;
+use tracing::info;
+
+use casper_storage::{
+    block_store::{
+        lmdb::{IndexedLmdbBlockStore, LmdbBlockStore},
+        types::{ApprovalsHashes, BlockHashHeightAndEra, BlockHeight, StateStore},
+        BlockStoreError, BlockStoreProvider, BlockStoreTransaction, DataReader, DataWriter,
+    },
+    global_state::{
+        error::Error as GlobalStateError,
+        transaction_source::{lmdb::LmdbEnvironment, TransactionSource},
+        trie_store::{
+            lmdb::LmdbTrieStore,
+            operations::{check_tries, TrieIntegrityIssue},
+        },
+    },
+};
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    execution::ExecutionResult,
+    Block, BlockHash, BlockHeader, Chainspec, Digest, EraId, Key, StoredValue, Transaction,
+    TransactionHash,
+};
+
+use crate::{
+    components::storage::{disjoint_sequences::DisjointSequences, COMPLETED_BLOCKS_STORAGE_KEY},
+    reactor::main_reactor::Config,
+    utils::WithDir,
+};
+
+/// Error returned as a result of checking the storage.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// Error opening or reading the block store.
+    #[error("error reading block store: {0}")]
+    BlockStore(#[from] BlockStoreError),
+
+    /// Error opening or reading the global state.
+    #[error("error reading global state: {0}")]
+    GlobalState(#[from] GlobalStateError),
+
+    /// The record of complete blocks could not be decoded or encoded.
+    #[error("invalid record of complete blocks: {0}")]
+    CompletedBlocks(bytesrepr::Error),
+}
+
+/// The outcome of checking the storage.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct StorageReport {
+    /// The number of complete blocks checked.
+    checked_block_count: u64,
+    /// The number of distinct state roots of complete blocks checked.
+    checked_state_root_count: u64,
+    /// The number of distinct state roots of complete blocks not checked, as their tries may have
+    /// been deleted by garbage collection.
+    unchecked_state_root_count: u64,
+    /// The number of sound tries found under the state roots.
+    sound_trie_count: u64,
+    /// The problems found.
+    issues: Vec<StorageIssue>,
+    /// The inclusive ranges of heights of complete blocks found damaged, from high to low.
+    damaged_ranges: Vec<(u64, u64)>,
+    /// Whether the storage was repaired.
+    repaired: bool,
+}
+
+impl StorageReport {
+    /// Returns `true` if any problem was found.
+    pub(crate) fn has_issues(&self) -> bool {
+        !self.issues.is_empty()
+    }
+
+    /// Returns the number of problems found.
+    pub(crate) fn issue_count(&self) -> usize {
+        self.issues.len()
+    }
+
+    /// Returns the number of complete blocks checked.
+    pub(crate) fn checked_block_count(&self) -> u64 {
+        self.checked_block_count
+    }
+
+    /// Returns whether the storage was repaired.
+    pub(crate) fn repaired(&self) -> bool {
+        self.repaired
+    }
+}
+
+/// A problem found in the storage.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) enum StorageIssue {
+    /// The indices were built by another version of the node, or never built at all, so they
+    /// weren't checked.
+    OutdatedIndices,
+    /// A block header can't be decoded.
+    UndecodableBlockHeader {
+        /// The hash the header is stored under.
+        stored_hash: BlockHash,
+        /// The decoding error.
+        error: String,
+    },
+    /// A block header is stored under a hash other than its own.
+    BlockHashMismatch {
+        /// The hash the header is stored under.
+        stored_hash: BlockHash,
+        /// The hash of the header.
+        block_hash: BlockHash,
+    },
+    /// There is no block header for a height recorded as complete.
+    MissingBlock {
+        /// The height of the block.
+        height: u64,
+    },
+    /// The body of a complete block is missing.
+    MissingBlockBody {
+        /// The height of the block.
+        height: u64,
+        /// The hash of the block.
+        block_hash: BlockHash,
+    },
+    /// The body of a complete block can't be decoded, or doesn't hash to what its header commits
+    /// to.
+    InvalidBlock {
+        /// The height of the block.
+        height: u64,
+        /// The hash of the block.
+        block_hash: BlockHash,
+        /// The validation error.
+        error: String,
+    },
+    /// The approvals hashes of a complete block with transactions are missing.
+    MissingApprovalsHashes {
+        /// The height of the block.
+        height: u64,
+        /// The hash of the block.
+        block_hash: BlockHash,
+    },
+    /// The approvals hashes of a complete block can't be decoded or don't match the block.
+    InvalidApprovalsHashes {
+        /// The height of the block.
+        height: u64,
+        /// The hash of the block.
+        block_hash: BlockHash,
+        /// The validation error.
+        error: String,
+    },
+    /// A transaction of a complete block is missing.
+    MissingTransaction {
+        /// The height of the block.
+        height: u64,
+        /// The hash of the transaction.
+        transaction_hash: TransactionHash,
+    },
+    /// A transaction of a complete block can't be decoded, or doesn't hash to its hash.
+    InvalidTransaction {
+        /// The height of the block.
+        height: u64,
+        /// The hash of the transaction.
+        transaction_hash: TransactionHash,
+    },
+    /// The execution result of a transaction of a complete block is missing.
+    MissingExecutionResult {
+        /// The height of the block.
+        height: u64,
+        /// The hash of the transaction.
+        transaction_hash: TransactionHash,
+    },
+    /// The execution result of a transaction of a complete block can't be decoded.
+    InvalidExecutionResult {
+        /// The height of the block.
+        height: u64,
+        /// The hash of the transaction.
+        transaction_hash: TransactionHash,
+        /// The decoding error.
+        error: String,
+    },
+    /// The block height index doesn't point at a stored block.
+    HeightIndexMismatch {
+        /// The height of the block.
+        height: u64,
+        /// The hash of the block.
+        block_hash: BlockHash,
+    },
+    /// The switch block index doesn't point at a stored switch block.
+    SwitchBlockIndexMismatch {
+        /// The era of the switch block.
+        era_id: EraId,
+        /// The hash of the switch block.
+        block_hash: BlockHash,
+    },
+    /// The transaction index doesn't point at the complete block holding a transaction.
+    TransactionIndexMismatch {
+        /// The hash of the transaction.
+        transaction_hash: TransactionHash,
+        /// The hash of the block.
+        block_hash: BlockHash,
+    },
+    /// A trie under the state root of a complete block is missing.
+    MissingTrie {
+        /// The state root hash.
+        state_root_hash: Digest,
+        /// The hash of the trie.
+        trie_hash: Digest,
+    },
+    /// A trie under the state root of a complete block can't be decoded, or doesn't hash to the
+    /// digest it's stored under.
+    CorruptedTrie {
+        /// The state root hash.
+        state_root_hash: Digest,
+        /// The hash of the trie.
+        trie_hash: Digest,
+    },
+    /// A leaf under the state root of a complete block isn't where its key leads.
+    MisplacedLeaf {
+        /// The state root hash.
+        state_root_hash: Digest,
+        /// The hash of the leaf.
+        trie_hash: Digest,
+    },
+}
+
+impl StorageIssue {
+    fn from_trie_issue(state_root_hash: Digest, issue: TrieIntegrityIssue) -> Self {
+        match issue {
+            TrieIntegrityIssue::Missing(trie_hash) => StorageIssue::MissingTrie {
+                state_root_hash,
+                trie_hash,
+            },
+            TrieIntegrityIssue::Corrupted(trie_hash) => StorageIssue::CorruptedTrie {
+                state_root_hash,
+                trie_hash,
+            },
+            TrieIntegrityIssue::MisplacedLeaf(trie_hash) => StorageIssue::MisplacedLeaf {
+                state_root_hash,
+                trie_hash,
+            },
+        }
+    }
+}
+
+/// Checks the storage, and repairs it if `repair` is set and problems were found.
+///
+/// The node must not be running, even when only checking, as the storage is read in a single long
+/// transaction.
+pub(crate) fn check_storage(
+    config: WithDir<Config>,
+    chainspec: Arc<Chainspec>,
+    repair: bool,
+) -> Result<StorageReport, Error> {
+    let root_dir = config.dir();
+    let config = config.value();
+    let storage_config = WithDir::new(root_dir, config.storage.clone());
+    let root = storage_config
+        .with_dir(config.storage.path.clone())
+        .join(&chainspec.network_config.name);
+    let total_size = config.storage.max_total_size();
+
+    let mut report = StorageReport::default();
+    let mut damaged_heights = BTreeSet::new();
+    let (completed_blocks, indices_are_current) = {
+        let block_store = LmdbBlockStore::open_read_only(&root, total_size)?;
+        let completed_blocks = read_completed_blocks(&block_store)?;
+        let indices_are_current = IndexedLmdbBlockStore::has_current_indices(&block_store)?;
+        let indexed_block_store = if indices_are_current {
+            Ok(IndexedLmdbBlockStore::open_read_only(block_store)?)
+        } else {
+            report.issues.push(StorageIssue::OutdatedIndices);
+            Err(block_store)
+        };
+
+        info!(root = %root.display(), "checking blocks");
+        let state_roots = match &indexed_block_store {
+            Ok(indexed_block_store) => check_blocks(
+                indexed_block_store.block_store(),
+                Some(indexed_block_store),
+                &completed_blocks,
+                &mut report,
+                &mut damaged_heights,
+            )?,
+            Err(block_store) => check_blocks(
+                block_store,
+                None,
+                &completed_blocks,
+                &mut report,
+                &mut damaged_heights,
+            )?,
+        };
+
+        let state_roots = match config.contract_runtime.gc_retained_state_roots() {
+            Some(retained_state_roots) => {
+                let (retained, unchecked_count) =
+                    retained_state_roots_of(state_roots, retained_state_roots);
+                report.unchecked_state_root_count = unchecked_count;
+                retained
+            }
+            None => state_roots,
+        };
+        info!(
+            state_root_count = state_roots.len(),
+            unchecked_state_root_count = report.unchecked_state_root_count,
+            "checking global state"
+        );
+        check_global_state(
+            &root,
+            config,
+            state_roots,
+            &mut report,
+            &mut damaged_heights,
+        )?;
+        (completed_blocks, indices_are_current)
+    };
+
+    report.damaged_ranges =
+        DisjointSequences::from(damaged_heights.iter().copied().collect::<Vec<_>>())
+            .sequences()
+            .iter()
+            .map(|sequence| (sequence.low(), sequence.high()))
+            .collect();
+
+    if repair && report.has_issues() {
+        info!("repairing storage");
+        let block_store = LmdbBlockStore::new(&root, total_size)?;
+        // Outdated indices are rebuilt when opening the store.
+        let mut indexed_block_store =
+            IndexedLmdbBlockStore::new(block_store, None, chainspec.protocol_version())?;
+        if indices_are_current {
+            indexed_block_store.reindex()?;
+        }
+        if !damaged_heights.is_empty() {
+            let mut completed_blocks = completed_blocks;
+            for height in &damaged_heights {
+                let _ = completed_blocks.remove(*height);
+            }
+            let serialized = completed_blocks
+                .to_bytes()
+                .map_err(Error::CompletedBlocks)?;
+            let mut rw_txn = indexed_block_store.checkout_rw()?;
+            rw_txn.write(&StateStore {
+                key: Cow::Borrowed(COMPLETED_BLOCKS_STORAGE_KEY),
+                value: serialized,
+            })?;
+            rw_txn.commit()?;
+        }
+        report.repaired = true;
+    }
+
+    Ok(report)
+}
+
+/// Reads the record of complete blocks, which is empty if it was never written.
+fn read_completed_blocks(block_store: &LmdbBlockStore) -> Result<DisjointSequences, Error> {
+    let ro_txn = block_store.checkout_ro()?;
+    let maybe_raw: Option<Vec<u8>> = ro_txn.read(COMPLETED_BLOCKS_STORAGE_KEY)?;
+    ro_txn.rollback();
+    match maybe_raw {
+        Some(raw) => Ok(DisjointSequences::from_vec(raw)
+            .map_err(Error::CompletedBlocks)?
+            .0),
+        None => Ok(DisjointSequences::default()),
+    }
+}
+
+/// Checks every stored block header against the indices, and the data of every complete block.
+///
+/// Returns the state roots of the complete blocks, each with the heights of the blocks having it.
+fn check_blocks(
+    block_store: &LmdbBlockStore,
+    maybe_indexed_block_store: Option<&IndexedLmdbBlockStore>,
+    completed_blocks: &DisjointSequences,
+    report: &mut StorageReport,
+    damaged_heights: &mut BTreeSet<u64>,
+) -> Result<BTreeMap<Digest, Vec<u64>>, Error> {
+    let ro_txn = block_store.checkout_ro()?;
+    let maybe_index_txn = maybe_indexed_block_store
+        .map(|indexed_block_store| indexed_block_store.checkout_ro())
+        .transpose()?;
+    let mut complete_heights = BTreeSet::new();
+    let mut state_roots: BTreeMap<Digest, Vec<u64>> = BTreeMap::new();
+
+    block_store.for_each_block_header(|stored_hash, maybe_block_header| {
+        let block_header = match maybe_block_header {
+            Ok(block_header) => block_header,
+            Err(error) => {
+                report.issues.push(StorageIssue::UndecodableBlockHeader {
+                    stored_hash,
+                    error: error.to_string(),
+                });
+                return Ok(());
+            }
+        };
+        let block_hash = block_header.block_hash();
+        if block_hash != stored_hash {
+            report.issues.push(StorageIssue::BlockHashMismatch {
+                stored_hash,
+                block_hash,
+            });
+            return Ok(());
+        }
+        let height = block_header.height();
+
+        if let Some(index_txn) = &maybe_index_txn {
+            check_header_indices(index_txn, &block_header, &mut report.issues);
+        }
+        if !completed_blocks.contains(height) {
+            return Ok(());
+        }
+
+        report.checked_block_count += 1;
+        let _ = complete_heights.insert(height);
+        state_roots
+            .entry(*block_header.state_root_hash())
+            .or_default()
+            .push(height);
+        let issue_count = report.issues.len();
+        match DataReader::<BlockHash, Block>::read(&ro_txn, block_hash) {
+            Ok(Some(block)) => match block.verify() {
+                Ok(()) => check_block_data(&ro_txn, maybe_index_txn.as_ref(), &block, report),
+                Err(error) => report.issues.push(StorageIssue::InvalidBlock {
+                    height,
+                    block_hash,
+                    error: error.to_string(),
+                }),
+            },
+            Ok(None) => report
+                .issues
+                .push(StorageIssue::MissingBlockBody { height, block_hash }),
+            Err(error) => report.issues.push(StorageIssue::InvalidBlock {
+                height,
+                block_hash,
+                error: error.to_string(),
+            }),
+        }
+        if report.issues.len() > issue_count {
+            let _ = damaged_heights.insert(height);
+        }
+        Ok(())
+    })?;
+
+    for sequence in completed_blocks.sequences() {
+        for height in sequence.low()..=sequence.high() {
+            if !complete_heights.contains(&height) {
+                report.issues.push(StorageIssue::MissingBlock { height });
+                let _ = damaged_heights.insert(height);
+            }
+        }
+    }
+
+    Ok(state_roots)
+}
+
+/// Checks that the height and switch block indices point at the given block header.
+fn check_header_indices<R>(
+    index_txn: &R,
+    block_header: &BlockHeader,
+    issues: &mut Vec<StorageIssue>,
+) where
+    R: DataReader<BlockHeight, BlockHeader> + DataReader<EraId, BlockHeader>,
+{
+    let block_hash = block_header.block_hash();
+    let indexed_by_height =
+        DataReader::<BlockHeight, BlockHeader>::read(index_txn, block_header.height());
+    if !matches!(indexed_by_height, Ok(Some(indexed)) if indexed.block_hash() == block_hash) {
+        issues.push(StorageIssue::HeightIndexMismatch {
+            height: block_header.height(),
+            block_hash,
+        });
+    }
+    if block_header.is_switch_block() {
+        let indexed_by_era =
+            DataReader::<EraId, BlockHeader>::read(index_txn, block_header.era_id());
+        if !matches!(indexed_by_era, Ok(Some(indexed)) if indexed.block_hash() == block_hash) {
+            issues.push(StorageIssue::SwitchBlockIndexMismatch {
+                era_id: block_header.era_id(),
+                block_hash,
+            });
+        }
+    }
+}
+
+/// Checks the approvals hashes, transactions and execution results of a complete block.
+fn check_block_data<R, I>(
+    ro_txn: &R,
+    maybe_index_txn: Option<&I>,
+    block: &Block,
+    report: &mut StorageReport,
+) where
+    R: DataReader<BlockHash, ApprovalsHashes>
+        + DataReader<TransactionHash, Transaction>
+        + DataReader<TransactionHash, ExecutionResult>,
+    I: DataReader<TransactionHash, BlockHashHeightAndEra>,
+{
+    let height = block.height();
+    let block_hash = *block.hash();
+    match DataReader::<BlockHash, ApprovalsHashes>::read(ro_txn, block_hash) {
+        Ok(Some(approvals_hashes)) => {
+            if let Err(error) = approvals_hashes.verify(block) {
+                report.issues.push(StorageIssue::InvalidApprovalsHashes {
+                    height,
+                    block_hash,
+                    error: error.to_string(),
+                });
+            }
+        }
+        // Blocks stored before approvals hashes were introduced have none.
+        Ok(None) => {
+            if matches!(block, Block::V2(_)) && block.all_transaction_hashes().next().is_some() {
+                report
+                    .issues
+                    .push(StorageIssue::MissingApprovalsHashes { height, block_hash });
+            }
+        }
+        Err(error) => report.issues.push(StorageIssue::InvalidApprovalsHashes {
+            height,
+            block_hash,
+            error: error.to_string(),
+        }),
+    }
+
+    for transaction_hash in block.all_transaction_hashes() {
+        match DataReader::<TransactionHash, Transaction>::read(ro_txn, transaction_hash) {
+            Ok(Some(transaction)) if has_valid_hash(&transaction, transaction_hash) => {}
+            Ok(Some(_)) | Err(_) => report.issues.push(StorageIssue::InvalidTransaction {
+                height,
+                transaction_hash,
+            }),
+            Ok(None) => report.issues.push(StorageIssue::MissingTransaction {
+                height,
+                transaction_hash,
+            }),
+        }
+        match DataReader::<TransactionHash, ExecutionResult>::read(ro_txn, transaction_hash) {
+            Ok(Some(_)) => {}
+            Ok(None) => report.issues.push(StorageIssue::MissingExecutionResult {
+                height,
+                transaction_hash,
+            }),
+            Err(error) => report.issues.push(StorageIssue::InvalidExecutionResult {
+                height,
+                transaction_hash,
+                error: error.to_string(),
+            }),
+        }
+        if let Some(index_txn) = maybe_index_txn {
+            let indexed = DataReader::<TransactionHash, BlockHashHeightAndEra>::read(
+                index_txn,
+                transaction_hash,
+            );
+            if !matches!(indexed, Ok(Some(indexed)) if indexed.block_hash == block_hash) {
+                report.issues.push(StorageIssue::TransactionIndexMismatch {
+                    transaction_hash,
+                    block_hash,
+                });
+            }
+        }
+    }
+}
+
+/// Returns `true` if the transaction hashes to the given hash.
+fn has_valid_hash(transaction: &Transaction, transaction_hash: TransactionHash) -> bool {
+    transaction.hash() == transaction_hash
+        && match transaction {
+            Transaction::Deploy(deploy) => deploy.has_valid_hash().is_ok(),
+            Transaction::V1(v1) => v1.has_valid_hash().is_ok(),
+        }
+}
+
+/// Returns the state roots of the `retained_count` highest complete blocks, which garbage
+/// collection never deletes, along with the number of other state roots.
+fn retained_state_roots_of(
+    state_roots: BTreeMap<Digest, Vec<u64>>,
+    retained_count: u64,
+) -> (BTreeMap<Digest, Vec<u64>>, u64) {
+    let heights: BTreeSet<u64> = state_roots.values().flatten().copied().collect();
+    let lowest_retained_height = heights
+        .iter()
+        .rev()
+        .take(usize::try_from(retained_count).unwrap_or(usize::MAX))
+        .last()
+        .copied()
+        .unwrap_or(0);
+    let total_count = state_roots.len() as u64;
+    let retained: BTreeMap<_, _> = state_roots
+        .into_iter()
+        .filter(|(_, heights)| {
+            heights
+                .iter()
+                .any(|height| *height >= lowest_retained_height)
+        })
+        .collect();
+    let unchecked_count = total_count - retained.len() as u64;
+    (retained, unchecked_count)
+}
+
+/// Checks that the global state under each of the given state roots is complete, marking the
+/// heights of the blocks having an incomplete one as damaged.
+fn check_global_state(
+    root: &Path,
+    config: &Config,
+    state_roots: BTreeMap<Digest, Vec<u64>>,
+    report: &mut StorageReport,
+    damaged_heights: &mut BTreeSet<u64>,
+) -> Result<(), Error> {
+    let environment = LmdbEnvironment::open_read_only(
+        root,
+        config.contract_runtime.max_global_state_size_or_default(),
+        config.contract_runtime.max_readers_or_default(),
+    )?;
+    let trie_store = LmdbTrieStore::open(&environment, None)?;
+    let txn = environment
+        .create_read_txn()
+        .map_err(GlobalStateError::from)?;
+    let mut sound_tries = HashSet::new();
+    for (state_root_hash, heights) in state_roots {
+        let issues = check_tries::<Key, StoredValue, _, _, GlobalStateError>(
+            &txn,
+            &trie_store,
+            &[state_root_hash],
+            &mut sound_tries,
+        )?;
+        report.checked_state_root_count += 1;
+        if !issues.is_empty() {
+            damaged_heights.extend(heights);
+            report.issues.extend(
+                issues
+                    .into_iter()
+                    .map(|issue| StorageIssue::from_trie_issue(state_root_hash, issue)),
+            );
+        }
+    }
+    report.sound_trie_count = sound_tries.len() as u64;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{testing::TestRng, ProtocolVersion, TestBlockBuilder};
+
+    use super::*;
+
+    #[test]
+    fn should_report_damaged_blocks() {
+        let rng = &mut TestRng::new();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let block_store = LmdbBlockStore::new(temp_dir.path(), 100 * 1024 * 1024).unwrap();
+        let mut block_store =
+            IndexedLmdbBlockStore::new(block_store, None, ProtocolVersion::V2_0_0).unwrap();
+
+        let sound_block = Block::from(
+            TestBlockBuilder::new()
+                .height(10)
+                .switch_block(false)
+                .build(rng),
+        );
+        // Blocks without transactions share the same empty body, so this one needs some.
+        let block_without_body = Block::from(
+            TestBlockBuilder::new()
+                .height(11)
+                .switch_block(false)
+                .random_transactions(1, rng)
+                .build(rng),
+        );
+        let block_without_transaction = Block::from(
+            TestBlockBuilder::new()
+                .height(13)
+                .switch_block(false)
+                .random_transactions(1, rng)
+                .build(rng),
+        );
+        let transaction_hash = block_without_transaction
+            .all_transaction_hashes()
+            .next()
+            .unwrap();
+        let mut rw_txn = block_store.checkout_rw().unwrap();
+        let _ = rw_txn.write(&sound_block).unwrap();
+        let _ = rw_txn.write(&block_without_body.clone_header()).unwrap();
+        let _ = rw_txn.write(&block_without_transaction).unwrap();
+        rw_txn.commit().unwrap();
+
+        // Block 12 is recorded as complete, but isn't stored at all.
+        let completed_blocks = DisjointSequences::from(vec![10, 11, 12, 13]);
+        let mut report = StorageReport::default();
+        let mut damaged_heights = BTreeSet::new();
+        let state_roots = check_blocks(
+            block_store.block_store(),
+            Some(&block_store),
+            &completed_blocks,
+            &mut report,
+            &mut damaged_heights,
+        )
+        .unwrap();
+
+        assert_eq!(report.checked_block_count, 3);
+        let mut issues = report.issues;
+        issues.sort_by_key(|issue| format!("{:?}", issue));
+        let mut expected = vec![
+            StorageIssue::MissingBlockBody {
+                height: 11,
+                block_hash: *block_without_body.hash(),
+            },
+            StorageIssue::MissingBlock { height: 12 },
+            StorageIssue::MissingApprovalsHashes {
+                height: 13,
+                block_hash: *block_without_transaction.hash(),
+            },
+            StorageIssue::MissingTransaction {
+                height: 13,
+                transaction_hash,
+            },
+            StorageIssue::MissingExecutionResult {
+                height: 13,
+                transaction_hash,
+            },
+        ];
+        expected.sort_by_key(|issue| format!("{:?}", issue));
+        assert_eq!(issues, expected);
+        assert_eq!(
+            damaged_heights.into_iter().collect::<Vec<_>>(),
+            vec![11, 12, 13]
+        );
+        assert_eq!(state_roots.len(), 3);
+        assert_eq!(state_roots[sound_block.state_root_hash()], vec![10]);
+    }
+
+    #[test]
+    fn should_only_check_state_roots_retained_by_garbage_collection() {
+        let rng = &mut TestRng::new();
+        let roots: Vec<Digest> = (0..4).map(|_| Digest::random(rng)).collect();
+        // The first root is shared by an old block and one of the most recent ones.
+        let state_roots = BTreeMap::from([
+            (roots[0], vec![1, 9]),
+            (roots[1], vec![2, 3]),
+            (roots[2], vec![7]),
+            (roots[3], vec![8, 10]),
+        ]);
+
+        let (retained, unchecked_count) = retained_state_roots_of(state_roots.clone(), 3);
+        assert_eq!(
+            retained.keys().collect::<BTreeSet<_>>(),
+            BTreeSet::from([&roots[0], &roots[3]])
+        );
+        assert_eq!(unchecked_count, 2);
+
+        let (retained, unchecked_count) = retained_state_roots_of(state_roots.clone(), 100);
+        assert_eq!(retained, state_roots);
+        assert_eq!(unchecked_count, 0);
+    }
+}
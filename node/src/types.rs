@@ -7,6 +7,7 @@ mod exit_code;
 mod max_ttl;
 mod node_config;
 mod node_id;
+mod peer_reputation;
 /// Peers map.
 mod status_feed;
 mod sync_leap;
@@ -34,6 +35,7 @@ pub use exit_code::ExitCode;
 pub(crate) use max_ttl::MaxTtl;
 pub use node_config::{NodeConfig, SyncHandling};
 pub(crate) use node_id::NodeId;
+pub(crate) use peer_reputation::{PeerReputation, PeerScore};
 pub use status_feed::{ChainspecInfo, GetStatusResult, StatusFeed};
 pub(crate) use sync_leap::{GlobalStatesMetadata, SyncLeap, SyncLeapIdentifier};
 pub(crate) use transaction::{
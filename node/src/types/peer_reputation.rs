@@ -0,0 +1,410 @@
+//! Reputation of peers we fetch data from.
+//!
+//! The fetchers record the outcome of every request sent to a peer, per kind of item. Components
+//! choosing which peers to fetch from use the resulting scores to prefer fast and reliable peers,
+//! while still spreading requests across all of them: peers are ordered by a weighted random draw,
+//! with the weight of a peer falling with every request currently in flight to it.
+
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::{Duration, Instant},
+};
+
+use datasize::DataSize;
+use rand::Rng;
+use serde::Serialize;
+
+use crate::{components::fetcher::Tag, types::NodeId, NodeRng};
+
+/// Weight of a new observation in the moving averages.
+const SMOOTHING_FACTOR: f64 = 0.2;
+
+/// Latency at which the speed component of a score is one half, also assumed for peers whose
+/// latency is not known yet.
+const REFERENCE_LATENCY_SECS: f64 = 1.0;
+
+/// Lowest possible score, ensuring that even the worst peers get an occasional chance to recover.
+const MIN_SCORE: f64 = 0.01;
+
+/// Time after which the statistics of a peer that was not fetched from are dropped.
+const MAX_STATS_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Statistics about fetching a single kind of item from a single peer.
+#[derive(Clone, DataSize, Debug)]
+struct PeerStats {
+    /// Number of requests currently awaiting a response.
+    in_flight: u32,
+    /// Number of items received.
+    successes: u64,
+    /// Number of requests that timed out.
+    timeouts: u64,
+    /// Number of invalid items received.
+    bad_items: u64,
+    /// Number of requests answered with the item being absent or the request being rejected.
+    absent: u64,
+    /// Moving average of the latency of successful requests, in seconds.
+    latency_secs: Option<f64>,
+    /// Moving average of the throughput of successful requests, in bytes per second.
+    throughput: Option<f64>,
+    /// Moving average of the fraction of requests that timed out.
+    timeout_rate: f64,
+    /// Moving average of the fraction of requests answered with an invalid item.
+    bad_item_rate: f64,
+    /// Moving average of the fraction of requests answered with the item being absent.
+    absent_rate: f64,
+    /// When the statistics were last updated.
+    last_update: Instant,
+}
+
+impl PeerStats {
+    fn new(now: Instant) -> Self {
+        PeerStats {
+            in_flight: 0,
+            successes: 0,
+            timeouts: 0,
+            bad_items: 0,
+            absent: 0,
+            latency_secs: None,
+            throughput: None,
+            timeout_rate: 0.0,
+            bad_item_rate: 0.0,
+            absent_rate: 0.0,
+            last_update: now,
+        }
+    }
+
+    /// Updates the failure rates with the outcome of a single request.
+    fn record_outcome(&mut self, timed_out: bool, bad_item: bool, absent: bool, now: Instant) {
+        self.timeout_rate = moving_average(Some(self.timeout_rate), indicator(timed_out));
+        self.bad_item_rate = moving_average(Some(self.bad_item_rate), indicator(bad_item));
+        self.absent_rate = moving_average(Some(self.absent_rate), indicator(absent));
+        self.last_update = now;
+    }
+
+    /// Returns the score of the peer for this kind of item, between `MIN_SCORE` and 1.
+    fn score(&self) -> f64 {
+        let reliability =
+            (1.0 - self.timeout_rate) * (1.0 - self.bad_item_rate) * (1.0 - self.absent_rate / 2.0);
+        let latency_secs = self.latency_secs.unwrap_or(REFERENCE_LATENCY_SECS);
+        let speed = REFERENCE_LATENCY_SECS / (REFERENCE_LATENCY_SECS + latency_secs);
+        (reliability * speed).max(MIN_SCORE)
+    }
+}
+
+fn indicator(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn moving_average(average: Option<f64>, sample: f64) -> f64 {
+    match average {
+        Some(average) => average + SMOOTHING_FACTOR * (sample - average),
+        None => sample,
+    }
+}
+
+/// The score of a peer for a single kind of item, as reported through the diagnostics port.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct PeerScore {
+    /// The peer.
+    pub(crate) peer: NodeId,
+    /// The kind of item.
+    pub(crate) tag: Tag,
+    /// The current score, between 0 and 1.
+    pub(crate) score: f64,
+    /// Number of requests currently awaiting a response.
+    pub(crate) in_flight: u32,
+    /// Average latency of successful requests.
+    pub(crate) latency: Option<Duration>,
+    /// Average throughput of successful requests, in bytes per second.
+    pub(crate) throughput: Option<f64>,
+    /// Number of items received.
+    pub(crate) successes: u64,
+    /// Number of requests that timed out.
+    pub(crate) timeouts: u64,
+    /// Number of invalid items received.
+    pub(crate) bad_items: u64,
+    /// Number of requests answered with the item being absent or the request being rejected.
+    pub(crate) absent: u64,
+}
+
+/// Shared reputation of the peers we fetch data from, by peer and kind of item.
+///
+/// Cloning yields a handle to the same underlying statistics.
+#[derive(Clone, DataSize, Debug, Default)]
+pub(crate) struct PeerReputation {
+    inner: Arc<RwLock<BTreeMap<NodeId, BTreeMap<Tag, PeerStats>>>>,
+}
+
+impl PeerReputation {
+    /// Creates a new, empty peer reputation.
+    pub(crate) fn new() -> Self {
+        PeerReputation::default()
+    }
+
+    fn read_inner(&self) -> RwLockReadGuard<BTreeMap<NodeId, BTreeMap<Tag, PeerStats>>> {
+        self.inner.read().unwrap()
+    }
+
+    fn write_inner(&self) -> RwLockWriteGuard<BTreeMap<NodeId, BTreeMap<Tag, PeerStats>>> {
+        self.inner.write().unwrap()
+    }
+
+    /// Applies `f` to the statistics of the given peer and kind of item, creating them if needed.
+    fn update<F: FnOnce(&mut PeerStats, Instant)>(&self, peer: NodeId, tag: Tag, f: F) {
+        let now = Instant::now();
+        let mut inner = self.write_inner();
+        let stats = inner
+            .entry(peer)
+            .or_default()
+            .entry(tag)
+            .or_insert_with(|| PeerStats::new(now));
+        f(stats, now);
+    }
+
+    /// Records that a request for an item was sent to a peer.
+    pub(crate) fn request_sent(&self, peer: NodeId, tag: Tag) {
+        self.update(peer, tag, |stats, _| {
+            stats.in_flight = stats.in_flight.saturating_add(1)
+        });
+    }
+
+    /// Records that a request for an item sent to a peer is no longer awaiting a response.
+    pub(crate) fn request_finished(&self, peer: NodeId, tag: Tag) {
+        self.update(peer, tag, |stats, _| {
+            stats.in_flight = stats.in_flight.saturating_sub(1)
+        });
+    }
+
+    /// Records a valid item of `size` bytes received from a peer `latency` after requesting it.
+    pub(crate) fn record_success(&self, peer: NodeId, tag: Tag, latency: Duration, size: usize) {
+        self.update(peer, tag, |stats, now| {
+            let latency_secs = latency.as_secs_f64();
+            stats.successes += 1;
+            stats.latency_secs = Some(moving_average(stats.latency_secs, latency_secs));
+            if latency_secs > 0.0 {
+                let throughput = size as f64 / latency_secs;
+                stats.throughput = Some(moving_average(stats.throughput, throughput));
+            }
+            stats.record_outcome(false, false, false, now);
+        });
+    }
+
+    /// Records a request to a peer that timed out.
+    pub(crate) fn record_timeout(&self, peer: NodeId, tag: Tag) {
+        self.update(peer, tag, |stats, now| {
+            stats.timeouts += 1;
+            stats.record_outcome(true, false, false, now);
+        });
+    }
+
+    /// Records an invalid item received from a peer.
+    pub(crate) fn record_bad_item(&self, peer: NodeId, tag: Tag) {
+        self.update(peer, tag, |stats, now| {
+            stats.bad_items += 1;
+            stats.record_outcome(false, true, false, now);
+        });
+    }
+
+    /// Records a peer not providing a requested item, either as it does not have it or as it
+    /// rejected the request.
+    pub(crate) fn record_absent(&self, peer: NodeId, tag: Tag) {
+        self.update(peer, tag, |stats, now| {
+            stats.absent += 1;
+            stats.record_outcome(false, false, true, now);
+        });
+    }
+
+    /// Returns the weight of a peer when choosing peers to fetch items of the given kind from, or
+    /// any kind of item if `tag` is `None`.
+    ///
+    /// The weight is the peer's score, reduced by the number of requests in flight to it.
+    fn weight(
+        inner: &BTreeMap<NodeId, BTreeMap<Tag, PeerStats>>,
+        peer: &NodeId,
+        tag: Option<Tag>,
+    ) -> f64 {
+        let unknown = PeerStats::new(Instant::now()).score();
+        let all_stats = match inner.get(peer) {
+            Some(all_stats) => all_stats,
+            None => return unknown,
+        };
+        let (score, in_flight) = match tag {
+            Some(tag) => all_stats
+                .get(&tag)
+                .map_or((unknown, 0), |stats| (stats.score(), stats.in_flight)),
+            None if all_stats.is_empty() => (unknown, 0),
+            None => {
+                let score_sum: f64 = all_stats.values().map(PeerStats::score).sum();
+                let in_flight = all_stats.values().map(|stats| stats.in_flight).sum();
+                (score_sum / all_stats.len() as f64, in_flight)
+            }
+        };
+        score / (1.0 + f64::from(in_flight))
+    }
+
+    /// Orders `peers` by preference for fetching items of the given kind from, or any kind of item
+    /// if `tag` is `None`, most preferred first.
+    ///
+    /// The order is a weighted random draw, so that better peers tend to come first, but requests
+    /// are still spread across all peers.
+    pub(crate) fn rank_peers(&self, tag: Option<Tag>, peers: &mut [NodeId], rng: &mut NodeRng) {
+        let keys: BTreeMap<NodeId, f64> = {
+            let inner = self.read_inner();
+            peers
+                .iter()
+                .map(|peer| {
+                    let weight = Self::weight(&inner, peer, tag);
+                    (*peer, rng.gen::<f64>().powf(1.0 / weight))
+                })
+                .collect()
+        };
+        peers.sort_by(|peer1, peer2| {
+            keys[peer2]
+                .partial_cmp(&keys[peer1])
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+
+    /// Chooses up to `count` of the given peers to fetch items of the given kind from, or any
+    /// kind of item if `tag` is `None`.
+    ///
+    /// If there are no more than `count` peers, all of them are returned in their original order.
+    pub(crate) fn select_peers(
+        &self,
+        tag: Option<Tag>,
+        mut peers: Vec<NodeId>,
+        count: usize,
+        rng: &mut NodeRng,
+    ) -> Vec<NodeId> {
+        if peers.len() > count {
+            self.rank_peers(tag, &mut peers, rng);
+            peers.truncate(count);
+        }
+        peers
+    }
+
+    /// Drops the statistics of peers not fetched from within the last hour.
+    pub(crate) fn prune(&self, now: Instant) {
+        let mut inner = self.write_inner();
+        inner.retain(|_, all_stats| {
+            all_stats.retain(|_, stats| {
+                stats.in_flight > 0
+                    || now.saturating_duration_since(stats.last_update) < MAX_STATS_AGE
+            });
+            !all_stats.is_empty()
+        });
+    }
+
+    /// Returns the current scores of all peers, by peer and kind of item.
+    pub(crate) fn scores(&self) -> Vec<PeerScore> {
+        self.read_inner()
+            .iter()
+            .flat_map(|(peer, all_stats)| {
+                all_stats.iter().map(|(tag, stats)| PeerScore {
+                    peer: *peer,
+                    tag: *tag,
+                    score: stats.score(),
+                    in_flight: stats.in_flight,
+                    latency: stats.latency_secs.map(Duration::from_secs_f64),
+                    throughput: stats.throughput,
+                    successes: stats.successes,
+                    timeouts: stats.timeouts,
+                    bad_items: stats.bad_items,
+                    absent: stats.absent,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn should_score_fast_reliable_peers_higher() {
+        let mut rng = TestRng::new();
+        let reputation = PeerReputation::new();
+        let fast = NodeId::random(&mut rng);
+        let slow = NodeId::random(&mut rng);
+        let flaky = NodeId::random(&mut rng);
+        let unknown = NodeId::random(&mut rng);
+        let tag = Tag::TrieOrChunk;
+
+        for _ in 0..10 {
+            reputation.record_success(fast, tag, Duration::from_millis(50), 1000);
+            reputation.record_success(slow, tag, Duration::from_secs(5), 1000);
+            reputation.record_success(flaky, tag, Duration::from_millis(50), 1000);
+            reputation.record_timeout(flaky, tag);
+            reputation.record_bad_item(flaky, tag);
+        }
+
+        let weight = |peer| PeerReputation::weight(&reputation.read_inner(), &peer, Some(tag));
+        assert!(weight(fast) > weight(unknown));
+        assert!(weight(unknown) > weight(slow));
+        assert!(weight(fast) > weight(flaky));
+        // Scores for other kinds of items are unaffected.
+        assert_eq!(
+            PeerReputation::weight(&reputation.read_inner(), &slow, Some(Tag::Block)),
+            weight(unknown)
+        );
+
+        // Requests in flight reduce the weight, spreading requests across peers.
+        let before = weight(fast);
+        reputation.request_sent(fast, tag);
+        assert!(weight(fast) < before);
+        reputation.request_finished(fast, tag);
+        assert_eq!(weight(fast), before);
+
+        let mut fast_first = 0;
+        for _ in 0..100 {
+            let mut peers = vec![slow, fast];
+            reputation.rank_peers(Some(tag), &mut peers, &mut rng);
+            if peers[0] == fast {
+                fast_first += 1;
+            }
+        }
+        assert!(fast_first > 50);
+    }
+
+    #[test]
+    fn should_select_all_peers_if_sufficient() {
+        let mut rng = TestRng::new();
+        let reputation = PeerReputation::new();
+        let peers: Vec<_> = (0..5).map(|_| NodeId::random(&mut rng)).collect();
+
+        assert_eq!(
+            reputation.select_peers(None, peers.clone(), 5, &mut rng),
+            peers
+        );
+        let selected = reputation.select_peers(None, peers.clone(), 3, &mut rng);
+        assert_eq!(selected.len(), 3);
+        assert!(selected.iter().all(|peer| peers.contains(peer)));
+    }
+
+    #[test]
+    fn should_prune_stale_statistics() {
+        let mut rng = TestRng::new();
+        let reputation = PeerReputation::new();
+        let peer = NodeId::random(&mut rng);
+        let busy_peer = NodeId::random(&mut rng);
+        reputation.record_success(peer, Tag::Block, Duration::from_millis(10), 100);
+        reputation.request_sent(busy_peer, Tag::Block);
+
+        reputation.prune(Instant::now());
+        assert_eq!(reputation.scores().len(), 2);
+
+        reputation.prune(Instant::now() + MAX_STATS_AGE);
+        let scores = reputation.scores();
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].peer, busy_peer);
+    }
+}
@@ -10,11 +10,15 @@ use datasize::DataSize;
 use itertools::Itertools;
 use num_rational::Ratio;
 use serde::Serialize;
-use tracing::info;
+use tracing::{info, warn};
 
-use casper_types::{
-    BlockHeaderV2, ChainNameDigest, EraId, FinalitySignatureV2, PublicKey, SecretKey, U512,
-};
+#[cfg(test)]
+use casper_types::SecretKey;
+use casper_types::{BlockHeaderV2, ChainNameDigest, EraId, FinalitySignatureV2, PublicKey, U512};
+
+#[cfg(test)]
+use crate::signer::LocalSigner;
+use crate::signer::{SigningRequest, ValidatorSigner};
 
 const MINIMUM_CUSP_ERA_COUNT: u64 = 2;
 const PROPOSED_BLOCK_ERA_TOLERANCE: u64 = 1;
@@ -47,7 +51,8 @@ pub(crate) struct ValidatorMatrix {
     chainspec_activation_era: EraId,
     #[data_size(skip)]
     finality_threshold_fraction: Ratio<u64>,
-    secret_signing_key: Arc<SecretKey>,
+    #[data_size(skip)]
+    signer: Arc<dyn ValidatorSigner>,
     public_signing_key: PublicKey,
     auction_delay: u64,
     signature_rewards_max_delay: u64,
@@ -61,19 +66,19 @@ impl ValidatorMatrix {
         chainspec_name_hash: ChainNameDigest,
         chainspec_validators: Option<BTreeMap<PublicKey, U512>>,
         chainspec_activation_era: EraId,
-        secret_signing_key: Arc<SecretKey>,
-        public_signing_key: PublicKey,
+        signer: Arc<dyn ValidatorSigner>,
         auction_delay: u64,
         signature_rewards_max_delay: u64,
     ) -> Self {
         let inner = Arc::new(RwLock::new(BTreeMap::new()));
+        let public_signing_key = signer.public_key().clone();
         ValidatorMatrix {
             inner,
             finality_threshold_fraction,
             chainspec_name_hash,
             chainspec_validators: chainspec_validators.map(Arc::new),
             chainspec_activation_era,
-            secret_signing_key,
+            signer,
             public_signing_key,
             auction_delay,
             signature_rewards_max_delay,
@@ -99,7 +104,7 @@ impl ValidatorMatrix {
             chainspec_activation_era: EraId::from(0),
             finality_threshold_fraction,
             public_signing_key,
-            secret_signing_key,
+            signer: Arc::new(LocalSigner::new(secret_signing_key)),
             auction_delay: 1,
             signature_rewards_max_delay: 3,
             retrograde_latch: None,
@@ -130,7 +135,7 @@ impl ValidatorMatrix {
             chainspec_activation_era: EraId::from(0),
             finality_threshold_fraction,
             public_signing_key,
-            secret_signing_key,
+            signer: Arc::new(LocalSigner::new(secret_signing_key)),
             auction_delay: 1,
             signature_rewards_max_delay: 3,
             retrograde_latch: None,
@@ -290,8 +295,8 @@ impl ValidatorMatrix {
         &self.public_signing_key
     }
 
-    pub(crate) fn secret_signing_key(&self) -> &Arc<SecretKey> {
-        &self.secret_signing_key
+    pub(crate) fn signer(&self) -> &Arc<dyn ValidatorSigner> {
+        &self.signer
     }
 
     /// Returns whether `pub_key` is the ID of a validator in this era, or `None` if the validator
@@ -320,13 +325,26 @@ impl ValidatorMatrix {
             .is_self_validator_in_era(block_header.era_id())
             .unwrap_or(false)
         {
-            return Some(FinalitySignatureV2::create(
-                block_header.block_hash(),
-                block_header.height(),
-                block_header.era_id(),
-                self.chainspec_name_hash,
-                &self.secret_signing_key,
-            ));
+            let request = SigningRequest::FinalitySignature {
+                block_hash: block_header.block_hash(),
+                block_height: block_header.height(),
+                era_id: block_header.era_id(),
+                chain_name_hash: self.chainspec_name_hash,
+            };
+            return match self.signer.sign(&request) {
+                Ok(signature) => Some(FinalitySignatureV2::new(
+                    block_header.block_hash(),
+                    block_header.height(),
+                    block_header.era_id(),
+                    self.chainspec_name_hash,
+                    signature,
+                    self.public_signing_key.clone(),
+                )),
+                Err(error) => {
+                    warn!(%error, %request, "failed to create finality signature");
+                    None
+                }
+            };
         }
         None
     }
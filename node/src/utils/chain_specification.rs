@@ -317,6 +317,13 @@ mod tests {
             upgrade: HostFunction::new(110, [0, 1, 2, 3, 4, 5]),
             call: HostFunction::new(111, [0, 1, 2, 3, 4, 5, 6, 7, 8]),
             print: HostFunction::new(112, [0, 1]),
+            emit: HostFunction::new(113, [0, 1, 2, 3]),
+            blake2b: HostFunction::new(114, [0, 1, 2, 3]),
+            generic_hash: HostFunction::new(115, [0, 1, 2, 3, 4]),
+            recover_secp256k1: HostFunction::new(116, [0, 1, 2, 3, 4, 5]),
+            verify_signature: HostFunction::new(117, [0, 1, 2, 3, 4, 5]),
+            remove: HostFunction::new(118, [0, 1, 2]),
+            iterate: HostFunction::new(119, [0, 1, 2, 3, 4, 5, 6, 7]),
         });
     static EXPECTED_GENESIS_WASM_COSTS: Lazy<WasmConfig> = Lazy::new(|| {
         let wasm_v1_config = WasmV1Config::new(
@@ -42,4 +42,6 @@ pub enum Cep18Error {
     MintBurnDisabled,
     CannotTargetSelfUser,
     InvalidBurnTarget,
+    /// An event could not be emitted.
+    FailedToEmitEvent,
 }
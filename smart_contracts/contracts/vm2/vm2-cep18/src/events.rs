@@ -0,0 +1,20 @@
+use casper_macros::casper;
+use casper_sdk::host::Entity;
+
+/// Emitted when tokens are moved from one entity to another.
+#[derive(Debug, PartialEq, Eq)]
+#[casper(event)]
+pub struct Transfer {
+    pub sender: Entity,
+    pub recipient: Entity,
+    pub amount: u64,
+}
+
+/// Emitted when the allowance of a spender over the tokens of an owner changes.
+#[derive(Debug, PartialEq, Eq)]
+#[casper(event)]
+pub struct Approval {
+    pub owner: Entity,
+    pub spender: Entity,
+    pub amount: u64,
+}
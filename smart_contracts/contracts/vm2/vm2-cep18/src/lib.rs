@@ -2,5 +2,6 @@ extern crate alloc;
 
 pub mod contract;
 pub mod error;
+pub mod events;
 pub mod security_badge;
 pub mod traits;
@@ -5,7 +5,11 @@ use casper_sdk::{
     log,
 };
 
-use crate::{error::Cep18Error, security_badge::SecurityBadge};
+use crate::{
+    error::Cep18Error,
+    events::{Approval, Transfer},
+    security_badge::SecurityBadge,
+};
 
 #[derive(Debug)]
 #[casper]
@@ -57,6 +61,30 @@ impl CEP18State {
 
         self.balances.insert(sender, &new_sender_balance);
         self.balances.insert(recipient, &new_recipient_balance);
+
+        host::emit(&Transfer {
+            sender: *sender,
+            recipient: *recipient,
+            amount,
+        })
+        .map_err(|_| Cep18Error::FailedToEmitEvent)?;
+        Ok(())
+    }
+
+    fn set_allowance(
+        &mut self,
+        owner: Entity,
+        spender: Entity,
+        amount: u64,
+    ) -> Result<(), Cep18Error> {
+        self.allowances.insert(&(owner, spender), &amount);
+
+        host::emit(&Approval {
+            owner,
+            spender,
+            amount,
+        })
+        .map_err(|_| Cep18Error::FailedToEmitEvent)?;
         Ok(())
     }
 }
@@ -117,9 +145,7 @@ pub trait CEP18 {
         if owner == spender {
             return Err(Cep18Error::CannotTargetSelfUser);
         }
-        let lookup_key = (owner, spender);
-        self.state_mut().allowances.insert(&lookup_key, &amount);
-        Ok(())
+        self.state_mut().set_allowance(owner, spender, amount)
     }
 
     #[casper(revert_on_error)]
@@ -131,8 +157,7 @@ pub trait CEP18 {
         let lookup_key = (owner, spender);
         let allowance = self.state().allowances.get(&lookup_key).unwrap_or_default();
         let allowance = allowance.saturating_sub(amount);
-        self.state_mut().allowances.insert(&lookup_key, &allowance);
-        Ok(())
+        self.state_mut().set_allowance(owner, spender, allowance)
     }
 
     #[casper(revert_on_error)]
@@ -144,8 +169,7 @@ pub trait CEP18 {
         let lookup_key = (owner, spender);
         let allowance = self.state().allowances.get(&lookup_key).unwrap_or_default();
         let allowance = allowance.saturating_add(amount);
-        self.state_mut().allowances.insert(&lookup_key, &allowance);
-        Ok(())
+        self.state_mut().set_allowance(owner, spender, allowance)
     }
 
     #[casper(revert_on_error)]
@@ -188,10 +212,7 @@ pub trait CEP18 {
             .transfer_balance(&owner, &recipient, amount)?;
 
         self.state_mut()
-            .allowances
-            .insert(&(owner, spender), &new_spender_allowance);
-
-        Ok(())
+            .set_allowance(owner, spender, new_spender_allowance)
     }
 }
 
@@ -36,6 +36,9 @@ struct MethodAttribute {
 struct StructMeta {
     #[darling(default)]
     contract_state: bool,
+    /// Makes the struct an event which can be emitted with `casper_sdk::host::emit`.
+    #[darling(default)]
+    event: bool,
 }
 
 #[derive(Debug, FromMeta)]
@@ -93,7 +96,13 @@ pub fn casper(attrs: TokenStream, item: TokenStream) -> TokenStream {
 
     if let Ok(item_struct) = syn::parse::<ItemStruct>(item.clone()) {
         let struct_meta = StructMeta::from_list(&attr_args).unwrap();
-        if !struct_meta.contract_state {
+        if struct_meta.event {
+            let partial = generate_casper_event_for_struct(item_struct);
+            quote! {
+                #partial
+            }
+            .into()
+        } else if !struct_meta.contract_state {
             let partial = generate_casper_state_for_struct(item_struct);
             quote! {
                 #partial
@@ -1115,6 +1124,21 @@ fn generate_casper_state_for_struct(item_struct: ItemStruct) -> impl quote::ToTo
     }
 }
 
+fn generate_casper_event_for_struct(item_struct: ItemStruct) -> impl quote::ToTokens {
+    let struct_name = &item_struct.ident;
+    let topic_name = struct_name.to_string();
+    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+    let partial = generate_casper_state_for_struct(item_struct.clone());
+
+    quote! {
+        #partial
+
+        impl #impl_generics casper_sdk::Event for #struct_name #ty_generics #where_clause {
+            const TOPIC: &'static str = #topic_name;
+        }
+    }
+}
+
 fn generate_casper_state_for_enum(item_enum: ItemEnum) -> impl quote::ToTokens {
     let maybe_derive_abi = get_maybe_derive_abi();
 
@@ -18,6 +18,23 @@ macro_rules! for_each_host_function {
                 value_ptr: *const u8,
                 value_size: usize,
             ) -> i32;
+            #[doc = r"Remove value from a storage available for caller's entity address."]
+            pub fn casper_remove(
+                key_space: u64,
+                key_ptr: *const u8,
+                key_size: usize,
+            ) -> i32;
+            #[doc = r"Read a page of the members of an iterable collection's index, from the given position."]
+            pub fn casper_iterate(
+                key_space: u64,
+                prefix_ptr: *const u8,
+                prefix_size: usize,
+                start: u64,
+                limit: u32,
+                info: *mut $crate::ReadInfo,
+                alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8,
+                alloc_ctx: *const core::ffi::c_void,
+            ) -> i32;
             pub fn casper_print(msg_ptr: *const u8, msg_size: usize,);
             pub fn casper_return(flags: u32, data_ptr: *const u8, data_len: usize,);
             pub fn casper_copy_input(
@@ -64,6 +81,46 @@ macro_rules! for_each_host_function {
             pub fn casper_env_block_time() -> u64;
 
             pub fn casper_transfer(entity_addr_ptr: *const u8, entity_addr_len: usize, amount: *const core::ffi::c_void,) -> u32;
+            #[doc = r"Emit a message under a topic of the currently executing entity."]
+            pub fn casper_emit(
+                topic_name_ptr: *const u8,
+                topic_name_size: usize,
+                payload_ptr: *const u8,
+                payload_size: usize,
+            ) -> i32;
+            #[doc = r"Hash the input with Blake2b into a 32 byte output buffer."]
+            pub fn casper_blake2b(
+                in_ptr: *const u8,
+                in_size: usize,
+                out_ptr: *mut u8,
+                out_size: usize,
+            ) -> i32;
+            #[doc = r"Hash the input with the given hash algorithm into a 32 byte output buffer."]
+            pub fn casper_generic_hash(
+                in_ptr: *const u8,
+                in_size: usize,
+                hash_algorithm: u32,
+                out_ptr: *mut u8,
+                out_size: usize,
+            ) -> i32;
+            #[doc = r"Recover the secp256k1 public key which signed the data."]
+            pub fn casper_recover_secp256k1(
+                data_ptr: *const u8,
+                data_size: usize,
+                signature_ptr: *const u8,
+                signature_size: usize,
+                public_key_ptr: *mut u8,
+                recovery_id: u32,
+            ) -> i32;
+            #[doc = r"Verify the signature of a message against a public key."]
+            pub fn casper_verify_signature(
+                message_ptr: *const u8,
+                message_size: usize,
+                signature_ptr: *const u8,
+                signature_size: usize,
+                public_key_ptr: *const u8,
+                public_key_size: usize,
+            ) -> i32;
         }
     };
 }
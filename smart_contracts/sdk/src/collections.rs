@@ -1,3 +1,6 @@
+mod index;
+mod iterable_map;
+mod iterable_set;
 mod lookup_key;
 
 mod map;
@@ -5,6 +8,8 @@ mod set;
 pub mod sorted_vector;
 mod vector;
 
+pub use iterable_map::IterableMap;
+pub use iterable_set::IterableSet;
 pub use map::Map;
 pub use set::Set;
 pub use vector::Vector;
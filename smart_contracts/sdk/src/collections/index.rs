@@ -0,0 +1,69 @@
+//! Index of the members of a collection, kept at consecutive positions so that they can be iterated
+//! over a page at a time.
+//!
+//! The index lives under its own context keys, next to the entries of the collection, whose layout
+//! it leaves untouched.  It is only maintained by the iterable collections, so the entries written
+//! by plain ones aren't part of it.
+use crate::host::{self, read_into_vec};
+use casper_executor_wasm_common::keyspace::{
+    collection_length_key, collection_member_key, collection_position_key, Keyspace,
+};
+
+/// Returns the number of members of the collection with the given prefix.
+pub(crate) fn len(prefix: &[u8]) -> u64 {
+    read_u64(&collection_length_key(prefix)).unwrap_or(0)
+}
+
+/// Adds a member to the collection with the given prefix, returning whether it is new.
+pub(crate) fn insert(prefix: &[u8], member: &[u8]) -> bool {
+    let position_key = collection_position_key(prefix, member);
+    if read_u64(&position_key).is_some() {
+        return false;
+    }
+    let length = len(prefix);
+    write(&collection_member_key(prefix, length), member);
+    write(&position_key, &length.to_le_bytes());
+    write(&collection_length_key(prefix), &(length + 1).to_le_bytes());
+    true
+}
+
+/// Removes a member from the collection with the given prefix, returning whether it was present.
+///
+/// The last member takes the position of the removed one, so the positions stay consecutive.
+pub(crate) fn remove(prefix: &[u8], member: &[u8]) -> bool {
+    let position_key = collection_position_key(prefix, member);
+    let Some(position) = read_u64(&position_key) else {
+        return false;
+    };
+    let last_position = len(prefix) - 1;
+    let last_member_key = collection_member_key(prefix, last_position);
+    if position != last_position {
+        let last_member =
+            read_into_vec(Keyspace::Context(&last_member_key)).expect("last member should exist");
+        write(&collection_member_key(prefix, position), &last_member);
+        write(
+            &collection_position_key(prefix, &last_member),
+            &position.to_le_bytes(),
+        );
+    }
+    host::casper_remove(Keyspace::Context(&last_member_key)).unwrap();
+    host::casper_remove(Keyspace::Context(&position_key)).unwrap();
+    write(&collection_length_key(prefix), &last_position.to_le_bytes());
+    true
+}
+
+/// Returns an iterator over the members of the collection with the given prefix.
+pub(crate) fn iter(prefix: &[u8]) -> host::CollectionIter<'_> {
+    host::iter_collection(prefix)
+}
+
+fn read_u64(context_key: &[u8]) -> Option<u64> {
+    let bytes = read_into_vec(Keyspace::Context(context_key))?;
+    Some(u64::from_le_bytes(
+        bytes.try_into().expect("index value should be a u64"),
+    ))
+}
+
+fn write(context_key: &[u8], value: &[u8]) {
+    host::casper_write(Keyspace::Context(context_key), value).unwrap();
+}
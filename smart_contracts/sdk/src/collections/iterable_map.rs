@@ -0,0 +1,177 @@
+use super::{index, Map};
+use crate::{
+    abi::{CasperABI, Declaration, Definition},
+    serializers::borsh::{BorshDeserialize, BorshSerialize},
+};
+
+/// A [`Map`] whose entries can be counted and iterated over.
+///
+/// The keys of the map are recorded in an index next to its entries, which makes inserting and
+/// removing entries more expensive than in a plain [`Map`].  Only the entries inserted through an
+/// `IterableMap` are indexed: entries of a [`Map`] with the same name can be read, but aren't
+/// counted nor iterated over.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+#[borsh(crate = "crate::serializers::borsh")]
+pub struct IterableMap<K, V> {
+    map: Map<K, V>,
+}
+
+impl<K, V> IterableMap<K, V>
+where
+    K: BorshSerialize,
+    V: BorshSerialize + BorshDeserialize,
+{
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            map: Map::new(name),
+        }
+    }
+
+    pub fn insert(&mut self, key: &K, value: &V) {
+        self.map.insert(key, value);
+        index::insert(self.map.name.as_bytes(), &borsh::to_vec(key).unwrap());
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.map.get(key)
+    }
+
+    /// Removes a key from the map, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.map.remove(key)?;
+        index::remove(self.map.name.as_bytes(), &borsh::to_vec(key).unwrap());
+        Some(value)
+    }
+
+    /// Returns the number of indexed entries of the map.
+    #[inline(always)]
+    pub fn len(&self) -> u64 {
+        index::len(self.map.name.as_bytes())
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K, V> IterableMap<K, V>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+{
+    /// Returns an iterator over the indexed entries of the map, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (K, V)> + '_ {
+        index::iter(self.map.name.as_bytes()).map(move |member| {
+            let key = borsh::from_slice(&member).unwrap();
+            let value = self.get(&key).expect("indexed entry should exist");
+            (key, value)
+        })
+    }
+}
+
+impl<K: CasperABI, V: CasperABI> CasperABI for IterableMap<K, V> {
+    fn populate_definitions(definitions: &mut crate::abi::Definitions) {
+        Map::<K, V>::populate_definitions(definitions);
+    }
+
+    fn declaration() -> Declaration {
+        format!("IterableMap<{}, {}>", K::declaration(), V::declaration())
+    }
+
+    #[inline]
+    fn definition() -> Definition {
+        Map::<K, V>::definition()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::native::{dispatch_with, Environment};
+
+    #[test]
+    fn test_iterable_map_remove_and_iter() {
+        dispatch_with(Environment::default(), || {
+            let mut map = IterableMap::<u64, u64>::new("test");
+            map.insert(&1, &2);
+            map.insert(&2, &3);
+            map.insert(&2, &4);
+            assert_eq!(map.len(), 2);
+
+            // Entries of a map with a longer name aren't visited.
+            let mut other_map = IterableMap::<u64, u64>::new("test_other");
+            other_map.insert(&3, &5);
+
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort();
+            assert_eq!(entries, vec![(1, 2), (2, 4)]);
+
+            assert_eq!(map.remove(&1), Some(2));
+            assert_eq!(map.remove(&1), None);
+            assert_eq!(map.get(&1), None);
+            assert_eq!(map.len(), 1);
+            assert_eq!(map.iter().collect::<Vec<_>>(), vec![(2, 4)]);
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_iterable_map_keeps_positions_consecutive() {
+        dispatch_with(Environment::default(), || {
+            let mut map = IterableMap::<u64, u64>::new("test");
+            for key in 0..5 {
+                map.insert(&key, &key);
+            }
+            // The last entry moves to the position of a removed one.
+            assert_eq!(map.remove(&1), Some(1));
+            assert_eq!(map.remove(&4), Some(4));
+            assert_eq!(map.len(), 3);
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort();
+            assert_eq!(entries, vec![(0, 0), (2, 2), (3, 3)]);
+
+            map.insert(&1, &10);
+            assert_eq!(map.len(), 4);
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort();
+            assert_eq!(entries, vec![(0, 0), (1, 10), (2, 2), (3, 3)]);
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_iterable_map_skips_entries_of_plain_map() {
+        dispatch_with(Environment::default(), || {
+            let mut plain_map = Map::<u64, u64>::new("test");
+            plain_map.insert(&1, &2);
+
+            let mut map = IterableMap::<u64, u64>::new("test");
+            assert_eq!(map.get(&1), Some(2));
+            assert_eq!(map.len(), 0);
+            assert_eq!(map.iter().count(), 0);
+
+            map.insert(&1, &3);
+            assert_eq!(plain_map.get(&1), Some(3));
+            assert_eq!(map.iter().collect::<Vec<_>>(), vec![(1, 3)]);
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_iterable_map_iter_over_multiple_pages() {
+        dispatch_with(Environment::default(), || {
+            let mut map = IterableMap::<u64, u64>::new("test");
+            for key in 0..250 {
+                map.insert(&key, &(key * 2));
+            }
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort();
+            assert_eq!(
+                entries,
+                (0..250).map(|key| (key, key * 2)).collect::<Vec<_>>()
+            );
+        })
+        .unwrap();
+    }
+}
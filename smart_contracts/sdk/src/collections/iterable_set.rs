@@ -0,0 +1,126 @@
+use crate::serializers::borsh::{BorshDeserialize, BorshSerialize};
+
+use super::{
+    index,
+    lookup_key::{Identity, LookupKey, LookupKeyOwned},
+    Set,
+};
+
+/// A [`Set`] whose elements can be counted and iterated over.
+///
+/// The elements of the set are recorded in an index next to its entries, which makes inserting
+/// and removing elements more expensive than in a plain [`Set`].  Only the elements inserted
+/// through an `IterableSet` are indexed: elements of a [`Set`] with the same prefix are contained
+/// in the set, but aren't counted nor iterated over.
+#[derive(Clone)]
+pub struct IterableSet<T, L = Identity>
+where
+    T: BorshSerialize,
+{
+    set: Set<T, L>,
+}
+
+impl<T, L> IterableSet<T, L>
+where
+    T: BorshSerialize,
+    L: LookupKeyOwned,
+    for<'a> <L as LookupKey<'a>>::Output: AsRef<[u8]>,
+{
+    pub fn new(prefix: String) -> Self {
+        Self {
+            set: Set::new(prefix),
+        }
+    }
+
+    pub fn insert(&mut self, key: T) {
+        let member = borsh::to_vec(&key).unwrap();
+        self.set.insert(key);
+        index::insert(self.set.prefix.as_bytes(), &member);
+    }
+
+    pub fn contains_key(&self, key: T) -> bool {
+        self.set.contains_key(key)
+    }
+
+    /// Removes an element from the set, returning whether it was present.
+    ///
+    /// Elements sharing their lookup key share their entry, so it is removed for all of them.
+    pub fn remove(&mut self, key: T) -> bool {
+        let member = borsh::to_vec(&key).unwrap();
+        let removed_entry = self.set.remove(key);
+        let removed_member = index::remove(self.set.prefix.as_bytes(), &member);
+        removed_entry || removed_member
+    }
+
+    /// Returns the number of indexed elements of the set.
+    #[inline(always)]
+    pub fn len(&self) -> u64 {
+        index::len(self.set.prefix.as_bytes())
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T, L> IterableSet<T, L>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    /// Returns an iterator over the indexed elements of the set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        index::iter(self.set.prefix.as_bytes()).map(|member| borsh::from_slice(&member).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use crate::host::native::{dispatch_with, Environment};
+
+    use super::{
+        super::set::tests::{Appended, Flag},
+        IterableSet, Set,
+    };
+
+    #[test]
+    fn should_remove_and_iterate() {
+        dispatch_with(Environment::default(), || {
+            let mut set: IterableSet<Flag, Appended> = IterableSet::new("Prefix".to_string());
+            set.insert(Flag::A);
+            set.insert(Flag::B);
+            set.insert(Flag::B);
+            assert_eq!(set.len(), 2);
+
+            let mut elements: Vec<_> = set.iter().collect();
+            elements.sort();
+            assert_eq!(elements, vec![Flag::A, Flag::B]);
+
+            assert!(set.remove(Flag::A));
+            assert!(!set.remove(Flag::A));
+            assert!(!set.contains_key(Flag::A));
+            assert!(set.contains_key(Flag::B));
+            assert_eq!(set.len(), 1);
+            assert_eq!(set.iter().collect::<Vec<_>>(), vec![Flag::B]);
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_skip_elements_of_plain_set() {
+        dispatch_with(Environment::default(), || {
+            let mut plain_set: Set<Flag, Appended> = Set::new("Prefix".to_string());
+            plain_set.insert(Flag::A);
+
+            let mut set: IterableSet<Flag, Appended> = IterableSet::new("Prefix".to_string());
+            assert!(set.contains_key(Flag::A));
+            assert!(set.is_empty());
+
+            set.insert(Flag::B);
+            assert_eq!(set.iter().collect::<Vec<_>>(), vec![Flag::B]);
+        })
+        .unwrap();
+    }
+}
@@ -35,21 +35,34 @@ where
     }
 
     pub fn insert(&mut self, key: &K, value: &V) {
-        let mut context_key = Vec::new();
-        context_key.extend(self.name.as_bytes());
-        // NOTE: We may want to create new keyspace for a hashed context element to avoid hashing in
-        // the wasm.
-        key.serialize(&mut context_key).unwrap();
+        let context_key = self.context_key(key);
         let prefix = Keyspace::Context(&context_key);
         host::casper_write(prefix, &borsh::to_vec(value).unwrap()).unwrap();
     }
 
     pub fn get(&self, key: &K) -> Option<V> {
-        let mut key_bytes = self.name.as_bytes().to_owned();
-        key.serialize(&mut key_bytes).unwrap();
-        let prefix = Keyspace::Context(&key_bytes);
+        let context_key = self.context_key(key);
+        let prefix = Keyspace::Context(&context_key);
         read_into_vec(prefix).map(|vec| borsh::from_slice(&vec).unwrap())
     }
+
+    /// Removes a key from the map, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let context_key = self.context_key(key);
+        let prefix = Keyspace::Context(&context_key);
+        let value = read_into_vec(prefix).map(|vec| borsh::from_slice(&vec).unwrap())?;
+        host::casper_remove(prefix).unwrap();
+        Some(value)
+    }
+
+    fn context_key(&self, key: &K) -> Vec<u8> {
+        let mut context_key = Vec::new();
+        context_key.extend(self.name.as_bytes());
+        // NOTE: We may want to create new keyspace for a hashed context element to avoid hashing in
+        // the wasm.
+        key.serialize(&mut context_key).unwrap();
+        context_key
+    }
 }
 
 impl<K: CasperABI, V: CasperABI> CasperABI for Map<K, V> {
@@ -75,6 +88,7 @@ impl<K: CasperABI, V: CasperABI> CasperABI for Map<K, V> {
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
+    use crate::host::native::{dispatch_with, Environment};
 
     #[test]
     fn test_compute_prefix() {
@@ -104,4 +118,19 @@ pub(crate) mod tests {
         assert_eq!(map.get(&1), Some(22));
         assert_eq!(map.get(&2), Some(33));
     }
+
+    #[test]
+    fn test_map_remove() {
+        dispatch_with(Environment::default(), || {
+            let mut map = Map::<u64, u64>::new("test");
+            map.insert(&1, &2);
+            map.insert(&2, &3);
+
+            assert_eq!(map.remove(&1), Some(2));
+            assert_eq!(map.remove(&1), None);
+            assert_eq!(map.get(&1), None);
+            assert_eq!(map.get(&2), Some(3));
+        })
+        .unwrap();
+    }
 }
@@ -10,7 +10,7 @@ pub struct Set<T, L = Identity>
 where
     T: BorshSerialize,
 {
-    prefix: String,
+    pub(super) prefix: String,
     lookup: L,
     _marker: PhantomData<T>,
 }
@@ -40,17 +40,44 @@ where
             host::casper_read(Keyspace::Context(lookup_key.as_ref()), |_size| None).unwrap();
         entry.is_some()
     }
+
+    /// Removes an element from the set, returning whether it was present.
+    ///
+    /// Elements sharing their lookup key share their entry, so it is removed for all of them.
+    pub fn remove(&mut self, key: T) -> bool {
+        let lookup_key = self.lookup.lookup(self.prefix.as_bytes(), &key);
+        host::casper_remove(Keyspace::Context(lookup_key.as_ref()))
+            .unwrap()
+            .is_some()
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use crate::prelude::*;
 
-    use crate::serializers::borsh::BorshSerialize;
+    use crate::{
+        host::native::{dispatch_with, Environment},
+        serializers::borsh::{BorshDeserialize, BorshSerialize},
+    };
+
+    use super::{LookupKey, Set};
+
+    /// Looks elements up under the prefix followed by the serialized element.
+    #[derive(Default)]
+    pub(crate) struct Appended;
+
+    impl<'a> LookupKey<'a> for Appended {
+        type Output = Vec<u8>;
 
-    use super::Set;
+        fn lookup<T: BorshSerialize>(&self, prefix: &'a [u8], key: &T) -> Self::Output {
+            let mut lookup_key = prefix.to_vec();
+            key.serialize(&mut lookup_key).unwrap();
+            lookup_key
+        }
+    }
 
-    #[derive(BorshSerialize)]
+    #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
     #[borsh(crate = "crate::serializers::borsh")]
     pub enum Flag {
         A,
@@ -76,4 +103,19 @@ mod tests {
         set.insert(Flag::C);
         assert!(set.contains_key(Flag::C));
     }
+
+    #[test]
+    fn should_remove() {
+        dispatch_with(Environment::default(), || {
+            let mut set: Set<Flag, Appended> = Set::new("Prefix".to_string());
+            set.insert(Flag::A);
+            set.insert(Flag::B);
+
+            assert!(set.remove(Flag::A));
+            assert!(!set.remove(Flag::A));
+            assert!(!set.contains_key(Flag::A));
+            assert!(set.contains_key(Flag::B));
+        })
+        .unwrap();
+    }
 }
@@ -11,9 +11,10 @@ use crate::{
 };
 
 use casper_executor_wasm_common::{
+    crypto::{HashAlgorithm, DIGEST_LENGTH, RECOVERED_PUBLIC_KEY_LENGTH},
     error::Error,
     flags::ReturnFlags,
-    keyspace::{Keyspace, KeyspaceTag},
+    keyspace::{IteratePage, Keyspace, KeyspaceTag, MAX_ITERATE_LIMIT},
 };
 use casper_sdk_sys::casper_env_caller;
 
@@ -21,7 +22,7 @@ use crate::{
     abi::{CasperABI, EnumVariant},
     reserve_vec_space,
     types::{Address, CallError},
-    ToCallData,
+    Event, ToCallData,
 };
 
 pub fn casper_print(msg: &str) {
@@ -170,6 +171,122 @@ pub fn casper_write(key: Keyspace, value: &[u8]) -> Result<(), Error> {
     }
 }
 
+/// Remove the value stored under a key.
+///
+/// Returns `Ok(None)` if there was no value under the key.
+pub fn casper_remove(key: Keyspace) -> Result<Option<()>, Error> {
+    let (key_space, key_bytes) = match key {
+        Keyspace::State => (KeyspaceTag::State as u64, &[][..]),
+        Keyspace::Context(key_bytes) => (KeyspaceTag::Context as u64, key_bytes),
+        Keyspace::NamedKey(key_bytes) => (KeyspaceTag::NamedKey as u64, key_bytes.as_bytes()),
+        Keyspace::PaymentInfo(payload) => (KeyspaceTag::PaymentInfo as u64, payload.as_bytes()),
+    };
+    let ret =
+        unsafe { casper_sdk_sys::casper_remove(key_space, key_bytes.as_ptr(), key_bytes.len()) };
+    if ret == 0 {
+        Ok(Some(()))
+    } else {
+        match Error::from(ret) {
+            Error::NotFound => Ok(None),
+            other => Err(other),
+        }
+    }
+}
+
+/// Read a page of at most `limit` members of the collection with the given prefix.
+///
+/// The page starts at the member at position `start`.  A page with fewer than `limit` members is
+/// the last one.  Only the members of the index maintained by iterable collections, such as
+/// [`IterableMap`](crate::collections::IterableMap), are visited, not every context key starting
+/// with the prefix.  Only collections of the context keyspace can be iterated over.
+pub fn casper_iterate(prefix: Keyspace, start: u64, limit: u32) -> Result<IteratePage, Error> {
+    let (key_space, prefix_bytes) = match prefix {
+        Keyspace::State => (KeyspaceTag::State as u64, &[][..]),
+        Keyspace::Context(key_bytes) => (KeyspaceTag::Context as u64, key_bytes),
+        Keyspace::NamedKey(key_bytes) => (KeyspaceTag::NamedKey as u64, key_bytes.as_bytes()),
+        Keyspace::PaymentInfo(payload) => (KeyspaceTag::PaymentInfo as u64, payload.as_bytes()),
+    };
+
+    let mut vec = Vec::new();
+    let ret = iterate_into(
+        key_space,
+        prefix_bytes,
+        start,
+        limit,
+        Some(|size| reserve_vec_space(&mut vec, size)),
+    );
+    if ret == 0 {
+        Ok(borsh::from_slice(&vec).expect("Page of members should deserialize"))
+    } else {
+        Err(Error::from(ret))
+    }
+}
+
+fn iterate_into<F: FnOnce(usize) -> Option<ptr::NonNull<u8>>>(
+    key_space: u64,
+    prefix: &[u8],
+    start: u64,
+    limit: u32,
+    alloc: Option<F>,
+) -> i32 {
+    let mut info = casper_sdk_sys::ReadInfo {
+        data: ptr::null(),
+        size: 0,
+    };
+    unsafe {
+        casper_sdk_sys::casper_iterate(
+            key_space,
+            prefix.as_ptr(),
+            prefix.len(),
+            start,
+            limit,
+            &mut info as *mut casper_sdk_sys::ReadInfo,
+            alloc_callback::<F>,
+            &alloc as *const _ as *mut c_void,
+        )
+    }
+}
+
+/// Iterate over all members of the collection with the given prefix.
+///
+/// Members are read from the host in pages, in the order of their positions in the collection.
+pub fn iter_collection(prefix: &[u8]) -> CollectionIter<'_> {
+    CollectionIter {
+        prefix,
+        position: 0,
+        page: Vec::new().into_iter(),
+        done: false,
+    }
+}
+
+/// An iterator over the members of a collection, created by [`iter_collection`].
+pub struct CollectionIter<'a> {
+    prefix: &'a [u8],
+    position: u64,
+    page: crate::prelude::vec::IntoIter<Vec<u8>>,
+    done: bool,
+}
+
+impl Iterator for CollectionIter<'_> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.page.len() == 0 && !self.done {
+            let page = casper_iterate(
+                Keyspace::Context(self.prefix),
+                self.position,
+                MAX_ITERATE_LIMIT,
+            )
+            .expect("Collection members should be iterable");
+            self.done = page.len() < MAX_ITERATE_LIMIT as usize;
+            self.page = page.into_iter();
+        }
+        let member = self.page.next()?;
+        self.position += 1;
+        Some(member)
+    }
+}
+
 pub fn casper_create(
     code: Option<&[u8]>,
     transferred_value: u128,
@@ -488,3 +605,117 @@ pub fn casper_transfer(target_account: &Address, amount: u128) -> Result<(), Cal
 pub fn get_block_time() -> u64 {
     unsafe { casper_sdk_sys::casper_env_block_time() }
 }
+
+/// Emit a message with the given payload under a topic of the current contract.
+///
+/// The topic is registered on its first use.
+pub fn casper_emit(topic_name: &str, payload: &[u8]) -> Result<(), Error> {
+    let ret = unsafe {
+        casper_sdk_sys::casper_emit(
+            topic_name.as_ptr(),
+            topic_name.len(),
+            payload.as_ptr(),
+            payload.len(),
+        )
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(Error::from(ret))
+    }
+}
+
+/// Emit an event under its topic.
+pub fn emit<T: Event>(event: &T) -> Result<(), Error> {
+    let payload = borsh::to_vec(event).expect("Event should serialize");
+    casper_emit(T::TOPIC, &payload)
+}
+
+/// Hash the input with Blake2b.
+pub fn casper_blake2b(input: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut digest = [0; DIGEST_LENGTH];
+    let ret = unsafe {
+        casper_sdk_sys::casper_blake2b(
+            input.as_ptr(),
+            input.len(),
+            digest.as_mut_ptr(),
+            digest.len(),
+        )
+    };
+    assert_eq!(ret, 0, "Blake2b should not fail with a digest sized buffer");
+    digest
+}
+
+/// Hash the input with the given hash algorithm.
+pub fn casper_generic_hash(
+    input: &[u8],
+    hash_algorithm: HashAlgorithm,
+) -> Result<[u8; DIGEST_LENGTH], Error> {
+    let mut digest = [0; DIGEST_LENGTH];
+    let ret = unsafe {
+        casper_sdk_sys::casper_generic_hash(
+            input.as_ptr(),
+            input.len(),
+            hash_algorithm as u32,
+            digest.as_mut_ptr(),
+            digest.len(),
+        )
+    };
+    if ret == 0 {
+        Ok(digest)
+    } else {
+        Err(Error::from(ret))
+    }
+}
+
+/// Recover the secp256k1 public key which signed the data.
+///
+/// The signature is expected to be serialized with Casper's `bytesrepr`, and so is the returned
+/// public key.
+pub fn casper_recover_secp256k1(
+    data: &[u8],
+    signature: &[u8],
+    recovery_id: u8,
+) -> Result<[u8; RECOVERED_PUBLIC_KEY_LENGTH], Error> {
+    let mut public_key = [0; RECOVERED_PUBLIC_KEY_LENGTH];
+    let ret = unsafe {
+        casper_sdk_sys::casper_recover_secp256k1(
+            data.as_ptr(),
+            data.len(),
+            signature.as_ptr(),
+            signature.len(),
+            public_key.as_mut_ptr(),
+            recovery_id.into(),
+        )
+    };
+    if ret == 0 {
+        Ok(public_key)
+    } else {
+        Err(Error::from(ret))
+    }
+}
+
+/// Verify the signature of a message against a public key.
+///
+/// Both the signature and the public key are expected to be serialized with Casper's `bytesrepr`.
+pub fn casper_verify_signature(
+    message: &[u8],
+    signature: &[u8],
+    public_key: &[u8],
+) -> Result<(), Error> {
+    let ret = unsafe {
+        casper_sdk_sys::casper_verify_signature(
+            message.as_ptr(),
+            message.len(),
+            signature.as_ptr(),
+            signature.len(),
+            public_key.as_ptr(),
+            public_key.len(),
+        )
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(Error::from(ret))
+    }
+}
@@ -1,6 +1,19 @@
 use bytes::Bytes;
-use casper_executor_wasm_common::flags::ReturnFlags;
+use casper_executor_wasm_common::{
+    crypto::{self, HashAlgorithm, DIGEST_LENGTH, RECOVERED_PUBLIC_KEY_LENGTH},
+    error::{
+        HOST_ERROR_INVALID_DATA, HOST_ERROR_INVALID_INPUT, HOST_ERROR_LIMIT_EXCEEDED,
+        HOST_ERROR_NOT_FOUND,
+    },
+    flags::ReturnFlags,
+    keyspace::{collection_member_key, IteratePage, KeyspaceTag, MAX_ITERATE_LIMIT},
+};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    PublicKey, Signature,
+};
 use core::{panic::UnwindSafe, slice};
+use num_traits::FromPrimitive;
 use once_cell::sync::Lazy;
 use rand::Rng;
 use std::{
@@ -122,10 +135,19 @@ impl From<&casper_sdk_sys::Param> for NativeParam {
     }
 }
 
+/// A message emitted by an entity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NativeMessage {
+    pub entity: Entity,
+    pub topic_name: String,
+    pub payload: Bytes,
+}
+
 #[derive(Clone, Debug)]
 pub struct Environment {
     pub db: Arc<RwLock<Container>>,
     contracts: Arc<RwLock<BTreeSet<Address>>>,
+    messages: Arc<RwLock<Vec<NativeMessage>>>,
     // input_data: Arc<RwLock<Option<Bytes>>>,
     input_data: Option<Bytes>,
     contract_address: Option<Address>,
@@ -137,6 +159,7 @@ impl Default for Environment {
         Self {
             db: Default::default(),
             contracts: Default::default(),
+            messages: Default::default(),
             input_data: Default::default(),
             contract_address: Default::default(),
             caller: DEFAULT_ADDRESS,
@@ -151,6 +174,7 @@ impl Environment {
         Self {
             db: Arc::new(RwLock::new(db)),
             contracts: Default::default(),
+            messages: Default::default(),
             input_data: Default::default(),
             contract_address: None,
             caller,
@@ -169,6 +193,11 @@ impl Environment {
         env
     }
 
+    /// Returns the messages emitted so far, in order of emission.
+    pub fn emitted_messages(&self) -> Vec<NativeMessage> {
+        self.messages.read().unwrap().clone()
+    }
+
     fn casper_env_transferred_value(&self, dest: *mut core::ffi::c_void) -> Result<(), NativeTrap> {
         let dest_ptr = NonNull::new(dest).expect("Valid pointer");
         let value = 0u128;
@@ -262,6 +291,72 @@ impl Environment {
         Ok(0)
     }
 
+    fn casper_remove(
+        &self,
+        key_space: u64,
+        key_ptr: *const u8,
+        key_size: usize,
+    ) -> Result<i32, NativeTrap> {
+        let key_bytes = unsafe { slice::from_raw_parts(key_ptr, key_size) };
+        let key_bytes = self.key_prefix(key_bytes);
+
+        let mut db = self.db.write().unwrap();
+        let removed = db
+            .get_mut(&key_space)
+            .and_then(|values| values.remove(key_bytes.as_slice()));
+        match removed {
+            Some(_value) => Ok(0),
+            None => Ok(HOST_ERROR_NOT_FOUND),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn casper_iterate(
+        &self,
+        key_space: u64,
+        prefix_ptr: *const u8,
+        prefix_size: usize,
+        start: u64,
+        limit: u32,
+        info: *mut casper_sdk_sys::ReadInfo,
+        alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8,
+        alloc_ctx: *const core::ffi::c_void,
+    ) -> Result<i32, NativeTrap> {
+        if key_space != KeyspaceTag::Context as u64 {
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        }
+        if limit == 0 || limit > MAX_ITERATE_LIMIT {
+            return Ok(HOST_ERROR_LIMIT_EXCEEDED);
+        }
+
+        let prefix = unsafe { slice::from_raw_parts(prefix_ptr, prefix_size) };
+
+        let db = self.db.read().unwrap();
+        let page: IteratePage = match db.get(&key_space) {
+            Some(values) => (start..)
+                .take(limit as usize)
+                .map_while(|position| {
+                    let member_key = self.key_prefix(&collection_member_key(prefix, position));
+                    values
+                        .get(member_key.as_slice())
+                        .map(|member| member.to_vec())
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let page_bytes = borsh::to_vec(&page).expect("Page of members should serialize");
+
+        let ptr = NonNull::new(alloc(page_bytes.len(), alloc_ctx as _));
+        if let Some(ptr) = ptr {
+            unsafe {
+                (*info).data = ptr.as_ptr();
+                (*info).size = page_bytes.len();
+                ptr::copy_nonoverlapping(page_bytes.as_ptr(), ptr.as_ptr(), page_bytes.len());
+            }
+        }
+        Ok(0)
+    }
+
     fn casper_print(&self, msg_ptr: *const u8, msg_size: usize) -> Result<(), NativeTrap> {
         let msg_bytes = unsafe { slice::from_raw_parts(msg_ptr, msg_size) };
         let msg = std::str::from_utf8(msg_bytes).expect("Valid UTF-8 string");
@@ -505,6 +600,131 @@ Example paths:
         todo!()
     }
 
+    fn casper_emit(
+        &self,
+        topic_name_ptr: *const u8,
+        topic_name_size: usize,
+        payload_ptr: *const u8,
+        payload_size: usize,
+    ) -> Result<i32, NativeTrap> {
+        let topic_name = unsafe { slice::from_raw_parts(topic_name_ptr, topic_name_size) };
+        let topic_name = match std::str::from_utf8(topic_name) {
+            Ok(topic_name) => topic_name.to_string(),
+            Err(_) => return Ok(HOST_ERROR_INVALID_DATA),
+        };
+        let payload = unsafe { slice::from_raw_parts(payload_ptr, payload_size) };
+
+        let entity = self
+            .contract_address
+            .map(Entity::Contract)
+            .unwrap_or(self.caller);
+
+        self.messages.write().unwrap().push(NativeMessage {
+            entity,
+            topic_name,
+            payload: Bytes::copy_from_slice(payload),
+        });
+        Ok(0)
+    }
+
+    fn casper_blake2b(
+        &self,
+        in_ptr: *const u8,
+        in_size: usize,
+        out_ptr: *mut u8,
+        out_size: usize,
+    ) -> Result<i32, NativeTrap> {
+        self.casper_generic_hash(
+            in_ptr,
+            in_size,
+            HashAlgorithm::Blake2b as u32,
+            out_ptr,
+            out_size,
+        )
+    }
+
+    fn casper_generic_hash(
+        &self,
+        in_ptr: *const u8,
+        in_size: usize,
+        hash_algorithm: u32,
+        out_ptr: *mut u8,
+        out_size: usize,
+    ) -> Result<i32, NativeTrap> {
+        let Some(hash_algorithm) = HashAlgorithm::from_u32(hash_algorithm) else {
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        };
+        if out_size != DIGEST_LENGTH {
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        }
+
+        let input = unsafe { slice::from_raw_parts(in_ptr, in_size) };
+        let digest = crypto::hash(hash_algorithm, input);
+        let out = unsafe { slice::from_raw_parts_mut(out_ptr, out_size) };
+        out.copy_from_slice(&digest);
+        Ok(0)
+    }
+
+    fn casper_recover_secp256k1(
+        &self,
+        data_ptr: *const u8,
+        data_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        public_key_ptr: *mut u8,
+        recovery_id: u32,
+    ) -> Result<i32, NativeTrap> {
+        let Ok(recovery_id) = u8::try_from(recovery_id) else {
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        };
+
+        let data = unsafe { slice::from_raw_parts(data_ptr, data_size) };
+        let signature_bytes = unsafe { slice::from_raw_parts(signature_ptr, signature_size) };
+        let Ok(signature) = bytesrepr::deserialize_from_slice::<_, Signature>(signature_bytes)
+        else {
+            return Ok(HOST_ERROR_INVALID_DATA);
+        };
+
+        let Ok(public_key) = casper_types::crypto::recover_secp256k1(data, &signature, recovery_id)
+        else {
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        };
+        let public_key_bytes = public_key
+            .to_bytes()
+            .expect("should serialize a public key");
+        debug_assert_eq!(public_key_bytes.len(), RECOVERED_PUBLIC_KEY_LENGTH);
+
+        let out = unsafe { slice::from_raw_parts_mut(public_key_ptr, RECOVERED_PUBLIC_KEY_LENGTH) };
+        out.copy_from_slice(&public_key_bytes);
+        Ok(0)
+    }
+
+    fn casper_verify_signature(
+        &self,
+        message_ptr: *const u8,
+        message_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+    ) -> Result<i32, NativeTrap> {
+        let message = unsafe { slice::from_raw_parts(message_ptr, message_size) };
+        let signature_bytes = unsafe { slice::from_raw_parts(signature_ptr, signature_size) };
+        let public_key_bytes = unsafe { slice::from_raw_parts(public_key_ptr, public_key_size) };
+
+        let (Ok(signature), Ok(public_key)) = (
+            bytesrepr::deserialize_from_slice::<_, Signature>(signature_bytes),
+            bytesrepr::deserialize_from_slice::<_, PublicKey>(public_key_bytes),
+        ) else {
+            return Ok(HOST_ERROR_INVALID_DATA);
+        };
+
+        if casper_types::crypto::verify(message, &signature, &public_key).is_err() {
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        }
+        Ok(0)
+    }
+
     fn casper_env_caller(
         &self,
         dest: *mut u8,
@@ -694,6 +914,43 @@ mod symbols {
         crate::host::native::handle_ret(_call_result)
     }
 
+    #[no_mangle]
+    pub extern "C" fn casper_remove(key_space: u64, key_ptr: *const u8, key_size: usize) -> i32 {
+        let _name = "casper_remove";
+        let _args = (&key_space, &key_ptr, &key_size);
+        let _call_result =
+            with_current_environment(|stub| stub.casper_remove(key_space, key_ptr, key_size));
+        crate::host::native::handle_ret(_call_result)
+    }
+
+    #[no_mangle]
+    #[allow(clippy::too_many_arguments)]
+    pub extern "C" fn casper_iterate(
+        key_space: u64,
+        prefix_ptr: *const u8,
+        prefix_size: usize,
+        start: u64,
+        limit: u32,
+        info: *mut ::casper_sdk_sys::ReadInfo,
+        alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8,
+        alloc_ctx: *const core::ffi::c_void,
+    ) -> i32 {
+        let _name = "casper_iterate";
+        let _call_result = with_current_environment(|stub| {
+            stub.casper_iterate(
+                key_space,
+                prefix_ptr,
+                prefix_size,
+                start,
+                limit,
+                info,
+                alloc,
+                alloc_ctx,
+            )
+        });
+        crate::host::native::handle_ret(_call_result)
+    }
+
     #[no_mangle]
     pub extern "C" fn casper_print(msg_ptr: *const u8, msg_size: usize) {
         let _name = "casper_print";
@@ -853,10 +1110,125 @@ mod symbols {
     pub extern "C" fn casper_env_block_time() -> u64 {
         0
     }
+
+    #[no_mangle]
+    pub extern "C" fn casper_emit(
+        topic_name_ptr: *const u8,
+        topic_name_size: usize,
+        payload_ptr: *const u8,
+        payload_size: usize,
+    ) -> i32 {
+        let _name = "casper_emit";
+        let _args = (
+            &topic_name_ptr,
+            &topic_name_size,
+            &payload_ptr,
+            &payload_size,
+        );
+        let _call_result = with_current_environment(|stub| {
+            stub.casper_emit(topic_name_ptr, topic_name_size, payload_ptr, payload_size)
+        });
+        crate::host::native::handle_ret(_call_result)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn casper_blake2b(
+        in_ptr: *const u8,
+        in_size: usize,
+        out_ptr: *mut u8,
+        out_size: usize,
+    ) -> i32 {
+        let _name = "casper_blake2b";
+        let _args = (&in_ptr, &in_size, &out_ptr, &out_size);
+        let _call_result = with_current_environment(|stub| {
+            stub.casper_blake2b(in_ptr, in_size, out_ptr, out_size)
+        });
+        crate::host::native::handle_ret(_call_result)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn casper_generic_hash(
+        in_ptr: *const u8,
+        in_size: usize,
+        hash_algorithm: u32,
+        out_ptr: *mut u8,
+        out_size: usize,
+    ) -> i32 {
+        let _name = "casper_generic_hash";
+        let _args = (&in_ptr, &in_size, &hash_algorithm, &out_ptr, &out_size);
+        let _call_result = with_current_environment(|stub| {
+            stub.casper_generic_hash(in_ptr, in_size, hash_algorithm, out_ptr, out_size)
+        });
+        crate::host::native::handle_ret(_call_result)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn casper_recover_secp256k1(
+        data_ptr: *const u8,
+        data_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        public_key_ptr: *mut u8,
+        recovery_id: u32,
+    ) -> i32 {
+        let _name = "casper_recover_secp256k1";
+        let _args = (
+            &data_ptr,
+            &data_size,
+            &signature_ptr,
+            &signature_size,
+            &public_key_ptr,
+            &recovery_id,
+        );
+        let _call_result = with_current_environment(|stub| {
+            stub.casper_recover_secp256k1(
+                data_ptr,
+                data_size,
+                signature_ptr,
+                signature_size,
+                public_key_ptr,
+                recovery_id,
+            )
+        });
+        crate::host::native::handle_ret(_call_result)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn casper_verify_signature(
+        message_ptr: *const u8,
+        message_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+    ) -> i32 {
+        let _name = "casper_verify_signature";
+        let _args = (
+            &message_ptr,
+            &message_size,
+            &signature_ptr,
+            &signature_size,
+            &public_key_ptr,
+            &public_key_size,
+        );
+        let _call_result = with_current_environment(|stub| {
+            stub.casper_verify_signature(
+                message_ptr,
+                message_size,
+                signature_ptr,
+                signature_size,
+                public_key_ptr,
+                public_key_size,
+            )
+        });
+        crate::host::native::handle_ret(_call_result)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use casper_executor_wasm_common::error::Error;
+
     use super::*;
 
     #[test]
@@ -869,6 +1241,119 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn test_emit() {
+        let env = Environment::default();
+        dispatch_with(env.clone(), || {
+            crate::host::casper_emit("Topic", b"payload").expect("Ok");
+        })
+        .unwrap();
+        assert_eq!(
+            env.emitted_messages(),
+            vec![NativeMessage {
+                entity: DEFAULT_ADDRESS,
+                topic_name: "Topic".to_string(),
+                payload: Bytes::from_static(b"payload"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_hash_known_answers() {
+        const INPUT: &[u8] = b"abc";
+        const BLAKE2B: &str = "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319";
+        const BLAKE3: &str = "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85";
+        const SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+        dispatch(|| {
+            assert_eq!(hex::encode(crate::host::casper_blake2b(INPUT)), BLAKE2B);
+            for (hash_algorithm, expected) in [
+                (HashAlgorithm::Blake2b, BLAKE2B),
+                (HashAlgorithm::Blake3, BLAKE3),
+                (HashAlgorithm::Sha256, SHA256),
+            ] {
+                let digest = crate::host::casper_generic_hash(INPUT, hash_algorithm).expect("Ok");
+                assert_eq!(hex::encode(digest), expected, "{hash_algorithm:?}");
+            }
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_hash_unknown_algorithm() {
+        dispatch(|| {
+            let input = b"abc";
+            let mut digest = [0; DIGEST_LENGTH];
+            let ret = unsafe {
+                casper_sdk_sys::casper_generic_hash(
+                    input.as_ptr(),
+                    input.len(),
+                    u32::MAX,
+                    digest.as_mut_ptr(),
+                    digest.len(),
+                )
+            };
+            assert_eq!(ret, HOST_ERROR_INVALID_INPUT);
+            assert_eq!(digest, [0; DIGEST_LENGTH]);
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let secret_key = casper_types::SecretKey::ed25519_from_bytes([1; 32]).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+        let message = b"message";
+        let signature = casper_types::crypto::sign(message, &secret_key, &public_key);
+        let signature_bytes = signature.to_bytes().unwrap();
+        let public_key_bytes = public_key.to_bytes().unwrap();
+
+        dispatch(|| {
+            crate::host::casper_verify_signature(message, &signature_bytes, &public_key_bytes)
+                .expect("valid signature should verify");
+            assert_eq!(
+                crate::host::casper_verify_signature(
+                    b"other message",
+                    &signature_bytes,
+                    &public_key_bytes
+                ),
+                Err(Error::InvalidInput)
+            );
+            assert_eq!(
+                crate::host::casper_verify_signature(message, b"garbage", &public_key_bytes),
+                Err(Error::InvalidData)
+            );
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_recover_secp256k1() {
+        let secret_key = casper_types::SecretKey::secp256k1_from_bytes([1; 32]).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+        let data = b"data";
+        let signature = casper_types::crypto::sign(data, &secret_key, &public_key);
+        let signature_bytes = signature.to_bytes().unwrap();
+        let public_key_bytes = public_key.to_bytes().unwrap();
+
+        dispatch(|| {
+            let recovered: Vec<_> = (0..2)
+                .filter_map(|recovery_id| {
+                    crate::host::casper_recover_secp256k1(data, &signature_bytes, recovery_id).ok()
+                })
+                .collect();
+            assert!(recovered
+                .iter()
+                .any(|recovered| recovered.as_slice() == public_key_bytes.as_slice()));
+
+            assert_eq!(
+                crate::host::casper_recover_secp256k1(data, b"garbage", 0),
+                Err(Error::InvalidData)
+            );
+        })
+        .unwrap();
+    }
+
     #[ignore]
     #[test]
     fn test_returns() {
@@ -70,6 +70,14 @@ pub trait ToCallData {
     fn input_data(&self) -> Option<crate::prelude::Vec<u8>>;
 }
 
+/// To derive this trait you have to use `#[casper(event)]` macro on top of a struct.
+///
+/// Events are emitted as messages under a topic named after the event.
+pub trait Event: BorshSerialize {
+    /// Name of the topic the event is emitted under.
+    const TOPIC: &'static str;
+}
+
 /// To derive this contract you have to use `#[casper]` macro on top of impl block.
 ///
 /// This proc macro handles generation of a manifest.
@@ -0,0 +1,607 @@
+//! Persistent indices of the block store.
+//!
+//! The indices are kept in dedicated LMDB databases living in the same environment as the block
+//! store, so they can be updated within the same write transaction as the data they refer to.
+//!
+//! Block heights and era IDs are stored as big-endian keys, which makes the LMDB key order match
+//! the numeric order and allows the highest entry to be found with a single cursor lookup.
+//!
+//! The optional initiator transactions index is keyed by the initiator's account hash followed by
+//! the big-endian block height and the transaction hash, so all transactions of a single account
+//! are adjacent and ordered by height, which allows height ranges to be read with one cursor scan.
+
+use std::convert::TryInto;
+
+use datasize::DataSize;
+use lmdb::{
+    Cursor, Database, DatabaseFlags, Environment, RwTransaction, Transaction as LmdbTransaction,
+    WriteFlags,
+};
+use lmdb_sys::{MDB_LAST, MDB_NEXT, MDB_PREV, MDB_SET_RANGE};
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    BlockHash, EraId, TransactionHash,
+};
+
+use super::lmdb_ext::{self, LmdbExtError};
+use crate::block_store::{types::BlockHashHeightAndEra, BlockStoreError};
+
+/// The version of the on-disk index format.
+///
+/// If the version stored alongside the indices differs from this one, the indices are rebuilt from
+/// the block headers and bodies on startup.
+pub(super) const INDICES_VERSION: u32 = 1;
+
+/// The raw key-value pairs of an index.
+#[cfg(test)]
+pub(super) type RawEntries = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// The LMDB databases holding the block store indices.
+#[derive(Clone, Copy, Eq, PartialEq, DataSize, Debug)]
+pub(super) struct IndexDatabases {
+    /// A map of block height to block ID.
+    #[data_size(skip)]
+    block_height: Database,
+    /// A map of era ID to switch block ID.
+    #[data_size(skip)]
+    switch_block_era_id: Database,
+    /// A map of transaction hashes to hashes, heights and era IDs of blocks containing them.
+    #[data_size(skip)]
+    transaction_hash: Database,
+    /// A map of initiator account hashes, block heights and transaction hashes to the hashes,
+    /// heights and era IDs of blocks containing the transactions.
+    ///
+    /// Only populated if the node is configured to do so, and not rebuilt alongside the other
+    /// indices since the block store alone can't tell which blocks were meant to be indexed.
+    #[data_size(skip)]
+    initiator_transactions: Database,
+}
+
+impl IndexDatabases {
+    pub(super) fn new(env: &Environment) -> Result<Self, lmdb::Error> {
+        Ok(IndexDatabases {
+            block_height: env.create_db(Some("block_height_index"), DatabaseFlags::empty())?,
+            switch_block_era_id: env
+                .create_db(Some("switch_block_era_id_index"), DatabaseFlags::empty())?,
+            transaction_hash: env
+                .create_db(Some("transaction_hash_index"), DatabaseFlags::empty())?,
+            initiator_transactions: env
+                .create_db(Some("initiator_transactions_index"), DatabaseFlags::empty())?,
+        })
+    }
+
+    /// Opens the existing index databases, without creating them if missing.
+    pub(super) fn open(env: &Environment) -> Result<Self, lmdb::Error> {
+        Ok(IndexDatabases {
+            block_height: env.open_db(Some("block_height_index"))?,
+            switch_block_era_id: env.open_db(Some("switch_block_era_id_index"))?,
+            transaction_hash: env.open_db(Some("transaction_hash_index"))?,
+            initiator_transactions: env.open_db(Some("initiator_transactions_index"))?,
+        })
+    }
+
+    /// Removes all entries from the indices derived from the block headers and bodies.
+    pub(super) fn clear(&self, txn: &mut RwTransaction) -> Result<(), LmdbExtError> {
+        txn.clear_db(self.block_height)?;
+        txn.clear_db(self.switch_block_era_id)?;
+        txn.clear_db(self.transaction_hash)?;
+        Ok(())
+    }
+
+    /// Returns the raw entries of the indices derived from the block headers and bodies, one list
+    /// per index, in key order.
+    #[cfg(test)]
+    pub(super) fn entries<Tx: LmdbTransaction>(
+        &self,
+        txn: &Tx,
+    ) -> Result<Vec<RawEntries>, LmdbExtError> {
+        [
+            self.block_height,
+            self.switch_block_era_id,
+            self.transaction_hash,
+        ]
+        .iter()
+        .map(|db| {
+            let mut cursor = txn.open_ro_cursor(*db)?;
+            cursor
+                .iter_start()
+                .map(|row| {
+                    row.map(|(raw_key, raw_value)| (raw_key.to_vec(), raw_value.to_vec()))
+                        .map_err(LmdbExtError::from)
+                })
+                .collect()
+        })
+        .collect()
+    }
+
+    /// Returns the hash of the block at the given height.
+    pub(super) fn block_hash_by_height<Tx: LmdbTransaction>(
+        &self,
+        txn: &Tx,
+        height: u64,
+    ) -> Result<Option<BlockHash>, LmdbExtError> {
+        get(txn, self.block_height, &height.to_be_bytes())
+    }
+
+    /// Returns the hash of the highest indexed block.
+    pub(super) fn highest_block_hash<Tx: LmdbTransaction>(
+        &self,
+        txn: &Tx,
+    ) -> Result<Option<BlockHash>, LmdbExtError> {
+        last(txn, self.block_height)
+    }
+
+    /// Returns the hash of the switch block of the given era.
+    pub(super) fn switch_block_hash_by_era_id<Tx: LmdbTransaction>(
+        &self,
+        txn: &Tx,
+        era_id: EraId,
+    ) -> Result<Option<BlockHash>, LmdbExtError> {
+        get(txn, self.switch_block_era_id, &era_id.value().to_be_bytes())
+    }
+
+    /// Returns the hash of the switch block of the highest indexed era.
+    pub(super) fn latest_switch_block_hash<Tx: LmdbTransaction>(
+        &self,
+        txn: &Tx,
+    ) -> Result<Option<BlockHash>, LmdbExtError> {
+        last(txn, self.switch_block_era_id)
+    }
+
+    /// Returns the hash, height and era of the block containing the given transaction.
+    pub(super) fn block_info_by_transaction_hash<Tx: LmdbTransaction>(
+        &self,
+        txn: &Tx,
+        transaction_hash: &TransactionHash,
+    ) -> Result<Option<BlockHashHeightAndEra>, LmdbExtError> {
+        let key = lmdb_ext::serialize_bytesrepr(transaction_hash)?;
+        get(txn, self.transaction_hash, &key)
+    }
+
+    pub(super) fn put_block_height(
+        &self,
+        txn: &mut RwTransaction,
+        height: u64,
+        block_hash: &BlockHash,
+    ) -> Result<(), LmdbExtError> {
+        put(txn, self.block_height, &height.to_be_bytes(), block_hash)
+    }
+
+    pub(super) fn delete_block_height(
+        &self,
+        txn: &mut RwTransaction,
+        height: u64,
+    ) -> Result<(), LmdbExtError> {
+        delete(txn, self.block_height, &height.to_be_bytes())
+    }
+
+    pub(super) fn put_switch_block(
+        &self,
+        txn: &mut RwTransaction,
+        era_id: EraId,
+        block_hash: &BlockHash,
+    ) -> Result<(), LmdbExtError> {
+        put(
+            txn,
+            self.switch_block_era_id,
+            &era_id.value().to_be_bytes(),
+            block_hash,
+        )
+    }
+
+    pub(super) fn delete_switch_block(
+        &self,
+        txn: &mut RwTransaction,
+        era_id: EraId,
+    ) -> Result<(), LmdbExtError> {
+        delete(txn, self.switch_block_era_id, &era_id.value().to_be_bytes())
+    }
+
+    pub(super) fn put_transaction(
+        &self,
+        txn: &mut RwTransaction,
+        transaction_hash: &TransactionHash,
+        block_info: &BlockHashHeightAndEra,
+    ) -> Result<(), LmdbExtError> {
+        let key = lmdb_ext::serialize_bytesrepr(transaction_hash)?;
+        put(txn, self.transaction_hash, &key, block_info)
+    }
+
+    pub(super) fn delete_transaction(
+        &self,
+        txn: &mut RwTransaction,
+        transaction_hash: &TransactionHash,
+    ) -> Result<(), LmdbExtError> {
+        let key = lmdb_ext::serialize_bytesrepr(transaction_hash)?;
+        delete(txn, self.transaction_hash, &key)
+    }
+
+    pub(super) fn put_initiator_transaction(
+        &self,
+        txn: &mut RwTransaction,
+        account_hash: &AccountHash,
+        transaction_hash: &TransactionHash,
+        block_info: &BlockHashHeightAndEra,
+    ) -> Result<(), LmdbExtError> {
+        let key = initiator_transaction_key(
+            account_hash,
+            block_info.block_height,
+            Some(transaction_hash),
+        )?;
+        put(
+            txn,
+            self.initiator_transactions,
+            &key,
+            &(*transaction_hash, *block_info),
+        )
+    }
+
+    pub(super) fn delete_initiator_transaction(
+        &self,
+        txn: &mut RwTransaction,
+        account_hash: &AccountHash,
+        block_height: u64,
+        transaction_hash: &TransactionHash,
+    ) -> Result<(), LmdbExtError> {
+        let key = initiator_transaction_key(account_hash, block_height, Some(transaction_hash))?;
+        delete(txn, self.initiator_transactions, &key)
+    }
+
+    /// Returns up to `limit` transactions initiated by the given account in blocks within the
+    /// inclusive height range, ordered by block height and transaction hash.
+    ///
+    /// If `start_after` is given, entries at `from_height` up to and including that transaction
+    /// are skipped, which allows resuming from the last entry of a previous page.
+    pub(super) fn initiator_transactions<Tx: LmdbTransaction>(
+        &self,
+        txn: &Tx,
+        account_hash: &AccountHash,
+        from_height: u64,
+        to_height: u64,
+        start_after: Option<&TransactionHash>,
+        limit: usize,
+    ) -> Result<Vec<(TransactionHash, BlockHashHeightAndEra)>, LmdbExtError> {
+        let mut entries = vec![];
+        if from_height > to_height || limit == 0 {
+            return Ok(entries);
+        }
+        let start_key = initiator_transaction_key(account_hash, from_height, start_after)?;
+        let end_key = initiator_transaction_key(account_hash, to_height, None)?;
+        let height_end = end_key.len();
+
+        let cursor = txn.open_ro_cursor(self.initiator_transactions)?;
+        let mut result = cursor.get(Some(&start_key), None, MDB_SET_RANGE);
+        loop {
+            let (raw_key, raw_value) = match result {
+                Ok((Some(raw_key), raw_value)) => (raw_key, raw_value),
+                Ok((None, _)) | Err(lmdb::Error::NotFound) => break,
+                Err(err) => return Err(err.into()),
+            };
+            // Keys are ordered by account hash and height first, so once the prefix is past the
+            // end of the range there are no more matching entries.
+            if raw_key.len() < height_end || raw_key[..height_end] > end_key[..] {
+                break;
+            }
+            if raw_key != &start_key[..] {
+                entries.push(lmdb_ext::deserialize_bytesrepr(raw_value)?);
+                if entries.len() == limit {
+                    break;
+                }
+            }
+            result = cursor.get(None, None, MDB_NEXT);
+        }
+        Ok(entries)
+    }
+
+    /// Walks the block height index from the highest block downwards, calling `f` with each height
+    /// and block hash until it returns `false` or the start of the index is reached.
+    pub(super) fn walk_block_heights_descending<Tx, F>(
+        &self,
+        txn: &Tx,
+        mut f: F,
+    ) -> Result<(), BlockStoreError>
+    where
+        Tx: LmdbTransaction,
+        F: FnMut(u64, BlockHash) -> Result<bool, BlockStoreError>,
+    {
+        let cursor = txn
+            .open_ro_cursor(self.block_height)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        let mut op = MDB_LAST;
+        loop {
+            let (raw_key, raw_value) = match cursor.get(None, None, op) {
+                Ok((Some(raw_key), raw_value)) => (raw_key, raw_value),
+                Ok((None, _)) | Err(lmdb::Error::NotFound) => return Ok(()),
+                Err(err) => return Err(BlockStoreError::InternalStorage(Box::new(err))),
+            };
+            let height = decode_height(raw_key)
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+            let block_hash = lmdb_ext::deserialize_bytesrepr(raw_value)
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+            if !f(height, block_hash)? {
+                return Ok(());
+            }
+            op = MDB_PREV;
+        }
+    }
+}
+
+fn decode_height(raw_key: &[u8]) -> Result<u64, LmdbExtError> {
+    raw_key
+        .try_into()
+        .map(u64::from_be_bytes)
+        .map_err(|err| LmdbExtError::DataCorrupted(Box::new(err)))
+}
+
+fn initiator_transaction_key(
+    account_hash: &AccountHash,
+    block_height: u64,
+    maybe_transaction_hash: Option<&TransactionHash>,
+) -> Result<Vec<u8>, LmdbExtError> {
+    let mut key = account_hash.value().to_vec();
+    key.extend_from_slice(&block_height.to_be_bytes());
+    if let Some(transaction_hash) = maybe_transaction_hash {
+        key.extend(lmdb_ext::serialize_bytesrepr(transaction_hash)?);
+    }
+    Ok(key)
+}
+
+fn get<Tx: LmdbTransaction, V: FromBytes + 'static>(
+    txn: &Tx,
+    db: Database,
+    key: &[u8],
+) -> Result<Option<V>, LmdbExtError> {
+    match txn.get(db, &key) {
+        Ok(raw) => lmdb_ext::deserialize_bytesrepr(raw).map(Some),
+        Err(lmdb::Error::NotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn last<Tx: LmdbTransaction, V: FromBytes + 'static>(
+    txn: &Tx,
+    db: Database,
+) -> Result<Option<V>, LmdbExtError> {
+    let cursor = txn.open_ro_cursor(db)?;
+    match cursor.get(None, None, MDB_LAST) {
+        Ok((_, raw)) => lmdb_ext::deserialize_bytesrepr(raw).map(Some),
+        Err(lmdb::Error::NotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn put<V: ToBytes>(
+    txn: &mut RwTransaction,
+    db: Database,
+    key: &[u8],
+    value: &V,
+) -> Result<(), LmdbExtError> {
+    let serialized_value = lmdb_ext::serialize_bytesrepr(value)?;
+    txn.put(db, &key, &serialized_value, WriteFlags::empty())?;
+    Ok(())
+}
+
+fn delete(txn: &mut RwTransaction, db: Database, key: &[u8]) -> Result<(), LmdbExtError> {
+    match txn.del(db, &key, None) {
+        Ok(()) | Err(lmdb::Error::NotFound) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lmdb::{Environment, EnvironmentFlags};
+    use tempfile::TempDir;
+
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    struct Fixture {
+        rng: TestRng,
+        env: Environment,
+        dbs: IndexDatabases,
+        _data_dir: TempDir,
+    }
+
+    impl Fixture {
+        fn new() -> Fixture {
+            let rng = TestRng::new();
+            let data_dir = TempDir::new().expect("should create temp dir");
+            let env = Environment::new()
+                .set_flags(EnvironmentFlags::NO_SUB_DIR)
+                .set_max_dbs(4)
+                .open(&data_dir.path().join("test.lmdb"))
+                .expect("should create lmdb env");
+            let dbs = IndexDatabases::new(&env).expect("should create dbs");
+            Fixture {
+                rng,
+                env,
+                dbs,
+                _data_dir: data_dir,
+            }
+        }
+    }
+
+    #[test]
+    fn should_return_highest_block_hash_in_numeric_order() {
+        let mut fixture = Fixture::new();
+        // 256 would sort before 1 if heights were encoded little-endian.
+        let heights = [1_u64, 256, 2, 255];
+        let hashes: Vec<BlockHash> = heights
+            .iter()
+            .map(|_| BlockHash::random(&mut fixture.rng))
+            .collect();
+
+        let mut txn = fixture.env.begin_rw_txn().unwrap();
+        assert_eq!(fixture.dbs.highest_block_hash(&txn).unwrap(), None);
+        for (height, hash) in heights.iter().zip(hashes.iter()) {
+            fixture
+                .dbs
+                .put_block_height(&mut txn, *height, hash)
+                .unwrap();
+        }
+        txn.commit().unwrap();
+
+        let txn = fixture.env.begin_ro_txn().unwrap();
+        assert_eq!(
+            fixture.dbs.highest_block_hash(&txn).unwrap(),
+            Some(hashes[1])
+        );
+        assert_eq!(
+            fixture.dbs.block_hash_by_height(&txn, 2).unwrap(),
+            Some(hashes[2])
+        );
+        assert_eq!(fixture.dbs.block_hash_by_height(&txn, 3).unwrap(), None);
+
+        let mut walked = vec![];
+        fixture
+            .dbs
+            .walk_block_heights_descending(&txn, |height, _| {
+                walked.push(height);
+                Ok(height > 2)
+            })
+            .unwrap();
+        assert_eq!(walked, vec![256, 255, 2]);
+    }
+
+    #[test]
+    fn should_put_get_and_delete_entries() {
+        let mut fixture = Fixture::new();
+        let block_hash = BlockHash::random(&mut fixture.rng);
+        let era_id = EraId::random(&mut fixture.rng);
+        let transaction_hash = TransactionHash::random(&mut fixture.rng);
+        let block_info = BlockHashHeightAndEra::new(block_hash, 7, era_id);
+
+        let mut txn = fixture.env.begin_rw_txn().unwrap();
+        fixture
+            .dbs
+            .put_switch_block(&mut txn, era_id, &block_hash)
+            .unwrap();
+        fixture
+            .dbs
+            .put_transaction(&mut txn, &transaction_hash, &block_info)
+            .unwrap();
+        assert_eq!(
+            fixture
+                .dbs
+                .switch_block_hash_by_era_id(&txn, era_id)
+                .unwrap(),
+            Some(block_hash)
+        );
+        assert_eq!(
+            fixture.dbs.latest_switch_block_hash(&txn).unwrap(),
+            Some(block_hash)
+        );
+        assert_eq!(
+            fixture
+                .dbs
+                .block_info_by_transaction_hash(&txn, &transaction_hash)
+                .unwrap(),
+            Some(block_info)
+        );
+
+        fixture.dbs.delete_switch_block(&mut txn, era_id).unwrap();
+        fixture
+            .dbs
+            .delete_transaction(&mut txn, &transaction_hash)
+            .unwrap();
+        // Deleting a missing entry is not an error.
+        fixture.dbs.delete_block_height(&mut txn, 7).unwrap();
+        assert_eq!(
+            fixture
+                .dbs
+                .switch_block_hash_by_era_id(&txn, era_id)
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            fixture
+                .dbs
+                .block_info_by_transaction_hash(&txn, &transaction_hash)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn should_page_through_initiator_transactions() {
+        let mut fixture = Fixture::new();
+        let account_hash = AccountHash::new([1; 32]);
+        let other_account_hash = AccountHash::new([2; 32]);
+        let era_id = EraId::random(&mut fixture.rng);
+
+        let mut txn = fixture.env.begin_rw_txn().unwrap();
+        let mut expected = vec![];
+        for height in [3_u64, 256, 1] {
+            let block_info =
+                BlockHashHeightAndEra::new(BlockHash::random(&mut fixture.rng), height, era_id);
+            for _ in 0..2 {
+                let transaction_hash = TransactionHash::random(&mut fixture.rng);
+                fixture
+                    .dbs
+                    .put_initiator_transaction(
+                        &mut txn,
+                        &account_hash,
+                        &transaction_hash,
+                        &block_info,
+                    )
+                    .unwrap();
+                let other_hash = TransactionHash::random(&mut fixture.rng);
+                fixture
+                    .dbs
+                    .put_initiator_transaction(
+                        &mut txn,
+                        &other_account_hash,
+                        &other_hash,
+                        &block_info,
+                    )
+                    .unwrap();
+                expected.push((transaction_hash, block_info));
+            }
+        }
+        txn.commit().unwrap();
+        expected.sort_by_key(|(transaction_hash, block_info)| {
+            (
+                block_info.block_height,
+                lmdb_ext::serialize_bytesrepr(transaction_hash).unwrap(),
+            )
+        });
+
+        let txn = fixture.env.begin_ro_txn().unwrap();
+        let all = fixture
+            .dbs
+            .initiator_transactions(&txn, &account_hash, 0, u64::MAX, None, usize::MAX)
+            .unwrap();
+        assert_eq!(all, expected);
+
+        let in_range = fixture
+            .dbs
+            .initiator_transactions(&txn, &account_hash, 2, 3, None, usize::MAX)
+            .unwrap();
+        assert_eq!(in_range, expected[2..4]);
+
+        // Resume from the first entry at height 3 and read a page of two.
+        let (last_hash, last_info) = expected[2];
+        let page = fixture
+            .dbs
+            .initiator_transactions(
+                &txn,
+                &account_hash,
+                last_info.block_height,
+                u64::MAX,
+                Some(&last_hash),
+                2,
+            )
+            .unwrap();
+        assert_eq!(page, expected[3..5]);
+
+        assert!(fixture
+            .dbs
+            .initiator_transactions(&txn, &account_hash, 4, 255, None, usize::MAX)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -4,7 +4,10 @@ use std::{
 };
 
 use super::{
-    lmdb_block_store::LmdbBlockStore, lmdb_ext::LmdbExtError, temp_map::TempMap, DbTableId,
+    index_databases::{IndexDatabases, INDICES_VERSION},
+    lmdb_block_store::LmdbBlockStore,
+    lmdb_ext::{self, LmdbExtError},
+    DbTableId,
 };
 use datasize::DataSize;
 use lmdb::{
@@ -17,27 +20,28 @@ use super::versioned_databases::VersionedDatabases;
 use crate::block_store::{
     block_provider::{BlockStoreTransaction, DataReader, DataWriter},
     types::{
-        ApprovalsHashes, BlockExecutionResults, BlockHashHeightAndEra, BlockHeight, BlockTransfers,
-        LatestSwitchBlock, StateStore, StateStoreKey, Tip, TransactionFinalizedApprovals,
+        ApprovalsHashes, BlockExecutionResults, BlockHashHeightAndEra, BlockHeight,
+        BlockTransactionInitiators, BlockTransfers, InitiatorTransactionsQuery, LatestSwitchBlock,
+        PrunableBlockData, StateStore, StateStoreKey, Tip, TransactionFinalizedApprovals,
     },
     BlockStoreError, BlockStoreProvider, DbRawBytesSpec,
 };
 use casper_types::{
-    execution::ExecutionResult, Approval, Block, BlockBody, BlockHash, BlockHeader,
-    BlockSignatures, Digest, EraId, ProtocolVersion, Transaction, TransactionHash, Transfer,
+    bytesrepr::FromBytes, execution::ExecutionResult, Approval, Block, BlockBody, BlockHash,
+    BlockHeader, BlockSignatures, Digest, EraId, ProtocolVersion, Transaction, TransactionHash,
+    Transfer,
 };
 
+/// Key in the state store under which the version of the persisted indices is kept.
+const INDICES_VERSION_KEY: &[u8] = b"block_store_indices_version";
+
 /// Indexed lmdb block store.
 #[derive(DataSize, Debug)]
 pub struct IndexedLmdbBlockStore {
     /// Block store
     block_store: LmdbBlockStore,
-    /// A map of block height to block ID.
-    block_height_index: BTreeMap<u64, BlockHash>,
-    /// A map of era ID to switch block ID.
-    switch_block_era_id_index: BTreeMap<EraId, BlockHash>,
-    /// A map of transaction hashes to hashes, heights and era IDs of blocks containing them.
-    transaction_hash_index: BTreeMap<TransactionHash, BlockHashHeightAndEra>,
+    /// The persistent block height, switch block and transaction hash indices.
+    indices: IndexDatabases,
 }
 
 impl IndexedLmdbBlockStore {
@@ -88,7 +92,7 @@ impl IndexedLmdbBlockStore {
     /// Inserts the relevant entries to the two indices.
     ///
     /// If a duplicate entry is encountered, neither index is updated and an error is returned.
-    pub(super) fn insert_to_block_header_indices(
+    fn insert_to_block_header_indices(
         block_height_index: &mut BTreeMap<u64, BlockHash>,
         switch_block_era_id_index: &mut BTreeMap<EraId, BlockHash>,
         block_header: &BlockHeader,
@@ -126,13 +130,101 @@ impl IndexedLmdbBlockStore {
     }
 
     /// Ctor.
+    ///
+    /// Stores created by versions which kept the indices in memory only are migrated by
+    /// building the persistent indices from the stored blocks once.
     pub fn new(
         block_store: LmdbBlockStore,
         hard_reset_to_start_of_era: Option<EraId>,
         protocol_version: ProtocolVersion,
     ) -> Result<IndexedLmdbBlockStore, BlockStoreError> {
-        // We now need to restore the block-height index. Log messages allow timing here.
+        let indices = IndexDatabases::new(&block_store.env)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        let indexed_block_store = IndexedLmdbBlockStore {
+            block_store,
+            indices,
+        };
+
+        if indexed_block_store.stored_indices_version()? == Some(INDICES_VERSION) {
+            if let Some(invalid_era) = hard_reset_to_start_of_era {
+                indexed_block_store.purge_blocks_for_hard_reset(invalid_era, protocol_version)?;
+            }
+        } else {
+            indexed_block_store.rebuild_indices(hard_reset_to_start_of_era, protocol_version)?;
+        }
+
+        Ok(indexed_block_store)
+    }
+
+    /// Opens the indices of a block store opened read-only, without rebuilding them.
+    ///
+    /// Fails if the indices were never built, which can be checked beforehand with
+    /// [`Self::has_current_indices`].
+    pub fn open_read_only(
+        block_store: LmdbBlockStore,
+    ) -> Result<IndexedLmdbBlockStore, BlockStoreError> {
+        let indices = IndexDatabases::open(&block_store.env)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        Ok(IndexedLmdbBlockStore {
+            block_store,
+            indices,
+        })
+    }
+
+    /// Returns `true` if the indices of the given block store were built by this version, rather
+    /// than being outdated or never built at all.
+    pub fn has_current_indices(block_store: &LmdbBlockStore) -> Result<bool, BlockStoreError> {
+        let txn = block_store
+            .env
+            .begin_ro_txn()
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        let maybe_raw = block_store.read_state_store(&txn, &INDICES_VERSION_KEY)?;
+        Ok(maybe_raw.is_some_and(|raw| {
+            u32::from_vec(raw).is_ok_and(|(version, _)| version == INDICES_VERSION)
+        }))
+    }
+
+    /// Returns the underlying block store.
+    pub fn block_store(&self) -> &LmdbBlockStore {
+        &self.block_store
+    }
+
+    /// Rebuilds the indices from the stored block headers and bodies, regardless of their
+    /// version.
+    pub fn reindex(&self) -> Result<(), BlockStoreError> {
+        // The protocol version only matters when purging blocks for a hard reset.
+        self.rebuild_indices(None, ProtocolVersion::V1_0_0)
+    }
+
+    /// Returns the version of the persisted indices, or `None` if they were never built.
+    fn stored_indices_version(&self) -> Result<Option<u32>, BlockStoreError> {
+        let txn = self
+            .block_store
+            .env
+            .begin_ro_txn()
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        let maybe_raw = self
+            .block_store
+            .read_state_store(&txn, &INDICES_VERSION_KEY)?;
+        maybe_raw
+            .map(|raw| {
+                u32::from_vec(raw)
+                    .map(|(version, _)| version)
+                    .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
+            })
+            .transpose()
+    }
+
+    /// Rebuilds the persistent indices by scanning every stored block header, purging the blocks
+    /// invalidated by a hard reset on the way.
+    fn rebuild_indices(
+        &self,
+        hard_reset_to_start_of_era: Option<EraId>,
+        protocol_version: ProtocolVersion,
+    ) -> Result<(), BlockStoreError> {
+        // We now need to build the block-height index. Log messages allow timing here.
         info!("indexing block store");
+        let block_store = &self.block_store;
         let mut block_height_index = BTreeMap::new();
         let mut switch_block_era_id_index = BTreeMap::new();
         let mut transaction_hash_index = BTreeMap::new();
@@ -239,6 +331,21 @@ impl IndexedLmdbBlockStore {
             .block_header_dbs
             .for_each_value_in_legacy(&mut block_txn, &mut init_fn)?;
 
+        info!("persisting block store indices");
+        self.write_indices(
+            &mut block_txn,
+            block_height_index,
+            switch_block_era_id_index,
+            transaction_hash_index,
+        )
+        .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        block_store.write_state_store(
+            &mut block_txn,
+            Cow::Borrowed(INDICES_VERSION_KEY),
+            &lmdb_ext::serialize_bytesrepr(&INDICES_VERSION)
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?,
+        )?;
+
         info!("block store reindexing complete");
         block_txn
             .commit()
@@ -263,14 +370,119 @@ impl IndexedLmdbBlockStore {
             block_store.execution_result_dbs,
             deleted_transaction_hashes,
         )
-        .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
+    }
 
-        Ok(Self {
-            block_store,
-            block_height_index,
-            switch_block_era_id_index,
-            transaction_hash_index,
-        })
+    /// Replaces the contents of the persistent indices with the given entries.
+    fn write_indices(
+        &self,
+        txn: &mut RwTransaction,
+        block_height_index: BTreeMap<u64, BlockHash>,
+        switch_block_era_id_index: BTreeMap<EraId, BlockHash>,
+        transaction_hash_index: BTreeMap<TransactionHash, BlockHashHeightAndEra>,
+    ) -> Result<(), LmdbExtError> {
+        self.indices.clear(txn)?;
+        for (height, block_hash) in block_height_index {
+            self.indices.put_block_height(txn, height, &block_hash)?;
+        }
+        for (era_id, block_hash) in switch_block_era_id_index {
+            self.indices.put_switch_block(txn, era_id, &block_hash)?;
+        }
+        for (transaction_hash, block_info) in transaction_hash_index {
+            self.indices
+                .put_transaction(txn, &transaction_hash, &block_info)?;
+        }
+        Ok(())
+    }
+
+    /// Purges blocks invalidated by a hard reset using the persistent block height index.
+    ///
+    /// Blocks from `invalid_era` onwards are deleted unless they were created under
+    /// `protocol_version`. As eras only increase with height, only the top of the index needs to
+    /// be visited. Block bodies are left in place, since other blocks may reference them.
+    fn purge_blocks_for_hard_reset(
+        &self,
+        invalid_era: EraId,
+        protocol_version: ProtocolVersion,
+    ) -> Result<(), BlockStoreError> {
+        let block_store = &self.block_store;
+        let mut txn = block_store
+            .env
+            .begin_rw_txn()
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+
+        let mut stale_block_headers = vec![];
+        self.indices
+            .walk_block_heights_descending(&txn, |_height, block_hash| {
+                let block_header = match block_store.get_single_block_header(&txn, &block_hash)? {
+                    Some(block_header) => block_header,
+                    None => return Ok(true),
+                };
+                if block_header.era_id() < invalid_era {
+                    return Ok(false);
+                }
+                if block_header.protocol_version() != protocol_version {
+                    stale_block_headers.push(block_header);
+                }
+                Ok(true)
+            })?;
+
+        info!(
+            block_count_to_be_deleted = stale_block_headers.len(),
+            "purging blocks after hard reset"
+        );
+        for block_header in stale_block_headers {
+            let block_hash = block_header.block_hash();
+            let transaction_hashes: Vec<TransactionHash> = match block_store
+                .block_body_dbs
+                .get(&txn, block_header.body_hash())
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?
+            {
+                Some(BlockBody::V1(v1_body)) => v1_body
+                    .deploy_and_transfer_hashes()
+                    .map(TransactionHash::from)
+                    .collect(),
+                Some(BlockBody::V2(v2_body)) => v2_body.all_transactions().copied().collect(),
+                None => vec![],
+            };
+
+            block_store.delete_block_header(&mut txn, &block_hash)?;
+            block_store.delete_finality_signatures(&mut txn, &block_hash)?;
+            self.indices
+                .delete_block_height(&mut txn, block_header.height())
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+            if block_header.is_switch_block() {
+                self.indices
+                    .delete_switch_block(&mut txn, block_header.era_id())
+                    .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+            }
+            for transaction_hash in transaction_hashes {
+                self.indices
+                    .delete_transaction(&mut txn, &transaction_hash)
+                    .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+                if let Some(transaction) = block_store
+                    .transaction_dbs
+                    .get(&txn, &transaction_hash)
+                    .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?
+                {
+                    self.indices
+                        .delete_initiator_transaction(
+                            &mut txn,
+                            &transaction.initiator_addr().account_hash(),
+                            block_header.height(),
+                            &transaction_hash,
+                        )
+                        .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+                }
+                block_store
+                    .execution_result_dbs
+                    .delete(&mut txn, &transaction_hash)
+                    .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+            }
+        }
+
+        txn.commit()
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
     }
 }
 
@@ -343,9 +555,7 @@ fn initialize_execution_result_dbs(
 pub struct IndexedLmdbBlockStoreRWTransaction<'t> {
     txn: RwTransaction<'t>,
     block_store: &'t LmdbBlockStore,
-    block_height_index: TempMap<'t, u64, BlockHash>,
-    switch_block_era_id_index: TempMap<'t, EraId, BlockHash>,
-    transaction_hash_index: TempMap<'t, TransactionHash, BlockHashHeightAndEra>,
+    indices: IndexDatabases,
 }
 
 impl IndexedLmdbBlockStoreRWTransaction<'_> {
@@ -355,7 +565,11 @@ impl IndexedLmdbBlockStoreRWTransaction<'_> {
         block_height: u64,
         block_hash: &BlockHash,
     ) -> Result<bool, BlockStoreError> {
-        if let Some(first) = self.block_height_index.get(&block_height) {
+        if let Some(first) = self
+            .indices
+            .block_hash_by_height(&self.txn, block_height)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?
+        {
             // There is a block in the index at this height
             if first != *block_hash {
                 Err(BlockStoreError::DuplicateBlock {
@@ -380,7 +594,11 @@ impl IndexedLmdbBlockStoreRWTransaction<'_> {
     ) -> Result<bool, BlockStoreError> {
         if block_header.is_switch_block() {
             let era_id = block_header.era_id();
-            if let Some(entry) = self.switch_block_era_id_index.get(&era_id) {
+            if let Some(entry) = self
+                .indices
+                .switch_block_hash_by_era_id(&self.txn, era_id)
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?
+            {
                 let block_hash = block_header.block_hash();
                 if entry != block_hash {
                     Err(BlockStoreError::DuplicateEraId {
@@ -408,19 +626,96 @@ impl IndexedLmdbBlockStoreRWTransaction<'_> {
         transaction_hashes: &[TransactionHash],
         block_hash: &BlockHash,
     ) -> Result<bool, BlockStoreError> {
-        if let Some(hash) = transaction_hashes.iter().find(|hash| {
-            self.transaction_hash_index
-                .get(hash)
-                .is_some_and(|old_details| old_details.block_hash != *block_hash)
-        }) {
-            return Err(BlockStoreError::DuplicateTransaction {
-                transaction_hash: *hash,
-                first: self.transaction_hash_index.get(hash).unwrap().block_hash,
-                second: *block_hash,
-            });
+        for hash in transaction_hashes {
+            if let Some(old_details) = self
+                .indices
+                .block_info_by_transaction_hash(&self.txn, hash)
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?
+            {
+                if old_details.block_hash != *block_hash {
+                    return Err(BlockStoreError::DuplicateTransaction {
+                        transaction_hash: *hash,
+                        first: old_details.block_hash,
+                        second: *block_hash,
+                    });
+                }
+            }
         }
         Ok(true)
     }
+
+    fn insert_to_block_height_index(
+        &mut self,
+        block_height: u64,
+        block_hash: &BlockHash,
+    ) -> Result<(), BlockStoreError> {
+        self.indices
+            .put_block_height(&mut self.txn, block_height, block_hash)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
+    }
+
+    fn insert_to_switch_block_index(
+        &mut self,
+        era_id: EraId,
+        block_hash: &BlockHash,
+    ) -> Result<(), BlockStoreError> {
+        self.indices
+            .put_switch_block(&mut self.txn, era_id, block_hash)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
+    }
+
+    fn insert_to_transaction_hash_index(
+        &mut self,
+        transaction_hashes: Vec<TransactionHash>,
+        block_info: BlockHashHeightAndEra,
+    ) -> Result<(), BlockStoreError> {
+        for hash in transaction_hashes {
+            self.indices
+                .put_transaction(&mut self.txn, &hash, &block_info)
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        }
+        Ok(())
+    }
+
+    fn remove_from_block_header_indices(
+        &mut self,
+        block_header: &BlockHeader,
+    ) -> Result<(), BlockStoreError> {
+        self.indices
+            .delete_block_height(&mut self.txn, block_header.height())
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        if block_header.is_switch_block() {
+            self.indices
+                .delete_switch_block(&mut self.txn, block_header.era_id())
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        }
+        Ok(())
+    }
+
+    /// Removes the entry of the given transaction of the block at the given height from the
+    /// initiator index, provided the transaction is still stored.
+    fn remove_from_initiator_index(
+        &mut self,
+        block_height: u64,
+        transaction_hash: &TransactionHash,
+    ) -> Result<(), BlockStoreError> {
+        if let Some(transaction) = self
+            .block_store
+            .transaction_dbs
+            .get(&self.txn, transaction_hash)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?
+        {
+            self.indices
+                .delete_initiator_transaction(
+                    &mut self.txn,
+                    &transaction.initiator_addr().account_hash(),
+                    block_height,
+                    transaction_hash,
+                )
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        }
+        Ok(())
+    }
 }
 
 pub struct IndexedLmdbBlockStoreReadTransaction<'t> {
@@ -446,75 +741,72 @@ enum DataType {
 }
 
 impl IndexedLmdbBlockStoreReadTransaction<'_> {
-    fn block_hash_from_index(&self, index: LmdbBlockStoreIndex) -> Option<&BlockHash> {
+    fn block_hash_from_index(
+        &self,
+        index: LmdbBlockStoreIndex,
+    ) -> Result<Option<BlockHash>, BlockStoreError> {
+        let indices = &self.block_store.indices;
         match index {
             LmdbBlockStoreIndex::BlockHeight(position) => match position {
-                IndexPosition::Tip => self.block_store.block_height_index.values().last(),
-                IndexPosition::Key(height) => self.block_store.block_height_index.get(&height),
+                IndexPosition::Tip => indices.highest_block_hash(&self.txn),
+                IndexPosition::Key(height) => indices.block_hash_by_height(&self.txn, height),
             },
             LmdbBlockStoreIndex::SwitchBlockEraId(position) => match position {
-                IndexPosition::Tip => self.block_store.switch_block_era_id_index.values().last(),
+                IndexPosition::Tip => indices.latest_switch_block_hash(&self.txn),
                 IndexPosition::Key(era_id) => {
-                    self.block_store.switch_block_era_id_index.get(&era_id)
+                    indices.switch_block_hash_by_era_id(&self.txn, era_id)
                 }
             },
         }
+        .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
     }
 
     fn read_block_indexed(
         &self,
         index: LmdbBlockStoreIndex,
     ) -> Result<Option<Block>, BlockStoreError> {
-        self.block_hash_from_index(index)
-            .and_then(|block_hash| {
+        self.block_hash_from_index(index)?
+            .map_or(Ok(None), |block_hash| {
                 self.block_store
                     .block_store
-                    .get_single_block(&self.txn, block_hash)
-                    .transpose()
+                    .get_single_block(&self.txn, &block_hash)
             })
-            .transpose()
     }
 
     fn read_block_header_indexed(
         &self,
         index: LmdbBlockStoreIndex,
     ) -> Result<Option<BlockHeader>, BlockStoreError> {
-        self.block_hash_from_index(index)
-            .and_then(|block_hash| {
+        self.block_hash_from_index(index)?
+            .map_or(Ok(None), |block_hash| {
                 self.block_store
                     .block_store
-                    .get_single_block_header(&self.txn, block_hash)
-                    .transpose()
+                    .get_single_block_header(&self.txn, &block_hash)
             })
-            .transpose()
     }
 
     fn read_block_signatures_indexed(
         &self,
         index: LmdbBlockStoreIndex,
     ) -> Result<Option<BlockSignatures>, BlockStoreError> {
-        self.block_hash_from_index(index)
-            .and_then(|block_hash| {
+        self.block_hash_from_index(index)?
+            .map_or(Ok(None), |block_hash| {
                 self.block_store
                     .block_store
-                    .get_block_signatures(&self.txn, block_hash)
-                    .transpose()
+                    .get_block_signatures(&self.txn, &block_hash)
             })
-            .transpose()
     }
 
     fn read_approvals_hashes_indexed(
         &self,
         index: LmdbBlockStoreIndex,
     ) -> Result<Option<ApprovalsHashes>, BlockStoreError> {
-        self.block_hash_from_index(index)
-            .and_then(|block_hash| {
+        self.block_hash_from_index(index)?
+            .map_or(Ok(None), |block_hash| {
                 self.block_store
                     .block_store
-                    .read_approvals_hashes(&self.txn, block_hash)
-                    .transpose()
+                    .read_approvals_hashes(&self.txn, &block_hash)
             })
-            .transpose()
     }
 
     fn contains_data_indexed(
@@ -522,24 +814,24 @@ impl IndexedLmdbBlockStoreReadTransaction<'_> {
         index: LmdbBlockStoreIndex,
         data_type: DataType,
     ) -> Result<bool, BlockStoreError> {
-        self.block_hash_from_index(index)
+        self.block_hash_from_index(index)?
             .map_or(Ok(false), |block_hash| match data_type {
                 DataType::Block => self
                     .block_store
                     .block_store
-                    .block_exists(&self.txn, block_hash),
+                    .block_exists(&self.txn, &block_hash),
                 DataType::BlockHeader => self
                     .block_store
                     .block_store
-                    .block_header_exists(&self.txn, block_hash),
+                    .block_header_exists(&self.txn, &block_hash),
                 DataType::ApprovalsHashes => self
                     .block_store
                     .block_store
-                    .approvals_hashes_exist(&self.txn, block_hash),
+                    .approvals_hashes_exist(&self.txn, &block_hash),
                 DataType::BlockSignatures => self
                     .block_store
                     .block_store
-                    .block_signatures_exist(&self.txn, block_hash),
+                    .block_signatures_exist(&self.txn, &block_hash),
             })
     }
 }
@@ -558,12 +850,7 @@ impl BlockStoreTransaction for IndexedLmdbBlockStoreRWTransaction<'_> {
     fn commit(self) -> Result<(), BlockStoreError> {
         self.txn
             .commit()
-            .map_err(|e| BlockStoreError::InternalStorage(Box::new(LmdbExtError::from(e))))?;
-
-        self.block_height_index.commit();
-        self.switch_block_era_id_index.commit();
-        self.transaction_hash_index.commit();
-        Ok(())
+            .map_err(|e| BlockStoreError::InternalStorage(Box::new(LmdbExtError::from(e))))
     }
 
     fn rollback(self) {
@@ -589,9 +876,7 @@ impl BlockStoreProvider for IndexedLmdbBlockStore {
         Ok(IndexedLmdbBlockStoreRWTransaction {
             txn,
             block_store: &self.block_store,
-            block_height_index: TempMap::new(&mut self.block_height_index),
-            switch_block_era_id_index: TempMap::new(&mut self.switch_block_era_id_index),
-            transaction_hash_index: TempMap::new(&mut self.transaction_hash_index),
+            indices: self.indices,
         })
     }
 }
@@ -812,11 +1097,42 @@ impl DataReader<TransactionHash, BlockHashHeightAndEra>
     for IndexedLmdbBlockStoreReadTransaction<'_>
 {
     fn read(&self, key: TransactionHash) -> Result<Option<BlockHashHeightAndEra>, BlockStoreError> {
-        Ok(self.block_store.transaction_hash_index.get(&key).copied())
+        self.block_store
+            .indices
+            .block_info_by_transaction_hash(&self.txn, &key)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
     }
 
     fn exists(&self, key: TransactionHash) -> Result<bool, BlockStoreError> {
-        Ok(self.block_store.transaction_hash_index.contains_key(&key))
+        DataReader::<TransactionHash, BlockHashHeightAndEra>::read(self, key)
+            .map(|res| res.is_some())
+    }
+}
+
+impl DataReader<InitiatorTransactionsQuery, Vec<(TransactionHash, BlockHashHeightAndEra)>>
+    for IndexedLmdbBlockStoreReadTransaction<'_>
+{
+    fn read(
+        &self,
+        query: InitiatorTransactionsQuery,
+    ) -> Result<Option<Vec<(TransactionHash, BlockHashHeightAndEra)>>, BlockStoreError> {
+        self.block_store
+            .indices
+            .initiator_transactions(
+                &self.txn,
+                &query.initiator_addr.account_hash(),
+                query.from_height,
+                query.to_height,
+                query.start_after.as_ref(),
+                query.limit,
+            )
+            .map(Some)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
+    }
+
+    fn exists(&self, query: InitiatorTransactionsQuery) -> Result<bool, BlockStoreError> {
+        DataReader::<InitiatorTransactionsQuery, Vec<_>>::read(self, query)
+            .map(|res| res.is_some_and(|entries| !entries.is_empty()))
     }
 }
 
@@ -942,20 +1258,18 @@ impl DataWriter<BlockHash, Block> for IndexedLmdbBlockStoreRWTransaction<'_> {
         let key = self.block_store.write_block(&mut self.txn, data)?;
 
         if update_height_index {
-            self.block_height_index.insert(block_height, *block_hash);
+            self.insert_to_block_height_index(block_height, block_hash)?;
         }
 
         if update_switch_block_index {
-            self.switch_block_era_id_index.insert(era_id, *block_hash);
+            self.insert_to_switch_block_index(era_id, block_hash)?;
         }
 
         if update_transaction_hash_index {
-            for hash in transaction_hashes {
-                self.transaction_hash_index.insert(
-                    hash,
-                    BlockHashHeightAndEra::new(*block_hash, block_height, era_id),
-                );
-            }
+            self.insert_to_transaction_hash_index(
+                transaction_hashes,
+                BlockHashHeightAndEra::new(*block_hash, block_height, era_id),
+            )?;
         }
 
         Ok(key)
@@ -981,14 +1295,13 @@ impl DataWriter<BlockHash, Block> for IndexedLmdbBlockStoreRWTransaction<'_> {
                 .delete_block_body(&mut self.txn, block.body_hash())?;
             */
 
-            self.block_height_index.remove(block.height());
-
-            if block.is_switch_block() {
-                self.switch_block_era_id_index.remove(block.era_id());
-            }
+            self.remove_from_block_header_indices(&block.clone_header())?;
 
             for hash in transaction_hashes {
-                self.transaction_hash_index.remove(hash);
+                self.remove_from_initiator_index(block.height(), &hash)?;
+                self.indices
+                    .delete_transaction(&mut self.txn, &hash)
+                    .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
             }
 
             self.block_store
@@ -998,6 +1311,55 @@ impl DataWriter<BlockHash, Block> for IndexedLmdbBlockStoreRWTransaction<'_> {
     }
 }
 
+impl DataWriter<BlockHash, PrunableBlockData> for IndexedLmdbBlockStoreRWTransaction<'_> {
+    fn write(&mut self, _data: &PrunableBlockData) -> Result<BlockHash, BlockStoreError> {
+        Err(BlockStoreError::UnsupportedOperation)
+    }
+
+    /// Removes the transactions, execution results, approvals hashes, transfers and body of the
+    /// given block, leaving its header and finality signatures in place.
+    fn delete(&mut self, key: BlockHash) -> Result<(), BlockStoreError> {
+        let block = match self.block_store.get_single_block(&self.txn, &key)? {
+            Some(block) => block,
+            None => return Ok(()),
+        };
+        let transaction_hashes: Vec<TransactionHash> = match &block {
+            Block::V1(v1) => v1
+                .deploy_and_transfer_hashes()
+                .map(TransactionHash::from)
+                .collect(),
+            Block::V2(v2) => v2.all_transactions().copied().collect(),
+        };
+
+        self.block_store
+            .delete_execution_results(&mut self.txn, &key)?;
+        self.block_store
+            .delete_approvals_hashes(&mut self.txn, &key)?;
+        self.block_store.delete_transfers(&mut self.txn, &key)?;
+
+        for transaction_hash in &transaction_hashes {
+            self.remove_from_initiator_index(block.height(), transaction_hash)?;
+            self.indices
+                .delete_transaction(&mut self.txn, transaction_hash)
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+            self.block_store
+                .delete_transaction(&mut self.txn, transaction_hash)?;
+            self.block_store
+                .finalized_transaction_approvals_dbs
+                .delete(&mut self.txn, transaction_hash)
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        }
+
+        // Bodies without transactions are identical across blocks of the same version, so they
+        // may still be referenced by retained blocks.
+        if !transaction_hashes.is_empty() {
+            self.block_store
+                .delete_block_body(&mut self.txn, block.body_hash())?;
+        }
+        Ok(())
+    }
+}
+
 impl DataWriter<BlockHash, ApprovalsHashes> for IndexedLmdbBlockStoreRWTransaction<'_> {
     fn write(&mut self, data: &ApprovalsHashes) -> Result<BlockHash, BlockStoreError> {
         self.block_store.write_approvals_hashes(&mut self.txn, data)
@@ -1034,11 +1396,11 @@ impl DataWriter<BlockHash, BlockHeader> for IndexedLmdbBlockStoreRWTransaction<'
         let key = self.block_store.write_block_header(&mut self.txn, data)?;
 
         if update_height_index {
-            self.block_height_index.insert(block_height, block_hash);
+            self.insert_to_block_height_index(block_height, &block_hash)?;
         }
 
         if update_switch_block_index {
-            self.switch_block_era_id_index.insert(era_id, block_hash);
+            self.insert_to_switch_block_index(era_id, &block_hash)?;
         }
 
         Ok(key)
@@ -1049,12 +1411,7 @@ impl DataWriter<BlockHash, BlockHeader> for IndexedLmdbBlockStoreRWTransaction<'
 
         if let Some(block_header) = maybe_block_header {
             self.block_store.delete_block_header(&mut self.txn, &key)?;
-
-            if block_header.is_switch_block() {
-                self.switch_block_era_id_index.remove(block_header.era_id());
-            }
-
-            self.block_height_index.remove(block_header.height());
+            self.remove_from_block_header_indices(&block_header)?;
         }
         Ok(())
     }
@@ -1119,12 +1476,10 @@ impl DataWriter<BlockHashHeightAndEra, BlockExecutionResults>
         )?;
 
         if update_transaction_hash_index {
-            for hash in transaction_hashes {
-                self.transaction_hash_index.insert(
-                    hash,
-                    BlockHashHeightAndEra::new(block_hash, block_height, era_id),
-                );
-            }
+            self.insert_to_transaction_hash_index(
+                transaction_hashes,
+                BlockHashHeightAndEra::new(block_hash, block_height, era_id),
+            )?;
         }
 
         Ok(data.block_info)
@@ -1135,6 +1490,31 @@ impl DataWriter<BlockHashHeightAndEra, BlockExecutionResults>
     }
 }
 
+impl DataWriter<BlockHashHeightAndEra, BlockTransactionInitiators>
+    for IndexedLmdbBlockStoreRWTransaction<'_>
+{
+    fn write(
+        &mut self,
+        data: &BlockTransactionInitiators,
+    ) -> Result<BlockHashHeightAndEra, BlockStoreError> {
+        for (transaction_hash, initiator_addr) in &data.initiators {
+            self.indices
+                .put_initiator_transaction(
+                    &mut self.txn,
+                    &initiator_addr.account_hash(),
+                    transaction_hash,
+                    &data.block_info,
+                )
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        }
+        Ok(data.block_info)
+    }
+
+    fn delete(&mut self, _key: BlockHashHeightAndEra) -> Result<(), BlockStoreError> {
+        Err(BlockStoreError::UnsupportedOperation)
+    }
+}
+
 impl DataWriter<BlockHash, BlockTransfers> for IndexedLmdbBlockStoreRWTransaction<'_> {
     fn write(&mut self, data: &BlockTransfers) -> Result<BlockHash, BlockStoreError> {
         self.block_store
@@ -1243,3 +1623,97 @@ impl DataReader<BlockHash, Vec<Transfer>> for IndexedLmdbBlockStoreRWTransaction
         self.block_store.has_transfers(&self.txn, &key)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{testing::TestRng, TestBlockBuilder};
+
+    use super::*;
+    use crate::block_store::lmdb::index_databases::RawEntries;
+
+    const MAX_STORE_SIZE: usize = 100 * 1024 * 1024;
+
+    fn index_entries(block_store: &IndexedLmdbBlockStore) -> Vec<RawEntries> {
+        let txn = block_store.block_store.env.begin_ro_txn().unwrap();
+        block_store.indices.entries(&txn).unwrap()
+    }
+
+    /// Writes three eras of blocks with transactions, which are indexed as they are written.
+    fn write_blocks(block_store: &mut IndexedLmdbBlockStore, rng: &mut TestRng) {
+        let mut rw_txn = block_store.checkout_rw().unwrap();
+        for height in 0..9_u64 {
+            let block = Block::from(
+                TestBlockBuilder::new()
+                    .height(height)
+                    .era(height / 3)
+                    .switch_block(height % 3 == 2)
+                    .random_transactions(2, rng)
+                    .build(rng),
+            );
+            let _ = rw_txn.write(&block).unwrap();
+        }
+        rw_txn.commit().unwrap();
+    }
+
+    #[test]
+    fn should_rebuild_missing_or_outdated_indices() {
+        let rng = &mut TestRng::new();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let expected_entries = {
+            let lmdb_block_store = LmdbBlockStore::new(temp_dir.path(), MAX_STORE_SIZE).unwrap();
+            let mut block_store =
+                IndexedLmdbBlockStore::new(lmdb_block_store, None, ProtocolVersion::V2_0_0)
+                    .unwrap();
+            write_blocks(&mut block_store, rng);
+            index_entries(&block_store)
+        };
+        assert_eq!(expected_entries.len(), 3);
+        assert!(expected_entries.iter().all(|entries| !entries.is_empty()));
+
+        // Stores written by versions keeping the indices in memory have no stored version, and
+        // stores of older versions may have indices in another format.
+        for stored_version in [None, Some(INDICES_VERSION - 1)] {
+            {
+                let lmdb_block_store =
+                    LmdbBlockStore::new(temp_dir.path(), MAX_STORE_SIZE).unwrap();
+                let block_store = IndexedLmdbBlockStore::open_read_only(lmdb_block_store).unwrap();
+                let mut txn = block_store.block_store.env.begin_rw_txn().unwrap();
+                block_store.indices.clear(&mut txn).unwrap();
+                match stored_version {
+                    Some(version) => {
+                        block_store
+                            .indices
+                            .put_block_height(&mut txn, 100, &BlockHash::random(rng))
+                            .unwrap();
+                        block_store
+                            .block_store
+                            .write_state_store(
+                                &mut txn,
+                                Cow::Borrowed(INDICES_VERSION_KEY),
+                                &lmdb_ext::serialize_bytesrepr(&version).unwrap(),
+                            )
+                            .unwrap();
+                    }
+                    None => block_store
+                        .block_store
+                        .delete_state_store(&mut txn, Cow::Borrowed(INDICES_VERSION_KEY))
+                        .unwrap(),
+                }
+                txn.commit().unwrap();
+                assert!(
+                    !IndexedLmdbBlockStore::has_current_indices(&block_store.block_store).unwrap()
+                );
+            }
+
+            let lmdb_block_store = LmdbBlockStore::new(temp_dir.path(), MAX_STORE_SIZE).unwrap();
+            let block_store =
+                IndexedLmdbBlockStore::new(lmdb_block_store, None, ProtocolVersion::V2_0_0)
+                    .unwrap();
+            assert_eq!(
+                block_store.stored_indices_version().unwrap(),
+                Some(INDICES_VERSION)
+            );
+            assert_eq!(index_entries(&block_store), expected_entries);
+        }
+    }
+}
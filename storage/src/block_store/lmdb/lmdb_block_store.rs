@@ -1,6 +1,7 @@
 use std::{
     borrow::Cow,
     collections::{BTreeSet, HashMap},
+    convert::TryFrom,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -16,7 +17,7 @@ use casper_types::{
 
 use super::{
     lmdb_ext::{LmdbExtError, TransactionExt},
-    versioned_databases::VersionedDatabases,
+    versioned_databases::{VersionedDatabases, VersionedKey, VersionedValue},
 };
 use crate::block_store::{
     error::BlockStoreError,
@@ -39,7 +40,7 @@ const STORAGE_DB_FILENAME: &str = "storage.lmdb";
 const MAX_TRANSACTIONS: u32 = 5;
 
 /// Maximum number of allowed dbs.
-const MAX_DB_COUNT: u32 = 17;
+const MAX_DB_COUNT: u32 = 21;
 
 /// OS-specific lmdb flags.
 #[cfg(not(target_os = "macos"))]
@@ -87,32 +88,50 @@ impl LmdbBlockStore {
     pub fn new(root_path: &Path, total_size: usize) -> Result<Self, BlockStoreError> {
         // Create the environment and databases.
         let env = new_environment(total_size, root_path)?;
+        Self::with_databases(root_path, env, false)
+    }
 
-        let block_header_dbs = VersionedDatabases::new(&env, "block_header", "block_header_v2")
-            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
-        let block_body_dbs =
-            VersionedDatabases::<_, BlockBody>::new(&env, "block_body", "block_body_v2")
-                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+    /// Opens an existing block store read-only, e.g. for offline inspection while the node is not
+    /// running.
+    pub fn open_read_only(root_path: &Path, total_size: usize) -> Result<Self, BlockStoreError> {
+        let env = open_environment_read_only(total_size, root_path)?;
+        Self::with_databases(root_path, env, true)
+    }
+
+    /// Opens the databases of the given environment, creating them unless `read_only`.
+    fn with_databases(
+        root_path: &Path,
+        env: Environment,
+        read_only: bool,
+    ) -> Result<Self, BlockStoreError> {
+        let block_header_dbs =
+            versioned_databases(&env, "block_header", "block_header_v2", read_only)?;
+        let block_body_dbs = versioned_databases(&env, "block_body", "block_body_v2", read_only)?;
         let block_metadata_dbs =
-            VersionedDatabases::new(&env, "block_metadata", "block_metadata_v2")
-                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
-        let transaction_dbs = VersionedDatabases::new(&env, "deploys", "transactions")
-            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+            versioned_databases(&env, "block_metadata", "block_metadata_v2", read_only)?;
+        let transaction_dbs = versioned_databases(&env, "deploys", "transactions", read_only)?;
         let execution_result_dbs =
-            VersionedDatabases::new(&env, "deploy_metadata", "execution_results")
-                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
-        let transfer_dbs = VersionedDatabases::new(&env, "transfer", "versioned_transfers")
-            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
-        let state_store_db = env
-            .create_db(Some("state_store"), DatabaseFlags::empty())
-            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+            versioned_databases(&env, "deploy_metadata", "execution_results", read_only)?;
+        let transfer_dbs = versioned_databases(&env, "transfer", "versioned_transfers", read_only)?;
+        let state_store_db = if read_only {
+            env.open_db(Some("state_store"))
+        } else {
+            env.create_db(Some("state_store"), DatabaseFlags::empty())
+        }
+        .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
 
-        let finalized_transaction_approvals_dbs =
-            VersionedDatabases::new(&env, "finalized_approvals", "versioned_finalized_approvals")
-                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
-        let approvals_hashes_dbs =
-            VersionedDatabases::new(&env, "approvals_hashes", "versioned_approvals_hashes")
-                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        let finalized_transaction_approvals_dbs = versioned_databases(
+            &env,
+            "finalized_approvals",
+            "versioned_finalized_approvals",
+            read_only,
+        )?;
+        let approvals_hashes_dbs = versioned_databases(
+            &env,
+            "approvals_hashes",
+            "versioned_approvals_hashes",
+            read_only,
+        )?;
 
         Ok(Self {
             root: root_path.to_path_buf(),
@@ -129,6 +148,26 @@ impl LmdbBlockStore {
         })
     }
 
+    /// Calls `f` with the hash under which each block header is stored, and the header or the
+    /// error decoding it, for every block header in the store.
+    pub fn for_each_block_header<F>(&self, mut f: F) -> Result<(), BlockStoreError>
+    where
+        F: FnMut(BlockHash, Result<BlockHeader, BlockStoreError>) -> Result<(), BlockStoreError>,
+    {
+        let txn = self
+            .env
+            .begin_ro_txn()
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        self.block_header_dbs.for_each_raw_key_and_value(
+            &txn,
+            &mut |raw_key, maybe_block_header| {
+                let digest = Digest::try_from(raw_key)
+                    .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+                f(BlockHash::new(digest), maybe_block_header)
+            },
+        )
+    }
+
     /// Write finality signatures.
     pub fn write_finality_signatures(
         &self,
@@ -576,6 +615,43 @@ pub(crate) fn new_environment(
         .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
 }
 
+fn open_environment_read_only(
+    total_size: usize,
+    root: &Path,
+) -> Result<Environment, BlockStoreError> {
+    Environment::new()
+        .set_flags(
+            EnvironmentFlags::READ_ONLY
+                | EnvironmentFlags::NO_SUB_DIR
+                | EnvironmentFlags::NO_TLS
+                | EnvironmentFlags::NO_READAHEAD,
+        )
+        .set_max_readers(MAX_TRANSACTIONS)
+        .set_max_dbs(MAX_DB_COUNT)
+        .set_map_size(total_size)
+        .open(&root.join(STORAGE_DB_FILENAME))
+        .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
+}
+
+/// Opens the given versioned databases, creating them unless `read_only`.
+fn versioned_databases<K, V>(
+    env: &Environment,
+    legacy_name: &str,
+    current_name: &str,
+    read_only: bool,
+) -> Result<VersionedDatabases<K, V>, BlockStoreError>
+where
+    K: VersionedKey + std::fmt::Display,
+    V: VersionedValue + 'static,
+{
+    if read_only {
+        VersionedDatabases::open(env, legacy_name, current_name)
+    } else {
+        VersionedDatabases::new(env, legacy_name, current_name)
+    }
+    .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
+}
+
 /// Returns all `Transform::WriteTransfer`s from the execution effects if this is an
 /// `ExecutionResult::Success`, or an empty `Vec` if `ExecutionResult::Failure`.
 fn successful_transfers(execution_result: &ExecutionResult) -> Vec<Transfer> {
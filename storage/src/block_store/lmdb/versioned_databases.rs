@@ -142,6 +142,19 @@ where
         })
     }
 
+    /// Opens the existing databases, without creating them if missing.
+    pub(super) fn open(
+        env: &Environment,
+        legacy_name: &str,
+        current_name: &str,
+    ) -> Result<Self, lmdb::Error> {
+        Ok(VersionedDatabases {
+            legacy: env.open_db(Some(legacy_name))?,
+            current: env.open_db(Some(current_name))?,
+            _phantom: PhantomData,
+        })
+    }
+
     pub(super) fn put(
         &self,
         txn: &mut RwTransaction,
@@ -294,6 +307,42 @@ where
         Ok(())
     }
 
+    /// Iterates every row in the current and then in the legacy database, calling `f` with the raw
+    /// key and the result of parsing the value, so that undecodable values don't end the
+    /// iteration.
+    pub(super) fn for_each_raw_key_and_value<Tx, F>(
+        &self,
+        txn: &Tx,
+        f: &mut F,
+    ) -> Result<(), BlockStoreError>
+    where
+        Tx: LmdbTransaction,
+        F: FnMut(&[u8], Result<V, BlockStoreError>) -> Result<(), BlockStoreError>,
+    {
+        let mut cursor = txn
+            .open_ro_cursor(self.current)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        for row in cursor.iter() {
+            let (raw_key, raw_val) =
+                row.map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+            let value = lmdb_ext::deserialize_bytesrepr(raw_val)
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)));
+            f(raw_key, value)?;
+        }
+        let mut cursor = txn
+            .open_ro_cursor(self.legacy)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+        for row in cursor.iter() {
+            let (raw_key, raw_val) =
+                row.map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?;
+            let value = lmdb_ext::deserialize::<V::Legacy>(raw_val)
+                .map(Into::into)
+                .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)));
+            f(raw_key, value)?;
+        }
+        Ok(())
+    }
+
     /// Writes to the `legacy` database.
     #[cfg(test)]
     pub(super) fn put_legacy(
@@ -4,10 +4,13 @@ use rand::Rng;
 
 #[cfg(test)]
 use casper_types::testing::TestRng;
-use casper_types::{BlockHash, BlockHashAndHeight, EraId};
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    BlockHash, BlockHashAndHeight, EraId,
+};
 
 /// Aggregates block identifying information.
-#[derive(Clone, Copy, Debug, DataSize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, DataSize)]
 pub struct BlockHashHeightAndEra {
     /// Block hash.
     pub block_hash: BlockHash,
@@ -43,3 +46,51 @@ impl From<BlockHashHeightAndEra> for BlockHashAndHeight {
         BlockHashAndHeight::new(bhhe.block_hash, bhhe.block_height)
     }
 }
+
+impl ToBytes for BlockHashHeightAndEra {
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.block_hash.write_bytes(writer)?;
+        self.block_height.write_bytes(writer)?;
+        self.era_id.write_bytes(writer)
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.block_hash.serialized_length()
+            + self.block_height.serialized_length()
+            + self.era_id.serialized_length()
+    }
+}
+
+impl FromBytes for BlockHashHeightAndEra {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (block_hash, remainder) = BlockHash::from_bytes(bytes)?;
+        let (block_height, remainder) = u64::from_bytes(remainder)?;
+        let (era_id, remainder) = EraId::from_bytes(remainder)?;
+        Ok((
+            BlockHashHeightAndEra {
+                block_hash,
+                block_height,
+                era_id,
+            },
+            remainder,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+        let block_hash_height_and_era = BlockHashHeightAndEra::random(rng);
+        bytesrepr::test_serialization_roundtrip(&block_hash_height_and_era);
+    }
+}
@@ -11,7 +11,8 @@ use std::{
 pub use approvals_hashes::{ApprovalsHashes, ApprovalsHashesValidationError};
 pub use block_hash_height_and_era::BlockHashHeightAndEra;
 use casper_types::{
-    execution::ExecutionResult, Approval, Block, BlockHash, BlockHeader, TransactionHash, Transfer,
+    execution::ExecutionResult, Approval, Block, BlockHash, BlockHeader, InitiatorAddr,
+    TransactionHash, Transfer,
 };
 
 pub(crate) use approvals_hashes::LegacyApprovalsHashes;
@@ -37,6 +38,28 @@ pub struct BlockExecutionResults {
     pub exec_results: ExecutionResults,
 }
 
+/// Initiators of the transactions of a block, used to populate the initiator transactions index.
+pub struct BlockTransactionInitiators {
+    /// Block info.
+    pub block_info: BlockHashHeightAndEra,
+    /// Transaction hashes along with the addresses of their initiators.
+    pub initiators: Vec<(TransactionHash, InitiatorAddr)>,
+}
+
+/// Query for the transactions initiated by a given account within a range of block heights.
+pub struct InitiatorTransactionsQuery {
+    /// Initiator of the transactions.
+    pub initiator_addr: InitiatorAddr,
+    /// Height of the lowest block to include.
+    pub from_height: u64,
+    /// Height of the highest block to include.
+    pub to_height: u64,
+    /// If set, the transactions at `from_height` up to and including this one are skipped.
+    pub start_after: Option<TransactionHash>,
+    /// Maximum number of transactions to return.
+    pub limit: usize,
+}
+
 /// Block transfers.
 pub struct BlockTransfers {
     /// Block hash.
@@ -66,6 +89,12 @@ impl StateStoreKey {
 /// Block tip anchor.
 pub struct Tip;
 
+/// Marker for the parts of a block which can be removed when pruning: its transactions, their
+/// finalized approvals and execution results, the approvals hashes, the transfers and the body.
+///
+/// The header, the finality signatures and the block height and switch block indices are kept.
+pub struct PrunableBlockData;
+
 /// Latest switch block anchor.
 pub struct LatestSwitchBlock;
 
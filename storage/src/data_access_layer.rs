@@ -36,6 +36,7 @@ mod key_prefix;
 pub mod message_topics;
 /// Mint provider.
 pub mod mint;
+mod multi_proof;
 /// Prefixed values provider.
 pub mod prefixed_values;
 mod protocol_upgrade;
@@ -45,6 +46,7 @@ pub mod prune;
 pub mod query;
 mod round_seigniorage;
 mod seigniorage_recipients;
+mod state_diff;
 /// Step provider.
 pub mod step;
 mod system_entity_registry;
@@ -84,11 +86,13 @@ pub use handle_refund::{HandleRefundMode, HandleRefundRequest, HandleRefundResul
 pub use key_prefix::KeyPrefix;
 pub use message_topics::{MessageTopicsRequest, MessageTopicsResult};
 pub use mint::{TransferRequest, TransferResult};
+pub use multi_proof::{MultiProofRequest, MultiProofResult};
 pub use protocol_upgrade::{ProtocolUpgradeRequest, ProtocolUpgradeResult};
 pub use prune::{PruneRequest, PruneResult};
 pub use query::{QueryRequest, QueryResult};
 pub use round_seigniorage::{RoundSeigniorageRateRequest, RoundSeigniorageRateResult};
 pub use seigniorage_recipients::{SeigniorageRecipientsRequest, SeigniorageRecipientsResult};
+pub use state_diff::{StateDiffEntry, StateDiffRequest, StateDiffResult};
 pub use step::{EvictItem, RewardItem, SlashItem, StepError, StepRequest, StepResult};
 pub use system_entity_registry::{
     SystemEntityRegistryPayload, SystemEntityRegistryRequest, SystemEntityRegistryResult,
@@ -191,6 +195,14 @@ where
         self.state.put_trie(request)
     }
 
+    fn state_diff(&self, request: StateDiffRequest) -> StateDiffResult {
+        self.state.state_diff(request)
+    }
+
+    fn multi_proof(&self, request: MultiProofRequest) -> MultiProofResult {
+        self.state.multi_proof(request)
+    }
+
     fn missing_children(&self, trie_raw: &[u8]) -> Result<Vec<Digest>, GlobalStateError> {
         self.state.missing_children(trie_raw)
     }
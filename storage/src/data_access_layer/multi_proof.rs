@@ -0,0 +1,44 @@
+//! Support for obtaining a single proof of several global state entries.
+use casper_types::{global_state::TrieMultiProof, Digest, Key, StoredValue};
+
+use crate::global_state::error::Error as GlobalStateError;
+
+/// Represents a request to read several entries of global state along with a single proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiProofRequest {
+    state_hash: Digest,
+    keys: Vec<Key>,
+}
+
+impl MultiProofRequest {
+    /// Creates new request.
+    pub fn new(state_hash: Digest, keys: Vec<Key>) -> Self {
+        MultiProofRequest { state_hash, keys }
+    }
+
+    /// Returns the state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the keys to read.
+    pub fn keys(&self) -> &[Key] {
+        &self.keys
+    }
+}
+
+/// Represents a result of a `multi_proof` request.
+#[derive(Debug)]
+pub enum MultiProofResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// None of the keys was found.
+    ValuesNotFound,
+    /// Contains the entries found, with a single proof of all of them.
+    Success {
+        /// The proof, holding the entries found.
+        proof: Box<TrieMultiProof<Key, StoredValue>>,
+    },
+    /// Failure.
+    Failure(GlobalStateError),
+}
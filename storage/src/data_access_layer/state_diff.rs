@@ -0,0 +1,71 @@
+//! Support for obtaining the differences between two global states.
+use casper_types::{Digest, Key, StoredValue};
+
+use crate::global_state::{
+    error::Error as GlobalStateError, trie_store::operations::TrieDiffEntry,
+};
+
+/// A difference between two global states.
+pub type StateDiffEntry = TrieDiffEntry<Key, StoredValue>;
+
+/// Represents a request to obtain the keys added, removed and modified between two global states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateDiffRequest {
+    from_state_hash: Digest,
+    to_state_hash: Digest,
+    start_after: Option<Key>,
+    max_entries: usize,
+}
+
+impl StateDiffRequest {
+    /// Creates new request.
+    pub fn new(
+        from_state_hash: Digest,
+        to_state_hash: Digest,
+        start_after: Option<Key>,
+        max_entries: usize,
+    ) -> Self {
+        StateDiffRequest {
+            from_state_hash,
+            to_state_hash,
+            start_after,
+            max_entries,
+        }
+    }
+
+    /// Returns the state root hash to diff from.
+    pub fn from_state_hash(&self) -> Digest {
+        self.from_state_hash
+    }
+
+    /// Returns the state root hash to diff to.
+    pub fn to_state_hash(&self) -> Digest {
+        self.to_state_hash
+    }
+
+    /// Returns the key after which the differences start, if any.
+    pub fn start_after(&self) -> Option<&Key> {
+        self.start_after.as_ref()
+    }
+
+    /// Returns the maximum number of differences to return.
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+}
+
+/// Represents a result of a `state_diff` request.
+#[derive(Debug)]
+pub enum StateDiffResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// Contains the differences between the two global states.
+    Success {
+        /// The differences, ordered by key.
+        entries: Vec<StateDiffEntry>,
+        /// The key to start the next page after, if there are more differences.
+        next_page: Option<Key>,
+    },
+    /// Failure.
+    Failure(GlobalStateError),
+}
@@ -1,5 +1,9 @@
 /// Storage errors.
 pub mod error;
+/// Garbage collection of unreferenced trie nodes.
+pub mod gc;
+/// Snapshots of the global state under a single state root.
+pub mod snapshot;
 /// Global State.
 pub mod state;
 /// Store module.
@@ -11,7 +15,7 @@ pub mod trie;
 /// Merkle Trie storage.
 pub mod trie_store;
 
-const MAX_DBS: u32 = 2;
+const MAX_DBS: u32 = 5;
 
 pub(crate) const DEFAULT_MAX_DB_SIZE: usize = 52_428_800; // 50 MiB
 
@@ -36,6 +36,10 @@ pub enum Error {
     #[error("Failed to put a trie into global state because some of its children were missing")]
     MissingTrieNodeChildren(Digest, TrieRaw, Vec<Digest>),
 
+    /// A trie was not found in global state.
+    #[error("Trie not found: {0}")]
+    MissingTrie(Digest),
+
     /// Failed to prune listed keys.
     #[error("Pruning attempt failed.")]
     FailedToPrune(Vec<Key>),
@@ -47,6 +51,10 @@ pub enum Error {
     /// Encountered a cache error.
     #[error("Cache error")]
     CacheError(#[from] TrieStoreCacheError),
+
+    /// A garbage collection of the trie store is already in progress.
+    #[error("Garbage collection already in progress")]
+    GarbageCollectionInProgress,
 }
 
 impl<T> From<sync::PoisonError<T>> for Error {
@@ -0,0 +1,656 @@
+//! Mark-and-sweep garbage collection of the trie store.
+//!
+//! Tries are only ever added to the trie store, so the nodes of state roots which are no longer
+//! of interest accumulate forever.  A garbage collection marks every trie reachable from a set of
+//! retained state roots, then sweeps the trie store, deleting every trie which wasn't marked.
+//!
+//! The state of a collection is kept in dedicated LMDB databases living in the same environment
+//! as the trie store, and every step is performed within a single write transaction.  A collection
+//! can hence be interrupted at any point (including by the process being killed) and resumed later
+//! by creating a new [`TrieGarbageCollector`] on the same environment and continuing to call
+//! [`TrieGarbageCollector::step`].
+//!
+//! While a collection is in progress, the trie store records the hashes of all tries written to it
+//! as pending marking, so a collection can run online, alongside the execution of new blocks.  New
+//! tries must only be written on top of retained state roots though, as any subtrie shared with
+//! an unretained root may already have been swept.
+
+use std::sync::Arc;
+
+use lmdb::{Cursor, Database, DatabaseFlags, RwTransaction, Transaction as LmdbTransaction};
+use lmdb_sys::{MDB_FIRST, MDB_NEXT, MDB_SET_RANGE};
+use tracing::info;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    Digest,
+};
+
+use crate::global_state::{
+    error::Error,
+    state::lmdb::LmdbGlobalState,
+    transaction_source::{lmdb::LmdbEnvironment, TransactionSource},
+    trie::LazilyDeserializedTrie,
+    trie_store::lmdb::LmdbTrieStore,
+};
+
+/// Name of the database holding the hashes of the tries marked as reachable.
+const MARKED_DB_NAME: &str = "TRIE_STORE_GC_MARKED";
+/// Name of the database holding the hashes of the tries awaiting marking.
+const PENDING_DB_NAME: &str = "TRIE_STORE_GC_PENDING";
+/// Name of the database holding the progress of the collection.
+const PROGRESS_DB_NAME: &str = "TRIE_STORE_GC_PROGRESS";
+/// Key under which the progress of the collection is stored.
+const PROGRESS_KEY: &[u8] = b"progress";
+/// Value stored in the marked and pending databases, which are only used as sets.
+const NO_VALUE: [u8; 0] = [];
+
+/// The phase of a trie store garbage collection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GcPhase {
+    /// No collection is in progress.
+    Idle,
+    /// Tries reachable from the retained state roots are being marked.
+    Mark,
+    /// Unmarked tries are being deleted from the trie store.
+    Sweep,
+}
+
+impl GcPhase {
+    fn tag(self) -> u8 {
+        match self {
+            GcPhase::Idle => 0,
+            GcPhase::Mark => 1,
+            GcPhase::Sweep => 2,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<Self, bytesrepr::Error> {
+        match tag {
+            0 => Ok(GcPhase::Idle),
+            1 => Ok(GcPhase::Mark),
+            2 => Ok(GcPhase::Sweep),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// The progress of a trie store garbage collection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GcProgress {
+    /// The current phase.
+    pub phase: GcPhase,
+    /// The number of tries marked as reachable so far.
+    pub tries_marked: u64,
+    /// The number of tries visited by the sweep phase so far.
+    pub tries_swept: u64,
+    /// The number of tries deleted so far.
+    pub tries_deleted: u64,
+}
+
+impl GcProgress {
+    const IDLE: GcProgress = GcProgress {
+        phase: GcPhase::Idle,
+        tries_marked: 0,
+        tries_swept: 0,
+        tries_deleted: 0,
+    };
+}
+
+/// The persisted state of a collection.
+struct GcState {
+    progress: GcProgress,
+    /// The hash of the last trie visited by the sweep phase.
+    sweep_cursor: Option<Digest>,
+}
+
+impl ToBytes for GcState {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.push(self.progress.phase.tag());
+        buffer.extend(self.progress.tries_marked.to_bytes()?);
+        buffer.extend(self.progress.tries_swept.to_bytes()?);
+        buffer.extend(self.progress.tries_deleted.to_bytes()?);
+        buffer.extend(self.sweep_cursor.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        1 + self.progress.tries_marked.serialized_length()
+            + self.progress.tries_swept.serialized_length()
+            + self.progress.tries_deleted.serialized_length()
+            + self.sweep_cursor.serialized_length()
+    }
+}
+
+impl FromBytes for GcState {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        let (tries_marked, remainder) = u64::from_bytes(remainder)?;
+        let (tries_swept, remainder) = u64::from_bytes(remainder)?;
+        let (tries_deleted, remainder) = u64::from_bytes(remainder)?;
+        let (sweep_cursor, remainder) = Option::<Digest>::from_bytes(remainder)?;
+        let state = GcState {
+            progress: GcProgress {
+                phase: GcPhase::from_tag(tag)?,
+                tries_marked,
+                tries_swept,
+                tries_deleted,
+            },
+            sweep_cursor,
+        };
+        Ok((state, remainder))
+    }
+}
+
+/// A mark-and-sweep garbage collector for the trie store of an [`LmdbGlobalState`].
+pub struct TrieGarbageCollector {
+    environment: Arc<LmdbEnvironment>,
+    trie_store: Arc<LmdbTrieStore>,
+    /// The root of the empty trie, which is always retained.
+    empty_root_hash: Digest,
+    marked_db: Database,
+    pending_db: Database,
+    progress_db: Database,
+}
+
+impl TrieGarbageCollector {
+    /// Creates a garbage collector for the given global state.
+    ///
+    /// If a collection was interrupted, it is resumed by subsequent calls to [`Self::step`].  The
+    /// collector must be created before any tries are written to the global state, so that tries
+    /// written during a resumed collection are not swept.
+    pub fn new(global_state: &LmdbGlobalState) -> Result<Self, Error> {
+        let environment = Arc::clone(&global_state.environment);
+        let env = environment.env();
+        let marked_db = env.create_db(Some(MARKED_DB_NAME), DatabaseFlags::empty())?;
+        let pending_db = env.create_db(Some(PENDING_DB_NAME), DatabaseFlags::empty())?;
+        let progress_db = env.create_db(Some(PROGRESS_DB_NAME), DatabaseFlags::empty())?;
+        let collector = TrieGarbageCollector {
+            environment,
+            trie_store: Arc::clone(&global_state.trie_store),
+            empty_root_hash: global_state.empty_root_hash,
+            marked_db,
+            pending_db,
+            progress_db,
+        };
+        if collector.progress()?.phase != GcPhase::Idle {
+            collector.trie_store.set_gc_write_barrier(Some(pending_db));
+        }
+        Ok(collector)
+    }
+
+    /// Returns the progress of the current collection.
+    pub fn progress(&self) -> Result<GcProgress, Error> {
+        let txn = self.environment.create_read_txn()?;
+        let progress = self.read_state(&txn)?.progress;
+        txn.commit()?;
+        Ok(progress)
+    }
+
+    /// Starts a collection which retains the tries reachable from the given state roots.
+    ///
+    /// Returns an error if a collection is already in progress.
+    pub fn start(&self, retained_state_roots: &[Digest]) -> Result<(), Error> {
+        // Enable the write barrier first, so tries written concurrently with the start of the
+        // collection are retained.
+        self.trie_store.set_gc_write_barrier(Some(self.pending_db));
+        let mut txn = self.environment.create_read_write_txn()?;
+        if self.read_state(&txn)?.progress.phase != GcPhase::Idle {
+            return Err(Error::GarbageCollectionInProgress);
+        }
+        txn.clear_db(self.marked_db)?;
+        for state_root in retained_state_roots
+            .iter()
+            .chain(std::iter::once(&self.empty_root_hash))
+        {
+            txn.put(
+                self.pending_db,
+                state_root,
+                &NO_VALUE,
+                lmdb::WriteFlags::empty(),
+            )?;
+        }
+        let state = GcState {
+            progress: GcProgress {
+                phase: GcPhase::Mark,
+                ..GcProgress::IDLE
+            },
+            sweep_cursor: None,
+        };
+        self.write_state(&mut txn, &state)?;
+        txn.commit()?;
+        info!(
+            retained_state_roots = retained_state_roots.len(),
+            "started trie store garbage collection"
+        );
+        Ok(())
+    }
+
+    /// Performs up to `max_tries` units of work of the current collection, committing them
+    /// atomically, and returns the updated progress.
+    ///
+    /// Pending tries are always marked before any sweeping is done.  Once the sweep phase has
+    /// visited the whole trie store, the collection finishes and the returned phase is
+    /// [`GcPhase::Idle`].
+    pub fn step(&self, max_tries: usize) -> Result<GcProgress, Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        let mut state = self.read_state(&txn)?;
+        if state.progress.phase == GcPhase::Idle {
+            txn.commit()?;
+            return Ok(state.progress);
+        }
+
+        let marked_count = self.mark(&mut txn, max_tries)?;
+        state.progress.tries_marked += marked_count as u64;
+        let remaining = max_tries.saturating_sub(marked_count);
+        let has_pending = first_key(&txn, self.pending_db)?.is_some();
+
+        if !has_pending && remaining > 0 {
+            if state.progress.phase == GcPhase::Mark {
+                state.progress.phase = GcPhase::Sweep;
+            }
+            let mut deleted = vec![];
+            let finished = self.sweep(&mut txn, &mut state, remaining, &mut deleted)?;
+            if finished {
+                txn.clear_db(self.marked_db)?;
+                txn.clear_db(self.pending_db)?;
+                let progress = state.progress;
+                self.write_state(
+                    &mut txn,
+                    &GcState {
+                        progress: GcProgress::IDLE,
+                        sweep_cursor: None,
+                    },
+                )?;
+                txn.commit()?;
+                self.trie_store.evict_from_node_cache(&deleted);
+                self.trie_store.set_gc_write_barrier(None);
+                info!(
+                    tries_marked = progress.tries_marked,
+                    tries_deleted = progress.tries_deleted,
+                    "finished trie store garbage collection"
+                );
+                return Ok(GcProgress {
+                    phase: GcPhase::Idle,
+                    ..progress
+                });
+            }
+            self.write_state(&mut txn, &state)?;
+            txn.commit()?;
+            self.trie_store.evict_from_node_cache(&deleted);
+            return Ok(state.progress);
+        }
+
+        self.write_state(&mut txn, &state)?;
+        txn.commit()?;
+        Ok(state.progress)
+    }
+
+    /// Abandons the current collection, if any, without deleting any further tries.
+    pub fn abort(&self) -> Result<(), Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        txn.clear_db(self.marked_db)?;
+        txn.clear_db(self.pending_db)?;
+        self.write_state(
+            &mut txn,
+            &GcState {
+                progress: GcProgress::IDLE,
+                sweep_cursor: None,
+            },
+        )?;
+        txn.commit()?;
+        self.trie_store.set_gc_write_barrier(None);
+        Ok(())
+    }
+
+    /// Runs a collection to completion, performing `batch_size` units of work per transaction.
+    ///
+    /// If a collection is already in progress it is resumed, otherwise a new one retaining the
+    /// tries reachable from the given state roots is started.  Intended to be used while nothing
+    /// else is accessing the global state.
+    pub fn run(
+        &self,
+        retained_state_roots: &[Digest],
+        batch_size: usize,
+    ) -> Result<GcProgress, Error> {
+        if self.progress()?.phase == GcPhase::Idle {
+            self.start(retained_state_roots)?;
+        } else {
+            info!("resuming trie store garbage collection");
+        }
+        loop {
+            let progress = self.step(batch_size.max(1))?;
+            if progress.phase == GcPhase::Idle {
+                return Ok(progress);
+            }
+            info!(
+                phase = ?progress.phase,
+                tries_marked = progress.tries_marked,
+                tries_swept = progress.tries_swept,
+                tries_deleted = progress.tries_deleted,
+                "trie store garbage collection in progress"
+            );
+        }
+    }
+
+    /// Marks up to `max_tries` pending tries, queueing their unmarked children for marking.
+    ///
+    /// Returns the number of pending tries processed.
+    fn mark(&self, txn: &mut RwTransaction, max_tries: usize) -> Result<usize, Error> {
+        let pending = keys_from(&*txn, self.pending_db, None, max_tries)?;
+        for trie_key in &pending {
+            let maybe_trie_bytes = match txn.get(self.trie_store.get_db(), trie_key) {
+                Ok(bytes) => Some(bytes.to_vec()),
+                Err(lmdb::Error::NotFound) => None,
+                Err(error) => return Err(error.into()),
+            };
+            // Tries missing from the store (e.g. queued by the write barrier after a previous
+            // collection finished) are simply dropped.
+            if let Some(trie_bytes) = maybe_trie_bytes {
+                let trie: LazilyDeserializedTrie = bytesrepr::deserialize_from_slice(trie_bytes)?;
+                for child in trie.iter_children() {
+                    if !contains(&*txn, self.marked_db, child.as_ref())? {
+                        txn.put(
+                            self.pending_db,
+                            &child,
+                            &NO_VALUE,
+                            lmdb::WriteFlags::empty(),
+                        )?;
+                    }
+                }
+                txn.put(
+                    self.marked_db,
+                    trie_key,
+                    &NO_VALUE,
+                    lmdb::WriteFlags::empty(),
+                )?;
+            }
+            txn.del(self.pending_db, trie_key, None)?;
+        }
+        Ok(pending.len())
+    }
+
+    /// Visits up to `max_tries` tries following the sweep cursor, deleting the unmarked ones and
+    /// adding their hashes to `deleted`.  They must be evicted from the cache of decoded tries
+    /// once the transaction is committed.
+    ///
+    /// Returns `true` if the end of the trie store was reached.
+    fn sweep(
+        &self,
+        txn: &mut RwTransaction,
+        state: &mut GcState,
+        max_tries: usize,
+        deleted: &mut Vec<Digest>,
+    ) -> Result<bool, Error> {
+        let trie_db = self.trie_store.get_db();
+        let start_after = state.sweep_cursor;
+        // One extra key is read to tell whether the end of the store was reached, and another one
+        // in case the first key read is the previously visited one.
+        let mut keys = keys_from(
+            &*txn,
+            trie_db,
+            start_after.as_ref().map(AsRef::as_ref),
+            max_tries.saturating_add(2),
+        )?;
+        if let Some(cursor) = start_after {
+            keys.retain(|key| key[..] != cursor.as_ref()[..]);
+        }
+        let finished = keys.len() <= max_tries;
+        keys.truncate(max_tries);
+
+        for trie_key in &keys {
+            if !contains(&*txn, self.marked_db, trie_key)? {
+                txn.del(trie_db, trie_key, None)?;
+                let (digest, _) = Digest::from_bytes(trie_key)?;
+                deleted.push(digest);
+                state.progress.tries_deleted += 1;
+            }
+        }
+        state.progress.tries_swept += keys.len() as u64;
+        if let Some(last_key) = keys.last() {
+            let (digest, _) = Digest::from_bytes(last_key)?;
+            state.sweep_cursor = Some(digest);
+        }
+        Ok(finished)
+    }
+
+    fn read_state<T: LmdbTransaction>(&self, txn: &T) -> Result<GcState, Error> {
+        match txn.get(self.progress_db, &PROGRESS_KEY) {
+            Ok(bytes) => Ok(bytesrepr::deserialize_from_slice(bytes)?),
+            Err(lmdb::Error::NotFound) => Ok(GcState {
+                progress: GcProgress::IDLE,
+                sweep_cursor: None,
+            }),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn write_state(&self, txn: &mut RwTransaction, state: &GcState) -> Result<(), Error> {
+        txn.put(
+            self.progress_db,
+            &PROGRESS_KEY,
+            &state.to_bytes()?,
+            lmdb::WriteFlags::empty(),
+        )?;
+        Ok(())
+    }
+}
+
+/// Returns up to `limit` keys of the given database, starting at `start` (inclusive) if given.
+fn keys_from<T: LmdbTransaction>(
+    txn: &T,
+    db: Database,
+    start: Option<&[u8]>,
+    limit: usize,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let mut keys = vec![];
+    if limit == 0 {
+        return Ok(keys);
+    }
+    let cursor = txn.open_ro_cursor(db)?;
+    let mut result = match start {
+        Some(start) => cursor.get(Some(start), None, MDB_SET_RANGE),
+        None => cursor.get(None, None, MDB_FIRST),
+    };
+    loop {
+        match result {
+            Ok((Some(key), _)) => keys.push(key.to_vec()),
+            Ok((None, _)) | Err(lmdb::Error::NotFound) => break,
+            Err(error) => return Err(error.into()),
+        }
+        if keys.len() == limit {
+            break;
+        }
+        result = cursor.get(None, None, MDB_NEXT);
+    }
+    Ok(keys)
+}
+
+fn first_key<T: LmdbTransaction>(txn: &T, db: Database) -> Result<Option<Vec<u8>>, Error> {
+    Ok(keys_from(txn, db, None, 1)?.pop())
+}
+
+fn contains<T: LmdbTransaction>(txn: &T, db: Database, key: &[u8]) -> Result<bool, Error> {
+    match txn.get(db, &key) {
+        Ok(_) => Ok(true),
+        Err(lmdb::Error::NotFound) => Ok(false),
+        Err(error) => Err(error.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{CLValue, Key, StoredValue};
+
+    use super::*;
+    use crate::global_state::state::{
+        lmdb::make_temporary_global_state, CommitProvider, StateProvider, StateReader,
+    };
+
+    fn test_value(value: u64) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(value).unwrap())
+    }
+
+    fn trie_count(global_state: &LmdbGlobalState) -> usize {
+        let txn = global_state.environment.create_read_txn().unwrap();
+        let count = keys_from(&txn, global_state.trie_store.get_db(), None, usize::MAX)
+            .unwrap()
+            .len();
+        txn.commit().unwrap();
+        count
+    }
+
+    /// Writes a few generations of values, returning the state root of each generation.
+    fn commit_generations(
+        global_state: &LmdbGlobalState,
+        initial_root: Digest,
+        generations: u64,
+    ) -> Vec<Digest> {
+        let mut roots = vec![initial_root];
+        for generation in 1..=generations {
+            let values = (0..10_u64)
+                .map(|index| (Key::Hash([index as u8; 32]), test_value(generation)))
+                .collect();
+            let root = global_state
+                .commit_values(*roots.last().unwrap(), values, Default::default())
+                .unwrap();
+            roots.push(root);
+        }
+        roots
+    }
+
+    fn assert_readable(global_state: &LmdbGlobalState, root: Digest, expected: u64) {
+        let reader = global_state
+            .checkout(root)
+            .unwrap()
+            .expect("should have root");
+        for index in 0..10_u64 {
+            assert_eq!(
+                reader.read(&Key::Hash([index as u8; 32])).unwrap(),
+                Some(test_value(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn should_delete_tries_unreachable_from_retained_roots() {
+        let (global_state, initial_root, _tempdir) = make_temporary_global_state(vec![]);
+        let roots = commit_generations(&global_state, initial_root, 5);
+        let count_before = trie_count(&global_state);
+
+        let collector = TrieGarbageCollector::new(&global_state).unwrap();
+        let retained = &roots[4..];
+        let progress = collector.run(retained, 3).unwrap();
+
+        assert_eq!(progress.phase, GcPhase::Idle);
+        assert_eq!(progress.tries_swept as usize, count_before);
+        assert!(progress.tries_deleted > 0);
+        assert_eq!(
+            trie_count(&global_state),
+            count_before - progress.tries_deleted as usize
+        );
+        assert_readable(&global_state, roots[4], 4);
+        assert_readable(&global_state, roots[5], 5);
+        assert!(global_state.checkout(roots[2]).unwrap().is_none());
+        assert!(global_state
+            .checkout(global_state.empty_state_root_hash())
+            .unwrap()
+            .is_some());
+        assert_eq!(collector.progress().unwrap().phase, GcPhase::Idle);
+    }
+
+    #[test]
+    fn should_evict_deleted_tries_from_node_cache() {
+        let (uncached_state, initial_root, _tempdir) = make_temporary_global_state(vec![]);
+        let trie_store = LmdbTrieStore::open(&uncached_state.environment, None)
+            .unwrap()
+            .with_node_cache(1_000);
+        let global_state = LmdbGlobalState::new(
+            Arc::clone(&uncached_state.environment),
+            Arc::new(trie_store),
+            uncached_state.empty_state_root_hash(),
+            uncached_state.max_query_depth,
+            uncached_state.enable_entity,
+        );
+        let roots = commit_generations(&global_state, initial_root, 3);
+        assert_readable(&global_state, roots[1], 1);
+        assert_readable(&global_state, roots[1], 1);
+        assert!(global_state.trie_store.node_cache_stats().hits > 0);
+
+        let collector = TrieGarbageCollector::new(&global_state).unwrap();
+        collector.run(&roots[3..], 100).unwrap();
+
+        assert!(global_state.checkout(roots[1]).unwrap().is_none());
+        assert_readable(&global_state, roots[3], 3);
+    }
+
+    #[test]
+    fn should_resume_interrupted_collection() {
+        let (global_state, initial_root, _tempdir) = make_temporary_global_state(vec![]);
+        let roots = commit_generations(&global_state, initial_root, 3);
+
+        let collector = TrieGarbageCollector::new(&global_state).unwrap();
+        collector.start(&roots[3..]).unwrap();
+        let progress = collector.step(2).unwrap();
+        assert_eq!(progress.phase, GcPhase::Mark);
+        assert!(matches!(
+            collector.start(&roots[3..]),
+            Err(Error::GarbageCollectionInProgress)
+        ));
+        drop(collector);
+
+        // A new collector picks up where the previous one left off.
+        let collector = TrieGarbageCollector::new(&global_state).unwrap();
+        assert_eq!(collector.progress().unwrap(), progress);
+        let progress = collector.run(&[], 2).unwrap();
+        assert_eq!(progress.phase, GcPhase::Idle);
+        assert_readable(&global_state, roots[3], 3);
+        assert!(global_state.checkout(roots[1]).unwrap().is_none());
+    }
+
+    #[test]
+    fn should_abort_collection() {
+        let (global_state, initial_root, _tempdir) = make_temporary_global_state(vec![]);
+        let roots = commit_generations(&global_state, initial_root, 2);
+        let count_before = trie_count(&global_state);
+
+        let collector = TrieGarbageCollector::new(&global_state).unwrap();
+        collector.start(&roots[2..]).unwrap();
+        collector.step(1).unwrap();
+        collector.abort().unwrap();
+
+        assert_eq!(collector.progress().unwrap(), GcProgress::IDLE);
+        assert_eq!(collector.step(10).unwrap(), GcProgress::IDLE);
+        assert_eq!(trie_count(&global_state), count_before);
+        assert_readable(&global_state, roots[1], 1);
+        // A new collection can be started once the previous one was aborted.
+        collector.start(&roots[2..]).unwrap();
+    }
+
+    #[test]
+    fn should_retain_tries_written_during_collection() {
+        let (global_state, initial_root, _tempdir) = make_temporary_global_state(vec![]);
+        let roots = commit_generations(&global_state, initial_root, 2);
+
+        let collector = TrieGarbageCollector::new(&global_state).unwrap();
+        collector.start(&roots[2..]).unwrap();
+        // Finish marking, so that new tries could only survive via the write barrier.
+        while collector.progress().unwrap().phase == GcPhase::Mark {
+            collector.step(1).unwrap();
+        }
+
+        let values = (0..10_u64)
+            .map(|index| (Key::Hash([index as u8; 32]), test_value(7)))
+            .collect();
+        let new_root = global_state
+            .commit_values(roots[2], values, Default::default())
+            .unwrap();
+        let progress = collector.run(&[], 1).unwrap();
+
+        assert_eq!(progress.phase, GcPhase::Idle);
+        assert_readable(&global_state, roots[2], 2);
+        assert_readable(&global_state, new_root, 7);
+        assert!(global_state.checkout(roots[1]).unwrap().is_none());
+    }
+}
@@ -0,0 +1,472 @@
+//! Export and import of global state snapshots.
+//!
+//! A snapshot holds every trie reachable from a single state root, so a node can bootstrap its
+//! global state from a file rather than fetching it trie by trie from its peers.  The tries are
+//! written in post-order, i.e. every trie follows all of its descendants, and are grouped into
+//! chunks each followed by its checksum.
+//!
+//! When importing, a trie is only persisted once all of its children are, exactly like tries
+//! fetched from peers, so the trie store never holds a trie with missing descendants.  As tries
+//! are addressed by their hash, a snapshot whose state root ends up persisted is hence complete
+//! and correct, and only the state root itself needs to be trusted.
+//!
+//! The layout of a snapshot is:
+//! * the magic bytes [`SNAPSHOT_MAGIC`] and the format version, as a little-endian `u32`,
+//! * the state root hash and the length-prefixed metadata of the snapshot,
+//! * any number of chunks, each made of a [`CHUNK_TAG`] byte, the length-prefixed chunk body,
+//!   holding length-prefixed tries, and the hash of the chunk body,
+//! * an [`END_TAG`] byte followed by the total numbers of tries and chunks, as little-endian
+//!   `u64`s.
+
+use std::{
+    convert::TryFrom,
+    io::{self, Read, Write},
+};
+
+use lmdb::RwTransaction;
+use thiserror::Error;
+use tracing::info;
+
+use casper_types::{
+    bytesrepr::{self, Bytes, FromBytes},
+    Digest, Key, StoredValue,
+};
+
+use crate::global_state::{
+    error,
+    state::lmdb::LmdbGlobalState,
+    store::Store,
+    transaction_source::{Readable, Transaction, TransactionSource},
+    trie::{LazilyDeserializedTrie, Trie},
+    trie_store::{
+        lmdb::LmdbTrieStore,
+        operations::{missing_children, put_trie},
+    },
+};
+
+/// The magic bytes a snapshot starts with.
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"CSPRGSS\0";
+/// The version of the snapshot format.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+/// The tag of a chunk record.
+pub const CHUNK_TAG: u8 = 0;
+/// The tag of the record ending a snapshot.
+pub const END_TAG: u8 = 1;
+
+/// The size of the chunk body after which a chunk is written out.
+const CHUNK_SIZE: usize = 8 * 1024 * 1024;
+/// The largest chunk body accepted when importing a snapshot.
+const MAX_CHUNK_SIZE: usize = 256 * 1024 * 1024;
+/// The largest metadata accepted when importing a snapshot.
+const MAX_METADATA_SIZE: usize = 1024 * 1024;
+
+/// Error exporting or importing a snapshot.
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    /// Error reading or writing the snapshot.
+    #[error("snapshot i/o error: {0}")]
+    Io(#[from] io::Error),
+
+    /// Error accessing the global state.
+    #[error(transparent)]
+    GlobalState(#[from] error::Error),
+
+    /// The snapshot does not start with the expected magic bytes.
+    #[error("not a global state snapshot")]
+    InvalidMagic,
+
+    /// The snapshot was written with an unsupported version of the format.
+    #[error("unsupported snapshot format version {0}")]
+    UnsupportedVersion(u32),
+
+    /// The state root to export is not in the trie store.
+    #[error("state root {0} not found")]
+    RootNotFound(Digest),
+
+    /// A trie reachable from the exported state root is not in the trie store.
+    #[error("trie {0} not found")]
+    TrieNotFound(Digest),
+
+    /// The snapshot contains an unknown record.
+    #[error("invalid snapshot record tag {0}")]
+    InvalidTag(u8),
+
+    /// A length in the snapshot exceeds the accepted maximum.
+    #[error("snapshot {what} too large: {size} bytes")]
+    TooLarge {
+        /// What is too large.
+        what: &'static str,
+        /// The size read from the snapshot.
+        size: usize,
+    },
+
+    /// The body of a chunk doesn't match its checksum.
+    #[error("checksum mismatch in snapshot chunk {0}")]
+    ChecksumMismatch(u64),
+
+    /// A trie precedes some of its children in the snapshot, or they are missing altogether.
+    #[error("trie {0} precedes some of its children in the snapshot")]
+    MissingChildren(Digest),
+
+    /// The numbers of tries and chunks recorded at the end of the snapshot don't match the ones
+    /// read.
+    #[error(
+        "snapshot ends after {expected_tries} tries in {expected_chunks} chunks, but \
+        {read_tries} tries in {read_chunks} chunks were read"
+    )]
+    CountMismatch {
+        /// The number of tries recorded.
+        expected_tries: u64,
+        /// The number of chunks recorded.
+        expected_chunks: u64,
+        /// The number of tries read.
+        read_tries: u64,
+        /// The number of chunks read.
+        read_chunks: u64,
+    },
+
+    /// The state root of the snapshot wasn't persisted by the import.
+    #[error("snapshot does not contain its state root {0}")]
+    Incomplete(Digest),
+}
+
+impl From<bytesrepr::Error> for SnapshotError {
+    fn from(error: bytesrepr::Error) -> Self {
+        SnapshotError::GlobalState(error.into())
+    }
+}
+
+impl From<lmdb::Error> for SnapshotError {
+    fn from(error: lmdb::Error) -> Self {
+        SnapshotError::GlobalState(error.into())
+    }
+}
+
+/// The header of a snapshot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotHeader {
+    /// The state root hash of the global state held in the snapshot.
+    pub state_root_hash: Digest,
+    /// Arbitrary metadata describing the snapshot.
+    pub metadata: Bytes,
+}
+
+/// The size of an exported or imported snapshot.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SnapshotStats {
+    /// The number of tries.
+    pub tries: u64,
+    /// The number of chunks.
+    pub chunks: u64,
+}
+
+/// A step of the post-order traversal of a trie.
+enum Visit {
+    /// The trie with the given hash has to be read, and its children visited.
+    Enter(Digest),
+    /// All the children of the given trie were written, so the trie can be written.
+    Exit(Bytes),
+}
+
+/// Exports every trie reachable from `state_root_hash` to `writer`.
+///
+/// The global state is read within a single transaction, so tries written concurrently don't
+/// affect the snapshot.
+pub fn export_snapshot<W: Write>(
+    global_state: &LmdbGlobalState,
+    state_root_hash: Digest,
+    metadata: &[u8],
+    mut writer: W,
+) -> Result<SnapshotStats, SnapshotError> {
+    let txn = global_state.environment.create_read_txn()?;
+    let trie_store = &*global_state.trie_store;
+    if read_trie(trie_store, &txn, &state_root_hash)?.is_none() {
+        return Err(SnapshotError::RootNotFound(state_root_hash));
+    }
+
+    writer.write_all(&SNAPSHOT_MAGIC)?;
+    writer.write_all(&SNAPSHOT_FORMAT_VERSION.to_le_bytes())?;
+    writer.write_all(state_root_hash.as_ref())?;
+    write_length_prefixed(&mut writer, metadata)?;
+
+    let mut stats = SnapshotStats::default();
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+    let mut stack = vec![Visit::Enter(state_root_hash)];
+    while let Some(visit) = stack.pop() {
+        match visit {
+            Visit::Enter(trie_hash) => {
+                let trie_bytes = read_trie(trie_store, &txn, &trie_hash)?
+                    .ok_or(SnapshotError::TrieNotFound(trie_hash))?;
+                let trie: LazilyDeserializedTrie = bytesrepr::deserialize_from_slice(&trie_bytes)?;
+                stack.push(Visit::Exit(trie_bytes));
+                stack.extend(trie.iter_children().map(Visit::Enter));
+            }
+            Visit::Exit(trie_bytes) => {
+                write_length_prefixed(&mut chunk, &trie_bytes)?;
+                stats.tries += 1;
+                if chunk.len() >= CHUNK_SIZE {
+                    write_chunk(&mut writer, &chunk)?;
+                    stats.chunks += 1;
+                    chunk.clear();
+                }
+            }
+        }
+    }
+    if !chunk.is_empty() {
+        write_chunk(&mut writer, &chunk)?;
+        stats.chunks += 1;
+    }
+    txn.commit()?;
+
+    writer.write_all(&[END_TAG])?;
+    writer.write_all(&stats.tries.to_le_bytes())?;
+    writer.write_all(&stats.chunks.to_le_bytes())?;
+    writer.flush()?;
+    info!(%state_root_hash, tries = stats.tries, chunks = stats.chunks, "exported snapshot");
+    Ok(stats)
+}
+
+/// A reader of a snapshot, giving access to its header before importing it.
+pub struct SnapshotReader<R> {
+    reader: R,
+    header: SnapshotHeader,
+}
+
+impl<R: Read> SnapshotReader<R> {
+    /// Reads the header of the snapshot from `reader`.
+    pub fn new(mut reader: R) -> Result<Self, SnapshotError> {
+        let mut magic = [0; SNAPSHOT_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidMagic);
+        }
+        let version = u32::from_le_bytes(read_array(&mut reader)?);
+        if version != SNAPSHOT_FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let state_root_hash = Digest::from(read_array::<_, { Digest::LENGTH }>(&mut reader)?);
+        let metadata = read_length_prefixed(&mut reader, "metadata", MAX_METADATA_SIZE)?;
+        Ok(SnapshotReader {
+            reader,
+            header: SnapshotHeader {
+                state_root_hash,
+                metadata: Bytes::from(metadata),
+            },
+        })
+    }
+
+    /// Returns the header of the snapshot.
+    pub fn header(&self) -> &SnapshotHeader {
+        &self.header
+    }
+
+    /// Imports the tries of the snapshot into the given global state.
+    ///
+    /// The tries of each chunk are persisted within a single transaction, once the checksum of the
+    /// chunk is verified.  An error is returned if the state root of the snapshot isn't persisted
+    /// by the time the snapshot ends; the tries persisted until then are complete subtries, so
+    /// they can be kept.
+    pub fn import(
+        mut self,
+        global_state: &LmdbGlobalState,
+    ) -> Result<SnapshotStats, SnapshotError> {
+        let state_root_hash = self.header.state_root_hash;
+        let trie_store = &*global_state.trie_store;
+        let mut stats = SnapshotStats::default();
+        let mut last_trie_hash = None;
+        loop {
+            let [tag] = read_array(&mut self.reader)?;
+            match tag {
+                CHUNK_TAG => {
+                    let chunk = read_length_prefixed(&mut self.reader, "chunk", MAX_CHUNK_SIZE)?;
+                    let checksum = Digest::from(read_array(&mut self.reader)?);
+                    if Digest::hash(&chunk) != checksum {
+                        return Err(SnapshotError::ChecksumMismatch(stats.chunks));
+                    }
+
+                    let mut txn = global_state.environment.create_read_write_txn()?;
+                    let mut remainder = chunk.as_slice();
+                    while !remainder.is_empty() {
+                        let (trie_bytes, rem) = Bytes::from_bytes(remainder)?;
+                        remainder = rem;
+                        last_trie_hash = Some(import_trie(&mut txn, trie_store, &trie_bytes)?);
+                        stats.tries += 1;
+                    }
+                    txn.commit()?;
+                    stats.chunks += 1;
+                }
+                END_TAG => {
+                    let expected_tries = u64::from_le_bytes(read_array(&mut self.reader)?);
+                    let expected_chunks = u64::from_le_bytes(read_array(&mut self.reader)?);
+                    if (expected_tries, expected_chunks) != (stats.tries, stats.chunks) {
+                        return Err(SnapshotError::CountMismatch {
+                            expected_tries,
+                            expected_chunks,
+                            read_tries: stats.tries,
+                            read_chunks: stats.chunks,
+                        });
+                    }
+                    break;
+                }
+                tag => return Err(SnapshotError::InvalidTag(tag)),
+            }
+        }
+
+        // The state root is the last trie of a snapshot, and it was only persisted along with all
+        // its descendants.
+        if last_trie_hash != Some(state_root_hash) {
+            return Err(SnapshotError::Incomplete(state_root_hash));
+        }
+        info!(%state_root_hash, tries = stats.tries, chunks = stats.chunks, "imported snapshot");
+        Ok(stats)
+    }
+}
+
+/// Persists a trie, provided all of its children are already persisted.
+fn import_trie(
+    txn: &mut RwTransaction,
+    trie_store: &LmdbTrieStore,
+    trie_bytes: &[u8],
+) -> Result<Digest, SnapshotError> {
+    let missing =
+        missing_children::<Key, StoredValue, _, _, error::Error>(&*txn, trie_store, trie_bytes)?;
+    if !missing.is_empty() {
+        let trie_hash = Digest::hash_into_chunks_if_necessary(trie_bytes);
+        return Err(SnapshotError::MissingChildren(trie_hash));
+    }
+    Ok(put_trie::<Key, StoredValue, _, _, error::Error>(
+        txn, trie_store, trie_bytes,
+    )?)
+}
+
+fn read_trie<T>(
+    trie_store: &LmdbTrieStore,
+    txn: &T,
+    trie_hash: &Digest,
+) -> Result<Option<Bytes>, SnapshotError>
+where
+    T: Readable<Handle = lmdb::Database>,
+    error::Error: From<T::Error>,
+{
+    Ok(Store::<Digest, Trie<Key, StoredValue>>::get_raw(
+        trie_store, txn, trie_hash,
+    )?)
+}
+
+fn write_chunk<W: Write>(writer: &mut W, chunk: &[u8]) -> io::Result<()> {
+    writer.write_all(&[CHUNK_TAG])?;
+    write_length_prefixed(writer, chunk)?;
+    writer.write_all(Digest::hash(chunk).as_ref())
+}
+
+fn write_length_prefixed<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    let length = u32::try_from(bytes.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "snapshot record too large"))?;
+    writer.write_all(&length.to_le_bytes())?;
+    writer.write_all(bytes)
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> io::Result<[u8; N]> {
+    let mut array = [0; N];
+    reader.read_exact(&mut array)?;
+    Ok(array)
+}
+
+fn read_length_prefixed<R: Read>(
+    reader: &mut R,
+    what: &'static str,
+    max_size: usize,
+) -> Result<Vec<u8>, SnapshotError> {
+    let size = u32::from_le_bytes(read_array(reader)?) as usize;
+    if size > max_size {
+        return Err(SnapshotError::TooLarge { what, size });
+    }
+    let mut bytes = vec![0; size];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::CLValue;
+
+    use super::*;
+    use crate::global_state::state::{
+        lmdb::make_temporary_global_state, CommitProvider, StateProvider, StateReader,
+    };
+
+    fn test_pairs(count: u64) -> Vec<(Key, StoredValue)> {
+        (0..count)
+            .map(|index| {
+                let mut hash = [0; 32];
+                hash[..8].copy_from_slice(&index.to_le_bytes());
+                (
+                    Key::Hash(hash),
+                    StoredValue::CLValue(CLValue::from_t(index).unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    fn export(global_state: &LmdbGlobalState, root: Digest) -> Vec<u8> {
+        let mut snapshot = vec![];
+        export_snapshot(global_state, root, b"metadata", &mut snapshot).unwrap();
+        snapshot
+    }
+
+    #[test]
+    fn should_import_exported_snapshot() {
+        let pairs = test_pairs(1_000);
+        let (source, root, _source_dir) = make_temporary_global_state(pairs.clone());
+        // Tries only reachable from the previous root are not exported.
+        let root = source
+            .commit_values(root, pairs[..10].to_vec(), Default::default())
+            .unwrap();
+        let snapshot = export(&source, root);
+
+        let (target, _, _target_dir) = make_temporary_global_state(vec![]);
+        let reader = SnapshotReader::new(snapshot.as_slice()).unwrap();
+        assert_eq!(
+            reader.header(),
+            &SnapshotHeader {
+                state_root_hash: root,
+                metadata: Bytes::from(b"metadata".to_vec()),
+            }
+        );
+        let stats = reader.import(&target).unwrap();
+        assert_eq!(stats.chunks, 1);
+
+        let view = target.checkout(root).unwrap().expect("should have root");
+        for (key, value) in pairs {
+            assert_eq!(view.read(&key).unwrap(), Some(value));
+        }
+    }
+
+    #[test]
+    fn should_reject_corrupted_chunk() {
+        let (source, root, _source_dir) = make_temporary_global_state(test_pairs(10));
+        let mut snapshot = export(&source, root);
+        // Flip a bit in the last trie of the chunk, just before the end record.
+        let index = snapshot.len() - 1 - 2 * 8 - Digest::LENGTH - 1;
+        snapshot[index] ^= 1;
+
+        let (target, _, _target_dir) = make_temporary_global_state(vec![]);
+        let reader = SnapshotReader::new(snapshot.as_slice()).unwrap();
+        assert!(matches!(
+            reader.import(&target),
+            Err(SnapshotError::ChecksumMismatch(0))
+        ));
+        assert!(target.checkout(root).unwrap().is_none());
+    }
+
+    #[test]
+    fn should_reject_truncated_snapshot() {
+        let (source, root, _source_dir) = make_temporary_global_state(test_pairs(10));
+        let snapshot = export(&source, root);
+
+        let (target, _, _target_dir) = make_temporary_global_state(vec![]);
+        let truncated = &snapshot[..snapshot.len() - 1];
+        let reader = SnapshotReader::new(truncated).unwrap();
+        assert!(matches!(reader.import(&target), Err(SnapshotError::Io(_))));
+    }
+}
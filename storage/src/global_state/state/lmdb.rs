@@ -15,8 +15,9 @@ use casper_types::{
 use super::CommitError;
 use crate::{
     data_access_layer::{
-        DataAccessLayer, FlushRequest, FlushResult, PutTrieRequest, PutTrieResult, TrieElement,
-        TrieRequest, TrieResult,
+        DataAccessLayer, FlushRequest, FlushResult, MultiProofRequest, MultiProofResult,
+        PutTrieRequest, PutTrieResult, StateDiffRequest, StateDiffResult, TrieElement, TrieRequest,
+        TrieResult,
     },
     global_state::{
         error::Error as GlobalStateError,
@@ -30,8 +31,8 @@ use crate::{
         trie_store::{
             lmdb::{LmdbTrieStore, ScratchTrieStore},
             operations::{
-                keys_with_prefix, missing_children, prune, put_trie, read, read_with_proof,
-                ReadResult, TriePruneResult,
+                diff, keys_with_prefix, missing_children, prune, put_trie, read,
+                read_with_multi_proof, read_with_proof, DiffResult, ReadResult, TriePruneResult,
             },
         },
         DEFAULT_ENABLE_ENTITY, DEFAULT_MAX_DB_SIZE, DEFAULT_MAX_QUERY_DEPTH, DEFAULT_MAX_READERS,
@@ -53,6 +54,41 @@ pub struct LmdbGlobalState {
     pub enable_entity: bool,
 }
 
+/// The post state of a scratch global state, held in memory on top of the LMDB global state it
+/// was created from.
+///
+/// Dropping it discards the post state, as none of its tries are written to LMDB.
+pub struct ScratchPostState {
+    /// Root hash of the post state.
+    state_root_hash: Digest,
+    /// Tries of the post state, read through to LMDB for the unchanged ones.
+    trie_store: ScratchTrieStore,
+}
+
+impl ScratchPostState {
+    /// Returns the root hash of the post state.
+    pub fn state_root_hash(&self) -> Digest {
+        self.state_root_hash
+    }
+
+    /// Reads the value under the given key in the post state, along with a proof of it.
+    pub fn read_with_proof(
+        &self,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, GlobalStateError> {
+        match read_with_proof::<Key, StoredValue, _, _, GlobalStateError>(
+            &self.trie_store,
+            &self.trie_store,
+            &self.state_root_hash,
+            key,
+        )? {
+            ReadResult::Found(proof) => Ok(Some(proof)),
+            ReadResult::NotFound => Ok(None),
+            ReadResult::RootNotFound => Err(GlobalStateError::RootNotFound),
+        }
+    }
+}
+
 /// Represents a "view" of global state at a particular root hash.
 pub struct LmdbGlobalStateView {
     /// Environment for LMDB.
@@ -139,6 +175,43 @@ impl LmdbGlobalState {
         Ok(new_state_root)
     }
 
+    /// Computes the post state of a scratch global state on top of `prestate_hash`, in memory
+    /// only: no trie is written to LMDB.
+    pub fn scratch_post_state(
+        &self,
+        prestate_hash: Digest,
+        scratch_global_state: ScratchGlobalState,
+    ) -> Result<ScratchPostState, GlobalStateError> {
+        let (stored_values, keys_to_prune) = scratch_global_state.into_inner();
+        let scratch_trie = self.get_scratch_store();
+        let mut state_root_hash = put_stored_values::<_, _, GlobalStateError>(
+            &scratch_trie,
+            &scratch_trie,
+            prestate_hash,
+            stored_values,
+        )?;
+
+        let mut txn = scratch_trie.create_read_write_txn()?;
+        for key in &keys_to_prune {
+            match prune::<Key, StoredValue, _, _, GlobalStateError>(
+                &mut txn,
+                &scratch_trie,
+                &state_root_hash,
+                key,
+            )? {
+                TriePruneResult::Pruned(new_root) => state_root_hash = new_root,
+                TriePruneResult::MissingKey => continue,
+                TriePruneResult::RootNotFound => return Err(GlobalStateError::RootNotFound),
+                TriePruneResult::Failure(gse) => return Err(gse),
+            }
+        }
+
+        Ok(ScratchPostState {
+            state_root_hash,
+            trie_store: scratch_trie,
+        })
+    }
+
     /// Get a reference to the lmdb global state's environment.
     #[must_use]
     pub fn environment(&self) -> &LmdbEnvironment {
@@ -409,6 +482,63 @@ impl StateProvider for LmdbGlobalState {
         }
     }
 
+    fn state_diff(&self, request: StateDiffRequest) -> StateDiffResult {
+        let txn = match self.environment.create_read_txn() {
+            Ok(ro) => ro,
+            Err(err) => return StateDiffResult::Failure(err.into()),
+        };
+        let result = match diff::<Key, StoredValue, _, _, GlobalStateError>(
+            &txn,
+            &*self.trie_store,
+            &request.from_state_hash(),
+            &request.to_state_hash(),
+            request.start_after(),
+            request.max_entries(),
+        ) {
+            Ok(DiffResult::Found { entries, more }) => {
+                let next_page = if more {
+                    entries.last().map(|entry| *entry.key())
+                } else {
+                    None
+                };
+                StateDiffResult::Success { entries, next_page }
+            }
+            Ok(DiffResult::RootNotFound) => StateDiffResult::RootNotFound,
+            Ok(DiffResult::MissingTrie(digest)) => {
+                StateDiffResult::Failure(GlobalStateError::MissingTrie(digest))
+            }
+            Err(err) => StateDiffResult::Failure(err),
+        };
+        match txn.commit() {
+            Ok(_) => result,
+            Err(err) => StateDiffResult::Failure(err.into()),
+        }
+    }
+
+    fn multi_proof(&self, request: MultiProofRequest) -> MultiProofResult {
+        let txn = match self.environment.create_read_txn() {
+            Ok(ro) => ro,
+            Err(err) => return MultiProofResult::Failure(err.into()),
+        };
+        let result = match read_with_multi_proof::<Key, StoredValue, _, _, GlobalStateError>(
+            &txn,
+            &*self.trie_store,
+            &request.state_hash(),
+            request.keys(),
+        ) {
+            Ok(ReadResult::Found(proof)) => MultiProofResult::Success {
+                proof: Box::new(proof),
+            },
+            Ok(ReadResult::NotFound) => MultiProofResult::ValuesNotFound,
+            Ok(ReadResult::RootNotFound) => MultiProofResult::RootNotFound,
+            Err(err) => MultiProofResult::Failure(err),
+        };
+        match txn.commit() {
+            Ok(_) => result,
+            Err(err) => MultiProofResult::Failure(err.into()),
+        }
+    }
+
     /// Finds all of the keys of missing directly descendant `Trie<K,V>` values.
     fn missing_children(&self, trie_raw: &[u8]) -> Result<Vec<Digest>, GlobalStateError> {
         let txn = self.environment.create_read_txn()?;
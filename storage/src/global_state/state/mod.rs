@@ -66,11 +66,12 @@ use crate::{
         EntryPointResult, EraValidatorsRequest, ExecutionResultsChecksumRequest,
         ExecutionResultsChecksumResult, FeeError, FeeRequest, FeeResult, FlushRequest, FlushResult,
         GenesisRequest, GenesisResult, HandleRefundMode, HandleRefundRequest, HandleRefundResult,
-        InsufficientBalanceHandling, MessageTopicsRequest, MessageTopicsResult, ProofHandling,
-        ProofsResult, ProtocolUpgradeRequest, ProtocolUpgradeResult, PruneRequest, PruneResult,
-        PutTrieRequest, PutTrieResult, QueryRequest, QueryResult, RoundSeigniorageRateRequest,
-        RoundSeigniorageRateResult, SeigniorageRecipientsRequest, SeigniorageRecipientsResult,
-        StepError, StepRequest, StepResult, SystemEntityRegistryPayload,
+        InsufficientBalanceHandling, MessageTopicsRequest, MessageTopicsResult, MultiProofRequest,
+        MultiProofResult, ProofHandling, ProofsResult, ProtocolUpgradeRequest,
+        ProtocolUpgradeResult, PruneRequest, PruneResult, PutTrieRequest, PutTrieResult,
+        QueryRequest, QueryResult, RoundSeigniorageRateRequest, RoundSeigniorageRateResult,
+        SeigniorageRecipientsRequest, SeigniorageRecipientsResult, StateDiffRequest,
+        StateDiffResult, StepError, StepRequest, StepResult, SystemEntityRegistryPayload,
         SystemEntityRegistryRequest, SystemEntityRegistryResult, SystemEntityRegistrySelector,
         TotalSupplyRequest, TotalSupplyResult, TrieRequest, TrieResult,
         EXECUTION_RESULTS_CHECKSUM_NAME,
@@ -2354,6 +2355,12 @@ pub trait StateProvider: Send + Sync + Sized {
     /// Persists a trie element.
     fn put_trie(&self, request: PutTrieRequest) -> PutTrieResult;
 
+    /// Returns the keys added, removed and modified between two states.
+    fn state_diff(&self, request: StateDiffRequest) -> StateDiffResult;
+
+    /// Reads several entries of the state along with a single proof of all of them.
+    fn multi_proof(&self, request: MultiProofRequest) -> MultiProofResult;
+
     /// Finds all the children of `trie_raw` which aren't present in the state.
     fn missing_children(&self, trie_raw: &[u8]) -> Result<Vec<Digest>, GlobalStateError>;
 
@@ -3,7 +3,7 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
     mem,
     ops::Deref,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
 };
 
 use tracing::{debug, error};
@@ -17,8 +17,8 @@ use casper_types::{
 
 use crate::{
     data_access_layer::{
-        FlushRequest, FlushResult, PutTrieRequest, PutTrieResult, TrieElement, TrieRequest,
-        TrieResult,
+        FlushRequest, FlushResult, MultiProofRequest, MultiProofResult, PutTrieRequest,
+        PutTrieResult, StateDiffRequest, StateDiffResult, TrieElement, TrieRequest, TrieResult,
     },
     global_state::{
         error::Error as GlobalStateError,
@@ -29,7 +29,8 @@ use crate::{
         trie_store::{
             lmdb::LmdbTrieStore,
             operations::{
-                keys_with_prefix, missing_children, put_trie, read, read_with_proof, ReadResult,
+                diff, keys_with_prefix, missing_children, put_trie, read, read_with_multi_proof,
+                read_with_proof, DiffResult, ReadResult,
             },
         },
     },
@@ -39,6 +40,10 @@ use crate::tracking_copy::TrackingCopy;
 
 type SharedCache = Arc<RwLock<Cache>>;
 
+/// The cached keys with a given prefix, and the stored keys with that prefix which are neither
+/// cached nor pruned.
+type PrefixKeys = (Vec<Key>, Vec<Key>);
+
 struct Cache {
     cached_values: HashMap<Key, (bool, StoredValue)>,
     pruned: BTreeSet<Key>,
@@ -164,6 +169,76 @@ impl Cache {
         Ok(())
     }
 
+    /// Reads the value of `key` as a view of the state would, without caching it.
+    fn peek(
+        &self,
+        txn: &lmdb::RoTransaction,
+        trie_store: &LmdbTrieStore,
+        root_hash: &Digest,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, GlobalStateError> {
+        if self.pruned.contains(key) {
+            return Ok(None);
+        }
+        if let Some(value) = self.get(key) {
+            return Ok(Some(value.clone()));
+        }
+        match read::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, GlobalStateError>(
+            txn, trie_store, root_hash, key,
+        )? {
+            ReadResult::Found(value) => Ok(Some(value)),
+            ReadResult::NotFound => Ok(None),
+            ReadResult::RootNotFound => panic!("ScratchGlobalState has invalid root"),
+        }
+    }
+
+    /// Reads the value of `key` a transform committed to the state would be applied to.
+    fn peek_committed(
+        &self,
+        txn: &lmdb::RoTransaction,
+        trie_store: &LmdbTrieStore,
+        state_hash: &Digest,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, GlobalStateError> {
+        if let Some(value) = self.get(key) {
+            return Ok(Some(value.clone()));
+        }
+        match read::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, GlobalStateError>(
+            txn, trie_store, state_hash, key,
+        )? {
+            ReadResult::Found(value) => Ok(Some(value)),
+            ReadResult::NotFound => Ok(None),
+            ReadResult::RootNotFound => {
+                error!(root_hash=?state_hash, "root not found");
+                Err(CommitError::ReadRootNotFound(*state_hash).into())
+            }
+        }
+    }
+
+    /// Returns the cached keys with the given prefix, and the keys with the given prefix stored
+    /// under `root_hash` which are neither cached nor pruned.
+    fn keys_with_prefix(
+        &self,
+        txn: &lmdb::RoTransaction,
+        trie_store: &LmdbTrieStore,
+        root_hash: &Digest,
+        prefix: &[u8],
+    ) -> Result<PrefixKeys, GlobalStateError> {
+        let cached_keys = self.cached_keys.keys_with_prefix(prefix);
+        let mut stored_keys = Vec::new();
+        let keys_iter =
+            keys_with_prefix::<Key, StoredValue, _, _>(txn, trie_store, root_hash, prefix);
+        for result in keys_iter {
+            let key = result?;
+            // If the key is pruned then we won't return it. If the key is already cached, then it
+            // is part of the cached keys so we don't add it again to avoid duplicates.
+            if !self.pruned.contains(&key) && !self.cached_values.contains_key(&key) {
+                stored_keys.push(key);
+            }
+        }
+        Ok((cached_keys, stored_keys))
+    }
+
     fn prune(&mut self, key: Key) -> Result<(), bytesrepr::Error> {
         self.cached_values.remove(&key);
         self.cached_keys.remove(&key.to_bytes()?);
@@ -206,10 +281,42 @@ impl Cache {
     }
 }
 
+/// The state shared by a fork of a scratch global state and the views checked out from it.
+struct Fork {
+    /// The cache of the forked state, which is only read by the fork.
+    base: SharedCache,
+    /// The accesses of the fork to the forked state.
+    log: Mutex<ForkLog>,
+}
+
+/// A change committed to a fork, to be committed again to the forked state on merge.
+enum ForkCommit {
+    Effects(Digest, Effects),
+    Values(Digest, Vec<(Key, StoredValue)>, BTreeSet<Key>),
+}
+
+/// The values a fork read from the forked state, each as first observed, and the changes
+/// committed to the fork.
+#[derive(Default)]
+struct ForkLog {
+    /// The values read by views of the fork.
+    reads: BTreeMap<(Digest, Key), Option<StoredValue>>,
+    /// The values transforms committed to the fork were applied to.
+    commit_reads: BTreeMap<(Digest, Key), Option<StoredValue>>,
+    /// The cached and stored keys listed by prefix.
+    prefix_reads: BTreeMap<(Digest, Vec<u8>), PrefixKeys>,
+    /// Whether a proof was read, which is only possible while nothing is cached.
+    requires_empty_cache: bool,
+    /// The changes committed to the fork, in order.
+    commits: Vec<ForkCommit>,
+}
+
 /// Global state implemented against LMDB as a backing data store.
 pub struct ScratchGlobalState {
     /// Underlying, cached stored values.
     cache: SharedCache,
+    /// The forked state, if this state is a fork.
+    fork: Option<Arc<Fork>>,
     /// Environment for LMDB.
     pub(crate) environment: Arc<LmdbEnvironment>,
     /// Trie store held within LMDB.
@@ -225,6 +332,7 @@ pub struct ScratchGlobalState {
 /// Represents a "view" of global state at a particular root hash.
 pub struct ScratchGlobalStateView {
     cache: SharedCache,
+    fork: Option<Arc<Fork>>,
     /// Environment for LMDB.
     pub(crate) environment: Arc<LmdbEnvironment>,
     /// Trie store held within LMDB.
@@ -237,6 +345,10 @@ impl ScratchGlobalStateView {
     /// Returns true if the pruned and cached values are both empty.
     pub fn is_empty(&self) -> bool {
         self.cache.read().unwrap().is_empty()
+            && self
+                .fork
+                .as_ref()
+                .is_none_or(|fork| fork.base.read().unwrap().is_empty())
     }
 }
 
@@ -252,6 +364,7 @@ impl ScratchGlobalState {
     ) -> Self {
         ScratchGlobalState {
             cache: Arc::new(RwLock::new(Cache::new())),
+            fork: None,
             environment,
             trie_store,
             empty_root_hash,
@@ -265,6 +378,122 @@ impl ScratchGlobalState {
         let cache = mem::replace(&mut *self.cache.write().unwrap(), Cache::new());
         cache.into_dirty_writes()
     }
+
+    /// Creates a fork of this state.
+    ///
+    /// The fork reads through to the values cached by this state, while the effects committed to
+    /// the fork are only visible to the fork.  The values the fork reads from this state are
+    /// recorded, so that the fork can later be merged back by [`ScratchGlobalState::merge`].  This
+    /// state must not be changed while the fork is in use.
+    pub fn fork(&self) -> ScratchGlobalState {
+        debug_assert!(self.fork.is_none(), "a fork cannot be forked");
+        ScratchGlobalState {
+            cache: Arc::new(RwLock::new(Cache::new())),
+            fork: Some(Arc::new(Fork {
+                base: Arc::clone(&self.cache),
+                log: Mutex::default(),
+            })),
+            environment: Arc::clone(&self.environment),
+            trie_store: Arc::clone(&self.trie_store),
+            empty_root_hash: self.empty_root_hash,
+            max_query_depth: self.max_query_depth,
+            enable_addressable_entity: self.enable_addressable_entity,
+        }
+    }
+
+    /// Merges a fork of this state back into it.
+    ///
+    /// If every value the fork read from this state is still the same, the changes committed to
+    /// the fork are committed to this state in the same order, leaving it as if they had been
+    /// committed to it in the first place, and `true` is returned.  Otherwise this state is left
+    /// unchanged and `false` is returned.
+    pub fn merge(&self, fork: &ScratchGlobalState) -> Result<bool, GlobalStateError> {
+        let log = match &fork.fork {
+            Some(fork) if Arc::ptr_eq(&fork.base, &self.cache) => {
+                mem::take(&mut *fork.log.lock().expect("poisoned fork log mutex"))
+            }
+            _ => return Ok(false),
+        };
+
+        {
+            let txn = self.environment.create_read_txn()?;
+            let cache = self.cache.read().unwrap();
+            if log.requires_empty_cache && !cache.is_empty() {
+                return Ok(false);
+            }
+            for ((root_hash, key), value) in &log.reads {
+                if cache.peek(&txn, &self.trie_store, root_hash, key)? != *value {
+                    return Ok(false);
+                }
+            }
+            for ((state_hash, key), value) in &log.commit_reads {
+                if cache.peek_committed(&txn, &self.trie_store, state_hash, key)? != *value {
+                    return Ok(false);
+                }
+            }
+            for ((root_hash, prefix), keys) in &log.prefix_reads {
+                if cache.keys_with_prefix(&txn, &self.trie_store, root_hash, prefix)? != *keys {
+                    return Ok(false);
+                }
+            }
+            txn.commit()?;
+        }
+
+        // The values the fork read are cached as if they had been read through views of this
+        // state, as they would have been without the fork.
+        {
+            let mut cache = self.cache.write().unwrap();
+            for ((_, key), value) in log.reads {
+                if let Some(value) = value {
+                    cache.insert_read(key, value)?;
+                }
+            }
+        }
+
+        for commit in log.commits {
+            match commit {
+                ForkCommit::Effects(state_hash, effects) => {
+                    self.commit_effects(state_hash, effects)?;
+                }
+                ForkCommit::Values(state_hash, write_values, prune_keys) => {
+                    self.commit_values(state_hash, write_values, prune_keys)?;
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns the value a transform committed to this state would be applied to, if it is
+    /// cached, or if this state is a fork and the value is not changed by the fork.
+    fn cached_value(
+        &self,
+        txn: &lmdb::RoTransaction,
+        state_hash: &Digest,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, GlobalStateError> {
+        let cache = self.cache.read().unwrap();
+        if let Some(value) = cache.get(key) {
+            return Ok(Some(value.clone()));
+        }
+        match &self.fork {
+            Some(fork) if !cache.pruned.contains(key) => {
+                let value = fork.base.read().unwrap().peek_committed(
+                    txn,
+                    &self.trie_store,
+                    state_hash,
+                    key,
+                )?;
+                fork.log
+                    .lock()
+                    .expect("poisoned fork log mutex")
+                    .commit_reads
+                    .entry((*state_hash, *key))
+                    .or_insert_with(|| value.clone());
+                Ok(value)
+            }
+            _ => Ok(None),
+        }
+    }
 }
 
 impl StateReader<Key, StoredValue> for ScratchGlobalStateView {
@@ -281,6 +510,27 @@ impl StateReader<Key, StoredValue> for ScratchGlobalStateView {
             }
         }
         let txn = self.environment.create_read_txn()?;
+        if let Some(fork) = &self.fork {
+            let value =
+                fork.base
+                    .read()
+                    .unwrap()
+                    .peek(&txn, &self.trie_store, &self.root_hash, key)?;
+            fork.log
+                .lock()
+                .expect("poisoned fork log mutex")
+                .reads
+                .entry((self.root_hash, *key))
+                .or_insert_with(|| value.clone());
+            if let Some(value) = &value {
+                self.cache
+                    .write()
+                    .expect("poisoned scratch cache lock")
+                    .insert_read(*key, value.clone())?;
+            }
+            txn.commit()?;
+            return Ok(value);
+        }
         let ret = match read::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
             &txn,
             self.trie_store.deref(),
@@ -309,6 +559,12 @@ impl StateReader<Key, StoredValue> for ScratchGlobalStateView {
         if !self.is_empty() {
             return Err(Self::Error::CannotProvideProofsOverCachedData);
         }
+        if let Some(fork) = &self.fork {
+            fork.log
+                .lock()
+                .expect("poisoned fork log mutex")
+                .requires_empty_cache = true;
+        }
 
         let txn = self.environment.create_read_txn()?;
         let ret = match read_with_proof::<
@@ -328,31 +584,45 @@ impl StateReader<Key, StoredValue> for ScratchGlobalStateView {
     }
 
     fn keys_with_prefix(&self, prefix: &[u8]) -> Result<Vec<Key>, Self::Error> {
-        let mut ret = Vec::new();
-        let cache = self.cache.read().expect("poisoned scratch cache mutex");
-        let cached_keys = cache.cached_keys.keys_with_prefix(prefix);
-        ret.extend(cached_keys);
-
         let txn = self.environment.create_read_txn()?;
-        let keys_iter = keys_with_prefix::<Key, StoredValue, _, _>(
-            &txn,
-            self.trie_store.deref(),
-            &self.root_hash,
-            prefix,
-        );
-        for result in keys_iter {
-            match result {
-                Ok(key) => {
-                    // If the key is pruned then we won't return it. If the key is already cached,
-                    // then it would have been picked up by the code above so we don't add it again
-                    // to avoid duplicates.
-                    if !cache.pruned.contains(&key) && !cache.cached_values.contains_key(&key) {
-                        ret.push(key);
+        let cache = self.cache.read().expect("poisoned scratch cache mutex");
+        let ret = match &self.fork {
+            None => {
+                let (mut ret, stored_keys) =
+                    cache.keys_with_prefix(&txn, &self.trie_store, &self.root_hash, prefix)?;
+                ret.extend(stored_keys);
+                ret
+            }
+            Some(fork) => {
+                let (base_cached_keys, base_stored_keys) = fork
+                    .base
+                    .read()
+                    .unwrap()
+                    .keys_with_prefix(&txn, &self.trie_store, &self.root_hash, prefix)?;
+                fork.log
+                    .lock()
+                    .expect("poisoned fork log mutex")
+                    .prefix_reads
+                    .entry((self.root_hash, prefix.to_vec()))
+                    .or_insert_with(|| (base_cached_keys.clone(), base_stored_keys.clone()));
+                // The keys are listed in the order the forked state would list them once the
+                // changes of the fork are committed to it.
+                let mut cached_keys = CacheTrie::new();
+                for key in base_cached_keys
+                    .into_iter()
+                    .chain(cache.cached_keys.keys_with_prefix(prefix))
+                {
+                    if !cache.pruned.contains(&key) {
+                        cached_keys.insert(&key.to_bytes()?, key);
                     }
                 }
-                Err(error) => return Err(error),
+                let mut ret = cached_keys.keys_with_prefix(prefix);
+                ret.extend(base_stored_keys.into_iter().filter(|key| {
+                    !cache.pruned.contains(key) && !cache.cached_values.contains_key(key)
+                }));
+                ret
             }
-        }
+        };
         txn.commit()?;
         Ok(ret)
     }
@@ -366,9 +636,21 @@ impl CommitProvider for ScratchGlobalState {
         state_hash: Digest,
         effects: Effects,
     ) -> Result<Digest, GlobalStateError> {
+        if let Some(fork) = &self.fork {
+            fork.log
+                .lock()
+                .expect("poisoned fork log mutex")
+                .commits
+                .push(ForkCommit::Effects(state_hash, effects.clone()));
+        }
         let txn = self.environment.create_read_txn()?;
         for (key, kind) in effects.value().into_iter().map(TransformV2::destructure) {
-            let cached_value = self.cache.read().unwrap().get(&key).cloned();
+            let cached_value = match kind {
+                TransformKindV2::Identity | TransformKindV2::Write(_) => {
+                    self.cache.read().unwrap().get(&key).cloned()
+                }
+                _ => self.cached_value(&txn, &state_hash, &key)?,
+            };
             let instruction = match (cached_value, kind) {
                 (_, TransformKindV2::Identity) => {
                     // effectively a noop.
@@ -439,6 +721,17 @@ impl CommitProvider for ScratchGlobalState {
         write_values: Vec<(Key, StoredValue)>,
         prune_keys: BTreeSet<Key>,
     ) -> Result<Digest, GlobalStateError> {
+        if let Some(fork) = &self.fork {
+            fork.log
+                .lock()
+                .expect("poisoned fork log mutex")
+                .commits
+                .push(ForkCommit::Values(
+                    state_hash,
+                    write_values.clone(),
+                    prune_keys.clone(),
+                ));
+        }
         let mut cache = self.cache.write().unwrap();
         for (key, value) in write_values {
             cache.insert_write(key, value)?;
@@ -489,6 +782,7 @@ impl StateProvider for ScratchGlobalState {
         let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_hash)?;
         let maybe_state = maybe_root.map(|_| ScratchGlobalStateView {
             cache: Arc::clone(&self.cache),
+            fork: self.fork.clone(),
             environment: Arc::clone(&self.environment),
             trie_store: Arc::clone(&self.trie_store),
             root_hash: state_hash,
@@ -571,6 +865,63 @@ impl StateProvider for ScratchGlobalState {
         }
     }
 
+    fn state_diff(&self, request: StateDiffRequest) -> StateDiffResult {
+        let txn = match self.environment.create_read_txn() {
+            Ok(ro) => ro,
+            Err(err) => return StateDiffResult::Failure(err.into()),
+        };
+        let result = match diff::<Key, StoredValue, _, _, GlobalStateError>(
+            &txn,
+            &*self.trie_store,
+            &request.from_state_hash(),
+            &request.to_state_hash(),
+            request.start_after(),
+            request.max_entries(),
+        ) {
+            Ok(DiffResult::Found { entries, more }) => {
+                let next_page = if more {
+                    entries.last().map(|entry| *entry.key())
+                } else {
+                    None
+                };
+                StateDiffResult::Success { entries, next_page }
+            }
+            Ok(DiffResult::RootNotFound) => StateDiffResult::RootNotFound,
+            Ok(DiffResult::MissingTrie(digest)) => {
+                StateDiffResult::Failure(GlobalStateError::MissingTrie(digest))
+            }
+            Err(err) => StateDiffResult::Failure(err),
+        };
+        match txn.commit() {
+            Ok(_) => result,
+            Err(err) => StateDiffResult::Failure(err.into()),
+        }
+    }
+
+    fn multi_proof(&self, request: MultiProofRequest) -> MultiProofResult {
+        let txn = match self.environment.create_read_txn() {
+            Ok(ro) => ro,
+            Err(err) => return MultiProofResult::Failure(err.into()),
+        };
+        let result = match read_with_multi_proof::<Key, StoredValue, _, _, GlobalStateError>(
+            &txn,
+            &*self.trie_store,
+            &request.state_hash(),
+            request.keys(),
+        ) {
+            Ok(ReadResult::Found(proof)) => MultiProofResult::Success {
+                proof: Box::new(proof),
+            },
+            Ok(ReadResult::NotFound) => MultiProofResult::ValuesNotFound,
+            Ok(ReadResult::RootNotFound) => MultiProofResult::RootNotFound,
+            Err(err) => MultiProofResult::Failure(err),
+        };
+        match txn.commit() {
+            Ok(_) => result,
+            Err(err) => MultiProofResult::Failure(err.into()),
+        }
+    }
+
     /// Finds all of the keys of missing directly descendant `Trie<K,V>` values
     fn missing_children(&self, trie_raw: &[u8]) -> Result<Vec<Digest>, GlobalStateError> {
         let txn = self.environment.create_read_txn()?;
@@ -764,6 +1115,38 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn scratch_post_state_is_not_written() {
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let TestState { state, root_hash } = create_test_state();
+
+        let effects = {
+            let mut tmp = Effects::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                let transform = TransformV2::new(*key, TransformKindV2::Write(value.to_owned()));
+                tmp.push(transform);
+            }
+            tmp
+        };
+
+        let scratch = state.create_scratch();
+        scratch.commit_effects(root_hash, effects.clone()).unwrap();
+        let post_state = state.scratch_post_state(root_hash, scratch).unwrap();
+        let post_state_hash = post_state.state_root_hash();
+
+        assert!(
+            state.checkout(post_state_hash).unwrap().is_none(),
+            "the scratch post state should not be written to the underlying lmdb state"
+        );
+        let TestPair { key, value } = &test_pairs_updated[2];
+        let proof = post_state.read_with_proof(key).unwrap().unwrap();
+        assert_eq!(proof.value(), value);
+
+        let lmdb_hash = state.commit_effects(root_hash, effects).unwrap();
+        assert_eq!(post_state_hash, lmdb_hash);
+    }
+
     #[test]
     fn commit_updates_state_with_add() {
         let test_pairs_updated = create_test_pairs_updated();
@@ -850,6 +1233,93 @@ pub(crate) mod tests {
         );
     }
 
+    fn write_effects(pairs: &[TestPair]) -> Effects {
+        let mut effects = Effects::new();
+        for TestPair { key, value } in pairs {
+            effects.push(TransformV2::new(
+                *key,
+                TransformKindV2::Write(value.to_owned()),
+            ));
+        }
+        effects
+    }
+
+    #[test]
+    fn merged_fork_matches_direct_commit() {
+        let test_pairs_updated = create_test_pairs_updated();
+        let TestState { state, root_hash } = create_test_state();
+
+        let scratch = state.create_scratch();
+        let forked = state.create_scratch();
+        for scratch in [&scratch, &forked] {
+            scratch
+                .commit_effects(root_hash, write_effects(&test_pairs_updated[..1]))
+                .unwrap();
+        }
+
+        // Read a value, list keys and apply an add transform on top of the previous writes.
+        let changes = |scratch: &ScratchGlobalState| {
+            let checkout = scratch.checkout(root_hash).unwrap().unwrap();
+            let value = checkout.read(&test_pairs_updated[1].key).unwrap();
+            let keys = checkout.keys_with_prefix(&[]).unwrap();
+            scratch
+                .commit_effects(root_hash, write_effects(&test_pairs_updated[2..]))
+                .unwrap();
+            scratch
+                .commit_effects(root_hash, create_test_transforms())
+                .unwrap();
+            (value, keys, checkout.keys_with_prefix(&[]).unwrap())
+        };
+
+        let fork = forked.fork();
+        let fork_changes = changes(&fork);
+        assert!(
+            forked
+                .checkout(root_hash)
+                .unwrap()
+                .unwrap()
+                .read(&test_pairs_updated[2].key)
+                .unwrap()
+                .is_none(),
+            "changes of a fork should not be visible before it is merged"
+        );
+        assert!(forked.merge(&fork).unwrap());
+
+        assert_eq!(fork_changes, changes(&scratch));
+        assert_eq!(forked.into_inner(), scratch.into_inner());
+    }
+
+    #[test]
+    fn fork_should_not_merge_after_conflicting_commit() {
+        let test_pairs_updated = create_test_pairs_updated();
+        let TestState { state, root_hash } = create_test_state();
+
+        let scratch = state.create_scratch();
+        let fork = scratch.fork();
+        let conflicting_fork = scratch.fork();
+
+        fork.commit_effects(root_hash, write_effects(&test_pairs_updated[..1]))
+            .unwrap();
+        let checkout = conflicting_fork.checkout(root_hash).unwrap().unwrap();
+        checkout.read(&test_pairs_updated[0].key).unwrap();
+        conflicting_fork
+            .commit_effects(root_hash, write_effects(&test_pairs_updated[1..2]))
+            .unwrap();
+
+        assert!(scratch.merge(&fork).unwrap());
+        assert!(!scratch.merge(&conflicting_fork).unwrap());
+
+        let (stored_values, pruned) = scratch.into_inner();
+        assert_eq!(
+            stored_values,
+            vec![(
+                test_pairs_updated[0].key,
+                test_pairs_updated[0].value.clone()
+            )]
+        );
+        assert!(pruned.is_empty());
+    }
+
     #[test]
     fn cache_trie_basic_insert_get() {
         let mut trie = CacheTrie::new();
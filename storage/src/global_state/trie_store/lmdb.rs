@@ -111,6 +111,7 @@ use std::{
 };
 
 use lmdb::{Database, DatabaseFlags, Transaction};
+use parking_lot::RwLock;
 
 use casper_types::{
     bytesrepr::{self, Bytes, ToBytes},
@@ -123,7 +124,11 @@ use crate::global_state::{
     store::Store,
     transaction_source::{lmdb::LmdbEnvironment, Readable, TransactionSource, Writable},
     trie::{LazilyDeserializedTrie, Trie},
-    trie_store::{self, TrieStore},
+    trie_store::{
+        self,
+        node_cache::{TrieNodeCache, TrieNodeCacheStats},
+        TrieStore,
+    },
 };
 
 /// An LMDB-backed trie store.
@@ -132,6 +137,10 @@ use crate::global_state::{
 #[derive(Debug, Clone)]
 pub struct LmdbTrieStore {
     db: Database,
+    /// The database recording tries written while a garbage collection is in progress, if any.
+    gc_write_barrier: Arc<RwLock<Option<Database>>>,
+    /// The cache of decoded tries, shared by all clones of this store.
+    node_cache: Arc<TrieNodeCache>,
 }
 
 impl LmdbTrieStore {
@@ -143,14 +152,36 @@ impl LmdbTrieStore {
     ) -> Result<Self, error::Error> {
         let name = Self::name(maybe_name);
         let db = env.env().create_db(Some(&name), flags)?;
-        Ok(LmdbTrieStore { db })
+        Ok(LmdbTrieStore::from_db(db))
     }
 
     /// Constructor for `LmdbTrieStore` which opens an existing lmdb store file.
     pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
         let name = Self::name(maybe_name);
         let db = env.env().open_db(Some(&name))?;
-        Ok(LmdbTrieStore { db })
+        Ok(LmdbTrieStore::from_db(db))
+    }
+
+    fn from_db(db: Database) -> Self {
+        LmdbTrieStore {
+            db,
+            gc_write_barrier: Arc::new(RwLock::new(None)),
+            node_cache: Arc::new(TrieNodeCache::new(0)),
+        }
+    }
+
+    /// Keeps up to `capacity` decoded tries in memory, so that frequently read tries, such as the
+    /// upper levels of the global state, are not looked up and decoded again on every read.
+    ///
+    /// A `capacity` of zero, the default, disables caching.
+    pub fn with_node_cache(mut self, capacity: usize) -> Self {
+        self.node_cache = Arc::new(TrieNodeCache::new(capacity));
+        self
+    }
+
+    /// Returns the statistics of the cache of decoded tries.
+    pub fn node_cache_stats(&self) -> TrieNodeCacheStats {
+        self.node_cache.stats()
     }
 
     fn name(maybe_name: Option<&str>) -> String {
@@ -163,9 +194,25 @@ impl LmdbTrieStore {
     pub fn get_db(&self) -> Database {
         self.db
     }
+
+    /// Sets the database into which the hashes of all subsequently written tries are recorded, or
+    /// stops recording them if `None`.
+    pub(crate) fn set_gc_write_barrier(&self, maybe_db: Option<Database>) {
+        *self.gc_write_barrier.write() = maybe_db;
+    }
+
+    /// Evicts the tries with the given hashes from the cache of decoded tries, once their deletion
+    /// has been committed.
+    pub(crate) fn evict_from_node_cache(&self, trie_keys: &[Digest]) {
+        self.node_cache.evict(trie_keys);
+    }
 }
 
-impl<K, V> Store<Digest, Trie<K, V>> for LmdbTrieStore {
+impl<K, V> Store<Digest, Trie<K, V>> for LmdbTrieStore
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
     type Error = error::Error;
 
     type Handle = Database;
@@ -173,9 +220,53 @@ impl<K, V> Store<Digest, Trie<K, V>> for LmdbTrieStore {
     fn handle(&self) -> Self::Handle {
         self.db
     }
+
+    fn get<T>(&self, txn: &T, key: &Digest) -> Result<Option<Trie<K, V>>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        Digest: AsRef<[u8]>,
+        Trie<K, V>: bytesrepr::FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        if let Some(trie) = self.node_cache.get(key) {
+            return Ok(Some(trie));
+        }
+        let generation = self.node_cache.generation();
+        let Some(bytes) = Store::<Digest, Trie<K, V>>::get_raw(self, txn, key)? else {
+            return Ok(None);
+        };
+        let trie = Store::<Digest, Trie<K, V>>::deserialize_value(self, &bytes)?;
+        self.node_cache.insert(*key, trie.clone(), generation);
+        Ok(Some(trie))
+    }
+
+    fn put_raw<T>(
+        &self,
+        txn: &mut T,
+        key: &Digest,
+        value_bytes: Cow<'_, [u8]>,
+    ) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        Digest: AsRef<[u8]>,
+        Self::Error: From<T::Error>,
+    {
+        txn.write(self.db, key.as_ref(), &value_bytes)?;
+        // Tries written during a garbage collection have to survive its sweep phase, so they are
+        // queued up for marking along with their descendants.
+        if let Some(pending_db) = *self.gc_write_barrier.read() {
+            txn.write(pending_db, key.as_ref(), &[])?;
+        }
+        Ok(())
+    }
 }
 
-impl<K, V> TrieStore<K, V> for LmdbTrieStore {}
+impl<K, V> TrieStore<K, V> for LmdbTrieStore
+where
+    K: Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+}
 
 /// Cache used by the scratch trie.  The keys represent the hash of the trie being cached.  The
 /// values represent:  1) A boolean, where `false` means the trie was _not_ written and `true` means
@@ -2,6 +2,7 @@
 //!
 //! See the [lmdb](lmdb/index.html#usage) modules for usage examples.
 pub mod lmdb;
+pub mod node_cache;
 /// Trie store operational logic.
 pub mod operations;
 
@@ -0,0 +1,221 @@
+//! Cache of decoded trie nodes.
+//!
+//! Every read walks the trie from its root, so the upper levels of the trie are looked up and
+//! decoded again on every query.  Decoded nodes are therefore kept in an LRU cache keyed by their
+//! digest.  As tries are content-addressed, a cached node never goes stale; it only has to be
+//! evicted when garbage collection deletes it from the store.
+//!
+//! A node read from the store while garbage collection deletes it must not be cached afterwards.
+//! The cache therefore has a generation, incremented on every eviction, and a node is only cached
+//! if no eviction happened since the generation was taken, before reading the node from the store.
+
+use std::{
+    any::Any,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use casper_types::Digest;
+use linked_hash_map::LinkedHashMap;
+use parking_lot::Mutex;
+
+use crate::global_state::trie::Trie;
+
+/// Statistics of a trie node cache.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrieNodeCacheStats {
+    /// Number of nodes found in memory.
+    pub hits: u64,
+    /// Number of nodes not found in memory, which had to be read from the store and decoded.
+    pub misses: u64,
+    /// Number of nodes currently held in memory.
+    pub size: usize,
+}
+
+/// An LRU cache of decoded trie nodes, keyed by their digest.
+///
+/// Nodes are held type-erased, since a trie store may be read with different key and value types.
+/// A node read with other types than the ones it was cached with counts as a miss.
+#[derive(Debug)]
+pub(crate) struct TrieNodeCache {
+    capacity: usize,
+    nodes: Mutex<CachedNodes>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// The cached nodes, in least recently used order.
+#[derive(Debug, Default)]
+struct CachedNodes {
+    by_digest: LinkedHashMap<Digest, Arc<dyn Any + Send + Sync>>,
+    /// Incremented whenever nodes are evicted.
+    generation: u64,
+}
+
+impl TrieNodeCache {
+    /// Creates a cache holding up to `capacity` decoded nodes.
+    ///
+    /// A `capacity` of zero disables caching.
+    pub(crate) fn new(capacity: usize) -> Self {
+        TrieNodeCache {
+            capacity,
+            nodes: Mutex::new(CachedNodes::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns a copy of the cached node with the given digest, if any.
+    pub(crate) fn get<K, V>(&self, digest: &Digest) -> Option<Trie<K, V>>
+    where
+        K: Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        if self.capacity == 0 {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        let maybe_node = self
+            .nodes
+            .lock()
+            .by_digest
+            .get_refresh(digest)
+            .and_then(|node| node.downcast_ref::<Trie<K, V>>().cloned());
+        if maybe_node.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        maybe_node
+    }
+
+    /// Returns the current generation of the cache, which must be taken before reading a node
+    /// from the store in order to cache it.
+    pub(crate) fn generation(&self) -> u64 {
+        self.nodes.lock().generation
+    }
+
+    /// Caches the given decoded node, evicting the least recently used ones if over capacity.
+    ///
+    /// The node is not cached if any node was evicted since `generation` was taken, as the node
+    /// might have been deleted from the store since it was read.
+    pub(crate) fn insert<K, V>(&self, digest: Digest, node: Trie<K, V>, generation: u64)
+    where
+        K: Send + Sync + 'static,
+        V: Send + Sync + 'static,
+    {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut nodes = self.nodes.lock();
+        if nodes.generation != generation {
+            return;
+        }
+        nodes.by_digest.insert(digest, Arc::new(node));
+        while nodes.by_digest.len() > self.capacity {
+            nodes.by_digest.pop_front();
+        }
+    }
+
+    /// Evicts the nodes with the given digests, once they have been deleted from the store.
+    pub(crate) fn evict(&self, digests: &[Digest]) {
+        if self.capacity == 0 || digests.is_empty() {
+            return;
+        }
+        let mut nodes = self.nodes.lock();
+        for digest in digests {
+            nodes.by_digest.remove(digest);
+        }
+        nodes.generation += 1;
+    }
+
+    /// Returns the statistics of the cache.
+    pub(crate) fn stats(&self) -> TrieNodeCacheStats {
+        TrieNodeCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            size: self.nodes.lock().by_digest.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::bytesrepr::Bytes;
+
+    use super::*;
+
+    fn leaf(value: u8) -> (Digest, Trie<Bytes, Bytes>) {
+        let leaf = Trie::Leaf {
+            key: Bytes::from(vec![value]),
+            value: Bytes::from(vec![value]),
+        };
+        (Digest::hash([value]), leaf)
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_nodes() {
+        let cache = TrieNodeCache::new(2);
+        let (digest_1, leaf_1) = leaf(1);
+        let (digest_2, leaf_2) = leaf(2);
+        let (digest_3, leaf_3) = leaf(3);
+        cache.insert(digest_1, leaf_1.clone(), cache.generation());
+        cache.insert(digest_2, leaf_2, cache.generation());
+        // Refresh the first node, so the second one is evicted next.
+        assert_eq!(cache.get::<Bytes, Bytes>(&digest_1), Some(leaf_1));
+        cache.insert(digest_3, leaf_3.clone(), cache.generation());
+
+        assert!(cache.get::<Bytes, Bytes>(&digest_2).is_none());
+        assert_eq!(cache.get::<Bytes, Bytes>(&digest_3), Some(leaf_3));
+        assert_eq!(
+            cache.stats(),
+            TrieNodeCacheStats {
+                hits: 2,
+                misses: 1,
+                size: 2
+            }
+        );
+
+        cache.evict(&[digest_3]);
+        assert!(cache.get::<Bytes, Bytes>(&digest_3).is_none());
+    }
+
+    #[test]
+    fn should_not_cache_nodes_read_before_an_eviction() {
+        let cache = TrieNodeCache::new(2);
+        let (digest_1, leaf_1) = leaf(1);
+        let (digest_2, leaf_2) = leaf(2);
+
+        // A reader misses the first node and reads it from the store, while the node is deleted
+        // and evicted by garbage collection.
+        let generation = cache.generation();
+        assert!(cache.get::<Bytes, Bytes>(&digest_1).is_none());
+        cache.evict(&[digest_1]);
+        cache.insert(digest_1, leaf_1, generation);
+        assert!(cache.get::<Bytes, Bytes>(&digest_1).is_none());
+
+        // Nodes read after the eviction are cached again.
+        cache.insert(digest_2, leaf_2.clone(), cache.generation());
+        assert_eq!(cache.get::<Bytes, Bytes>(&digest_2), Some(leaf_2));
+    }
+
+    #[test]
+    fn should_miss_on_other_node_types() {
+        let cache = TrieNodeCache::new(1);
+        let (digest, leaf) = leaf(1);
+        cache.insert(digest, leaf, cache.generation());
+        assert!(cache.get::<Bytes, Digest>(&digest).is_none());
+        assert_eq!(cache.stats().misses, 1);
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_capacity() {
+        let cache = TrieNodeCache::new(0);
+        let (digest, leaf) = leaf(1);
+        cache.insert(digest, leaf, cache.generation());
+        assert!(cache.get::<Bytes, Bytes>(&digest).is_none());
+        assert_eq!(cache.stats().size, 0);
+    }
+}
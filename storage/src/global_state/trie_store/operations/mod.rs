@@ -2,16 +2,23 @@ pub(crate) mod store_wrappers;
 #[cfg(test)]
 mod tests;
 
-#[cfg(test)]
-use std::collections::HashSet;
-use std::{borrow::Cow, cmp, collections::VecDeque, convert::TryInto, mem};
+use std::{
+    borrow::Cow,
+    cmp,
+    collections::{HashSet, VecDeque},
+    convert::TryInto,
+    marker::PhantomData,
+    mem,
+};
 
 use num_traits::FromPrimitive;
 use tracing::{error, warn};
 
 use casper_types::{
     bytesrepr::{self, Bytes, FromBytes, ToBytes},
-    global_state::{Pointer, TrieMerkleProof, TrieMerkleProofStep},
+    global_state::{
+        Pointer, TrieMerkleProof, TrieMerkleProofStep, TrieMultiProof, TrieMultiProofStep,
+    },
     Digest,
 };
 
@@ -240,6 +247,158 @@ where
     }
 }
 
+/// Reads the values under the given `keys` along with a single proof of all those found.
+///
+/// The tries on the paths shared by several keys are only included once in the proof.  Returns
+/// [`ReadResult::NotFound`] if none of the keys is found.
+pub fn read_with_multi_proof<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    root: &Digest,
+    keys: &[K],
+) -> Result<ReadResult<TrieMultiProof<K, V>>, E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    let mut paths = keys
+        .iter()
+        .map(|key| Ok((key.to_bytes()?, key)))
+        .collect::<Result<Vec<_>, bytesrepr::Error>>()?;
+    paths.sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
+    paths.dedup_by(|(left, _), (right, _)| left == right);
+
+    let root_trie: Trie<K, V> = match store.get(txn, root)? {
+        Some(root) => root,
+        None => return Ok(ReadResult::RootNotFound),
+    };
+    let mut prover: TrieMultiProver<K, V, T, S, E> = TrieMultiProver {
+        txn,
+        store,
+        entries: vec![],
+        steps: vec![],
+        _error: PhantomData,
+    };
+    if !prover.prove(root_trie, 0, &paths)? {
+        return Ok(ReadResult::NotFound);
+    }
+    Ok(ReadResult::Found(TrieMultiProof::new(
+        prover.entries,
+        prover.steps,
+    )))
+}
+
+struct TrieMultiProver<'a, K, V, T, S, E> {
+    txn: &'a T,
+    store: &'a S,
+    entries: Vec<(K, V)>,
+    steps: Vec<TrieMultiProofStep>,
+    _error: PhantomData<E>,
+}
+
+impl<K, V, T, S, E> TrieMultiProver<'_, K, V, T, S, E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    /// Appends the steps proving the entries of `trie` found under `paths`, which are sorted and
+    /// all start with the `depth` bytes leading to `trie`.
+    ///
+    /// Returns `false`, having appended nothing, if none of the entries is found.
+    fn prove(
+        &mut self,
+        trie: Trie<K, V>,
+        depth: usize,
+        paths: &[(Vec<u8>, &K)],
+    ) -> Result<bool, E> {
+        match trie {
+            Trie::Leaf { key, value } => {
+                if !paths.iter().any(|(_, path_key)| **path_key == key) {
+                    return Ok(false);
+                }
+                self.entries.push((key, value));
+                self.steps.push(TrieMultiProofStep::Leaf);
+            }
+            Trie::Node { pointer_block } => {
+                let mut proven_indices = vec![];
+                let mut indexed_pointers = vec![];
+                for (index, pointer) in pointer_block.as_indexed_pointers() {
+                    // As the paths are sorted and share their first `depth` bytes, the ones going
+                    // through this child are contiguous.
+                    let start = paths.partition_point(|(path, _)| {
+                        path.get(depth).is_none_or(|byte| *byte < index)
+                    });
+                    let end = paths.partition_point(|(path, _)| {
+                        path.get(depth).is_none_or(|byte| *byte <= index)
+                    });
+                    let child_paths = &paths[start..end];
+                    if !child_paths.is_empty()
+                        && self.prove_pointer(&pointer, depth + 1, child_paths)?
+                    {
+                        proven_indices.push(index);
+                    } else {
+                        indexed_pointers.push((index, pointer));
+                    }
+                }
+                if proven_indices.is_empty() {
+                    return Ok(false);
+                }
+                self.steps
+                    .push(TrieMultiProofStep::node(proven_indices, indexed_pointers));
+            }
+            Trie::Extension { affix, pointer } => {
+                let start = paths.partition_point(|(path, _)| {
+                    sub_path(path, depth, affix.len()) < affix.as_slice()
+                });
+                let end = paths.partition_point(|(path, _)| {
+                    sub_path(path, depth, affix.len()) <= affix.as_slice()
+                });
+                let child_paths = &paths[start..end];
+                if child_paths.is_empty()
+                    || !self.prove_pointer(&pointer, depth + affix.len(), child_paths)?
+                {
+                    return Ok(false);
+                }
+                self.steps.push(TrieMultiProofStep::extension(affix.into()));
+            }
+        }
+        Ok(true)
+    }
+
+    fn prove_pointer(
+        &mut self,
+        pointer: &Pointer,
+        depth: usize,
+        paths: &[(Vec<u8>, &K)],
+    ) -> Result<bool, E> {
+        match self.store.get(self.txn, pointer.hash())? {
+            Some(trie) => self.prove(trie, depth, paths),
+            None => {
+                warn!(
+                    "No trie value at key: {:?} (reading multi-proof at depth: {})",
+                    pointer.hash(),
+                    depth
+                );
+                Ok(false)
+            }
+        }
+    }
+}
+
+/// Returns the `len` bytes of `path` following its first `depth` bytes, or fewer if it is shorter.
+fn sub_path(path: &[u8], depth: usize, len: usize) -> &[u8] {
+    let rest = path.get(depth..).unwrap_or_default();
+    &rest[..cmp::min(rest.len(), len)]
+}
+
 /// Given a serialized trie, find any children that are referenced but not present in the database.
 pub fn missing_children<K, V, T, S, E>(
     txn: &T,
@@ -1254,89 +1413,569 @@ where
     keys_with_prefix(txn, store, root, &[])
 }
 
-/// Checks the integrity of the trie store.
-#[cfg(test)]
-pub fn check_integrity<K, V, T, S, E>(
+/// A difference between the leaves of two tries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrieDiffEntry<K, V> {
+    /// The key is only present in the second trie.
+    Added {
+        /// The key.
+        key: K,
+        /// The value in the second trie.
+        value: V,
+    },
+    /// The key is only present in the first trie.
+    Removed {
+        /// The key.
+        key: K,
+        /// The value in the first trie.
+        value: V,
+    },
+    /// The key is present in both tries, with different values.
+    Modified {
+        /// The key.
+        key: K,
+        /// The value in the first trie.
+        old_value: V,
+        /// The value in the second trie.
+        new_value: V,
+    },
+}
+
+impl<K, V> TrieDiffEntry<K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        match self {
+            TrieDiffEntry::Added { key, .. }
+            | TrieDiffEntry::Removed { key, .. }
+            | TrieDiffEntry::Modified { key, .. } => key,
+        }
+    }
+}
+
+/// Result of diffing two tries.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DiffResult<K, V> {
+    /// Differences found between the two tries, ordered by key.
+    Found {
+        /// The differences.
+        entries: Vec<TrieDiffEntry<K, V>>,
+        /// Whether there are more differences after the last entry.
+        more: bool,
+    },
+    /// Either root hash not found in trie store.
+    RootNotFound,
+    /// A trie below either root not found in trie store.
+    MissingTrie(Digest),
+}
+
+/// Returns up to `limit` differences between the tries at `from_root` and `to_root`, ordered by
+/// key and only including the keys after `start_after` if given.
+///
+/// Both tries are walked together, and subtrees with the same digest at the same position in both
+/// are skipped without being read, so the cost of a diff depends on the size of the differences
+/// rather than on the size of the tries.  A `limit` of zero is treated as one.
+pub fn diff<K, V, T, S, E>(
     txn: &T,
     store: &S,
-    trie_keys_to_visit: Vec<Digest>,
-) -> Result<(), E>
+    from_root: &Digest,
+    to_root: &Digest,
+    start_after: Option<&K>,
+    limit: usize,
+) -> Result<DiffResult<K, V>, E>
 where
-    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
-    V: ToBytes + FromBytes + std::fmt::Debug,
+    K: ToBytes + FromBytes + Clone + Eq,
+    V: ToBytes + FromBytes + Clone + Eq,
     T: Readable<Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<T::Error>,
     E: From<S::Error> + From<bytesrepr::Error>,
 {
-    for state_root in &trie_keys_to_visit {
-        match store.get(txn, state_root)? {
-            Some(Trie::Node { .. }) => {}
-            other => panic!(
-                "Should have a pointer block node as state root but received {:?} instead",
-                other
-            ),
+    let mut roots = Vec::with_capacity(2);
+    for root in [from_root, to_root] {
+        match store.get(txn, root)? {
+            Some(trie) => roots.push(DiffSubtree {
+                hash: *root,
+                trie,
+                consumed: 0,
+            }),
+            None => return Ok(DiffResult::RootNotFound),
         }
     }
-    let mut trie_keys_to_visit: Vec<(Vec<u8>, Digest)> = trie_keys_to_visit
+    let to = roots.pop();
+    let from = roots.pop();
+
+    let mut differ = TrieDiffer {
+        txn,
+        store,
+        cursor: start_after.map(ToBytes::to_bytes).transpose()?,
+        limit: cmp::max(limit, 1),
+        entries: vec![],
+        _error: PhantomData,
+    };
+    let more = match differ.diff_subtrees(&mut vec![], from, to) {
+        Ok(()) => false,
+        Err(DiffInterrupt::Full) => true,
+        Err(DiffInterrupt::MissingTrie(digest)) => return Ok(DiffResult::MissingTrie(digest)),
+        Err(DiffInterrupt::Error(error)) => return Err(error),
+    };
+    let mut entries = differ.entries;
+    entries.truncate(differ.limit);
+    Ok(DiffResult::Found { entries, more })
+}
+
+/// The side of a diff a trie belongs to.
+#[derive(Clone, Copy)]
+enum DiffSide {
+    From,
+    To,
+}
+
+/// A subtree of either trie being diffed.
+struct DiffSubtree<K, V> {
+    hash: Digest,
+    trie: Trie<K, V>,
+    /// Number of affix bytes of an extension already walked through.
+    consumed: usize,
+}
+
+/// The subtree below another one on the path of a byte, before it is read from the store.
+enum DiffChild<K, V> {
+    /// The trie a pointer refers to.
+    Pointer(Pointer),
+    /// The rest of an extension whose affix has only been walked through in part.
+    Affix(DiffSubtree<K, V>),
+}
+
+impl<K, V> DiffChild<K, V> {
+    /// Returns whether both children are the same subtree, which can be told from the pointers
+    /// alone.
+    fn is_same_as(&self, other: &DiffChild<K, V>) -> bool {
+        match (self, other) {
+            (DiffChild::Pointer(pointer), DiffChild::Pointer(other_pointer)) => {
+                pointer.hash() == other_pointer.hash()
+            }
+            (DiffChild::Affix(subtree), DiffChild::Affix(other_subtree)) => {
+                subtree.hash == other_subtree.hash && subtree.consumed == other_subtree.consumed
+            }
+            (DiffChild::Pointer(_), DiffChild::Affix(_))
+            | (DiffChild::Affix(_), DiffChild::Pointer(_)) => false,
+        }
+    }
+}
+
+/// A leaf of either trie being diffed.
+struct DiffLeaf<K, V> {
+    key_bytes: Vec<u8>,
+    key: K,
+    value: V,
+    side: DiffSide,
+}
+
+/// Reason for a diff to stop before walking both tries entirely.
+enum DiffInterrupt<E> {
+    /// One more entry than requested has been collected.
+    Full,
+    /// A trie was not found in the store.
+    MissingTrie(Digest),
+    /// Error reading from the store.
+    Error(E),
+}
+
+struct TrieDiffer<'a, K, V, T, S, E> {
+    txn: &'a T,
+    store: &'a S,
+    cursor: Option<Vec<u8>>,
+    limit: usize,
+    entries: Vec<TrieDiffEntry<K, V>>,
+    _error: PhantomData<E>,
+}
+
+impl<K, V, T, S, E> TrieDiffer<'_, K, V, T, S, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    fn diff_subtrees(
+        &mut self,
+        path: &mut Vec<u8>,
+        from: Option<DiffSubtree<K, V>>,
+        to: Option<DiffSubtree<K, V>>,
+    ) -> Result<(), DiffInterrupt<E>> {
+        if let (Some(from), Some(to)) = (&from, &to) {
+            if from.hash == to.hash && from.consumed == to.consumed {
+                return Ok(());
+            }
+        }
+        match (from, to) {
+            (
+                Some(DiffSubtree {
+                    trie: Trie::Leaf { key, value },
+                    ..
+                }),
+                to,
+            ) => {
+                let leaf = self.leaf(key, value, DiffSide::From)?;
+                self.merge_leaf(path, leaf, to, DiffSide::To)
+            }
+            (
+                from,
+                Some(DiffSubtree {
+                    trie: Trie::Leaf { key, value },
+                    ..
+                }),
+            ) => {
+                let leaf = self.leaf(key, value, DiffSide::To)?;
+                self.merge_leaf(path, leaf, from, DiffSide::From)
+            }
+            (Some(from), Some(to)) => {
+                for byte in 0..=u8::MAX {
+                    path.push(byte);
+                    if !self.is_before_cursor(path) {
+                        let from_child = Self::child(&from, byte);
+                        let to_child = Self::child(&to, byte);
+                        let is_same = match (&from_child, &to_child) {
+                            (Some(from_child), Some(to_child)) => from_child.is_same_as(to_child),
+                            _ => false,
+                        };
+                        if !is_same {
+                            let from_child = self.read_child(from_child)?;
+                            let to_child = self.read_child(to_child)?;
+                            self.diff_subtrees(path, from_child, to_child)?;
+                        }
+                    }
+                    path.pop();
+                }
+                Ok(())
+            }
+            (Some(from), None) => self.walk_leaves(path, from, DiffSide::From, &mut None),
+            (None, Some(to)) => self.walk_leaves(path, to, DiffSide::To, &mut None),
+            (None, None) => Ok(()),
+        }
+    }
+
+    /// Diffs a single leaf against a subtree of the other trie.
+    fn merge_leaf(
+        &mut self,
+        path: &mut Vec<u8>,
+        leaf: DiffLeaf<K, V>,
+        other: Option<DiffSubtree<K, V>>,
+        other_side: DiffSide,
+    ) -> Result<(), DiffInterrupt<E>> {
+        let mut pending = Some(leaf);
+        if let Some(other) = other {
+            self.walk_leaves(path, other, other_side, &mut pending)?;
+        }
+        match pending {
+            Some(leaf) => self.emit_leaf(leaf),
+            None => Ok(()),
+        }
+    }
+
+    /// Visits the leaves of a subtree in key order, emitting the pending leaf of the other trie
+    /// in order among them.
+    fn walk_leaves(
+        &mut self,
+        path: &mut Vec<u8>,
+        subtree: DiffSubtree<K, V>,
+        side: DiffSide,
+        pending: &mut Option<DiffLeaf<K, V>>,
+    ) -> Result<(), DiffInterrupt<E>> {
+        match subtree.trie {
+            Trie::Leaf { key, value } => {
+                let leaf = self.leaf(key, value, side)?;
+                self.visit_leaf(leaf, pending)
+            }
+            Trie::Node { pointer_block } => {
+                for (index, pointer) in pointer_block.as_indexed_pointers() {
+                    path.push(index);
+                    if !self.is_before_cursor(path) {
+                        let child = self.load(&pointer)?;
+                        self.walk_leaves(path, child, side, pending)?;
+                    }
+                    path.pop();
+                }
+                Ok(())
+            }
+            Trie::Extension { affix, pointer } => {
+                let depth = path.len();
+                path.extend_from_slice(&affix[subtree.consumed..]);
+                if !self.is_before_cursor(path) {
+                    let child = self.load(&pointer)?;
+                    self.walk_leaves(path, child, side, pending)?;
+                }
+                path.truncate(depth);
+                Ok(())
+            }
+        }
+    }
+
+    fn visit_leaf(
+        &mut self,
+        leaf: DiffLeaf<K, V>,
+        pending: &mut Option<DiffLeaf<K, V>>,
+    ) -> Result<(), DiffInterrupt<E>> {
+        if let Some(pending_leaf) = pending.take() {
+            match pending_leaf.key_bytes.cmp(&leaf.key_bytes) {
+                cmp::Ordering::Less => self.emit_leaf(pending_leaf)?,
+                cmp::Ordering::Equal => return self.emit_pair(pending_leaf, leaf),
+                cmp::Ordering::Greater => *pending = Some(pending_leaf),
+            }
+        }
+        self.emit_leaf(leaf)
+    }
+
+    /// Emits a leaf only present in one of the tries.
+    fn emit_leaf(&mut self, leaf: DiffLeaf<K, V>) -> Result<(), DiffInterrupt<E>> {
+        if self.is_at_or_before_cursor(&leaf.key_bytes) {
+            return Ok(());
+        }
+        let DiffLeaf {
+            key, value, side, ..
+        } = leaf;
+        self.push(match side {
+            DiffSide::From => TrieDiffEntry::Removed { key, value },
+            DiffSide::To => TrieDiffEntry::Added { key, value },
+        })
+    }
+
+    /// Emits a leaf present in both tries, if its value differs.
+    fn emit_pair(
+        &mut self,
+        leaf: DiffLeaf<K, V>,
+        other: DiffLeaf<K, V>,
+    ) -> Result<(), DiffInterrupt<E>> {
+        if self.is_at_or_before_cursor(&leaf.key_bytes) || leaf.value == other.value {
+            return Ok(());
+        }
+        let (from, to) = match leaf.side {
+            DiffSide::From => (leaf, other),
+            DiffSide::To => (other, leaf),
+        };
+        self.push(TrieDiffEntry::Modified {
+            key: from.key,
+            old_value: from.value,
+            new_value: to.value,
+        })
+    }
+
+    fn push(&mut self, entry: TrieDiffEntry<K, V>) -> Result<(), DiffInterrupt<E>> {
+        self.entries.push(entry);
+        if self.entries.len() > self.limit {
+            return Err(DiffInterrupt::Full);
+        }
+        Ok(())
+    }
+
+    /// Returns the subtree below the given one on the path of `byte`, if any, without reading it.
+    fn child(subtree: &DiffSubtree<K, V>, byte: u8) -> Option<DiffChild<K, V>> {
+        match &subtree.trie {
+            Trie::Leaf { .. } => None,
+            Trie::Node { pointer_block } => {
+                pointer_block[usize::from(byte)].map(DiffChild::Pointer)
+            }
+            Trie::Extension { affix, pointer } => {
+                if affix[subtree.consumed] != byte {
+                    None
+                } else if subtree.consumed + 1 < affix.len() {
+                    Some(DiffChild::Affix(DiffSubtree {
+                        hash: subtree.hash,
+                        trie: subtree.trie.clone(),
+                        consumed: subtree.consumed + 1,
+                    }))
+                } else {
+                    Some(DiffChild::Pointer(*pointer))
+                }
+            }
+        }
+    }
+
+    /// Reads a child subtree from the store, unless it is the rest of an extension.
+    fn read_child(
+        &self,
+        child: Option<DiffChild<K, V>>,
+    ) -> Result<Option<DiffSubtree<K, V>>, DiffInterrupt<E>> {
+        match child {
+            Some(DiffChild::Pointer(pointer)) => self.load(&pointer).map(Some),
+            Some(DiffChild::Affix(subtree)) => Ok(Some(subtree)),
+            None => Ok(None),
+        }
+    }
+
+    fn load(&self, pointer: &Pointer) -> Result<DiffSubtree<K, V>, DiffInterrupt<E>> {
+        match self.store.get(self.txn, pointer.hash()) {
+            Ok(Some(trie)) => Ok(DiffSubtree {
+                hash: *pointer.hash(),
+                trie,
+                consumed: 0,
+            }),
+            Ok(None) => Err(DiffInterrupt::MissingTrie(*pointer.hash())),
+            Err(error) => Err(DiffInterrupt::Error(error.into())),
+        }
+    }
+
+    fn leaf(&self, key: K, value: V, side: DiffSide) -> Result<DiffLeaf<K, V>, DiffInterrupt<E>> {
+        let key_bytes = key
+            .to_bytes()
+            .map_err(|error| DiffInterrupt::Error(error.into()))?;
+        Ok(DiffLeaf {
+            key_bytes,
+            key,
+            value,
+            side,
+        })
+    }
+
+    /// Returns `true` if all keys starting with `path` are before the cursor.
+    fn is_before_cursor(&self, path: &[u8]) -> bool {
+        self.cursor
+            .as_ref()
+            .is_some_and(|cursor| path < cursor.as_slice() && !cursor.starts_with(path))
+    }
+
+    fn is_at_or_before_cursor(&self, key_bytes: &[u8]) -> bool {
+        self.cursor
+            .as_ref()
+            .is_some_and(|cursor| key_bytes <= cursor.as_slice())
+    }
+}
+
+/// A problem found in the trie store by [`check_tries`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrieIntegrityIssue {
+    /// A trie referenced by a root or by its parent is not in the store.
+    Missing(Digest),
+    /// A trie can't be decoded, or is stored under a digest other than its hash.
+    Corrupted(Digest),
+    /// A leaf whose key doesn't start with the path leading to it.
+    MisplacedLeaf(Digest),
+}
+
+/// A step of the walk done by [`check_tries`].
+enum TrieCheckStep {
+    /// Checks the trie stored under the digest, reached via the given path.
+    Enter(Vec<u8>, Digest),
+    /// Leaves the trie stored under the digest, whose subtree is sound if no issues were found
+    /// since the given number of issues.
+    Exit(Digest, usize),
+}
+
+/// Walks the tries under each of the given roots, returning the problems found.
+///
+/// Tries in `visited` are skipped, and every trie whose whole subtree is sound is added to it, so
+/// that roots sharing most of their tries can be checked one after the other without walking the
+/// shared tries again.  Tries leading to a problem are never added, so the problem is reported for
+/// every root it belongs to.
+pub fn check_tries<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    roots: &[Digest],
+    visited: &mut HashSet<Digest>,
+) -> Result<Vec<TrieIntegrityIssue>, E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + std::fmt::Debug,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    let mut issues = vec![];
+    let mut steps: Vec<TrieCheckStep> = roots
         .iter()
-        .map(|blake2b_hash| (Vec::new(), *blake2b_hash))
+        .rev()
+        .map(|root| TrieCheckStep::Enter(Vec::new(), *root))
         .collect();
-    let mut visited = HashSet::new();
-    while let Some((mut path, trie_key)) = trie_keys_to_visit.pop() {
-        if !visited.insert(trie_key) {
-            continue;
-        }
-        let maybe_retrieved_trie: Option<Trie<K, V>> = store.get(txn, &trie_key)?;
-        if let Some(trie_value) = &maybe_retrieved_trie {
-            let hash_of_trie_value = {
-                let node_bytes = trie_value.to_bytes()?;
-                Digest::hash(&node_bytes)
-            };
-            if trie_key != hash_of_trie_value {
-                panic!(
-                    "Trie key {:?} has corrupted value {:?} (hash of value is {:?})",
-                    trie_key, trie_value, hash_of_trie_value
-                );
+    while let Some(step) = steps.pop() {
+        let (mut path, trie_key) = match step {
+            TrieCheckStep::Enter(path, trie_key) => (path, trie_key),
+            TrieCheckStep::Exit(trie_key, issue_count) => {
+                if issues.len() == issue_count {
+                    let _ = visited.insert(trie_key);
+                }
+                continue;
             }
+        };
+        if visited.contains(&trie_key) {
+            continue;
         }
-        match maybe_retrieved_trie {
-            // If we can't find the trie_key; it is missing and we'll return it
+        let trie_bytes = match store.get_raw(txn, &trie_key)? {
+            Some(trie_bytes) => trie_bytes,
             None => {
-                panic!("Missing trie key: {:?}", trie_key)
+                issues.push(TrieIntegrityIssue::Missing(trie_key));
+                continue;
             }
-            // If we could retrieve the node and it is a leaf, the search can move on
-            Some(Trie::Leaf { key, .. }) => {
-                let key_bytes = key.to_bytes()?;
-                if !key_bytes.starts_with(&path) {
-                    panic!(
-                                "Trie key {:?} belongs to a leaf with a corrupted affix. Key bytes: {:?}, Path: {:?}.",
-                                trie_key, key_bytes, path
-                            );
+        };
+        let trie_value = match bytesrepr::deserialize_from_slice::<_, Trie<K, V>>(&trie_bytes) {
+            Ok(trie_value) if trie_value.trie_hash()? == trie_key => trie_value,
+            _ => {
+                issues.push(TrieIntegrityIssue::Corrupted(trie_key));
+                continue;
+            }
+        };
+        match trie_value {
+            // If we hit a leaf, the search can move on once its key is checked
+            Trie::Leaf { key, .. } => {
+                if key.to_bytes()?.starts_with(&path) {
+                    let _ = visited.insert(trie_key);
+                } else {
+                    issues.push(TrieIntegrityIssue::MisplacedLeaf(trie_key));
                 }
             }
             // If we hit a pointer block, queue up all of the nodes it points to
-            Some(Trie::Node { pointer_block }) => {
+            Trie::Node { pointer_block } => {
+                steps.push(TrieCheckStep::Exit(trie_key, issues.len()));
                 for (byte, pointer) in pointer_block.as_indexed_pointers() {
                     let mut new_path = path.clone();
                     new_path.push(byte);
-                    match pointer {
-                        Pointer::LeafPointer(descendant_leaf_trie_key) => {
-                            trie_keys_to_visit.push((new_path, descendant_leaf_trie_key))
-                        }
-                        Pointer::NodePointer(descendant_node_trie_key) => {
-                            trie_keys_to_visit.push((new_path, descendant_node_trie_key))
-                        }
-                    }
+                    steps.push(TrieCheckStep::Enter(new_path, pointer.into_hash()));
                 }
             }
             // If we hit an extension block, add its pointer to the queue
-            Some(Trie::Extension { pointer, affix }) => {
+            Trie::Extension { pointer, affix } => {
+                steps.push(TrieCheckStep::Exit(trie_key, issues.len()));
                 path.extend_from_slice(affix.as_slice());
-                trie_keys_to_visit.push((path, pointer.into_hash()))
+                steps.push(TrieCheckStep::Enter(path, pointer.into_hash()));
             }
         }
     }
+    Ok(issues)
+}
+
+/// Checks the integrity of the trie store.
+#[cfg(test)]
+pub fn check_integrity<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    trie_keys_to_visit: Vec<Digest>,
+) -> Result<(), E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + std::fmt::Debug,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    for state_root in &trie_keys_to_visit {
+        match store.get(txn, state_root)? {
+            Some(Trie::Node { .. }) => {}
+            other => panic!(
+                "Should have a pointer block node as state root but received {:?} instead",
+                other
+            ),
+        }
+    }
+    let issues =
+        check_tries::<K, V, T, S, E>(txn, store, &trie_keys_to_visit, &mut HashSet::new())?;
+    assert!(
+        issues.is_empty(),
+        "trie store integrity issues: {:?}",
+        issues
+    );
     Ok(())
 }
 
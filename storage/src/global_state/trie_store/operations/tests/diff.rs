@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+
+use casper_types::Digest;
+
+use super::*;
+use crate::global_state::{
+    store::Store,
+    trie_store::operations::{diff, DiffResult, TrieDiffEntry},
+};
+
+type TestDiffEntry = TrieDiffEntry<TestKey, TestValue>;
+type TestState = (Digest, BTreeMap<TestKey, TestValue>);
+
+/// Returns keys sharing long prefixes, so that the tries have extensions, as well as keys
+/// spread evenly over the trie.
+fn test_key(index: u8) -> TestKey {
+    if index % 2 == 0 {
+        TestKey([1, 2, 3, 4, index % 3, index, 0])
+    } else {
+        let mut key = [0; TEST_KEY_LENGTH];
+        key.copy_from_slice(&Digest::hash([index]).value()[..TEST_KEY_LENGTH]);
+        TestKey(key)
+    }
+}
+
+fn test_value(index: u8, version: u8) -> TestValue {
+    TestValue([index, version, 0, 0, 0, 0])
+}
+
+fn write_all(
+    context: &LmdbTestContext,
+    mut root: Digest,
+    pairs: &BTreeMap<TestKey, TestValue>,
+) -> Digest {
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    for (key, value) in pairs {
+        match write::<_, _, _, _, error::Error>(&mut txn, &context.store, &root, key, value)
+            .unwrap()
+        {
+            WriteResult::Written(new_root) => root = new_root,
+            WriteResult::AlreadyExists => {}
+            WriteResult::RootNotFound => panic!("root not found"),
+        }
+    }
+    txn.commit().unwrap();
+    root
+}
+
+fn expected_diff(
+    from: &BTreeMap<TestKey, TestValue>,
+    to: &BTreeMap<TestKey, TestValue>,
+) -> Vec<TestDiffEntry> {
+    let keys: BTreeMap<_, _> = from.keys().chain(to.keys()).map(|key| (key, ())).collect();
+    keys.into_keys()
+        .filter_map(|key| match (from.get(key), to.get(key)) {
+            (Some(value), None) => Some(TrieDiffEntry::Removed {
+                key: *key,
+                value: *value,
+            }),
+            (None, Some(value)) => Some(TrieDiffEntry::Added {
+                key: *key,
+                value: *value,
+            }),
+            (Some(old_value), Some(new_value)) if old_value != new_value => {
+                Some(TrieDiffEntry::Modified {
+                    key: *key,
+                    old_value: *old_value,
+                    new_value: *new_value,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn diff_page(
+    context: &LmdbTestContext,
+    from: &Digest,
+    to: &Digest,
+    start_after: Option<&TestKey>,
+    limit: usize,
+) -> DiffResult<TestKey, TestValue> {
+    let txn = context.environment.create_read_txn().unwrap();
+    let result = diff::<TestKey, TestValue, _, _, error::Error>(
+        &txn,
+        &context.store,
+        from,
+        to,
+        start_after,
+        limit,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    result
+}
+
+/// Returns the states before and after adding, modifying and leaving keys untouched.
+fn test_states() -> (LmdbTestContext, TestState, TestState) {
+    let (empty_root, tries) = create_empty_trie::<TestKey, TestValue>().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    let before: BTreeMap<_, _> = (0..60)
+        .map(|index| (test_key(index), test_value(index, 0)))
+        .collect();
+    let before_root = write_all(&context, empty_root, &before);
+
+    let changes: BTreeMap<_, _> = (0..90)
+        .filter(|index| index % 5 == 0 || *index >= 60)
+        .map(|index| (test_key(index), test_value(index, 1)))
+        .collect();
+    let after_root = write_all(&context, before_root, &changes);
+    let mut after = before.clone();
+    after.extend(changes);
+
+    (context, (before_root, before), (after_root, after))
+}
+
+#[test]
+fn should_diff_tries() {
+    let (context, (before_root, before), (after_root, after)) = test_states();
+
+    for (from_root, from, to_root, to) in [
+        (&before_root, &before, &after_root, &after),
+        (&after_root, &after, &before_root, &before),
+    ] {
+        let expected = expected_diff(from, to);
+        assert!(!expected.is_empty());
+        assert_eq!(
+            diff_page(&context, from_root, to_root, None, usize::MAX),
+            DiffResult::Found {
+                entries: expected,
+                more: false
+            }
+        );
+    }
+}
+
+#[test]
+fn should_page_through_diff() {
+    let (context, (before_root, before), (after_root, after)) = test_states();
+    let expected = expected_diff(&before, &after);
+
+    let mut entries = vec![];
+    let mut start_after = None;
+    loop {
+        match diff_page(&context, &before_root, &after_root, start_after.as_ref(), 7) {
+            DiffResult::Found {
+                entries: page,
+                more,
+            } => {
+                assert!(page.len() == 7 || !more);
+                start_after = page.last().map(|entry| *entry.key());
+                entries.extend(page);
+                if !more {
+                    break;
+                }
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+    assert_eq!(entries, expected);
+}
+
+#[test]
+fn should_return_empty_diff_for_same_root() {
+    let (context, (before_root, _), _) = test_states();
+    assert_eq!(
+        diff_page(&context, &before_root, &before_root, None, 10),
+        DiffResult::Found {
+            entries: vec![],
+            more: false
+        }
+    );
+}
+
+#[test]
+fn should_not_diff_missing_root() {
+    let (context, (before_root, _), _) = test_states();
+    let missing_root = Digest::hash([0]);
+    assert_eq!(
+        diff_page(&context, &before_root, &missing_root, None, 10),
+        DiffResult::RootNotFound
+    );
+    assert_eq!(
+        diff_page(&context, &missing_root, &before_root, None, 10),
+        DiffResult::RootNotFound
+    );
+}
+
+#[test]
+fn should_not_read_shared_subtrees() {
+    let (context, (before_root, before), (after_root, after)) = test_states();
+
+    // Delete a subtree both tries share below their roots: the diff never reads it.
+    let txn = context.environment.create_read_txn().unwrap();
+    let root_pointers = |root: &Digest| match Store::<Digest, Trie<TestKey, TestValue>>::get(
+        &context.store,
+        &txn,
+        root,
+    ) {
+        Ok(Some(Trie::Node { pointer_block })) => pointer_block.as_indexed_pointers().collect(),
+        _ => panic!("root should be a node"),
+    };
+    let before_pointers: Vec<_> = root_pointers(&before_root);
+    let after_pointers: Vec<_> = root_pointers(&after_root);
+    let shared = *before_pointers
+        .iter()
+        .find(|pointer| after_pointers.contains(pointer))
+        .map(|(_, pointer)| pointer.hash())
+        .expect("tries should share a subtree");
+    txn.commit().unwrap();
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    txn.del(context.store.get_db(), &shared, None).unwrap();
+    txn.commit().unwrap();
+
+    assert_eq!(
+        diff_page(&context, &before_root, &after_root, None, usize::MAX),
+        DiffResult::Found {
+            entries: expected_diff(&before, &after),
+            more: false
+        }
+    );
+}
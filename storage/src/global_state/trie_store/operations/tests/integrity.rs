@@ -0,0 +1,102 @@
+use std::collections::HashSet;
+
+use lmdb::WriteFlags;
+
+use super::*;
+use crate::global_state::trie_store::operations::{check_tries, TrieIntegrityIssue};
+
+fn check(
+    context: &LmdbTestContext,
+    roots: &[Digest],
+    visited: &mut HashSet<Digest>,
+) -> Vec<TrieIntegrityIssue> {
+    let txn = context.environment.create_read_txn().unwrap();
+    let issues =
+        check_tries::<TestKey, TestValue, _, _, error::Error>(&txn, &context.store, roots, visited)
+            .unwrap();
+    txn.commit().unwrap();
+    issues
+}
+
+#[test]
+fn should_find_no_issues_in_sound_tries() {
+    let (root, tries) = create_6_leaf_trie().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    let mut visited = HashSet::new();
+    assert!(check(&context, &[root], &mut visited).is_empty());
+    let expected: HashSet<_> = tries.iter().map(|trie| trie.hash).collect();
+    assert_eq!(visited, expected);
+}
+
+#[test]
+fn should_report_missing_and_corrupted_tries_for_every_root() {
+    let (root, tries) = create_6_leaf_trie().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+    // `tries` holds the 6 leaves followed by their parents, from the root downwards.
+    let missing_leaf = tries[2].hash;
+    let corrupted_node = tries[TEST_LEAVES_LENGTH + 6].hash;
+
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    txn.del(context.store.get_db(), &missing_leaf, None)
+        .unwrap();
+    txn.put(
+        context.store.get_db(),
+        &corrupted_node,
+        &tries[0].trie.to_bytes().unwrap(),
+        WriteFlags::empty(),
+    )
+    .unwrap();
+    txn.commit().unwrap();
+
+    let expected: HashSet<_> = vec![
+        TrieIntegrityIssue::Missing(missing_leaf),
+        TrieIntegrityIssue::Corrupted(corrupted_node),
+    ]
+    .into_iter()
+    .collect();
+    let mut visited = HashSet::new();
+    for _ in 0..2 {
+        let issues: HashSet<_> = check(&context, &[root], &mut visited).into_iter().collect();
+        assert_eq!(issues, expected);
+    }
+    // Only the tries leading to an issue are walked again.
+    assert!(!visited.contains(&root));
+    assert!(!visited.contains(&missing_leaf));
+    assert!(!visited.contains(&corrupted_node));
+    assert!(visited.contains(&tries[5].hash));
+}
+
+#[test]
+fn should_report_undecodable_tries() {
+    let (root, tries) = create_6_leaf_trie().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+    let undecodable_leaf = tries[4].hash;
+
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    txn.put(
+        context.store.get_db(),
+        &undecodable_leaf,
+        &[255u8, 1, 2],
+        WriteFlags::empty(),
+    )
+    .unwrap();
+    txn.commit().unwrap();
+
+    assert_eq!(
+        check(&context, &[root], &mut HashSet::new()),
+        vec![TrieIntegrityIssue::Corrupted(undecodable_leaf)]
+    );
+}
+
+#[test]
+fn should_report_misplaced_leaves() {
+    let leaf = HashedTrie::new(TEST_LEAVES[0].clone()).unwrap();
+    let root = HashedTrie::new(Trie::node(&[(7, Pointer::LeafPointer(leaf.hash))])).unwrap();
+    let context = LmdbTestContext::new(&[leaf.clone(), root.clone()]).unwrap();
+
+    assert_eq!(
+        check(&context, &[root.hash], &mut HashSet::new()),
+        vec![TrieIntegrityIssue::MisplacedLeaf(leaf.hash)]
+    );
+}
@@ -1,6 +1,9 @@
 pub(crate) mod bytesrepr_utils;
+mod diff;
 mod ee_699;
+mod integrity;
 mod keys;
+mod multi_proof;
 mod proptests;
 mod prune;
 mod read;
@@ -628,8 +631,8 @@ struct LmdbTestContext {
 impl LmdbTestContext {
     fn new<K, V>(tries: &[HashedTrie<K, V>]) -> anyhow::Result<Self>
     where
-        K: FromBytes + ToBytes,
-        V: FromBytes + ToBytes,
+        K: FromBytes + ToBytes + Clone + Send + Sync + 'static,
+        V: FromBytes + ToBytes + Clone + Send + Sync + 'static,
     {
         let _temp_dir = tempdir()?;
         let environment = LmdbEnvironment::new(
@@ -649,8 +652,8 @@ impl LmdbTestContext {
 
     fn update<K, V>(&self, tries: &[HashedTrie<K, V>]) -> anyhow::Result<()>
     where
-        K: ToBytes,
-        V: ToBytes,
+        K: ToBytes + Clone + Send + Sync + 'static,
+        V: ToBytes + Clone + Send + Sync + 'static,
     {
         put_tries::<_, _, _, _, error::Error>(&self.environment, &self.store, tries)?;
         Ok(())
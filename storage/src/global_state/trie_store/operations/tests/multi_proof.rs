@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use casper_types::{bytesrepr::ToBytes, global_state::TrieMultiProof, Digest};
+
+use super::*;
+use crate::global_state::trie_store::operations::read_with_multi_proof;
+
+type TestMultiProof = TrieMultiProof<TestKey, TestValue>;
+
+/// Returns keys sharing long prefixes, so that the trie has extensions, as well as keys spread
+/// evenly over the trie.
+fn test_key(index: u8) -> TestKey {
+    if index % 2 == 0 {
+        TestKey([1, 2, 3, 4, index % 3, index, 0])
+    } else {
+        let mut key = [0; TEST_KEY_LENGTH];
+        key.copy_from_slice(&Digest::hash([index]).value()[..TEST_KEY_LENGTH]);
+        TestKey(key)
+    }
+}
+
+fn test_state() -> (LmdbTestContext, Digest, BTreeMap<TestKey, TestValue>) {
+    let (mut root, tries) = create_empty_trie::<TestKey, TestValue>().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+    let pairs: BTreeMap<_, _> = (0..60)
+        .map(|index| (test_key(index), TestValue([index, 0, 0, 0, 0, 0])))
+        .collect();
+
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    for (key, value) in &pairs {
+        match write::<_, _, _, _, error::Error>(&mut txn, &context.store, &root, key, value)
+            .unwrap()
+        {
+            WriteResult::Written(new_root) => root = new_root,
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+    txn.commit().unwrap();
+    (context, root, pairs)
+}
+
+fn read_multi_proof(
+    context: &LmdbTestContext,
+    root: &Digest,
+    keys: &[TestKey],
+) -> ReadResult<TestMultiProof> {
+    let txn = context.environment.create_read_txn().unwrap();
+    let result =
+        read_with_multi_proof::<_, _, _, _, error::Error>(&txn, &context.store, root, keys)
+            .unwrap();
+    txn.commit().unwrap();
+    result
+}
+
+#[test]
+fn should_prove_all_found_entries() {
+    let (context, root, pairs) = test_state();
+    // Unordered, duplicated and missing keys.
+    let keys = vec![
+        test_key(10),
+        test_key(3),
+        test_key(100),
+        test_key(40),
+        test_key(3),
+        test_key(101),
+        test_key(57),
+    ];
+
+    let proof = match read_multi_proof(&context, &root, &keys) {
+        ReadResult::Found(proof) => proof,
+        result => panic!("unexpected result {:?}", result),
+    };
+    assert_eq!(proof.compute_state_hash(), Ok(root));
+
+    let mut expected: Vec<_> = [3, 10, 40, 57]
+        .iter()
+        .map(|index| (test_key(*index), pairs[&test_key(*index)]))
+        .collect();
+    expected.sort_by_key(|(key, _)| key.to_bytes().unwrap());
+    assert_eq!(proof.entries(), expected.as_slice());
+}
+
+#[test]
+fn should_share_common_trie_steps() {
+    let (context, root, pairs) = test_state();
+    let keys: Vec<_> = pairs.keys().copied().collect();
+
+    let multi_proof = match read_multi_proof(&context, &root, &keys) {
+        ReadResult::Found(proof) => proof,
+        result => panic!("unexpected result {:?}", result),
+    };
+    assert_eq!(multi_proof.compute_state_hash(), Ok(root));
+    assert_eq!(multi_proof.entries().len(), keys.len());
+
+    let txn = context.environment.create_read_txn().unwrap();
+    let mut proofs_length = 0;
+    for key in &keys {
+        match read_with_proof::<TestKey, TestValue, _, _, error::Error>(
+            &txn,
+            &context.store,
+            &root,
+            key,
+        )
+        .unwrap()
+        {
+            ReadResult::Found(proof) => proofs_length += proof.serialized_length(),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+    txn.commit().unwrap();
+    assert!(multi_proof.serialized_length() < proofs_length);
+}
+
+#[test]
+fn should_not_prove_missing_entries() {
+    let (context, root, _) = test_state();
+    assert_eq!(
+        read_multi_proof(&context, &root, &[test_key(100), test_key(101)]),
+        ReadResult::NotFound
+    );
+    assert_eq!(read_multi_proof(&context, &root, &[]), ReadResult::NotFound);
+    assert_eq!(
+        read_multi_proof(&context, &Digest::hash([0]), &[test_key(0)]),
+        ReadResult::RootNotFound
+    );
+}
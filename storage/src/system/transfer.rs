@@ -419,24 +419,23 @@ impl TransferRuntimeArgsBuilder {
     where
         R: StateReader<Key, StoredValue, Error = GlobalStateError>,
     {
-        let (to, target) = match self
-            .resolve_transfer_target_mode(protocol_version, Rc::clone(&tracking_copy))?
-        {
-            TransferTargetMode::ExistingAccount {
-                main_purse: purse_uref,
-                target_account_hash: target_account,
-            } => (Some(target_account), purse_uref),
-            TransferTargetMode::PurseExists {
-                target_account_hash,
-                purse_uref,
-            } => (target_account_hash, purse_uref),
-            TransferTargetMode::CreateAccount(_) => {
-                // Method "build()" is called after `resolve_transfer_target_mode` is first called
-                // and handled by creating a new account. Calling `resolve_transfer_target_mode`
-                // for the second time should never return `CreateAccount` variant.
-                return Err(TransferError::InvalidOperation);
-            }
-        };
+        let (to, target) =
+            match self.resolve_transfer_target_mode(protocol_version, Rc::clone(&tracking_copy))? {
+                TransferTargetMode::ExistingAccount {
+                    main_purse: purse_uref,
+                    target_account_hash: target_account,
+                } => (Some(target_account), purse_uref),
+                TransferTargetMode::PurseExists {
+                    target_account_hash,
+                    purse_uref,
+                } => (target_account_hash, purse_uref),
+                TransferTargetMode::CreateAccount(_) => {
+                    // Method "build()" is called after `resolve_transfer_target_mode` is first called
+                    // and handled by creating a new account. Calling `resolve_transfer_target_mode`
+                    // for the second time should never return `CreateAccount` variant.
+                    return Err(TransferError::InvalidOperation);
+                }
+            };
 
         let source = self.resolve_source_uref(from, Rc::clone(&tracking_copy))?;
 
@@ -1170,7 +1170,7 @@ fn add_should_work() {
 
     let (global_state, root_hash, _tempdir) = state::lmdb::make_temporary_global_state(pairs);
 
-    let (effects, cache) = {
+    let (effects, cache, messages) = {
         let view = global_state.checkout(root_hash).unwrap().unwrap();
         let mut tracking_copy =
             TrackingCopy::new(view, DEFAULT_MAX_QUERY_DEPTH, DEFAULT_ENABLE_ENTITY);
@@ -1183,7 +1183,11 @@ fn add_should_work() {
         assert!(
             matches!(tracking_copy.get(&key), Ok(Some(StoredValue::CLValue(initial_value))) if initial_value.clone().into_t::<i32>().unwrap() == 2)
         );
-        (tracking_copy.effects(), tracking_copy.cache())
+        (
+            tracking_copy.effects(),
+            tracking_copy.cache(),
+            tracking_copy.messages(),
+        )
     };
 
     let view = global_state.checkout(root_hash).unwrap().unwrap();
@@ -1191,7 +1195,7 @@ fn add_should_work() {
     assert!(
         matches!(tc.get(&key), Ok(Some(StoredValue::CLValue(initial_value))) if initial_value.clone().into_t::<i32>().unwrap() == 1)
     );
-    tc.apply_changes(effects, cache);
+    tc.apply_changes(effects, cache, messages);
     assert!(
         matches!(tc.get(&key), Ok(Some(StoredValue::CLValue(initial_value))) if initial_value.clone().into_t::<i32>().unwrap() == 2)
     );
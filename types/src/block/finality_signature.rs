@@ -4,6 +4,7 @@ mod finality_signature_v2;
 pub use finality_signature_v1::FinalitySignatureV1;
 pub use finality_signature_v2::FinalitySignatureV2;
 
+use alloc::vec::Vec;
 use core::{
     fmt::{self, Display, Formatter},
     hash::Hash,
@@ -17,10 +18,20 @@ use rand::Rng;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::{crypto, BlockHash, EraId, PublicKey, Signature};
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    crypto, BlockHash, EraId, PublicKey, Signature,
+};
 #[cfg(any(feature = "testing", test))]
 use crate::{testing::TestRng, ChainNameDigest};
 
+const TAG_LENGTH: usize = U8_SERIALIZED_LENGTH;
+
+/// Tag for finality signature v1.
+const FINALITY_SIGNATURE_V1_TAG: u8 = 0;
+/// Tag for finality signature v2.
+const FINALITY_SIGNATURE_V2_TAG: u8 = 1;
+
 /// A validator's signature of a block, confirming it is finalized.
 ///
 /// Clients and joining nodes should wait until the signers' combined weight exceeds the fault
@@ -135,3 +146,62 @@ impl Display for FinalitySignature {
         }
     }
 }
+
+impl ToBytes for FinalitySignature {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buf = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        match self {
+            FinalitySignature::V1(fs) => {
+                writer.push(FINALITY_SIGNATURE_V1_TAG);
+                fs.write_bytes(writer)?;
+            }
+            FinalitySignature::V2(fs) => {
+                writer.push(FINALITY_SIGNATURE_V2_TAG);
+                fs.write_bytes(writer)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialized_length(&self) -> usize {
+        TAG_LENGTH
+            + match self {
+                FinalitySignature::V1(fs) => fs.serialized_length(),
+                FinalitySignature::V2(fs) => fs.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for FinalitySignature {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            FINALITY_SIGNATURE_V1_TAG => {
+                let (fs, remainder) = FinalitySignatureV1::from_bytes(remainder)?;
+                Ok((FinalitySignature::V1(fs), remainder))
+            }
+            FINALITY_SIGNATURE_V2_TAG => {
+                let (fs, remainder) = FinalitySignatureV2::from_bytes(remainder)?;
+                Ok((FinalitySignature::V2(fs), remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+        let finality_signature = FinalitySignature::random(rng);
+        bytesrepr::test_serialization_roundtrip(&finality_signature);
+    }
+}
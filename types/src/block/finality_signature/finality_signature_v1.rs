@@ -15,7 +15,10 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(any(feature = "testing", test))]
 use crate::testing::TestRng;
-use crate::{crypto, BlockHash, EraId, PublicKey, SecretKey, Signature};
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    crypto, BlockHash, EraId, PublicKey, SecretKey, Signature,
+};
 
 /// A validator's signature of a block, confirming it is finalized.
 ///
@@ -235,6 +238,46 @@ impl Display for FinalitySignatureV1 {
     }
 }
 
+impl ToBytes for FinalitySignatureV1 {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.block_hash.write_bytes(writer)?;
+        self.era_id.write_bytes(writer)?;
+        self.signature.write_bytes(writer)?;
+        self.public_key.write_bytes(writer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.block_hash.serialized_length()
+            + self.era_id.serialized_length()
+            + self.signature.serialized_length()
+            + self.public_key.serialized_length()
+    }
+}
+
+impl FromBytes for FinalitySignatureV1 {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (block_hash, remainder) = BlockHash::from_bytes(bytes)?;
+        let (era_id, remainder) = EraId::from_bytes(remainder)?;
+        let (signature, remainder) = Signature::from_bytes(remainder)?;
+        let (public_key, remainder) = PublicKey::from_bytes(remainder)?;
+        let finality_signature = FinalitySignatureV1 {
+            block_hash,
+            era_id,
+            signature,
+            public_key,
+            #[cfg(any(feature = "once_cell", test))]
+            is_verified: OnceCell::new(),
+        };
+        Ok((finality_signature, remainder))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,4 +305,11 @@ mod tests {
         // Test should fail b/c `signature` is over `era_id=1` and here we're using `era_id=2`.
         assert!(invalid_finality_signature.is_verified().is_err());
     }
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+        let finality_signature = FinalitySignatureV1::random(rng);
+        bytesrepr::test_serialization_roundtrip(&finality_signature);
+    }
 }
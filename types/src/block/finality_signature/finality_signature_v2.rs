@@ -17,7 +17,10 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(any(feature = "testing", test))]
 use crate::testing::TestRng;
-use crate::{crypto, BlockHash, ChainNameDigest, EraId, PublicKey, SecretKey, Signature};
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    crypto, BlockHash, ChainNameDigest, EraId, PublicKey, SecretKey, Signature,
+};
 
 /// A validator's signature of a block, confirming it is finalized.
 ///
@@ -115,8 +118,8 @@ impl FinalitySignatureV2 {
         self.verify()
     }
 
-    /// Constructs a new `FinalitySignatureV2`.
-    #[cfg(any(feature = "testing", test))]
+    /// Constructs a new `FinalitySignatureV2` from a signature created elsewhere, e.g. by a remote
+    /// signer.
     pub fn new(
         block_hash: BlockHash,
         block_height: u64,
@@ -169,7 +172,8 @@ impl FinalitySignatureV2 {
         )
     }
 
-    fn bytes_to_sign(
+    /// Returns the bytes signed by a finality signature with the given fields.
+    pub fn bytes_to_sign(
         block_hash: BlockHash,
         block_height: u64,
         era_id: EraId,
@@ -312,6 +316,54 @@ impl Display for FinalitySignatureV2 {
     }
 }
 
+impl ToBytes for FinalitySignatureV2 {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.block_hash.write_bytes(writer)?;
+        self.block_height.write_bytes(writer)?;
+        self.era_id.write_bytes(writer)?;
+        self.chain_name_hash.write_bytes(writer)?;
+        self.signature.write_bytes(writer)?;
+        self.public_key.write_bytes(writer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.block_hash.serialized_length()
+            + self.block_height.serialized_length()
+            + self.era_id.serialized_length()
+            + self.chain_name_hash.serialized_length()
+            + self.signature.serialized_length()
+            + self.public_key.serialized_length()
+    }
+}
+
+impl FromBytes for FinalitySignatureV2 {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (block_hash, remainder) = BlockHash::from_bytes(bytes)?;
+        let (block_height, remainder) = u64::from_bytes(remainder)?;
+        let (era_id, remainder) = EraId::from_bytes(remainder)?;
+        let (chain_name_hash, remainder) = ChainNameDigest::from_bytes(remainder)?;
+        let (signature, remainder) = Signature::from_bytes(remainder)?;
+        let (public_key, remainder) = PublicKey::from_bytes(remainder)?;
+        let finality_signature = FinalitySignatureV2 {
+            block_hash,
+            block_height,
+            era_id,
+            chain_name_hash,
+            signature,
+            public_key,
+            #[cfg(any(feature = "once_cell", test))]
+            is_verified: OnceCell::new(),
+        };
+        Ok((finality_signature, remainder))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -357,4 +409,11 @@ mod tests {
         };
         assert!(invalid_finality_signature.is_verified().is_err());
     }
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+        let finality_signature = FinalitySignatureV2::random(rng);
+        bytesrepr::test_serialization_roundtrip(&finality_signature);
+    }
 }
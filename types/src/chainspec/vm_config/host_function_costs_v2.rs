@@ -29,8 +29,27 @@ const DEFAULT_NEW_UREF_COST: u32 = 17_000;
 const DEFAULT_PRINT_COST: u32 = 20_000;
 const DEFAULT_PRINT_TEXT_SIZE_WEIGHT: u32 = 4_600;
 
+const DEFAULT_EMIT_TOPIC_NAME_SIZE_WEIGHT: u32 = 30_000;
+const DEFAULT_EMIT_PAYLOAD_SIZE_WEIGHT: u32 = 120_000;
+
+const DEFAULT_BLAKE2B_COST: u32 = 1_200_000;
+
+const DEFAULT_GENERIC_HASH_COST: u32 = 1_200_000;
+const DEFAULT_GENERIC_HASH_INPUT_SIZE_WEIGHT: u32 = 120_000;
+
+const DEFAULT_RECOVER_SECP256K1_COST: u32 = 1_300_000;
+const DEFAULT_RECOVER_SECP256K1_SIZE_WEIGHT: u32 = 120_000;
+
+const DEFAULT_VERIFY_SIGNATURE_COST: u32 = 1_300_000;
+const DEFAULT_VERIFY_SIGNATURE_SIZE_WEIGHT: u32 = 120_000;
+
 const DEFAULT_READ_VALUE_COST: u32 = 60_000;
 
+const DEFAULT_REMOVE_COST: u32 = 14_000;
+
+const DEFAULT_ITERATE_COST: u32 = 60_000;
+const DEFAULT_ITERATE_MEMBER_WEIGHT: u32 = 60_000;
+
 const DEFAULT_RET_COST: u32 = 23_000;
 const DEFAULT_RET_VALUE_SIZE_WEIGHT: u32 = 420_000;
 
@@ -89,6 +108,20 @@ pub struct HostFunctionCostsV2 {
     pub call: HostFunction<[Cost; 9]>,
     /// Cost of calling the `print` host function.
     pub print: HostFunction<[Cost; 2]>,
+    /// Cost of calling the `emit` host function.
+    pub emit: HostFunction<[Cost; 4]>,
+    /// Cost of calling the `blake2b` host function.
+    pub blake2b: HostFunction<[Cost; 4]>,
+    /// Cost of calling the `generic_hash` host function.
+    pub generic_hash: HostFunction<[Cost; 5]>,
+    /// Cost of calling the `recover_secp256k1` host function.
+    pub recover_secp256k1: HostFunction<[Cost; 6]>,
+    /// Cost of calling the `verify_signature` host function.
+    pub verify_signature: HostFunction<[Cost; 6]>,
+    /// Cost of calling the `remove` host function.
+    pub remove: HostFunction<[Cost; 3]>,
+    /// Cost of calling the `iterate` host function.
+    pub iterate: HostFunction<[Cost; 8]>,
 }
 
 impl Zero for HostFunctionCostsV2 {
@@ -107,6 +140,13 @@ impl Zero for HostFunctionCostsV2 {
             upgrade: HostFunction::zero(),
             call: HostFunction::zero(),
             print: HostFunction::zero(),
+            emit: HostFunction::zero(),
+            blake2b: HostFunction::zero(),
+            generic_hash: HostFunction::zero(),
+            recover_secp256k1: HostFunction::zero(),
+            verify_signature: HostFunction::zero(),
+            remove: HostFunction::zero(),
+            iterate: HostFunction::zero(),
         }
     }
 
@@ -125,6 +165,13 @@ impl Zero for HostFunctionCostsV2 {
             upgrade,
             call,
             print,
+            emit,
+            blake2b,
+            generic_hash,
+            recover_secp256k1,
+            verify_signature,
+            remove,
+            iterate,
         } = self;
         read.is_zero()
             && write.is_zero()
@@ -139,6 +186,13 @@ impl Zero for HostFunctionCostsV2 {
             && upgrade.is_zero()
             && call.is_zero()
             && print.is_zero()
+            && emit.is_zero()
+            && blake2b.is_zero()
+            && generic_hash.is_zero()
+            && recover_secp256k1.is_zero()
+            && verify_signature.is_zero()
+            && remove.is_zero()
+            && iterate.is_zero()
     }
 }
 
@@ -206,6 +260,65 @@ impl Default for HostFunctionCostsV2 {
                 DEFAULT_PRINT_COST,
                 [NOT_USED, DEFAULT_PRINT_TEXT_SIZE_WEIGHT],
             ),
+            emit: HostFunction::new(
+                DEFAULT_FIXED_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_EMIT_TOPIC_NAME_SIZE_WEIGHT,
+                    NOT_USED,
+                    DEFAULT_EMIT_PAYLOAD_SIZE_WEIGHT,
+                ],
+            ),
+            blake2b: HostFunction::new(
+                DEFAULT_BLAKE2B_COST,
+                [NOT_USED, DEFAULT_ARG_CHARGE, NOT_USED, NOT_USED],
+            ),
+            generic_hash: HostFunction::new(
+                DEFAULT_GENERIC_HASH_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_GENERIC_HASH_INPUT_SIZE_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
+            recover_secp256k1: HostFunction::new(
+                DEFAULT_RECOVER_SECP256K1_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_RECOVER_SECP256K1_SIZE_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
+            verify_signature: HostFunction::new(
+                DEFAULT_VERIFY_SIGNATURE_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_VERIFY_SIGNATURE_SIZE_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
+            remove: HostFunction::new(DEFAULT_REMOVE_COST, [NOT_USED, NOT_USED, NOT_USED]),
+            iterate: HostFunction::new(
+                DEFAULT_ITERATE_COST,
+                [
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                    DEFAULT_ITERATE_MEMBER_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
         }
     }
 }
@@ -226,6 +339,13 @@ impl ToBytes for HostFunctionCostsV2 {
         ret.append(&mut self.upgrade.to_bytes()?);
         ret.append(&mut self.call.to_bytes()?);
         ret.append(&mut self.print.to_bytes()?);
+        ret.append(&mut self.emit.to_bytes()?);
+        ret.append(&mut self.blake2b.to_bytes()?);
+        ret.append(&mut self.generic_hash.to_bytes()?);
+        ret.append(&mut self.recover_secp256k1.to_bytes()?);
+        ret.append(&mut self.verify_signature.to_bytes()?);
+        ret.append(&mut self.remove.to_bytes()?);
+        ret.append(&mut self.iterate.to_bytes()?);
         Ok(ret)
     }
 
@@ -243,6 +363,13 @@ impl ToBytes for HostFunctionCostsV2 {
             + self.upgrade.serialized_length()
             + self.call.serialized_length()
             + self.print.serialized_length()
+            + self.emit.serialized_length()
+            + self.blake2b.serialized_length()
+            + self.generic_hash.serialized_length()
+            + self.recover_secp256k1.serialized_length()
+            + self.verify_signature.serialized_length()
+            + self.remove.serialized_length()
+            + self.iterate.serialized_length()
     }
 }
 
@@ -261,6 +388,13 @@ impl FromBytes for HostFunctionCostsV2 {
         let (upgrade, rem) = FromBytes::from_bytes(rem)?;
         let (call, rem) = FromBytes::from_bytes(rem)?;
         let (print, rem) = FromBytes::from_bytes(rem)?;
+        let (emit, rem) = FromBytes::from_bytes(rem)?;
+        let (blake2b, rem) = FromBytes::from_bytes(rem)?;
+        let (generic_hash, rem) = FromBytes::from_bytes(rem)?;
+        let (recover_secp256k1, rem) = FromBytes::from_bytes(rem)?;
+        let (verify_signature, rem) = FromBytes::from_bytes(rem)?;
+        let (remove, rem) = FromBytes::from_bytes(rem)?;
+        let (iterate, rem) = FromBytes::from_bytes(rem)?;
         Ok((
             HostFunctionCostsV2 {
                 read,
@@ -276,6 +410,13 @@ impl FromBytes for HostFunctionCostsV2 {
                 upgrade,
                 call,
                 print,
+                emit,
+                blake2b,
+                generic_hash,
+                recover_secp256k1,
+                verify_signature,
+                remove,
+                iterate,
             },
             rem,
         ))
@@ -299,6 +440,13 @@ impl Distribution<HostFunctionCostsV2> for Standard {
             upgrade: rng.gen(),
             call: rng.gen(),
             print: rng.gen(),
+            emit: rng.gen(),
+            blake2b: rng.gen(),
+            generic_hash: rng.gen(),
+            recover_secp256k1: rng.gen(),
+            verify_signature: rng.gen(),
+            remove: rng.gen(),
+            iterate: rng.gen(),
         }
     }
 }
@@ -332,6 +480,13 @@ pub mod gens {
             upgrade in host_function_cost_v2_arb(),
             call in host_function_cost_v2_arb(),
             print in host_function_cost_v2_arb(),
+            emit in host_function_cost_v2_arb(),
+            blake2b in host_function_cost_v2_arb(),
+            generic_hash in host_function_cost_v2_arb(),
+            recover_secp256k1 in host_function_cost_v2_arb(),
+            verify_signature in host_function_cost_v2_arb(),
+            remove in host_function_cost_v2_arb(),
+            iterate in host_function_cost_v2_arb(),
         ) -> HostFunctionCostsV2 {
             HostFunctionCostsV2 {
                 read,
@@ -347,6 +502,13 @@ pub mod gens {
                 upgrade,
                 call,
                 print,
+                emit,
+                blake2b,
+                generic_hash,
+                recover_secp256k1,
+                verify_signature,
+                remove,
+                iterate,
             }
         }
     }
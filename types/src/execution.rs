@@ -4,6 +4,7 @@ mod effects;
 mod execution_result;
 pub mod execution_result_v1;
 mod execution_result_v2;
+mod execution_trace;
 mod transform;
 mod transform_error;
 mod transform_kind;
@@ -12,6 +13,7 @@ pub use effects::Effects;
 pub use execution_result::ExecutionResult;
 pub use execution_result_v1::ExecutionResultV1;
 pub use execution_result_v2::ExecutionResultV2;
+pub use execution_trace::{CallFrame, ExecutionTrace, ExecutionTracer, HostFunctionCall};
 pub use transform::TransformV2;
 pub use transform_error::TransformError;
 pub use transform_kind::{TransformInstruction, TransformKindV2};
@@ -0,0 +1,499 @@
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(any(feature = "testing", test))]
+use rand::Rng;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+#[cfg(any(feature = "std", test))]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    Gas, Key, TransactionArgs,
+};
+#[cfg(any(feature = "testing", test))]
+use crate::{testing::TestRng, RuntimeArgs};
+
+/// The maximum depth of the call frames generated by `CallFrame::random`.
+#[cfg(any(feature = "testing", test))]
+const RANDOM_MAX_DEPTH: usize = 3;
+
+/// A host function invoked by a contract, along with the cost it was charged.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    any(feature = "std", test),
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct HostFunctionCall {
+    /// The name of the host function, as imported by the Wasm module.
+    name: String,
+    /// The cost charged for the host function call.
+    cost: Gas,
+}
+
+impl HostFunctionCall {
+    /// Constructs a new `HostFunctionCall`.
+    pub fn new(name: String, cost: Gas) -> Self {
+        HostFunctionCall { name, cost }
+    }
+
+    /// Returns the name of the host function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the cost charged for the host function call.
+    pub fn cost(&self) -> Gas {
+        self.cost
+    }
+
+    /// Returns a random `HostFunctionCall`.
+    #[cfg(any(feature = "testing", test))]
+    pub fn random(rng: &mut TestRng) -> Self {
+        HostFunctionCall {
+            name: rng.random_string(4..32),
+            cost: Gas::new(rng.gen::<u64>()),
+        }
+    }
+}
+
+impl ToBytes for HostFunctionCall {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.name.serialized_length() + self.cost.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.name.write_bytes(writer)?;
+        self.cost.write_bytes(writer)
+    }
+}
+
+impl FromBytes for HostFunctionCall {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (name, remainder) = String::from_bytes(bytes)?;
+        let (cost, remainder) = Gas::from_bytes(remainder)?;
+        Ok((HostFunctionCall { name, cost }, remainder))
+    }
+}
+
+/// A single frame of the contract call tree of an execution.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    any(feature = "std", test),
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct CallFrame {
+    /// The key of the account or contract making the call.
+    caller: Key,
+    /// The key of the account or contract being called.
+    callee: Key,
+    /// The entry point being called.
+    entry_point: String,
+    /// The arguments of the call.
+    args: TransactionArgs,
+    /// The gas used by the call, including the gas used by the calls it made.
+    gas_used: Gas,
+    /// The error the call failed with, if any.
+    error: Option<String>,
+    /// The host functions invoked by the call, in invocation order.
+    host_function_calls: Vec<HostFunctionCall>,
+    /// The global state keys read by the call, in access order.
+    reads: Vec<Key>,
+    /// The global state keys written by the call, in access order.
+    writes: Vec<Key>,
+    /// The calls made by the call, in call order.
+    calls: Vec<CallFrame>,
+}
+
+impl CallFrame {
+    /// Constructs a new `CallFrame` of a call which is yet to record anything.
+    pub fn new(caller: Key, callee: Key, entry_point: String, args: TransactionArgs) -> Self {
+        CallFrame {
+            caller,
+            callee,
+            entry_point,
+            args,
+            gas_used: Gas::zero(),
+            error: None,
+            host_function_calls: Vec::new(),
+            reads: Vec::new(),
+            writes: Vec::new(),
+            calls: Vec::new(),
+        }
+    }
+
+    /// Returns the key of the account or contract making the call.
+    pub fn caller(&self) -> &Key {
+        &self.caller
+    }
+
+    /// Returns the key of the account or contract being called.
+    pub fn callee(&self) -> &Key {
+        &self.callee
+    }
+
+    /// Returns the entry point being called.
+    pub fn entry_point(&self) -> &str {
+        &self.entry_point
+    }
+
+    /// Returns the arguments of the call.
+    pub fn args(&self) -> &TransactionArgs {
+        &self.args
+    }
+
+    /// Returns the gas used by the call, including the gas used by the calls it made.
+    pub fn gas_used(&self) -> Gas {
+        self.gas_used
+    }
+
+    /// Returns the error the call failed with, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Returns the host functions invoked by the call.
+    pub fn host_function_calls(&self) -> &[HostFunctionCall] {
+        &self.host_function_calls
+    }
+
+    /// Returns the global state keys read by the call.
+    pub fn reads(&self) -> &[Key] {
+        &self.reads
+    }
+
+    /// Returns the global state keys written by the call.
+    pub fn writes(&self) -> &[Key] {
+        &self.writes
+    }
+
+    /// Returns the calls made by the call.
+    pub fn calls(&self) -> &[CallFrame] {
+        &self.calls
+    }
+
+    /// Returns a random `CallFrame`.
+    #[cfg(any(feature = "testing", test))]
+    pub fn random(rng: &mut TestRng) -> Self {
+        Self::random_with_depth(rng, RANDOM_MAX_DEPTH)
+    }
+
+    #[cfg(any(feature = "testing", test))]
+    fn random_with_depth(rng: &mut TestRng, depth: usize) -> Self {
+        let args = if rng.gen() {
+            TransactionArgs::Named(RuntimeArgs::random(rng))
+        } else {
+            TransactionArgs::Bytesrepr(rng.random_vec(0..64).into())
+        };
+        let call_count = if depth == 0 { 0 } else { rng.gen_range(0..3) };
+        CallFrame {
+            caller: Key::Hash(rng.gen()),
+            callee: Key::Hash(rng.gen()),
+            entry_point: rng.random_string(1..32),
+            args,
+            gas_used: Gas::new(rng.gen::<u64>()),
+            error: rng.gen::<bool>().then(|| rng.random_string(16..64)),
+            host_function_calls: (0..rng.gen_range(0..8))
+                .map(|_| HostFunctionCall::random(rng))
+                .collect(),
+            reads: (0..rng.gen_range(0..8))
+                .map(|_| Key::Hash(rng.gen()))
+                .collect(),
+            writes: (0..rng.gen_range(0..8))
+                .map(|_| Key::Hash(rng.gen()))
+                .collect(),
+            calls: (0..call_count)
+                .map(|_| Self::random_with_depth(rng, depth - 1))
+                .collect(),
+        }
+    }
+}
+
+impl ToBytes for CallFrame {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.caller.serialized_length()
+            + self.callee.serialized_length()
+            + self.entry_point.serialized_length()
+            + self.args.serialized_length()
+            + self.gas_used.serialized_length()
+            + self.error.serialized_length()
+            + self.host_function_calls.serialized_length()
+            + self.reads.serialized_length()
+            + self.writes.serialized_length()
+            + self.calls.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.caller.write_bytes(writer)?;
+        self.callee.write_bytes(writer)?;
+        self.entry_point.write_bytes(writer)?;
+        self.args.write_bytes(writer)?;
+        self.gas_used.write_bytes(writer)?;
+        self.error.write_bytes(writer)?;
+        self.host_function_calls.write_bytes(writer)?;
+        self.reads.write_bytes(writer)?;
+        self.writes.write_bytes(writer)?;
+        self.calls.write_bytes(writer)
+    }
+}
+
+impl FromBytes for CallFrame {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (caller, remainder) = Key::from_bytes(bytes)?;
+        let (callee, remainder) = Key::from_bytes(remainder)?;
+        let (entry_point, remainder) = String::from_bytes(remainder)?;
+        let (args, remainder) = TransactionArgs::from_bytes(remainder)?;
+        let (gas_used, remainder) = Gas::from_bytes(remainder)?;
+        let (error, remainder) = Option::<String>::from_bytes(remainder)?;
+        let (host_function_calls, remainder) = Vec::<HostFunctionCall>::from_bytes(remainder)?;
+        let (reads, remainder) = Vec::<Key>::from_bytes(remainder)?;
+        let (writes, remainder) = Vec::<Key>::from_bytes(remainder)?;
+        let (calls, remainder) = Vec::<CallFrame>::from_bytes(remainder)?;
+        Ok((
+            CallFrame {
+                caller,
+                callee,
+                entry_point,
+                args,
+                gas_used,
+                error,
+                host_function_calls,
+                reads,
+                writes,
+                calls,
+            },
+            remainder,
+        ))
+    }
+}
+
+/// A trace of an execution: the tree of the contract calls it made, with the host functions
+/// invoked and the global state accessed by each of them.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(
+    any(feature = "std", test),
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct ExecutionTrace {
+    /// The top-level calls of the execution, normally a single one.
+    calls: Vec<CallFrame>,
+}
+
+impl ExecutionTrace {
+    /// Returns the top-level calls of the execution.
+    pub fn calls(&self) -> &[CallFrame] {
+        &self.calls
+    }
+
+    /// Returns a random `ExecutionTrace`.
+    #[cfg(any(feature = "testing", test))]
+    pub fn random(rng: &mut TestRng) -> Self {
+        ExecutionTrace {
+            calls: (0..rng.gen_range(0..3))
+                .map(|_| CallFrame::random(rng))
+                .collect(),
+        }
+    }
+}
+
+impl ToBytes for ExecutionTrace {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.calls.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.calls.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.calls.write_bytes(writer)
+    }
+}
+
+impl FromBytes for ExecutionTrace {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (calls, remainder) = Vec::<CallFrame>::from_bytes(bytes)?;
+        Ok((ExecutionTrace { calls }, remainder))
+    }
+}
+
+/// Records an [`ExecutionTrace`] while the execution is in progress.
+///
+/// The virtual machines enter a call frame when a contract is called and exit it when the call
+/// returns; host function calls and global state accesses are attributed to the innermost frame
+/// entered.  Anything recorded outside of a frame is dropped.
+#[derive(Default, Debug)]
+pub struct ExecutionTracer {
+    /// The frames entered and not yet exited, innermost last.
+    frames: Vec<CallFrame>,
+    /// The top-level frames exited so far.
+    calls: Vec<CallFrame>,
+}
+
+impl ExecutionTracer {
+    /// Constructs a new `ExecutionTracer`.
+    pub fn new() -> Self {
+        ExecutionTracer::default()
+    }
+
+    /// Enters the frame of a call.
+    pub fn enter_call(
+        &mut self,
+        caller: Key,
+        callee: Key,
+        entry_point: String,
+        args: TransactionArgs,
+    ) {
+        self.frames
+            .push(CallFrame::new(caller, callee, entry_point, args));
+    }
+
+    /// Exits the innermost frame, recording the gas used by the call and its error, if any.
+    pub fn exit_call(&mut self, gas_used: Gas, error: Option<String>) {
+        if let Some(mut frame) = self.frames.pop() {
+            frame.gas_used = gas_used;
+            frame.error = error;
+            self.push_exited(frame);
+        }
+    }
+
+    /// Records the invocation of a host function by the innermost frame.
+    pub fn enter_host_function(&mut self, name: &str) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame
+                .host_function_calls
+                .push(HostFunctionCall::new(name.into(), Gas::zero()));
+        }
+    }
+
+    /// Adds the cost charged for the last host function invoked by the innermost frame.
+    pub fn charge_host_function(&mut self, cost: Gas) {
+        if let Some(host_function_call) = self
+            .frames
+            .last_mut()
+            .and_then(|frame| frame.host_function_calls.last_mut())
+        {
+            host_function_call.cost = host_function_call.cost.saturating_add(cost);
+        }
+    }
+
+    /// Records a read of global state by the innermost frame.
+    pub fn record_read(&mut self, key: Key) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.reads.push(key);
+        }
+    }
+
+    /// Records a write to global state by the innermost frame.
+    pub fn record_write(&mut self, key: Key) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.writes.push(key);
+        }
+    }
+
+    /// Consumes the tracer, returning the recorded trace.
+    ///
+    /// Frames which were never exited are closed as they are.
+    pub fn into_trace(mut self) -> ExecutionTrace {
+        while let Some(frame) = self.frames.pop() {
+            self.push_exited(frame);
+        }
+        ExecutionTrace { calls: self.calls }
+    }
+
+    fn push_exited(&mut self, frame: CallFrame) {
+        match self.frames.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => self.calls.push(frame),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRng;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let rng = &mut TestRng::new();
+
+        let trace = ExecutionTrace::random(rng);
+        bytesrepr::test_serialization_roundtrip(&trace);
+    }
+
+    #[test]
+    fn should_nest_call_frames() {
+        let account = Key::Hash([1; 32]);
+        let contract = Key::Hash([2; 32]);
+        let mut tracer = ExecutionTracer::new();
+
+        tracer.enter_call(
+            account,
+            account,
+            "call".into(),
+            TransactionArgs::Named(RuntimeArgs::new()),
+        );
+        tracer.enter_host_function("casper_call_contract");
+        tracer.charge_host_function(Gas::new(10));
+        tracer.enter_call(
+            account,
+            contract,
+            "transfer".into(),
+            TransactionArgs::Named(RuntimeArgs::new()),
+        );
+        tracer.record_read(contract);
+        tracer.record_write(Key::Hash([3; 32]));
+        tracer.exit_call(Gas::new(5), Some("revert".into()));
+        tracer.record_read(account);
+        tracer.exit_call(Gas::new(20), None);
+        // Recorded outside of any frame, so dropped.
+        tracer.record_write(account);
+
+        let trace = tracer.into_trace();
+        assert_eq!(trace.calls().len(), 1);
+        let root = &trace.calls()[0];
+        assert_eq!(root.gas_used(), Gas::new(20));
+        assert_eq!(root.error(), None);
+        assert_eq!(
+            root.host_function_calls(),
+            &[HostFunctionCall::new(
+                "casper_call_contract".into(),
+                Gas::new(10)
+            )]
+        );
+        assert_eq!(root.reads(), &[account]);
+        assert!(root.writes().is_empty());
+        assert_eq!(root.calls().len(), 1);
+        let nested = &root.calls()[0];
+        assert_eq!(nested.callee(), &contract);
+        assert_eq!(nested.entry_point(), "transfer");
+        assert_eq!(nested.gas_used(), Gas::new(5));
+        assert_eq!(nested.error(), Some("revert"));
+        assert_eq!(nested.reads(), &[contract]);
+        assert_eq!(nested.writes(), &[Key::Hash([3; 32])]);
+    }
+}
@@ -31,7 +31,9 @@ use crate::{
         gens::{public_key_arb_no_system, secret_key_arb_no_system},
     },
     deploy_info::gens::deploy_info_arb,
-    global_state::{Pointer, TrieMerkleProof, TrieMerkleProofStep},
+    global_state::{
+        Pointer, TrieMerkleProof, TrieMerkleProofStep, TrieMultiProof, TrieMultiProofStep,
+    },
     package::{EntityVersionKey, EntityVersions, Groups, PackageStatus},
     system::{
         auction::{
@@ -1063,6 +1065,35 @@ pub fn trie_merkle_proof_arb() -> impl Strategy<Value = TrieMerkleProof<Key, Sto
         .prop_map(|(key, value, proof_steps)| TrieMerkleProof::new(key, value, proof_steps.into()))
 }
 
+pub fn trie_multi_proof_step_arb() -> impl Strategy<Value = TrieMultiProofStep> {
+    const PROVEN_INDICES_SIZE: usize = 4;
+    const POINTERS_SIZE: usize = 32;
+    const AFFIX_SIZE: usize = 6;
+
+    prop_oneof![
+        Just(TrieMultiProofStep::Leaf),
+        (
+            vec(<u8>::arbitrary(), PROVEN_INDICES_SIZE),
+            vec((<u8>::arbitrary(), trie_pointer_arb()), POINTERS_SIZE)
+        )
+            .prop_map(|(proven_indices, indexed_pointers)| {
+                TrieMultiProofStep::node(proven_indices, indexed_pointers)
+            }),
+        vec(<u8>::arbitrary(), AFFIX_SIZE).prop_map(TrieMultiProofStep::extension)
+    ]
+}
+
+pub fn trie_multi_proof_arb() -> impl Strategy<Value = TrieMultiProof<Key, StoredValue>> {
+    const ENTRIES_SIZE: usize = 3;
+    const STEPS_SIZE: usize = 6;
+
+    (
+        vec((key_arb(), stored_value_arb()), ENTRIES_SIZE),
+        vec(trie_multi_proof_step_arb(), STEPS_SIZE),
+    )
+        .prop_map(|(entries, steps)| TrieMultiProof::new(entries, steps))
+}
+
 pub fn transaction_scheduling_arb() -> impl Strategy<Value = TransactionScheduling> {
     prop_oneof![
         Just(TransactionScheduling::Standard),
@@ -1,6 +1,8 @@
 //! Types for global state.
 mod merkle_proof;
+mod multi_proof;
 mod pointer;
 
 pub use merkle_proof::{TrieMerkleProof, TrieMerkleProofStep};
+pub use multi_proof::{TrieMultiProof, TrieMultiProofError, TrieMultiProofStep};
 pub use pointer::Pointer;
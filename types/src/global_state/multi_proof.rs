@@ -0,0 +1,357 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, Bytes, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    Digest,
+};
+
+use super::pointer::Pointer;
+
+const TRIE_MULTI_PROOF_STEP_LEAF_ID: u8 = 0;
+const TRIE_MULTI_PROOF_STEP_NODE_ID: u8 = 1;
+const TRIE_MULTI_PROOF_STEP_EXTENSION_ID: u8 = 2;
+
+// Serialization tags and radix of the tries of the global state.
+const TRIE_LEAF_TAG: u8 = 0;
+const TRIE_NODE_TAG: u8 = 1;
+const TRIE_EXTENSION_TAG: u8 = 2;
+const TRIE_RADIX: usize = 256;
+
+/// A component of a proof that several entries exist in the Merkle trie.
+///
+/// The steps of a [`TrieMultiProof`] rebuild the tries on the paths from its entries to the root
+/// in post-order: each step consumes the pointers to the subtries rebuilt by the previous steps,
+/// and yields a pointer to the trie it rebuilds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrieMultiProofStep {
+    /// Corresponds to the leaf of the next entry of the proof.
+    Leaf,
+    /// Corresponds to a trie node.
+    Node {
+        /// Indices of the pointers to the subtries rebuilt by the previous steps, in order.
+        proven_indices: Bytes,
+        /// The other indexed pointers of the node.
+        indexed_pointers: Vec<(u8, Pointer)>,
+    },
+    /// Corresponds to a trie extension, pointing to the subtrie rebuilt by the previous step.
+    Extension {
+        /// Affix bytes.
+        affix: Bytes,
+    },
+}
+
+impl TrieMultiProofStep {
+    /// Constructor for [`TrieMultiProofStep::Node`]
+    pub fn node(proven_indices: Vec<u8>, indexed_pointers: Vec<(u8, Pointer)>) -> Self {
+        TrieMultiProofStep::Node {
+            proven_indices: proven_indices.into(),
+            indexed_pointers,
+        }
+    }
+
+    /// Constructor for [`TrieMultiProofStep::Extension`]
+    pub fn extension(affix: Vec<u8>) -> Self {
+        TrieMultiProofStep::Extension {
+            affix: affix.into(),
+        }
+    }
+}
+
+impl ToBytes for TrieMultiProofStep {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                TrieMultiProofStep::Leaf => 0,
+                TrieMultiProofStep::Node {
+                    proven_indices,
+                    indexed_pointers,
+                } => proven_indices.serialized_length() + indexed_pointers.serialized_length(),
+                TrieMultiProofStep::Extension { affix } => affix.serialized_length(),
+            }
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        match self {
+            TrieMultiProofStep::Leaf => writer.push(TRIE_MULTI_PROOF_STEP_LEAF_ID),
+            TrieMultiProofStep::Node {
+                proven_indices,
+                indexed_pointers,
+            } => {
+                writer.push(TRIE_MULTI_PROOF_STEP_NODE_ID);
+                proven_indices.write_bytes(writer)?;
+                indexed_pointers.write_bytes(writer)?;
+            }
+            TrieMultiProofStep::Extension { affix } => {
+                writer.push(TRIE_MULTI_PROOF_STEP_EXTENSION_ID);
+                affix.write_bytes(writer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromBytes for TrieMultiProofStep {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            TRIE_MULTI_PROOF_STEP_LEAF_ID => Ok((TrieMultiProofStep::Leaf, rem)),
+            TRIE_MULTI_PROOF_STEP_NODE_ID => {
+                let (proven_indices, rem) = FromBytes::from_bytes(rem)?;
+                let (indexed_pointers, rem) = FromBytes::from_bytes(rem)?;
+                Ok((
+                    TrieMultiProofStep::Node {
+                        proven_indices,
+                        indexed_pointers,
+                    },
+                    rem,
+                ))
+            }
+            TRIE_MULTI_PROOF_STEP_EXTENSION_ID => {
+                let (affix, rem) = FromBytes::from_bytes(rem)?;
+                Ok((TrieMultiProofStep::Extension { affix }, rem))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// Error returned when computing the state hash of a [`TrieMultiProof`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TrieMultiProofError {
+    /// The steps of the proof don't rebuild a single trie holding exactly all its entries.
+    Malformed,
+    /// A trie of the proof could not be serialized.
+    Bytesrepr(bytesrepr::Error),
+}
+
+impl Display for TrieMultiProofError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            TrieMultiProofError::Malformed => write!(formatter, "malformed trie multi-proof"),
+            TrieMultiProofError::Bytesrepr(error) => {
+                write!(
+                    formatter,
+                    "error serializing trie of multi-proof: {}",
+                    error
+                )
+            }
+        }
+    }
+}
+
+impl From<bytesrepr::Error> for TrieMultiProofError {
+    fn from(error: bytesrepr::Error) -> Self {
+        TrieMultiProofError::Bytesrepr(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for TrieMultiProofError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            TrieMultiProofError::Bytesrepr(error) => Some(error),
+            TrieMultiProofError::Malformed => None,
+        }
+    }
+}
+
+/// A proof that several entries are present in the Merkle trie.
+///
+/// Unlike a set of [`TrieMerkleProof`](super::TrieMerkleProof)s, the tries on the paths shared by
+/// several entries are only included once.  Given a state hash `x`, one can validate a proof `p`
+/// by checking `x == p.compute_state_hash()?`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrieMultiProof<K, V> {
+    entries: Vec<(K, V)>,
+    steps: Vec<TrieMultiProofStep>,
+}
+
+impl<K, V> TrieMultiProof<K, V> {
+    /// Constructor for [`TrieMultiProof`]
+    pub fn new(entries: Vec<(K, V)>, steps: Vec<TrieMultiProofStep>) -> Self {
+        TrieMultiProof { entries, steps }
+    }
+
+    /// Getter for the proven entries, ordered by the serialized key.
+    pub fn entries(&self) -> &[(K, V)] {
+        &self.entries
+    }
+
+    /// Getter for the proof steps.
+    pub fn steps(&self) -> &[TrieMultiProofStep] {
+        &self.steps
+    }
+
+    /// Transforms a [`TrieMultiProof`] into the entries it contains.
+    pub fn into_entries(self) -> Vec<(K, V)> {
+        self.entries
+    }
+
+    /// Computes the hash of the trie holding all the entries of the proof.
+    pub fn compute_state_hash(&self) -> Result<Digest, TrieMultiProofError>
+    where
+        K: ToBytes,
+        V: ToBytes,
+    {
+        let mut entries = self.entries.iter();
+        let mut pointers: Vec<Pointer> = Vec::new();
+        for step in &self.steps {
+            let mut trie_bytes = Vec::new();
+            let pointer = match step {
+                TrieMultiProofStep::Leaf => {
+                    let (key, value) = entries.next().ok_or(TrieMultiProofError::Malformed)?;
+                    trie_bytes.push(TRIE_LEAF_TAG);
+                    key.write_bytes(&mut trie_bytes)?;
+                    value.write_bytes(&mut trie_bytes)?;
+                    Pointer::LeafPointer(Digest::hash_into_chunks_if_necessary(&trie_bytes))
+                }
+                TrieMultiProofStep::Node {
+                    proven_indices,
+                    indexed_pointers,
+                } => {
+                    let first_proven = pointers
+                        .len()
+                        .checked_sub(proven_indices.len())
+                        .ok_or(TrieMultiProofError::Malformed)?;
+                    let mut pointer_block = [None; TRIE_RADIX];
+                    for (index, pointer) in indexed_pointers {
+                        pointer_block[usize::from(*index)] = Some(*pointer);
+                    }
+                    for (index, pointer) in
+                        proven_indices.iter().zip(pointers.drain(first_proven..))
+                    {
+                        pointer_block[usize::from(*index)] = Some(pointer);
+                    }
+                    trie_bytes.push(TRIE_NODE_TAG);
+                    for pointer in pointer_block.iter() {
+                        pointer.write_bytes(&mut trie_bytes)?;
+                    }
+                    Pointer::NodePointer(Digest::hash_into_chunks_if_necessary(&trie_bytes))
+                }
+                TrieMultiProofStep::Extension { affix } => {
+                    let pointer = pointers.pop().ok_or(TrieMultiProofError::Malformed)?;
+                    trie_bytes.push(TRIE_EXTENSION_TAG);
+                    affix.write_bytes(&mut trie_bytes)?;
+                    pointer.write_bytes(&mut trie_bytes)?;
+                    Pointer::NodePointer(Digest::hash_into_chunks_if_necessary(&trie_bytes))
+                }
+            };
+            pointers.push(pointer);
+        }
+        match (entries.next(), pointers.as_slice()) {
+            (None, [root]) => Ok(*root.hash()),
+            _ => Err(TrieMultiProofError::Malformed),
+        }
+    }
+}
+
+impl<K, V> ToBytes for TrieMultiProof<K, V>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.entries.serialized_length() + self.steps.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.entries.write_bytes(writer)?;
+        self.steps.write_bytes(writer)
+    }
+}
+
+impl<K, V> FromBytes for TrieMultiProof<K, V>
+where
+    K: FromBytes,
+    V: FromBytes,
+{
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (entries, rem) = FromBytes::from_bytes(bytes)?;
+        let (steps, rem) = FromBytes::from_bytes(rem)?;
+        Ok((TrieMultiProof { entries, steps }, rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{bytesrepr, gens, CLValue, Key, StoredValue};
+
+    proptest! {
+        #[test]
+        fn trie_multi_proof_serialization_is_correct(proof in gens::trie_multi_proof_arb()) {
+            bytesrepr::test_serialization_roundtrip(&proof)
+        }
+    }
+
+    #[test]
+    fn should_reject_malformed_proofs() {
+        let entry = (
+            Key::Hash([1; 32]),
+            StoredValue::CLValue(CLValue::from_t(1u8).unwrap()),
+        );
+        let malformed_proofs = [
+            // No trie at all.
+            TrieMultiProof::new(vec![], vec![]),
+            // A leaf without an entry.
+            TrieMultiProof::new(vec![], vec![TrieMultiProofStep::Leaf]),
+            // An entry without a leaf.
+            TrieMultiProof::new(
+                vec![entry.clone(), entry.clone()],
+                vec![TrieMultiProofStep::Leaf],
+            ),
+            // A node pointing to more subtries than rebuilt.
+            TrieMultiProof::new(
+                vec![entry.clone()],
+                vec![
+                    TrieMultiProofStep::Leaf,
+                    TrieMultiProofStep::node(vec![1, 2], vec![]),
+                ],
+            ),
+            // An extension without a subtrie.
+            TrieMultiProof::new(vec![], vec![TrieMultiProofStep::extension(vec![1])]),
+            // Two tries left over.
+            TrieMultiProof::new(
+                vec![entry.clone(), entry.clone()],
+                vec![TrieMultiProofStep::Leaf, TrieMultiProofStep::Leaf],
+            ),
+        ];
+        for proof in malformed_proofs {
+            assert_eq!(
+                proof.compute_state_hash(),
+                Err(TrieMultiProofError::Malformed)
+            );
+        }
+
+        // Two leaves sharing a node rebuild a single trie.
+        let proof = TrieMultiProof::new(
+            vec![entry.clone(), entry],
+            vec![
+                TrieMultiProofStep::Leaf,
+                TrieMultiProofStep::Leaf,
+                TrieMultiProofStep::node(vec![1, 2], vec![]),
+            ],
+        );
+        assert!(proof.compute_state_hash().is_ok());
+    }
+}